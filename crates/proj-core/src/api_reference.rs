@@ -0,0 +1,758 @@
+// API reference generation - the generated sections of API-type docs databases
+//
+// Public items are grouped by the module they're defined in, each with its
+// signature, a parameter table, and its doc comment. Methods are nested
+// under the type, trait, or class they belong to, the way rustdoc, Sphinx,
+// typedoc, and godoc lay out a reference.
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::source_analyzer::{
+    GeneratedSection, ItemKind, Language, ProjectStructure, SourceItem, Visibility,
+};
+
+/// Generate API reference sections. Single-language projects get one
+/// top-level section per module; mixed-language projects get one per
+/// language with the modules nested underneath.
+pub fn generate_sections(structure: &ProjectStructure) -> Vec<GeneratedSection> {
+    let mut sections = Vec::new();
+    let mut sort_order = 1;
+
+    let public = structure
+        .modules
+        .iter()
+        .filter(|item| is_documented(item))
+        .count();
+    let module_count = structure
+        .modules
+        .iter()
+        .filter(|item| is_documented(item))
+        .map(|item| (item.file_path.extension(), item.module.as_str()))
+        .collect::<std::collections::BTreeSet<_>>()
+        .len();
+    sections.push(GeneratedSection {
+        section_id: "1".to_string(),
+        title: "Overview".to_string(),
+        level: 1,
+        sort_order,
+        content: format!(
+            "API reference for {}, generated from its {} source.\n\n- **Modules**: {}\n- **Public items**: {}\n",
+            structure.name,
+            structure.language_label(),
+            module_count,
+            public
+        ),
+        generated: true,
+        source_file: None,
+    });
+
+    if structure.languages.len() <= 1 {
+        push_modules(
+            &mut sections,
+            &mut sort_order,
+            &structure.language,
+            &structure.modules,
+            None,
+            1,
+        );
+        return sections;
+    }
+
+    for (index, group) in structure.languages.iter().enumerate() {
+        sort_order += 1;
+        let section_id = format!("{}", index + 2);
+        sections.push(GeneratedSection {
+            section_id: section_id.clone(),
+            title: format!("{} API", group.language.as_str()),
+            level: 1,
+            sort_order,
+            content: format!(
+                "Public {} API ({} files).",
+                group.language.as_str(),
+                group.file_count
+            ),
+            generated: true,
+            source_file: None,
+        });
+
+        push_modules(
+            &mut sections,
+            &mut sort_order,
+            &group.language,
+            &group.modules,
+            Some(&section_id),
+            2,
+        );
+    }
+
+    sections
+}
+
+/// Public, top-level items that get their own section
+fn is_documented(item: &SourceItem) -> bool {
+    item.visibility == Visibility::Public
+        && item.parent.is_none()
+        && !matches!(item.kind, ItemKind::Module | ItemKind::Impl)
+}
+
+/// Push one section per module, with its items and their methods beneath it
+fn push_modules(
+    sections: &mut Vec<GeneratedSection>,
+    sort_order: &mut i32,
+    language: &Language,
+    items: &[SourceItem],
+    parent_id: Option<&str>,
+    level: i32,
+) {
+    // Methods by the type they belong to; trait methods are documented
+    // with the trait, so trait impls only contribute an "Implements" line
+    let traits: Vec<&str> = items
+        .iter()
+        .filter(|i| i.kind == ItemKind::Trait)
+        .map(|i| i.name.as_str())
+        .collect();
+    let mut methods: HashMap<&str, Vec<&SourceItem>> = HashMap::new();
+    let mut implements: HashMap<&str, Vec<&str>> = HashMap::new();
+    for item in items {
+        if item.kind == ItemKind::Impl {
+            if let Some((trait_name, type_name)) = item.name.split_once(" for ") {
+                implements.entry(type_name).or_default().push(trait_name);
+            }
+            continue;
+        }
+        let Some(parent) = item.parent.as_deref() else {
+            continue;
+        };
+        let in_trait = traits.contains(&parent);
+        if !parent.contains(" for ") && (item.visibility == Visibility::Public || in_trait) {
+            methods.entry(parent).or_default().push(item);
+        }
+    }
+
+    let mut modules: BTreeMap<&str, Vec<&SourceItem>> = BTreeMap::new();
+    for item in items.iter().filter(|i| is_documented(i)) {
+        modules.entry(item.module.as_str()).or_default().push(item);
+    }
+
+    for (index, (module, mut module_items)) in modules.into_iter().enumerate() {
+        // Types first, then constants, then functions, each in source order
+        module_items.sort_by_key(|item| match item.kind {
+            ItemKind::Function => 2,
+            ItemKind::Constant => 1,
+            _ => 0,
+        });
+
+        *sort_order += 1;
+        let module_id = match parent_id {
+            Some(parent) => format!("{}.{}", parent, index + 1),
+            None => format!("{}", index + 2),
+        };
+        let mut files: Vec<String> = module_items.iter().map(|i| file_name(i)).collect();
+        files.dedup();
+        let single_file = module_items
+            .iter()
+            .all(|i| i.file_path == module_items[0].file_path);
+        sections.push(GeneratedSection {
+            section_id: module_id.clone(),
+            title: display_module(module, language),
+            level,
+            sort_order: *sort_order,
+            content: module_content(&module_items, &files, language),
+            generated: true,
+            source_file: single_file
+                .then(|| module_items[0].file_path.to_string_lossy().to_string()),
+        });
+
+        for (item_index, item) in module_items.iter().enumerate() {
+            *sort_order += 1;
+            let item_id = format!("{}.{}", module_id, item_index + 1);
+            let mut content = item_content(item, language);
+            if let Some(traits) = implements.get(item.name.as_str()) {
+                let list = traits
+                    .iter()
+                    .map(|t| format!("`{}`", t))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                content.push_str(&format!("\n\n**Implements:** {}", list));
+            }
+            sections.push(GeneratedSection {
+                section_id: item_id.clone(),
+                title: item_title(item, language),
+                level: level + 1,
+                sort_order: *sort_order,
+                content,
+                generated: true,
+                source_file: Some(item.file_path.to_string_lossy().to_string()),
+            });
+
+            let is_type = !matches!(item.kind, ItemKind::Function | ItemKind::Constant);
+            let Some(members) = methods.get(item.name.as_str()).filter(|_| is_type) else {
+                continue;
+            };
+            let separator = if *language == Language::Rust {
+                "::"
+            } else {
+                "."
+            };
+            for (method_index, method) in members.iter().enumerate() {
+                *sort_order += 1;
+                sections.push(GeneratedSection {
+                    section_id: format!("{}.{}", item_id, method_index + 1),
+                    title: format!("{}{}{}()", item.name, separator, method.name),
+                    level: level + 2,
+                    sort_order: *sort_order,
+                    content: item_content(method, language),
+                    generated: true,
+                    source_file: Some(method.file_path.to_string_lossy().to_string()),
+                });
+            }
+        }
+    }
+}
+
+fn display_module(module: &str, language: &Language) -> String {
+    match (module, language) {
+        ("crate", Language::Rust) => "crate (root)".to_string(),
+        ("", _) => "(root)".to_string(),
+        _ => module.to_string(),
+    }
+}
+
+/// Module section body: where it's defined and a summary table of its items
+fn module_content(items: &[&SourceItem], files: &[String], language: &Language) -> String {
+    let mut content = format!(
+        "Defined in {}.\n\n| Item | Kind | Summary |\n|------|------|---------|\n",
+        files
+            .iter()
+            .map(|f| format!("`{}`", f))
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+    for item in items {
+        let summary = item
+            .doc_comment
+            .as_deref()
+            .map(|doc| parse_doc(doc).description)
+            .and_then(|d| d.lines().next().map(str::to_string))
+            .unwrap_or_default();
+        content.push_str(&format!(
+            "| `{}` | {} | {} |\n",
+            item.name,
+            kind_label(item, language),
+            escape_cell(&summary)
+        ));
+    }
+    content
+}
+
+fn item_title(item: &SourceItem, language: &Language) -> String {
+    match item.kind {
+        ItemKind::Function => format!("{}()", item.name),
+        _ => format!("{} ({})", item.name, kind_label(item, language)),
+    }
+}
+
+/// What each language calls the kind of item
+fn kind_label(item: &SourceItem, language: &Language) -> &'static str {
+    match (&item.kind, language) {
+        (ItemKind::Struct, Language::Python | Language::TypeScript) => "class",
+        (ItemKind::Struct, _) => "struct",
+        (ItemKind::Enum, _) => "enum",
+        (ItemKind::Trait, Language::TypeScript | Language::Go) => "interface",
+        (ItemKind::Trait, _) => "trait",
+        (ItemKind::Type, _) => "type",
+        (ItemKind::Constant, _) => "const",
+        (ItemKind::Function, _) => "function",
+        (ItemKind::Module, _) => "module",
+        (ItemKind::Impl, _) => "impl",
+    }
+}
+
+/// Section body for an item: signature, description, parameters, and return value
+fn item_content(item: &SourceItem, language: &Language) -> String {
+    let doc = parse_doc(item.doc_comment.as_deref().unwrap_or(""));
+    let mut parts = Vec::new();
+
+    if let Some(signature) = &item.signature {
+        parts.push(format!(
+            "```{}\n{}\n```",
+            fence_language(language),
+            signature
+        ));
+    }
+    if !doc.description.is_empty() {
+        parts.push(doc.description.clone());
+    }
+
+    if item.kind == ItemKind::Function {
+        let params = item
+            .signature
+            .as_deref()
+            .map(|s| parameters(s, &item.name, language))
+            .unwrap_or_default();
+        if !params.is_empty() {
+            let mut table = String::from(
+                "**Parameters**\n\n| Name | Type | Description |\n|------|------|-------------|\n",
+            );
+            for param in &params {
+                let ty = match (&param.ty, &param.default) {
+                    (Some(ty), Some(default)) => format!("`{}` (default `{}`)", ty, default),
+                    (Some(ty), None) => format!("`{}`", ty),
+                    (None, Some(default)) => format!("default `{}`", default),
+                    (None, None) => String::new(),
+                };
+                let lookup = param
+                    .name
+                    .trim_start_matches(['*', '.'])
+                    .trim_end_matches('?');
+                let description = doc
+                    .params
+                    .iter()
+                    .find(|(name, _)| name.trim_start_matches('*') == lookup)
+                    .map(|(_, d)| d.as_str())
+                    .unwrap_or("");
+                table.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    param.name,
+                    escape_cell(&ty),
+                    escape_cell(description)
+                ));
+            }
+            parts.push(table.trim_end().to_string());
+        }
+    }
+
+    if let Some(returns) = &doc.returns {
+        parts.push(format!("**Returns:** {}", returns));
+    }
+
+    if parts.is_empty() {
+        return format!("Defined in `{}`", file_name(item));
+    }
+    parts.join("\n\n")
+}
+
+fn fence_language(language: &Language) -> &'static str {
+    match language {
+        Language::Rust => "rust",
+        Language::Python => "python",
+        Language::TypeScript => "typescript",
+        Language::Go => "go",
+        Language::Unknown => "",
+    }
+}
+
+fn file_name(item: &SourceItem) -> String {
+    item.file_path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("")
+        .to_string()
+}
+
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', " ")
+}
+
+/// A parameter from a function signature
+#[derive(Debug, PartialEq)]
+struct Param {
+    name: String,
+    ty: Option<String>,
+    default: Option<String>,
+}
+
+/// Parameters of function `name` in its signature, without receivers
+/// (`self`, `cls`) and Python's bare `*` and `/` markers
+fn parameters(signature: &str, name: &str, language: &Language) -> Vec<Param> {
+    let Some(list) = parameter_list(signature, name) else {
+        return Vec::new();
+    };
+
+    let mut params: Vec<Param> = split_top_level(list, ',')
+        .into_iter()
+        .map(str::trim)
+        .filter(|p| !p.is_empty() && !is_receiver(p, language))
+        .map(|p| parse_param(p, language))
+        .collect();
+
+    // Go groups names that share a type: `a, b int`
+    if *language == Language::Go {
+        let mut shared: Option<String> = None;
+        for param in params.iter_mut().rev() {
+            match &param.ty {
+                Some(ty) => shared = Some(ty.clone()),
+                None => param.ty = shared.clone(),
+            }
+        }
+    }
+    params
+}
+
+/// The text between the parentheses of the parameter list following `name`
+fn parameter_list<'a>(signature: &'a str, name: &str) -> Option<&'a str> {
+    let mut search = 0;
+    let open = loop {
+        let position = search + signature[search..].find(name)?;
+        search = position + name.len();
+        let boundary = signature[..position]
+            .chars()
+            .last()
+            .map_or(true, |c| !c.is_alphanumeric() && c != '_');
+        if !boundary {
+            continue;
+        }
+        let after = &signature[search..];
+        // Skip generic parameters: name<T>(...) or Name[T any](...)
+        let after_generics = match after.chars().next() {
+            Some(open @ ('<' | '[')) => {
+                let close = if open == '<' { '>' } else { ']' };
+                let mut depth = 0;
+                let end = after.char_indices().find_map(|(i, c)| {
+                    if c == open {
+                        depth += 1;
+                    } else if c == close {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Some(i + 1);
+                        }
+                    }
+                    None
+                })?;
+                &after[end..]
+            }
+            _ => after,
+        };
+        let trimmed = after_generics.trim_start();
+        if trimmed.starts_with('(') {
+            break signature.len() - trimmed.len();
+        }
+        // Arrow functions: name = (...) => / name = async (...) =>
+        if trimmed.starts_with('=') && !trimmed.starts_with("==") {
+            let paren = trimmed.find('(')?;
+            break signature.len() - trimmed.len() + paren;
+        }
+    };
+
+    let mut depth = 0;
+    for (i, c) in signature[open..].char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(&signature[open + 1..open + i]);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Split on `separator` outside brackets, parentheses, braces, and generics
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0i32;
+    let mut start = 0;
+    let mut prev = ' ';
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '>' if prev != '-' && prev != '=' => depth -= 1,
+            c if c == separator && depth == 0 => {
+                parts.push(&text[start..i]);
+                start = i + c.len_utf8();
+            }
+            _ => {}
+        }
+        prev = c;
+    }
+    parts.push(&text[start..]);
+    parts
+}
+
+fn is_receiver(param: &str, language: &Language) -> bool {
+    match language {
+        Language::Rust => {
+            let param = param.trim_start_matches('&').trim_start();
+            let param = param.strip_prefix("mut ").unwrap_or(param);
+            // &'a self, &'a mut self
+            let param = match param.strip_prefix('\'') {
+                Some(rest) => rest
+                    .split_once(' ')
+                    .map(|(_, r)| r.trim_start_matches("mut ").trim())
+                    .unwrap_or(rest),
+                None => param,
+            };
+            param == "self" || param.starts_with("self:")
+        }
+        Language::Python => {
+            let name = param.split([':', '=']).next().unwrap_or("").trim();
+            matches!(name, "self" | "cls" | "*" | "/")
+        }
+        Language::TypeScript => param.starts_with("this:"),
+        _ => false,
+    }
+}
+
+fn parse_param(param: &str, language: &Language) -> Param {
+    if *language == Language::Go {
+        return match param.split_once(char::is_whitespace) {
+            Some((name, ty)) => Param {
+                name: name.to_string(),
+                ty: Some(ty.trim().to_string()),
+                default: None,
+            },
+            None => Param {
+                name: param.to_string(),
+                ty: None,
+                default: None,
+            },
+        };
+    }
+
+    // Default value after the first top-level `=` that isn't part of `=>`
+    let (declaration, default) = match find_default(param) {
+        Some(i) => (&param[..i], Some(param[i + 1..].trim().to_string())),
+        None => (param, None),
+    };
+    let (name, ty) = match split_top_level(declaration, ':').as_slice() {
+        [name, rest @ ..] if !rest.is_empty() => {
+            (name.trim(), Some(rest.join(":").trim().to_string()))
+        }
+        _ => (declaration.trim(), None),
+    };
+    let name = name.strip_prefix("mut ").unwrap_or(name);
+    Param {
+        name: name.to_string(),
+        ty,
+        default,
+    }
+}
+
+fn find_default(param: &str) -> Option<usize> {
+    let mut depth = 0i32;
+    let bytes = param.as_bytes();
+    for (i, c) in param.char_indices() {
+        match c {
+            '(' | '[' | '{' | '<' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '>' if i > 0 && !matches!(bytes[i - 1], b'-' | b'=') => depth -= 1,
+            '=' if depth == 0
+                && bytes.get(i + 1) != Some(&b'>')
+                && bytes.get(i + 1) != Some(&b'=') =>
+            {
+                return Some(i)
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
+/// A doc comment split into prose, documented parameters, and return value
+#[derive(Debug, Default)]
+struct DocParts {
+    description: String,
+    params: Vec<(String, String)>,
+    returns: Option<String>,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum DocBlock {
+    Prose,
+    /// Google-style `Args:` or rustdoc `# Arguments`
+    Params,
+    /// numpydoc `Parameters` / `----------`
+    NumpyParams,
+    Returns,
+}
+
+/// Split a doc comment into its parts. Understands Sphinx (`:param x:`),
+/// Google (`Args:`), numpydoc (`Parameters` underlined), JSDoc
+/// (`@param x`), and rustdoc (`# Arguments` lists) conventions.
+fn parse_doc(doc: &str) -> DocParts {
+    let mut parts = DocParts::default();
+    let mut prose: Vec<&str> = Vec::new();
+    let mut block = DocBlock::Prose;
+    // Blank lines end a block, except the ones between a header and its first entry
+    let mut block_empty = true;
+    let lines: Vec<&str> = doc.lines().collect();
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        let trimmed = line.trim();
+        let underlined = lines
+            .get(i + 1)
+            .is_some_and(|next| next.trim().len() >= 3 && next.trim().chars().all(|c| c == '-'));
+        i += 1;
+
+        // Section headers
+        let header = trimmed.trim_start_matches('#').trim().trim_end_matches(':');
+        let is_header = trimmed.starts_with("# ")
+            || (trimmed.ends_with(':') && !header.contains(' '))
+            || underlined;
+        if is_header {
+            let next = match header {
+                "Args" | "Arguments" | "Parameters" | "Params" if underlined => {
+                    Some(DocBlock::NumpyParams)
+                }
+                "Args" | "Arguments" | "Parameters" | "Params" => Some(DocBlock::Params),
+                "Returns" | "Return" | "Yields" => Some(DocBlock::Returns),
+                _ => None,
+            };
+            if let Some(next) = next {
+                block = next;
+                block_empty = true;
+                if underlined {
+                    i += 1;
+                }
+                continue;
+            }
+            if block != DocBlock::Prose && (underlined || trimmed.starts_with("# ")) {
+                block = DocBlock::Prose;
+            }
+        }
+
+        // Tagged lines work anywhere
+        if let Some(rest) = trimmed.strip_prefix(":param ") {
+            if let Some((names, description)) = rest.split_once(':') {
+                let name = names.split_whitespace().last().unwrap_or("");
+                parts
+                    .params
+                    .push((name.to_string(), description.trim().to_string()));
+                continue;
+            }
+        }
+        if let Some(rest) = trimmed
+            .strip_prefix(":returns:")
+            .or_else(|| trimmed.strip_prefix(":return:"))
+        {
+            parts.returns = Some(rest.trim().to_string());
+            continue;
+        }
+        if trimmed.starts_with(":type ") || trimmed.starts_with(":rtype:") {
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix("@param ") {
+            let rest = skip_jsdoc_type(rest);
+            let (name, description) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+            let name = name
+                .trim_start_matches('[')
+                .split(['=', ']'])
+                .next()
+                .unwrap_or(name);
+            parts.params.push((
+                name.to_string(),
+                description.trim().trim_start_matches("- ").to_string(),
+            ));
+            continue;
+        }
+        if let Some(rest) = trimmed
+            .strip_prefix("@returns")
+            .or_else(|| trimmed.strip_prefix("@return"))
+        {
+            parts.returns = Some(skip_jsdoc_type(rest.trim()).trim().to_string());
+            continue;
+        }
+
+        if block == DocBlock::Prose {
+            prose.push(line);
+            continue;
+        }
+        if trimmed.is_empty() {
+            if block != DocBlock::NumpyParams && !block_empty {
+                block = DocBlock::Prose;
+            }
+            continue;
+        }
+        block_empty = false;
+
+        match block {
+            DocBlock::Prose => {}
+            DocBlock::Params => match doc_list_param(trimmed) {
+                Some(param) => parts.params.push(param),
+                None => append_last(&mut parts.params, trimmed),
+            },
+            DocBlock::NumpyParams => match trimmed.split_once(" : ") {
+                Some((name, _)) if !line.starts_with(char::is_whitespace) => {
+                    parts.params.push((name.trim().to_string(), String::new()))
+                }
+                _ if !line.starts_with(char::is_whitespace) && !trimmed.contains(' ') => {
+                    parts.params.push((trimmed.to_string(), String::new()))
+                }
+                _ => append_last(&mut parts.params, trimmed),
+            },
+            DocBlock::Returns => {
+                let returns = parts.returns.get_or_insert_with(String::new);
+                if !returns.is_empty() {
+                    returns.push(' ');
+                }
+                returns.push_str(trimmed);
+            }
+        }
+    }
+
+    parts.description = prose.join("\n").trim().to_string();
+    parts
+}
+
+/// `{Type} rest` -> `rest`
+fn skip_jsdoc_type(text: &str) -> &str {
+    if !text.starts_with('{') {
+        return text;
+    }
+    let mut depth = 0;
+    for (i, c) in text.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return text[i + 1..].trim_start();
+                }
+            }
+            _ => {}
+        }
+    }
+    text
+}
+
+/// A parameter line in a Google `Args:` block (`name (type): text`) or a
+/// rustdoc `# Arguments` list (`` * `name` - text ``)
+fn doc_list_param(line: &str) -> Option<(String, String)> {
+    let line = line
+        .strip_prefix("* ")
+        .or_else(|| line.strip_prefix("- "))
+        .unwrap_or(line);
+
+    if let Some(rest) = line.strip_prefix('`') {
+        let (name, description) = rest.split_once('`')?;
+        let description = description.trim_start_matches([' ', '-', ':']);
+        return Some((name.to_string(), description.trim().to_string()));
+    }
+
+    let name: String = line
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '*')
+        .collect();
+    let rest = line[name.len()..].trim_start();
+    let rest = match rest.strip_prefix('(') {
+        Some(typed) => typed.split_once(')').map(|(_, r)| r)?.trim_start(),
+        None => rest,
+    };
+    let description = rest.strip_prefix(':')?;
+    (!name.is_empty()).then(|| (name, description.trim().to_string()))
+}
+
+fn append_last(params: &mut [(String, String)], text: &str) {
+    if let Some((_, description)) = params.last_mut() {
+        if !description.is_empty() {
+            description.push(' ');
+        }
+        description.push_str(text);
+    }
+}
@@ -0,0 +1,891 @@
+// Config module - Full implementation in Task #5
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+/// Project configuration stored in .tracking/config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProjectConfig {
+    pub name: String,
+    pub project_type: String,
+    pub description: Option<String>,
+    pub schema_version: String,
+    pub auto_backup: bool,
+    pub auto_session: bool,
+    /// Whether to auto-commit on session end (opt-in, default false)
+    #[serde(default)]
+    pub auto_commit: bool,
+    /// How to handle auto-commit: "auto" (silent) or "prompt" (ask each time)
+    #[serde(default = "default_auto_commit_mode")]
+    pub auto_commit_mode: String,
+    /// Whether to auto-commit when a task is completed (default true)
+    #[serde(default = "default_true")]
+    pub auto_commit_on_task: bool,
+    /// Hours after which an active session counts as stale (0 disables)
+    #[serde(default = "default_session_timeout_hours")]
+    pub session_timeout_hours: i64,
+    /// What to do with a stale session: "close" (placeholder summary), "prompt", or "abandon"
+    #[serde(default = "default_auto_close_policy")]
+    pub auto_close_policy: String,
+    /// Commit message for session-end auto-commits
+    /// (placeholders: {summary}, {session_id}, {tasks}, {decisions}, {branch}, {date})
+    #[serde(default = "default_commit_template")]
+    pub commit_template: String,
+    /// Commit message for task-completion auto-commits
+    /// (placeholders: {task_id}, {task}, {session_id}, {branch}, {date})
+    #[serde(default = "default_task_commit_template")]
+    pub task_commit_template: String,
+    /// Whether `proj status` syncs tasks with TODO/FIXME/HACK comments (opt-in)
+    #[serde(default)]
+    pub scan_todos_on_status: bool,
+    /// Scoring used by `proj context --ranked`
+    #[serde(default)]
+    pub ranking: RankingConfig,
+    /// URLs notified of session ends, task completions, and new blockers
+    #[serde(default)]
+    pub webhooks: Vec<WebhookConfig>,
+    /// Jira/Linear queries re-run by `proj task import --refresh` (saved on first import)
+    #[serde(default)]
+    pub task_imports: Vec<TaskImportConfig>,
+    /// Hours between automatic refreshes of imported tasks on `proj status` (0 disables)
+    #[serde(default = "default_task_import_refresh_hours")]
+    pub task_import_refresh_hours: i64,
+    /// What `proj cleanup --auto` does with old closed items, per table
+    /// (tasks, decisions, blockers, questions, notes, sessions, commits, activity);
+    /// tables without a policy are kept
+    #[serde(default)]
+    pub retention: BTreeMap<String, RetentionPolicy>,
+    /// Reminders `proj status` and `proj resume --for-ai` give when logging falls behind
+    #[serde(default)]
+    pub nudges: NudgesConfig,
+    /// How writes from outside a terminal (AI agents, scripts) are handled: "direct", or
+    /// "approval" to queue them for `proj approve`
+    #[serde(default = "default_agent_writes")]
+    pub agent_writes: String,
+    /// Named output shapes for `proj context --profile`. An entry named like a built-in
+    /// profile (codegen, review, planning) overrides only the fields it sets.
+    #[serde(default)]
+    pub context_profiles: BTreeMap<String, ContextProfile>,
+    /// Record each command's duration and output size in the tracking database, for
+    /// `proj stats --usage`. Off by default; nothing leaves the machine.
+    #[serde(default)]
+    pub usage_metrics: bool,
+    /// Reuse `proj status` and `proj context` output while nothing they read has changed
+    #[serde(default = "default_true")]
+    pub output_cache: bool,
+    /// Never go on the network: update checks, webhooks, and task import refreshes are
+    /// skipped, and commands that need a remote service say so instead of timing out
+    #[serde(default)]
+    pub offline: bool,
+    /// PEM file of extra CA certificates to trust for HTTPS, e.g. a company proxy's
+    #[serde(default)]
+    pub ca_bundle: Option<String>,
+    /// Days `proj trash` keeps .tracking/ directories that uninstall and schema restores
+    /// set aside before they are purged (0 keeps them until `proj trash purge`)
+    #[serde(default = "default_trash_retention_days")]
+    pub trash_retention_days: u32,
+    /// Set by `proj init --workspace`: this project groups the projects below it
+    #[serde(default)]
+    pub workspace: bool,
+    /// A workspace's member projects, relative to its root
+    #[serde(default)]
+    pub workspace_members: Vec<String>,
+}
+
+/// Rules `nudges.disabled` can name
+pub const NUDGE_RULES: &[&str] = &["no_decisions", "unlogged_commits", "stale_blocker"];
+
+/// When to nudge about logging. Each threshold set to 0 turns its rule off.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NudgesConfig {
+    /// false turns every nudge off for this project
+    pub enabled: bool,
+    /// Minutes into a session with no decisions logged (no_decisions)
+    pub no_decisions_minutes: i64,
+    /// Commits per logged decision or note this session before suggesting a review (unlogged_commits)
+    pub commits_per_log: i64,
+    /// Days a blocker can stay active before it's flagged (stale_blocker)
+    pub blocker_age_days: i64,
+    /// Rules to skip, e.g. ["stale_blocker"]
+    pub disabled: Vec<String>,
+}
+
+impl Default for NudgesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            no_decisions_minutes: 30,
+            commits_per_log: 3,
+            blocker_age_days: 7,
+            disabled: Vec::new(),
+        }
+    }
+}
+
+impl NudgesConfig {
+    /// Whether a rule should be evaluated
+    pub fn rule_enabled(&self, rule: &str) -> bool {
+        self.enabled && !self.disabled.iter().any(|r| r == rule)
+    }
+}
+
+/// Tables a context profile can draw from
+pub const CONTEXT_PROFILE_TABLES: &[&str] = &[
+    "decisions",
+    "notes",
+    "questions",
+    "tasks",
+    "blockers",
+    "commits",
+    "docs",
+];
+
+/// Fields a context profile can emit for each item
+pub const CONTEXT_PROFILE_FIELDS: &[&str] =
+    &["type", "id", "title", "content", "rationale", "date"];
+
+/// One `proj context --profile`; fields left unset come from the built-in profile of
+/// the same name, or from [`ProfileSettings::default`] for new profiles
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContextProfile {
+    /// Tables searched, from [`CONTEXT_PROFILE_TABLES`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tables: Option<Vec<String>>,
+    /// Most items considered, best-ranked first
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+    /// What each item shows, from [`CONTEXT_PROFILE_FIELDS`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fields: Option<Vec<String>>,
+    /// Hard cap on the whole output, in estimated tokens
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_tokens: Option<usize>,
+}
+
+/// A context profile with every field filled in
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProfileSettings {
+    pub tables: Vec<String>,
+    pub limit: usize,
+    pub fields: Vec<String>,
+    pub max_tokens: usize,
+}
+
+impl Default for ProfileSettings {
+    fn default() -> Self {
+        Self {
+            tables: ["decisions", "notes", "questions", "commits", "docs"]
+                .map(String::from)
+                .to_vec(),
+            limit: 20,
+            fields: ["type", "id", "title", "content", "rationale"]
+                .map(String::from)
+                .to_vec(),
+            max_tokens: 2000,
+        }
+    }
+}
+
+impl ContextProfile {
+    /// Names of the profiles every project has
+    pub const BUILTIN: &'static [&'static str] = &["codegen", "review", "planning"];
+
+    /// A built-in profile: codegen (conventions and docs to write code against), review
+    /// (decisions, commits, and open work to check changes against), or planning
+    /// (open tasks, blockers, and questions)
+    pub fn builtin(name: &str) -> Option<ProfileSettings> {
+        let strings = |items: &[&str]| items.iter().map(|s| s.to_string()).collect();
+        let (tables, limit, fields, max_tokens): (&[&str], _, &[&str], _) = match name {
+            "codegen" => (
+                &["decisions", "notes", "docs"],
+                15,
+                &["title", "content", "rationale"],
+                1500,
+            ),
+            "review" => (
+                &["decisions", "commits", "tasks", "blockers"],
+                20,
+                &["type", "id", "title", "content", "rationale", "date"],
+                2000,
+            ),
+            "planning" => (
+                &["tasks", "blockers", "questions", "decisions"],
+                25,
+                &["type", "id", "title", "content", "date"],
+                2500,
+            ),
+            _ => return None,
+        };
+        Some(ProfileSettings {
+            tables: strings(tables),
+            limit,
+            fields: strings(fields),
+            max_tokens,
+        })
+    }
+
+    /// Fill unset fields from `base`
+    fn over(&self, base: ProfileSettings) -> ProfileSettings {
+        ProfileSettings {
+            tables: self.tables.clone().unwrap_or(base.tables),
+            limit: self.limit.unwrap_or(base.limit),
+            fields: self.fields.clone().unwrap_or(base.fields),
+            max_tokens: self.max_tokens.unwrap_or(base.max_tokens),
+        }
+    }
+}
+
+/// Retention for one table
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionPolicy {
+    /// "keep", "archive" (tasks, decisions, blockers), "purge", or "compress" (sessions)
+    pub action: String,
+    /// Days since the item was closed before the action applies
+    #[serde(default)]
+    pub after_days: u32,
+}
+
+/// A saved `proj task import`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskImportConfig {
+    /// "jira" or "linear"
+    pub from: String,
+    /// JQL for Jira, team key for Linear
+    pub query: String,
+}
+
+/// One webhook target
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebhookConfig {
+    pub url: String,
+    /// Events to send: session_end, task_completed, blocker_added (empty = all)
+    #[serde(default)]
+    pub events: Vec<String>,
+    /// Signs each payload with HMAC-SHA256 (X-Proj-Signature header)
+    #[serde(default)]
+    pub secret: Option<String>,
+}
+
+/// Relevance scoring for `proj context --ranked`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RankingConfig {
+    /// Days for the recency bonus to halve
+    pub recency_half_life_days: f64,
+    /// Recency bonus for an item logged just now
+    pub recency_weight: f64,
+    /// Bonus per occurrence of a query word in the item's content (capped at 5)
+    pub frequency_weight: f64,
+    /// Score multiplier per item type: decision, note, question, task, blocker, commit, section (docs)
+    pub table_weights: BTreeMap<String, f64>,
+    /// Bonus when a tag equals the whole query
+    pub tag_boost: f64,
+    /// Bonus per query word that equals a tag
+    pub tag_word_boost: f64,
+}
+
+impl Default for RankingConfig {
+    fn default() -> Self {
+        Self {
+            recency_half_life_days: 30.0,
+            recency_weight: 2.0,
+            frequency_weight: 0.5,
+            table_weights: [
+                ("decision", 1.0),
+                ("note", 1.0),
+                ("question", 1.0),
+                ("commit", 1.0),
+                ("section", 0.8),
+            ]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect(),
+            tag_boost: 6.0,
+            tag_word_boost: 2.0,
+        }
+    }
+}
+
+fn default_auto_commit_mode() -> String {
+    "prompt".to_string()
+}
+
+fn default_session_timeout_hours() -> i64 {
+    8
+}
+
+fn default_auto_close_policy() -> String {
+    "close".to_string()
+}
+
+fn default_commit_template() -> String {
+    "[proj] {summary}".to_string()
+}
+
+fn default_task_commit_template() -> String {
+    "[proj] Completed task #{task_id}: {task}".to_string()
+}
+
+fn default_task_import_refresh_hours() -> i64 {
+    24
+}
+
+fn default_agent_writes() -> String {
+    "direct".to_string()
+}
+
+fn default_trash_retention_days() -> u32 {
+    30
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for ProjectConfig {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            project_type: String::new(),
+            description: None,
+            schema_version: crate::SCHEMA_VERSION.to_string(),
+            auto_backup: true,
+            auto_session: true,
+            auto_commit: false,
+            auto_commit_mode: "prompt".to_string(),
+            auto_commit_on_task: true,
+            session_timeout_hours: default_session_timeout_hours(),
+            auto_close_policy: default_auto_close_policy(),
+            commit_template: default_commit_template(),
+            task_commit_template: default_task_commit_template(),
+            scan_todos_on_status: false,
+            ranking: RankingConfig::default(),
+            webhooks: Vec::new(),
+            task_imports: Vec::new(),
+            task_import_refresh_hours: default_task_import_refresh_hours(),
+            retention: BTreeMap::new(),
+            nudges: NudgesConfig::default(),
+            agent_writes: default_agent_writes(),
+            context_profiles: BTreeMap::new(),
+            usage_metrics: false,
+            output_cache: true,
+            offline: false,
+            ca_bundle: None,
+            trash_retention_days: default_trash_retention_days(),
+            workspace: false,
+            workspace_members: Vec::new(),
+        }
+    }
+}
+
+/// Set to 1 (or 0) to turn offline mode on (or off) whatever config.json says
+pub const OFFLINE_ENV: &str = "PROJ_OFFLINE";
+
+/// Whether proj stays off the network: $PROJ_OFFLINE, else `offline` in the
+/// project's config.json, else in ~/.proj/config.json (outside a project)
+pub fn offline() -> bool {
+    if let Some(value) = std::env::var(OFFLINE_ENV).ok().filter(|v| !v.is_empty()) {
+        return value != "0" && value != "false";
+    }
+    if let Ok(config) = ProjectConfig::load() {
+        return config.offline;
+    }
+    load_global_defaults()
+        .ok()
+        .flatten()
+        .and_then(|defaults| defaults.get("offline").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+/// Path of a PEM file of CA certificates to trust, whatever config.json says
+pub const CA_BUNDLE_ENV: &str = "PROJ_CA_BUNDLE";
+
+/// Extra CA certificates for HTTPS: $PROJ_CA_BUNDLE, else `ca_bundle` in the
+/// project's config.json, else in ~/.proj/config.json
+pub fn ca_bundle() -> Option<PathBuf> {
+    if let Some(path) = std::env::var_os(CA_BUNDLE_ENV).filter(|v| !v.is_empty()) {
+        return Some(PathBuf::from(path));
+    }
+    let path = match ProjectConfig::load() {
+        Ok(config) => config.ca_bundle,
+        Err(_) => load_global_defaults()
+            .ok()
+            .flatten()
+            .and_then(|defaults| defaults.get("ca_bundle")?.as_str().map(String::from)),
+    };
+    path.filter(|p| !p.is_empty()).map(PathBuf::from)
+}
+
+/// Days trashed data is kept: `trash_retention_days` in the project's config.json,
+/// else in ~/.proj/config.json (outside a project)
+pub fn trash_retention_days() -> u32 {
+    if let Ok(config) = ProjectConfig::load() {
+        return config.trash_retention_days;
+    }
+    load_global_defaults()
+        .ok()
+        .flatten()
+        .and_then(|defaults| defaults.get("trash_retention_days")?.as_u64())
+        .map(|days| days as u32)
+        .unwrap_or_else(default_trash_retention_days)
+}
+
+/// Fields that describe one project and are never inherited from ~/.proj/config.json
+pub const PROJECT_ONLY_KEYS: &[&str] = &[
+    "name",
+    "project_type",
+    "description",
+    "schema_version",
+    "workspace",
+    "workspace_members",
+];
+
+/// Values accepted for auto_commit_mode
+pub const AUTO_COMMIT_MODES: &[&str] = &["prompt", "auto"];
+
+/// Values accepted for auto_close_policy
+pub const AUTO_CLOSE_POLICIES: &[&str] = &["close", "prompt", "abandon"];
+
+/// Values accepted for agent_writes
+pub const AGENT_WRITE_MODES: &[&str] = &["direct", "approval"];
+
+/// Events a webhook can subscribe to
+pub const WEBHOOK_EVENTS: &[&str] = &["session_end", "task_completed", "blocker_added"];
+
+impl ProjectConfig {
+    /// Load config from the project's .tracking/config.json
+    pub fn load() -> anyhow::Result<Self> {
+        let config_path = crate::paths::get_config_path()?;
+        let content = std::fs::read_to_string(&config_path)?;
+        Self::from_json(&content)
+    }
+
+    /// Parse a project's config.json, filling keys it doesn't set from ~/.proj/config.json
+    pub fn from_json(content: &str) -> anyhow::Result<Self> {
+        let mut value: serde_json::Value = serde_json::from_str(content)?;
+        if let (Some(fields), Some(global)) = (value.as_object_mut(), load_global_defaults()?) {
+            for (key, v) in global {
+                if !PROJECT_ONLY_KEYS.contains(&key.as_str()) && !fields.contains_key(&key) {
+                    fields.insert(key, v);
+                }
+            }
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+
+    /// Save config to the project's .tracking/config.json
+    ///
+    /// Keys ~/.proj/config.json sets are left out when the file doesn't already have
+    /// them and the value is the global or built-in default, so the project keeps
+    /// inheriting later changes to the global file.
+    pub fn save(&self) -> anyhow::Result<()> {
+        self.save_to(&crate::paths::get_config_path()?)
+    }
+
+    /// Save config to a given config.json (see [`ProjectConfig::save`])
+    pub fn save_to(&self, config_path: &std::path::Path) -> anyhow::Result<()> {
+        let existing: Option<serde_json::Map<String, serde_json::Value>> =
+            std::fs::read_to_string(config_path)
+                .ok()
+                .and_then(|c| serde_json::from_str(&c).ok());
+
+        let builtin = serde_json::to_value(Self::default())?;
+        let mut value = serde_json::to_value(self)?;
+        if let (Some(fields), Some(global)) = (value.as_object_mut(), load_global_defaults()?) {
+            for (key, v) in global {
+                let set_in_file = existing.as_ref().is_some_and(|e| e.contains_key(&key));
+                let inherited =
+                    fields.get(&key) == Some(&v) || fields.get(&key) == builtin.get(&key);
+                if !set_in_file && inherited && !PROJECT_ONLY_KEYS.contains(&key.as_str()) {
+                    fields.remove(&key);
+                }
+            }
+        }
+        let content = serde_json::to_string_pretty(&value)?;
+        std::fs::write(config_path, content)?;
+        Ok(())
+    }
+
+    /// A `proj context --profile` by name, with unset fields filled in
+    pub fn context_profile(&self, name: &str) -> Option<ProfileSettings> {
+        let builtin = ContextProfile::builtin(name);
+        match self.context_profiles.get(name) {
+            Some(profile) => Some(profile.over(builtin.unwrap_or_default())),
+            None => builtin,
+        }
+    }
+
+    /// Built-in profiles, then the project's own
+    pub fn context_profile_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = ContextProfile::BUILTIN
+            .iter()
+            .map(|n| n.to_string())
+            .collect();
+        for name in self.context_profiles.keys() {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+        names
+    }
+
+    /// Check values serde accepts but proj doesn't
+    pub fn validate(&self) -> Result<(), String> {
+        if !AUTO_COMMIT_MODES.contains(&self.auto_commit_mode.as_str()) {
+            return Err(format!(
+                "auto_commit_mode must be one of: {} (got '{}')",
+                AUTO_COMMIT_MODES.join(", "),
+                self.auto_commit_mode
+            ));
+        }
+        if !AUTO_CLOSE_POLICIES.contains(&self.auto_close_policy.as_str()) {
+            return Err(format!(
+                "auto_close_policy must be one of: {} (got '{}')",
+                AUTO_CLOSE_POLICIES.join(", "),
+                self.auto_close_policy
+            ));
+        }
+        if !AGENT_WRITE_MODES.contains(&self.agent_writes.as_str()) {
+            return Err(format!(
+                "agent_writes must be one of: {} (got '{}')",
+                AGENT_WRITE_MODES.join(", "),
+                self.agent_writes
+            ));
+        }
+        if self.session_timeout_hours < 0 {
+            return Err("session_timeout_hours can't be negative (0 disables)".to_string());
+        }
+        if self.task_import_refresh_hours < 0 {
+            return Err("task_import_refresh_hours can't be negative (0 disables)".to_string());
+        }
+        if self.ranking.recency_half_life_days <= 0.0 {
+            return Err("ranking.recency_half_life_days must be greater than 0".to_string());
+        }
+        for webhook in &self.webhooks {
+            if !webhook.url.starts_with("http://") && !webhook.url.starts_with("https://") {
+                return Err(format!(
+                    "webhook url '{}' must start with http(s)://",
+                    webhook.url
+                ));
+            }
+            if let Some(event) = webhook
+                .events
+                .iter()
+                .find(|e| !WEBHOOK_EVENTS.contains(&e.as_str()))
+            {
+                return Err(format!(
+                    "webhook event '{}' isn't one of: {}",
+                    event,
+                    WEBHOOK_EVENTS.join(", ")
+                ));
+            }
+        }
+        let nudges = &self.nudges;
+        if nudges.no_decisions_minutes < 0
+            || nudges.commits_per_log < 0
+            || nudges.blocker_age_days < 0
+        {
+            return Err("nudges thresholds can't be negative (0 turns a rule off)".to_string());
+        }
+        if let Some(rule) = nudges
+            .disabled
+            .iter()
+            .find(|r| !NUDGE_RULES.contains(&r.as_str()))
+        {
+            return Err(format!(
+                "nudges.disabled: unknown rule '{}' (rules: {})",
+                rule,
+                NUDGE_RULES.join(", ")
+            ));
+        }
+        for (name, profile) in &self.context_profiles {
+            let check = |values: &Option<Vec<String>>, what: &str, allowed: &[&str]| {
+                let Some(values) = values else {
+                    return Ok(());
+                };
+                if values.is_empty() {
+                    return Err(format!("context_profiles.{}.{} can't be empty", name, what));
+                }
+                match values.iter().find(|v| !allowed.contains(&v.as_str())) {
+                    Some(v) => Err(format!(
+                        "context_profiles.{}.{}: unknown '{}' (use: {})",
+                        name,
+                        what,
+                        v,
+                        allowed.join(", ")
+                    )),
+                    None => Ok(()),
+                }
+            };
+            check(&profile.tables, "tables", CONTEXT_PROFILE_TABLES)?;
+            check(&profile.fields, "fields", CONTEXT_PROFILE_FIELDS)?;
+            if profile.limit == Some(0) || profile.max_tokens == Some(0) {
+                return Err(format!(
+                    "context_profiles.{}: limit and max_tokens must be greater than 0",
+                    name
+                ));
+            }
+        }
+        for import in &self.task_imports {
+            if import.from != "jira" && import.from != "linear" {
+                return Err(format!(
+                    "task_imports source '{}' must be jira or linear",
+                    import.from
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Keys set in ~/.proj/config.json, or None if it doesn't exist
+pub fn load_global_defaults() -> anyhow::Result<Option<serde_json::Map<String, serde_json::Value>>>
+{
+    let path = crate::paths::get_global_config_path()?;
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&path)?;
+    let defaults = serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+    Ok(Some(defaults))
+}
+
+/// Global registry entry
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RegistryEntry {
+    pub path: String,
+    pub name: String,
+    #[serde(alias = "type")]
+    pub project_type: String,
+    pub registered_at: String,
+    pub schema_version: String,
+}
+
+/// Global registry stored in ~/.proj/registry.json
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Registry {
+    pub registered_projects: Vec<RegistryEntry>,
+    pub current_schema_version: String,
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self {
+            registered_projects: Vec::new(),
+            current_schema_version: crate::SCHEMA_VERSION.to_string(),
+        }
+    }
+}
+
+/// Global backup settings stored in ~/.proj/backup.json
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct BackupConfig {
+    /// Named remote targets for `proj backup --remote <name>`
+    #[serde(default)]
+    pub remotes: BTreeMap<String, RemoteConfig>,
+    /// Push to every configured remote after `proj session end`
+    #[serde(default)]
+    pub push_on_session_end: bool,
+    /// Encrypt every backup (same as always passing --encrypt)
+    #[serde(default)]
+    pub encrypt: bool,
+    /// Always keep this many of the newest remote backups per project
+    #[serde(default)]
+    pub keep_last: Option<usize>,
+    /// Delete remote backups older than this many days (the newest `keep_last` are kept)
+    #[serde(default)]
+    pub keep_days: Option<u32>,
+}
+
+/// One remote backup target. Credentials left out here fall back to environment variables.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct RemoteConfig {
+    /// s3://bucket/prefix/, webdav://host/path/, or sftp://user@host/path/
+    pub url: String,
+    /// S3 region (default: $AWS_REGION or us-east-1)
+    #[serde(default)]
+    pub region: Option<String>,
+    /// S3-compatible endpoint, e.g. https://minio.example.com (path-style requests)
+    #[serde(default)]
+    pub endpoint: Option<String>,
+    #[serde(default)]
+    pub access_key_id: Option<String>,
+    #[serde(default)]
+    pub secret_access_key: Option<String>,
+    /// WebDAV user name
+    #[serde(default)]
+    pub username: Option<String>,
+    /// WebDAV password
+    #[serde(default)]
+    pub password: Option<String>,
+}
+
+impl BackupConfig {
+    /// Load ~/.proj/backup.json, or defaults if it doesn't exist
+    pub fn load() -> anyhow::Result<Self> {
+        let path = crate::paths::get_backup_config_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let config: BackupConfig = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        Ok(config)
+    }
+}
+
+/// Release channels proj can update itself from
+pub const UPDATE_CHANNELS: [&str; 3] = ["stable", "beta", "none"];
+
+/// Self-update settings stored in ~/.proj/update.json
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UpdateConfig {
+    /// "stable", "beta" (pre-releases as well), or "none" (no checks or downloads)
+    #[serde(default = "default_update_channel")]
+    pub channel: String,
+    /// Unix timestamp before which updates aren't downloaded or applied (`proj update --defer`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deferred_until: Option<u64>,
+    /// minisign public key release archives are also checked against; it only stands
+    /// in for the key built into proj in builds that have none
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+}
+
+fn default_update_channel() -> String {
+    "stable".to_string()
+}
+
+impl Default for UpdateConfig {
+    fn default() -> Self {
+        Self {
+            channel: default_update_channel(),
+            deferred_until: None,
+            public_key: None,
+        }
+    }
+}
+
+impl UpdateConfig {
+    /// Load ~/.proj/update.json, or defaults if it doesn't exist
+    pub fn load() -> anyhow::Result<Self> {
+        let path = crate::paths::get_update_config_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let config: UpdateConfig = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        if !UPDATE_CHANNELS.contains(&config.channel.as_str()) {
+            anyhow::bail!(
+                "Unknown update channel '{}' in {}. Use {}",
+                config.channel,
+                path.display(),
+                UPDATE_CHANNELS.join(", ")
+            );
+        }
+        Ok(config)
+    }
+
+    /// Write ~/.proj/update.json
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = crate::paths::get_update_config_path()?;
+        crate::paths::ensure_dir(&crate::paths::get_global_dir()?)?;
+        std::fs::write(&path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(())
+    }
+
+    /// Whether `proj update --defer` is still holding updates back
+    pub fn deferred(&self, now: u64) -> bool {
+        self.deferred_until.is_some_and(|until| until > now)
+    }
+}
+
+/// Embedding provider for `proj embed` and `proj context --semantic`, stored in ~/.proj/embeddings.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EmbeddingConfig {
+    /// "ollama" (local model) or "openai" (any OpenAI-compatible endpoint)
+    pub provider: String,
+    /// Embedding model, e.g. nomic-embed-text or text-embedding-3-small
+    pub model: String,
+    /// API base URL (default: http://localhost:11434 for ollama, https://api.openai.com/v1 for openai)
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// API key for OpenAI-compatible endpoints (default: $OPENAI_API_KEY)
+    #[serde(default)]
+    pub api_key: Option<String>,
+}
+
+impl EmbeddingConfig {
+    /// Load ~/.proj/embeddings.json, or None if no provider is configured
+    pub fn load() -> anyhow::Result<Option<Self>> {
+        let path = crate::paths::get_embeddings_config_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let config: EmbeddingConfig = serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        Ok(Some(config))
+    }
+}
+
+/// Issue tracker credentials for `proj task sync`, stored in ~/.proj/integrations.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrationsConfig {
+    #[serde(default)]
+    pub github: Option<GithubConfig>,
+    #[serde(default)]
+    pub jira: Option<JiraConfig>,
+    #[serde(default)]
+    pub linear: Option<LinearConfig>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GithubConfig {
+    /// Personal access token (default: `gh auth token`, then $GITHUB_TOKEN)
+    #[serde(default)]
+    pub token: Option<String>,
+    /// API base URL for GitHub Enterprise (default: https://api.github.com)
+    #[serde(default)]
+    pub api_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JiraConfig {
+    /// Site URL, e.g. https://acme.atlassian.net
+    pub base_url: String,
+    /// Atlassian account email (Jira Cloud); leave out for Server/Data Center
+    #[serde(default)]
+    pub email: Option<String>,
+    /// API token, or a personal access token on Server/Data Center (default: $JIRA_API_TOKEN)
+    #[serde(default)]
+    pub api_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinearConfig {
+    /// Personal API key (default: $LINEAR_API_KEY)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// GraphQL endpoint (default: https://api.linear.app/graphql)
+    #[serde(default)]
+    pub api_url: Option<String>,
+}
+
+impl IntegrationsConfig {
+    /// Load ~/.proj/integrations.json, or the defaults if it doesn't exist
+    pub fn load() -> anyhow::Result<Self> {
+        let path = crate::paths::get_integrations_config_path()?;
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let content = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .map_err(|e| anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))
+    }
+}
@@ -0,0 +1,169 @@
+// Database module - Full implementation in Task #4
+
+use anyhow::{Context, Result};
+use fs2::FileExt;
+use rusqlite::{Connection, ErrorCode, OpenFlags, Transaction, TransactionBehavior};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs::{File, OpenOptions};
+use std::path::Path;
+use std::thread;
+use std::time::Duration;
+use tracing::debug;
+
+use crate::diagnostics;
+use crate::paths::{ensure_dir, get_global_dir};
+
+/// How long a statement waits for another process's lock before failing with "database is locked"
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Attempts for operations that still come back busy (SQLite skips the busy handler when waiting could deadlock)
+const BUSY_ATTEMPTS: u32 = 4;
+
+/// Opens or creates a SQLite database with standard settings
+pub fn open_database(path: &Path) -> Result<Connection> {
+    debug!(target: "proj::db", "opening {}", path.display());
+    let mut conn = Connection::open(path)?;
+    diagnostics::profile_sql(&mut conn);
+
+    // Wait for other writers (editor extension, git hooks, another terminal) instead of failing
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+
+    // Enable foreign keys
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+
+    // Use WAL mode for better concurrency; switching needs a moment of exclusive access
+    retry_busy(|| conn.execute_batch("PRAGMA journal_mode = WAL;"))?;
+
+    Ok(conn)
+}
+
+/// Run an operation, retrying with backoff while the database is busy or locked
+pub fn retry_busy<T>(mut op: impl FnMut() -> rusqlite::Result<T>) -> rusqlite::Result<T> {
+    let mut delay = Duration::from_millis(50);
+    let mut attempt = 1;
+    loop {
+        match op() {
+            Err(e) if is_busy(&e) && attempt < BUSY_ATTEMPTS => {
+                debug!(target: "proj::db", "busy (attempt {}), retrying in {:?}", attempt, delay);
+                thread::sleep(delay);
+                delay *= 2;
+                attempt += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Start a transaction that writes. It takes the write lock at BEGIN, where the busy
+/// timeout and retries apply; a deferred transaction that reads first and then writes
+/// fails at once with "database is locked" if another writer got in between.
+pub fn write_transaction(conn: &Connection) -> rusqlite::Result<Transaction<'_>> {
+    retry_busy(|| Transaction::new_unchecked(conn, TransactionBehavior::Immediate))
+}
+
+pub(crate) fn is_busy(error: &rusqlite::Error) -> bool {
+    matches!(
+        error,
+        rusqlite::Error::SqliteFailure(e, _)
+            if e.code == ErrorCode::DatabaseBusy || e.code == ErrorCode::DatabaseLocked
+    )
+}
+
+/// Advisory lock held while a database's schema is migrated; released on drop
+pub struct MigrationLock {
+    _file: File,
+}
+
+/// Take the migration lock for a database, waiting if another process holds it.
+/// The lock file lives in ~/.proj/locks so it never shows up in a project's git status.
+pub fn lock_for_migration(db_path: &Path) -> Result<MigrationLock> {
+    let canonical = db_path
+        .canonicalize()
+        .unwrap_or_else(|_| db_path.to_path_buf());
+    let digest = Sha256::digest(canonical.to_string_lossy().as_bytes());
+    let name: String = digest[..8].iter().map(|b| format!("{:02x}", b)).collect();
+
+    let dir = get_global_dir()?.join("locks");
+    ensure_dir(&dir)?;
+    let lock_path = dir.join(format!("{}.lock", name));
+    let file = OpenOptions::new()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .open(&lock_path)
+        .with_context(|| format!("Failed to open lock file {}", lock_path.display()))?;
+
+    if file.try_lock_exclusive().is_err() {
+        eprintln!("Waiting for another proj process to finish migrating the database...");
+        file.lock_exclusive()
+            .with_context(|| format!("Failed to lock {}", lock_path.display()))?;
+    }
+    debug!(target: "proj::db", "migration lock held for {}", canonical.display());
+    Ok(MigrationLock { _file: file })
+}
+
+/// Creates a backup of the database. Goes through SQLite (VACUUM INTO) rather than
+/// copying the file, so changes still in the write-ahead log are included.
+pub fn backup_database(source: &Path, dest: &Path) -> Result<()> {
+    let conn = Connection::open_with_flags(source, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    conn.busy_timeout(BUSY_TIMEOUT)?;
+    // VACUUM INTO won't write over an existing file
+    if dest.exists() {
+        std::fs::remove_file(dest)?;
+    }
+    conn.execute("VACUUM INTO ?1", [dest.to_string_lossy()])?;
+    Ok(())
+}
+
+/// UUID of a record, its identity outside this database (e.g. `record_uuid(conn, "tasks", "task_id", 12)`)
+pub fn record_uuid(conn: &Connection, table: &str, pk: &str, id: i64) -> Option<String> {
+    conn.query_row(
+        &format!("SELECT uuid FROM {} WHERE {} = ?1", table, pk),
+        [id],
+        |row| row.get(0),
+    )
+    .ok()
+    .flatten()
+}
+
+/// Column names of a table in "main" or an attached database
+pub fn table_columns(conn: &Connection, schema: &str, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA {}.table_info({})", schema, table))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(columns)
+}
+
+/// IDs joined for an SQL `IN (...)` list
+pub fn id_list(ids: &BTreeSet<i64>) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Gets the schema version from the database
+pub fn get_schema_version(conn: &Connection) -> Result<Option<String>> {
+    let result: Result<String, _> = conn.query_row(
+        "SELECT value FROM project_meta WHERE key = 'schema_version'",
+        [],
+        |row| row.get(0),
+    );
+
+    match result {
+        Ok(version) => Ok(Some(version)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Sets the schema version in the database
+pub fn set_schema_version(conn: &Connection, version: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO project_meta (key, value) VALUES ('schema_version', ?1)",
+        [version],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+// Diagnostics - timing of SQL statements and external commands, logged through `tracing`
+//
+// Nothing is logged unless the embedding application installs a subscriber
+// (the proj CLI does so for --debug / --trace).
+
+use std::io;
+use std::process::{Command, Output};
+use std::time::{Duration, Instant};
+
+use rusqlite::Connection;
+use tracing::{debug, trace, Level};
+
+/// Statements at least this slow are logged with --debug; --trace logs every statement
+const SLOW_QUERY: Duration = Duration::from_millis(100);
+
+/// Time every statement on this connection when diagnostics are enabled
+pub fn profile_sql(conn: &mut Connection) {
+    if tracing::enabled!(Level::DEBUG) {
+        conn.profile(Some(log_statement));
+    }
+}
+
+fn log_statement(sql: &str, elapsed: Duration) {
+    let sql = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+    let ms = elapsed.as_secs_f64() * 1000.0;
+    if elapsed >= SLOW_QUERY {
+        debug!(target: "proj::sql", "slow query ({:.1}ms): {}", ms, sql);
+    } else {
+        trace!(target: "proj::sql", "{:.1}ms: {}", ms, sql);
+    }
+}
+
+/// Run a command (git, mostly) and log what ran, where, and how long it took
+pub fn output(cmd: &mut Command) -> io::Result<Output> {
+    let start = Instant::now();
+    let result = cmd.output();
+
+    if tracing::enabled!(Level::DEBUG) {
+        let line = std::iter::once(cmd.get_program())
+            .chain(cmd.get_args())
+            .map(|a| a.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let dir = cmd
+            .get_current_dir()
+            .map(|d| d.display().to_string())
+            .unwrap_or_else(|| ".".to_string());
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        match &result {
+            Ok(out) => debug!(
+                target: "proj::exec",
+                "{} (in {}) -> {} in {:.1}ms",
+                line,
+                dir,
+                out.status,
+                ms
+            ),
+            Err(e) => debug!(target: "proj::exec", "{} (in {}) failed: {}", line, dir, e),
+        }
+    }
+    result
+}
@@ -6,13 +6,19 @@ use std::path::{Path, PathBuf};
 use anyhow::{Context, Result};
 use chrono::Utc;
 use rusqlite::Connection;
+use tracing::debug;
 
+use crate::database::write_transaction;
+use crate::diagnostics;
+use crate::error::ProjError;
 use crate::schema_docs::{self, DocType};
 
 /// Open or create a documentation database
 pub fn open_docs_db(path: &Path) -> Result<Connection> {
-    let conn = Connection::open(path)
+    debug!(target: "proj::db", "opening {}", path.display());
+    let mut conn = Connection::open(path)
         .with_context(|| format!("Failed to open docs database at {:?}", path))?;
+    diagnostics::profile_sql(&mut conn);
 
     // Enable foreign keys
     conn.execute_batch("PRAGMA foreign_keys = ON;")?;
@@ -194,6 +200,7 @@ pub struct Section {
 }
 
 /// Insert a new section
+#[allow(clippy::too_many_arguments)]
 pub fn insert_section(
     conn: &Connection,
     section_id: &str,
@@ -458,6 +465,234 @@ pub fn search_sections(conn: &Connection, query: &str) -> Result<Vec<Section>> {
     }
 }
 
+/// A `[[section:3.2]]` or `[[section:3.2|link text]]` reference in section content
+#[derive(Debug, Clone, PartialEq)]
+pub struct CrossRef {
+    /// section_id being referenced
+    pub target: String,
+    pub label: Option<String>,
+    /// Byte range of the whole `[[...]]` in the content
+    pub range: std::ops::Range<usize>,
+}
+
+/// Find cross-references in section content, in order
+pub fn find_crossrefs(content: &str) -> Vec<CrossRef> {
+    const OPEN: &str = "[[section:";
+
+    let mut refs = Vec::new();
+    let mut pos = 0;
+    while let Some(offset) = content[pos..].find(OPEN) {
+        let start = pos + offset;
+        let inner_start = start + OPEN.len();
+        let Some(len) = content[inner_start..].find("]]") else {
+            break;
+        };
+        let inner = &content[inner_start..inner_start + len];
+        let end = inner_start + len + 2;
+
+        let (target, label) = match inner.split_once('|') {
+            Some((target, label)) => (target.trim(), Some(label.trim())),
+            None => (inner.trim(), None),
+        };
+        if !target.is_empty() && !inner.contains('\n') {
+            refs.push(CrossRef {
+                target: target.to_string(),
+                label: label.filter(|l| !l.is_empty()).map(str::to_string),
+                range: start..end,
+            });
+            pos = end;
+        } else {
+            pos = inner_start;
+        }
+    }
+    refs
+}
+
+/// Replace each cross-reference with the text `render` returns for it
+pub fn replace_crossrefs(content: &str, render: impl Fn(&CrossRef) -> String) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut pos = 0;
+    for crossref in find_crossrefs(content) {
+        output.push_str(&content[pos..crossref.range.start]);
+        output.push_str(&render(&crossref));
+        pos = crossref.range.end;
+    }
+    output.push_str(&content[pos..]);
+    output
+}
+
+/// HTML id / link fragment for a section, e.g. `section-3-2` for "3.2"
+pub fn section_anchor(section_id: &str) -> String {
+    let slug: String = section_id
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!("section-{}", slug)
+}
+
+/// A saved copy of the docs sections
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub id: i64,
+    pub name: String,
+    pub created_at: String,
+    pub section_count: i64,
+}
+
+/// A section as it was when a snapshot was taken
+#[derive(Debug, Clone)]
+pub struct SnapshotSection {
+    pub section_id: String,
+    pub title: String,
+    pub level: i32,
+    pub sort_order: i32,
+    pub content: String,
+    pub content_hash: String,
+    pub generated: bool,
+    pub source_file: Option<String>,
+}
+
+/// Save a copy of every section under a name
+pub fn create_snapshot(conn: &Connection, name: &str) -> Result<Snapshot> {
+    // Databases created before snapshots existed get the tables here
+    schema_docs::init_docs_db(conn)?;
+
+    let exists = conn
+        .query_row(
+            "SELECT 1 FROM snapshots WHERE name = ?1",
+            [name],
+            |_| Ok(()),
+        )
+        .is_ok();
+    if exists {
+        return Err(ProjError::InvalidInput(format!("Snapshot '{}' already exists", name)).into());
+    }
+
+    let sections = get_all_sections(conn)?;
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%S%.6f").to_string();
+
+    let tx = write_transaction(conn)?;
+    tx.execute(
+        "INSERT INTO snapshots (name, created_at) VALUES (?1, ?2)",
+        [name, &now],
+    )?;
+    let id = tx.last_insert_rowid();
+    for section in &sections {
+        tx.execute(
+            r#"INSERT INTO snapshot_sections
+               (snapshot_id, section_id, title, level, sort_order, content, content_hash, generated, source_file)
+               VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)"#,
+            rusqlite::params![
+                id,
+                section.section_id,
+                section.title,
+                section.level,
+                section.sort_order,
+                section.content,
+                hash_content(&section.content),
+                section.generated as i32,
+                section.source_file,
+            ],
+        )?;
+    }
+    tx.commit()?;
+
+    Ok(Snapshot {
+        id,
+        name: name.to_string(),
+        created_at: now,
+        section_count: sections.len() as i64,
+    })
+}
+
+/// All snapshots, oldest first
+pub fn list_snapshots(conn: &Connection) -> Result<Vec<Snapshot>> {
+    if !has_snapshots_table(conn) {
+        return Ok(Vec::new());
+    }
+    let mut stmt = conn.prepare(
+        r#"SELECT s.id, s.name, s.created_at,
+                  (SELECT COUNT(*) FROM snapshot_sections WHERE snapshot_id = s.id)
+           FROM snapshots s ORDER BY s.created_at, s.id"#,
+    )?;
+    let snapshots = stmt
+        .query_map([], |row| {
+            Ok(Snapshot {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                created_at: row.get(2)?,
+                section_count: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(snapshots)
+}
+
+/// Sections saved in a snapshot, in document order
+pub fn get_snapshot_sections(conn: &Connection, snapshot_id: i64) -> Result<Vec<SnapshotSection>> {
+    let mut stmt = conn.prepare(
+        r#"SELECT section_id, title, level, sort_order, content, content_hash, generated, source_file
+           FROM snapshot_sections WHERE snapshot_id = ?1 ORDER BY sort_order"#,
+    )?;
+    let sections = stmt
+        .query_map([snapshot_id], |row| {
+            Ok(SnapshotSection {
+                section_id: row.get(0)?,
+                title: row.get(1)?,
+                level: row.get(2)?,
+                sort_order: row.get(3)?,
+                content: row.get::<_, Option<String>>(4)?.unwrap_or_default(),
+                content_hash: row.get(5)?,
+                generated: row.get::<_, i32>(6)? != 0,
+                source_file: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(sections)
+}
+
+/// Delete a snapshot, returning false if there was none by that name
+pub fn delete_snapshot(conn: &Connection, name: &str) -> Result<bool> {
+    if !has_snapshots_table(conn) {
+        return Ok(false);
+    }
+    let tx = write_transaction(conn)?;
+    let id: Option<i64> = tx
+        .query_row("SELECT id FROM snapshots WHERE name = ?1", [name], |row| {
+            row.get(0)
+        })
+        .ok();
+    let Some(id) = id else {
+        return Ok(false);
+    };
+    tx.execute("DELETE FROM snapshot_sections WHERE snapshot_id = ?1", [id])?;
+    tx.execute("DELETE FROM snapshots WHERE id = ?1", [id])?;
+    tx.commit()?;
+    Ok(true)
+}
+
+fn has_snapshots_table(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'snapshots'",
+        [],
+        |_| Ok(()),
+    )
+    .is_ok()
+}
+
+/// SHA256 of a section's content
+pub fn hash_content(content: &str) -> String {
+    use sha2::{Digest, Sha256};
+
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
 /// Track an analyzed file for change detection
 #[allow(dead_code)]
 pub fn track_analyzed_file(conn: &Connection, file_path: &str, content_hash: &str) -> Result<()> {
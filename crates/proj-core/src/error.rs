@@ -0,0 +1,57 @@
+// Error types - conditions callers may want to handle (or map to exit codes) rather than just print
+
+use thiserror::Error;
+
+use crate::database::is_busy;
+
+/// Failures with a known cause. Functions still return `anyhow::Result`;
+/// use [`ProjError::find`] to recover the cause from an error.
+#[derive(Debug, Error)]
+pub enum ProjError {
+    /// No `.tracking/` directory in the current directory or any parent
+    #[error("Not in a proj-tracked project (no .tracking/ directory found)")]
+    NotAProject,
+
+    /// The tracking database needs `proj upgrade` before this operation
+    #[error("Schema v{found} is out of date (needs v{required}). Run 'proj upgrade' first.")]
+    SchemaMismatch { found: String, required: String },
+
+    /// Another process held the database lock past the busy timeout
+    #[error("Database is locked by another process. Try again in a moment.")]
+    DbLocked,
+
+    /// The project has no documentation database
+    #[error("No documentation database found. Run 'proj docs init' first.")]
+    DocsDbMissing,
+
+    /// git is not installed, or the project is not a git repository
+    #[error("Git unavailable: {0}")]
+    GitUnavailable(String),
+
+    /// An argument or the item it names is invalid (unknown ID, bad date, unsupported value)
+    #[error("{0}")]
+    InvalidInput(String),
+
+    /// There was nothing to change (no active session to end, no fields to update)
+    #[error("{0}")]
+    NothingToDo(String),
+}
+
+/// SQLite errors carry no ProjError, so busy/locked failures borrow this one
+static DB_LOCKED: ProjError = ProjError::DbLocked;
+
+impl ProjError {
+    /// The ProjError behind an error, looking through any added context.
+    /// SQLite busy/locked failures are reported as [`ProjError::DbLocked`].
+    pub fn find(err: &anyhow::Error) -> Option<&ProjError> {
+        err.chain().find_map(|cause| {
+            if let Some(e) = cause.downcast_ref::<ProjError>() {
+                return Some(e);
+            }
+            match cause.downcast_ref::<rusqlite::Error>() {
+                Some(e) if is_busy(e) => Some(&DB_LOCKED),
+                _ => None,
+            }
+        })
+    }
+}
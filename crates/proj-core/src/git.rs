@@ -0,0 +1,522 @@
+// Git integration - sync recent commits into tracking database
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::{Command, Output};
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+use crate::database::write_transaction;
+use crate::diagnostics;
+use crate::error::ProjError;
+
+/// A git commit record
+pub struct GitCommit {
+    pub hash: String,
+    pub short_hash: String,
+    pub author: String,
+    pub message: String,
+    pub committed_at: String,
+    pub files_changed: i64,
+    pub insertions: i64,
+    pub deletions: i64,
+}
+
+/// Run a git command, failing with ProjError::GitUnavailable if git can't be started
+pub fn run(cmd: &mut Command) -> Result<Output> {
+    diagnostics::output(cmd).map_err(|e| ProjError::GitUnavailable(e.to_string()).into())
+}
+
+/// Whether a directory is inside a git work tree (the repository may start higher up,
+/// as with a package in a monorepo)
+pub fn is_repo(path: &Path) -> bool {
+    path.ancestors().any(|dir| dir.join(".git").exists())
+}
+
+/// Pathspec limiting git to a project nested below the repository root, so its
+/// commits and diffs leave out the rest of the repository. Empty at the root.
+pub fn path_scope(project_root: &Path) -> &'static [&'static str] {
+    if project_root.join(".git").exists() {
+        &[]
+    } else {
+        &["--", "."]
+    }
+}
+
+/// Sync recent git commits into the tracking database.
+/// Uses INSERT OR IGNORE to be idempotent.
+pub fn sync_recent_commits(conn: &Connection, project_root: &Path, limit: usize) -> Result<()> {
+    record_commits(conn, project_root, &[&format!("-{}", limit)])
+}
+
+/// Record a single commit (e.g., "HEAD" from the post-commit hook)
+pub fn record_commit(conn: &Connection, project_root: &Path, rev: &str) -> Result<()> {
+    record_commits(conn, project_root, &["-1", rev])
+}
+
+/// Run git log with the given revision arguments and insert the commits, with the
+/// files each one changed and the tasks it names (schema v1.16)
+fn record_commits(conn: &Connection, project_root: &Path, revs: &[&str]) -> Result<()> {
+    if !is_repo(project_root) {
+        return Ok(());
+    }
+
+    // One record per commit: fields split by \x1f after a \x1e marker, then --numstat lines.
+    // --relative keeps paths relative to a project nested below the repository root.
+    let mut args = vec!["log"];
+    args.extend_from_slice(revs);
+    args.extend([
+        "--format=%x1e%H%x1f%h%x1f%an%x1f%ai%x1f%B%x1f",
+        "--numstat",
+        "--relative",
+    ]);
+    args.extend_from_slice(path_scope(project_root));
+    let output = diagnostics::output(Command::new("git").args(&args).current_dir(project_root));
+
+    let output = match output {
+        Ok(o) if o.status.success() => o,
+        _ => return Ok(()), // Git not available or not a repo, silently skip
+    };
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let commits = parse_git_log_output(&stdout);
+
+    // Databases from before v1.16 only get the commit rows
+    let detailed: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='git_commit_files')",
+        [],
+        |row| row.get(0),
+    )?;
+    let branch = if detailed {
+        current_branch(project_root)
+    } else {
+        None
+    };
+
+    let tx = write_transaction(conn)?;
+    // Oldest first, so commit IDs follow history
+    for parsed in commits.iter().rev() {
+        let commit = &parsed.commit;
+        // Insert into git_commits (ignore duplicates by hash)
+        if detailed {
+            tx.execute(
+                "INSERT OR IGNORE INTO git_commits (hash, short_hash, author, message, committed_at, files_changed, insertions, deletions, branch)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+                rusqlite::params![
+                    commit.hash,
+                    commit.short_hash,
+                    commit.author,
+                    commit.message,
+                    commit.committed_at,
+                    commit.files_changed,
+                    commit.insertions,
+                    commit.deletions,
+                    branch,
+                ],
+            )?;
+        } else {
+            tx.execute(
+                "INSERT OR IGNORE INTO git_commits (hash, short_hash, author, message, committed_at, files_changed, insertions, deletions)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)",
+                rusqlite::params![
+                    commit.hash,
+                    commit.short_hash,
+                    commit.author,
+                    commit.message,
+                    commit.committed_at,
+                    commit.files_changed,
+                    commit.insertions,
+                    commit.deletions,
+                ],
+            )?;
+            continue;
+        }
+
+        // Commits recorded before the upgrade get their files and task links filled in here
+        let commit_id: i64 = tx.query_row(
+            "SELECT commit_id FROM git_commits WHERE hash = ?1",
+            [&commit.hash],
+            |row| row.get(0),
+        )?;
+        for file in &parsed.files {
+            tx.execute(
+                "INSERT OR IGNORE INTO git_commit_files (commit_id, path, insertions, deletions)
+                 VALUES (?1, ?2, ?3, ?4)",
+                rusqlite::params![commit_id, file.path, file.insertions, file.deletions],
+            )?;
+        }
+        for task_id in &parsed.task_ids {
+            tx.execute(
+                "INSERT OR IGNORE INTO git_commit_tasks (commit_id, task_id)
+                 SELECT ?1, task_id FROM tasks WHERE task_id = ?2",
+                [commit_id, *task_id],
+            )?;
+        }
+    }
+    tx.commit()?;
+
+    Ok(())
+}
+
+/// Full hashes of the commits reachable from a branch (None if git can't resolve it)
+pub fn branch_commits(project_root: &Path, branch: &str) -> Option<HashSet<String>> {
+    let output = diagnostics::output(
+        Command::new("git")
+            .args(["log", "--format=%H", branch, "--"])
+            .current_dir(project_root),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .collect(),
+    )
+}
+
+/// Name of the checked-out branch (None when detached or not a git repo)
+pub fn current_branch(project_root: &Path) -> Option<String> {
+    let output = diagnostics::output(
+        Command::new("git")
+            .args(["symbolic-ref", "--quiet", "--short", "HEAD"])
+            .current_dir(project_root),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!branch.is_empty()).then_some(branch)
+}
+
+/// Who is working here: git user.name (or user.email), else the OS user name
+pub fn current_author(project_root: &Path) -> Option<String> {
+    for key in ["user.name", "user.email"] {
+        let output = diagnostics::output(
+            Command::new("git")
+                .args(["config", key])
+                .current_dir(project_root),
+        );
+        if let Ok(output) = output {
+            let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
+            if output.status.success() && !value.is_empty() {
+                return Some(value);
+            }
+        }
+    }
+    ["USER", "USERNAME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|user| !user.trim().is_empty())
+}
+
+/// URL of a remote (e.g. "origin"), if the repo has one
+pub fn remote_url(project_root: &Path, remote: &str) -> Option<String> {
+    let output = diagnostics::output(
+        Command::new("git")
+            .args(["remote", "get-url", remote])
+            .current_dir(project_root),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let url = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!url.is_empty()).then_some(url)
+}
+
+/// Last commit made before a UTC timestamp ("YYYY-MM-DD HH:MM:SS"), if any
+pub fn commit_before(project_root: &Path, before: &str) -> Option<String> {
+    let output = diagnostics::output(
+        Command::new("git")
+            .args([
+                "rev-list",
+                "-1",
+                &format!("--before={} +0000", before),
+                "HEAD",
+            ])
+            .current_dir(project_root),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!hash.is_empty()).then_some(hash)
+}
+
+/// Git's empty tree, used as the diff base when nothing was committed before
+const EMPTY_TREE: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Zero-context diff of the working tree against a base commit (None = everything)
+pub fn diff_since(project_root: &Path, base: Option<&str>) -> Option<String> {
+    let output = diagnostics::output(
+        Command::new("git")
+            .args(["diff", "-U0", "--no-color", base.unwrap_or(EMPTY_TREE)])
+            .args(match path_scope(project_root) {
+                [] => &["--"][..],
+                scope => scope,
+            })
+            .current_dir(project_root),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Whether a commit is still in the repository (not rewritten away or garbage collected)
+pub fn commit_exists(project_root: &Path, hash: &str) -> bool {
+    diagnostics::output(
+        Command::new("git")
+            .args(["cat-file", "-e", &format!("{}^{{commit}}", hash)])
+            .current_dir(project_root),
+    )
+    .is_ok_and(|o| o.status.success())
+}
+
+/// Contents of a file at a given commit (None if it didn't exist there)
+pub fn file_at(project_root: &Path, rev: &str, path: &str) -> Option<String> {
+    let output = diagnostics::output(
+        Command::new("git")
+            .args(["show", &format!("{}:{}", rev, path)])
+            .current_dir(project_root),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// A commit as read from git log, before it's stored
+struct ParsedCommit {
+    commit: GitCommit,
+    files: Vec<FileChange>,
+    /// Task IDs the message names (see `task_refs`)
+    task_ids: Vec<i64>,
+}
+
+/// One file a commit changed (binary files count 0 lines)
+struct FileChange {
+    path: String,
+    insertions: i64,
+    deletions: i64,
+}
+
+/// Parse the output of git log in the format `record_commits` asks for
+fn parse_git_log_output(output: &str) -> Vec<ParsedCommit> {
+    let mut commits = Vec::new();
+
+    for record in output.split('\x1e') {
+        let fields: Vec<&str> = record.splitn(6, '\x1f').collect();
+        let [hash, short_hash, author, date, body, numstat] = fields[..] else {
+            continue;
+        };
+        let hash = hash.trim().to_string();
+
+        // Validate this looks like a commit hash (40 hex chars)
+        if hash.len() != 40 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            continue;
+        }
+
+        // Parse the date - git outputs "2024-01-15 10:30:00 -0600", we want "2024-01-15 10:30:00"
+        let committed_at = date
+            .split_whitespace()
+            .take(2)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        let files: Vec<FileChange> = numstat.lines().filter_map(parse_numstat).collect();
+        commits.push(ParsedCommit {
+            commit: GitCommit {
+                hash,
+                short_hash: short_hash.trim().to_string(),
+                author: author.trim().to_string(),
+                message: body.lines().next().unwrap_or_default().trim().to_string(),
+                committed_at,
+                files_changed: files.len() as i64,
+                insertions: files.iter().map(|f| f.insertions).sum(),
+                deletions: files.iter().map(|f| f.deletions).sum(),
+            },
+            files,
+            task_ids: task_refs(body),
+        });
+    }
+
+    commits
+}
+
+/// Parse a --numstat line like "10\t2\tsrc/main.rs" ("-" counts for binary files).
+/// Renames show as the new path.
+fn parse_numstat(line: &str) -> Option<FileChange> {
+    let mut parts = line.splitn(3, '\t');
+    let insertions = parts.next()?.trim();
+    let deletions = parts.next()?;
+    let path = parts.next()?.trim();
+    if path.is_empty() {
+        return None;
+    }
+    Some(FileChange {
+        path: renamed_path(path),
+        insertions: insertions.parse().unwrap_or(0),
+        deletions: deletions.trim().parse().unwrap_or(0),
+    })
+}
+
+/// New path of a numstat rename: "old => new" or "src/{old => new}/file"
+fn renamed_path(path: &str) -> String {
+    if !path.contains(" => ") {
+        return path.to_string();
+    }
+    if let (Some(open), Some(close)) = (path.find('{'), path.rfind('}')) {
+        if open < close {
+            let inner = &path[open + 1..close];
+            let new = inner.split(" => ").nth(1).unwrap_or(inner);
+            return format!("{}{}{}", &path[..open], new, &path[close + 1..])
+                .replace("//", "/")
+                .trim_start_matches('/')
+                .to_string();
+        }
+    }
+    path.split(" => ").nth(1).unwrap_or(path).to_string()
+}
+
+/// Task IDs a commit message names, in order, without repeats: `#task-12` or `#t12`
+/// anywhere, or a `Proj-Task: 12, 14` trailer line
+pub fn task_refs(message: &str) -> Vec<i64> {
+    let mut ids = Vec::new();
+    let mut add = |id: i64| {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    };
+
+    for line in message.lines() {
+        if let Some((key, value)) = line.split_once(':') {
+            if key.trim().eq_ignore_ascii_case("proj-task") {
+                value
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter_map(|v| v.trim_start_matches('#').parse().ok())
+                    .for_each(&mut add);
+                continue;
+            }
+        }
+        for prefix in ["#task-", "#t"] {
+            for (pos, _) in line.match_indices(prefix) {
+                let rest = &line[pos + prefix.len()..];
+                let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+                // A letter or digit right after the number means it's some other word
+                let whole = !matches!(
+                    rest[digits.len()..].chars().next(),
+                    Some(c) if c.is_alphanumeric() || c == '_'
+                );
+                if whole {
+                    if let Ok(id) = digits.parse() {
+                        add(id);
+                    }
+                }
+            }
+        }
+    }
+    ids
+}
+
+/// Get recent commits from the database
+pub fn get_recent_commits(conn: &Connection, limit: usize) -> Result<Vec<GitCommit>> {
+    let mut stmt = conn.prepare(
+        "SELECT hash, short_hash, author, message, committed_at, files_changed, insertions, deletions
+         FROM git_commits
+         ORDER BY committed_at DESC
+         LIMIT ?1",
+    )?;
+
+    let commits = stmt
+        .query_map([limit as i64], |row| {
+            Ok(GitCommit {
+                hash: row.get(0)?,
+                short_hash: row.get(1)?,
+                author: row.get(2)?,
+                message: row.get(3)?,
+                committed_at: row.get(4)?,
+                files_changed: row.get(5)?,
+                insertions: row.get(6)?,
+                deletions: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(commits)
+}
+
+/// Get commits since a given datetime (for session activity)
+pub fn get_commits_since(conn: &Connection, since: &str) -> Result<Vec<GitCommit>> {
+    let mut stmt = conn.prepare(
+        "SELECT hash, short_hash, author, message, committed_at, files_changed, insertions, deletions
+         FROM git_commits
+         WHERE committed_at >= ?1
+         ORDER BY committed_at ASC",
+    )?;
+
+    let commits = stmt
+        .query_map([since], |row| {
+            Ok(GitCommit {
+                hash: row.get(0)?,
+                short_hash: row.get(1)?,
+                author: row.get(2)?,
+                message: row.get(3)?,
+                committed_at: row.get(4)?,
+                files_changed: row.get(5)?,
+                insertions: row.get(6)?,
+                deletions: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(commits)
+}
+
+/// Get count of commits since a given datetime
+pub fn get_commit_count_since(conn: &Connection, since: &str) -> Result<i64> {
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM git_commits WHERE committed_at >= ?1",
+        [since],
+        |row| row.get(0),
+    )?;
+    Ok(count)
+}
+
+/// Search git commits by message, optionally only those on or after `since` (YYYY-MM-DD)
+pub fn search_git_commits(
+    conn: &Connection,
+    query: &str,
+    since: Option<&str>,
+    limit: usize,
+) -> Result<Vec<(i64, String, String, String)>> {
+    let pattern = format!("%{}%", query);
+    let mut stmt = conn.prepare(
+        "SELECT commit_id, short_hash, message, committed_at
+         FROM git_commits
+         WHERE message LIKE ?1 AND (?2 IS NULL OR committed_at >= ?2)
+         ORDER BY committed_at DESC
+         LIMIT ?3",
+    )?;
+
+    let results = stmt
+        .query_map(rusqlite::params![pattern, since, limit as i64], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(results)
+}
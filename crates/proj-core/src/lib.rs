@@ -0,0 +1,43 @@
+//! proj-core - typed access to proj tracking data
+//!
+//! The library behind the `proj` CLI. Editor plugins, bots, and other Rust
+//! tools can read and write a project's tracking database through these
+//! modules instead of shelling out to `proj` and parsing its output.
+//!
+//! ```no_run
+//! use proj_core::{database, paths, session};
+//!
+//! # fn main() -> anyhow::Result<()> {
+//! let conn = database::open_database(&paths::get_tracking_db_path()?)?;
+//! if let Some(active) = session::get_active_session(&conn)? {
+//!     println!("Session {} started {}", active.session_id, active.started_at);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Databases created or changed through this crate stay fully compatible
+//! with the CLI, as long as both agree on [`SCHEMA_VERSION`].
+
+pub mod api_reference;
+pub mod config;
+pub mod database;
+pub mod diagnostics;
+pub mod docs_db;
+pub mod error;
+pub mod git;
+pub mod models;
+pub mod paths;
+pub mod projignore;
+pub mod repository;
+pub mod schema;
+pub mod schema_docs;
+pub mod session;
+pub mod source_analyzer;
+
+pub use error::ProjError;
+
+/// Tracking database schema this crate reads and writes
+pub const SCHEMA_VERSION: &str = "1.22";
+/// Oldest schema `proj upgrade` can migrate from
+pub const MIN_SCHEMA_VERSION: &str = "1.0";
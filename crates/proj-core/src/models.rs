@@ -12,9 +12,11 @@ pub struct Session {
     pub agent: Option<String>,
     pub summary: Option<String>,
     pub files_touched: Option<String>, // JSON array
-    pub status: String,                // active, completed, abandoned
+    pub status: String,                // active, paused, completed, abandoned
     pub full_context_shown: bool,
     pub structured_summary: Option<String>, // JSON structured summary (v1.4)
+    pub name: Option<String>,               // Parallel session label (v1.8)
+    pub resumed_at: Option<DateTime<Utc>>,  // Last resume after a pause (v1.8)
 }
 
 /// Decision record
@@ -32,7 +34,7 @@ pub struct Decision {
 }
 
 /// Task record
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Task {
     pub task_id: i64,
     pub session_id: Option<i64>,
@@ -44,6 +46,41 @@ pub struct Task {
     pub blocked_by: Option<String>,
     pub parent_task_id: Option<i64>,
     pub notes: Option<String>,
+    pub due: Option<String>, // YYYY-MM-DD (v1.5)
+}
+
+/// Tasks due within this many days count as "due soon"
+pub const DUE_SOON_DAYS: i64 = 3;
+
+/// How a task's due date relates to today
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DueStatus {
+    Overdue,
+    DueSoon,
+    Upcoming,
+    NoDueDate,
+}
+
+impl Task {
+    /// Classify the task's due date relative to today (UTC)
+    pub fn due_status(&self) -> DueStatus {
+        let Some(due) = self
+            .due
+            .as_deref()
+            .and_then(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").ok())
+        else {
+            return DueStatus::NoDueDate;
+        };
+
+        let today = Utc::now().date_naive();
+        if due < today {
+            DueStatus::Overdue
+        } else if (due - today).num_days() <= DUE_SOON_DAYS {
+            DueStatus::DueSoon
+        } else {
+            DueStatus::Upcoming
+        }
+    }
 }
 
 /// Blocker record
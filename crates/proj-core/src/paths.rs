@@ -0,0 +1,161 @@
+// Path utilities - Full implementation in Task #8
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::error::ProjError;
+
+/// Environment variable naming the directory to look for a project in instead of the
+/// current directory (also set by the global `--project` flag)
+pub const PROJECT_ROOT_ENV: &str = "PROJ_PROJECT_ROOT";
+
+/// Gets the directory project lookup starts from: $PROJ_PROJECT_ROOT if set,
+/// otherwise the current directory
+pub fn get_start_dir() -> Result<PathBuf> {
+    let cwd = std::env::current_dir()?;
+    match std::env::var_os(PROJECT_ROOT_ENV).filter(|v| !v.is_empty()) {
+        Some(dir) => Ok(cwd.join(dir)),
+        None => Ok(cwd),
+    }
+}
+
+/// Gets the project root directory by looking for .tracking/
+pub fn get_project_root() -> Result<PathBuf> {
+    let start = get_start_dir()?;
+    let mut current = start.clone();
+
+    loop {
+        let tracking_dir = current.join(".tracking");
+        if tracking_dir.exists() && tracking_dir.is_dir() {
+            return Ok(current);
+        }
+
+        if !current.pop() {
+            let err = anyhow::Error::from(ProjError::NotAProject);
+            return Err(if std::env::var_os(PROJECT_ROOT_ENV).is_some() {
+                err.context(format!("No project at or above {}", start.display()))
+            } else {
+                err
+            });
+        }
+    }
+}
+
+/// Gets the path to the tracking database
+pub fn get_tracking_db_path() -> Result<PathBuf> {
+    let root = get_project_root()?;
+    Ok(root.join(".tracking").join("tracking.db"))
+}
+
+/// Gets the path to the project config
+pub fn get_config_path() -> Result<PathBuf> {
+    let root = get_project_root()?;
+    Ok(root.join(".tracking").join("config.json"))
+}
+
+/// Gets the global proj directory (~/.proj/)
+pub fn get_global_dir() -> Result<PathBuf> {
+    let home = dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?;
+    Ok(home.join(".proj"))
+}
+
+/// Gets the path to the global registry
+pub fn get_registry_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("registry.json"))
+}
+
+/// Gets the path to the global config defaults that project configs inherit
+pub fn get_global_config_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("config.json"))
+}
+
+/// Gets the backups directory
+pub fn get_backups_dir() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("backups"))
+}
+
+/// Gets the directory `proj uninstall` saves project bundles in before deleting data
+pub fn get_exports_dir() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("exports"))
+}
+
+/// Gets the directory .tracking/ directories are moved to instead of being deleted
+pub fn get_trash_dir() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("trash"))
+}
+
+/// Gets the path to the global backup settings (remote targets, retention)
+pub fn get_backup_config_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("backup.json"))
+}
+
+/// Gets the path to the global embedding provider settings
+pub fn get_embeddings_config_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("embeddings.json"))
+}
+
+/// Gets the path to the global LLM provider settings
+pub fn get_llm_config_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("llm.json"))
+}
+
+/// Gets the path to a user's own AGENTS.md instructions, used instead of the built-in ones
+pub fn get_agents_template_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("AGENTS.template.md"))
+}
+
+/// Gets the path to the global issue tracker credentials
+pub fn get_integrations_config_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("integrations.json"))
+}
+
+/// Gets the diagnostics log directory (.tracking/logs, or ~/.proj/logs outside a project)
+pub fn get_logs_dir() -> Result<PathBuf> {
+    match get_project_root() {
+        Ok(root) => Ok(root.join(".tracking").join("logs")),
+        Err(_) => Ok(get_global_dir()?.join("logs")),
+    }
+}
+
+/// Gets the directory `proj status` and `proj context` cache their output in
+pub fn get_cache_dir() -> Result<PathBuf> {
+    Ok(get_project_root()?.join(".tracking").join("cache"))
+}
+
+/// Gets the path to the global self-update settings (channel, deferral)
+pub fn get_update_config_path() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("update.json"))
+}
+
+/// Gets the pending update directory for auto-update staging
+pub fn get_pending_update_dir() -> Result<PathBuf> {
+    Ok(get_global_dir()?.join("pending_update"))
+}
+
+/// Whether two paths name the same location, as stored in the registry.
+/// On Windows the comparison ignores case, separator style, and the `\\?\` prefix
+/// that canonicalize() adds; elsewhere only a trailing separator is ignored.
+pub fn same_path(a: &Path, b: &Path) -> bool {
+    path_key(a) == path_key(b)
+}
+
+fn path_key(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    if cfg!(windows) {
+        let path = path
+            .strip_prefix(r"\\?\")
+            .unwrap_or(&path)
+            .replace('/', "\\");
+        path.trim_end_matches('\\').to_lowercase()
+    } else {
+        path.trim_end_matches('/').to_string()
+    }
+}
+
+/// Ensures a directory exists, creating it if necessary
+pub fn ensure_dir(path: &PathBuf) -> Result<()> {
+    if !path.exists() {
+        std::fs::create_dir_all(path)?;
+    }
+    Ok(())
+}
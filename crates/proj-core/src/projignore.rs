@@ -0,0 +1,57 @@
+// .projignore - per-project exclusions for source analysis and TODO scanning
+//
+// Same syntax as .gitignore, read from the project root. Patterns are
+// relative to the root, so "vendor/" skips vendor directories anywhere and
+// "/third_party" only the top-level one.
+
+use std::path::{Path, PathBuf};
+
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use tracing::warn;
+
+/// Name of the ignore file in the project root
+pub const PROJIGNORE_FILE: &str = ".projignore";
+
+/// Patterns from a project's .projignore (empty when there is none)
+pub struct ProjIgnore {
+    root: PathBuf,
+    matcher: Gitignore,
+}
+
+impl ProjIgnore {
+    /// Load `.projignore` from the project root. A missing file ignores
+    /// nothing; invalid patterns are skipped with a warning.
+    pub fn load(project_root: &Path) -> Self {
+        let mut builder = GitignoreBuilder::new(project_root);
+        let path = project_root.join(PROJIGNORE_FILE);
+        if path.is_file() {
+            if let Some(err) = builder.add(&path) {
+                warn!("{}: {}", path.display(), err);
+            }
+        }
+        let matcher = builder.build().unwrap_or_else(|err| {
+            warn!("{}: {}", path.display(), err);
+            Gitignore::empty()
+        });
+
+        ProjIgnore {
+            root: project_root.to_path_buf(),
+            matcher,
+        }
+    }
+
+    /// Whether a path (absolute, or relative to the project root) is excluded,
+    /// either directly or because one of its parent directories is
+    pub fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.matcher.is_empty() {
+            return false;
+        }
+        let relative = path.strip_prefix(&self.root).unwrap_or(path);
+        if relative.as_os_str().is_empty() || relative.is_absolute() {
+            return false;
+        }
+        self.matcher
+            .matched_path_or_any_parents(relative, is_dir)
+            .is_ignore()
+    }
+}
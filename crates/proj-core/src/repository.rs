@@ -0,0 +1,269 @@
+// Repository - the queries `proj status` and `proj resume` build their output from
+//
+// A Repository reads inside one transaction, so everything a command shows comes from
+// the same snapshot of the database, and each query is prepared once per connection
+// (prepare_cached) however many times it runs. Active tasks and blockers are loaded at
+// most once: later calls, including the priority-task and blocker-count helpers, reuse
+// the first result instead of scanning the tables again.
+
+use std::cell::OnceCell;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::{Connection, Row, Transaction};
+
+use crate::models::{Blocker, ContextNote, Decision, Question, Task};
+
+const TASK_COLUMNS: &str = "task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes, due";
+
+/// Overdue, then due soon, then by priority, then oldest first
+const TASK_ORDER: &str = "ORDER BY
+           CASE WHEN due < date('now') THEN 0 WHEN due <= date('now', '+3 days') THEN 1 ELSE 2 END,
+           CASE priority WHEN 'urgent' THEN 1 WHEN 'high' THEN 2 WHEN 'normal' THEN 3 ELSE 4 END,
+           created_at";
+
+/// Read access to a project's tracking data for the length of one command
+pub struct Repository<'a> {
+    tx: Transaction<'a>,
+    active_tasks: OnceCell<Vec<Task>>,
+    active_blockers: OnceCell<Vec<Blocker>>,
+}
+
+impl<'a> Repository<'a> {
+    /// Start reading. Nothing is locked until the first query; the snapshot is
+    /// released by `finish` or when the repository is dropped.
+    pub fn begin(conn: &'a Connection) -> Result<Self> {
+        Ok(Self {
+            tx: conn.unchecked_transaction()?,
+            active_tasks: OnceCell::new(),
+            active_blockers: OnceCell::new(),
+        })
+    }
+
+    /// End the read transaction
+    pub fn finish(self) -> Result<()> {
+        self.tx.commit()?;
+        Ok(())
+    }
+
+    /// The connection, for queries that aren't part of the repository
+    pub fn conn(&self) -> &Connection {
+        &self.tx
+    }
+
+    /// Tasks that aren't completed or cancelled, in priority order
+    pub fn active_tasks(&self) -> Result<&[Task]> {
+        if let Some(tasks) = self.active_tasks.get() {
+            return Ok(tasks);
+        }
+        let mut stmt = self.tx.prepare_cached(&format!(
+            "SELECT {} FROM tasks WHERE status NOT IN ('completed', 'cancelled') {}",
+            TASK_COLUMNS, TASK_ORDER
+        ))?;
+        let tasks = stmt
+            .query_map([], task_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(self.active_tasks.get_or_init(|| tasks))
+    }
+
+    /// The first `limit` pending, in-progress, or blocked tasks, in priority order
+    pub fn priority_tasks(&self, limit: usize) -> Result<Vec<Task>> {
+        self.first_tasks(&["pending", "in_progress", "blocked"], limit)
+    }
+
+    /// The task to work on next: the first pending or in-progress one in priority order
+    pub fn next_task(&self) -> Result<Option<Task>> {
+        Ok(self
+            .first_tasks(&["pending", "in_progress"], 1)?
+            .into_iter()
+            .next())
+    }
+
+    /// The first `limit` tasks with one of these statuses. Taken from the active tasks
+    /// when they're loaded; otherwise only those rows are read.
+    fn first_tasks(&self, statuses: &[&str], limit: usize) -> Result<Vec<Task>> {
+        if let Some(tasks) = self.active_tasks.get() {
+            return Ok(tasks
+                .iter()
+                .filter(|t| statuses.contains(&t.status.as_str()))
+                .take(limit)
+                .cloned()
+                .collect());
+        }
+        let list = statuses
+            .iter()
+            .map(|s| format!("'{}'", s))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut stmt = self.tx.prepare_cached(&format!(
+            "SELECT {} FROM tasks WHERE status IN ({}) {} LIMIT ?1",
+            TASK_COLUMNS, list, TASK_ORDER
+        ))?;
+        let tasks = stmt
+            .query_map([limit as i64], task_from_row)?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(tasks)
+    }
+
+    /// Active blockers, newest first
+    pub fn active_blockers(&self) -> Result<&[Blocker]> {
+        if let Some(blockers) = self.active_blockers.get() {
+            return Ok(blockers);
+        }
+        let mut stmt = self.tx.prepare_cached(
+            "SELECT blocker_id, session_id, created_at, resolved_at, description, status, resolution, related_task_id
+             FROM blockers
+             WHERE status = 'active'
+             ORDER BY created_at DESC",
+        )?;
+        let blockers = stmt
+            .query_map([], |row| {
+                Ok(Blocker {
+                    blocker_id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    created_at: parse_datetime(row.get::<_, String>(2)?),
+                    resolved_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
+                    description: row.get(4)?,
+                    status: row.get(5)?,
+                    resolution: row.get(6)?,
+                    related_task_id: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(self.active_blockers.get_or_init(|| blockers))
+    }
+
+    /// Number of active blockers, counted from the loaded list when there is one
+    pub fn active_blocker_count(&self) -> Result<i64> {
+        if let Some(blockers) = self.active_blockers.get() {
+            return Ok(blockers.len() as i64);
+        }
+        let mut stmt = self
+            .tx
+            .prepare_cached("SELECT COUNT(*) FROM blockers WHERE status = 'active'")?;
+        Ok(stmt.query_row([], |row| row.get(0))?)
+    }
+
+    /// Open and overdue task counts
+    pub fn open_task_counts(&self) -> Result<(i64, i64)> {
+        let mut stmt = self.tx.prepare_cached(
+            "SELECT COUNT(*), COALESCE(SUM(due < date('now')), 0) FROM tasks
+             WHERE status NOT IN ('completed', 'cancelled')",
+        )?;
+        Ok(stmt.query_row([], |row| Ok((row.get(0)?, row.get(1)?)))?)
+    }
+
+    /// The `limit` newest active decisions
+    pub fn recent_decisions(&self, limit: usize) -> Result<Vec<Decision>> {
+        let mut stmt = self.tx.prepare_cached(
+            "SELECT decision_id, session_id, created_at, topic, decision, rationale, alternatives, status, superseded_by
+             FROM decisions
+             WHERE status = 'active'
+             ORDER BY created_at DESC
+             LIMIT ?1",
+        )?;
+        let decisions = stmt
+            .query_map([limit as i64], |row| {
+                Ok(Decision {
+                    decision_id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    created_at: parse_datetime(row.get::<_, String>(2)?),
+                    topic: row.get(3)?,
+                    decision: row.get(4)?,
+                    rationale: row.get(5)?,
+                    alternatives: row.get(6)?,
+                    status: row.get(7)?,
+                    superseded_by: row.get(8)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(decisions)
+    }
+
+    /// Open questions, newest first
+    pub fn open_questions(&self) -> Result<Vec<Question>> {
+        let mut stmt = self.tx.prepare_cached(
+            "SELECT question_id, session_id, created_at, answered_at, question, context, answer, status
+             FROM questions
+             WHERE status = 'open'
+             ORDER BY created_at DESC",
+        )?;
+        let questions = stmt
+            .query_map([], |row| {
+                Ok(Question {
+                    question_id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    created_at: parse_datetime(row.get::<_, String>(2)?),
+                    answered_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
+                    question: row.get(4)?,
+                    context: row.get(5)?,
+                    answer: row.get(6)?,
+                    status: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(questions)
+    }
+
+    /// Number of active context notes in each category, largest first
+    pub fn note_category_counts(&self) -> Result<Vec<(String, i64)>> {
+        let mut stmt = self.tx.prepare_cached(
+            "SELECT category, COUNT(*) FROM context_notes
+             WHERE status = 'active'
+             GROUP BY category
+             ORDER BY COUNT(*) DESC, category",
+        )?;
+        let counts = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(counts)
+    }
+
+    /// Active context notes, grouped by category
+    pub fn active_context_notes(&self) -> Result<Vec<ContextNote>> {
+        let mut stmt = self.tx.prepare_cached(
+            "SELECT note_id, session_id, created_at, updated_at, category, title, content, status
+             FROM context_notes
+             WHERE status = 'active'
+             ORDER BY category, created_at",
+        )?;
+        let notes = stmt
+            .query_map([], |row| {
+                Ok(ContextNote {
+                    note_id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    created_at: parse_datetime(row.get::<_, String>(2)?),
+                    updated_at: parse_datetime(row.get::<_, String>(3)?),
+                    category: row.get(4)?,
+                    title: row.get(5)?,
+                    content: row.get(6)?,
+                    status: row.get(7)?,
+                })
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(notes)
+    }
+}
+
+fn task_from_row(row: &Row) -> rusqlite::Result<Task> {
+    Ok(Task {
+        task_id: row.get(0)?,
+        session_id: row.get(1)?,
+        created_at: parse_datetime(row.get::<_, String>(2)?),
+        completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
+        description: row.get(4)?,
+        status: row.get(5)?,
+        priority: row.get(6)?,
+        blocked_by: row.get(7)?,
+        parent_task_id: row.get(8)?,
+        notes: row.get(9)?,
+        due: row.get(10)?,
+    })
+}
+
+/// Parse datetime string from SQLite
+fn parse_datetime(s: String) -> DateTime<Utc> {
+    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| dt.and_utc())
+        .unwrap_or_else(|_| Utc::now())
+}
@@ -0,0 +1,982 @@
+// SQL schemas - Full implementation in Task #7
+
+/// Tracking database schema (embedded as const)
+pub const TRACKING_SCHEMA: &str = r#"
+-- Project metadata
+CREATE TABLE IF NOT EXISTS project_meta (
+    key TEXT PRIMARY KEY,
+    value TEXT,
+    updated_at TEXT DEFAULT (datetime('now'))
+);
+
+-- Sessions
+CREATE TABLE IF NOT EXISTS sessions (
+    session_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    started_at TEXT DEFAULT (datetime('now')),
+    ended_at TEXT,
+    agent TEXT,
+    summary TEXT,
+    files_touched TEXT,
+    status TEXT DEFAULT 'active',
+    full_context_shown INTEGER DEFAULT 0,
+    structured_summary TEXT,
+    name TEXT,
+    resumed_at TEXT,
+    branch TEXT,
+    author TEXT,
+    uuid TEXT
+);
+
+-- Decisions
+CREATE TABLE IF NOT EXISTS decisions (
+    decision_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    topic TEXT NOT NULL,
+    decision TEXT NOT NULL,
+    rationale TEXT,
+    alternatives TEXT,
+    status TEXT DEFAULT 'active',
+    superseded_by INTEGER,
+    branch TEXT,
+    author TEXT,
+    uuid TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id),
+    FOREIGN KEY (superseded_by) REFERENCES decisions(decision_id)
+);
+
+-- Tasks
+CREATE TABLE IF NOT EXISTS tasks (
+    task_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    completed_at TEXT,
+    description TEXT NOT NULL,
+    status TEXT DEFAULT 'pending',
+    priority TEXT DEFAULT 'normal',
+    blocked_by TEXT,
+    parent_task_id INTEGER,
+    notes TEXT,
+    due TEXT,
+    branch TEXT,
+    source TEXT,
+    source_ref TEXT,
+    author TEXT,
+    uuid TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id),
+    FOREIGN KEY (parent_task_id) REFERENCES tasks(task_id)
+);
+
+-- Blockers
+CREATE TABLE IF NOT EXISTS blockers (
+    blocker_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    resolved_at TEXT,
+    description TEXT NOT NULL,
+    status TEXT DEFAULT 'active',
+    resolution TEXT,
+    related_task_id INTEGER,
+    branch TEXT,
+    author TEXT,
+    uuid TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id),
+    FOREIGN KEY (related_task_id) REFERENCES tasks(task_id)
+);
+
+-- Context notes
+CREATE TABLE IF NOT EXISTS context_notes (
+    note_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    updated_at TEXT DEFAULT (datetime('now')),
+    category TEXT NOT NULL,
+    title TEXT NOT NULL,
+    content TEXT NOT NULL,
+    status TEXT DEFAULT 'active',
+    branch TEXT,
+    author TEXT,
+    uuid TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id)
+);
+
+-- Milestones
+CREATE TABLE IF NOT EXISTS milestones (
+    milestone_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_at TEXT DEFAULT (datetime('now')),
+    target_date TEXT,
+    achieved_at TEXT,
+    name TEXT NOT NULL,
+    description TEXT,
+    status TEXT DEFAULT 'pending'
+);
+
+-- Questions
+CREATE TABLE IF NOT EXISTS questions (
+    question_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    answered_at TEXT,
+    question TEXT NOT NULL,
+    context TEXT,
+    answer TEXT,
+    status TEXT DEFAULT 'open',
+    branch TEXT,
+    uuid TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id)
+);
+
+-- Session references
+CREATE TABLE IF NOT EXISTS session_references (
+    reference_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    title TEXT NOT NULL,
+    url TEXT,
+    type TEXT,
+    notes TEXT,
+    relevance TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id)
+);
+
+-- Activity log
+CREATE TABLE IF NOT EXISTS activity_log (
+    log_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    timestamp TEXT DEFAULT (datetime('now')),
+    action_type TEXT NOT NULL,
+    action_id INTEGER,
+    summary TEXT NOT NULL,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id)
+);
+
+-- Context snapshots for delta tracking
+CREATE TABLE IF NOT EXISTS context_snapshots (
+    snapshot_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    session_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    snapshot_type TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    item_counts TEXT,
+    FOREIGN KEY (session_id) REFERENCES sessions(session_id)
+);
+
+-- Compressed sessions
+CREATE TABLE IF NOT EXISTS compressed_sessions (
+    compression_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_at TEXT DEFAULT (datetime('now')),
+    session_ids TEXT NOT NULL,
+    date_range_start TEXT,
+    date_range_end TEXT,
+    compressed_summary TEXT NOT NULL,
+    original_token_estimate INTEGER,
+    compressed_token_estimate INTEGER
+);
+
+-- Git commits (v1.4)
+CREATE TABLE IF NOT EXISTS git_commits (
+    commit_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    hash TEXT NOT NULL UNIQUE,
+    short_hash TEXT NOT NULL,
+    author TEXT,
+    message TEXT NOT NULL,
+    committed_at TEXT NOT NULL,
+    files_changed INTEGER DEFAULT 0,
+    insertions INTEGER DEFAULT 0,
+    deletions INTEGER DEFAULT 0,
+    synced_at TEXT DEFAULT (datetime('now')),
+    branch TEXT
+);
+
+-- Files each commit changed (v1.16)
+CREATE TABLE IF NOT EXISTS git_commit_files (
+    commit_file_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    commit_id INTEGER NOT NULL REFERENCES git_commits(commit_id) ON DELETE CASCADE,
+    path TEXT NOT NULL,
+    insertions INTEGER DEFAULT 0,
+    deletions INTEGER DEFAULT 0,
+    UNIQUE(commit_id, path)
+);
+
+-- Tasks a commit names in its message: #task-N, #tN, or a Proj-Task: N trailer (v1.16)
+CREATE TABLE IF NOT EXISTS git_commit_tasks (
+    commit_id INTEGER NOT NULL REFERENCES git_commits(commit_id) ON DELETE CASCADE,
+    task_id INTEGER NOT NULL REFERENCES tasks(task_id) ON DELETE CASCADE,
+    PRIMARY KEY(commit_id, task_id)
+);
+
+-- Tags on tasks, decisions, and notes (v1.11); one item reference per row
+CREATE TABLE IF NOT EXISTS item_tags (
+    item_tag_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    tag TEXT NOT NULL,
+    task_id INTEGER,
+    decision_id INTEGER,
+    note_id INTEGER,
+    created_at TEXT DEFAULT (datetime('now')),
+    FOREIGN KEY (task_id) REFERENCES tasks(task_id),
+    FOREIGN KEY (decision_id) REFERENCES decisions(decision_id),
+    FOREIGN KEY (note_id) REFERENCES context_notes(note_id)
+);
+
+-- Embedding vectors for semantic search (v1.12); record_id points into table_name
+-- (decisions, context_notes, or the docs database's sections)
+CREATE TABLE IF NOT EXISTS embeddings (
+    embedding_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    table_name TEXT NOT NULL,
+    record_id INTEGER NOT NULL,
+    model TEXT NOT NULL,
+    content_hash TEXT NOT NULL,
+    dimensions INTEGER NOT NULL,
+    vector BLOB NOT NULL,
+    created_at TEXT DEFAULT (datetime('now')),
+    UNIQUE(table_name, record_id)
+);
+
+-- Links between tasks and issues in external trackers (v1.13); remote_id is the
+-- tracker's own reference, e.g. owner/repo#12 for GitHub
+CREATE TABLE IF NOT EXISTS task_links (
+    link_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER NOT NULL,
+    provider TEXT NOT NULL,
+    remote_id TEXT NOT NULL,
+    url TEXT,
+    remote_state TEXT,
+    local_status TEXT,
+    synced_at TEXT DEFAULT (datetime('now')),
+    UNIQUE(provider, remote_id)
+);
+
+-- Every insert, update, and delete on tracked records (v1.17), written by AUDIT_TRIGGERS.
+-- changes is JSON: the new row for inserts, the old row for deletes, and
+-- {"column": [old, new]} for the columns an update changed
+CREATE TABLE IF NOT EXISTS audit_log (
+    audit_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp TEXT DEFAULT (datetime('now')),
+    table_name TEXT NOT NULL,
+    record_id INTEGER NOT NULL,
+    action TEXT NOT NULL,
+    changes TEXT,
+    session_id INTEGER,
+    author TEXT
+);
+
+-- Commands queued by `agent_writes: approval` until `proj approve` applies or rejects
+-- them (v1.18); args is the command line as a JSON array, without the program name
+CREATE TABLE IF NOT EXISTS pending_writes (
+    pending_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    created_at TEXT DEFAULT (datetime('now')),
+    args TEXT NOT NULL,
+    session_id INTEGER,
+    author TEXT,
+    status TEXT DEFAULT 'pending',
+    resolved_at TEXT,
+    error TEXT
+);
+
+-- Command durations and output sizes, recorded with usage_metrics on (v1.19).
+-- output_tokens is NULL when it couldn't be counted (output to a terminal, Windows)
+CREATE TABLE IF NOT EXISTS command_metrics (
+    metric_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    timestamp TEXT DEFAULT (datetime('now')),
+    command TEXT NOT NULL,
+    flags TEXT,
+    duration_ms INTEGER NOT NULL,
+    output_tokens INTEGER,
+    terminal INTEGER DEFAULT 0,
+    success INTEGER DEFAULT 1
+);
+
+-- Decision topics (v1.20). An alias row points at the topic it was merged or renamed
+-- into (alias_of), so decisions logged under it land there; the others are canonical.
+CREATE TABLE IF NOT EXISTS decision_topics (
+    topic TEXT PRIMARY KEY COLLATE NOCASE,
+    alias_of TEXT COLLATE NOCASE,
+    created_at TEXT DEFAULT (datetime('now'))
+);
+
+-- Docs sections a decision relates to (v1.21). section_id is the section's number in
+-- the docs database; title is what the section was called when the link was made
+CREATE TABLE IF NOT EXISTS decision_sections (
+    decision_id INTEGER NOT NULL,
+    section_id TEXT NOT NULL,
+    title TEXT,
+    PRIMARY KEY (decision_id, section_id),
+    FOREIGN KEY (decision_id) REFERENCES decisions(decision_id) ON DELETE CASCADE
+);
+
+-- Files attached to tracked items (v1.22); one item reference per row. The file is
+-- kept in the database, so backups and exports carry it; sha256 is of content
+CREATE TABLE IF NOT EXISTS attachments (
+    attachment_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    task_id INTEGER,
+    decision_id INTEGER,
+    note_id INTEGER,
+    blocker_id INTEGER,
+    question_id INTEGER,
+    file_name TEXT NOT NULL,
+    size INTEGER NOT NULL,
+    sha256 TEXT NOT NULL,
+    content BLOB NOT NULL,
+    author TEXT,
+    created_at TEXT DEFAULT (datetime('now')),
+    FOREIGN KEY (task_id) REFERENCES tasks(task_id) ON DELETE CASCADE,
+    FOREIGN KEY (decision_id) REFERENCES decisions(decision_id) ON DELETE CASCADE,
+    FOREIGN KEY (note_id) REFERENCES context_notes(note_id) ON DELETE CASCADE,
+    FOREIGN KEY (blocker_id) REFERENCES blockers(blocker_id) ON DELETE CASCADE,
+    FOREIGN KEY (question_id) REFERENCES questions(question_id) ON DELETE CASCADE
+);
+
+-- Indexes
+CREATE INDEX IF NOT EXISTS idx_git_commits_hash ON git_commits(hash);
+CREATE INDEX IF NOT EXISTS idx_git_commits_date ON git_commits(committed_at);
+CREATE INDEX IF NOT EXISTS idx_git_commit_files_path ON git_commit_files(path);
+CREATE INDEX IF NOT EXISTS idx_git_commit_tasks_task ON git_commit_tasks(task_id);
+CREATE INDEX IF NOT EXISTS idx_sessions_status ON sessions(status);
+CREATE INDEX IF NOT EXISTS idx_sessions_started ON sessions(started_at);
+CREATE INDEX IF NOT EXISTS idx_tasks_status ON tasks(status);
+CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags(tag);
+CREATE INDEX IF NOT EXISTS idx_decisions_topic ON decisions(topic);
+CREATE INDEX IF NOT EXISTS idx_context_notes_category ON context_notes(category);
+CREATE INDEX IF NOT EXISTS idx_activity_log_session ON activity_log(session_id);
+CREATE INDEX IF NOT EXISTS idx_activity_log_timestamp ON activity_log(timestamp);
+CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp);
+CREATE INDEX IF NOT EXISTS idx_audit_log_record ON audit_log(table_name, record_id);
+CREATE INDEX IF NOT EXISTS idx_pending_writes_status ON pending_writes(status);
+CREATE INDEX IF NOT EXISTS idx_command_metrics_timestamp ON command_metrics(timestamp);
+CREATE INDEX IF NOT EXISTS idx_decision_topics_alias ON decision_topics(alias_of);
+CREATE INDEX IF NOT EXISTS idx_decision_sections_section ON decision_sections(section_id);
+CREATE INDEX IF NOT EXISTS idx_attachments_decision ON attachments(decision_id);
+CREATE INDEX IF NOT EXISTS idx_context_snapshots_session ON context_snapshots(session_id);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_sessions_uuid ON sessions(uuid);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_uuid ON tasks(uuid);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_decisions_uuid ON decisions(uuid);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_context_notes_uuid ON context_notes(uuid);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_blockers_uuid ON blockers(uuid);
+CREATE UNIQUE INDEX IF NOT EXISTS idx_questions_uuid ON questions(uuid);
+"#;
+
+/// SQL expression for a random (version 4) UUID
+macro_rules! new_uuid {
+    () => {
+        "lower(hex(randomblob(4)) || '-' || hex(randomblob(2)) || '-4' || substr(hex(randomblob(2)), 2) || '-' || substr('89ab', 1 + abs(random()) % 4, 1) || substr(hex(randomblob(2)), 2) || '-' || hex(randomblob(6)))"
+    };
+}
+
+/// Give a table's new rows a UUID unless the insert brought one (imports, merges)
+macro_rules! uuid_trigger {
+    ($table:literal) => {
+        concat!(
+            "CREATE TRIGGER IF NOT EXISTS ",
+            $table,
+            "_uuid_insert AFTER INSERT ON ",
+            $table,
+            " WHEN new.uuid IS NULL BEGIN\n    UPDATE ",
+            $table,
+            " SET uuid = ",
+            new_uuid!(),
+            " WHERE rowid = new.rowid;\nEND;\n"
+        )
+    };
+}
+
+/// Fill in UUIDs for rows that predate them
+macro_rules! uuid_backfill {
+    ($table:literal) => {
+        concat!(
+            "UPDATE ",
+            $table,
+            " SET uuid = ",
+            new_uuid!(),
+            " WHERE uuid IS NULL;\n"
+        )
+    };
+}
+
+/// Triggers that give every session, task, decision, note, blocker, and question a UUID,
+/// the record's identity across databases (row IDs are only meaningful locally)
+pub const UUID_TRIGGERS: &str = concat!(
+    uuid_trigger!("sessions"),
+    uuid_trigger!("tasks"),
+    uuid_trigger!("decisions"),
+    uuid_trigger!("context_notes"),
+    uuid_trigger!("blockers"),
+    uuid_trigger!("questions"),
+);
+
+/// Drop a table's UUID trigger (schema rollback from v1.15)
+macro_rules! uuid_trigger_drop {
+    ($table:literal) => {
+        concat!("DROP TRIGGER IF EXISTS ", $table, "_uuid_insert;\n")
+    };
+}
+
+/// Undo UUID_TRIGGERS
+pub const DROP_UUID_TRIGGERS: &str = concat!(
+    uuid_trigger_drop!("sessions"),
+    uuid_trigger_drop!("tasks"),
+    uuid_trigger_drop!("decisions"),
+    uuid_trigger_drop!("context_notes"),
+    uuid_trigger_drop!("blockers"),
+    uuid_trigger_drop!("questions"),
+);
+
+/// Assign UUIDs to existing rows (schema upgrade to v1.15)
+pub const UUID_BACKFILL: &str = concat!(
+    uuid_backfill!("sessions"),
+    uuid_backfill!("tasks"),
+    uuid_backfill!("decisions"),
+    uuid_backfill!("context_notes"),
+    uuid_backfill!("blockers"),
+    uuid_backfill!("questions"),
+);
+
+/// SQL expression for the active session's ID
+macro_rules! active_session {
+    () => {
+        "(SELECT session_id FROM sessions WHERE status = 'active' ORDER BY session_id DESC LIMIT 1)"
+    };
+}
+
+/// SQL expression for the active session's author
+macro_rules! active_author {
+    () => {
+        "(SELECT author FROM sessions WHERE status = 'active' ORDER BY session_id DESC LIMIT 1)"
+    };
+}
+
+/// Insert, update, and delete triggers that record a table's changes in audit_log. Only the
+/// listed columns are recorded, and an update that touches none of them isn't logged, so
+/// bookkeeping columns (uuid, sync timestamps) stay out. Changes are credited to the active
+/// session and its author; `with_author` credits inserts to the new row's author instead.
+macro_rules! audit_triggers {
+    ($table:literal, $pk:literal, [$($col:literal),+]) => {
+        audit_triggers!(
+            $table,
+            $pk,
+            active_session!(),
+            active_session!(),
+            active_author!(),
+            active_author!(),
+            active_author!(),
+            [$($col),+]
+        )
+    };
+    ($table:literal, $pk:literal, with_author, [$($col:literal),+]) => {
+        audit_triggers!(
+            $table,
+            $pk,
+            active_session!(),
+            active_session!(),
+            concat!("COALESCE(new.author, ", active_author!(), ")"),
+            active_author!(),
+            active_author!(),
+            [$($col),+]
+        )
+    };
+    (
+        $table:literal,
+        $pk:literal,
+        $new_session:expr,
+        $old_session:expr,
+        $insert_author:expr,
+        $update_author:expr,
+        $delete_author:expr,
+        [$first:literal $(, $col:literal)*]
+    ) => {
+        concat!(
+            "CREATE TRIGGER IF NOT EXISTS ", $table, "_audit_insert AFTER INSERT ON ", $table, " BEGIN\n",
+            "    INSERT INTO audit_log (table_name, record_id, action, changes, session_id, author) VALUES ('",
+            $table, "', new.", $pk, ", 'insert', json_object('", $first, "', new.", $first,
+            $(", '", $col, "', new.", $col,)* "), ", $new_session, ", ", $insert_author, ");\nEND;\n",
+            "CREATE TRIGGER IF NOT EXISTS ", $table, "_audit_update AFTER UPDATE ON ", $table,
+            " WHEN old.", $first, " IS NOT new.", $first, $(" OR old.", $col, " IS NOT new.", $col,)* " BEGIN\n",
+            "    INSERT INTO audit_log (table_name, record_id, action, changes, session_id, author) VALUES ('",
+            $table, "', new.", $pk, ", 'update', (SELECT json_group_object(k, json_array(o, n)) FROM (SELECT '",
+            $first, "' AS k, old.", $first, " AS o, new.", $first, " AS n",
+            $(" UNION ALL SELECT '", $col, "', old.", $col, ", new.", $col,)* ") WHERE o IS NOT n), ",
+            $new_session, ", ", $update_author, ");\nEND;\n",
+            "CREATE TRIGGER IF NOT EXISTS ", $table, "_audit_delete AFTER DELETE ON ", $table, " BEGIN\n",
+            "    INSERT INTO audit_log (table_name, record_id, action, changes, session_id, author) VALUES ('",
+            $table, "', old.", $pk, ", 'delete', json_object('", $first, "', old.", $first,
+            $(", '", $col, "', old.", $col,)* "), ", $old_session, ", ", $delete_author, ");\nEND;\n"
+        )
+    };
+}
+
+/// Triggers that record changes to sessions, tasks, decisions, notes, blockers, questions,
+/// milestones, tags, and tracker links in audit_log (the tables audited since v1.17)
+pub const AUDIT_TRIGGERS: &str = concat!(
+    audit_triggers!(
+        "sessions",
+        "session_id",
+        "new.session_id",
+        "old.session_id",
+        "new.author",
+        "new.author",
+        "old.author",
+        [
+            "started_at",
+            "ended_at",
+            "agent",
+            "summary",
+            "files_touched",
+            "status",
+            "structured_summary",
+            "name",
+            "resumed_at",
+            "branch",
+            "author"
+        ]
+    ),
+    audit_triggers!(
+        "tasks",
+        "task_id",
+        with_author,
+        [
+            "session_id",
+            "created_at",
+            "completed_at",
+            "description",
+            "status",
+            "priority",
+            "blocked_by",
+            "parent_task_id",
+            "notes",
+            "due",
+            "branch",
+            "source",
+            "source_ref",
+            "author"
+        ]
+    ),
+    audit_triggers!(
+        "decisions",
+        "decision_id",
+        with_author,
+        [
+            "session_id",
+            "created_at",
+            "topic",
+            "decision",
+            "rationale",
+            "alternatives",
+            "status",
+            "superseded_by",
+            "branch",
+            "author"
+        ]
+    ),
+    audit_triggers!(
+        "context_notes",
+        "note_id",
+        with_author,
+        [
+            "session_id",
+            "created_at",
+            "updated_at",
+            "category",
+            "title",
+            "content",
+            "status",
+            "branch",
+            "author"
+        ]
+    ),
+    audit_triggers!(
+        "blockers",
+        "blocker_id",
+        with_author,
+        [
+            "session_id",
+            "created_at",
+            "resolved_at",
+            "description",
+            "status",
+            "resolution",
+            "related_task_id",
+            "branch",
+            "author"
+        ]
+    ),
+    audit_triggers!(
+        "questions",
+        "question_id",
+        [
+            "session_id",
+            "created_at",
+            "answered_at",
+            "question",
+            "context",
+            "answer",
+            "status",
+            "branch"
+        ]
+    ),
+    audit_triggers!(
+        "milestones",
+        "milestone_id",
+        [
+            "created_at",
+            "target_date",
+            "achieved_at",
+            "name",
+            "description",
+            "status"
+        ]
+    ),
+    audit_triggers!(
+        "item_tags",
+        "item_tag_id",
+        ["tag", "task_id", "decision_id", "note_id", "created_at"]
+    ),
+    audit_triggers!(
+        "task_links",
+        "link_id",
+        [
+            "task_id",
+            "provider",
+            "remote_id",
+            "url",
+            "remote_state",
+            "local_status"
+        ]
+    ),
+);
+
+/// Drop a table's audit triggers (schema rollback from v1.17)
+macro_rules! audit_triggers_drop {
+    ($table:literal) => {
+        concat!(
+            "DROP TRIGGER IF EXISTS ",
+            $table,
+            "_audit_insert;\nDROP TRIGGER IF EXISTS ",
+            $table,
+            "_audit_update;\nDROP TRIGGER IF EXISTS ",
+            $table,
+            "_audit_delete;\n"
+        )
+    };
+}
+
+/// Undo AUDIT_TRIGGERS
+pub const DROP_AUDIT_TRIGGERS: &str = concat!(
+    audit_triggers_drop!("sessions"),
+    audit_triggers_drop!("tasks"),
+    audit_triggers_drop!("decisions"),
+    audit_triggers_drop!("context_notes"),
+    audit_triggers_drop!("blockers"),
+    audit_triggers_drop!("questions"),
+    audit_triggers_drop!("milestones"),
+    audit_triggers_drop!("item_tags"),
+    audit_triggers_drop!("task_links"),
+);
+
+/// Audit triggers for decision topics and aliases (v1.20). topic is the key, so
+/// record_id is the row's rowid
+pub const DECISION_TOPICS_AUDIT_TRIGGERS: &str = audit_triggers!(
+    "decision_topics",
+    "rowid",
+    ["topic", "alias_of", "created_at"]
+);
+
+/// Undo DECISION_TOPICS_AUDIT_TRIGGERS
+pub const DROP_DECISION_TOPICS_AUDIT_TRIGGERS: &str = audit_triggers_drop!("decision_topics");
+
+/// Audit triggers for links from decisions to docs sections (v1.21). The key is
+/// (decision_id, section_id), so record_id is the row's rowid
+pub const DECISION_SECTIONS_AUDIT_TRIGGERS: &str = audit_triggers!(
+    "decision_sections",
+    "rowid",
+    ["decision_id", "section_id", "title"]
+);
+
+/// Undo DECISION_SECTIONS_AUDIT_TRIGGERS
+pub const DROP_DECISION_SECTIONS_AUDIT_TRIGGERS: &str = audit_triggers_drop!("decision_sections");
+
+/// Audit triggers for attachments (v1.22). The file content is left out; sha256 and size
+/// record what it was
+pub const ATTACHMENTS_AUDIT_TRIGGERS: &str = audit_triggers!(
+    "attachments",
+    "attachment_id",
+    with_author,
+    [
+        "task_id",
+        "decision_id",
+        "note_id",
+        "blocker_id",
+        "question_id",
+        "file_name",
+        "size",
+        "sha256",
+        "author",
+        "created_at"
+    ]
+);
+
+/// Undo ATTACHMENTS_AUDIT_TRIGGERS
+pub const DROP_ATTACHMENTS_AUDIT_TRIGGERS: &str = audit_triggers_drop!("attachments");
+
+/// Every set of audit triggers, applied in this order to a new database. A table added
+/// after v1.17 gets its own set, listed here and created by the migration that adds the
+/// table (with the matching drop in the step's down).
+pub const AUDIT_TRIGGER_SETS: &[&str] = &[
+    AUDIT_TRIGGERS,
+    DECISION_TOPICS_AUDIT_TRIGGERS,
+    DECISION_SECTIONS_AUDIT_TRIGGERS,
+    ATTACHMENTS_AUDIT_TRIGGERS,
+];
+
+/// Expands `$m!($args.., table, key column, indexed text)` for each table in the search index
+macro_rules! fts_tables {
+    ($m:ident $(, $arg:literal)*) => {
+        concat!(
+            $m!($($arg,)* "decisions", "decision_id", "topic || ' ' || decision || ' ' || COALESCE(rationale, '')"),
+            $m!($($arg,)* "tasks", "task_id", "description || ' ' || COALESCE(notes, '')"),
+            $m!($($arg,)* "context_notes", "note_id", "category || ' ' || title || ' ' || content"),
+            $m!($($arg,)* "blockers", "blocker_id", "description || ' ' || COALESCE(resolution, '')"),
+            $m!($($arg,)* "questions", "question_id", "question || ' ' || COALESCE(context, '') || ' ' || COALESCE(answer, '')"),
+            $m!($($arg,)* "sessions", "session_id", "summary"),
+            $m!($($arg,)* "git_commits", "commit_id", "short_hash || ': ' || message"),
+        )
+    };
+}
+
+/// Index every row of a table into `$into`
+macro_rules! fts_fill {
+    ($into:literal, $table:literal, $key:literal, $text:literal) => {
+        concat!(
+            "INSERT INTO ",
+            $into,
+            " (content, table_name, record_id)\n",
+            "    SELECT ",
+            $text,
+            ", '",
+            $table,
+            "', ",
+            $key,
+            " FROM ",
+            $table,
+            " WHERE (",
+            $text,
+            ") IS NOT NULL;\n"
+        )
+    };
+}
+
+/// Insert, update, and delete triggers that keep a table's entries in `$into` current.
+/// Inserts clear the record's entry too: the UUID trigger updates a new row before the
+/// insert trigger runs, and that update has already indexed it.
+macro_rules! fts_triggers {
+    ($into:literal, $table:literal, $key:literal, $text:literal) => {
+        concat!(
+            "CREATE TRIGGER IF NOT EXISTS ",
+            $table,
+            "_fts_insert AFTER INSERT ON ",
+            $table,
+            " BEGIN\n",
+            "    DELETE FROM ",
+            $into,
+            " WHERE table_name = '",
+            $table,
+            "' AND record_id = new.",
+            $key,
+            ";\n",
+            "    INSERT INTO ",
+            $into,
+            " (content, table_name, record_id)\n",
+            "        SELECT ",
+            $text,
+            ", '",
+            $table,
+            "', ",
+            $key,
+            " FROM ",
+            $table,
+            " WHERE ",
+            $key,
+            " = new.",
+            $key,
+            " AND (",
+            $text,
+            ") IS NOT NULL;\n",
+            "END;\n",
+            "CREATE TRIGGER IF NOT EXISTS ",
+            $table,
+            "_fts_update AFTER UPDATE ON ",
+            $table,
+            " BEGIN\n",
+            "    DELETE FROM ",
+            $into,
+            " WHERE table_name = '",
+            $table,
+            "' AND record_id = old.",
+            $key,
+            ";\n",
+            "    INSERT INTO ",
+            $into,
+            " (content, table_name, record_id)\n",
+            "        SELECT ",
+            $text,
+            ", '",
+            $table,
+            "', ",
+            $key,
+            " FROM ",
+            $table,
+            " WHERE ",
+            $key,
+            " = new.",
+            $key,
+            " AND (",
+            $text,
+            ") IS NOT NULL;\n",
+            "END;\n",
+            "CREATE TRIGGER IF NOT EXISTS ",
+            $table,
+            "_fts_delete AFTER DELETE ON ",
+            $table,
+            " BEGIN\n",
+            "    DELETE FROM ",
+            $into,
+            " WHERE table_name = '",
+            $table,
+            "' AND record_id = old.",
+            $key,
+            ";\n",
+            "END;\n"
+        )
+    };
+}
+
+/// FTS5 index for full-text search (v1.6). The indexed text is kept in tracking_fts_docs,
+/// one row per record, and tracking_fts indexes it as external content. Its triggers
+/// remove a changed record's old entry with the FTS5 'delete' command, by rowid.
+macro_rules! fts_schema {
+    () => {
+        r#"
+CREATE TABLE IF NOT EXISTS tracking_fts_docs (
+    doc_id INTEGER PRIMARY KEY,
+    content TEXT,
+    table_name TEXT NOT NULL,
+    record_id INTEGER NOT NULL,
+    UNIQUE (table_name, record_id)
+);
+
+CREATE VIRTUAL TABLE IF NOT EXISTS tracking_fts USING fts5(
+    content,
+    table_name UNINDEXED,
+    record_id UNINDEXED,
+    content='tracking_fts_docs',
+    content_rowid='doc_id',
+    tokenize='porter'
+);
+
+CREATE TRIGGER IF NOT EXISTS tracking_fts_docs_ai AFTER INSERT ON tracking_fts_docs BEGIN
+    INSERT INTO tracking_fts(rowid, content, table_name, record_id)
+    VALUES (new.doc_id, new.content, new.table_name, new.record_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tracking_fts_docs_au AFTER UPDATE ON tracking_fts_docs BEGIN
+    INSERT INTO tracking_fts(tracking_fts, rowid, content, table_name, record_id)
+    VALUES('delete', old.doc_id, old.content, old.table_name, old.record_id);
+    INSERT INTO tracking_fts(rowid, content, table_name, record_id)
+    VALUES (new.doc_id, new.content, new.table_name, new.record_id);
+END;
+
+CREATE TRIGGER IF NOT EXISTS tracking_fts_docs_ad AFTER DELETE ON tracking_fts_docs BEGIN
+    INSERT INTO tracking_fts(tracking_fts, rowid, content, table_name, record_id)
+    VALUES('delete', old.doc_id, old.content, old.table_name, old.record_id);
+END;
+"#
+    };
+}
+
+/// FTS5 index for full-text search
+pub const FTS_SCHEMA: &str = fts_schema!();
+
+/// Triggers that keep tracking_fts_docs (and through it tracking_fts) in sync with the
+/// tables it indexes
+pub const FTS_TRIGGERS: &str = fts_tables!(fts_triggers, "tracking_fts_docs");
+
+/// Undo FTS_TRIGGERS (schema rollback from v1.7)
+pub const DROP_FTS_TRIGGERS: &str = r#"
+DROP TRIGGER IF EXISTS decisions_fts_insert;
+DROP TRIGGER IF EXISTS decisions_fts_update;
+DROP TRIGGER IF EXISTS decisions_fts_delete;
+DROP TRIGGER IF EXISTS tasks_fts_insert;
+DROP TRIGGER IF EXISTS tasks_fts_update;
+DROP TRIGGER IF EXISTS tasks_fts_delete;
+DROP TRIGGER IF EXISTS context_notes_fts_insert;
+DROP TRIGGER IF EXISTS context_notes_fts_update;
+DROP TRIGGER IF EXISTS context_notes_fts_delete;
+DROP TRIGGER IF EXISTS blockers_fts_insert;
+DROP TRIGGER IF EXISTS blockers_fts_update;
+DROP TRIGGER IF EXISTS blockers_fts_delete;
+DROP TRIGGER IF EXISTS questions_fts_insert;
+DROP TRIGGER IF EXISTS questions_fts_update;
+DROP TRIGGER IF EXISTS questions_fts_delete;
+DROP TRIGGER IF EXISTS sessions_fts_insert;
+DROP TRIGGER IF EXISTS sessions_fts_update;
+DROP TRIGGER IF EXISTS sessions_fts_delete;
+DROP TRIGGER IF EXISTS git_commits_fts_insert;
+DROP TRIGGER IF EXISTS git_commits_fts_update;
+DROP TRIGGER IF EXISTS git_commits_fts_delete;
+"#;
+
+/// Migrations applied by `proj upgrade`, oldest first; `proj rollback --schema` undoes them.
+/// `batch` groups the migrations applied by one upgrade run. The table belongs to the
+/// migration runner, so it isn't tied to a schema version.
+pub const MIGRATIONS_SCHEMA: &str = r#"
+CREATE TABLE IF NOT EXISTS schema_migrations (
+    migration_id INTEGER PRIMARY KEY AUTOINCREMENT,
+    from_version TEXT NOT NULL,
+    to_version TEXT NOT NULL,
+    batch INTEGER NOT NULL,
+    applied_at TEXT DEFAULT (datetime('now'))
+);
+"#;
+
+/// Recreate tracking_fts and repopulate it from the indexed tables
+pub const FTS_REBUILD: &str = concat!(
+    "DROP TABLE IF EXISTS tracking_fts;\nDROP TABLE IF EXISTS tracking_fts_docs;\n",
+    fts_schema!(),
+    fts_tables!(fts_fill, "tracking_fts_docs"),
+);
+
+/// Recreate the contentless tracking_fts of v1.3, which can match records but not return
+/// their text (schema rollback from v1.6)
+pub const FTS_CONTENTLESS: &str = concat!(
+    "DROP TABLE IF EXISTS tracking_fts;\nDROP TABLE IF EXISTS tracking_fts_docs;\n",
+    "CREATE VIRTUAL TABLE IF NOT EXISTS tracking_fts USING fts5(content, table_name, record_id, content='', tokenize='porter');\n",
+    fts_tables!(fts_fill, "tracking_fts"),
+);
+
+/// Initialize database with schema
+pub fn init_tracking_schema(conn: &rusqlite::Connection) -> anyhow::Result<()> {
+    conn.execute_batch(TRACKING_SCHEMA)?;
+    conn.execute_batch(FTS_SCHEMA)?;
+    conn.execute_batch(FTS_TRIGGERS)?;
+    conn.execute_batch(UUID_TRIGGERS)?;
+    for triggers in AUDIT_TRIGGER_SETS {
+        conn.execute_batch(triggers)?;
+    }
+    conn.execute_batch(MIGRATIONS_SCHEMA)?;
+
+    // Set schema version
+    conn.execute(
+        "INSERT OR REPLACE INTO project_meta (key, value) VALUES ('schema_version', ?1)",
+        [crate::SCHEMA_VERSION],
+    )?;
+
+    Ok(())
+}
+
+/// Rebuild the full-text search index from scratch, returning the number of indexed entries
+pub fn rebuild_fts_index(conn: &rusqlite::Connection) -> anyhow::Result<i64> {
+    let tx = crate::database::write_transaction(conn)?;
+    tx.execute_batch(FTS_REBUILD)?;
+    tx.execute_batch(FTS_TRIGGERS)?;
+    let count: i64 = tx.query_row("SELECT COUNT(*) FROM tracking_fts", [], |row| row.get(0))?;
+    tx.commit()?;
+    Ok(count)
+}
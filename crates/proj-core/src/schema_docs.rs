@@ -161,6 +161,27 @@ CREATE TABLE IF NOT EXISTS source_map (
     FOREIGN KEY (section_id) REFERENCES sections(section_id)
 );
 
+-- Saved copies of all sections, compared with proj docs diff
+CREATE TABLE IF NOT EXISTS snapshots (
+    id INTEGER PRIMARY KEY,
+    name TEXT UNIQUE NOT NULL,              -- user-given or timestamp name
+    created_at TEXT DEFAULT (datetime('now'))
+);
+
+CREATE TABLE IF NOT EXISTS snapshot_sections (
+    snapshot_id INTEGER NOT NULL,
+    section_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    level INTEGER NOT NULL DEFAULT 1,
+    sort_order INTEGER NOT NULL,
+    content TEXT DEFAULT '',
+    content_hash TEXT NOT NULL,             -- SHA256 of content, for quick comparison
+    generated INTEGER DEFAULT 0,
+    source_file TEXT,
+    PRIMARY KEY (snapshot_id, section_id),
+    FOREIGN KEY (snapshot_id) REFERENCES snapshots(id) ON DELETE CASCADE
+);
+
 -- Indexes
 CREATE INDEX IF NOT EXISTS idx_sections_parent ON sections(parent_id);
 CREATE INDEX IF NOT EXISTS idx_sections_level ON sections(level);
@@ -197,6 +218,7 @@ impl DocType {
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<DocType> {
         match s.to_lowercase().as_str() {
             "architecture" => Some(DocType::Architecture),
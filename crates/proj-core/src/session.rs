@@ -0,0 +1,634 @@
+// Session management - get_or_create_session and related functions
+
+use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use rusqlite::Connection;
+
+use crate::config::ProjectConfig;
+use crate::git;
+use crate::models::Session;
+use crate::paths::get_project_root;
+
+/// Result of get_or_create_session that indicates if a stale session was closed
+pub struct SessionResult {
+    pub session: Session,
+    pub auto_closed_session: Option<Session>,
+}
+
+/// How stale sessions are detected and handled (session_timeout_hours, auto_close_policy)
+pub struct StalePolicy {
+    /// Hours since start (or last resume) before a session is stale; 0 disables
+    pub timeout_hours: i64,
+    /// "close", "prompt", or "abandon"
+    pub action: String,
+}
+
+impl StalePolicy {
+    pub fn from_config(config: &ProjectConfig) -> Self {
+        Self {
+            timeout_hours: config.session_timeout_hours,
+            action: config.auto_close_policy.clone(),
+        }
+    }
+
+    /// Policy of the current project, or the defaults if config.json can't be read
+    pub fn current() -> Self {
+        let config = ProjectConfig::load().unwrap_or_default();
+        Self::from_config(&config)
+    }
+
+    /// Whether the session has run past the timeout since it started or was last resumed
+    pub fn is_stale(&self, session: &Session) -> bool {
+        let since = session.resumed_at.unwrap_or(session.started_at);
+        self.timeout_hours > 0 && Utc::now() - since > Duration::hours(self.timeout_hours)
+    }
+
+    /// Choice the policy makes without asking anyone. "prompt" closes the
+    /// session; callers with a terminal ask the user instead.
+    pub fn default_choice(&self) -> StaleChoice {
+        match self.action.as_str() {
+            "abandon" => StaleChoice::Abandon,
+            _ => StaleChoice::Close,
+        }
+    }
+}
+
+/// What the stale-session policy did with a session
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StaleOutcome {
+    /// Completed with the "(auto-closed)" placeholder summary
+    Closed,
+    /// Completed with a summary supplied by the caller
+    Summarized,
+    /// Marked abandoned
+    Abandoned,
+    /// Left open (timer restarted)
+    KeptOpen,
+}
+
+/// What to do with a stale session
+#[derive(Debug, Clone, PartialEq)]
+pub enum StaleChoice {
+    /// Close with the "(auto-closed)" placeholder summary
+    Close,
+    /// Close with this summary (a blank one falls back to the placeholder)
+    Summarize(String),
+    /// Mark abandoned
+    Abandon,
+    /// Leave open and restart the timer
+    KeepOpen,
+}
+
+/// The active session before the stale-session policy has been applied
+pub enum SessionState {
+    /// No active session
+    Missing,
+    /// Active session within the timeout
+    Active(Session),
+    /// Active session past the timeout; settle it with [`settle_stale_session`]
+    Stale(Session, StalePolicy),
+}
+
+/// Looks up the active session and whether it has gone stale
+pub fn session_state(conn: &Connection) -> Result<SessionState> {
+    let Some(session) = get_active_session(conn)? else {
+        return Ok(SessionState::Missing);
+    };
+    let policy = StalePolicy::current();
+    if policy.is_stale(&session) {
+        Ok(SessionState::Stale(session, policy))
+    } else {
+        Ok(SessionState::Active(session))
+    }
+}
+
+/// Gets the currently active session, or creates a new one if none exists.
+/// If an active session is stale, it is handled according to the project's auto_close_policy.
+pub fn get_or_create_session(conn: &Connection) -> Result<Session> {
+    let result = get_or_create_session_with_info(conn)?;
+    Ok(result.session)
+}
+
+/// Gets the currently active session with info about whether a stale session was closed.
+/// Never prompts: a stale session gets the policy's [`StalePolicy::default_choice`].
+pub fn get_or_create_session_with_info(conn: &Connection) -> Result<SessionResult> {
+    match session_state(conn)? {
+        SessionState::Missing => Ok(SessionResult {
+            session: create_session(conn)?,
+            auto_closed_session: None,
+        }),
+        SessionState::Active(session) => Ok(SessionResult {
+            session,
+            auto_closed_session: None,
+        }),
+        SessionState::Stale(session, policy) => {
+            settle_stale_session(conn, session, policy.default_choice())
+        }
+    }
+}
+
+/// Applies a choice to a stale session and starts a new session unless it was kept open
+pub fn settle_stale_session(
+    conn: &Connection,
+    session: Session,
+    choice: StaleChoice,
+) -> Result<SessionResult> {
+    if apply_stale_choice(conn, &session, choice)? == StaleOutcome::KeptOpen {
+        return Ok(SessionResult {
+            session,
+            auto_closed_session: None,
+        });
+    }
+
+    let closed_session = get_session(conn, session.session_id)?.unwrap_or(session);
+    Ok(SessionResult {
+        session: create_session(conn)?,
+        auto_closed_session: Some(closed_session),
+    })
+}
+
+/// Applies a choice to a stale session
+pub fn apply_stale_choice(
+    conn: &Connection,
+    session: &Session,
+    choice: StaleChoice,
+) -> Result<StaleOutcome> {
+    match choice {
+        StaleChoice::Summarize(summary) if !summary.trim().is_empty() => {
+            end_session(conn, session.session_id, summary.trim())?;
+            Ok(StaleOutcome::Summarized)
+        }
+        StaleChoice::Abandon => {
+            abandon_session(conn, session.session_id)?;
+            Ok(StaleOutcome::Abandoned)
+        }
+        StaleChoice::KeepOpen => {
+            // Restart the timer so the question isn't asked again on the next command
+            conn.execute(
+                "UPDATE sessions SET resumed_at = datetime('now') WHERE session_id = ?1",
+                [session.session_id],
+            )?;
+            Ok(StaleOutcome::KeptOpen)
+        }
+        _ => {
+            auto_close_session(conn, session.session_id)?;
+            Ok(StaleOutcome::Closed)
+        }
+    }
+}
+
+/// Notice shown after the stale-session policy closed a session
+pub fn stale_notice(closed: &Session) -> String {
+    let outcome = match closed.status.as_str() {
+        "abandoned" => "Abandoned",
+        _ if closed.summary.as_deref() == Some("(auto-closed)") => "Auto-closed",
+        _ => "Closed",
+    };
+    format!(
+        "Previous session #{} was stale ({}+ hours). {}.",
+        closed.session_id,
+        StalePolicy::current().timeout_hours,
+        outcome
+    )
+}
+
+/// Auto-closes a stale session with a marker summary
+fn auto_close_session(conn: &Connection, session_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET ended_at = datetime('now'), status = 'completed', summary = '(auto-closed)' WHERE session_id = ?1",
+        [session_id],
+    )?;
+    Ok(())
+}
+
+/// Marks a stale session as abandoned (no summary)
+fn abandon_session(conn: &Connection, session_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET ended_at = datetime('now'), status = 'abandoned' WHERE session_id = ?1",
+        [session_id],
+    )?;
+    Ok(())
+}
+
+/// Gets the currently active session if one exists
+pub fn get_active_session(conn: &Connection) -> Result<Option<Session>> {
+    let mut stmt = conn.prepare(
+        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary, name, resumed_at
+         FROM sessions
+         WHERE status = 'active'
+         ORDER BY started_at DESC
+         LIMIT 1"
+    )?;
+
+    let session = stmt.query_row([], |row| {
+        Ok(Session {
+            session_id: row.get(0)?,
+            started_at: parse_datetime(row.get::<_, String>(1)?),
+            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+            agent: row.get(3)?,
+            summary: row.get(4)?,
+            files_touched: row.get(5)?,
+            status: row.get(6)?,
+            full_context_shown: row.get::<_, i32>(7)? != 0,
+            structured_summary: row.get(8)?,
+            name: row.get(9)?,
+            resumed_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+        })
+    });
+
+    match session {
+        Ok(s) => Ok(Some(s)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Creates a new session
+pub fn create_session(conn: &Connection) -> Result<Session> {
+    create_named_session(conn, None)
+}
+
+/// Creates a new session with an optional name (for parallel work like a hotfix)
+pub fn create_named_session(conn: &Connection, name: Option<&str>) -> Result<Session> {
+    create_session_by(conn, name, None)
+}
+
+/// Creates a new session attributed to `author` (resolved with `resolve_author`)
+pub fn create_session_by(
+    conn: &Connection,
+    name: Option<&str>,
+    author: Option<&str>,
+) -> Result<Session> {
+    let branch = get_project_root()
+        .ok()
+        .and_then(|root| git::current_branch(&root));
+    let author = resolve_author(author);
+    conn.execute(
+        "INSERT INTO sessions (status, full_context_shown, name, branch, author) VALUES ('active', 0, ?1, ?2, ?3)",
+        rusqlite::params![name, branch, author],
+    )?;
+
+    let session_id = conn.last_insert_rowid();
+
+    if let Some(ref b) = branch {
+        set_last_branch(conn, b)?;
+    }
+
+    // Clean up any stale session warning markers
+    cleanup_warned_markers();
+
+    // Fetch the created session
+    let mut stmt = conn.prepare(
+        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary, name, resumed_at
+         FROM sessions
+         WHERE session_id = ?1"
+    )?;
+
+    let session = stmt.query_row([session_id], |row| {
+        Ok(Session {
+            session_id: row.get(0)?,
+            started_at: parse_datetime(row.get::<_, String>(1)?),
+            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+            agent: row.get(3)?,
+            summary: row.get(4)?,
+            files_touched: row.get(5)?,
+            status: row.get(6)?,
+            full_context_shown: row.get::<_, i32>(7)? != 0,
+            structured_summary: row.get(8)?,
+            name: row.get(9)?,
+            resumed_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+        })
+    })?;
+
+    Ok(session)
+}
+
+/// Ends a session with a summary
+pub fn end_session(conn: &Connection, session_id: i64, summary: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET ended_at = datetime('now'), status = 'completed', summary = ?1 WHERE session_id = ?2",
+        rusqlite::params![summary, session_id],
+    )?;
+    Ok(())
+}
+
+/// Ends a session with both plain and structured summaries
+pub fn end_session_with_structured(
+    conn: &Connection,
+    session_id: i64,
+    summary: &str,
+    structured_summary: &str,
+) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET ended_at = datetime('now'), status = 'completed', summary = ?1, structured_summary = ?2 WHERE session_id = ?3",
+        rusqlite::params![summary, structured_summary, session_id],
+    )?;
+    Ok(())
+}
+
+/// Pauses an active session; it keeps its activity but stops receiving new entries
+pub fn pause_session(conn: &Connection, session_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET status = 'paused' WHERE session_id = ?1 AND status = 'active'",
+        [session_id],
+    )?;
+    Ok(())
+}
+
+/// Makes a paused session the active one again
+pub fn resume_session(conn: &Connection, session_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET status = 'active', resumed_at = datetime('now') WHERE session_id = ?1 AND status = 'paused'",
+        [session_id],
+    )?;
+    Ok(())
+}
+
+/// Gets all paused sessions, most recently started first
+pub fn get_paused_sessions(conn: &Connection) -> Result<Vec<Session>> {
+    let mut stmt = conn.prepare(
+        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary, name, resumed_at
+         FROM sessions
+         WHERE status = 'paused'
+         ORDER BY started_at DESC"
+    )?;
+
+    let sessions = stmt.query_map([], |row| {
+        Ok(Session {
+            session_id: row.get(0)?,
+            started_at: parse_datetime(row.get::<_, String>(1)?),
+            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+            agent: row.get(3)?,
+            summary: row.get(4)?,
+            files_touched: row.get(5)?,
+            status: row.get(6)?,
+            full_context_shown: row.get::<_, i32>(7)? != 0,
+            structured_summary: row.get(8)?,
+            name: row.get(9)?,
+            resumed_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+        })
+    })?;
+
+    sessions
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.into())
+}
+
+/// Current git branch for tagging a new entry. If the branch changed since proj last
+/// looked, the switch is noted in the session's activity.
+pub fn track_branch(conn: &Connection, session_id: i64) -> Result<Option<String>> {
+    let Some(branch) = get_project_root()
+        .ok()
+        .and_then(|root| git::current_branch(&root))
+    else {
+        return Ok(None);
+    };
+
+    let last: Option<String> = conn
+        .query_row(
+            "SELECT value FROM project_meta WHERE key = 'last_branch'",
+            [],
+            |row| row.get(0),
+        )
+        .ok();
+
+    if last.as_deref() != Some(branch.as_str()) {
+        if let Some(prev) = last {
+            conn.execute(
+                "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'branch_switch', NULL, ?2)",
+                rusqlite::params![session_id, format!("Switched branch: {} → {}", prev, branch)],
+            )?;
+        }
+        set_last_branch(conn, &branch)?;
+    }
+
+    Ok(Some(branch))
+}
+
+/// Remember the branch proj last saw, to detect switches
+fn set_last_branch(conn: &Connection, branch: &str) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO project_meta (key, value) VALUES ('last_branch', ?1)",
+        [branch],
+    )?;
+    Ok(())
+}
+
+/// Who new entries are attributed to: an explicit --author, else $PROJ_AUTHOR,
+/// else git user.name/email, else the OS user
+pub fn resolve_author(explicit: Option<&str>) -> Option<String> {
+    let explicit = explicit
+        .map(str::to_string)
+        .or_else(|| std::env::var("PROJ_AUTHOR").ok())
+        .map(|a| a.trim().to_string())
+        .filter(|a| !a.is_empty());
+    if explicit.is_some() {
+        return explicit;
+    }
+    let root = get_project_root()
+        .ok()
+        .or_else(|| crate::paths::get_start_dir().ok())?;
+    git::current_author(&root)
+}
+
+/// Marks a session as having shown full context
+pub fn mark_full_context_shown(conn: &Connection, session_id: i64) -> Result<()> {
+    conn.execute(
+        "UPDATE sessions SET full_context_shown = 1 WHERE session_id = ?1",
+        [session_id],
+    )?;
+    Ok(())
+}
+
+/// Clean up stale session warning markers from .tracking directory
+fn cleanup_warned_markers() {
+    let tracking_dir = std::path::Path::new(".tracking");
+    if !tracking_dir.exists() {
+        return;
+    }
+
+    // Remove any .warned_stale_* files
+    if let Ok(entries) = std::fs::read_dir(tracking_dir) {
+        for entry in entries.flatten() {
+            if let Some(name) = entry.file_name().to_str() {
+                if name.starts_with(".warned_stale_") {
+                    let _ = std::fs::remove_file(entry.path());
+                }
+            }
+        }
+    }
+}
+
+/// Gets the last N completed sessions
+pub fn get_recent_sessions(conn: &Connection, limit: usize) -> Result<Vec<Session>> {
+    let mut stmt = conn.prepare(
+        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary, name, resumed_at
+         FROM sessions
+         ORDER BY started_at DESC
+         LIMIT ?1"
+    )?;
+
+    let sessions = stmt.query_map([limit as i64], |row| {
+        Ok(Session {
+            session_id: row.get(0)?,
+            started_at: parse_datetime(row.get::<_, String>(1)?),
+            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+            agent: row.get(3)?,
+            summary: row.get(4)?,
+            files_touched: row.get(5)?,
+            status: row.get(6)?,
+            full_context_shown: row.get::<_, i32>(7)? != 0,
+            structured_summary: row.get(8)?,
+            name: row.get(9)?,
+            resumed_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+        })
+    })?;
+
+    sessions
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| e.into())
+}
+
+/// Which sessions `list_sessions` returns
+#[derive(Default)]
+pub struct SessionFilter {
+    /// Only sessions with one of these statuses (any status when empty)
+    pub statuses: Vec<String>,
+    /// Only sessions started on or after this date (YYYY-MM-DD)
+    pub since: Option<String>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+/// Sessions matching the filter, newest first, and how many match in all
+pub fn list_sessions(conn: &Connection, filter: &SessionFilter) -> Result<(Vec<Session>, i64)> {
+    let statuses = serde_json::to_string(&filter.statuses)?;
+    let condition = "(json_array_length(?1) = 0 OR status IN (SELECT value FROM json_each(?1)))
+           AND (?2 IS NULL OR date(started_at) >= ?2)";
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM sessions WHERE {}", condition),
+        rusqlite::params![statuses, filter.since],
+        |row| row.get(0),
+    )?;
+
+    let mut stmt = conn.prepare(&format!(
+        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary, name, resumed_at
+         FROM sessions
+         WHERE {}
+         ORDER BY started_at DESC
+         LIMIT ?3 OFFSET ?4",
+        condition
+    ))?;
+    let limit = filter.limit.map_or(-1, |l| l as i64);
+    let sessions = stmt.query_map(
+        rusqlite::params![statuses, filter.since, limit, filter.offset as i64],
+        |row| {
+            Ok(Session {
+                session_id: row.get(0)?,
+                started_at: parse_datetime(row.get::<_, String>(1)?),
+                ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+                agent: row.get(3)?,
+                summary: row.get(4)?,
+                files_touched: row.get(5)?,
+                status: row.get(6)?,
+                full_context_shown: row.get::<_, i32>(7)? != 0,
+                structured_summary: row.get(8)?,
+                name: row.get(9)?,
+                resumed_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+            })
+        },
+    )?;
+
+    Ok((sessions.collect::<Result<Vec<_>, _>>()?, total))
+}
+
+/// Gets a session by ID
+pub fn get_session(conn: &Connection, session_id: i64) -> Result<Option<Session>> {
+    let mut stmt = conn.prepare(
+        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary, name, resumed_at
+         FROM sessions
+         WHERE session_id = ?1"
+    )?;
+
+    let session = stmt.query_row([session_id], |row| {
+        Ok(Session {
+            session_id: row.get(0)?,
+            started_at: parse_datetime(row.get::<_, String>(1)?),
+            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+            agent: row.get(3)?,
+            summary: row.get(4)?,
+            files_touched: row.get(5)?,
+            status: row.get(6)?,
+            full_context_shown: row.get::<_, i32>(7)? != 0,
+            structured_summary: row.get(8)?,
+            name: row.get(9)?,
+            resumed_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+        })
+    });
+
+    match session {
+        Ok(s) => Ok(Some(s)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Finds the session that was running at the given time ("YYYY-MM-DD HH:MM:SS", UTC)
+pub fn find_session_at(conn: &Connection, at: &str) -> Result<Option<Session>> {
+    let session_id: Option<i64> = conn
+        .query_row(
+            "SELECT session_id FROM sessions
+             WHERE started_at <= ?1 AND COALESCE(ended_at, datetime('now')) >= ?1
+             ORDER BY started_at DESC
+             LIMIT 1",
+            [at],
+            |row| row.get(0),
+        )
+        .ok();
+
+    match session_id {
+        Some(id) => get_session(conn, id),
+        None => Ok(None),
+    }
+}
+
+/// Gets the last completed session
+pub fn get_last_completed_session(conn: &Connection) -> Result<Option<Session>> {
+    let mut stmt = conn.prepare(
+        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary, name, resumed_at
+         FROM sessions
+         WHERE status = 'completed'
+         ORDER BY ended_at DESC
+         LIMIT 1"
+    )?;
+
+    let session = stmt.query_row([], |row| {
+        Ok(Session {
+            session_id: row.get(0)?,
+            started_at: parse_datetime(row.get::<_, String>(1)?),
+            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
+            agent: row.get(3)?,
+            summary: row.get(4)?,
+            files_touched: row.get(5)?,
+            status: row.get(6)?,
+            full_context_shown: row.get::<_, i32>(7)? != 0,
+            structured_summary: row.get(8)?,
+            name: row.get(9)?,
+            resumed_at: row.get::<_, Option<String>>(10)?.map(parse_datetime),
+        })
+    });
+
+    match session {
+        Ok(s) => Ok(Some(s)),
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Parse datetime string from SQLite
+fn parse_datetime(s: String) -> DateTime<Utc> {
+    // SQLite stores as "YYYY-MM-DD HH:MM:SS"
+    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| dt.and_utc())
+        .unwrap_or_else(|_| Utc::now())
+}
@@ -0,0 +1,2221 @@
+// Source code analyzer for generating documentation
+// Supports Rust, Python, TypeScript, and Go; mixed-language projects are analyzed per language
+
+use anyhow::{Context, Result};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::projignore::ProjIgnore;
+use crate::schema_docs::DocType;
+
+/// Detected item from source code
+#[derive(Debug, Clone)]
+pub struct SourceItem {
+    pub kind: ItemKind,
+    pub name: String,
+    pub visibility: Visibility,
+    pub doc_comment: Option<String>,
+    pub file_path: PathBuf,
+    #[allow(dead_code)]
+    pub line_number: usize,
+    #[allow(dead_code)]
+    pub children: Vec<SourceItem>,
+    /// Declaration as written, up to its body, on one line
+    pub signature: Option<String>,
+    /// Enclosing impl, trait, or class of a method (`Foo`, `Display for Foo`)
+    pub parent: Option<String>,
+    /// Module the item is defined in (see `module_name`)
+    pub module: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ItemKind {
+    Module,
+    Struct,
+    Enum,
+    Trait,
+    Impl,
+    Function,
+    Constant,
+    Type,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Visibility {
+    Public,
+    PublicCrate,
+    Private,
+}
+
+/// Project structure detected from source analysis
+#[derive(Debug)]
+pub struct ProjectStructure {
+    pub name: String,
+    /// Primary language (the first entry in `languages`)
+    pub language: Language,
+    /// All items across every language
+    pub modules: Vec<SourceItem>,
+    #[allow(dead_code)]
+    pub entry_points: Vec<PathBuf>,
+    pub file_count: usize,
+    pub total_lines: usize,
+    /// Per-language breakdown, primary language first
+    pub languages: Vec<LanguageGroup>,
+}
+
+impl ProjectStructure {
+    /// Human-readable language label, e.g. "Rust" or "Rust + TypeScript"
+    pub fn language_label(&self) -> String {
+        if self.languages.len() <= 1 {
+            return self.language.as_str().to_string();
+        }
+        self.languages
+            .iter()
+            .map(|g| g.language.as_str())
+            .collect::<Vec<_>>()
+            .join(" + ")
+    }
+}
+
+/// Items and file statistics for a single language within a project
+#[derive(Debug)]
+pub struct LanguageGroup {
+    pub language: Language,
+    pub modules: Vec<SourceItem>,
+    #[allow(dead_code)]
+    pub entry_points: Vec<PathBuf>,
+    pub file_count: usize,
+    pub total_lines: usize,
+    /// `use`/`import` relationships between modules of this language
+    pub dependencies: Vec<ModuleDependency>,
+}
+
+/// A dependency edge between two internal modules (`from` uses `to`)
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ModuleDependency {
+    pub from: String,
+    pub to: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Language {
+    Rust,
+    Python,
+    TypeScript,
+    Go,
+    Unknown,
+}
+
+impl Language {
+    pub fn from_extension(ext: &str) -> Self {
+        match ext {
+            "rs" => Language::Rust,
+            "py" => Language::Python,
+            "ts" | "tsx" => Language::TypeScript,
+            "go" => Language::Go,
+            _ => Language::Unknown,
+        }
+    }
+
+    pub fn extensions(&self) -> &[&str] {
+        match self {
+            Language::Rust => &["rs"],
+            Language::Python => &["py"],
+            Language::TypeScript => &["ts", "tsx"],
+            Language::Go => &["go"],
+            Language::Unknown => &[],
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Language::Rust => "Rust",
+            Language::Python => "Python",
+            Language::TypeScript => "TypeScript",
+            Language::Go => "Go",
+            Language::Unknown => "Unknown",
+        }
+    }
+}
+
+/// Parse results for one file, reused until its size or modification time changes
+struct CachedFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    lines: usize,
+    items: Vec<SourceItem>,
+    /// Import targets before resolution against the project's modules
+    imports: Vec<String>,
+}
+
+/// Per-file analysis kept between runs, so re-analyzing a project only
+/// re-parses the files that changed (used by `proj docs watch`)
+#[derive(Default)]
+pub struct AnalysisCache {
+    files: HashMap<PathBuf, CachedFile>,
+}
+
+impl AnalysisCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+/// Analyze a project directory
+pub fn analyze_project(project_root: &Path) -> Result<ProjectStructure> {
+    analyze_project_cached(project_root, &mut AnalysisCache::new())
+}
+
+/// Analyze a project directory, re-parsing only files that changed since
+/// the cache was last used
+pub fn analyze_project_cached(
+    project_root: &Path,
+    cache: &mut AnalysisCache,
+) -> Result<ProjectStructure> {
+    let ignore = ProjIgnore::load(project_root);
+
+    // Detect every language present, primary first
+    let languages = detect_languages(project_root, &ignore)?;
+
+    // Get project name
+    let name = project_root
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("project")
+        .to_string();
+
+    let mut groups = Vec::new();
+    for language in languages {
+        let group = analyze_language(project_root, language, &ignore, cache)?;
+        if group.file_count > 0 {
+            groups.push(group);
+        }
+    }
+
+    // Forget deleted files
+    cache.files.retain(|path, _| path.exists());
+
+    let language = groups
+        .first()
+        .map(|g| g.language.clone())
+        .unwrap_or(Language::Unknown);
+
+    Ok(ProjectStructure {
+        name,
+        language,
+        modules: groups.iter().flat_map(|g| g.modules.clone()).collect(),
+        entry_points: groups.iter().flat_map(|g| g.entry_points.clone()).collect(),
+        file_count: groups.iter().map(|g| g.file_count).sum(),
+        total_lines: groups.iter().map(|g| g.total_lines).sum(),
+        languages: groups,
+    })
+}
+
+/// Analyze all source files of a single language
+fn analyze_language(
+    project_root: &Path,
+    language: Language,
+    ignore: &ProjIgnore,
+    cache: &mut AnalysisCache,
+) -> Result<LanguageGroup> {
+    // Find source files
+    let source_files = find_source_files(project_root, &language, ignore)?;
+    let source_root = source_root(project_root, &language);
+
+    // Internal module names, used to resolve imports
+    let known_modules: BTreeSet<String> = source_files
+        .iter()
+        .map(|f| module_name(&source_root, f, &language))
+        .collect();
+
+    // Parse each file
+    let mut modules = Vec::new();
+    let mut entry_points = Vec::new();
+    let mut total_lines = 0;
+    let mut dependencies = BTreeSet::new();
+
+    for file_path in &source_files {
+        // Check if this is an entry point
+        let file_name = file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if file_name == "main.rs" || file_name == "lib.rs" || file_name == "mod.rs" {
+            entry_points.push(file_path.clone());
+        }
+
+        let from = module_name(&source_root, file_path, &language);
+        let metadata = std::fs::metadata(file_path).ok();
+        let modified = metadata.as_ref().and_then(|m| m.modified().ok());
+        let len = metadata.as_ref().map(|m| m.len()).unwrap_or(0);
+
+        let unchanged = cache.files.get(file_path).is_some_and(|cached| {
+            modified.is_some() && cached.modified == modified && cached.len == len
+        });
+        if !unchanged {
+            let content = std::fs::read_to_string(file_path)
+                .with_context(|| format!("Failed to read {:?}", file_path))?;
+
+            // Parse the file
+            let mut items = match language {
+                Language::Rust => parse_rust_file(&content, file_path)?,
+                Language::Python => parse_python_file(&content, file_path)?,
+                Language::TypeScript => parse_typescript_file(&content, file_path)?,
+                Language::Go => parse_go_file(&content, file_path)?,
+                Language::Unknown => Vec::new(),
+            };
+            for item in &mut items {
+                item.module = from.clone();
+            }
+            let imports = extract_dependencies(&content, &from, file_path, &source_root, &language);
+
+            cache.files.insert(
+                file_path.clone(),
+                CachedFile {
+                    modified,
+                    len,
+                    lines: content.lines().count(),
+                    items,
+                    imports,
+                },
+            );
+        }
+        let Some(file) = cache.files.get(file_path) else {
+            continue;
+        };
+
+        total_lines += file.lines;
+        modules.extend(file.items.iter().cloned());
+
+        // Record module dependencies
+        for to in file
+            .imports
+            .iter()
+            .filter_map(|target| resolve_module(target, &known_modules))
+        {
+            if to != from {
+                dependencies.insert(ModuleDependency {
+                    from: from.clone(),
+                    to,
+                });
+            }
+        }
+    }
+
+    Ok(LanguageGroup {
+        language,
+        modules,
+        entry_points,
+        file_count: source_files.len(),
+        total_lines,
+        dependencies: dependencies.into_iter().collect(),
+    })
+}
+
+/// Detect all languages in a project, primary language first.
+/// The primary language comes from manifest files when present; the rest
+/// are ordered by how many source files they have.
+fn detect_languages(project_root: &Path, ignore: &ProjIgnore) -> Result<Vec<Language>> {
+    let counts = count_files_by_language(project_root, ignore);
+
+    let mut languages: Vec<(Language, usize)> = counts.into_iter().collect();
+    languages.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.as_str().cmp(b.0.as_str())));
+    let mut languages: Vec<Language> = languages.into_iter().map(|(lang, _)| lang).collect();
+
+    if let Some(primary) = detect_manifest_language(project_root) {
+        languages.retain(|l| *l != primary);
+        languages.insert(0, primary);
+    }
+
+    if languages.is_empty() {
+        languages.push(Language::Unknown);
+    }
+
+    Ok(languages)
+}
+
+/// Detect the primary language from language-specific manifest files
+fn detect_manifest_language(project_root: &Path) -> Option<Language> {
+    if project_root.join("Cargo.toml").exists() {
+        return Some(Language::Rust);
+    }
+    if project_root.join("package.json").exists() {
+        // Could be JS or TS - check for tsconfig
+        if project_root.join("tsconfig.json").exists() {
+            return Some(Language::TypeScript);
+        }
+    }
+    if project_root.join("go.mod").exists() {
+        return Some(Language::Go);
+    }
+    if project_root.join("setup.py").exists()
+        || project_root.join("pyproject.toml").exists()
+        || project_root.join("requirements.txt").exists()
+    {
+        return Some(Language::Python);
+    }
+    None
+}
+
+/// Count source files by extension
+fn count_files_by_language(project_root: &Path, ignore: &ProjIgnore) -> HashMap<Language, usize> {
+    let mut counts: HashMap<Language, usize> = HashMap::new();
+
+    fn count_files(
+        dir: &Path,
+        ignore: &ProjIgnore,
+        counts: &mut HashMap<Language, usize>,
+        depth: usize,
+    ) {
+        if depth > 5 {
+            return; // Don't go too deep
+        }
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if ignore.is_ignored(&path, path.is_dir()) {
+                    continue;
+                }
+                if path.is_dir() {
+                    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                    // Skip common non-source directories
+                    if ![
+                        "target",
+                        "node_modules",
+                        ".git",
+                        "vendor",
+                        "__pycache__",
+                        "build",
+                        "dist",
+                        ".tracking",
+                    ]
+                    .contains(&name)
+                    {
+                        count_files(&path, ignore, counts, depth + 1);
+                    }
+                } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                    let lang = Language::from_extension(ext);
+                    if lang != Language::Unknown {
+                        *counts.entry(lang).or_insert(0) += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    count_files(project_root, ignore, &mut counts, 0);
+    counts
+}
+
+/// Find all source files for a language
+fn find_source_files(
+    project_root: &Path,
+    language: &Language,
+    ignore: &ProjIgnore,
+) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let extensions = language.extensions();
+
+    fn walk_dir(
+        dir: &Path,
+        extensions: &[&str],
+        ignore: &ProjIgnore,
+        files: &mut Vec<PathBuf>,
+        depth: usize,
+    ) {
+        if depth > 10 {
+            return;
+        }
+
+        if let Ok(entries) = std::fs::read_dir(dir) {
+            for entry in entries.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if ignore.is_ignored(&path, path.is_dir()) {
+                    continue;
+                }
+                if path.is_dir() {
+                    let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+                    // Skip non-source directories
+                    if ![
+                        "target",
+                        "node_modules",
+                        ".git",
+                        "vendor",
+                        "__pycache__",
+                        "build",
+                        "dist",
+                        ".tracking",
+                    ]
+                    .contains(&name)
+                    {
+                        walk_dir(&path, extensions, ignore, files, depth + 1);
+                    }
+                } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
+                    if extensions.contains(&ext) {
+                        files.push(path);
+                    }
+                }
+            }
+        }
+    }
+
+    let start_dir = source_root(project_root, language);
+
+    walk_dir(&start_dir, extensions, ignore, &mut files, 0);
+
+    // Sort for consistent ordering
+    files.sort();
+
+    Ok(files)
+}
+
+/// Directory source files are discovered from (and module names are relative to)
+fn source_root(project_root: &Path, language: &Language) -> PathBuf {
+    // For Rust, start from src/ if it exists
+    if *language == Language::Rust && project_root.join("src").is_dir() {
+        project_root.join("src")
+    } else {
+        project_root.to_path_buf()
+    }
+}
+
+/// Module name for a source file, in the language's own notation
+/// (`commands::task` for Rust, `pkg.util` for Python, `web/app` for TypeScript,
+/// and the package directory for Go)
+fn module_name(source_root: &Path, file_path: &Path, language: &Language) -> String {
+    let relative = file_path.strip_prefix(source_root).unwrap_or(file_path);
+    let mut parts: Vec<String> = relative
+        .with_extension("")
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy().to_string())
+        .collect();
+
+    match language {
+        Language::Rust => {
+            if matches!(
+                parts.last().map(String::as_str),
+                Some("mod") | Some("main") | Some("lib")
+            ) {
+                parts.pop();
+            }
+            if parts.is_empty() {
+                "crate".to_string()
+            } else {
+                parts.join("::")
+            }
+        }
+        Language::Python => {
+            if parts.last().map(String::as_str) == Some("__init__") {
+                parts.pop();
+            }
+            parts.join(".")
+        }
+        Language::TypeScript => {
+            if parts.last().map(String::as_str) == Some("index") && parts.len() > 1 {
+                parts.pop();
+            }
+            parts.join("/")
+        }
+        Language::Go => {
+            parts.pop();
+            if parts.is_empty() {
+                "main".to_string()
+            } else {
+                parts.join("/")
+            }
+        }
+        Language::Unknown => parts.join("/"),
+    }
+}
+
+/// Extract candidate import targets from a file, expressed as module names
+fn extract_dependencies(
+    content: &str,
+    module: &str,
+    file_path: &Path,
+    source_root: &Path,
+    language: &Language,
+) -> Vec<String> {
+    let mut targets = Vec::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("//") {
+            continue;
+        }
+
+        match language {
+            Language::Rust => {
+                // `crate::a::b` paths anywhere (covers `use` and inline paths)
+                let mut rest = trimmed;
+                while let Some(idx) = rest.find("crate::") {
+                    rest = &rest[idx + "crate::".len()..];
+                    targets.extend(expand_rust_path(rest, ""));
+                }
+                // `use super::x` and `use self::x` relative to this module
+                if let Some(path) = trimmed
+                    .strip_prefix("use ")
+                    .or_else(|| trimmed.strip_prefix("pub use "))
+                {
+                    let base: Vec<&str> = if module == "crate" {
+                        Vec::new()
+                    } else {
+                        module.split("::").collect()
+                    };
+                    if let Some(rel) = path.strip_prefix("super::") {
+                        let parent = base[..base.len().saturating_sub(1)].join("::");
+                        targets.extend(expand_rust_path(rel, &parent));
+                    } else if let Some(rel) = path.strip_prefix("self::") {
+                        targets.extend(expand_rust_path(rel, &base.join("::")));
+                    }
+                }
+            }
+            Language::Python => {
+                if let Some(rest) = trimmed.strip_prefix("from ") {
+                    let target = rest.split_whitespace().next().unwrap_or("");
+                    let target = if target.starts_with('.') {
+                        // Relative import: resolve against this module's package
+                        let dots = target.chars().take_while(|c| *c == '.').count();
+                        let mut base: Vec<&str> = module.split('.').collect();
+                        base.truncate(base.len().saturating_sub(dots));
+                        let remainder = target.trim_start_matches('.');
+                        if !remainder.is_empty() {
+                            base.push(remainder);
+                        }
+                        base.join(".")
+                    } else {
+                        target.to_string()
+                    };
+                    targets.push(target);
+                } else if let Some(rest) = trimmed.strip_prefix("import ") {
+                    for name in rest.split(',') {
+                        let name = name.split_whitespace().next().unwrap_or("");
+                        targets.push(name.to_string());
+                    }
+                }
+            }
+            Language::TypeScript => {
+                // import ... from './x', export ... from './x', import './x', require('./x')
+                for quote in ['\'', '"'] {
+                    let spec = [
+                        format!("from {}", quote),
+                        format!("import {}", quote),
+                        format!("require({}", quote),
+                    ]
+                    .iter()
+                    .find_map(|marker| {
+                        trimmed
+                            .find(marker.as_str())
+                            .map(|idx| &trimmed[idx + marker.len()..])
+                    });
+                    if let Some(spec) = spec.and_then(|s| s.split(quote).next()) {
+                        if spec.starts_with('.') {
+                            let dir = file_path.parent().unwrap_or(source_root);
+                            let resolved = normalize_path(&dir.join(spec));
+                            targets.push(module_name(
+                                source_root,
+                                &resolved.with_extension("ts"),
+                                language,
+                            ));
+                        }
+                    }
+                }
+            }
+            Language::Go => {
+                // Both `import "x/y"` and lines inside an import ( ... ) block
+                let spec = trimmed.strip_prefix("import ").unwrap_or(trimmed);
+                let spec = spec.split_whitespace().last().unwrap_or("");
+                if spec.len() > 2 && spec.starts_with('"') && spec.ends_with('"') {
+                    targets.push(spec.trim_matches('"').to_string());
+                }
+            }
+            Language::Unknown => {}
+        }
+    }
+
+    targets
+}
+
+/// Expand a Rust path (the part after `crate::`) into module paths,
+/// handling one level of `{a, b::c}` grouping
+fn expand_rust_path(path: &str, prefix: &str) -> Vec<String> {
+    let join = |p: &str| {
+        if prefix.is_empty() {
+            p.to_string()
+        } else {
+            format!("{}::{}", prefix, p)
+        }
+    };
+
+    let head: String = path
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == ':')
+        .collect();
+    let head = head.trim_end_matches(':');
+
+    if path[head.len()..].trim_start_matches("::").starts_with('{') {
+        let inner = path.split_once('{').map(|(_, r)| r).unwrap_or("");
+        let inner = inner.split('}').next().unwrap_or("");
+        return inner
+            .split(',')
+            .map(|p| p.trim())
+            .filter(|p| !p.is_empty())
+            .map(|p| {
+                if head.is_empty() {
+                    join(p)
+                } else {
+                    join(&format!("{}::{}", head, p))
+                }
+            })
+            .collect();
+    }
+
+    if head.is_empty() {
+        Vec::new()
+    } else {
+        vec![join(head)]
+    }
+}
+
+/// Resolve an import target to the longest matching internal module
+fn resolve_module(target: &str, known_modules: &BTreeSet<String>) -> Option<String> {
+    if target.is_empty() {
+        return None;
+    }
+    if known_modules.contains(target) {
+        return Some(target.to_string());
+    }
+
+    // Longest known module that is a prefix of the target (a::b::Item -> a::b)
+    let prefix_match = known_modules
+        .iter()
+        .filter(|m| {
+            target.len() > m.len()
+                && target.starts_with(m.as_str())
+                && matches!(target.as_bytes()[m.len()], b':' | b'.' | b'/')
+        })
+        .max_by_key(|m| m.len());
+    if let Some(m) = prefix_match {
+        return Some(m.clone());
+    }
+
+    // Go import paths are fully qualified; match on the trailing package path
+    known_modules
+        .iter()
+        .filter(|m| *m != "main" && target.ends_with(&format!("/{}", m)))
+        .max_by_key(|m| m.len())
+        .cloned()
+}
+
+/// Collapse `.` and `..` components without touching the filesystem
+fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            std::path::Component::CurDir => {}
+            std::path::Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Parse a Rust source file
+fn parse_rust_file(content: &str, file_path: &Path) -> Result<Vec<SourceItem>> {
+    let mut items = Vec::new();
+    let mut current_doc = String::new();
+    let lines: Vec<&str> = content.lines().collect();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        // Collect doc comments
+        if trimmed.starts_with("///") || trimmed.starts_with("//!") {
+            let doc_line = trimmed
+                .trim_start_matches("///")
+                .trim_start_matches("//!")
+                .trim();
+            if !current_doc.is_empty() {
+                current_doc.push('\n');
+            }
+            current_doc.push_str(doc_line);
+            continue;
+        }
+
+        // Skip regular comments
+        if trimmed.starts_with("//") {
+            continue;
+        }
+
+        // Parse item declarations
+        let visibility = if trimmed.starts_with("pub(crate)") {
+            Visibility::PublicCrate
+        } else if trimmed.starts_with("pub") {
+            Visibility::Public
+        } else {
+            Visibility::Private
+        };
+
+        // Remove visibility prefix for parsing
+        let without_vis = trimmed
+            .trim_start_matches("pub(crate)")
+            .trim_start_matches("pub")
+            .trim();
+
+        // Detect item type
+        if let Some(item) = parse_rust_item(
+            without_vis,
+            visibility,
+            &current_doc,
+            file_path,
+            line_num + 1,
+        ) {
+            items.push(item);
+        }
+
+        // Clear doc comment if this wasn't a blank line
+        if !trimmed.is_empty() {
+            current_doc.clear();
+        }
+    }
+
+    attach_signatures(&mut items, &lines, &Language::Rust);
+    assign_brace_parents(&mut items, &lines, "\"");
+
+    Ok(items)
+}
+
+/// Parse a single Rust item declaration
+fn parse_rust_item(
+    line: &str,
+    visibility: Visibility,
+    doc_comment: &str,
+    file_path: &Path,
+    line_number: usize,
+) -> Option<SourceItem> {
+    // Parse different item types
+    if line.starts_with("mod ") {
+        let name = extract_name(line, "mod ");
+        return Some(SourceItem {
+            kind: ItemKind::Module,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    if line.starts_with("struct ") {
+        let name = extract_name(line, "struct ");
+        return Some(SourceItem {
+            kind: ItemKind::Struct,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    if line.starts_with("enum ") {
+        let name = extract_name(line, "enum ");
+        return Some(SourceItem {
+            kind: ItemKind::Enum,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    if line.starts_with("trait ") {
+        let name = extract_name(line, "trait ");
+        return Some(SourceItem {
+            kind: ItemKind::Trait,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    if line.starts_with("impl ") || line.starts_with("impl<") {
+        // Extract impl name (struct being implemented or trait for struct)
+        let name = extract_impl_name(line);
+        return Some(SourceItem {
+            kind: ItemKind::Impl,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    if line.starts_with("fn ") || line.starts_with("async fn ") {
+        let prefix = if line.starts_with("async ") {
+            "async fn "
+        } else {
+            "fn "
+        };
+        let name = extract_name(line, prefix);
+        return Some(SourceItem {
+            kind: ItemKind::Function,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    if line.starts_with("const ") {
+        let name = extract_name(line, "const ");
+        return Some(SourceItem {
+            kind: ItemKind::Constant,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    if line.starts_with("type ") {
+        let name = extract_name(line, "type ");
+        return Some(SourceItem {
+            kind: ItemKind::Type,
+            name,
+            visibility,
+            doc_comment: non_empty_doc(doc_comment),
+            file_path: file_path.to_path_buf(),
+            line_number,
+            children: Vec::new(),
+            signature: None,
+            parent: None,
+            module: String::new(),
+        });
+    }
+
+    None
+}
+
+/// Extract name from a declaration line
+fn extract_name(line: &str, prefix: &str) -> String {
+    let after_prefix = line.strip_prefix(prefix).unwrap_or(line);
+    after_prefix
+        .split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Extract impl name (handles generic impls and trait impls)
+fn extract_impl_name(line: &str) -> String {
+    // Handle patterns like:
+    // impl Foo
+    // impl<T> Foo<T>
+    // impl Trait for Foo
+    // impl<T> Trait for Foo<T>
+
+    let line = line.trim_start_matches("impl").trim();
+
+    // Skip generic parameters
+    let line = if line.starts_with('<') {
+        // Find matching >
+        let mut depth = 0;
+        let mut end = 0;
+        for (i, c) in line.chars().enumerate() {
+            match c {
+                '<' => depth += 1,
+                '>' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = i + 1;
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        line[end..].trim()
+    } else {
+        line
+    };
+
+    // Check for "Trait for Type" pattern
+    if let Some(idx) = line.find(" for ") {
+        let trait_name = line[..idx].split('<').next().unwrap_or("").trim();
+        let type_name = line[idx + 5..]
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .next()
+            .unwrap_or("")
+            .trim();
+        return format!("{} for {}", trait_name, type_name);
+    }
+
+    // Just the type name
+    line.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+fn non_empty_doc(doc: &str) -> Option<String> {
+    if doc.is_empty() {
+        None
+    } else {
+        Some(doc.to_string())
+    }
+}
+
+/// Parse a Python source file
+fn parse_python_file(content: &str, file_path: &Path) -> Result<Vec<SourceItem>> {
+    let mut items = Vec::new();
+    let mut current_doc = String::new();
+    let mut in_docstring = false;
+    let mut docstring_delimiter = "";
+    let lines: Vec<&str> = content.lines().collect();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        // Handle docstrings (""" or ''')
+        if !in_docstring {
+            if trimmed.starts_with("\"\"\"") || trimmed.starts_with("'''") {
+                docstring_delimiter = if trimmed.starts_with("\"\"\"") {
+                    "\"\"\""
+                } else {
+                    "'''"
+                };
+                // Check if docstring ends on same line
+                let after_start = &trimmed[3..];
+                if after_start.contains(docstring_delimiter) {
+                    // Single-line docstring
+                    let end_idx = after_start.find(docstring_delimiter).unwrap();
+                    current_doc = after_start[..end_idx].to_string();
+                } else {
+                    in_docstring = true;
+                    current_doc = after_start.to_string();
+                }
+                continue;
+            }
+        } else {
+            if trimmed.contains(docstring_delimiter) {
+                let end_idx = trimmed.find(docstring_delimiter).unwrap();
+                if !current_doc.is_empty() {
+                    current_doc.push('\n');
+                }
+                current_doc.push_str(&trimmed[..end_idx]);
+                in_docstring = false;
+                continue;
+            } else {
+                if !current_doc.is_empty() {
+                    current_doc.push('\n');
+                }
+                current_doc.push_str(trimmed);
+                continue;
+            }
+        }
+
+        // Skip comments
+        if trimmed.starts_with('#') {
+            continue;
+        }
+
+        // Detect class definitions
+        if trimmed.starts_with("class ") {
+            let name = extract_python_name(trimmed, "class ");
+            items.push(SourceItem {
+                kind: ItemKind::Struct, // Use Struct for Python classes
+                name,
+                visibility: Visibility::Public,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Detect function definitions
+        if trimmed.starts_with("def ") || trimmed.starts_with("async def ") {
+            let prefix = if trimmed.starts_with("async ") {
+                "async def "
+            } else {
+                "def "
+            };
+            let name = extract_python_name(trimmed, prefix);
+            let visibility = if name.starts_with('_') && !name.starts_with("__") {
+                Visibility::Private
+            } else {
+                Visibility::Public
+            };
+            items.push(SourceItem {
+                kind: ItemKind::Function,
+                name,
+                visibility,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Clear doc if we hit a non-def/class line
+        if !trimmed.is_empty()
+            && !trimmed.starts_with("def ")
+            && !trimmed.starts_with("async def ")
+            && !trimmed.starts_with("class ")
+            && !trimmed.starts_with('@')
+        {
+            current_doc.clear();
+        }
+    }
+
+    attach_signatures(&mut items, &lines, &Language::Python);
+    assign_python_members(&mut items, &lines);
+
+    Ok(items)
+}
+
+/// Extract name from Python definition
+fn extract_python_name(line: &str, prefix: &str) -> String {
+    let after_prefix = line.strip_prefix(prefix).unwrap_or(line);
+    after_prefix
+        .split(|c: char| c == '(' || c == ':' || c.is_whitespace())
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Parse a TypeScript source file
+fn parse_typescript_file(content: &str, file_path: &Path) -> Result<Vec<SourceItem>> {
+    let mut items = Vec::new();
+    let mut current_doc = String::new();
+    let mut in_jsdoc = false;
+    let lines: Vec<&str> = content.lines().collect();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        // Handle JSDoc comments
+        if trimmed.starts_with("/**") {
+            in_jsdoc = true;
+            let after_start = trimmed.strip_prefix("/**").unwrap_or("").trim();
+            if after_start.ends_with("*/") {
+                // Single-line JSDoc
+                current_doc = after_start
+                    .strip_suffix("*/")
+                    .unwrap_or(after_start)
+                    .trim()
+                    .to_string();
+                in_jsdoc = false;
+            } else {
+                current_doc = after_start.to_string();
+            }
+            continue;
+        }
+
+        if in_jsdoc {
+            if trimmed.contains("*/") {
+                let before_end = trimmed.strip_suffix("*/").unwrap_or(trimmed);
+                let content_part = before_end.trim_start_matches('*').trim();
+                if !current_doc.is_empty() && !content_part.is_empty() {
+                    current_doc.push('\n');
+                }
+                current_doc.push_str(content_part);
+                in_jsdoc = false;
+            } else {
+                let content_part = trimmed.trim_start_matches('*').trim();
+                if !current_doc.is_empty() && !content_part.is_empty() {
+                    current_doc.push('\n');
+                }
+                current_doc.push_str(content_part);
+            }
+            continue;
+        }
+
+        // Skip regular comments
+        if trimmed.starts_with("//") {
+            continue;
+        }
+
+        // Determine visibility
+        let visibility = if trimmed.starts_with("export ") || trimmed.starts_with("public ") {
+            Visibility::Public
+        } else {
+            // Explicit `private` and unmarked members are both private in TypeScript
+            Visibility::Private
+        };
+
+        // Remove modifiers for parsing
+        let without_modifiers = trimmed
+            .trim_start_matches("export ")
+            .trim_start_matches("default ")
+            .trim_start_matches("public ")
+            .trim_start_matches("private ")
+            .trim_start_matches("protected ")
+            .trim_start_matches("static ")
+            .trim_start_matches("async ")
+            .trim_start_matches("declare ");
+
+        // Detect interfaces
+        if without_modifiers.starts_with("interface ") {
+            let name = extract_ts_name(without_modifiers, "interface ");
+            items.push(SourceItem {
+                kind: ItemKind::Trait, // Use Trait for interfaces
+                name,
+                visibility,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Detect classes
+        if without_modifiers.starts_with("class ") {
+            let name = extract_ts_name(without_modifiers, "class ");
+            items.push(SourceItem {
+                kind: ItemKind::Struct,
+                name,
+                visibility,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Detect type aliases
+        if without_modifiers.starts_with("type ") {
+            let name = extract_ts_name(without_modifiers, "type ");
+            items.push(SourceItem {
+                kind: ItemKind::Type,
+                name,
+                visibility,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Detect enums
+        if without_modifiers.starts_with("enum ") {
+            let name = extract_ts_name(without_modifiers, "enum ");
+            items.push(SourceItem {
+                kind: ItemKind::Enum,
+                name,
+                visibility,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Detect functions
+        if without_modifiers.starts_with("function ") {
+            let name = extract_ts_name(without_modifiers, "function ");
+            items.push(SourceItem {
+                kind: ItemKind::Function,
+                name,
+                visibility,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Detect const/let declarations (top-level functions as arrow functions)
+        if (without_modifiers.starts_with("const ") || without_modifiers.starts_with("let "))
+            && (without_modifiers.contains(" = (") || without_modifiers.contains(" = async ("))
+        {
+            let keyword = if without_modifiers.starts_with("const ") {
+                "const "
+            } else {
+                "let "
+            };
+            let name = extract_ts_name(without_modifiers, keyword);
+            items.push(SourceItem {
+                kind: ItemKind::Function,
+                name,
+                visibility,
+                doc_comment: non_empty_doc(&current_doc),
+                file_path: file_path.to_path_buf(),
+                line_number: line_num + 1,
+                children: Vec::new(),
+                signature: None,
+                parent: None,
+                module: String::new(),
+            });
+            current_doc.clear();
+        }
+
+        // Clear doc on non-declaration lines
+        if !trimmed.is_empty() && !trimmed.starts_with('@') {
+            current_doc.clear();
+        }
+    }
+
+    add_typescript_methods(&mut items, &lines, file_path);
+    attach_signatures(&mut items, &lines, &Language::TypeScript);
+
+    Ok(items)
+}
+
+/// Extract name from TypeScript definition
+fn extract_ts_name(line: &str, prefix: &str) -> String {
+    let after_prefix = line.strip_prefix(prefix).unwrap_or(line);
+    after_prefix
+        .split(|c: char| {
+            c == '<' || c == '(' || c == '{' || c == ':' || c == '=' || c.is_whitespace()
+        })
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Parse a Go source file
+fn parse_go_file(content: &str, file_path: &Path) -> Result<Vec<SourceItem>> {
+    let mut items = Vec::new();
+    let mut current_doc = String::new();
+    let lines: Vec<&str> = content.lines().collect();
+
+    for (line_num, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+
+        // Collect doc comments (// comments before declarations)
+        if trimmed.starts_with("//") {
+            let comment = trimmed.strip_prefix("//").unwrap_or("").trim();
+            if !current_doc.is_empty() {
+                current_doc.push('\n');
+            }
+            current_doc.push_str(comment);
+            continue;
+        }
+
+        // Determine visibility (Go uses capitalization)
+        fn is_exported(name: &str) -> bool {
+            name.chars().next().is_some_and(|c| c.is_uppercase())
+        }
+
+        // Detect type declarations (struct, interface)
+        if trimmed.starts_with("type ") {
+            // type Name struct { or type Name interface {
+            let parts: Vec<&str> = trimmed.split_whitespace().collect();
+            if parts.len() >= 3 {
+                let name = parts[1].to_string();
+                let kind = match parts[2] {
+                    "struct" => ItemKind::Struct,
+                    "interface" => ItemKind::Trait,
+                    _ => ItemKind::Type,
+                };
+                let visibility = if is_exported(&name) {
+                    Visibility::Public
+                } else {
+                    Visibility::Private
+                };
+                items.push(SourceItem {
+                    kind,
+                    name,
+                    visibility,
+                    doc_comment: non_empty_doc(&current_doc),
+                    file_path: file_path.to_path_buf(),
+                    line_number: line_num + 1,
+                    children: Vec::new(),
+                    signature: None,
+                    parent: None,
+                    module: String::new(),
+                });
+            }
+            current_doc.clear();
+        }
+
+        // Detect function declarations
+        if trimmed.starts_with("func ") {
+            // func Name(...) or func (receiver) Name(...)
+            let after_func = trimmed.strip_prefix("func ").unwrap_or("");
+
+            // Check for method (has receiver)
+            let name = if after_func.starts_with('(') {
+                // Method: func (r *Receiver) Name(...)
+                if let Some(close_paren) = after_func.find(')') {
+                    let after_receiver = after_func[close_paren + 1..].trim();
+                    extract_go_func_name(after_receiver)
+                } else {
+                    String::new()
+                }
+            } else {
+                // Regular function
+                extract_go_func_name(after_func)
+            };
+
+            if !name.is_empty() {
+                let visibility = if is_exported(&name) {
+                    Visibility::Public
+                } else {
+                    Visibility::Private
+                };
+                items.push(SourceItem {
+                    kind: ItemKind::Function,
+                    name,
+                    visibility,
+                    doc_comment: non_empty_doc(&current_doc),
+                    file_path: file_path.to_path_buf(),
+                    line_number: line_num + 1,
+                    children: Vec::new(),
+                    signature: None,
+                    parent: None,
+                    module: String::new(),
+                });
+            }
+            current_doc.clear();
+        }
+
+        // Detect const declarations
+        if trimmed.starts_with("const ") {
+            let after_const = trimmed.strip_prefix("const ").unwrap_or("");
+            let name = after_const
+                .split(|c: char| c == '=' || c == '(' || c.is_whitespace())
+                .next()
+                .unwrap_or("")
+                .to_string();
+
+            if !name.is_empty() && name != "(" {
+                let visibility = if is_exported(&name) {
+                    Visibility::Public
+                } else {
+                    Visibility::Private
+                };
+                items.push(SourceItem {
+                    kind: ItemKind::Constant,
+                    name,
+                    visibility,
+                    doc_comment: non_empty_doc(&current_doc),
+                    file_path: file_path.to_path_buf(),
+                    line_number: line_num + 1,
+                    children: Vec::new(),
+                    signature: None,
+                    parent: None,
+                    module: String::new(),
+                });
+            }
+            current_doc.clear();
+        }
+
+        // Clear doc on non-comment, non-declaration lines
+        if !trimmed.is_empty() && !trimmed.starts_with("//") {
+            current_doc.clear();
+        }
+    }
+
+    attach_signatures(&mut items, &lines, &Language::Go);
+    assign_go_receivers(&mut items);
+
+    Ok(items)
+}
+
+/// Extract function name from Go func declaration
+fn extract_go_func_name(s: &str) -> String {
+    s.split(|c: char| c == '(' || c == '[' || c.is_whitespace())
+        .next()
+        .unwrap_or("")
+        .to_string()
+}
+
+/// Fill in each item's signature from its declaration line onward
+fn attach_signatures(items: &mut [SourceItem], lines: &[&str], language: &Language) {
+    for item in items.iter_mut().filter(|i| i.signature.is_none()) {
+        item.signature = collect_signature(lines, item.line_number - 1, language).map(|(s, _)| s);
+    }
+}
+
+/// The declaration starting at line `start`, up to where its body begins,
+/// with whitespace collapsed, and the index of the line it ends on
+fn collect_signature(lines: &[&str], start: usize, language: &Language) -> Option<(String, usize)> {
+    let quotes = match language {
+        Language::Rust | Language::Go => "\"",
+        _ => "\"'`",
+    };
+    let count_angles = matches!(language, Language::Rust | Language::TypeScript);
+    let mut signature = String::new();
+    let mut depth = 0i32;
+    let mut quote: Option<char> = None;
+    let mut prev = ' ';
+
+    for (index, line) in lines.iter().enumerate().skip(start).take(20) {
+        let mut done = false;
+        for c in line.trim().chars() {
+            if let Some(q) = quote {
+                if c == q && prev != '\\' {
+                    quote = None;
+                }
+            } else if quotes.contains(c) {
+                quote = Some(c);
+            } else {
+                match c {
+                    '(' | '[' => depth += 1,
+                    ')' | ']' => depth -= 1,
+                    '<' if count_angles => depth += 1,
+                    // An arrow function's body follows `=>`
+                    '>' if prev == '=' && *language == Language::TypeScript && depth == 0 => {
+                        signature.pop();
+                        done = true;
+                    }
+                    '>' if prev == '=' && *language == Language::TypeScript => {}
+                    '>' if count_angles && prev != '-' => depth -= 1,
+                    '{' | ';' if depth == 0 && *language != Language::Python => done = true,
+                    ':' if depth == 0 && *language == Language::Python => done = true,
+                    _ => {}
+                }
+            }
+            if done {
+                break;
+            }
+            signature.push(c);
+            prev = c;
+        }
+
+        // Go and TypeScript declarations end with the line unless a
+        // parameter list is still open; Rust and Python run to `{`/`;`/`:`
+        let line_ends = depth <= 0 && matches!(language, Language::Go | Language::TypeScript);
+        if done || line_ends || index + 1 == lines.len() {
+            return Some((normalize_signature(&signature), index));
+        }
+        signature.push(' ');
+        prev = ' ';
+    }
+
+    None
+}
+
+fn normalize_signature(signature: &str) -> String {
+    signature
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(", )", ")")
+        .replace(",)", ")")
+        .replace(" )", ")")
+        .trim_end_matches(|c: char| c == '=' || c == ',' || c.is_whitespace())
+        .to_string()
+}
+
+/// Brace depth through source lines, ignoring braces in strings and comments
+struct BraceScanner {
+    quotes: &'static str,
+    in_block_comment: bool,
+}
+
+impl BraceScanner {
+    /// Braces on a line, in order (`true` for an opening brace)
+    fn braces(&mut self, line: &str) -> Vec<bool> {
+        let chars: Vec<char> = line.chars().collect();
+        let mut braces = Vec::new();
+        let mut quote: Option<char> = None;
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            let next = chars.get(i + 1).copied();
+            if self.in_block_comment {
+                if c == '*' && next == Some('/') {
+                    self.in_block_comment = false;
+                    i += 1;
+                }
+            } else if let Some(q) = quote {
+                if c == '\\' {
+                    i += 1;
+                } else if c == q {
+                    quote = None;
+                }
+            } else if c == '/' && next == Some('/') {
+                break;
+            } else if c == '/' && next == Some('*') {
+                self.in_block_comment = true;
+                i += 1;
+            } else if self.quotes.contains(c) {
+                quote = Some(c);
+            } else if c == '\'' && chars.get(i + 2) == Some(&'\'') {
+                // Char literal such as '{'
+                i += 2;
+            } else if c == '{' || c == '}' {
+                braces.push(c == '{');
+            }
+            i += 1;
+        }
+        braces
+    }
+}
+
+/// Lines directly inside the body of one of `containers` (keyed by the
+/// container's declaration line), mapped to that container's name
+fn brace_members(
+    lines: &[&str],
+    containers: &HashMap<usize, String>,
+    quotes: &'static str,
+) -> HashMap<usize, String> {
+    let mut scanner = BraceScanner {
+        quotes,
+        in_block_comment: false,
+    };
+    let mut members = HashMap::new();
+    let mut stack: Vec<(String, usize)> = Vec::new();
+    let mut pending: Option<String> = None;
+    let mut depth = 0;
+
+    for (index, line) in lines.iter().enumerate() {
+        if let Some((name, open_depth)) = stack.last() {
+            if *open_depth + 1 == depth {
+                members.insert(index, name.clone());
+            }
+        }
+        if let Some(name) = containers.get(&index) {
+            pending = Some(name.clone());
+        }
+        for open in scanner.braces(line) {
+            if open {
+                if let Some(name) = pending.take() {
+                    stack.push((name, depth));
+                }
+                depth += 1;
+            } else {
+                depth = depth.saturating_sub(1);
+                if stack.last().is_some_and(|(_, d)| *d == depth) {
+                    stack.pop();
+                }
+            }
+        }
+        // `struct Foo;` and `struct Foo(u32);` have no body
+        if pending.is_some() && line.trim_end().ends_with(';') {
+            pending = None;
+        }
+    }
+
+    members
+}
+
+/// Set the enclosing impl or trait of each Rust function declared directly in one
+fn assign_brace_parents(items: &mut [SourceItem], lines: &[&str], quotes: &'static str) {
+    let containers: HashMap<usize, String> = items
+        .iter()
+        .filter(|i| matches!(i.kind, ItemKind::Impl | ItemKind::Trait))
+        .map(|i| (i.line_number - 1, i.name.clone()))
+        .collect();
+    let members = brace_members(lines, &containers, quotes);
+    for item in items.iter_mut().filter(|i| i.kind == ItemKind::Function) {
+        item.parent = members.get(&(item.line_number - 1)).cloned();
+    }
+}
+
+/// Set Python methods' classes and read docstrings, which follow the
+/// declaration (the parser's preceding-string doc is a guess)
+fn assign_python_members(items: &mut [SourceItem], lines: &[&str]) {
+    let by_line: HashMap<usize, usize> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| (item.line_number - 1, index))
+        .collect();
+    // Enclosing definitions: (class name, or None for a function; indent)
+    let mut stack: Vec<(Option<String>, usize)> = Vec::new();
+
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            index += 1;
+            continue;
+        }
+        while stack.last().is_some_and(|(_, i)| *i >= indent) {
+            stack.pop();
+        }
+
+        let Some(&item_index) = by_line.get(&index) else {
+            index += 1;
+            continue;
+        };
+        let item = &mut items[item_index];
+        match stack.last() {
+            Some((Some(class), _)) if item.kind == ItemKind::Function => {
+                item.parent = Some(class.clone())
+            }
+            // Functions nested in other functions aren't part of the module's interface
+            Some((None, _)) => item.visibility = Visibility::Private,
+            _ => {}
+        }
+        let end = collect_signature(lines, index, &Language::Python)
+            .map(|(_, end)| end)
+            .unwrap_or(index);
+        item.doc_comment = python_docstring(lines, end + 1);
+        stack.push((
+            (item.kind == ItemKind::Struct).then(|| item.name.clone()),
+            indent,
+        ));
+        index = end + 1;
+    }
+}
+
+/// The docstring starting at the first non-blank line at or after `start`
+fn python_docstring(lines: &[&str], start: usize) -> Option<String> {
+    let (first_index, first) = lines
+        .iter()
+        .enumerate()
+        .skip(start)
+        .find(|(_, l)| !l.trim().is_empty())?;
+    let first = first.trim().trim_start_matches(['r', 'R', 'u', 'U']);
+    let delimiter = ["\"\"\"", "'''"]
+        .into_iter()
+        .find(|d| first.starts_with(d))?;
+    let first = &first[3..];
+
+    if let Some(end) = first.find(delimiter) {
+        return non_empty_doc(first[..end].trim());
+    }
+
+    let mut body = vec![first.trim_end()];
+    for line in &lines[first_index + 1..] {
+        if let Some(end) = line.find(delimiter) {
+            body.push(&line[..end]);
+            break;
+        }
+        body.push(line);
+    }
+    // Remove the indentation shared by the lines after the first
+    let common = body[1..]
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let doc = std::iter::once(body[0].trim())
+        .chain(
+            body[1..]
+                .iter()
+                .map(|l| l.get(common..).unwrap_or("").trim_end()),
+        )
+        .collect::<Vec<_>>()
+        .join("\n");
+    non_empty_doc(doc.trim())
+}
+
+/// Add TypeScript class methods, which the line parser doesn't see
+fn add_typescript_methods(items: &mut Vec<SourceItem>, lines: &[&str], file_path: &Path) {
+    let classes: HashMap<usize, String> = items
+        .iter()
+        .filter(|i| i.kind == ItemKind::Struct)
+        .map(|i| (i.line_number - 1, i.name.clone()))
+        .collect();
+    if classes.is_empty() {
+        return;
+    }
+
+    let mut members: Vec<(usize, String)> =
+        brace_members(lines, &classes, "\"'`").into_iter().collect();
+    members.sort();
+    for (index, class) in members {
+        let Some((name, visibility)) = typescript_method(lines[index].trim()) else {
+            continue;
+        };
+        items.push(SourceItem {
+            kind: ItemKind::Function,
+            name,
+            visibility,
+            doc_comment: preceding_jsdoc(lines, index),
+            file_path: file_path.to_path_buf(),
+            line_number: index + 1,
+            children: Vec::new(),
+            signature: None,
+            parent: Some(class),
+            module: String::new(),
+        });
+    }
+    items.sort_by_key(|i| i.line_number);
+}
+
+/// Method name and visibility if a class body line declares a method
+fn typescript_method(line: &str) -> Option<(String, Visibility)> {
+    let mut visibility = Visibility::Public;
+    let mut rest = line;
+    while let Some(word) = rest.split_whitespace().next() {
+        match word {
+            "private" | "protected" => visibility = Visibility::Private,
+            "public" | "static" | "async" | "readonly" | "abstract" | "override" | "get"
+            | "set" => {}
+            _ => break,
+        }
+        rest = rest[word.len()..].trim_start();
+    }
+
+    let rest = rest.trim_start_matches('*');
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || matches!(c, '_' | '$' | '#'))
+        .collect();
+    let keyword = matches!(
+        name.as_str(),
+        "if" | "for" | "while" | "switch" | "return" | "catch" | "function" | "super" | "this"
+    );
+    let after = rest[name.len()..].trim_start_matches('?');
+    if name.is_empty() || keyword || !(after.starts_with('(') || after.starts_with('<')) {
+        return None;
+    }
+    if name.starts_with('#') {
+        visibility = Visibility::Private;
+    }
+    Some((name, visibility))
+}
+
+/// The JSDoc comment ending just above line `index` (decorators in between are skipped)
+fn preceding_jsdoc(lines: &[&str], index: usize) -> Option<String> {
+    let mut end = index;
+    while end > 0 && lines[end - 1].trim().starts_with('@') {
+        end -= 1;
+    }
+    if end == 0 || !lines[end - 1].trim().ends_with("*/") {
+        return None;
+    }
+    let start = (0..end)
+        .rev()
+        .find(|&i| lines[i].trim().starts_with("/**"))?;
+    let doc = lines[start..end]
+        .iter()
+        .map(|l| {
+            l.trim()
+                .trim_start_matches("/**")
+                .trim_end_matches("*/")
+                .trim_start_matches('*')
+                .trim()
+        })
+        .filter(|l| !l.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n");
+    non_empty_doc(&doc)
+}
+
+/// Set each Go method's receiver type as its parent
+fn assign_go_receivers(items: &mut [SourceItem]) {
+    for item in items.iter_mut().filter(|i| i.kind == ItemKind::Function) {
+        let Some(receiver) = item
+            .signature
+            .as_deref()
+            .and_then(|s| s.strip_prefix("func ("))
+            .and_then(|s| s.split(')').next())
+        else {
+            continue;
+        };
+        let type_name = receiver
+            .split_whitespace()
+            .last()
+            .unwrap_or("")
+            .trim_start_matches('*')
+            .split('[')
+            .next()
+            .unwrap_or("");
+        if !type_name.is_empty() {
+            item.parent = Some(type_name.to_string());
+        }
+    }
+}
+
+/// Generate sections for a docs database of the given type: a module-by-module
+/// reference for API docs, the architecture overview for everything else
+pub fn generate_sections_for(
+    structure: &ProjectStructure,
+    doc_type: DocType,
+) -> Vec<GeneratedSection> {
+    match doc_type {
+        DocType::Api => crate::api_reference::generate_sections(structure),
+        _ => generate_sections(structure),
+    }
+}
+
+/// Generate documentation sections from project structure.
+/// Single-language projects get flat top-level sections; mixed-language
+/// projects get one top-level section per language with the same
+/// breakdown nested underneath.
+pub fn generate_sections(structure: &ProjectStructure) -> Vec<GeneratedSection> {
+    let mut sections = Vec::new();
+    let mut sort_order = 0;
+
+    // Overview section
+    sort_order += 1;
+    let mut overview = format!(
+        "This is a {} project.\n\n- **Files**: {}\n- **Lines of code**: {}\n",
+        structure.language_label(),
+        structure.file_count,
+        structure.total_lines
+    );
+    if structure.languages.len() > 1 {
+        overview.push_str("\n**Languages:**\n\n");
+        for group in &structure.languages {
+            overview.push_str(&format!(
+                "- {}: {} files, {} lines\n",
+                group.language.as_str(),
+                group.file_count,
+                group.total_lines
+            ));
+        }
+    }
+    sections.push(GeneratedSection {
+        section_id: "1".to_string(),
+        title: "Overview".to_string(),
+        level: 1,
+        sort_order,
+        content: overview,
+        generated: true,
+        source_file: None,
+    });
+
+    if structure.languages.len() <= 1 {
+        let dependencies = structure
+            .languages
+            .first()
+            .map(|g| g.dependencies.as_slice())
+            .unwrap_or(&[]);
+        push_item_sections(
+            &mut sections,
+            &mut sort_order,
+            &structure.modules,
+            dependencies,
+            None,
+            1,
+        );
+        return sections;
+    }
+
+    for (index, group) in structure.languages.iter().enumerate() {
+        sort_order += 1;
+        let section_id = format!("{}", index + 2);
+        sections.push(GeneratedSection {
+            section_id: section_id.clone(),
+            title: group.language.as_str().to_string(),
+            level: 1,
+            sort_order,
+            content: format!(
+                "{} code in the project ({} files, {} lines).",
+                group.language.as_str(),
+                group.file_count,
+                group.total_lines
+            ),
+            generated: true,
+            source_file: None,
+        });
+
+        push_item_sections(
+            &mut sections,
+            &mut sort_order,
+            &group.modules,
+            &group.dependencies,
+            Some(&section_id),
+            2,
+        );
+    }
+
+    sections
+}
+
+/// Push Modules / Data Types / Traits / Public Functions / Module Dependencies
+/// sections for a set of items. With no parent, groups are numbered by sort order
+/// at the top level; with a parent, groups are numbered sequentially beneath it.
+fn push_item_sections(
+    sections: &mut Vec<GeneratedSection>,
+    sort_order: &mut i32,
+    items: &[SourceItem],
+    dependencies: &[ModuleDependency],
+    parent_id: Option<&str>,
+    level: i32,
+) {
+    // Group items by kind
+    let mut structs = Vec::new();
+    let mut enums = Vec::new();
+    let mut traits = Vec::new();
+    let mut functions = Vec::new();
+    let mut modules = Vec::new();
+
+    for item in items {
+        match item.kind {
+            ItemKind::Struct => structs.push(item),
+            ItemKind::Enum => enums.push(item),
+            ItemKind::Trait => traits.push(item),
+            ItemKind::Function if item.visibility == Visibility::Public => functions.push(item),
+            ItemKind::Module => modules.push(item),
+            _ => {}
+        }
+    }
+
+    let mut group_count = 0;
+    let mut next_group_id = |sort_order: i32| {
+        group_count += 1;
+        match parent_id {
+            Some(parent) => format!("{}.{}", parent, group_count),
+            None => format!("{}", sort_order),
+        }
+    };
+
+    // Modules section
+    if !modules.is_empty() {
+        *sort_order += 1;
+        let module_list = modules
+            .iter()
+            .map(|m| format!("- `{}`", m.name))
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        sections.push(GeneratedSection {
+            section_id: next_group_id(*sort_order),
+            title: "Modules".to_string(),
+            level,
+            sort_order: *sort_order,
+            content: format!(
+                "The project is organized into the following modules:\n\n{}",
+                module_list
+            ),
+            generated: true,
+            source_file: None,
+        });
+    }
+
+    // Data Types section
+    if !structs.is_empty() || !enums.is_empty() {
+        *sort_order += 1;
+        let group_start = *sort_order;
+        let section_id = next_group_id(group_start);
+        sections.push(GeneratedSection {
+            section_id: section_id.clone(),
+            title: "Data Types".to_string(),
+            level,
+            sort_order: *sort_order,
+            content: "Key data structures used in the project.".to_string(),
+            generated: true,
+            source_file: None,
+        });
+
+        // Add structs
+        for item in &structs {
+            if item.visibility == Visibility::Public {
+                *sort_order += 1;
+                sections.push(GeneratedSection {
+                    section_id: format!("{}.{}", section_id, *sort_order - group_start),
+                    title: format!("{} (struct)", item.name),
+                    level: level + 1,
+                    sort_order: *sort_order,
+                    content: item_content(item),
+                    generated: true,
+                    source_file: Some(item.file_path.to_string_lossy().to_string()),
+                });
+            }
+        }
+
+        // Add enums
+        for item in &enums {
+            if item.visibility == Visibility::Public {
+                *sort_order += 1;
+                sections.push(GeneratedSection {
+                    section_id: format!("{}.{}", section_id, *sort_order - group_start),
+                    title: format!("{} (enum)", item.name),
+                    level: level + 1,
+                    sort_order: *sort_order,
+                    content: item_content(item),
+                    generated: true,
+                    source_file: Some(item.file_path.to_string_lossy().to_string()),
+                });
+            }
+        }
+    }
+
+    // Traits section
+    if !traits.is_empty() {
+        *sort_order += 1;
+        let group_start = *sort_order;
+        let section_id = next_group_id(group_start);
+        sections.push(GeneratedSection {
+            section_id: section_id.clone(),
+            title: "Traits".to_string(),
+            level,
+            sort_order: *sort_order,
+            content: "Trait definitions that define shared behavior.".to_string(),
+            generated: true,
+            source_file: None,
+        });
+
+        for item in &traits {
+            if item.visibility == Visibility::Public {
+                *sort_order += 1;
+                sections.push(GeneratedSection {
+                    section_id: format!("{}.{}", section_id, *sort_order - group_start),
+                    title: item.name.clone(),
+                    level: level + 1,
+                    sort_order: *sort_order,
+                    content: item_content(item),
+                    generated: true,
+                    source_file: Some(item.file_path.to_string_lossy().to_string()),
+                });
+            }
+        }
+    }
+
+    // Public Functions section
+    if !functions.is_empty() {
+        *sort_order += 1;
+        let group_start = *sort_order;
+        let section_id = next_group_id(group_start);
+        sections.push(GeneratedSection {
+            section_id: section_id.clone(),
+            title: "Public Functions".to_string(),
+            level,
+            sort_order: *sort_order,
+            content: "Public functions exposed by the project.".to_string(),
+            generated: true,
+            source_file: None,
+        });
+
+        for item in &functions {
+            *sort_order += 1;
+            sections.push(GeneratedSection {
+                section_id: format!("{}.{}", section_id, *sort_order - group_start),
+                title: format!("{}()", item.name),
+                level: level + 1,
+                sort_order: *sort_order,
+                content: item_content(item),
+                generated: true,
+                source_file: Some(item.file_path.to_string_lossy().to_string()),
+            });
+        }
+    }
+
+    // Module Dependencies section
+    if !dependencies.is_empty() {
+        *sort_order += 1;
+        sections.push(GeneratedSection {
+            section_id: next_group_id(*sort_order),
+            title: "Module Dependencies".to_string(),
+            level,
+            sort_order: *sort_order,
+            content: format!(
+                "How modules depend on each other (each arrow points to a module being used).\n\n{}",
+                dependency_diagram(dependencies)
+            ),
+            generated: true,
+            source_file: None,
+        });
+    }
+}
+
+/// Render module dependencies as a Mermaid flowchart
+fn dependency_diagram(dependencies: &[ModuleDependency]) -> String {
+    let node_id = |name: &str| {
+        let id: String = name
+            .chars()
+            .map(|c| if c.is_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("m_{}", id)
+    };
+
+    let nodes: BTreeSet<&str> = dependencies
+        .iter()
+        .flat_map(|d| [d.from.as_str(), d.to.as_str()])
+        .collect();
+
+    let mut diagram = String::from("```mermaid\ngraph LR\n");
+    for node in &nodes {
+        diagram.push_str(&format!("    {}[\"{}\"]\n", node_id(node), node));
+    }
+    for dep in dependencies {
+        diagram.push_str(&format!(
+            "    {} --> {}\n",
+            node_id(&dep.from),
+            node_id(&dep.to)
+        ));
+    }
+    diagram.push_str("```");
+    diagram
+}
+
+/// Section body for a single item: its doc comment, or where it is defined
+fn item_content(item: &SourceItem) -> String {
+    item.doc_comment.clone().unwrap_or_else(|| {
+        format!(
+            "Defined in `{}`",
+            item.file_path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+        )
+    })
+}
+
+/// A section to be inserted into the docs database
+#[derive(Debug)]
+pub struct GeneratedSection {
+    pub section_id: String,
+    pub title: String,
+    pub level: i32,
+    pub sort_order: i32,
+    pub content: String,
+    pub generated: bool,
+    pub source_file: Option<String>,
+}
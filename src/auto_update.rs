@@ -1,24 +1,165 @@
 // Auto-update - check for and apply pending updates at startup
+//
+// The background download (commands/update_check.rs) stages an update in
+// ~/.proj/pending_update with its minisign signature: a patch from the running
+// version when the release has one (made with `zstd --patch-from`), otherwise the
+// full release archive. Before the binary is swapped, the signature is checked
+// again and the new binary is built from the patch or taken out of the archive,
+// so nothing unsigned ever replaces proj. Nothing is applied on the "none"
+// channel, while updates are deferred, offline, or with --no-self-update.
 
 use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::time::SystemTime;
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
 use colored::Colorize;
+use minisign_verify::{PublicKey, Signature};
 
+use crate::config::UpdateConfig;
 use crate::paths::get_pending_update_dir;
 
+/// Set (to anything but 0) to turn self-updates off, like --no-self-update
+pub const NO_SELF_UPDATE_ENV: &str = "PROJ_NO_SELF_UPDATE";
+
+/// Staged release archive or patch; each is staged with its <name>.minisig signature
+pub const STAGED_ARCHIVE: &str = "proj.tar.gz";
+pub const STAGED_PATCH: &str = "proj.patch.zst";
+
+/// Key the release archives are signed with, built into official releases
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("PROJ_RELEASE_PUBLIC_KEY");
+
+/// Whether --no-self-update or $PROJ_NO_SELF_UPDATE turned self-updates off
+pub fn self_update_disabled() -> bool {
+    // Checked before clap parses the arguments, so look for the flag itself
+    std::env::args_os()
+        .skip(1)
+        .any(|arg| arg == "--no-self-update")
+        || std::env::var(NO_SELF_UPDATE_ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Whether updates may be downloaded and applied without being asked for
+pub fn automatic_updates(config: &UpdateConfig) -> bool {
+    !self_update_disabled()
+        && config.channel != "none"
+        && !config.deferred(now())
+        && !crate::config::offline()
+}
+
+/// Current Unix timestamp
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Check a release archive against its minisign signature. The key built into
+/// proj always has to accept it; `public_key` from update.json is an extra check on
+/// top of that, and only stands in for the built-in key in builds that have none.
+pub fn verify_signature(data: &[u8], signature: &str, public_key: Option<&str>) -> Result<()> {
+    // An unset repository variable still builds in an empty key
+    let built_in = RELEASE_PUBLIC_KEY.filter(|k| !k.trim().is_empty());
+    check_signature(data, signature, built_in, public_key)
+}
+
+fn check_signature(
+    data: &[u8],
+    signature: &str,
+    built_in: Option<&str>,
+    configured: Option<&str>,
+) -> Result<()> {
+    if built_in.is_none() && configured.is_none() {
+        return Err(anyhow!(
+            "This build of proj has no release signing key, so the download can't be verified. \
+             Set public_key in ~/.proj/update.json, or update through your package manager."
+        ));
+    }
+    let signature =
+        Signature::decode(signature).map_err(|e| anyhow!("Invalid signature file: {}", e))?;
+    if let Some(key) = built_in {
+        verify_with(key, data, &signature).context("Checked against the built-in key")?;
+    }
+    if let Some(key) = configured {
+        verify_with(key, data, &signature).context("Checked against public_key in update.json")?;
+    }
+    Ok(())
+}
+
+fn verify_with(key: &str, data: &[u8], signature: &Signature) -> Result<()> {
+    // The bare key or the two lines of a minisign.pub file
+    let key = PublicKey::from_base64(key.trim())
+        .or_else(|_| PublicKey::decode(key))
+        .map_err(|e| anyhow!("Invalid release signing key: {}", e))?;
+    key.verify(data, signature, false)
+        .map_err(|e| anyhow!("Signature check failed: {}", e))
+}
+
+/// Apply a patch made with `zstd --patch-from=<old> <new>` to the old binary. The
+/// frame checksum fails when `old` isn't the binary the patch was made from.
+pub fn apply_patch(old: &[u8], patch: &[u8]) -> Result<Vec<u8>> {
+    let mut decoder = zstd::stream::read::Decoder::with_dictionary(patch, old)?;
+    // Patches of whole binaries need a long window
+    decoder.window_log_max(31)?;
+    let mut binary = Vec::new();
+    decoder
+        .read_to_end(&mut binary)
+        .map_err(|e| anyhow!("Patch doesn't apply to this binary: {}", e))?;
+    Ok(binary)
+}
+
+/// Check the staged update's signature and produce the new binary from it (the
+/// running binary with the staged patch applied, or the one in the staged
+/// archive), returning the binary's path
+pub fn staged_binary(staging: &Path, public_key: Option<&str>) -> Result<PathBuf> {
+    let binary = staging.join("proj");
+    if staging.join(STAGED_PATCH).exists() {
+        let patch = read_signed(staging, STAGED_PATCH, public_key)?;
+        let current = fs::read(std::env::current_exe()?)?;
+        fs::write(&binary, apply_patch(&current, &patch)?)?;
+    } else {
+        let archive = read_signed(staging, STAGED_ARCHIVE, public_key)?;
+        let decoder = flate2::read::GzDecoder::new(&archive[..]);
+        tar::Archive::new(decoder)
+            .unpack(staging)
+            .map_err(|e| anyhow!("Failed to extract archive: {}", e))?;
+    }
+    if fs::metadata(&binary).map(|m| m.len()).unwrap_or(0) == 0 {
+        return Err(anyhow!("The staged update has no proj binary"));
+    }
+    Ok(binary)
+}
+
+/// A staged file, once its signature checks out
+fn read_signed(staging: &Path, name: &str, public_key: Option<&str>) -> Result<Vec<u8>> {
+    let data = fs::read(staging.join(name)).context("No staged update found")?;
+    let signature = fs::read_to_string(staging.join(format!("{}.minisig", name)))
+        .context("The staged update has no signature")?;
+    verify_signature(&data, &signature, public_key)?;
+    Ok(data)
+}
+
 /// Check for and apply a pending update at startup
 /// Returns Ok(true) if an update was applied (process will exit and re-exec)
 /// Returns Ok(false) if no pending update exists
 /// Returns Err if something went wrong (caller should continue normally)
 pub fn check_and_apply_pending() -> Result<bool> {
     let staging = get_pending_update_dir()?;
-    let pending_binary = staging.join("proj");
     let version_file = staging.join("version");
 
     // No pending update
-    if !pending_binary.exists() {
+    if !staging.exists() {
+        return Ok(false);
+    }
+    let config = UpdateConfig::load()?;
+    if !automatic_updates(&config) {
+        return Ok(false);
+    }
+    if !staging.join(STAGED_ARCHIVE).exists() && !staging.join(STAGED_PATCH).exists() {
+        // Staged by an older proj, without a signature to check
+        let _ = fs::remove_dir_all(&staging);
         return Ok(false);
     }
 
@@ -49,19 +190,26 @@ pub fn check_and_apply_pending() -> Result<bool> {
         .unwrap_or_else(|_| "unknown".to_string());
     let current_version = env!("CARGO_PKG_VERSION");
 
-    // Verify the staged binary exists and is executable
-    let pending_meta = fs::metadata(&pending_binary)?;
-    if pending_meta.len() == 0 {
-        // Empty file, clean up and skip
-        let _ = fs::remove_dir_all(&staging);
-        return Ok(false);
-    }
+    // Signature first; a download that fails it is thrown away
+    let pending_binary = match staged_binary(&staging, config.public_key.as_deref()) {
+        Ok(binary) => binary,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            eprintln!(
+                "{} Discarded the downloaded proj {}: {:#}",
+                "⚠".yellow(),
+                new_version,
+                e
+            );
+            return Ok(false);
+        }
+    };
 
     // Atomic replace: rename pending binary over current
     // On Unix, this is atomic if same filesystem
     if let Err(e) = fs::rename(&pending_binary, &current_exe) {
         // Rename failed (possibly different filesystem), try copy + delete
-        if let Err(_) = fs::copy(&pending_binary, &current_exe) {
+        if fs::copy(&pending_binary, &current_exe).is_err() {
             // Copy also failed, clean up and continue with old binary
             let _ = fs::remove_dir_all(&staging);
             return Err(e.into());
@@ -99,3 +247,125 @@ pub fn check_and_apply_pending() -> Result<bool> {
 
     std::process::exit(status.code().unwrap_or(0));
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    // A throwaway minisign key pair; the signature is of SIGNED_DATA, prehashed as
+    // `minisign -S` does by default
+    const TEST_PUBLIC_KEY: &str = "RWQBAgMEBQYHCOpKbGPinFIKvvVQexMuxfmVR3auvr57kkIe6mkURtIs";
+    const OTHER_PUBLIC_KEY: &str = "RWQIBwYFBAMCAf0XJDhaoMdbZPt4zWAvodmR/ev3axPFjtcC6sg16fYY";
+    const SIGNED_DATA: &[u8] = b"proj release archive\n";
+    const SIGNATURE: &str = "untrusted comment: signature from minisign secret key
+RUQBAgMEBQYHCNhOP8ihhZBg4HGAYvJ3RhlVI5FlOdf/l08Sm/jOB79Lu6OcVP/mecs6NU4SUF12pGii+YwaXYurUmHcVf9jfA4=
+trusted comment: timestamp:1760000000\tfile:proj.tar.gz
+kXNy96y4UrAz7BLyznlcpKD62Hotvsbu6+4C/cJ9LM3jTGGbz5VDkRQhGpVxzjZ+KkzudS5JAeeHC2cg/BtbBg==
+";
+
+    #[test]
+    fn signature_from_the_signing_key_verifies() {
+        check_signature(SIGNED_DATA, SIGNATURE, Some(TEST_PUBLIC_KEY), None).unwrap();
+        check_signature(SIGNED_DATA, SIGNATURE, None, Some(TEST_PUBLIC_KEY)).unwrap();
+    }
+
+    #[test]
+    fn minisign_pub_file_is_accepted_as_key() {
+        let file = format!(
+            "untrusted comment: minisign public key\n{}\n",
+            TEST_PUBLIC_KEY
+        );
+        check_signature(SIGNED_DATA, SIGNATURE, Some(&file), None).unwrap();
+    }
+
+    #[test]
+    fn tampered_data_is_rejected() {
+        let err = check_signature(
+            b"proj release archive!\n",
+            SIGNATURE,
+            Some(TEST_PUBLIC_KEY),
+            None,
+        )
+        .unwrap_err();
+        assert!(format!("{:#}", err).contains("Signature check failed"));
+    }
+
+    #[test]
+    fn signature_from_another_key_is_rejected() {
+        assert!(check_signature(SIGNED_DATA, SIGNATURE, Some(OTHER_PUBLIC_KEY), None).is_err());
+    }
+
+    #[test]
+    fn configured_key_cannot_replace_the_built_in_one() {
+        let err = check_signature(
+            SIGNED_DATA,
+            SIGNATURE,
+            Some(OTHER_PUBLIC_KEY),
+            Some(TEST_PUBLIC_KEY),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("built-in key"));
+    }
+
+    #[test]
+    fn configured_key_must_also_accept_the_signature() {
+        check_signature(
+            SIGNED_DATA,
+            SIGNATURE,
+            Some(TEST_PUBLIC_KEY),
+            Some(TEST_PUBLIC_KEY),
+        )
+        .unwrap();
+        let err = check_signature(
+            SIGNED_DATA,
+            SIGNATURE,
+            Some(TEST_PUBLIC_KEY),
+            Some(OTHER_PUBLIC_KEY),
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("update.json"));
+    }
+
+    #[test]
+    fn missing_and_malformed_keys_are_reported() {
+        let err = check_signature(SIGNED_DATA, SIGNATURE, None, None).unwrap_err();
+        assert!(err.to_string().contains("no release signing key"));
+        let err = check_signature(SIGNED_DATA, SIGNATURE, Some("not a key"), None).unwrap_err();
+        assert!(format!("{:#}", err).contains("Invalid release signing key"));
+        let err = check_signature(SIGNED_DATA, "garbage", Some(TEST_PUBLIC_KEY), None).unwrap_err();
+        assert!(err.to_string().starts_with("Invalid signature file"));
+    }
+
+    /// A patch from `old` to `new`, like `zstd --patch-from=old new`
+    fn make_patch(old: &[u8], new: &[u8]) -> Vec<u8> {
+        let mut encoder =
+            zstd::stream::write::Encoder::with_dictionary(Vec::new(), 19, old).unwrap();
+        encoder.include_checksum(true).unwrap();
+        encoder.write_all(new).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    #[test]
+    fn patch_rebuilds_the_new_binary() {
+        let old: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+        let mut new = old.clone();
+        new[1000..1016].copy_from_slice(b"proj 1.9.0 build");
+        new.extend_from_slice(b"appended section");
+
+        let patch = make_patch(&old, &new);
+        assert!(patch.len() < new.len() / 10);
+        assert_eq!(apply_patch(&old, &patch).unwrap(), new);
+    }
+
+    #[test]
+    fn patch_for_another_binary_is_rejected() {
+        let old: Vec<u8> = (0..64 * 1024).map(|i| (i % 251) as u8).collect();
+        let mut new = old.clone();
+        new[1000..1016].copy_from_slice(b"proj 1.9.0 build");
+        let patch = make_patch(&old, &new);
+
+        let other: Vec<u8> = (0..64 * 1024).map(|i| (i % 241) as u8).collect();
+        assert!(apply_patch(&other, &patch).is_err());
+    }
+}
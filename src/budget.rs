@@ -0,0 +1,48 @@
+// Token budgets - fit context output for AI agents under a token limit
+
+/// Rough token estimate (4 chars per token average)
+pub fn estimate_tokens(text: &str) -> usize {
+    (text.chars().count() + 3) / 4
+}
+
+/// Shorten text to about `max_tokens`, cutting at a word boundary and adding "..."
+pub fn ellipsize(text: &str, max_tokens: usize) -> String {
+    if estimate_tokens(text) <= max_tokens {
+        return text.to_string();
+    }
+    let max_chars = (max_tokens * 4).saturating_sub(3);
+    let cut: String = text.chars().take(max_chars).collect();
+    let cut = match cut.rfind(char::is_whitespace) {
+        Some(pos) if pos > max_chars / 2 => &cut[..pos],
+        _ => cut.as_str(),
+    };
+    format!("{}...", cut.trim_end())
+}
+
+/// Running total of tokens spent against a limit
+pub struct Budget {
+    pub max_tokens: usize,
+    pub used: usize,
+}
+
+impl Budget {
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            max_tokens,
+            used: 0,
+        }
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.max_tokens.saturating_sub(self.used)
+    }
+
+    /// Spend `tokens` if they fit; returns whether they did
+    pub fn take(&mut self, tokens: usize) -> bool {
+        if tokens > self.remaining() {
+            return false;
+        }
+        self.used += tokens;
+        true
+    }
+}
@@ -1,6 +1,6 @@
 // CLI module - Full implementation in Task #3
 
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Parser)]
 #[command(name = "proj")]
@@ -12,6 +12,40 @@ pub struct Cli {
     #[arg(long, global = true)]
     pub no_color: bool,
 
+    /// Log database, git, and network activity to stderr
+    #[arg(long, global = true)]
+    pub debug: bool,
+
+    /// Like --debug, plus every SQL statement with its timing
+    #[arg(long, global = true)]
+    pub trace: bool,
+
+    /// Write diagnostics to .tracking/logs/ instead of stderr (implies --debug)
+    #[arg(long, global = true)]
+    pub log_file: bool,
+
+    /// Stable, tab-separated output for scripts (status, tasks, session, check)
+    #[arg(long, global = true)]
+    pub porcelain: bool,
+
+    /// Use the project at this path instead of the current directory ($PROJ_PROJECT_ROOT)
+    #[arg(
+        long,
+        global = true,
+        value_name = "PATH",
+        num_args = 0..=1,
+        default_missing_value = "."
+    )]
+    pub project: Option<String>,
+
+    /// Print long listings directly instead of through $PAGER
+    #[arg(long, global = true)]
+    pub no_pager: bool,
+
+    /// Don't download or apply proj updates ($PROJ_NO_SELF_UPDATE)
+    #[arg(long, global = true)]
+    pub no_self_update: bool,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -38,7 +72,7 @@ pub enum Commands {
         /// Generate docs from source analysis
         #[arg(long)]
         docs_generate: bool,
-        /// Import docs from markdown files
+        /// Import docs from markdown, reStructuredText, AsciiDoc, or Org files
         #[arg(long)]
         docs_import: bool,
         /// Create skeleton documentation
@@ -59,6 +93,13 @@ pub enum Commands {
         /// Install shell hook for automatic session tracking (non-interactive)
         #[arg(long)]
         shell_hook: bool,
+        /// Start from a template: built-in (rust-cli) or ~/.proj/templates/<name>.json
+        #[arg(long)]
+        template: Option<String>,
+        /// Make this project a workspace that groups the projects below it
+        /// (also converts an existing project)
+        #[arg(long)]
+        workspace: bool,
     },
     /// Migrate existing project to proj format
     Migrate,
@@ -70,39 +111,121 @@ pub enum Commands {
         verbose: bool,
         #[arg(long)]
         full: bool,
+        /// Summarize every project in the workspace this project belongs to
+        #[arg(long, conflicts_with_all = ["quiet", "verbose", "full"])]
+        workspace: bool,
     },
     /// Enter project - silent if session exists, shows context if new session
     Enter,
+    /// Short, cached prompt segment (project, active session, open blockers) for PS1/starship
+    Prompt {
+        /// Custom layout with {name}, {session}, {blockers}, and {tasks}
+        #[arg(long)]
+        format: Option<String>,
+    },
     /// Detailed context for resuming work
     Resume {
         #[arg(long)]
         for_ai: bool,
+        /// Fit the JSON into about this many tokens (most important items first)
+        #[arg(long, requires = "for_ai")]
+        max_tokens: Option<usize>,
     },
     /// Session management
     Session(SessionCommands),
-    /// Log decisions/notes/blockers/questions
+    /// Log decisions/notes/blockers/questions, or browse recorded commits
     Log(LogCommands),
+    /// Blocker management
+    Blocker(BlockerCommands),
+    /// Question management
+    Question(QuestionCommands),
+    /// Context notes: goals, constraints, assumptions, requirements
+    Note(NoteCommands),
+    /// Decisions by topic; rename and merge topics
+    Decision(DecisionCommands),
+    /// Files attached to decisions, notes, blockers, questions, and tasks
+    Attachment(AttachmentCommands),
     /// Task management
     Task(TaskCommands),
     /// Shortcut for 'task list'
-    Tasks,
-    /// Search decisions and notes
+    Tasks(TaskListArgs),
+    /// Search decisions, notes, commits, and docs sections
     Context {
         topic: String,
         #[arg(long)]
         ranked: bool,
-        /// Show last 10 items chronologically across all tables
+        /// Show the latest items chronologically across all tables
         #[arg(long)]
         recent: bool,
+        /// Only items logged on this git branch (commits: those on the branch)
+        #[arg(long)]
+        branch: Option<String>,
+        /// Only items created on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only items logged by this person (part of the name is enough; commits: git author)
+        #[arg(long)]
+        author: Option<String>,
+        /// Maximum number of results (default: 20, or 10 with --recent)
+        #[arg(long)]
+        limit: Option<usize>,
+        /// Show the score breakdown for each ranked result
+        #[arg(short, long)]
+        verbose: bool,
+        /// Print the highest-ranked results that fit in this many tokens, as plain text for an LLM
+        #[arg(long, conflicts_with = "recent")]
+        max_tokens: Option<usize>,
+        /// Find items by meaning using stored embeddings (see 'proj embed build')
+        #[arg(long, conflicts_with_all = ["recent", "ranked", "max_tokens"])]
+        semantic: bool,
+        /// Use a named context profile: codegen, review, planning, or one from config.json
+        /// (--limit and --max-tokens override its values)
+        #[arg(long, conflicts_with_all = ["recent", "ranked", "semantic"])]
+        profile: Option<String>,
+    },
+    /// Full-text search across decisions, tasks, notes, blockers, questions, sessions, and commits
+    Search {
+        query: String,
+        /// Search every registered project
+        #[arg(long)]
+        all: bool,
+        /// Output results as JSON
+        #[arg(long)]
+        json: bool,
+        /// Maximum number of results
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+    /// Rebuild the full-text search index
+    Reindex,
+    /// Create tasks from TODO/FIXME/HACK comments and close those whose comment is gone
+    ScanTodos {
+        /// Show what would change without touching tasks
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Review session for missed logging (cleanup pass)
-    Review,
-    /// Show changes since last status
-    Delta,
+    Review {
+        /// Output the review and suggestions as JSON
+        #[arg(long)]
+        json: bool,
+        /// Log suggestions by number without prompting (e.g., --accept 1,3)
+        #[arg(long, value_delimiter = ',')]
+        accept: Vec<usize>,
+    },
+    /// Show what changed since the last check (tasks, decisions, blockers, commits)
+    Delta {
+        /// Output the changes as JSON
+        #[arg(long)]
+        json: bool,
+    },
     /// Compress old sessions
     Compress {
         #[arg(long)]
         auto: bool,
+        /// Summarize through the LLM in ~/.proj/llm.json (basic compression if unreachable)
+        #[arg(long)]
+        llm: bool,
     },
     /// Clean up stale items
     Cleanup {
@@ -110,40 +233,222 @@ pub enum Commands {
         auto: bool,
         #[arg(long, default_value = "30")]
         days: u32,
+        /// Run 'proj db optimize' afterwards
+        #[arg(long)]
+        optimize: bool,
+        /// Report what would be resolved, archived, purged, or compressed without changing anything
+        #[arg(long)]
+        dry_run: bool,
     },
     /// Upgrade database schema
     Upgrade {
+        /// Show pending changes and the full SQL plan without applying them
         #[arg(long)]
         info: bool,
         #[arg(long)]
         all: bool,
         #[arg(long)]
         auto: bool,
+        /// With --all, how many projects to upgrade at once (default: CPU count, up to 4)
+        #[arg(long, requires = "all")]
+        jobs: Option<usize>,
     },
     /// Register project in global registry
-    Register,
-    /// List registered projects
-    Registered,
+    Register {
+        /// Update a registered project's path after moving it
+        #[arg(long = "move", num_args = 2, value_names = ["OLD", "NEW"])]
+        move_paths: Option<Vec<String>>,
+    },
+    /// List registered projects with their last activity
+    Registered {
+        /// Remove missing and duplicate entries and pick up renamed projects
+        #[arg(long)]
+        prune: bool,
+    },
     /// Overview of all projects
-    Dashboard,
-    /// Generate AI context snapshot
-    Snapshot,
-    /// Export session history
+    Dashboard {
+        /// Aggregate tasks, blockers, and recent sessions across all projects (non-interactive)
+        #[arg(long, conflicts_with = "tui")]
+        all: bool,
+        /// Interactive terminal dashboard for the current project
+        #[arg(long)]
+        tui: bool,
+    },
+    /// Print a ready-to-paste system prompt for a new AI conversation
+    Prime {
+        /// Wrap for the target model: claude (XML tags), openai (markdown headings), or plain
+        #[arg(long, default_value = "plain")]
+        format: String,
+        /// Token budget for the whole prompt (most important parts first)
+        #[arg(long, default_value_t = 4000)]
+        max_tokens: usize,
+    },
+    /// Generate AI context snapshot, or bundle the project into one shareable file
+    Snapshot {
+        /// Write a .tar.zst with tracking data, docs, config, and a SUMMARY.md
+        #[arg(long, conflicts_with = "restore")]
+        bundle: bool,
+        /// Bundle file to write [default: <project>-snapshot-<date>.tar.zst]
+        #[arg(long, requires = "bundle")]
+        output: Option<std::path::PathBuf>,
+        /// Unpack a bundle into this project (creating one if there is none here)
+        #[arg(long, value_name = "FILE")]
+        restore: Option<std::path::PathBuf>,
+        /// With --restore, merge into a project that already has tracking data
+        #[arg(long, requires = "restore")]
+        merge: bool,
+    },
+    /// Summarize recent work for a standup (markdown for chat, or JSON)
+    Standup {
+        /// Look back this many days instead of since the last standup
+        #[arg(long)]
+        days: Option<u32>,
+        /// Include every registered project
+        #[arg(long)]
+        all: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Review writes queued by agent_writes: approval, then apply or reject them
+    Approve {
+        /// Queued writes to apply (or reject, with --reject)
+        ids: Vec<i64>,
+        /// Every queued write
+        #[arg(long, conflicts_with = "ids")]
+        all: bool,
+        /// Reject instead of applying
+        #[arg(long)]
+        reject: bool,
+    },
+    /// Review every insert, update, and delete made to tracked records, newest first
+    Audit {
+        /// Only changes on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only changes to one table: sessions, tasks, decisions, notes, blockers, questions, milestones, tags, links
+        #[arg(long)]
+        table: Option<String>,
+        /// Only changes to this record (needs --table)
+        #[arg(long, requires = "table")]
+        id: Option<i64>,
+        /// Only changes made during this session
+        #[arg(long)]
+        session: Option<i64>,
+        /// Maximum entries to show
+        #[arg(long, default_value = "50")]
+        limit: usize,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Velocity and activity metrics: sessions, tasks, blockers, decisions, commits
+    Stats {
+        /// Number of weeks to cover, including this one
+        #[arg(long, default_value_t = 12)]
+        weeks: u32,
+        /// Show how often each command ran, how long it took, and how much it printed
+        /// (recorded with usage_metrics on)
+        #[arg(long)]
+        usage: bool,
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Stream task, decision, session, and blocker changes as they happen
+    Watch {
+        /// Emit newline-delimited JSON events (for editor integrations)
+        #[arg(long)]
+        json: bool,
+        /// How often to check the database, in milliseconds
+        #[arg(long, default_value_t = 500)]
+        interval: u64,
+    },
+    /// Export session history and tracking data
     Export {
+        /// Output format: md, json, csv, sqlite, ics
         #[arg(long, default_value = "md")]
         format: String,
+        /// Tables to include (comma-separated): sessions, tasks, decisions, notes, blockers, questions, commits
+        #[arg(long, value_delimiter = ',')]
+        tables: Vec<String>,
+        /// Only include records created on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Write to a file (csv: a directory) instead of stdout
+        #[arg(long)]
+        output: Option<String>,
+    },
+    /// Import tracking data from a JSON export
+    Import {
+        /// File created by 'proj export --format json'
+        file: String,
+        /// Merge into a project that already has tracking data
+        #[arg(long)]
+        merge: bool,
+        /// Show what would be imported without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Merge another contributor's tracking database into this one
+    Merge {
+        /// Their .tracking/tracking.db (or a copy of it)
+        file: std::path::PathBuf,
+        /// Show what would be merged without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Sync tracking data with other machines through .tracking/sync/
+    Sync {
+        /// For records changed on both machines: newest, local, remote, ask
+        #[arg(long, default_value = "newest")]
+        strategy: String,
+        /// Show what would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Manual backup, or 'proj backup verify' to check the newest one
+    #[command(args_conflicts_with_subcommands = true)]
+    Backup {
+        #[command(subcommand)]
+        command: Option<BackupSubcommand>,
+        /// Also push to a remote: a name from ~/.proj/backup.json, "all", or an s3://, webdav://, sftp:// URL
+        #[arg(long)]
+        remote: Option<String>,
+        /// Encrypt the backup with the key in the OS keychain
+        #[arg(long)]
+        encrypt: bool,
+        /// Decrypt an encrypted backup (.enc) next to it
+        #[arg(long, value_name = "FILE", conflicts_with_all = ["remote", "encrypt"])]
+        decrypt: Option<String>,
+        /// Print the backup encryption key so it can be stored safely
+        #[arg(long)]
+        show_key: bool,
+    },
+    /// Preflight: database, AGENTS.md and its links, hooks, docs staleness, and registry
+    Check {
+        /// Print the results as JSON
+        #[arg(long)]
+        json: bool,
+        /// Fail on warnings too (outdated AGENTS.md, missing hooks, stale docs, ...)
+        #[arg(long)]
+        strict: bool,
+    },
+    /// Reports for CI jobs, such as a project health comment on pull requests
+    Ci(CiCommands),
+    /// Check databases, registry, and environment for problems
+    Doctor {
+        /// Repair what can be fixed safely (schema upgrade, orphaned references, stale registry entries)
+        #[arg(long)]
+        fix: bool,
     },
-    /// Manual backup
-    Backup,
-    /// Verify database integrity
-    Check,
     /// Add extension tables
     Extend {
         #[arg(long = "type")]
         extension_type: String,
     },
-    /// Archive completed project
-    Archive,
+    /// Archive completed project, or old items with 'proj archive items'
+    Archive(ArchiveCommands),
     /// Check for updates and optionally apply them
     Update {
         /// Download and apply the update immediately
@@ -152,45 +457,76 @@ pub enum Commands {
         /// Just check, don't start background download
         #[arg(long)]
         check: bool,
+        /// Release channel to update from: stable, beta (pre-releases too), or none
+        /// (no automatic checks)
+        #[arg(long, value_parser = ["stable", "beta", "none"])]
+        channel: Option<String>,
+        /// Hold off automatic updates for a while (12h, 7d, 2w), or 'off' to resume them
+        #[arg(long, value_name = "DURATION", conflicts_with = "apply")]
+        defer: Option<String>,
     },
     /// Release a new version (maintainer only)
     Release {
         /// Version to release (e.g., 1.4.0) - skips version selection prompt
         version: Option<String>,
-        /// Check release status and update formulas
+        /// Check release status and update the release targets in release.toml
         #[arg(long)]
         check: bool,
     },
-    /// Rollback a release or restore schema from backup
+    /// Rollback a release, or the project schema with down-migrations or from backup
     Rollback {
-        /// Version to rollback (defaults to latest release)
+        /// Version to rollback (defaults to latest release); with --schema, the schema version to return to
         version: Option<String>,
-        /// Restore project schema from backup instead of release rollback
+        /// Roll back the project schema instead of a release (defaults to undoing the last upgrade)
         #[arg(long)]
         schema: bool,
-        /// List available schema backups
+        /// List applied migrations and available schema backups
         #[arg(long)]
         list: bool,
+        /// With --schema, restore .tracking/ from a backup instead of running migrations down
+        #[arg(long, requires = "schema")]
+        backup: bool,
+        /// With --schema, show the SQL plan without applying it
+        #[arg(long, requires = "schema")]
+        info: bool,
+        /// With --schema, show which tables and rows would change without changing them
+        #[arg(long, requires = "schema")]
+        dry_run: bool,
     },
     /// Shell integration for automatic session tracking
     Shell(ShellCommands),
+    /// Git hooks that record commits and branch switches as they happen
+    Githook(GithookCommands),
+    /// Compare and update the proj instructions in AGENTS.md
+    Agents(AgentsCommands),
+    /// Embeddings for semantic search (proj context --semantic)
+    Embed(EmbedCommands),
+    /// Database maintenance
+    Db(DbCommands),
+    /// View and change config.json settings, or the global defaults projects inherit
+    Config(ConfigCommands),
     /// Uninstall proj from projects
     Uninstall {
         /// Remove shell hook only, keep project data
         #[arg(long)]
         shell: bool,
-        /// Remove .tracking/ from current project only
-        #[arg(long)]
-        project: bool,
+        /// Remove .tracking/ from current project only; set by the global --project
+        #[arg(skip)]
+        current: bool,
         /// Remove shell hook + .tracking/ from ALL registered projects
         #[arg(long)]
         all: bool,
         /// Skip confirmation prompts (for non-interactive use)
         #[arg(long, short = 'y')]
         force: bool,
+        /// Delete tracking data without saving an export bundle to ~/.proj/exports first
+        #[arg(long)]
+        no_export: bool,
     },
     /// Project documentation database
     Docs(DocsCommands),
+    /// .tracking/ directories set aside by uninstall and schema restores
+    Trash(TrashCommands),
 }
 
 #[derive(Parser)]
@@ -215,6 +551,227 @@ pub enum ShellSubcommand {
     Check,
 }
 
+#[derive(Parser)]
+pub struct CiCommands {
+    #[command(subcommand)]
+    pub command: CiSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum CiSubcommand {
+    /// Project health for a pull request: blockers, overdue tasks, docs staleness, and
+    /// the branch's commits against the decisions logged on it
+    Report {
+        /// Output format: md or json
+        #[arg(long, default_value = "md")]
+        format: String,
+        /// Branch or commit the pull request merges into (default: origin/$GITHUB_BASE_REF,
+        /// then origin's default branch)
+        #[arg(long)]
+        base: Option<String>,
+        /// Write the report to a file instead of stdout
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+        /// Comment the report on a pull request, replacing the last one posted (default:
+        /// the pull request in $GITHUB_REF)
+        #[arg(long, value_name = "PR", num_args = 0..=1)]
+        post: Option<Option<i64>>,
+        /// GitHub repo (owner/name) to post to, instead of the origin remote's
+        #[arg(long, requires = "post")]
+        repo: Option<String>,
+    },
+}
+
+#[derive(Parser)]
+pub struct EmbedCommands {
+    #[command(subcommand)]
+    pub command: EmbedSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum EmbedSubcommand {
+    /// Embed new and changed decisions, notes, and doc sections
+    Build {
+        /// Embed every item again, even if unchanged
+        #[arg(long)]
+        rebuild: bool,
+    },
+    /// Show the provider and how many items are embedded
+    Status,
+}
+
+#[derive(Subcommand)]
+pub enum BackupSubcommand {
+    /// Check a backup's integrity and compare its row counts with the live database
+    Verify {
+        /// Backup file to check (defaults to this project's newest backup)
+        file: Option<String>,
+    },
+}
+
+#[derive(Parser)]
+pub struct ArchiveCommands {
+    /// Without a subcommand, archives the whole project
+    #[command(subcommand)]
+    pub command: Option<ArchiveSubcommand>,
+}
+
+#[derive(Subcommand)]
+pub enum ArchiveSubcommand {
+    /// Move old completed tasks, superseded decisions, and resolved blockers to the archive
+    Items {
+        /// Only items closed more than this many days ago
+        #[arg(long, default_value_t = 90)]
+        older_than: u32,
+        /// Only this kind of item: task, decision, blocker
+        #[arg(long = "type", value_name = "TYPE")]
+        kind: Option<String>,
+        /// Show what would be archived without moving anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Move an archived item back
+    Restore {
+        /// Item type: task, decision, blocker
+        #[arg(value_name = "TYPE")]
+        kind: String,
+        /// Item ID
+        id: i64,
+    },
+    /// List archived items
+    List {
+        /// Only this kind of item: task, decision, blocker
+        #[arg(long = "type", value_name = "TYPE")]
+        kind: Option<String>,
+    },
+}
+
+#[derive(Parser)]
+pub struct DbCommands {
+    #[command(subcommand)]
+    pub command: DbSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum DbSubcommand {
+    /// Switch to WAL, rebuild search indexes, ANALYZE, and VACUUM
+    Optimize,
+}
+
+#[derive(Parser)]
+pub struct TrashCommands {
+    #[command(subcommand)]
+    pub command: TrashSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum TrashSubcommand {
+    /// List trashed .tracking/ directories and when they expire
+    List,
+    /// Put a trashed .tracking/ back in its project (the current one, if any, is trashed in its place)
+    Restore {
+        /// Trash entry, as shown by 'proj trash list'
+        id: String,
+        /// Restore into this directory instead of the project it came from
+        #[arg(long, value_name = "PATH")]
+        to: Option<String>,
+    },
+    /// Delete expired entries for good, or one entry, or everything with --all
+    Purge {
+        /// Trash entry to delete (defaults to the ones past trash_retention_days)
+        #[arg(conflicts_with = "all")]
+        id: Option<String>,
+        /// Delete every entry
+        #[arg(long)]
+        all: bool,
+        /// Skip the confirmation prompt
+        #[arg(long, short = 'y')]
+        force: bool,
+    },
+}
+
+#[derive(Parser)]
+pub struct ConfigCommands {
+    #[command(subcommand)]
+    pub command: ConfigSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum ConfigSubcommand {
+    /// Show every setting and where its value comes from
+    List {
+        /// Show ~/.proj/config.json instead of this project's config
+        #[arg(long)]
+        global: bool,
+    },
+    /// Print one setting (dotted keys reach nested values, e.g. ranking.tag_boost)
+    Get {
+        key: String,
+        #[arg(long)]
+        global: bool,
+    },
+    /// Change a setting (values are parsed as JSON, otherwise stored as text)
+    Set {
+        key: String,
+        value: String,
+        /// Set the default in ~/.proj/config.json for projects that don't override it
+        #[arg(long)]
+        global: bool,
+    },
+    /// Remove a setting so it falls back to the global or built-in default
+    Unset {
+        key: String,
+        #[arg(long)]
+        global: bool,
+    },
+    /// Open config.json in $VISUAL/$EDITOR and validate it on save
+    Edit {
+        #[arg(long)]
+        global: bool,
+    },
+}
+
+#[derive(Parser)]
+pub struct AgentsCommands {
+    #[command(subcommand)]
+    pub command: AgentsSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum AgentsSubcommand {
+    /// Show what 'proj agents update' would change
+    Diff,
+    /// Replace the proj instructions with the current template, keeping custom sections
+    Update,
+    /// Relink CLAUDE.md/GEMINI.md and rewrite .cursorrules, .windsurfrules, and CONVENTIONS.md from AGENTS.md
+    Sync,
+}
+
+#[derive(Parser)]
+pub struct GithookCommands {
+    #[command(subcommand)]
+    pub command: GithookSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum GithookSubcommand {
+    /// Install post-commit and post-checkout hooks in this repository
+    Install,
+    /// Remove proj's git hooks (other hook content is kept)
+    Uninstall,
+    /// Show git hook status
+    Status,
+    /// Called by the installed hooks
+    #[command(hide = true)]
+    Run {
+        /// Hook name (post-commit, post-checkout)
+        hook: String,
+        /// Arguments git passed to the hook
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+}
+
 #[derive(Parser)]
 pub struct SessionCommands {
     #[command(subcommand)]
@@ -224,46 +781,352 @@ pub struct SessionCommands {
 #[derive(Subcommand)]
 pub enum SessionSubcommand {
     /// Start new session
-    Start,
+    Start {
+        /// Start a named parallel session (e.g., "hotfix"), pausing the current one
+        #[arg(long)]
+        name: Option<String>,
+        /// Who is working in the session [default: $PROJ_AUTHOR, git user.name, or $USER]
+        #[arg(long)]
+        author: Option<String>,
+    },
+    /// Pause the active session so it can be resumed later
+    Pause,
+    /// Resume a paused session (pauses the current one, if any)
+    Resume {
+        /// Session ID to resume (see 'proj session list')
+        id: i64,
+    },
     /// End session with summary (1-3 sentences describing what was accomplished)
     End {
         /// What was accomplished this session (be specific, not generic)
-        summary: String,
+        #[arg(required_unless_present_any = ["suggest", "accept"])]
+        summary: Option<String>,
         /// Force end even if no activity was logged
         #[arg(long)]
         force: bool,
+        /// Skip the auto-commit for this session end, even if enabled in config.json
+        #[arg(long)]
+        no_auto_commit: bool,
+        /// Print a draft summary built from this session's decisions, tasks, and commits, without ending it
+        #[arg(long, conflicts_with_all = ["summary", "accept", "force", "no_auto_commit"])]
+        suggest: bool,
+        /// End the session with the draft summary
+        #[arg(long, conflicts_with = "summary")]
+        accept: bool,
+        /// With --suggest or --accept: polish the draft through the LLM in ~/.proj/llm.json
+        /// (the plain draft if unreachable)
+        #[arg(long)]
+        llm: bool,
     },
     /// List recent sessions
-    List,
+    List {
+        /// Only sessions with this status: active, paused, completed, abandoned
+        /// (comma-separated for several)
+        #[arg(long)]
+        status: Option<String>,
+        /// Only sessions started on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        #[command(flatten)]
+        page: PageArgs,
+    },
+    /// Close stale sessions in all registered projects (per each project's auto_close_policy)
+    Gc {
+        /// Show what would be closed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Parser)]
 pub struct LogCommands {
     #[command(subcommand)]
     pub command: LogSubcommand,
+    /// Attribute the entry to a past session (by ID)
+    #[arg(long, global = true)]
+    pub session: Option<i64>,
+    /// Backdate the entry (e.g., "2024-06-30 14:05", local time)
+    #[arg(long, global = true)]
+    pub at: Option<String>,
+    /// Who the entry is from [default: $PROJ_AUTHOR, git user.name, or $USER]
+    #[arg(long, global = true)]
+    pub author: Option<String>,
+    /// Attach a file to each entry logged (repeatable)
+    #[arg(long = "attach", value_name = "PATH", global = true)]
+    pub attach: Vec<std::path::PathBuf>,
+    /// Input saved with a queued command, read in place of stdin
+    #[arg(long, global = true, hide = true)]
+    pub stdin_text: Option<String>,
 }
 
 #[derive(Subcommand)]
 pub enum LogSubcommand {
-    /// Log a decision
+    /// Log a decision ('-' for any field reads it from stdin; '-' alone reads decisions)
     Decision {
         topic: String,
-        decision: String,
+        /// Required unless the topic is '-' alone
+        decision: Option<String>,
         rationale: Option<String>,
+        /// Tag the decision (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Link the decision to a docs section by its ID, e.g. 3.2 (repeatable)
+        #[arg(long = "section")]
+        sections: Vec<String>,
     },
-    /// Log a note
+    /// Log a note ('-' for any field reads it from stdin; '-' alone reads notes)
     Note {
         category: String,
-        title: String,
-        content: String,
+        /// Required unless the category is '-' alone
+        title: Option<String>,
+        /// Required unless the category is '-' alone
+        content: Option<String>,
+        /// Tag the note (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+    },
+    /// Log a blocker ('-' reads blockers from stdin, one per line)
+    Blocker {
+        description: String,
+        /// Task this blocker is holding up (marks it blocked)
+        #[arg(long)]
+        task: Option<i64>,
     },
-    /// Log a blocker
-    Blocker { description: String },
-    /// Log a question
+    /// Log a question ('-' reads questions from stdin, one per line)
     Question {
         question: String,
         context: Option<String>,
     },
+    /// Log a JSON array of decisions, notes, blockers, questions, and tasks in one transaction
+    Batch {
+        /// JSON file with the items, each with a "type"; '-' reads stdin
+        #[arg(long = "json", value_name = "FILE")]
+        file: String,
+        /// Check every item and report, without logging anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Browse recorded git commits, newest first (--author filters by commit author)
+    Commits {
+        /// Only commits on or after a date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only commits whose message names this task (#task-N, #tN, or Proj-Task: N)
+        #[arg(long)]
+        task: Option<i64>,
+        /// List the files each commit changed
+        #[arg(long)]
+        files: bool,
+        /// Maximum commits to show
+        #[arg(long, default_value = "20")]
+        limit: usize,
+    },
+}
+
+#[derive(Parser)]
+pub struct BlockerCommands {
+    #[command(subcommand)]
+    pub command: BlockerSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum BlockerSubcommand {
+    /// Resolve a blocker with a note on how it was resolved
+    Resolve {
+        id: i64,
+        resolution: String,
+        /// Move the linked task back to pending
+        #[arg(long)]
+        unblock: bool,
+    },
+    /// List active blockers
+    List,
+}
+
+#[derive(Parser)]
+pub struct QuestionCommands {
+    #[command(subcommand)]
+    pub command: QuestionSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum QuestionSubcommand {
+    /// Answer an open question
+    Answer { id: i64, answer: String },
+    /// List open questions
+    List,
+}
+
+#[derive(Parser)]
+pub struct NoteCommands {
+    #[command(subcommand)]
+    pub command: NoteSubcommand,
+    /// Input saved with a queued command, read in place of stdin
+    #[arg(long, global = true, hide = true)]
+    pub stdin_text: Option<String>,
+}
+
+#[derive(Subcommand)]
+pub enum NoteSubcommand {
+    /// Add a context note ('-' as the title alone reads notes from stdin)
+    Add {
+        /// Required unless --url or --from-clipboard gives one
+        title: Option<String>,
+        /// Markdown content; '-' reads stdin. Left out: stdin when piped, else $EDITOR
+        content: Option<String>,
+        /// Take the content from the clipboard (the title defaults to its first line)
+        #[arg(long, conflicts_with_all = ["content", "url"])]
+        from_clipboard: bool,
+        /// Save a link as a reference note, titled with the page's title
+        #[arg(long, value_name = "LINK")]
+        url: Option<String>,
+        /// With --url, also keep the text of the page
+        #[arg(long, requires = "url")]
+        readable: bool,
+        /// goal, constraint, assumption, requirement, or note (any unique prefix)
+        #[arg(long, short, default_value = "note", value_parser = parse_note_category)]
+        category: String,
+        /// Tag the note (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Who the note is from [default: $PROJ_AUTHOR, git user.name, or $USER]
+        #[arg(long)]
+        author: Option<String>,
+    },
+    /// List context notes, grouped by category
+    List {
+        /// Only notes in this category (any unique prefix)
+        #[arg(long, short, value_parser = parse_note_category)]
+        category: Option<String>,
+        /// active, outdated, archived, or all
+        #[arg(long, default_value = "active")]
+        status: String,
+        #[command(flatten)]
+        page: PageArgs,
+    },
+    /// Show a note with its full content and tags
+    Show { id: i64 },
+    /// Change a note's title, category, or content (no options: edit the content in $EDITOR)
+    Edit {
+        id: i64,
+        #[arg(long)]
+        title: Option<String>,
+        /// New category (any unique prefix)
+        #[arg(long, short, value_parser = parse_note_category)]
+        category: Option<String>,
+        /// New markdown content; '-' reads stdin
+        #[arg(long)]
+        content: Option<String>,
+    },
+    /// Archive a note so it no longer shows in status and context
+    Archive { id: i64 },
+}
+
+/// Categories a context note can have
+pub const NOTE_CATEGORIES: [&str; 5] = ["goal", "constraint", "assumption", "requirement", "note"];
+
+/// Resolve a note category from its name or a prefix ("req" → requirement). The
+/// categories start with different letters, so any prefix is unambiguous.
+pub fn parse_note_category(input: &str) -> Result<String, String> {
+    let input = input.trim().to_lowercase();
+    NOTE_CATEGORIES
+        .iter()
+        .find(|c| !input.is_empty() && c.starts_with(&input))
+        .map(|c| c.to_string())
+        .ok_or_else(|| format!("valid categories: {}", NOTE_CATEGORIES.join(", ")))
+}
+
+#[derive(Parser)]
+pub struct DecisionCommands {
+    #[command(subcommand)]
+    pub command: DecisionSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum DecisionSubcommand {
+    /// List decisions, newest first
+    List {
+        /// Group decisions under their topic
+        #[arg(long)]
+        by_topic: bool,
+        /// Only decisions on this topic (or one of its aliases)
+        #[arg(long)]
+        topic: Option<String>,
+        /// active, superseded, reversed, or all
+        #[arg(long, default_value = "active")]
+        status: String,
+        #[command(flatten)]
+        page: PageArgs,
+    },
+    /// List topics with their decision counts and aliases
+    Topics,
+    /// Rename a topic, including on decisions already logged (the old name becomes an alias)
+    Rename { from: String, to: String },
+    /// Merge topics into one; their decisions move to it and their names become aliases
+    Merge {
+        #[arg(required = true)]
+        from: Vec<String>,
+        /// The topic to merge into (created if it doesn't exist)
+        #[arg(long)]
+        into: String,
+    },
+    /// Write decisions as numbered ADR files (MADR), updating the ones proj wrote before
+    ExportAdr {
+        /// ADR directory [default: docs/adr in the project]
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+        /// Show which files would be written without writing them
+        #[arg(long)]
+        dry_run: bool,
+    },
+    /// Read an ADR directory into decisions, mapping ADR statuses to decision statuses
+    ImportAdr {
+        /// ADR directory [default: docs/adr in the project]
+        #[arg(long)]
+        dir: Option<std::path::PathBuf>,
+        /// Topic for ADRs that don't name one
+        #[arg(long, default_value = "architecture")]
+        topic: String,
+        /// Show what would be imported without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Parser)]
+pub struct AttachmentCommands {
+    #[command(subcommand)]
+    pub command: AttachmentSubcommand,
+}
+
+#[derive(Subcommand)]
+pub enum AttachmentSubcommand {
+    /// Attach files to an item already logged
+    Add {
+        /// task, decision, note, blocker, or question
+        kind: String,
+        id: i64,
+        #[arg(required = true)]
+        paths: Vec<std::path::PathBuf>,
+    },
+    /// List attachments, all or one item's
+    List {
+        /// task, decision, note, blocker, or question
+        kind: Option<String>,
+        #[arg(requires = "kind")]
+        id: Option<i64>,
+    },
+    /// Open an attachment with the system's default application
+    Open { id: i64 },
+    /// Write an attachment to a file
+    Save {
+        id: i64,
+        /// Where to write it [default: its file name, in the current directory]
+        #[arg(long, short)]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Delete an attachment
+    Remove { id: i64 },
 }
 
 #[derive(Parser)]
@@ -279,6 +1142,15 @@ pub enum TaskSubcommand {
         description: String,
         #[arg(long, default_value = "normal")]
         priority: String,
+        /// Due date (YYYY-MM-DD)
+        #[arg(long)]
+        due: Option<String>,
+        /// Tag the task (repeatable)
+        #[arg(long = "tag")]
+        tags: Vec<String>,
+        /// Who the task is from [default: $PROJ_AUTHOR, git user.name, or $USER]
+        #[arg(long)]
+        author: Option<String>,
     },
     /// Update an existing task
     Update {
@@ -291,9 +1163,75 @@ pub enum TaskSubcommand {
         priority: Option<String>,
         #[arg(long)]
         blocked_by: Option<String>,
+        /// With --status completed: refuse unless the task has linked commits and all
+        /// of them are still in git
+        #[arg(long)]
+        verify_commits: bool,
+    },
+    /// Show a task with its notes, tags, blockers, and linked commits
+    Show { id: i64 },
+    /// Find an active task by typing part of it, then show, start, or complete it
+    Pick {
+        /// Print the picked task's ID instead of asking what to do with it
+        #[arg(long, conflicts_with_all = ["start", "done"])]
+        print: bool,
+        /// Mark the picked task in progress
+        #[arg(long, conflicts_with = "done")]
+        start: bool,
+        /// Mark the picked task completed
+        #[arg(long)]
+        done: bool,
+    },
+    /// Edit a task's description, priority, or notes
+    Edit {
+        id: i64,
+        /// New description
+        #[arg(long)]
+        description: Option<String>,
+        #[arg(long)]
+        priority: Option<String>,
+        #[arg(long)]
+        notes: Option<String>,
+        /// Due date (YYYY-MM-DD), or "none" to clear it
+        #[arg(long)]
+        due: Option<String>,
+    },
+    /// Delete a task (e.g., one created by accident)
+    Delete {
+        id: i64,
+        /// Skip confirmation prompt (for non-interactive use)
+        #[arg(long, short = 'y')]
+        force: bool,
     },
     /// List tasks
-    List,
+    List(TaskListArgs),
+    /// Import tickets from Jira or Linear as read-only tasks
+    Import {
+        /// Tracker to import from
+        #[arg(long, value_parser = ["jira", "linear"], required_unless_present = "refresh")]
+        from: Option<String>,
+        /// Jira issues to import, as a JQL query
+        #[arg(long, conflicts_with = "team")]
+        jql: Option<String>,
+        /// Linear team key (e.g. ENG) whose issues to import
+        #[arg(long)]
+        team: Option<String>,
+        /// Re-run every saved import
+        #[arg(long, conflicts_with_all = ["from", "jql", "team"])]
+        refresh: bool,
+    },
+    /// Sync tasks with an issue tracker (tasks tagged 'publish' get issues)
+    Sync {
+        /// Tracker to sync with
+        #[arg(value_parser = ["github"])]
+        provider: String,
+        /// Repository as owner/name (default: from the origin remote)
+        #[arg(long)]
+        repo: Option<String>,
+        /// Show what would change without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
 }
 
 #[derive(Parser)]
@@ -309,13 +1247,17 @@ pub enum DocsSubcommand {
         /// Generate from source analysis (non-interactive)
         #[arg(long)]
         generate: bool,
-        /// Import from markdown files (non-interactive)
+        /// Import existing docs: .md, .rst, .adoc, .org (non-interactive)
         #[arg(long)]
         import: bool,
         /// Create skeleton documentation (non-interactive)
         #[arg(long)]
         new: bool,
+        /// Import an OpenAPI/Swagger spec (YAML or JSON) into an api docs database
+        #[arg(long, value_name = "SPEC")]
+        import_openapi: Option<std::path::PathBuf>,
         /// Documentation type: architecture, framework, guide, api, spec
+        /// (api generates a module-by-module API reference)
         #[arg(long, default_value = "architecture")]
         doc_type: String,
         /// Project name (defaults to directory name)
@@ -333,6 +1275,12 @@ pub enum DocsSubcommand {
         #[arg(long)]
         force: bool,
     },
+    /// Watch source files and update generated sections as they change
+    Watch {
+        /// How long to wait for more changes before updating, in milliseconds
+        #[arg(long, default_value_t = 300)]
+        debounce: u64,
+    },
     /// Search documentation
     Search {
         /// Search query
@@ -346,11 +1294,49 @@ pub enum DocsSubcommand {
         /// Output file (defaults to stdout)
         #[arg(long)]
         output: Option<String>,
+        /// Link glossary terms to a glossary appended to the export
+        #[arg(long)]
+        link_terms: bool,
+        /// List the decisions linked to each section after it (proj log decision --section)
+        #[arg(long)]
+        decisions: bool,
     },
     /// Display a section
     Show {
         /// Section ID to display (e.g., "1.2.3")
         section: Option<String>,
+        /// Table of contents: skip and cap entries
+        #[command(flatten)]
+        page: PageArgs,
+    },
+    /// Find a section by typing part of its title or ID, then show it
+    Pick {
+        /// Print the picked section's ID instead of showing it
+        #[arg(long)]
+        print: bool,
+    },
+    /// Report [[section:ID]] references to missing sections and missing source files
+    CheckLinks,
+    /// Save a copy of all sections, to compare with 'proj docs diff' later
+    Snapshot {
+        /// Snapshot name (defaults to the current date and time)
+        name: Option<String>,
+        /// List saved snapshots
+        #[arg(long, conflicts_with_all = ["name", "delete"])]
+        list: bool,
+        /// Delete a snapshot
+        #[arg(long, value_name = "NAME", conflicts_with = "name")]
+        delete: Option<String>,
+    },
+    /// Show which sections changed between two snapshots
+    Diff {
+        /// Older snapshot
+        from: String,
+        /// Newer snapshot, or "current" for the docs as they are now [default: current]
+        to: Option<String>,
+        /// Only list changed sections, without their line changes
+        #[arg(long)]
+        stat: bool,
     },
     /// Manage terminology
     Term(DocsTermCommands),
@@ -382,4 +1368,50 @@ pub enum DocsTermSubcommand {
         /// Search query
         query: String,
     },
+    /// Suggest terms from source types and recurring phrases in the docs
+    Extract {
+        /// Add every candidate without asking
+        #[arg(long, short = 'y')]
+        yes: bool,
+        /// Only list the candidates
+        #[arg(long)]
+        dry_run: bool,
+        /// Maximum number of candidates
+        #[arg(long, default_value_t = 25)]
+        limit: usize,
+        /// Mentions a phrase from the docs needs to be suggested
+        #[arg(long, default_value_t = 2)]
+        min_count: usize,
+    },
+}
+
+/// Filters for 'proj tasks' and 'proj task list'
+#[derive(Args)]
+pub struct TaskListArgs {
+    /// Only tasks with this tag
+    #[arg(long)]
+    pub tag: Option<String>,
+    /// Only tasks with this status: pending, in_progress, blocked, completed, cancelled,
+    /// or all (comma-separated for several) [default: open tasks]
+    #[arg(long)]
+    pub status: Option<String>,
+    /// Only tasks with this priority: low, normal, high, urgent (comma-separated for several)
+    #[arg(long)]
+    pub priority: Option<String>,
+    /// Only tasks created on or after this date (YYYY-MM-DD)
+    #[arg(long)]
+    pub since: Option<String>,
+    #[command(flatten)]
+    pub page: PageArgs,
+}
+
+/// Which part of a long list to show
+#[derive(Args)]
+pub struct PageArgs {
+    /// Show at most this many entries
+    #[arg(long)]
+    pub limit: Option<usize>,
+    /// Skip this many entries first
+    #[arg(long, default_value_t = 0)]
+    pub offset: usize,
 }
@@ -0,0 +1,488 @@
+// Agents command - compare and update the proj instructions in AGENTS.md
+//
+// The instructions proj maintains sit between marker comments that record which
+// template wrote them (`v2` for the built-in one, `user:<hash>` for a template in
+// ~/.proj/AGENTS.template.md). Everything outside the markers belongs to the user,
+// and so does any `###` section inside them whose heading ends with `<!-- custom -->`:
+// `proj agents update` replaces the rest and keeps those as they are.
+//
+// CLAUDE.md and GEMINI.md are links to AGENTS.md. Cursor, Windsurf, and Aider get
+// generated copies instead (.cursorrules, .windsurfrules, CONVENTIONS.md), rewritten
+// by `proj agents sync` and after every update.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use sha2::{Digest, Sha256};
+
+use crate::cli::{AgentsCommands, AgentsSubcommand};
+use crate::commands::init::{
+    link_agent_aliases, setup_project_agents, sync_agent_files, PROJECT_AGENTS_TEMPLATE,
+    PROJECT_AGENTS_TEMPLATE_VERSION,
+};
+use crate::error::ProjError;
+use crate::paths::{get_agents_template_path, get_project_root};
+
+/// Start of the managed block; the template label and " -->" follow
+const BEGIN: &str = "<!-- proj:agents begin ";
+
+/// End of the managed block
+const END: &str = "<!-- proj:agents end -->";
+
+/// Heading suffix that keeps a section through updates
+const CUSTOM: &str = "<!-- custom -->";
+
+/// Heading the instructions start with (and that files from before the markers have)
+const TRACKING_HEADING: &str = "## Project Tracking";
+
+/// Rules files generated from AGENTS.md: (file, tool that reads it)
+pub const RULES_FILES: [(&str, &str); 3] = [
+    (".cursorrules", "Cursor"),
+    (".windsurfrules", "Windsurf"),
+    ("CONVENTIONS.md", "Aider"),
+];
+
+/// First line of a generated rules file; a file without it is the user's own
+const GENERATED_HEADER: &str =
+    "<!-- Generated from AGENTS.md by proj. Edit AGENTS.md, then run 'proj agents sync'. -->";
+
+/// Aider only reads conventions it's told about
+const AIDER_CONFIG: &str = ".aider.conf.yml";
+
+/// The instructions proj maintains, and the label recorded in the begin marker
+pub struct Template {
+    pub label: String,
+    body: String,
+}
+
+impl Template {
+    /// ~/.proj/AGENTS.template.md if there is one, otherwise the built-in template
+    pub fn load() -> Result<Self> {
+        let path = get_agents_template_path()?;
+        if path.exists() {
+            let body = std::fs::read_to_string(&path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let hash = format!("{:x}", Sha256::digest(body.as_bytes()));
+            return Ok(Self {
+                label: format!("user:{}", &hash[..8]),
+                body,
+            });
+        }
+        let start = PROJECT_AGENTS_TEMPLATE
+            .find(TRACKING_HEADING)
+            .unwrap_or_default();
+        Ok(Self {
+            label: format!("v{}", PROJECT_AGENTS_TEMPLATE_VERSION),
+            body: PROJECT_AGENTS_TEMPLATE[start..].to_string(),
+        })
+    }
+
+    /// The instructions wrapped in their markers
+    pub fn block(&self) -> String {
+        format!(
+            "{}{} -->\n{}\n{}\n",
+            BEGIN,
+            self.label,
+            self.body.trim(),
+            END
+        )
+    }
+
+    /// A new AGENTS.md: the title and intro, then the instructions
+    pub fn fresh_file(&self) -> String {
+        let start = PROJECT_AGENTS_TEMPLATE
+            .find(TRACKING_HEADING)
+            .unwrap_or_default();
+        format!("{}{}", &PROJECT_AGENTS_TEMPLATE[..start], self.block())
+    }
+}
+
+/// Where the instructions sit in an AGENTS.md: byte range and the label that wrote
+/// them. Files from before the markers have an unlabeled "## Project Tracking"
+/// section that runs to the next "## " heading.
+fn find_block(content: &str) -> Option<(usize, usize, Option<String>)> {
+    if let Some(start) = content.find(BEGIN) {
+        let label_end = content[start..].find("-->")? + start;
+        let label = content[start + BEGIN.len()..label_end].trim().to_string();
+        let end = content[start..]
+            .find(END)
+            .map(|pos| start + pos + END.len())
+            .unwrap_or(content.len());
+        return Some((start, end, Some(label)));
+    }
+    let start = content.find(TRACKING_HEADING)?;
+    let after = start + TRACKING_HEADING.len();
+    let end = content[after..]
+        .find("\n## ")
+        .map(|pos| after + pos)
+        .unwrap_or(content.len());
+    Some((start, end, None))
+}
+
+/// Whether an AGENTS.md has proj's instructions at all
+pub fn has_instructions(content: &str) -> bool {
+    find_block(content).is_some()
+}
+
+/// The label of the template that wrote a file's instructions ("unversioned" for
+/// files from before the markers), if it differs from `template`
+pub fn outdated_label(content: &str, template: &Template) -> Option<String> {
+    let (_, _, label) = find_block(content)?;
+    match label {
+        Some(label) if label == template.label => None,
+        Some(label) => Some(label),
+        None => Some("unversioned".to_string()),
+    }
+}
+
+/// The file with its instructions replaced by `template` (appended if it has none),
+/// keeping sections marked custom. Also returns the headings of the sections kept.
+pub fn updated(content: &str, template: &Template) -> (String, Vec<String>) {
+    let Some((start, end, _)) = find_block(content) else {
+        return (
+            format!("{}\n\n{}", content.trim_end(), template.block()),
+            Vec::new(),
+        );
+    };
+    let old = &content[start..end];
+    let (body, kept) = merge(old, &template.body);
+    let block = Template {
+        label: template.label.clone(),
+        body,
+    }
+    .block();
+
+    let before = content[..start].trim_end();
+    let after = content[end..].trim_start();
+    let mut out = String::new();
+    if !before.is_empty() {
+        out.push_str(before);
+        out.push_str("\n\n");
+    }
+    out.push_str(&block);
+    if !after.is_empty() {
+        out.push('\n');
+        out.push_str(after);
+        if !after.ends_with('\n') {
+            out.push('\n');
+        }
+    }
+    (out, kept)
+}
+
+/// A `###` section: heading text without the custom marker, and its lines
+struct Part<'a> {
+    heading: Option<String>,
+    custom: bool,
+    lines: Vec<&'a str>,
+}
+
+/// Split instructions at their `###` headings (ignoring ones inside code fences)
+fn split(text: &str) -> Vec<Part<'_>> {
+    let mut parts = vec![Part {
+        heading: None,
+        custom: false,
+        lines: Vec::new(),
+    }];
+    let mut in_fence = false;
+    for line in text.lines() {
+        if line.starts_with(BEGIN) || line.starts_with(END) {
+            continue;
+        }
+        if line.trim_start().starts_with("```") {
+            in_fence = !in_fence;
+        }
+        if !in_fence && line.starts_with("### ") {
+            let custom = line.trim_end().ends_with(CUSTOM);
+            let heading = line
+                .trim_end()
+                .trim_end_matches(CUSTOM)
+                .trim_start_matches("### ")
+                .trim()
+                .to_string();
+            parts.push(Part {
+                heading: Some(heading),
+                custom,
+                lines: Vec::new(),
+            });
+        }
+        if let Some(part) = parts.last_mut() {
+            part.lines.push(line);
+        }
+    }
+    parts
+}
+
+/// New instructions, with each custom section from the old ones in place of the
+/// template's section of the same name, or after the section it followed before
+fn merge(old: &str, new: &str) -> (String, Vec<String>) {
+    let old_parts = split(old);
+    let mut kept = Vec::new();
+    let mut out: Vec<Vec<&str>> = Vec::new();
+    let mut headings: Vec<Option<String>> = Vec::new();
+
+    for part in split(new) {
+        match old_parts
+            .iter()
+            .find(|p| p.custom && p.heading.is_some() && p.heading == part.heading)
+        {
+            Some(custom) => {
+                kept.extend(custom.heading.clone());
+                out.push(custom.lines.clone());
+            }
+            None => out.push(part.lines),
+        }
+        headings.push(part.heading);
+    }
+
+    // Custom sections the template doesn't have, after the section they followed
+    for (i, part) in old_parts.iter().enumerate() {
+        if !part.custom || headings.contains(&part.heading) {
+            continue;
+        }
+        let anchor = old_parts[..i]
+            .iter()
+            .rev()
+            .find_map(|p| headings.iter().position(|h| *h == p.heading));
+        let at = anchor.map(|pos| pos + 1).unwrap_or(out.len());
+        out.insert(at, part.lines.clone());
+        headings.insert(at, part.heading.clone());
+        kept.extend(part.heading.clone());
+    }
+
+    let body = out
+        .iter()
+        .map(|lines| lines.join("\n").trim_end().to_string())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    (body, kept)
+}
+
+/// A generated rules file's content: the header, then AGENTS.md without the markers
+fn rules_content(agents: &str) -> String {
+    let lines: Vec<&str> = agents
+        .trim()
+        .lines()
+        .filter(|line| !line.starts_with(BEGIN) && !line.starts_with(END))
+        .collect();
+    format!("{}\n\n{}\n", GENERATED_HEADER, lines.join("\n"))
+}
+
+/// Write .cursorrules, .windsurfrules, and CONVENTIONS.md from AGENTS.md, backing up
+/// ones the user wrote to <file>.bak, and point a new .aider.conf.yml at
+/// CONVENTIONS.md. Returns the files written.
+pub fn write_rules_files(project_root: &Path) -> Result<Vec<&'static str>> {
+    let Ok(agents) = std::fs::read_to_string(project_root.join("AGENTS.md")) else {
+        return Ok(Vec::new());
+    };
+    let content = rules_content(&agents);
+
+    let mut written = Vec::new();
+    for (name, _) in RULES_FILES {
+        let path = project_root.join(name);
+        if let Ok(existing) = std::fs::read_to_string(&path) {
+            if existing == content {
+                continue;
+            }
+            if !existing.starts_with(GENERATED_HEADER) {
+                std::fs::rename(&path, project_root.join(format!("{}.bak", name)))?;
+            }
+        }
+        std::fs::write(&path, &content)
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        written.push(name);
+    }
+
+    let aider_config = project_root.join(AIDER_CONFIG);
+    if !aider_config.exists() {
+        std::fs::write(&aider_config, "read: CONVENTIONS.md\n")?;
+        written.push(AIDER_CONFIG);
+    }
+    Ok(written)
+}
+
+/// Whether an existing .aider.conf.yml doesn't mention CONVENTIONS.md
+fn aider_config_missing_read(project_root: &Path) -> bool {
+    std::fs::read_to_string(project_root.join(AIDER_CONFIG))
+        .is_ok_and(|config| !config.contains("CONVENTIONS.md"))
+}
+
+/// A reminder for `proj upgrade` when this project's AGENTS.md instructions came
+/// from another template than the current one
+pub fn upgrade_hint() -> Option<String> {
+    let root = get_project_root().ok()?;
+    let content = std::fs::read_to_string(root.join("AGENTS.md")).ok()?;
+    let template = Template::load().ok()?;
+    let found = outdated_label(&content, &template)?;
+    Some(format!(
+        "AGENTS.md instructions are {} (current template: {}). Review with '{}', apply with '{}'.",
+        found,
+        template.label,
+        "proj agents diff".cyan(),
+        "proj agents update".cyan()
+    ))
+}
+
+pub fn run(cmd: AgentsCommands) -> Result<()> {
+    let root = get_project_root()?;
+    match cmd.command {
+        AgentsSubcommand::Diff => diff(&root),
+        AgentsSubcommand::Update => update(&root),
+        AgentsSubcommand::Sync => sync(&root),
+    }
+}
+
+/// Show what `proj agents update` would change
+fn diff(root: &Path) -> Result<()> {
+    let template = Template::load()?;
+    let path = root.join("AGENTS.md");
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        println!(
+            "No AGENTS.md yet. '{}' would create one ({}).",
+            "proj agents update".cyan(),
+            template.label
+        );
+        return Ok(());
+    };
+    let (new, kept) = updated(&content, &template);
+    if new == content {
+        println!("AGENTS.md is up to date ({}).", template.label);
+        return Ok(());
+    }
+
+    let from = find_block(&content)
+        .map(|(_, _, label)| label.unwrap_or_else(|| "unversioned".to_string()))
+        .unwrap_or_else(|| "none".to_string());
+    println!(
+        "\n{} {} → {}\n",
+        "AGENTS.md instructions:".bold(),
+        from,
+        template.label
+    );
+    print_diff(&content, &new);
+    if !kept.is_empty() {
+        println!(
+            "\n{}",
+            format!("Custom sections kept: {}", kept.join(", ")).dimmed()
+        );
+    }
+    println!("\nApply with '{}'.", "proj agents update".cyan());
+    Ok(())
+}
+
+/// Replace the instructions with the current template, keeping custom sections
+fn update(root: &Path) -> Result<()> {
+    let template = Template::load()?;
+    let path = root.join("AGENTS.md");
+    if !path.exists() {
+        setup_project_agents(root)?;
+        println!(
+            "{} Created AGENTS.md ({}) with CLAUDE.md/GEMINI.md links and rules files for {}",
+            "✓".green(),
+            template.label,
+            RULES_FILES.map(|(_, tool)| tool).join(", ")
+        );
+        return Ok(());
+    }
+
+    let content = std::fs::read_to_string(&path)?;
+    let (new, kept) = updated(&content, &template);
+    if new == content {
+        println!("AGENTS.md is up to date ({}).", template.label);
+    } else {
+        // Write in place so aliases hard-linked to AGENTS.md follow along
+        std::fs::write(&path, new)?;
+        println!("{} Updated AGENTS.md to {}", "✓".green(), template.label);
+        if !kept.is_empty() {
+            println!("  Kept custom sections: {}", kept.join(", "));
+        }
+    }
+    for name in write_rules_files(root)? {
+        println!("{} Wrote {}", "✓".green(), name);
+    }
+    Ok(())
+}
+
+/// Bring CLAUDE.md, GEMINI.md, and the generated rules files in line with AGENTS.md
+fn sync(root: &Path) -> Result<()> {
+    if !root.join("AGENTS.md").exists() {
+        return Err(ProjError::NothingToDo(
+            "No AGENTS.md yet. Create one with 'proj agents update'.".to_string(),
+        )
+        .into());
+    }
+
+    // Without symlinks, CLAUDE.md/GEMINI.md are hard links or copies whose edits go
+    // into AGENTS.md first. Elsewhere a real file there is hand-written; linking backs it up.
+    if cfg!(windows) {
+        if let Some(name) = sync_agent_files(root)? {
+            println!("{} Synced {} edits into AGENTS.md", "✓".green(), name);
+        }
+    }
+    link_agent_aliases(root)?;
+
+    let written = write_rules_files(root)?;
+    for name in &written {
+        println!("{} Wrote {}", "✓".green(), name);
+    }
+    if aider_config_missing_read(root) {
+        println!(
+            "{} {} doesn't load CONVENTIONS.md. Add '{}' to it for Aider to follow the rules.",
+            "⚠".yellow(),
+            AIDER_CONFIG,
+            "read: CONVENTIONS.md".cyan()
+        );
+    }
+    if written.is_empty() {
+        println!("Agent rules files match AGENTS.md.");
+    }
+    Ok(())
+}
+
+/// Added and removed lines, each group under the section heading it falls in
+fn print_diff(old: &str, new: &str) {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut heading = "";
+    let mut shown = "";
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        let (line, change) = if i < a.len() && j < b.len() && a[i] == b[j] {
+            i += 1;
+            j += 1;
+            (b[j - 1], None)
+        } else if j >= b.len() || (i < a.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            i += 1;
+            (a[i - 1], Some(format!("- {}", a[i - 1]).red()))
+        } else {
+            j += 1;
+            (b[j - 1], Some(format!("+ {}", b[j - 1]).green()))
+        };
+        if line.starts_with("## ") || line.starts_with("### ") {
+            heading = line;
+        }
+        if let Some(change) = change {
+            if shown != heading {
+                if line != heading {
+                    println!("  {}", heading.dimmed());
+                }
+                shown = heading;
+            }
+            println!("    {}", change);
+        }
+    }
+}
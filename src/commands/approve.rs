@@ -0,0 +1,706 @@
+// Approve command - review writes queued by `agent_writes: approval`
+//
+// With that setting, commands that change the project and are run without a terminal
+// (AI agents, scripts) don't touch it: their command line is saved in pending_writes.
+// `proj approve` runs the ones you accept as if they had been run then, and marks
+// the rest rejected. Applying or rejecting needs a terminal, so an agent can't
+// approve its own writes.
+
+use anyhow::{Context, Result};
+use atty::Stream;
+use clap::Parser;
+use colored::Colorize;
+use dialoguer::Select;
+use rusqlite::Connection;
+
+use crate::cli::{
+    AgentsSubcommand, ArchiveSubcommand, AttachmentSubcommand, BackupSubcommand, BlockerSubcommand,
+    Cli, Commands, ConfigSubcommand, DecisionSubcommand, DocsSubcommand, DocsTermSubcommand,
+    EmbedSubcommand, GithookSubcommand, LogSubcommand, NoteSubcommand, QuestionSubcommand,
+    ShellSubcommand, TaskSubcommand, TrashSubcommand,
+};
+use crate::commands::{log, note, note_capture};
+use crate::config::ProjectConfig;
+use crate::database::{get_schema_version, open_database};
+use crate::error::ProjError;
+use crate::paths::{get_project_root, get_tracking_db_path, PROJECT_ROOT_ENV};
+use crate::porcelain;
+use crate::session::{get_active_session, resolve_author};
+use crate::stdin_input;
+
+/// A queued command
+struct PendingWrite {
+    pending_id: i64,
+    created_at: String,
+    args: Vec<String>,
+    session_id: Option<i64>,
+    author: Option<String>,
+    /// Why the last attempt to apply it failed
+    error: Option<String>,
+}
+
+impl PendingWrite {
+    /// The command line as it was run
+    fn command_line(&self) -> String {
+        let args: Vec<String> = self
+            .args
+            .iter()
+            .map(|arg| {
+                if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"') {
+                    format!("{:?}", arg)
+                } else {
+                    arg.clone()
+                }
+            })
+            .collect();
+        format!("proj {}", args.join(" "))
+    }
+}
+
+/// Whether a command should be queued instead of run: it changes the project, it
+/// isn't run from a terminal, and the project has `agent_writes: approval`
+pub fn should_queue(command: &Commands) -> bool {
+    is_agent_write(command)
+        && !atty::is(Stream::Stdin)
+        && ProjectConfig::load().is_ok_and(|c| c.agent_writes == "approval")
+}
+
+/// Every command that can change the project. Anything `is_read_only` doesn't
+/// name counts, so a new command is queued until it's listed there. Sessions are
+/// left alone: a queued `session end` would end whichever session is active when
+/// it's approved. So are the git hooks, which record commits and branch switches
+/// git has already made, and `proj approve`, which checks for a terminal itself.
+fn is_agent_write(command: &Commands) -> bool {
+    let exempt = match command {
+        Commands::Enter | Commands::Session(_) | Commands::Approve { .. } => true,
+        Commands::Githook(cmd) => matches!(cmd.command, GithookSubcommand::Run { .. }),
+        _ => false,
+    };
+    !exempt && !is_read_only(command)
+}
+
+/// Commands that only read tracking data and config.json, including the ones
+/// that write what they read to a file or print it for another tool
+fn is_read_only(command: &Commands) -> bool {
+    match command {
+        Commands::Status { .. }
+        | Commands::Prompt { .. }
+        | Commands::Resume { .. }
+        | Commands::Tasks(_)
+        | Commands::Context { .. }
+        | Commands::Search { .. }
+        | Commands::Delta { .. }
+        | Commands::Dashboard { .. }
+        | Commands::Prime { .. }
+        | Commands::Standup { .. }
+        | Commands::Audit { .. }
+        | Commands::Stats { .. }
+        | Commands::Watch { .. }
+        | Commands::Export { .. }
+        | Commands::Check { .. }
+        | Commands::Ci(_) => true,
+        Commands::Snapshot { restore, .. } => restore.is_none(),
+        Commands::Registered { prune } => !prune,
+        Commands::Review { accept, .. } => accept.is_empty(),
+        Commands::ScanTodos { dry_run }
+        | Commands::Cleanup { dry_run, .. }
+        | Commands::Import { dry_run, .. }
+        | Commands::Merge { dry_run, .. }
+        | Commands::Sync { dry_run, .. } => *dry_run,
+        Commands::Upgrade { info, .. } => *info,
+        Commands::Doctor { fix } => !fix,
+        Commands::Rollback {
+            list,
+            info,
+            dry_run,
+            ..
+        } => *list || *info || *dry_run,
+        Commands::Backup { command, .. } => {
+            matches!(command, Some(BackupSubcommand::Verify { .. }))
+        }
+        Commands::Update {
+            apply,
+            channel,
+            defer,
+            ..
+        } => !apply && channel.is_none() && defer.is_none(),
+        Commands::Release { check, .. } => *check,
+        Commands::Log(cmd) => matches!(cmd.command, LogSubcommand::Commits { .. }),
+        Commands::Task(cmd) => match &cmd.command {
+            TaskSubcommand::Show { .. } | TaskSubcommand::List(_) => true,
+            TaskSubcommand::Pick { start, done, .. } => !start && !done,
+            TaskSubcommand::Sync { dry_run, .. } => *dry_run,
+            _ => false,
+        },
+        Commands::Blocker(cmd) => matches!(cmd.command, BlockerSubcommand::List),
+        Commands::Question(cmd) => matches!(cmd.command, QuestionSubcommand::List),
+        Commands::Note(cmd) => matches!(
+            cmd.command,
+            NoteSubcommand::List { .. } | NoteSubcommand::Show { .. }
+        ),
+        Commands::Decision(cmd) => match &cmd.command {
+            DecisionSubcommand::List { .. }
+            | DecisionSubcommand::Topics
+            | DecisionSubcommand::ExportAdr { .. } => true,
+            DecisionSubcommand::ImportAdr { dry_run, .. } => *dry_run,
+            _ => false,
+        },
+        Commands::Attachment(cmd) => !matches!(
+            cmd.command,
+            AttachmentSubcommand::Add { .. } | AttachmentSubcommand::Remove { .. }
+        ),
+        Commands::Archive(cmd) => matches!(cmd.command, Some(ArchiveSubcommand::List { .. })),
+        Commands::Trash(cmd) => matches!(cmd.command, TrashSubcommand::List),
+        Commands::Config(cmd) => matches!(
+            cmd.command,
+            ConfigSubcommand::List { .. } | ConfigSubcommand::Get { .. }
+        ),
+        Commands::Shell(cmd) => matches!(
+            cmd.command,
+            ShellSubcommand::Status | ShellSubcommand::Check
+        ),
+        Commands::Githook(cmd) => matches!(cmd.command, GithookSubcommand::Status),
+        Commands::Agents(cmd) => matches!(cmd.command, AgentsSubcommand::Diff),
+        Commands::Embed(cmd) => matches!(cmd.command, EmbedSubcommand::Status),
+        Commands::Docs(cmd) => match &cmd.command {
+            DocsSubcommand::Status
+            | DocsSubcommand::Search { .. }
+            | DocsSubcommand::Export { .. }
+            | DocsSubcommand::Show { .. }
+            | DocsSubcommand::Pick { .. }
+            | DocsSubcommand::CheckLinks
+            | DocsSubcommand::Diff { .. } => true,
+            DocsSubcommand::Snapshot { list, .. } => *list,
+            DocsSubcommand::Term(term) => matches!(
+                term.command,
+                DocsTermSubcommand::List | DocsTermSubcommand::Search { .. }
+            ),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Save the current command line for `proj approve`. Log entries are pinned to the
+/// time they were queued and new entries to the author queuing them, so approving
+/// later doesn't change who or when they're from.
+pub fn queue(command: &Commands) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    require_pending_writes(&conn)?;
+
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let explicit_author = match command {
+        Commands::Log(cmd) => {
+            if cmd.session.is_none() && cmd.at.is_none() {
+                let now = chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string();
+                insert_option(&mut args, &["--at".to_string(), now]);
+            }
+            Some(cmd.author.clone())
+        }
+        Commands::Task(cmd) => match &cmd.command {
+            TaskSubcommand::Add { author, .. } => Some(author.clone()),
+            _ => None,
+        },
+        Commands::Note(cmd) => match &cmd.command {
+            NoteSubcommand::Add { author, .. } => Some(author.clone()),
+            _ => None,
+        },
+        _ => None,
+    };
+    let author = resolve_author(explicit_author.clone().flatten().as_deref());
+    if let (Some(None), Some(author)) = (&explicit_author, &author) {
+        insert_option(&mut args, &["--author".to_string(), author.clone()]);
+    }
+    // Piped input is gone by the time the write is approved (and approving in a
+    // terminal would open an editor), so it's read now and saved with the command.
+    // So is a batch file, which may have changed or gone by then, and the clipboard.
+    let saved_input = match command {
+        _ if reads_stdin(command) => Some(stdin_input::read()?),
+        Commands::Log(cmd) => match &cmd.command {
+            LogSubcommand::Batch { file, .. } => Some(
+                std::fs::read_to_string(file)
+                    .with_context(|| format!("Failed to read {}", file))?,
+            ),
+            _ => None,
+        },
+        Commands::Note(cmd) => match &cmd.command {
+            NoteSubcommand::Add {
+                from_clipboard: true,
+                ..
+            } => Some(note_capture::read_clipboard()?),
+            _ => None,
+        },
+        _ => None,
+    };
+    if let Some(text) = saved_input {
+        insert_option(&mut args, &[format!("--stdin-text={}", text)]);
+    }
+    check_queued(&conn, &args)?;
+    let session_id = get_active_session(&conn)?.map(|s| s.session_id);
+
+    conn.execute(
+        "INSERT INTO pending_writes (args, session_id, author) VALUES (?1, ?2, ?3)",
+        rusqlite::params![serde_json::to_string(&args)?, session_id, author],
+    )?;
+    let pending_id = conn.last_insert_rowid();
+
+    if porcelain::enabled() {
+        porcelain::print(&["pending", &pending_id.to_string()]);
+        return Ok(());
+    }
+    println!(
+        "{} Queued for approval (#{}): this project has agent_writes set to approval.",
+        "⏸".yellow(),
+        pending_id
+    );
+    println!(
+        "  Run '{}' in a terminal to review it.",
+        "proj approve".cyan()
+    );
+    Ok(())
+}
+
+/// Parse a command line about to be queued and read its saved input the way running
+/// it would, so a write that can only fail is refused now rather than at approval
+fn check_queued(conn: &Connection, args: &[String]) -> Result<()> {
+    let cli = parse_saved(args)?;
+    match &cli.command {
+        Commands::Log(cmd) => log::check_input(conn, cmd),
+        Commands::Note(cmd) => note::check_input(cmd),
+        _ => Ok(()),
+    }
+}
+
+/// A saved command line, parsed as `proj` would parse it
+fn parse_saved(args: &[String]) -> Result<Cli> {
+    Ok(Cli::try_parse_from(
+        std::iter::once("proj".to_string()).chain(args.iter().cloned()),
+    )?)
+}
+
+/// Whether the command reads stdin: a field given as '-', or note content left out
+fn reads_stdin(command: &Commands) -> bool {
+    match command {
+        Commands::Log(cmd) => match &cmd.command {
+            LogSubcommand::Decision {
+                topic,
+                decision,
+                rationale,
+                ..
+            } => [
+                Some(topic.as_str()),
+                decision.as_deref(),
+                rationale.as_deref(),
+            ]
+            .contains(&Some("-")),
+            LogSubcommand::Note {
+                category,
+                title,
+                content,
+                ..
+            } => [
+                Some(category.as_str()),
+                title.as_deref(),
+                content.as_deref(),
+            ]
+            .contains(&Some("-")),
+            LogSubcommand::Blocker { description, .. } => description == "-",
+            LogSubcommand::Question { question, context } => {
+                question == "-" || context.as_deref() == Some("-")
+            }
+            LogSubcommand::Batch { file, .. } => file == "-",
+            LogSubcommand::Commits { .. } => false,
+        },
+        Commands::Note(cmd) => match &cmd.command {
+            NoteSubcommand::Add {
+                title,
+                content,
+                from_clipboard,
+                url,
+                ..
+            } => {
+                title.as_deref() == Some("-")
+                    || content.as_deref() == Some("-")
+                    || (content.is_none() && !from_clipboard && url.is_none())
+            }
+            NoteSubcommand::Edit {
+                title,
+                category,
+                content,
+                ..
+            } => match content.as_deref() {
+                Some(c) => c == "-",
+                None => title.is_none() && category.is_none(),
+            },
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Add an option to a saved command line. Options have to come before a `--`.
+fn insert_option(args: &mut Vec<String>, option: &[String]) {
+    let at = args.iter().position(|a| a == "--").unwrap_or(args.len());
+    args.splice(at..at, option.iter().cloned());
+}
+
+pub fn run(ids: Vec<i64>, all: bool, reject: bool) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    require_pending_writes(&conn)?;
+    let pending = load_pending(&conn)?;
+    let interactive = atty::is(Stream::Stdin);
+
+    if ids.is_empty() && !all {
+        if reject {
+            return Err(ProjError::InvalidInput(
+                "--reject needs the IDs to reject, or --all".to_string(),
+            )
+            .into());
+        }
+        if interactive && !porcelain::enabled() && !pending.is_empty() {
+            return review(&conn, &pending);
+        }
+        list(&pending);
+        return Ok(());
+    }
+
+    if !interactive {
+        return Err(ProjError::InvalidInput(
+            "Applying or rejecting queued writes needs a terminal".to_string(),
+        )
+        .into());
+    }
+    let mut selected: Vec<&PendingWrite> = if all {
+        pending.iter().collect()
+    } else {
+        ids.iter()
+            .map(|id| {
+                pending
+                    .iter()
+                    .find(|p| p.pending_id == *id)
+                    .ok_or_else(|| ProjError::InvalidInput(format!("No queued write #{}", id)))
+            })
+            .collect::<std::result::Result<_, _>>()?
+    };
+    if selected.is_empty() {
+        return Err(ProjError::NothingToDo("No writes awaiting approval.".to_string()).into());
+    }
+    // Queue order, so a task is added before the update that follows it
+    selected.sort_by_key(|p| p.pending_id);
+    selected.dedup_by_key(|p| p.pending_id);
+
+    let mut failed = 0;
+    for p in selected {
+        if reject {
+            reject_one(&conn, p)?;
+        } else if !apply_one(&conn, p)? {
+            failed += 1;
+        }
+    }
+    if failed > 0 {
+        anyhow::bail!("{} queued write(s) failed and are still queued", failed);
+    }
+    Ok(())
+}
+
+/// Walk through the queue, asking about each write
+fn review(conn: &Connection, pending: &[PendingWrite]) -> Result<()> {
+    let choices = ["Apply", "Reject", "Skip", "Stop"];
+    for p in pending {
+        println!();
+        print_entry(p);
+        let choice = Select::new()
+            .with_prompt("Apply this?")
+            .items(&choices)
+            .default(0)
+            .interact()?;
+        match choice {
+            0 => {
+                apply_one(conn, p)?;
+            }
+            1 => reject_one(conn, p)?,
+            2 => continue,
+            _ => break,
+        }
+    }
+    Ok(())
+}
+
+/// Run a queued command; false (and the write stays queued) if it fails
+fn apply_one(conn: &Connection, p: &PendingWrite) -> Result<bool> {
+    println!("{} #{} {}", "→".cyan(), p.pending_id, p.command_line());
+    match replay(&p.args) {
+        Ok(()) => {
+            conn.execute(
+                "UPDATE pending_writes SET status = 'applied', resolved_at = datetime('now'), error = NULL
+                 WHERE pending_id = ?1",
+                [p.pending_id],
+            )?;
+            Ok(true)
+        }
+        Err(e) => {
+            let message = format!("{:#}", e);
+            let message = message.lines().next().unwrap_or_default();
+            conn.execute(
+                "UPDATE pending_writes SET error = ?1 WHERE pending_id = ?2",
+                rusqlite::params![message, p.pending_id],
+            )?;
+            println!(
+                "{} #{} failed, still queued: {}",
+                "✗".red(),
+                p.pending_id,
+                message
+            );
+            Ok(false)
+        }
+    }
+}
+
+/// Run a saved command line the way `proj` would have run it, global options
+/// (--project, --porcelain, --no-color, ...) included, then put back the ones
+/// `proj approve` itself runs with
+fn replay(args: &[String]) -> Result<()> {
+    let mut cli = parse_saved(args)?;
+    // It was queued in this project, whatever directory the path was relative to
+    if cli.project.is_some() {
+        cli.project = Some(get_project_root()?.to_string_lossy().to_string());
+    }
+    let project = std::env::var_os(PROJECT_ROOT_ENV);
+    let porcelain = porcelain::enabled();
+    let colorize = colored::control::SHOULD_COLORIZE.should_colorize();
+
+    crate::apply_global_options(&mut cli);
+    let result = crate::dispatch(cli.command);
+
+    match project {
+        Some(dir) => std::env::set_var(PROJECT_ROOT_ENV, dir),
+        None => std::env::remove_var(PROJECT_ROOT_ENV),
+    }
+    porcelain::set(porcelain);
+    colored::control::set_override(colorize);
+    result
+}
+
+fn reject_one(conn: &Connection, p: &PendingWrite) -> Result<()> {
+    conn.execute(
+        "UPDATE pending_writes SET status = 'rejected', resolved_at = datetime('now')
+         WHERE pending_id = ?1",
+        [p.pending_id],
+    )?;
+    println!(
+        "{} Rejected #{}: {}",
+        "✗".red(),
+        p.pending_id,
+        p.command_line()
+    );
+    Ok(())
+}
+
+fn list(pending: &[PendingWrite]) {
+    if porcelain::enabled() {
+        for p in pending {
+            porcelain::print(&[
+                &p.pending_id.to_string(),
+                &p.created_at,
+                &p.session_id.map(|id| id.to_string()).unwrap_or_default(),
+                p.author.as_deref().unwrap_or_default(),
+                &p.command_line(),
+            ]);
+        }
+        return;
+    }
+    if pending.is_empty() {
+        println!("No writes awaiting approval.");
+        return;
+    }
+    println!("\n{} ({}):", "Awaiting approval".bold(), pending.len());
+    for p in pending {
+        println!();
+        print_entry(p);
+    }
+    println!(
+        "\nApply with '{}', or reject with '{}'.",
+        "proj approve <id>... | --all".cyan(),
+        "proj approve --reject <id>...".cyan()
+    );
+}
+
+fn print_entry(p: &PendingWrite) {
+    let mut queued = vec![p.created_at.get(..16).unwrap_or(&p.created_at).to_string()];
+    if let Some(id) = p.session_id {
+        queued.push(format!("session #{}", id));
+    }
+    if let Some(author) = &p.author {
+        queued.push(author.clone());
+    }
+    println!(
+        "  {}  {}",
+        format!("#{}", p.pending_id).yellow(),
+        queued.join(" · ").dimmed()
+    );
+    println!("      {}", p.command_line());
+    if let Some(error) = &p.error {
+        println!("      {}", format!("Last attempt failed: {}", error).red());
+    }
+}
+
+/// Queued writes, oldest first
+fn load_pending(conn: &Connection) -> Result<Vec<PendingWrite>> {
+    let mut stmt = conn.prepare(
+        "SELECT pending_id, created_at, args, session_id, author, error
+         FROM pending_writes WHERE status = 'pending' ORDER BY pending_id",
+    )?;
+    let pending = stmt
+        .query_map([], |row| {
+            let args: String = row.get(2)?;
+            Ok(PendingWrite {
+                pending_id: row.get(0)?,
+                created_at: row.get(1)?,
+                args: serde_json::from_str(&args).unwrap_or_default(),
+                session_id: row.get(3)?,
+                author: row.get(4)?,
+                error: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(pending)
+}
+
+/// Number of writes awaiting approval (0 before schema v1.18)
+pub fn pending_count(conn: &Connection) -> i64 {
+    conn.query_row(
+        "SELECT COUNT(*) FROM pending_writes WHERE status = 'pending'",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap_or(0)
+}
+
+/// Fail with an upgrade hint if the tracking database predates the approval queue
+fn require_pending_writes(conn: &Connection) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='pending_writes')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.18".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::init_tracking_schema;
+
+    fn writes(args: &str) -> bool {
+        let args = std::iter::once("proj").chain(args.split_whitespace());
+        let cli = Cli::try_parse_from(args).unwrap_or_else(|e| panic!("{}", e));
+        is_agent_write(&cli.command)
+    }
+
+    #[test]
+    fn changes_are_agent_writes() {
+        for args in [
+            "task add parser",
+            "task update 3 --status completed",
+            "log decision api rest simple",
+            "log note architecture layout three-crates",
+            "log blocker waiting",
+            "blocker resolve 2 upstream-fixed",
+            "config set usage_metrics true",
+            "attachment add task 1 diagram.png",
+            "import backup.json",
+            "snapshot --restore 2",
+            "doctor --fix",
+            "upgrade",
+            "rollback --schema 1.19",
+        ] {
+            assert!(writes(args), "`proj {}` should be queued", args);
+        }
+    }
+
+    #[test]
+    fn reads_are_not_agent_writes() {
+        for args in [
+            "status",
+            "tasks",
+            "search parser",
+            "audit --table tasks",
+            "export --format json",
+            "task show 3",
+            "task list",
+            "blocker list",
+            "config get agent_writes",
+            "attachment list",
+            "import backup.json --dry-run",
+            "doctor",
+            "upgrade --info",
+            "rollback --list",
+            "log commits",
+        ] {
+            assert!(!writes(args), "`proj {}` should run", args);
+        }
+    }
+
+    #[test]
+    fn sessions_hooks_and_approve_are_exempt() {
+        for args in [
+            "enter",
+            "session end summary",
+            "session start",
+            "githook run post-commit",
+            "approve",
+        ] {
+            assert!(!writes(args), "`proj {}` should run", args);
+        }
+    }
+
+    #[test]
+    fn queued_input_is_checked() {
+        let conn = Connection::open_in_memory().unwrap();
+        init_tracking_schema(&conn).unwrap();
+        let check = |args: &[&str]| {
+            let args: Vec<String> = args.iter().map(|a| a.to_string()).collect();
+            check_queued(&conn, &args)
+        };
+
+        // One test, since the saved input is shared by the whole process
+        for args in [
+            &["log", "note", "-", "--stdin-text=goal\tShip it\tby friday"][..],
+            &["log", "decision", "api", "-", "--stdin-text=REST, for now"],
+            &["note", "add", "Layout", "--stdin-text=Three crates"],
+            &["task", "add", "parser"],
+        ] {
+            assert!(
+                check(args).is_ok(),
+                "`proj {}` should be queued",
+                args.join(" ")
+            );
+        }
+        for args in [
+            &["log", "note", "-", "--stdin-text=nonsense\ttitle\tcontent"][..],
+            &["log", "note", "-", "--stdin-text=a\tb\tc\td"],
+            &["log", "decision", "-", "--stdin-text={\"topic\": 1}"],
+            &["log", "decision", "api", "-", "--stdin-text=  "],
+            &["note", "add", "-", "--stdin-text="],
+            &["note", "add", "Layout", "--stdin-text=\n"],
+            &["task", "add"],
+        ] {
+            assert!(
+                check(args).is_err(),
+                "`proj {}` should be refused",
+                args.join(" ")
+            );
+        }
+    }
+}
@@ -1,18 +1,111 @@
-// Archive command - archive a completed project
+// Archive command - archive a completed project, or old items within one
+//
+// `proj archive items` moves completed tasks, superseded decisions, and
+// resolved blockers that were closed long enough ago into
+// .tracking/archive.db (same schema, attached while copying), taking their
+// tags and tracker links along. They drop out of status, context, and search
+// because they're no longer in the tracking database; `proj archive restore`
+// moves one back.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use dialoguer::Confirm;
+use rusqlite::Connection;
 
+use crate::cli::{ArchiveCommands, ArchiveSubcommand};
 use crate::config::{ProjectConfig, Registry};
-use crate::database::open_database;
+use crate::database::{id_list, open_database, table_columns, write_transaction};
+use crate::error::ProjError;
 use crate::paths::{
     ensure_dir, get_backups_dir, get_config_path, get_project_root, get_registry_path,
-    get_tracking_db_path,
+    get_tracking_db_path, same_path,
 };
+use crate::schema::init_tracking_schema;
+use crate::text::truncate;
+
+/// An archivable item type
+pub struct Kind {
+    pub name: &'static str,
+    table: &'static str,
+    id_column: &'static str,
+    /// SQL for a one-line description of a row
+    summary: &'static str,
+    /// Closed items whose closing date is older than ?1 (a datetime)
+    eligible: &'static str,
+    /// Rows in other tables that belong to the item and move with it: (table, column)
+    dependents: &'static [(&'static str, &'static str)],
+    /// Column pointing at another item that must not be archived without it: (kind, column)
+    references: Option<(&'static str, &'static str)>,
+}
 
-pub fn run() -> Result<()> {
+const KINDS: [Kind; 3] = [
+    Kind {
+        name: "task",
+        table: "tasks",
+        id_column: "task_id",
+        summary: "description",
+        eligible: "SELECT task_id FROM tasks
+                   WHERE status IN ('completed', 'cancelled')
+                     AND COALESCE(completed_at, created_at) < ?1",
+        dependents: &[
+            ("item_tags", "task_id"),
+            ("task_links", "task_id"),
+            ("attachments", "task_id"),
+            ("git_commit_tasks", "task_id"),
+        ],
+        references: Some(("task", "parent_task_id")),
+    },
+    Kind {
+        name: "decision",
+        table: "decisions",
+        id_column: "decision_id",
+        summary: "topic || ': ' || decision",
+        // Superseded decisions date from the decision that replaced them
+        eligible: "SELECT d.decision_id FROM decisions d
+                   LEFT JOIN decisions n ON n.decision_id = d.superseded_by
+                   WHERE d.status IN ('superseded', 'reversed')
+                     AND COALESCE(n.created_at, d.created_at) < ?1",
+        dependents: &[
+            ("item_tags", "decision_id"),
+            ("attachments", "decision_id"),
+            ("decision_sections", "decision_id"),
+        ],
+        references: Some(("decision", "superseded_by")),
+    },
+    Kind {
+        name: "blocker",
+        table: "blockers",
+        id_column: "blocker_id",
+        summary: "description",
+        eligible: "SELECT blocker_id FROM blockers
+                   WHERE status = 'resolved' AND COALESCE(resolved_at, created_at) < ?1",
+        dependents: &[("attachments", "blocker_id")],
+        references: Some(("task", "related_task_id")),
+    },
+];
+
+/// Items picked for archiving or purging, per kind
+pub type Selection = Vec<(&'static Kind, BTreeSet<i64>)>;
+
+pub fn run(cmd: ArchiveCommands) -> Result<()> {
+    match cmd.command {
+        None => archive_project(),
+        Some(ArchiveSubcommand::Items {
+            older_than,
+            kind,
+            dry_run,
+        }) => archive_items(older_than, kind.as_deref(), dry_run),
+        Some(ArchiveSubcommand::Restore { kind, id }) => restore(&kind, id),
+        Some(ArchiveSubcommand::List { kind }) => list(kind.as_deref()),
+    }
+}
+
+/// Archive the whole project
+fn archive_project() -> Result<()> {
     // Load config
     let config = load_config()?;
     let project_root = get_project_root()?;
@@ -39,12 +132,12 @@ pub fn run() -> Result<()> {
         );
     }
 
-    // End any active session
+    // End any active or paused session
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)?;
     conn.execute(
         "UPDATE sessions SET status = 'completed', ended_at = datetime('now'), summary = 'Project archived'
-         WHERE status = 'active'",
+         WHERE status IN ('active', 'paused')",
         [],
     )?;
 
@@ -97,7 +190,7 @@ fn unregister_project(project_path: &str) -> Result<()> {
 
     registry
         .registered_projects
-        .retain(|p| p.path != project_path);
+        .retain(|p| !same_path(Path::new(&p.path), Path::new(project_path)));
 
     let content = serde_json::to_string_pretty(&registry)?;
     std::fs::write(&registry_path, content)?;
@@ -111,6 +204,482 @@ fn load_config() -> Result<ProjectConfig> {
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
+
+pub fn parse_kind(name: &str) -> Result<&'static Kind> {
+    let name = name.trim_end_matches('s');
+    KINDS.iter().find(|k| k.name == name).ok_or_else(|| {
+        ProjError::InvalidInput(format!(
+            "Unknown item type '{}'. Use: task, decision, blocker",
+            name
+        ))
+        .into()
+    })
+}
+
+fn kind_by_name(name: &str) -> &'static Kind {
+    KINDS
+        .iter()
+        .find(|k| k.name == name)
+        .expect("references name a known kind")
+}
+
+fn get_archive_db_path() -> Result<PathBuf> {
+    Ok(get_tracking_db_path()?.with_file_name("archive.db"))
+}
+
+/// Move eligible items to the archive database
+fn archive_items(older_than: u32, kind: Option<&str>, dry_run: bool) -> Result<()> {
+    let kinds: Vec<&'static Kind> = match kind {
+        Some(name) => vec![parse_kind(name)?],
+        None => KINDS.iter().collect(),
+    };
+
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let kinds = kinds
+        .into_iter()
+        .map(|kind| (kind, older_than))
+        .collect::<Vec<_>>();
+    let (selected, kept) = select_closed(&conn, &kinds)?;
+
+    let total: usize = selected.iter().map(|(_, ids)| ids.len()).sum();
+    if total == 0 {
+        if kept > 0 {
+            println!(
+                "Nothing to archive: {} item(s) closed more than {} days ago are still referenced by open items.",
+                kept, older_than
+            );
+        } else {
+            println!(
+                "Nothing closed more than {} days ago to archive.",
+                older_than
+            );
+        }
+        return Ok(());
+    }
+
+    for (kind, ids) in &selected {
+        if ids.is_empty() {
+            continue;
+        }
+        println!(
+            "{} {}s:",
+            if dry_run {
+                "Would archive"
+            } else {
+                "Archiving"
+            },
+            kind.name
+        );
+        for (id, summary) in summaries(&conn, "main", kind, ids)? {
+            println!("  #{} {}", id, truncate(&summary, 70));
+        }
+    }
+    if kept > 0 {
+        println!(
+            "{}",
+            format!("Kept {} item(s) still referenced by open items.", kept).dimmed()
+        );
+    }
+    if dry_run {
+        return Ok(());
+    }
+
+    move_to_archive(&conn, &selected)?;
+
+    println!(
+        "\n{} Archived {} item(s) to .tracking/archive.db",
+        "✓".green(),
+        total
+    );
+    println!("Restore one with 'proj archive restore <type> <id>'.");
+    Ok(())
+}
+
+/// Closed items of the given kinds older than their cutoff in days, minus any
+/// that open items still point at. Also returns how many were held back.
+pub fn select_closed(
+    conn: &Connection,
+    kinds: &[(&'static Kind, u32)],
+) -> Result<(Selection, usize)> {
+    let mut selected: Selection = Vec::new();
+    for (kind, days) in kinds {
+        let cutoff = (Utc::now() - chrono::Duration::days(*days as i64))
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let mut stmt = conn.prepare(kind.eligible)?;
+        let ids = stmt
+            .query_map([&cutoff], |row| row.get::<_, i64>(0))?
+            .collect::<Result<BTreeSet<_>, _>>()?;
+        selected.push((kind, ids));
+    }
+    let kept = keep_referenced(conn, &mut selected)?;
+    Ok((selected, kept))
+}
+
+/// Move the selected items, with their tags and links, into .tracking/archive.db
+pub fn move_to_archive(conn: &Connection, selected: &Selection) -> Result<()> {
+    with_archive(conn, |conn| {
+        let tx = write_transaction(conn)?;
+        let now = Utc::now().format("%Y-%m-%d %H:%M:%S").to_string();
+        for (kind, ids) in selected {
+            if ids.is_empty() {
+                continue;
+            }
+            let in_list = id_list(ids);
+            for (table, column) in kind.dependents {
+                move_rows(
+                    &tx,
+                    "main",
+                    "archive",
+                    table,
+                    &format!("{} IN ({})", column, in_list),
+                )?;
+            }
+            move_rows(
+                &tx,
+                "main",
+                "archive",
+                kind.table,
+                &format!("{} IN ({})", kind.id_column, in_list),
+            )?;
+            for id in ids {
+                tx.execute(
+                    "INSERT OR REPLACE INTO archive.archived_items (kind, item_id, archived_at) VALUES (?1, ?2, ?3)",
+                    rusqlite::params![kind.name, id, now],
+                )?;
+            }
+        }
+        tx.commit()?;
+        Ok(())
+    })
+}
+
+/// Delete the selected items and their tags and links for good
+pub fn purge(conn: &Connection, selected: &Selection) -> Result<()> {
+    let tx = write_transaction(conn)?;
+    // Selected items may point at each other; check once everything is gone
+    tx.execute_batch("PRAGMA defer_foreign_keys = ON;")?;
+    for (kind, ids) in selected {
+        if ids.is_empty() {
+            continue;
+        }
+        let in_list = id_list(ids);
+        for (table, column) in kind.dependents {
+            tx.execute(
+                &format!("DELETE FROM {} WHERE {} IN ({})", table, column, in_list),
+                [],
+            )?;
+        }
+        tx.execute(
+            &format!(
+                "DELETE FROM {} WHERE {} IN ({})",
+                kind.table, kind.id_column, in_list
+            ),
+            [],
+        )?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Drop items that open (non-selected) items still point at, until nothing changes.
+/// Returns how many were dropped.
+fn keep_referenced(conn: &Connection, selected: &mut Selection) -> Result<usize> {
+    let mut kept = 0;
+    loop {
+        let mut dropped = Vec::new();
+        for kind in &KINDS {
+            let Some((target, column)) = kind.references else {
+                continue;
+            };
+            let sources = selected
+                .iter()
+                .find(|(k, _)| k.name == kind.name)
+                .map(|(_, ids)| ids.clone())
+                .unwrap_or_default();
+            let Some((_, targets)) = selected.iter().find(|(k, _)| k.name == target) else {
+                continue;
+            };
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {id}, {col} FROM {table} WHERE {col} IS NOT NULL",
+                id = kind.id_column,
+                col = column,
+                table = kind.table
+            ))?;
+            let pairs = stmt
+                .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)))?
+                .collect::<Result<Vec<_>, _>>()?;
+            for (source, referenced) in pairs {
+                if targets.contains(&referenced) && !sources.contains(&source) {
+                    dropped.push((target, referenced));
+                }
+            }
+        }
+        if dropped.is_empty() {
+            return Ok(kept);
+        }
+        for (target, id) in dropped {
+            if let Some((_, ids)) = selected.iter_mut().find(|(k, _)| k.name == target) {
+                if ids.remove(&id) {
+                    kept += 1;
+                }
+            }
+        }
+    }
+}
+
+/// Move an archived item back into the tracking database
+fn restore(kind: &str, id: i64) -> Result<()> {
+    let kind = parse_kind(kind)?;
+    let archive_path = get_archive_db_path()?;
+    if !archive_path.exists() {
+        return Err(ProjError::InvalidInput("Nothing has been archived yet.".to_string()).into());
+    }
+
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let summary = with_archive(&conn, |conn| {
+        let ids = BTreeSet::from([id]);
+        let Some((_, summary)) = summaries(conn, "archive", kind, &ids)?.pop() else {
+            return Err(ProjError::InvalidInput(format!(
+                "{} #{} is not in the archive. See 'proj archive list'.",
+                kind.name, id
+            ))
+            .into());
+        };
+
+        // Restoring must not leave the item pointing at something still archived
+        if let Some((target, column)) = kind.references {
+            let target = kind_by_name(target);
+            let referenced: Option<i64> = conn.query_row(
+                &format!(
+                    "SELECT {} FROM archive.{} WHERE {} = ?1",
+                    column, kind.table, kind.id_column
+                ),
+                [id],
+                |row| row.get(0),
+            )?;
+            if let Some(referenced) = referenced {
+                let archived: bool = conn.query_row(
+                    &format!(
+                        "SELECT EXISTS(SELECT 1 FROM archive.{} WHERE {} = ?1)",
+                        target.table, target.id_column
+                    ),
+                    [referenced],
+                    |row| row.get(0),
+                )?;
+                if archived {
+                    return Err(ProjError::InvalidInput(format!(
+                        "{} #{} refers to {} #{}, which is also archived. Restore that first.",
+                        kind.name, id, target.name, referenced
+                    ))
+                    .into());
+                }
+            }
+        }
+
+        let tx = write_transaction(conn)?;
+        move_rows(
+            &tx,
+            "archive",
+            "main",
+            kind.table,
+            &format!("{} = {}", kind.id_column, id),
+        )?;
+        for (table, column) in kind.dependents {
+            move_rows(
+                &tx,
+                "archive",
+                "main",
+                table,
+                &format!("{} = {}", column, id),
+            )?;
+        }
+        tx.execute(
+            "DELETE FROM archive.archived_items WHERE kind = ?1 AND item_id = ?2",
+            rusqlite::params![kind.name, id],
+        )?;
+        tx.commit()?;
+        Ok(summary)
+    })?;
+
+    println!(
+        "{} Restored {} #{}: {}",
+        "✓".green(),
+        kind.name,
+        id,
+        truncate(&summary, 60)
+    );
+    Ok(())
+}
+
+/// List archived items, newest first
+fn list(kind: Option<&str>) -> Result<()> {
+    let kind = kind.map(parse_kind).transpose()?;
+    let archive_path = get_archive_db_path()?;
+    if !archive_path.exists() {
+        println!("Nothing has been archived yet.");
+        return Ok(());
+    }
+
+    let conn = open_database(&archive_path)?;
+    let mut shown = 0;
+    for k in KINDS
+        .iter()
+        .filter(|k| kind.map_or(true, |kind| kind.name == k.name))
+    {
+        let mut stmt = conn.prepare(&format!(
+            "SELECT a.item_id, a.archived_at, {summary} FROM archived_items a
+             JOIN {table} t ON t.{id} = a.item_id
+             WHERE a.kind = ?1 ORDER BY a.archived_at DESC, a.item_id DESC",
+            summary = k.summary,
+            table = k.table,
+            id = k.id_column
+        ))?;
+        let rows = stmt
+            .query_map([k.name], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        if rows.is_empty() {
+            continue;
+        }
+        println!("{}", format!("Archived {}s", k.name).bold());
+        for (id, archived_at, summary) in rows {
+            println!(
+                "  #{} {} {}",
+                id,
+                truncate(&summary, 60),
+                format!(
+                    "(archived {})",
+                    archived_at.get(..10).unwrap_or(&archived_at)
+                )
+                .dimmed()
+            );
+        }
+        shown += 1;
+    }
+    if shown == 0 {
+        println!("No archived items.");
+    }
+    Ok(())
+}
+
+/// Run `f` with the archive database attached as `archive`, creating it if needed.
+/// Foreign keys are off meanwhile: archived rows point at sessions that stay behind.
+fn with_archive<T>(conn: &Connection, f: impl FnOnce(&Connection) -> Result<T>) -> Result<T> {
+    let archive_path = get_archive_db_path()?;
+    if !archive_path.exists() {
+        let archive = Connection::open(&archive_path)
+            .with_context(|| format!("Failed to create {}", archive_path.display()))?;
+        init_tracking_schema(&archive)?;
+    }
+
+    conn.execute(
+        "ATTACH DATABASE ?1 AS archive",
+        [archive_path.to_string_lossy()],
+    )?;
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+
+    let result = prepare_archive(conn).and_then(|_| f(conn));
+
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    conn.execute("DETACH DATABASE archive", [])?;
+    result
+}
+
+/// Bring the archive's tables up to the tracking database's columns (it may
+/// have been created by an older version, before some tables existed) and make
+/// sure the index table exists
+fn prepare_archive(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE TABLE IF NOT EXISTS archive.archived_items (
+            kind TEXT NOT NULL,
+            item_id INTEGER NOT NULL,
+            archived_at TEXT NOT NULL,
+            PRIMARY KEY (kind, item_id)
+        );",
+    )?;
+    let tables = KINDS
+        .iter()
+        .map(|k| k.table)
+        .chain(
+            KINDS
+                .iter()
+                .flat_map(|k| k.dependents.iter().map(|(t, _)| *t)),
+        )
+        .collect::<BTreeSet<_>>();
+    for table in tables {
+        let archived = table_columns(conn, "archive", table)?;
+        if archived.is_empty() {
+            conn.execute_batch(&format!(
+                "CREATE TABLE IF NOT EXISTS archive.{t} AS SELECT * FROM main.{t} WHERE 0",
+                t = table
+            ))?;
+            continue;
+        }
+        for column in table_columns(conn, "main", table)? {
+            if !archived.contains(&column) {
+                conn.execute_batch(&format!(
+                    "ALTER TABLE archive.{} ADD COLUMN {}",
+                    table, column
+                ))?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Copy matching rows from one database to the other, then delete the originals
+fn move_rows(conn: &Connection, from: &str, to: &str, table: &str, condition: &str) -> Result<()> {
+    let columns = table_columns(conn, from, table)?.join(", ");
+    conn.execute(
+        &format!(
+            "INSERT OR REPLACE INTO {to}.{t} ({c}) SELECT {c} FROM {from}.{t} WHERE {w}",
+            to = to,
+            from = from,
+            t = table,
+            c = columns,
+            w = condition
+        ),
+        [],
+    )?;
+    conn.execute(
+        &format!("DELETE FROM {}.{} WHERE {}", from, table, condition),
+        [],
+    )?;
+    Ok(())
+}
+
+/// (id, summary) for the given items
+pub fn summaries(
+    conn: &Connection,
+    schema: &str,
+    kind: &Kind,
+    ids: &BTreeSet<i64>,
+) -> Result<Vec<(i64, String)>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {id}, {summary} FROM {schema}.{table} WHERE {id} IN ({ids}) ORDER BY {id}",
+        id = kind.id_column,
+        summary = kind.summary,
+        schema = schema,
+        table = kind.table,
+        ids = id_list(ids)
+    ))?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
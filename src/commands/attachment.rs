@@ -0,0 +1,525 @@
+// Attachment commands - files attached to tracked items (attachments table)
+//
+// An attached file is copied into the tracking database, so backups, exports, and
+// snapshots carry it and it stays readable after the original moves or changes.
+// `proj log ... --attach <path>` attaches files as entries are logged; `proj
+// attachment` adds them to items logged earlier, lists them, and opens or saves a copy.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use rusqlite::{Connection, OptionalExtension};
+use sha2::{Digest, Sha256};
+
+use crate::cli::{AttachmentCommands, AttachmentSubcommand};
+use crate::database::{get_schema_version, open_database, write_transaction};
+use crate::error::ProjError;
+use crate::paths::get_tracking_db_path;
+use crate::porcelain;
+use crate::session::resolve_author;
+
+/// Largest file that can be attached; the database isn't the place for build output
+const MAX_SIZE: u64 = 10 * 1024 * 1024;
+
+const KINDS: [&str; 5] = ["task", "decision", "note", "blocker", "question"];
+
+/// Something files can be attached to
+#[derive(Debug, Clone, Copy)]
+pub enum Item {
+    Task(i64),
+    Decision(i64),
+    Note(i64),
+    Blocker(i64),
+    Question(i64),
+}
+
+impl Item {
+    fn parse(kind: &str, id: i64) -> Result<Item> {
+        Ok(match kind {
+            "task" => Item::Task(id),
+            "decision" => Item::Decision(id),
+            "note" => Item::Note(id),
+            "blocker" => Item::Blocker(id),
+            "question" => Item::Question(id),
+            other => {
+                return Err(ProjError::InvalidInput(format!(
+                    "Unknown kind '{}'. Use {}",
+                    other,
+                    KINDS.join(", ")
+                ))
+                .into())
+            }
+        })
+    }
+
+    fn kind(&self) -> &'static str {
+        match self {
+            Item::Task(_) => "task",
+            Item::Decision(_) => "decision",
+            Item::Note(_) => "note",
+            Item::Blocker(_) => "blocker",
+            Item::Question(_) => "question",
+        }
+    }
+
+    /// Table the item lives in
+    fn table(&self) -> &'static str {
+        match self {
+            Item::Task(_) => "tasks",
+            Item::Decision(_) => "decisions",
+            Item::Note(_) => "context_notes",
+            Item::Blocker(_) => "blockers",
+            Item::Question(_) => "questions",
+        }
+    }
+
+    /// attachments column referencing this kind of item (also the item table's key)
+    fn column(&self) -> &'static str {
+        match self {
+            Item::Task(_) => "task_id",
+            Item::Decision(_) => "decision_id",
+            Item::Note(_) => "note_id",
+            Item::Blocker(_) => "blocker_id",
+            Item::Question(_) => "question_id",
+        }
+    }
+
+    fn id(&self) -> i64 {
+        match self {
+            Item::Task(id)
+            | Item::Decision(id)
+            | Item::Note(id)
+            | Item::Blocker(id)
+            | Item::Question(id) => *id,
+        }
+    }
+}
+
+/// A file read in, ready to attach
+pub struct File {
+    pub name: String,
+    content: Vec<u8>,
+    sha256: String,
+}
+
+/// An attachment as listed
+struct Attachment {
+    attachment_id: i64,
+    kind: &'static str,
+    item_id: i64,
+    file_name: String,
+    size: i64,
+    created_at: String,
+}
+
+pub fn run(cmd: AttachmentCommands) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    require_attachments(&conn)?;
+
+    match cmd.command {
+        AttachmentSubcommand::Add { kind, id, paths } => {
+            cmd_attachment_add(&conn, Item::parse(&kind, id)?, &paths)
+        }
+        AttachmentSubcommand::List { kind, id } => cmd_attachment_list(&conn, kind.as_deref(), id),
+        AttachmentSubcommand::Open { id } => cmd_attachment_open(&conn, id),
+        AttachmentSubcommand::Save { id, output } => cmd_attachment_save(&conn, id, output),
+        AttachmentSubcommand::Remove { id } => cmd_attachment_remove(&conn, id),
+    }
+}
+
+/// Read the files to attach, so a missing or oversized one stops the command
+/// before anything is logged
+pub fn read_files(paths: &[PathBuf]) -> Result<Vec<File>> {
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        let metadata = std::fs::metadata(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        if !metadata.is_file() {
+            return Err(
+                ProjError::InvalidInput(format!("{} is not a file", path.display())).into(),
+            );
+        }
+        if metadata.len() > MAX_SIZE {
+            return Err(ProjError::InvalidInput(format!(
+                "{} is {}; attachments can be at most {}",
+                path.display(),
+                format_size(metadata.len() as i64),
+                format_size(MAX_SIZE as i64)
+            ))
+            .into());
+        }
+        let content =
+            std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_else(|| path.display().to_string());
+        files.push(File {
+            name,
+            sha256: format!("{:x}", Sha256::digest(&content)),
+            content,
+        });
+    }
+    Ok(files)
+}
+
+/// Attach files to an item, timestamped `at` (None means now); returns the new
+/// attachment IDs in the order of `files`
+pub fn attach(
+    conn: &Connection,
+    item: Item,
+    files: &[File],
+    author: Option<&str>,
+    at: Option<&str>,
+) -> Result<Vec<i64>> {
+    if files.is_empty() {
+        return Ok(Vec::new());
+    }
+    require_attachments(conn)?;
+    let mut ids = Vec::with_capacity(files.len());
+    for file in files {
+        conn.execute(
+            &format!(
+                "INSERT INTO attachments ({}, file_name, size, sha256, content, author, created_at)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, COALESCE(?7, datetime('now')))",
+                item.column()
+            ),
+            rusqlite::params![
+                item.id(),
+                file.name,
+                file.content.len() as i64,
+                file.sha256,
+                file.content,
+                author,
+                at
+            ],
+        )?;
+        ids.push(conn.last_insert_rowid());
+    }
+    Ok(ids)
+}
+
+/// "  → diagram.png (attachment #4)" under a confirmation line
+pub fn print_attached(files: &[File], ids: &[i64]) {
+    for (file, id) in files.iter().zip(ids) {
+        println!(
+            "  {} {} {}",
+            "→".dimmed(),
+            file.name,
+            format!("(attachment #{})", id).dimmed()
+        );
+    }
+}
+
+/// File names and IDs of an item's attachments; none on databases from before them
+pub fn attachments_for(conn: &Connection, item: Item) -> Result<Vec<(i64, String)>> {
+    if !has_attachments(conn)? {
+        return Ok(Vec::new());
+    }
+    let mut stmt = conn.prepare(&format!(
+        "SELECT attachment_id, file_name FROM attachments WHERE {} = ?1 ORDER BY attachment_id",
+        item.column()
+    ))?;
+    let attachments = stmt
+        .query_map([item.id()], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(attachments)
+}
+
+fn has_attachments(conn: &Connection) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='attachments')",
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+/// Fail with an upgrade hint if the tracking database predates attachments
+fn require_attachments(conn: &Connection) -> Result<()> {
+    if !has_attachments(conn)? {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.22".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Attach files to an item already logged
+fn cmd_attachment_add(conn: &Connection, item: Item, paths: &[PathBuf]) -> Result<()> {
+    let exists: bool = conn
+        .query_row(
+            &format!(
+                "SELECT 1 FROM {} WHERE {} = ?1",
+                item.table(),
+                item.column()
+            ),
+            [item.id()],
+            |_| Ok(true),
+        )
+        .optional()?
+        .unwrap_or(false);
+    if !exists {
+        bail!("{} #{} not found", capitalize(item.kind()), item.id());
+    }
+
+    let files = read_files(paths)?;
+    let tx = write_transaction(conn)?;
+    let ids = attach(&tx, item, &files, resolve_author(None).as_deref(), None)?;
+    tx.commit()?;
+
+    if porcelain::enabled() {
+        for id in &ids {
+            porcelain::print(&[&id.to_string()]);
+        }
+        return Ok(());
+    }
+    println!(
+        "{} Attached {} to {} #{}",
+        "✓".green(),
+        plural(files.len(), "file"),
+        item.kind(),
+        item.id()
+    );
+    print_attached(&files, &ids);
+    Ok(())
+}
+
+/// List attachments: all of them, one kind's, or one item's
+fn cmd_attachment_list(conn: &Connection, kind: Option<&str>, id: Option<i64>) -> Result<()> {
+    // Only the column matters when listing a whole kind
+    let item = kind.map(|k| Item::parse(k, id.unwrap_or(0))).transpose()?;
+    let filter = match item {
+        Some(item) => format!(
+            "WHERE {col} IS NOT NULL AND (?1 IS NULL OR {col} = ?1)",
+            col = item.column()
+        ),
+        None => "WHERE ?1 IS NULL".to_string(),
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT attachment_id, task_id, decision_id, note_id, blocker_id, question_id,
+                file_name, size, created_at
+         FROM attachments {} ORDER BY attachment_id",
+        filter
+    ))?;
+    let attachments = stmt
+        .query_map([id], |row| {
+            let refs: [Option<i64>; 5] = [
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+                row.get(5)?,
+            ];
+            let (kind, item_id) = KINDS
+                .iter()
+                .zip(refs)
+                .find_map(|(kind, id)| id.map(|id| (*kind, id)))
+                .unwrap_or(("-", 0));
+            Ok(Attachment {
+                attachment_id: row.get(0)?,
+                kind,
+                item_id,
+                file_name: row.get(6)?,
+                size: row.get(7)?,
+                created_at: row.get(8)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Porcelain: ID, kind, item ID, size in bytes, created, file name
+    if porcelain::enabled() {
+        for a in &attachments {
+            porcelain::print(&[
+                &a.attachment_id.to_string(),
+                a.kind,
+                &a.item_id.to_string(),
+                &a.size.to_string(),
+                &a.created_at,
+                &a.file_name,
+            ]);
+        }
+        return Ok(());
+    }
+
+    if attachments.is_empty() {
+        match (item, id) {
+            (Some(item), Some(id)) => println!("No attachments on {} #{}.", item.kind(), id),
+            (Some(item), None) => println!("No attachments on {}s.", item.kind()),
+            _ => println!("No attachments."),
+        }
+        return Ok(());
+    }
+
+    println!("{}", "Attachments:".bold());
+    println!("{}", "-".repeat(60));
+    for a in &attachments {
+        let date = a.created_at.split(' ').next().unwrap_or(&a.created_at);
+        println!(
+            "  #{:<4} {} {} {} {}",
+            a.attachment_id,
+            format!("[{} #{}]", a.kind, a.item_id).cyan(),
+            a.file_name,
+            format!("({})", format_size(a.size)).dimmed(),
+            date.dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Open a copy of an attachment with the system's default application
+fn cmd_attachment_open(conn: &Connection, attachment_id: i64) -> Result<()> {
+    let (file_name, content) = load(conn, attachment_id)?;
+
+    // A directory per attachment keeps the original file name, which is what the
+    // opening application goes by
+    let dir = std::env::temp_dir()
+        .join("proj-attachments")
+        .join(attachment_id.to_string());
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {:?}", dir))?;
+    let path = dir.join(safe_file_name(&file_name));
+    std::fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))?;
+
+    let status = opener(&path)
+        .status()
+        .with_context(|| format!("Failed to open {} (saved to {})", file_name, path.display()))?;
+    if !status.success() {
+        bail!(
+            "Couldn't open {} ({}). It was saved to {}",
+            file_name,
+            status,
+            path.display()
+        );
+    }
+    if !porcelain::enabled() {
+        println!(
+            "{} Opened {} {}",
+            "✓".green(),
+            file_name,
+            format!("(copy at {})", path.display()).dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// The platform's "open with the default application" command
+fn opener(path: &Path) -> Command {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+    command.arg(path);
+    command
+}
+
+/// Write an attachment to a file, never over an existing one
+fn cmd_attachment_save(
+    conn: &Connection,
+    attachment_id: i64,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let (file_name, content) = load(conn, attachment_id)?;
+    let path = output.unwrap_or_else(|| PathBuf::from(safe_file_name(&file_name)));
+    if path.exists() {
+        bail!("{} already exists", path.display());
+    }
+    std::fs::write(&path, &content).with_context(|| format!("Failed to write {:?}", path))?;
+
+    if porcelain::enabled() {
+        porcelain::print(&[&path.display().to_string()]);
+        return Ok(());
+    }
+    println!(
+        "{} Saved attachment #{} to {} ({})",
+        "✓".green(),
+        attachment_id,
+        path.display(),
+        format_size(content.len() as i64)
+    );
+    Ok(())
+}
+
+/// Delete an attachment
+fn cmd_attachment_remove(conn: &Connection, attachment_id: i64) -> Result<()> {
+    let (file_name, _) = load(conn, attachment_id)?;
+    conn.execute(
+        "DELETE FROM attachments WHERE attachment_id = ?1",
+        [attachment_id],
+    )?;
+    if !porcelain::enabled() {
+        println!(
+            "{} Removed attachment #{} ({})",
+            "✓".green(),
+            attachment_id,
+            file_name
+        );
+    }
+    Ok(())
+}
+
+/// An attachment's file name and content
+fn load(conn: &Connection, attachment_id: i64) -> Result<(String, Vec<u8>)> {
+    let attachment = conn
+        .query_row(
+            "SELECT file_name, content FROM attachments WHERE attachment_id = ?1",
+            [attachment_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    match attachment {
+        Some(attachment) => Ok(attachment),
+        None => bail!("Attachment #{} not found", attachment_id),
+    }
+}
+
+/// The last component of a stored file name, so an imported one can't write
+/// outside the directory it's saved to
+fn safe_file_name(file_name: &str) -> String {
+    let name = file_name
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .trim();
+    if name.is_empty() || name == "." || name == ".." {
+        "attachment".to_string()
+    } else {
+        name.to_string()
+    }
+}
+
+/// "12.3 KB"
+fn format_size(bytes: i64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        format!("1 {}", word)
+    } else {
+        format!("{} {}s", n, word)
+    }
+}
@@ -0,0 +1,253 @@
+// Audit command - review the inserts, updates, and deletes recorded in audit_log
+//
+// The log is written by triggers (schema::AUDIT_TRIGGER_SETS), so every change proj makes
+// is in it, whichever command or agent made it.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::database::{get_schema_version, open_database};
+use crate::error::ProjError;
+use crate::paths::get_tracking_db_path;
+use crate::porcelain;
+use crate::text::truncate;
+
+/// Audited tables: (name used on the command line, table)
+const AUDITED_TABLES: &[(&str, &str)] = &[
+    ("sessions", "sessions"),
+    ("tasks", "tasks"),
+    ("decisions", "decisions"),
+    ("notes", "context_notes"),
+    ("blockers", "blockers"),
+    ("questions", "questions"),
+    ("milestones", "milestones"),
+    ("tags", "item_tags"),
+    ("links", "task_links"),
+    ("topics", "decision_topics"),
+    ("sections", "decision_sections"),
+    ("attachments", "attachments"),
+];
+
+/// Longest value shown for a field before it's cut short
+const VALUE_WIDTH: usize = 60;
+
+/// One recorded change
+#[derive(Serialize)]
+struct AuditEntry {
+    audit_id: i64,
+    timestamp: String,
+    /// Table name as used on the command line (notes, tags, ...)
+    table: String,
+    record_id: i64,
+    /// insert, update, or delete
+    action: String,
+    /// Row values for inserts and deletes; {"column": [old, new]} for updates
+    changes: Value,
+    session_id: Option<i64>,
+    author: Option<String>,
+}
+
+pub fn run(
+    since: Option<String>,
+    table: Option<String>,
+    id: Option<i64>,
+    session: Option<i64>,
+    limit: usize,
+    json: bool,
+) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    require_audit_log(&conn)?;
+
+    if let Some(since) = &since {
+        if chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_err() {
+            return Err(ProjError::InvalidInput(format!(
+                "Invalid --since '{}'. Use YYYY-MM-DD.",
+                since
+            ))
+            .into());
+        }
+    }
+    let table = match &table {
+        Some(name) => Some(
+            AUDITED_TABLES
+                .iter()
+                .find(|(n, t)| n == name || t == name)
+                .map(|(_, t)| *t)
+                .ok_or_else(|| {
+                    let names: Vec<&str> = AUDITED_TABLES.iter().map(|(n, _)| *n).collect();
+                    ProjError::InvalidInput(format!(
+                        "Unknown table '{}'. Use one of: {}",
+                        name,
+                        names.join(", ")
+                    ))
+                })?,
+        ),
+        None => None,
+    };
+
+    let entries = load_entries(&conn, since.as_deref(), table, id, session, limit)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&entries)?);
+        return Ok(());
+    }
+
+    if porcelain::enabled() {
+        for e in &entries {
+            porcelain::print(&[
+                &e.audit_id.to_string(),
+                &e.timestamp,
+                &e.action,
+                &e.table,
+                &e.record_id.to_string(),
+                &e.session_id.map(|id| id.to_string()).unwrap_or_default(),
+                e.author.as_deref().unwrap_or_default(),
+                &e.changes.to_string(),
+            ]);
+        }
+        return Ok(());
+    }
+
+    if entries.is_empty() {
+        println!(
+            "No changes recorded{}.",
+            if since.is_some() || table.is_some() || session.is_some() {
+                " that match"
+            } else {
+                ""
+            }
+        );
+        return Ok(());
+    }
+
+    println!("\n{} ({}):\n", "Audit log".bold(), entries.len());
+    for e in &entries {
+        let action = match e.action.as_str() {
+            "insert" => "insert".green(),
+            "delete" => "delete".red(),
+            other => other.yellow(),
+        };
+        let mut who = Vec::new();
+        if let Some(id) = e.session_id {
+            who.push(format!("session #{}", id));
+        }
+        if let Some(author) = &e.author {
+            who.push(author.clone());
+        }
+        println!(
+            "  {}  {:<6}  {} #{}  {}",
+            e.timestamp.get(..16).unwrap_or(&e.timestamp),
+            action,
+            e.table.trim_end_matches('s'),
+            e.record_id,
+            who.join(" · ").dimmed()
+        );
+        for line in describe_changes(&e.action, &e.changes) {
+            println!("      {}", line);
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// Newest matching entries first
+fn load_entries(
+    conn: &Connection,
+    since: Option<&str>,
+    table: Option<&str>,
+    id: Option<i64>,
+    session: Option<i64>,
+    limit: usize,
+) -> Result<Vec<AuditEntry>> {
+    let mut stmt = conn.prepare(
+        "SELECT audit_id, timestamp, table_name, record_id, action, changes, session_id, author
+         FROM audit_log
+         WHERE (?1 IS NULL OR timestamp >= ?1)
+           AND (?2 IS NULL OR table_name = ?2)
+           AND (?3 IS NULL OR record_id = ?3)
+           AND (?4 IS NULL OR session_id = ?4)
+         ORDER BY audit_id DESC
+         LIMIT ?5",
+    )?;
+    let entries = stmt
+        .query_map(
+            rusqlite::params![since, table, id, session, limit as i64],
+            |row| {
+                let table: String = row.get(2)?;
+                let changes: Option<String> = row.get(5)?;
+                Ok(AuditEntry {
+                    audit_id: row.get(0)?,
+                    timestamp: row.get(1)?,
+                    table: AUDITED_TABLES
+                        .iter()
+                        .find(|(_, t)| *t == table)
+                        .map(|(n, _)| n.to_string())
+                        .unwrap_or(table),
+                    record_id: row.get(3)?,
+                    action: row.get(4)?,
+                    changes: changes
+                        .and_then(|c| serde_json::from_str(&c).ok())
+                        .unwrap_or(Value::Null),
+                    session_id: row.get(6)?,
+                    author: row.get(7)?,
+                })
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(entries)
+}
+
+/// "field: old → new" for updates, "field: value" for the set fields of inserts and deletes
+fn describe_changes(action: &str, changes: &Value) -> Vec<String> {
+    let Some(fields) = changes.as_object() else {
+        return Vec::new();
+    };
+    fields
+        .iter()
+        .filter_map(|(field, value)| {
+            if action == "update" {
+                let (old, new) = (value.get(0)?, value.get(1)?);
+                Some(format!(
+                    "{}: {} → {}",
+                    field.dimmed(),
+                    show_value(old),
+                    show_value(new)
+                ))
+            } else if value.is_null() {
+                None
+            } else {
+                Some(format!("{}: {}", field.dimmed(), show_value(value)))
+            }
+        })
+        .collect()
+}
+
+fn show_value(value: &Value) -> String {
+    match value {
+        Value::Null => "(none)".dimmed().to_string(),
+        Value::String(s) => truncate(&s.replace('\n', " "), VALUE_WIDTH),
+        other => other.to_string(),
+    }
+}
+
+/// Fail with an upgrade hint if the tracking database predates the audit log
+fn require_audit_log(conn: &Connection) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='audit_log')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.17".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
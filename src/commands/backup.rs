@@ -1,22 +1,74 @@
 // Backup command - manual backup of tracking data
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
+use colored::Colorize;
 
-use crate::config::ProjectConfig;
+use crate::config::{BackupConfig, ProjectConfig, RemoteConfig};
+use crate::crypto::{decrypt_file, encrypt_file, ensure_key, export_key, ENCRYPTED_SUFFIX};
 use crate::database::backup_database;
-use crate::paths::{ensure_dir, get_backups_dir, get_config_path, get_tracking_db_path};
+use crate::docs_db::find_docs_db;
+use crate::error::ProjError;
+use crate::paths::{
+    ensure_dir, get_backup_config_path, get_backups_dir, get_config_path, get_project_root,
+    get_tracking_db_path,
+};
+use crate::porcelain;
+use crate::remote::{prune, Remote};
+
+pub fn run(
+    remote: Option<String>,
+    encrypt: bool,
+    decrypt: Option<String>,
+    show_key: bool,
+) -> Result<()> {
+    if show_key {
+        println!("{}", export_key()?);
+        return Ok(());
+    }
+
+    if let Some(file) = decrypt {
+        return decrypt_backup(Path::new(&file));
+    }
+
+    if remote.is_some() && crate::config::offline() {
+        return Err(ProjError::InvalidInput(
+            "Offline mode is on; remote backups need the network".to_string(),
+        )
+        .into());
+    }
 
-pub fn run() -> Result<()> {
     // Load project config
     let config = load_config()?;
+    let settings = BackupConfig::load()?;
+    let encrypt = encrypt || settings.encrypt;
+    if encrypt {
+        ensure_key()?;
+    }
 
     // Create backup
-    let backup_path = backup_tracking_db(&config.name, "manual")?;
+    let mut backup_path = backup_tracking_db(&config.name, "manual")?;
+    if encrypt {
+        backup_path = encrypt_file(&backup_path)?;
+    }
 
-    println!("Backup created: {}", backup_path.display());
+    println!(
+        "Backup created: {}{}",
+        backup_path.display(),
+        if encrypt { " (encrypted)" } else { "" }
+    );
+
+    if let Some(target) = remote {
+        warn_if_credentials_exposed(&settings);
+        let targets = resolve_remotes(&settings, &target)?;
+        let files = backup_files(&config.name, backup_path, "manual", encrypt)?;
+        for (name, remote_config) in &targets {
+            push_to_remote(name, remote_config, &config.name, &files, &settings)?;
+        }
+        return Ok(());
+    }
 
     // List recent backups
     let backups_dir = get_backups_dir()?;
@@ -29,7 +81,7 @@ pub fn run() -> Result<()> {
         })
         .collect();
 
-    backups.sort_by(|a, b| b.file_name().cmp(&a.file_name()));
+    backups.sort_by_key(|b| std::cmp::Reverse(b.file_name()));
 
     if !backups.is_empty() {
         println!("\nRecent backups ({} total):", backups.len());
@@ -64,12 +116,206 @@ fn backup_tracking_db(project_name: &str, reason: &str) -> Result<PathBuf> {
     Ok(backup_path)
 }
 
+/// Push fresh backups to every configured remote when push_on_session_end is set
+/// (not in offline mode)
+pub fn push_after_session_end() -> Result<()> {
+    let settings = BackupConfig::load()?;
+    if !settings.push_on_session_end || settings.remotes.is_empty() {
+        return Ok(());
+    }
+    if crate::config::offline() {
+        porcelain::note(format!(
+            "  {} Offline: remote backups not pushed",
+            "ℹ".dimmed()
+        ));
+        return Ok(());
+    }
+    warn_if_credentials_exposed(&settings);
+
+    let config = load_config()?;
+    if settings.encrypt {
+        ensure_key()?;
+    }
+    let mut backup_path = backup_tracking_db(&config.name, "session")?;
+    if settings.encrypt {
+        backup_path = encrypt_file(&backup_path)?;
+    }
+    let files = backup_files(&config.name, backup_path, "session", settings.encrypt)?;
+
+    for (name, remote_config) in &settings.remotes {
+        // One unreachable remote shouldn't stop the others
+        if let Err(e) = push_to_remote(name, remote_config, &config.name, &files, &settings) {
+            porcelain::note(format!(
+                "  {} Backup to {} failed: {}",
+                "⚠".yellow(),
+                name,
+                e
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Resolve --remote: a configured name, "all", or a URL
+fn resolve_remotes(settings: &BackupConfig, target: &str) -> Result<Vec<(String, RemoteConfig)>> {
+    if target == "all" {
+        if settings.remotes.is_empty() {
+            bail!("No remotes configured in ~/.proj/backup.json");
+        }
+        return Ok(settings
+            .remotes
+            .iter()
+            .map(|(name, remote)| (name.clone(), remote.clone()))
+            .collect());
+    }
+
+    if let Some(remote) = settings.remotes.get(target) {
+        return Ok(vec![(target.to_string(), remote.clone())]);
+    }
+
+    if target.contains("://") {
+        let remote = RemoteConfig {
+            url: target.to_string(),
+            ..Default::default()
+        };
+        return Ok(vec![(target.to_string(), remote)]);
+    }
+
+    let names: Vec<&str> = settings.remotes.keys().map(|k| k.as_str()).collect();
+    bail!(
+        "Unknown remote '{}'. Configured: {}. Add it to ~/.proj/backup.json or pass a URL.",
+        target,
+        if names.is_empty() {
+            "none".to_string()
+        } else {
+            names.join(", ")
+        }
+    )
+}
+
+/// The tracking backup plus a copy of the docs database, if the project has one
+fn backup_files(
+    project_name: &str,
+    tracking: PathBuf,
+    reason: &str,
+    encrypt: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut files = vec![tracking];
+
+    if let Some(docs_path) = find_docs_db(&get_project_root()?) {
+        let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
+        let backup_path =
+            get_backups_dir()?.join(format!("{}_docs_{}_{}.db", project_name, timestamp, reason));
+        backup_database(&docs_path, &backup_path)
+            .with_context(|| format!("Failed to create backup at {:?}", backup_path))?;
+        files.push(if encrypt {
+            encrypt_file(&backup_path)?
+        } else {
+            backup_path
+        });
+    }
+
+    Ok(files)
+}
+
+/// Decrypt an encrypted backup next to it, dropping the .enc suffix
+fn decrypt_backup(path: &Path) -> Result<()> {
+    let name = path.to_string_lossy();
+    let Some(output) = name.strip_suffix(ENCRYPTED_SUFFIX) else {
+        bail!(
+            "{} is not an encrypted backup (expected a {} file)",
+            name,
+            ENCRYPTED_SUFFIX
+        );
+    };
+    let output = Path::new(output);
+    if output.exists() {
+        bail!("{} already exists", output.display());
+    }
+
+    decrypt_file(path, output)?;
+    println!("{} Decrypted to {}", "✓".green(), output.display());
+    Ok(())
+}
+
+/// Upload backup files to one remote and apply the retention policy there
+fn push_to_remote(
+    name: &str,
+    remote_config: &RemoteConfig,
+    project_name: &str,
+    files: &[PathBuf],
+    settings: &BackupConfig,
+) -> Result<()> {
+    let remote = Remote::from_config(remote_config)?;
+    porcelain::note(format!(
+        "Pushing to {} ({})",
+        name.bold(),
+        remote_config.url
+    ));
+
+    for file in files {
+        remote
+            .upload(project_name, file)
+            .with_context(|| format!("Failed to upload {}", file.display()))?;
+        let size_kb = std::fs::metadata(file).map(|m| m.len()).unwrap_or(0) as f64 / 1024.0;
+        porcelain::note(format!(
+            "  {} {} ({:.1} KB)",
+            "✓".green(),
+            file.file_name().unwrap_or_default().to_string_lossy(),
+            size_kb
+        ));
+    }
+
+    let pruned = prune(
+        &remote,
+        project_name,
+        settings.keep_last,
+        settings.keep_days,
+    )?;
+    if !pruned.is_empty() {
+        porcelain::note(format!(
+            "  Removed {} old backup(s) per retention policy",
+            pruned.len()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Secrets in backup.json should only be readable by the owner
+fn warn_if_credentials_exposed(settings: &BackupConfig) {
+    let has_secrets = settings
+        .remotes
+        .values()
+        .any(|r| r.secret_access_key.is_some() || r.password.is_some());
+    if !has_secrets {
+        return;
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        if let Ok(path) = get_backup_config_path() {
+            if let Ok(meta) = std::fs::metadata(&path) {
+                if meta.permissions().mode() & 0o077 != 0 {
+                    eprintln!(
+                        "{} {} contains credentials but is readable by others. Run: chmod 600 {}",
+                        "⚠".yellow(),
+                        path.display(),
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+}
+
 /// Load project configuration
 fn load_config() -> Result<ProjectConfig> {
     let config_path = get_config_path()?;
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
@@ -0,0 +1,454 @@
+// Backup verify - check a backup and compare it with the live tracking database
+//
+// `proj backup verify` copies the project's newest backup (a `proj backup` .db,
+// decrypted when it's .enc, or the tracking.db of a schema backup) to a scratch
+// file, since full-text index checks need to write. It runs PRAGMA
+// integrity_check on that and on the live database, and lists their row counts
+// side by side. `proj rollback --schema --backup`
+// compares the two row by row to show what a restore would change before it
+// happens.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::config::ProjectConfig;
+use crate::crypto::{decrypt_file, ENCRYPTED_SUFFIX};
+use crate::database::{get_schema_version, open_database};
+use crate::error::ProjError;
+use crate::paths::{get_backups_dir, get_tracking_db_path};
+
+/// Row IDs listed per change before the rest are only counted
+const SHOW_IDS: usize = 10;
+
+pub fn run(file: Option<String>) -> Result<()> {
+    let backup = match file {
+        Some(file) => PathBuf::from(file),
+        None => newest_backup(&ProjectConfig::load()?.name)?,
+    };
+    if !backup.exists() {
+        return Err(ProjError::InvalidInput(format!("{} doesn't exist", backup.display())).into());
+    }
+
+    // Next to the backups, and gone again whatever the outcome
+    let scratch = get_backups_dir()?.join(".verify.db");
+    let sidecar =
+        |path: &Path, suffix: &str| PathBuf::from(format!("{}{}", path.display(), suffix));
+    let result = (|| {
+        if backup.to_string_lossy().ends_with(ENCRYPTED_SUFFIX) {
+            decrypt_file(&backup, &scratch)?;
+        } else {
+            std::fs::copy(&backup, &scratch)
+                .with_context(|| format!("Failed to read {}", backup.display()))?;
+            // Schema backups are copied with the rest of .tracking/, WAL included
+            if sidecar(&backup, "-wal").exists() {
+                std::fs::copy(sidecar(&backup, "-wal"), sidecar(&scratch, "-wal"))?;
+            }
+        }
+        verify(&scratch, &backup)
+    })();
+    for path in [
+        sidecar(&scratch, "-wal"),
+        sidecar(&scratch, "-shm"),
+        scratch,
+    ] {
+        let _ = std::fs::remove_file(path);
+    }
+    result
+}
+
+/// The most recently written backup of a project: `proj backup` files and the
+/// tracking.db of schema backups alike
+fn newest_backup(project_name: &str) -> Result<PathBuf> {
+    let backups_dir = get_backups_dir()?;
+    let mut candidates = Vec::new();
+
+    if backups_dir.exists() {
+        let prefix = format!("{}_tracking_", project_name);
+        for entry in std::fs::read_dir(&backups_dir)? {
+            let path = entry?.path();
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if path.is_dir() {
+                if schema_backup_project(&path).as_deref() == Some(project_name) {
+                    candidates.push(path.join("tracking.db"));
+                }
+            } else if name.starts_with(&prefix)
+                && (name.ends_with(".db") || name.ends_with(&format!(".db{}", ENCRYPTED_SUFFIX)))
+            {
+                candidates.push(path);
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter_map(|path| {
+            let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+            Some((modified, path))
+        })
+        .max()
+        .map(|(_, path)| path)
+        .ok_or_else(|| {
+            ProjError::NothingToDo(format!(
+                "No backups of '{}' in {}. Create one with 'proj backup'.",
+                project_name,
+                backups_dir.display()
+            ))
+            .into()
+        })
+}
+
+/// The project a schema backup directory (from `proj upgrade`) belongs to
+fn schema_backup_project(dir: &Path) -> Option<String> {
+    let content = std::fs::read_to_string(dir.join("backup_metadata.json")).ok()?;
+    let metadata: serde_json::Value = serde_json::from_str(&content).ok()?;
+    metadata.get("project_name")?.as_str().map(String::from)
+}
+
+/// Integrity-check the backup at `path` (shown as `shown`) and compare row counts
+fn verify(path: &Path, shown: &Path) -> Result<()> {
+    println!("{} {}", "Verifying".bold(), shown.display());
+    println!();
+
+    let backup = open_checked(path, shown, OpenFlags::default())?;
+    let live = open_database(&get_tracking_db_path()?)?;
+
+    let backup_ok = integrity(&backup, "Backup");
+    let live_ok = integrity(&live, "Live database");
+
+    let version = |conn: &Connection| {
+        get_schema_version(conn)
+            .ok()
+            .flatten()
+            .unwrap_or_else(|| "unknown".to_string())
+    };
+    let (backup_version, live_version) = (version(&backup), version(&live));
+    if backup_version == live_version {
+        println!(
+            "  {} Schema v{} (same as live)",
+            "✓".green(),
+            backup_version
+        );
+    } else {
+        println!(
+            "  {} Schema v{} (live database: v{})",
+            "ℹ".dimmed(),
+            backup_version,
+            live_version
+        );
+    }
+
+    let backup_counts = row_counts(&backup)?;
+    let live_counts = row_counts(&live)?;
+    let tables: BTreeSet<&String> = backup_counts.keys().chain(live_counts.keys()).collect();
+
+    println!();
+    println!(
+        "  {:<28} {:>8} {:>8}",
+        "Table".bold(),
+        "Backup".bold(),
+        "Live".bold()
+    );
+    for table in &tables {
+        let cell = |count: Option<&i64>| count.map_or("-".to_string(), |n| n.to_string());
+        let note = match (backup_counts.get(*table), live_counts.get(*table)) {
+            (Some(b), Some(l)) if b != l => format!("{:+}", l - b),
+            (Some(_), None) => "not in live database".to_string(),
+            (None, Some(_)) => "not in backup".to_string(),
+            _ => String::new(),
+        };
+        println!(
+            "  {:<28} {:>8} {:>8}  {}",
+            table,
+            cell(backup_counts.get(*table)),
+            cell(live_counts.get(*table)),
+            note.dimmed()
+        );
+    }
+    println!();
+
+    if !backup_ok {
+        bail!(
+            "{} failed its integrity check; don't restore from it",
+            shown.display()
+        );
+    }
+    if !live_ok {
+        println!(
+            "{} The live database is damaged. Restore it with 'proj rollback --schema --backup', or recover it with 'sqlite3 .recover'.",
+            "⚠".yellow()
+        );
+    }
+    println!(
+        "{} Backup verified: {} tables, {} rows",
+        "✓".green(),
+        backup_counts.len(),
+        backup_counts.values().sum::<i64>()
+    );
+    Ok(())
+}
+
+/// Print what putting the backup database at `backup` in place of `live` would
+/// change, table by table and row by row
+pub fn print_restore_changes(live: &Path, backup: &Path) -> Result<()> {
+    // Read-only, so not even a WAL checkpoint touches either file
+    let live = open_checked(live, live, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let backup = open_checked(backup, backup, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    let live_tables = user_tables(&live)?;
+    let backup_tables = user_tables(&backup)?;
+
+    println!("Changes to tracking.db:");
+    let mut unchanged = 0;
+    let tables: BTreeSet<&String> = live_tables.iter().chain(backup_tables.iter()).collect();
+    for table in tables {
+        match (live_tables.contains(table), backup_tables.contains(table)) {
+            (true, false) => {
+                println!(
+                    "  {} {} dropped ({} rows)",
+                    "-".red(),
+                    table,
+                    count_rows(&live, table)?
+                );
+                continue;
+            }
+            (false, true) => {
+                println!(
+                    "  {} {} created ({} rows)",
+                    "+".green(),
+                    table,
+                    count_rows(&backup, table)?
+                );
+                continue;
+            }
+            _ => {}
+        }
+
+        let live_columns = columns(&live, table)?;
+        let backup_columns = columns(&backup, table)?;
+        let common: Vec<String> = live_columns
+            .iter()
+            .filter(|c| backup_columns.contains(c))
+            .cloned()
+            .collect();
+        let live_rows = rows(&live, table, &common)?;
+        let backup_rows = rows(&backup, table, &common)?;
+
+        let mut lines = Vec::new();
+        let removed: Vec<i64> = live_rows
+            .keys()
+            .filter(|id| !backup_rows.contains_key(id))
+            .copied()
+            .collect();
+        let added: Vec<i64> = backup_rows
+            .keys()
+            .filter(|id| !live_rows.contains_key(id))
+            .copied()
+            .collect();
+        let changed: Vec<i64> = live_rows
+            .iter()
+            .filter(|(id, values)| backup_rows.get(id).is_some_and(|b| b != *values))
+            .map(|(id, _)| *id)
+            .collect();
+        if !removed.is_empty() {
+            lines.push(format!(
+                "{} {} removed: {}",
+                "-".red(),
+                rows_label(removed.len()),
+                ids(&removed)
+            ));
+        }
+        if !added.is_empty() {
+            lines.push(format!(
+                "{} {} back: {}",
+                "+".green(),
+                rows_label(added.len()),
+                ids(&added)
+            ));
+        }
+        if !changed.is_empty() {
+            lines.push(format!(
+                "{} {} changed: {}",
+                "~".yellow(),
+                rows_label(changed.len()),
+                ids(&changed)
+            ));
+        }
+        let dropped: Vec<&String> = live_columns
+            .iter()
+            .filter(|c| !common.contains(c))
+            .collect();
+        if !dropped.is_empty() {
+            lines.push(format!(
+                "{} columns dropped: {}",
+                "-".red(),
+                dropped
+                    .iter()
+                    .map(|c| c.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        let created: Vec<&String> = backup_columns
+            .iter()
+            .filter(|c| !common.contains(c))
+            .collect();
+        if !created.is_empty() {
+            lines.push(format!(
+                "{} columns created: {}",
+                "+".green(),
+                created
+                    .iter()
+                    .map(|c| c.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        if lines.is_empty() {
+            unchanged += 1;
+            continue;
+        }
+        println!("  {}", table.bold());
+        for line in lines {
+            println!("    {}", line);
+        }
+    }
+    if unchanged > 0 {
+        println!("  {}", format!("{} tables unchanged", unchanged).dimmed());
+    }
+    Ok(())
+}
+
+fn rows_label(n: usize) -> String {
+    format!("{} row{}", n, if n == 1 { "" } else { "s" })
+}
+
+/// `#1, #2, ... and 5 more`
+fn ids(ids: &[i64]) -> String {
+    let mut shown: Vec<String> = ids
+        .iter()
+        .take(SHOW_IDS)
+        .map(|id| format!("#{}", id))
+        .collect();
+    if ids.len() > SHOW_IDS {
+        shown.push(format!("and {} more", ids.len() - SHOW_IDS));
+    }
+    shown.join(", ")
+}
+
+/// Open a database, failing early if the file isn't one (named `shown` in errors)
+fn open_checked(path: &Path, shown: &Path, flags: OpenFlags) -> Result<Connection> {
+    let conn = Connection::open_with_flags(path, flags)
+        .with_context(|| format!("Failed to open {}", shown.display()))?;
+    conn.query_row("SELECT COUNT(*) FROM sqlite_master", [], |row| {
+        row.get::<_, i64>(0)
+    })
+    .with_context(|| format!("{} is not a readable SQLite database", shown.display()))?;
+    Ok(conn)
+}
+
+/// Run PRAGMA integrity_check and print the result; true if it passed
+fn integrity(conn: &Connection, name: &str) -> bool {
+    let problems: Result<Vec<String>, _> =
+        conn.prepare("PRAGMA integrity_check").and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()
+        });
+
+    match problems {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => {
+            println!("  {} {} integrity check passed", "✓".green(), name);
+            true
+        }
+        Ok(rows) => {
+            println!("  {} {} failed integrity check:", "✗".red(), name);
+            for row in rows.iter().take(SHOW_IDS) {
+                println!("      {}", row);
+            }
+            false
+        }
+        Err(e) => {
+            println!("  {} {} integrity check failed: {}", "✗".red(), name, e);
+            false
+        }
+    }
+}
+
+/// Tables holding data: not SQLite's own, and not full-text indexes or their
+/// shadow tables, which are rebuilt from the others
+fn user_tables(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT name, COALESCE(sql, '') FROM sqlite_master
+         WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )?;
+    let tables = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let virtual_tables: Vec<&String> = tables
+        .iter()
+        .filter(|(_, sql)| sql.to_uppercase().starts_with("CREATE VIRTUAL TABLE"))
+        .map(|(name, _)| name)
+        .collect();
+    Ok(tables
+        .iter()
+        .filter(|(name, _)| {
+            !virtual_tables
+                .iter()
+                .any(|vt| name == *vt || name.starts_with(&format!("{}_", vt)))
+        })
+        .map(|(name, _)| name.clone())
+        .collect())
+}
+
+fn row_counts(conn: &Connection) -> Result<BTreeMap<String, i64>> {
+    user_tables(conn)?
+        .into_iter()
+        .map(|table| Ok((table.clone(), count_rows(conn, &table)?)))
+        .collect()
+}
+
+fn count_rows(conn: &Connection, table: &str) -> Result<i64> {
+    Ok(conn.query_row(
+        &format!("SELECT COUNT(*) FROM {}", quote(table)),
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+fn columns(conn: &Connection, table: &str) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", quote(table)))?;
+    let columns = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(columns)
+}
+
+/// Each row's values in `columns`, by rowid
+fn rows(conn: &Connection, table: &str, columns: &[String]) -> Result<BTreeMap<i64, Vec<Value>>> {
+    let mut select = vec!["rowid".to_string()];
+    select.extend(columns.iter().map(|c| quote(c)));
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {} FROM {}",
+        select.join(", "),
+        quote(table)
+    ))?;
+    let rows = stmt
+        .query_map([], |row| {
+            let values = (1..=columns.len())
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<rusqlite::Result<Vec<_>>>()?;
+            Ok((row.get::<_, i64>(0)?, values))
+        })?
+        .collect::<rusqlite::Result<BTreeMap<_, _>>>()?;
+    Ok(rows)
+}
+
+fn quote(identifier: &str) -> String {
+    format!("\"{}\"", identifier.replace('"', "\"\""))
+}
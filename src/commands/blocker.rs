@@ -0,0 +1,155 @@
+// Blocker commands - resolve, list
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::cli::{BlockerCommands, BlockerSubcommand};
+use crate::database::{open_database, write_transaction};
+use crate::paths::get_tracking_db_path;
+use crate::session::get_or_create_session_with_info;
+use crate::text::truncate;
+
+pub fn run(cmd: BlockerCommands) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    match cmd.command {
+        BlockerSubcommand::Resolve {
+            id,
+            resolution,
+            unblock,
+        } => {
+            let session_result = get_or_create_session_with_info(&conn)?;
+            let session = session_result.session;
+
+            // Notify if a stale session was closed
+            if let Some(closed) = session_result.auto_closed_session {
+                eprintln!(
+                    "{} Previous session #{} was stale. Started new session #{}",
+                    "⚠".yellow(),
+                    closed.session_id,
+                    session.session_id
+                );
+            }
+
+            cmd_blocker_resolve(&conn, session.session_id, id, &resolution, unblock)
+        }
+        BlockerSubcommand::List => cmd_blocker_list(&conn),
+    }
+}
+
+/// Resolve a blocker, optionally unblocking its linked task
+fn cmd_blocker_resolve(
+    conn: &Connection,
+    session_id: i64,
+    blocker_id: i64,
+    resolution: &str,
+    unblock: bool,
+) -> Result<()> {
+    let blocker: Option<(String, String, Option<i64>)> = conn
+        .query_row(
+            "SELECT description, status, related_task_id FROM blockers WHERE blocker_id = ?1",
+            [blocker_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )
+        .ok();
+
+    let Some((description, status, related_task_id)) = blocker else {
+        bail!("Blocker #{} not found", blocker_id);
+    };
+
+    if status == "resolved" {
+        bail!("Blocker #{} is already resolved", blocker_id);
+    }
+
+    let tx = write_transaction(conn)?;
+
+    tx.execute(
+        "UPDATE blockers SET status = 'resolved', resolution = ?1, resolved_at = datetime('now') WHERE blocker_id = ?2",
+        rusqlite::params![resolution, blocker_id],
+    )?;
+
+    let summary = format!("Blocker resolved: {}", truncate(&description, 50));
+    tx.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'blocker_resolve', ?2, ?3)",
+        rusqlite::params![session_id, blocker_id, summary],
+    )?;
+
+    let mut unblocked_task = None;
+    if let Some(task_id) = related_task_id {
+        if unblock {
+            let changed = tx.execute(
+                "UPDATE tasks SET status = 'pending', blocked_by = NULL WHERE task_id = ?1 AND status = 'blocked'",
+                [task_id],
+            )?;
+            if changed > 0 {
+                unblocked_task = Some(task_id);
+            }
+        }
+    }
+
+    tx.commit()?;
+
+    println!(
+        "{} Resolved blocker #{}: {}",
+        "✓".green(),
+        blocker_id,
+        truncate(&description, 50)
+    );
+
+    if let Some(task_id) = unblocked_task {
+        println!("  {} Task #{} unblocked", "→".green(), task_id);
+    } else if let Some(task_id) = related_task_id {
+        if !unblock {
+            println!(
+                "  {} Linked task #{}. Use --unblock to move it back to pending.",
+                "→".dimmed(),
+                task_id
+            );
+        }
+    }
+
+    Ok(())
+}
+
+/// List active blockers
+fn cmd_blocker_list(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "SELECT blocker_id, description, related_task_id, created_at
+         FROM blockers
+         WHERE status = 'active'
+         ORDER BY created_at",
+    )?;
+
+    let blockers = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<i64>>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if blockers.is_empty() {
+        println!("No active blockers.");
+        return Ok(());
+    }
+
+    println!("{}", "Active Blockers:".bold());
+    println!("{}", "-".repeat(60));
+
+    for (blocker_id, description, related_task_id, created_at) in blockers {
+        let date = created_at.split(' ').next().unwrap_or(&created_at);
+        print!("{} #{:<4} {}", "✗".red(), blocker_id, description);
+        if let Some(task_id) = related_task_id {
+            print!(" {}", format!("(task #{})", task_id).dimmed());
+        }
+        println!(" {}", date.dimmed());
+    }
+
+    Ok(())
+}
@@ -1,116 +1,516 @@
-// Check command - verify database integrity
+// Check command - preflight for a project: the tracking database, AGENTS.md and its
+// CLAUDE.md/GEMINI.md links, git and shell hooks, docs staleness, and the registry entry
+//
+// Every check comes out ok, warn, or fail. Any failure (or warning, with --strict) makes
+// the command exit non-zero so it can gate CI; --porcelain prints a line per check and
+// --json the whole report.
 
-use anyhow::{Context, Result};
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use serde::Serialize;
 
+use crate::commands::init::AGENT_ALIASES;
+use crate::commands::{agents, docs, docs_openapi, githook, registered, shell};
 use crate::config::ProjectConfig;
 use crate::database::open_database;
-use crate::paths::{get_config_path, get_tracking_db_path};
+use crate::docs_db;
+use crate::paths::{get_config_path, get_project_root, get_tracking_db_path, same_path};
+use crate::porcelain;
 use crate::SCHEMA_VERSION;
 
-pub fn run() -> Result<()> {
+/// Outcome of one check
+#[derive(Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Outcome {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// One check as --json prints it
+#[derive(Serialize)]
+struct CheckResult {
+    check: String,
+    status: Outcome,
+    detail: String,
+}
+
+/// The whole --json output
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    passed: bool,
+    failures: usize,
+    warnings: usize,
+    checks: &'a [CheckResult],
+}
+
+/// The checks run so far, printed as they come in unless the report is going out as JSON
+struct Report {
+    json: bool,
+    results: Vec<CheckResult>,
+}
+
+impl Report {
+    /// Heading for a group of checks (people only)
+    fn section(&self, title: &str) {
+        if !self.json && !porcelain::enabled() {
+            println!("\n{}", title);
+        }
+    }
+
+    /// Record one check result and print `line` for people, or
+    /// "<ok|warn|fail> <check> <detail>" for scripts
+    fn report(&mut self, outcome: Outcome, check: &str, detail: &str, line: String) {
+        if porcelain::enabled() && !self.json {
+            let status = match outcome {
+                Outcome::Ok => "ok",
+                Outcome::Warn => "warn",
+                Outcome::Fail => "fail",
+            };
+            porcelain::print(&[status, check, detail]);
+        } else if !self.json {
+            println!("{}", line);
+        }
+        self.results.push(CheckResult {
+            check: check.to_string(),
+            status: outcome,
+            detail: detail.to_string(),
+        });
+    }
+
+    fn ok(&mut self, check: &str, detail: &str, line: String) {
+        self.report(
+            Outcome::Ok,
+            check,
+            detail,
+            format!("  {} {}", "✓".green(), line),
+        );
+    }
+
+    fn warn(&mut self, check: &str, detail: &str, line: String) {
+        self.report(
+            Outcome::Warn,
+            check,
+            detail,
+            format!("  {} {}", "⚠".yellow(), line),
+        );
+    }
+
+    fn fail(&mut self, check: &str, detail: &str, line: String) {
+        self.report(
+            Outcome::Fail,
+            check,
+            detail,
+            format!("  {} {}", "✗".red(), line),
+        );
+    }
+
+    fn count(&self, outcome: Outcome) -> usize {
+        self.results.iter().filter(|r| r.status == outcome).count()
+    }
+}
+
+pub fn run(json: bool, strict: bool) -> Result<()> {
     // Load config
     let config = load_config()?;
+    let root = get_project_root()?;
+    let mut report = Report {
+        json,
+        results: Vec::new(),
+    };
 
-    println!("Checking database integrity...\n");
+    let tracking_db = get_tracking_db_path()?;
+    if !json && !porcelain::enabled() {
+        println!("Checking project...\n");
+        println!("Tracking DB: {}", tracking_db.display());
+    }
+    check_tracking_db(&mut report, &tracking_db);
 
-    let mut issues = Vec::new();
+    report.section(&format!("Config: {}", get_config_path()?.display()));
+    report.ok(
+        "config",
+        &config.name,
+        format!("Project: {} ({})", config.name, config.project_type),
+    );
 
-    // Check tracking database
-    let tracking_db = get_tracking_db_path()?;
-    println!("Tracking DB: {}", tracking_db.display());
-
-    if tracking_db.exists() {
-        match open_database(&tracking_db) {
-            Ok(conn) => {
-                // Integrity check
-                match conn.query_row("PRAGMA integrity_check", [], |row| row.get::<_, String>(0)) {
-                    Ok(result) if result == "ok" => {
-                        println!("  {} Integrity check passed", "✓".green());
-                    }
-                    Ok(result) => {
-                        println!("  {} Integrity issues: {}", "✗".red(), result);
-                        issues.push(format!("Tracking DB integrity: {}", result));
-                    }
-                    Err(e) => {
-                        println!("  {} Integrity check failed: {}", "✗".red(), e);
-                        issues.push(format!("Integrity check error: {}", e));
-                    }
-                }
+    report.section("Agent files:");
+    check_agents(&mut report, &root)?;
 
-                // Check schema version
-                let current_version: String = conn
-                    .query_row(
-                        "SELECT value FROM project_meta WHERE key = 'schema_version'",
-                        [],
-                        |row| row.get(0),
-                    )
-                    .unwrap_or_else(|_| "1.0".to_string());
-
-                if current_version == SCHEMA_VERSION {
-                    println!("  {} Schema version: v{}", "✓".green(), current_version);
-                } else {
-                    println!(
-                        "  {} Schema needs upgrade: v{} → v{}",
-                        "⚠".yellow(),
-                        current_version,
-                        SCHEMA_VERSION
-                    );
-                }
+    report.section("Hooks:");
+    check_hooks(&mut report, &root);
 
-                // Check table counts
-                let session_count: i64 = conn
-                    .query_row("SELECT COUNT(*) FROM sessions", [], |row| row.get(0))
-                    .unwrap_or(0);
-                let task_count: i64 = conn
-                    .query_row("SELECT COUNT(*) FROM tasks", [], |row| row.get(0))
-                    .unwrap_or(0);
-                let decision_count: i64 = conn
-                    .query_row("SELECT COUNT(*) FROM decisions", [], |row| row.get(0))
-                    .unwrap_or(0);
-
-                println!(
-                    "  {} {} sessions, {} tasks, {} decisions",
-                    "✓".green(),
-                    session_count,
-                    task_count,
-                    decision_count
-                );
+    report.section("Docs:");
+    check_docs(&mut report, &root);
+
+    report.section("Registry:");
+    check_registry(&mut report, &root, &config);
+
+    // Summary
+    let failed = report.count(Outcome::Fail);
+    let warned = report.count(Outcome::Warn);
+    let failing = failed + if strict { warned } else { 0 };
+    if json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&JsonReport {
+                passed: failing == 0,
+                failures: failed,
+                warnings: warned,
+                checks: &report.results,
+            })?
+        );
+    } else if !porcelain::enabled() {
+        println!();
+        if failed == 0 && warned == 0 {
+            println!("{} All checks passed", "✓".green());
+        } else {
+            let mut counts = Vec::new();
+            if failed > 0 {
+                counts.push(format!("{} failed", failed));
             }
-            Err(e) => {
-                println!("  {} Error: {}", "✗".red(), e);
-                issues.push(format!("Tracking DB error: {}", e));
+            if warned > 0 {
+                counts.push(format!("{} warning(s)", warned));
+            }
+            let mark = if failing > 0 {
+                "✗".red()
+            } else {
+                "⚠".yellow()
+            };
+            println!("{} {}", mark, counts.join(", "));
+            for result in &report.results {
+                if result.status == Outcome::Fail || (strict && result.status == Outcome::Warn) {
+                    println!("  • {}: {}", result.check, result.detail);
+                }
             }
         }
+    }
+
+    if failing > 0 {
+        bail!("{} check(s) failed", failing);
+    }
+    Ok(())
+}
+
+/// Integrity, schema version, and record counts of the tracking database
+fn check_tracking_db(report: &mut Report, tracking_db: &Path) {
+    if !tracking_db.exists() {
+        report.fail("database", "not found", "File not found".to_string());
+        return;
+    }
+    let conn = match open_database(tracking_db) {
+        Ok(conn) => conn,
+        Err(e) => {
+            report.fail("database", &e.to_string(), format!("Error: {}", e));
+            return;
+        }
+    };
+
+    // Integrity check
+    match conn.query_row("PRAGMA integrity_check", [], |row| row.get::<_, String>(0)) {
+        Ok(result) if result == "ok" => {
+            report.ok("integrity", "ok", "Integrity check passed".to_string())
+        }
+        Ok(result) => report.fail(
+            "integrity",
+            &result,
+            format!("Integrity issues: {}", result),
+        ),
+        Err(e) => report.fail(
+            "integrity",
+            &e.to_string(),
+            format!("Integrity check failed: {}", e),
+        ),
+    }
+
+    // Check schema version
+    let current_version: String = conn
+        .query_row(
+            "SELECT value FROM project_meta WHERE key = 'schema_version'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or_else(|_| "1.0".to_string());
+
+    if current_version == SCHEMA_VERSION {
+        report.ok(
+            "schema",
+            &current_version,
+            format!("Schema version: v{}", current_version),
+        );
     } else {
-        println!("  {} File not found", "✗".red());
-        issues.push("Tracking database not found".to_string());
+        report.warn(
+            "schema",
+            &format!("{} -> {}", current_version, SCHEMA_VERSION),
+            format!(
+                "Schema needs upgrade: v{} → v{} (run 'proj upgrade')",
+                current_version, SCHEMA_VERSION
+            ),
+        );
     }
 
-    // Check config
-    println!("\nConfig: {}", get_config_path()?.display());
-    println!("  {} Project: {}", "✓".green(), config.name);
-    println!("  {} Type: {}", "✓".green(), config.project_type);
+    // Check table counts
+    let count = |table: &str| -> i64 {
+        conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+            row.get(0)
+        })
+        .unwrap_or(0)
+    };
+    let records = format!(
+        "{} sessions, {} tasks, {} decisions",
+        count("sessions"),
+        count("tasks"),
+        count("decisions")
+    );
+    report.ok("records", &records, records.clone());
+}
 
-    // Summary
-    if issues.is_empty() {
-        println!("\n{} All checks passed", "✓".green());
+/// AGENTS.md has current instructions, and CLAUDE.md/GEMINI.md resolve to it
+fn check_agents(report: &mut Report, root: &Path) -> Result<()> {
+    let agents_path = root.join("AGENTS.md");
+    let Ok(content) = std::fs::read_to_string(&agents_path) else {
+        report.fail(
+            "agents-md",
+            "missing",
+            "AGENTS.md missing (run 'proj agents update')".to_string(),
+        );
+        return Ok(());
+    };
+
+    let template = agents::Template::load()?;
+    if !agents::has_instructions(&content) {
+        report.fail(
+            "agents-md",
+            "no proj instructions",
+            "AGENTS.md has no proj instructions (run 'proj agents update')".to_string(),
+        );
     } else {
-        println!("\n{} Found {} issue(s):", "⚠".yellow(), issues.len());
-        for issue in &issues {
-            println!("  • {}", issue);
+        match agents::outdated_label(&content, &template) {
+            None => report.ok(
+                "agents-md",
+                &template.label,
+                format!("AGENTS.md ({})", template.label),
+            ),
+            Some(old) => report.warn(
+                "agents-md",
+                &format!("{} -> {}", old, template.label),
+                format!(
+                    "AGENTS.md instructions are {}, current is {} (run 'proj agents update')",
+                    old, template.label
+                ),
+            ),
         }
     }
 
+    let agents_file = agents_path.canonicalize().unwrap_or(agents_path);
+    for name in AGENT_ALIASES {
+        let path = root.join(name);
+        let check = name.to_lowercase().replace('.', "-");
+        if path.is_symlink() {
+            match path.canonicalize() {
+                Ok(target) if same_path(&target, &agents_file) => {
+                    report.ok(&check, "symlink", format!("{} → AGENTS.md", name))
+                }
+                Ok(target) => report.fail(
+                    &check,
+                    &format!("points to {}", target.display()),
+                    format!(
+                        "{} points to {}, not AGENTS.md (run 'proj agents sync')",
+                        name,
+                        target.display()
+                    ),
+                ),
+                Err(_) => report.fail(
+                    &check,
+                    "broken symlink",
+                    format!("{} is a broken symlink (run 'proj agents sync')", name),
+                ),
+            }
+        } else if path.is_file() {
+            // Where symlinks aren't available it's a hard link or a copy
+            if std::fs::read(&path).ok().as_deref() == Some(content.as_bytes()) {
+                report.ok(&check, "copy", format!("{} matches AGENTS.md", name));
+            } else {
+                report.fail(
+                    &check,
+                    "differs from AGENTS.md",
+                    format!("{} differs from AGENTS.md (run 'proj agents sync')", name),
+                );
+            }
+        } else {
+            report.fail(
+                &check,
+                "missing",
+                format!("{} missing (run 'proj agents sync')", name),
+            );
+        }
+    }
     Ok(())
 }
 
+/// proj's git hooks in this repository and the shell integration
+fn check_hooks(report: &mut Report, root: &Path) {
+    match githook::missing_hooks(root) {
+        Ok(missing) if missing.is_empty() => {
+            report.ok("git-hooks", "installed", "Git hooks installed".to_string())
+        }
+        Ok(missing) => report.warn(
+            "git-hooks",
+            &format!("missing {}", missing.join(",")),
+            format!(
+                "Git hooks not installed: {} (run 'proj githook install')",
+                missing.join(", ")
+            ),
+        ),
+        Err(e) => report.warn(
+            "git-hooks",
+            &format!("{:#}", e),
+            format!("Git hooks: {:#}", e),
+        ),
+    }
+
+    if shell::is_installed() {
+        report.ok(
+            "shell-hook",
+            "installed",
+            "Shell integration installed".to_string(),
+        );
+    } else {
+        report.warn(
+            "shell-hook",
+            "not installed",
+            "Shell integration not installed (run 'proj shell install')".to_string(),
+        );
+    }
+}
+
+/// The docs database opens and isn't behind its source files or spec
+fn check_docs(report: &mut Report, root: &Path) {
+    let Some(path) = docs_db::find_docs_db(root) else {
+        report.ok(
+            "docs",
+            "none",
+            "No documentation database (optional)".to_string(),
+        );
+        return;
+    };
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let conn = match docs_db::open_docs_db(&path) {
+        Ok(conn) if docs_db::is_valid_docs_db(&conn) => conn,
+        Ok(_) => {
+            report.fail(
+                "docs",
+                "missing the docs tables",
+                format!("{} is missing the docs tables", name),
+            );
+            return;
+        }
+        Err(e) => {
+            report.fail(
+                "docs",
+                &format!("{:#}", e),
+                format!("{} can't be opened: {:#}", name, e),
+            );
+            return;
+        }
+    };
+
+    let generated_from = crate::schema_docs::get_meta(&conn, "generated_from")
+        .ok()
+        .flatten();
+    match generated_from.as_deref() {
+        Some("source_analysis") => match docs::stale_source_files(&conn, root) {
+            Some(stale) if stale > 0 => report.warn(
+                "docs",
+                &format!("{} source files changed", stale),
+                format!(
+                    "{}: {} source file(s) changed since last update (run 'proj docs refresh')",
+                    name, stale
+                ),
+            ),
+            _ => report.ok("docs", "current", format!("{} is current", name)),
+        },
+        Some(docs_openapi::GENERATED_FROM) => match docs_openapi::is_stale(&conn, root) {
+            Ok(Some(false)) => report.ok("docs", "current", format!("{} is current", name)),
+            Ok(Some(true)) => report.warn(
+                "docs",
+                "spec changed",
+                format!(
+                    "{}: spec changed since last update (run 'proj docs refresh')",
+                    name
+                ),
+            ),
+            Ok(None) => report.warn(
+                "docs",
+                "spec not found",
+                format!("{}: spec file not found", name),
+            ),
+            Err(e) => report.fail("docs", &format!("{:#}", e), format!("{}: {:#}", name, e)),
+        },
+        _ => report.ok("docs", "ok", name),
+    }
+}
+
+/// This project has exactly one registry entry and its name matches the config
+fn check_registry(report: &mut Report, root: &Path, config: &ProjectConfig) {
+    let registry = match registered::load_registry() {
+        Ok(registry) => registry,
+        Err(e) => {
+            report.fail(
+                "registry",
+                &format!("{:#}", e),
+                format!("registry.json can't be read: {:#}", e),
+            );
+            return;
+        }
+    };
+
+    let here = registered::canonical_path(&root.to_string_lossy());
+    let entries: Vec<_> = registry
+        .registered_projects
+        .iter()
+        .filter(|entry| same_path(&registered::canonical_path(&entry.path), &here))
+        .collect();
+    match entries.as_slice() {
+        [] => report.warn(
+            "registry",
+            "not registered",
+            "Not in the registry (run 'proj register')".to_string(),
+        ),
+        [entry] if entry.name != config.name => report.warn(
+            "registry",
+            &format!("registered as {}", entry.name),
+            format!(
+                "Registered as '{}' but the project is '{}' (run 'proj registered --prune')",
+                entry.name, config.name
+            ),
+        ),
+        [entry] => report.ok(
+            "registry",
+            &entry.name,
+            format!("Registered as {}", entry.name),
+        ),
+        _ => report.warn(
+            "registry",
+            &format!("{} entries", entries.len()),
+            format!(
+                "Registered {} times (run 'proj registered --prune')",
+                entries.len()
+            ),
+        ),
+    }
+}
+
 /// Load project configuration
 fn load_config() -> Result<ProjectConfig> {
     let config_path = get_config_path()?;
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
@@ -0,0 +1,496 @@
+// CI command - a project health report for pull requests
+//
+// `proj ci report` runs in a CI job against the checked-out tracking database. It
+// reports active blockers, overdue tasks, and docs staleness, and sets the commits on
+// the pull request's branch against the decisions logged on it, so a PR that changes
+// a lot without recording why stands out. --post comments the markdown on the pull
+// request through the GitHub client `proj task sync github` uses; the comment carries a
+// marker, so later runs edit it rather than piling up new ones.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::cli::{CiCommands, CiSubcommand};
+use crate::commands::{docs, docs_openapi};
+use crate::config::ProjectConfig;
+use crate::database::open_database;
+use crate::docs_db;
+use crate::error::ProjError;
+use crate::git;
+use crate::github::GitHub;
+use crate::models::DueStatus;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::repository::Repository;
+
+/// Marks the comment --post wrote, so the next run can find and replace it
+const COMMENT_MARKER: &str = "<!-- proj:ci-report -->";
+
+#[derive(Serialize)]
+struct CiReport {
+    project: String,
+    /// The pull request's branch (None when detached outside a PR job)
+    branch: Option<String>,
+    /// What the branch was compared against
+    base: Option<String>,
+    blockers: Vec<BlockerItem>,
+    overdue_tasks: Vec<TaskItem>,
+    docs: DocsItem,
+    /// Commits on the branch since the base (None when they couldn't be listed)
+    commits: Option<Vec<CommitItem>>,
+    /// Decisions logged on the branch
+    decisions: Vec<DecisionItem>,
+}
+
+#[derive(Serialize)]
+struct BlockerItem {
+    id: i64,
+    description: String,
+    task_id: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct TaskItem {
+    id: i64,
+    description: String,
+    priority: String,
+    due: String,
+}
+
+#[derive(Serialize)]
+struct DocsItem {
+    /// File name of the docs database, if there is one
+    database: Option<String>,
+    /// "none", "current", "stale", "spec_missing", or "invalid"
+    status: &'static str,
+    /// Source files changed since the last refresh (source-generated docs)
+    stale_files: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct CommitItem {
+    hash: String,
+    subject: String,
+    /// Tasks the message names (#task-N, #tN, or a Proj-Task: trailer)
+    tasks: Vec<i64>,
+}
+
+#[derive(Serialize)]
+struct DecisionItem {
+    id: i64,
+    topic: String,
+    decision: String,
+}
+
+pub fn run(cmd: CiCommands) -> Result<()> {
+    match cmd.command {
+        CiSubcommand::Report {
+            format,
+            base,
+            output,
+            post,
+            repo,
+        } => cmd_report(&format, base, output, post, repo.as_deref()),
+    }
+}
+
+fn cmd_report(
+    format: &str,
+    base: Option<String>,
+    output: Option<std::path::PathBuf>,
+    post: Option<Option<i64>>,
+    repo: Option<&str>,
+) -> Result<()> {
+    if format != "md" && format != "json" {
+        return Err(ProjError::InvalidInput(format!(
+            "Unknown format '{}'. Use md or json.",
+            format
+        ))
+        .into());
+    }
+    // Fail on a missing PR number before doing the work
+    let pr = post
+        .map(|number| {
+            number.or_else(pull_request_from_env).ok_or_else(|| {
+                ProjError::InvalidInput(
+                    "No pull request to post to: pass --post <PR> ($GITHUB_REF isn't a pull request)"
+                        .to_string(),
+                )
+            })
+        })
+        .transpose()?;
+
+    let root = get_project_root()?;
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    let report = build_report(&conn, &root, base)?;
+
+    let markdown = render_markdown(&report);
+    let text = if format == "json" {
+        serde_json::to_string_pretty(&report)? + "\n"
+    } else {
+        markdown.clone()
+    };
+    match output {
+        Some(path) => std::fs::write(&path, &text)
+            .with_context(|| format!("Failed to write {}", path.display()))?,
+        None => print!("{}", text),
+    }
+
+    if let Some(number) = pr {
+        let github = GitHub::connect(&root, repo)?;
+        let url = github.upsert_comment(number, COMMENT_MARKER, &markdown)?;
+        // stderr, so the report on stdout stays clean for a pipe
+        eprintln!(
+            "{} Posted report to {}#{}{}",
+            "✓".green(),
+            github.repo,
+            number,
+            if url.is_empty() {
+                String::new()
+            } else {
+                format!(": {}", url)
+            }
+        );
+    }
+    Ok(())
+}
+
+/// The pull request number in GitHub Actions' $GITHUB_REF (refs/pull/<n>/merge)
+fn pull_request_from_env() -> Option<i64> {
+    let github_ref = std::env::var("GITHUB_REF").ok()?;
+    github_ref
+        .strip_prefix("refs/pull/")?
+        .split('/')
+        .next()?
+        .parse()
+        .ok()
+}
+
+fn build_report(conn: &Connection, root: &Path, base: Option<String>) -> Result<CiReport> {
+    let project = ProjectConfig::load()
+        .map(|c| c.name)
+        .unwrap_or_else(|_| "project".to_string());
+
+    let repo = Repository::begin(conn)?;
+    let blockers = repo
+        .active_blockers()?
+        .iter()
+        .map(|b| BlockerItem {
+            id: b.blocker_id,
+            description: b.description.clone(),
+            task_id: b.related_task_id,
+        })
+        .collect();
+    let overdue_tasks = repo
+        .active_tasks()?
+        .iter()
+        .filter(|t| t.due_status() == DueStatus::Overdue)
+        .map(|t| TaskItem {
+            id: t.task_id,
+            description: t.description.clone(),
+            priority: t.priority.clone(),
+            due: t.due.clone().unwrap_or_default(),
+        })
+        .collect();
+    repo.finish()?;
+
+    // In a PR job the checkout is a detached merge commit; Actions names the branch
+    let branch = std::env::var("GITHUB_HEAD_REF")
+        .ok()
+        .filter(|b| !b.is_empty())
+        .or_else(|| git::current_branch(root));
+    let base = base.or_else(|| default_base(root));
+    let commits = base.as_deref().and_then(|b| branch_commits(root, b));
+
+    let decisions = match &branch {
+        Some(branch) => {
+            let mut stmt = conn.prepare(
+                "SELECT decision_id, topic, decision FROM decisions
+                 WHERE branch = ?1 AND status = 'active'
+                 ORDER BY created_at",
+            )?;
+            let rows = stmt.query_map([branch], |row| {
+                Ok(DecisionItem {
+                    id: row.get(0)?,
+                    topic: row.get(1)?,
+                    decision: row.get(2)?,
+                })
+            })?;
+            rows.collect::<Result<Vec<_>, _>>()?
+        }
+        None => Vec::new(),
+    };
+
+    Ok(CiReport {
+        project,
+        branch,
+        base,
+        blockers,
+        overdue_tasks,
+        docs: docs_status(root),
+        commits,
+        decisions,
+    })
+}
+
+/// origin/$GITHUB_BASE_REF in a PR job, else the branch origin/HEAD points at
+fn default_base(root: &Path) -> Option<String> {
+    if let Some(base) = std::env::var("GITHUB_BASE_REF")
+        .ok()
+        .filter(|b| !b.is_empty())
+    {
+        return Some(format!("origin/{}", base));
+    }
+    let output = git::run(
+        Command::new("git")
+            .args([
+                "symbolic-ref",
+                "--quiet",
+                "--short",
+                "refs/remotes/origin/HEAD",
+            ])
+            .current_dir(root),
+    )
+    .ok()?;
+    let base = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !base.is_empty()).then_some(base)
+}
+
+/// Commits on HEAD that aren't on `base`, newest first (None if git can't compare
+/// them, as in a shallow clone)
+fn branch_commits(root: &Path, base: &str) -> Option<Vec<CommitItem>> {
+    let output = git::run(
+        Command::new("git")
+            .args([
+                "log",
+                "--no-merges",
+                "--format=%x1e%h%x1f%B",
+                &format!("{}..HEAD", base),
+            ])
+            .args(git::path_scope(root))
+            .current_dir(root),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .split('\x1e')
+            .filter_map(|record| {
+                let (hash, message) = record.split_once('\x1f')?;
+                Some(CommitItem {
+                    hash: hash.to_string(),
+                    subject: message.lines().next().unwrap_or_default().to_string(),
+                    tasks: git::task_refs(message),
+                })
+            })
+            .collect(),
+    )
+}
+
+/// Whether the docs database is behind its source files or spec
+fn docs_status(root: &Path) -> DocsItem {
+    let Some(path) = docs_db::find_docs_db(root) else {
+        return DocsItem {
+            database: None,
+            status: "none",
+            stale_files: None,
+        };
+    };
+    let database = path.file_name().map(|n| n.to_string_lossy().to_string());
+    let status = |status, stale_files| DocsItem {
+        database: database.clone(),
+        status,
+        stale_files,
+    };
+
+    let conn = match docs_db::open_docs_db(&path) {
+        Ok(conn) if docs_db::is_valid_docs_db(&conn) => conn,
+        _ => return status("invalid", None),
+    };
+    let generated_from = crate::schema_docs::get_meta(&conn, "generated_from")
+        .ok()
+        .flatten();
+    match generated_from.as_deref() {
+        Some("source_analysis") => match docs::stale_source_files(&conn, root) {
+            Some(stale) if stale > 0 => status("stale", Some(stale)),
+            stale => status("current", stale),
+        },
+        Some(docs_openapi::GENERATED_FROM) => match docs_openapi::is_stale(&conn, root) {
+            Ok(Some(true)) => status("stale", None),
+            Ok(Some(false)) => status("current", None),
+            Ok(None) => status("spec_missing", None),
+            Err(_) => status("invalid", None),
+        },
+        _ => status("current", None),
+    }
+}
+
+/// The report as a PR comment. IDs go in code spans so GitHub doesn't link them to
+/// issues.
+fn render_markdown(report: &CiReport) -> String {
+    let mut out = String::new();
+    let _ = writeln!(out, "{}", COMMENT_MARKER);
+    let _ = writeln!(out, "## Project health: {}\n", report.project);
+
+    let docs = match (report.docs.status, report.docs.stale_files) {
+        ("none", _) => "No docs database".to_string(),
+        ("stale", Some(files)) => format!(
+            "**Stale**: {} source {} changed since the last refresh",
+            files,
+            plural(files, "file")
+        ),
+        ("stale", None) => "**Stale**: the spec changed since the last refresh".to_string(),
+        ("spec_missing", _) => "**Spec file not found**".to_string(),
+        ("invalid", _) => "**Can't be read**".to_string(),
+        _ => "Current".to_string(),
+    };
+    let branch_label = report
+        .branch
+        .as_deref()
+        .map(|b| format!("`{}`", b))
+        .unwrap_or_else(|| "this branch".to_string());
+    let untracked = report
+        .commits
+        .as_ref()
+        .map(|c| c.iter().filter(|c| c.tasks.is_empty()).count());
+
+    let _ = writeln!(out, "| Check | Status |");
+    let _ = writeln!(out, "|-------|--------|");
+    let _ = writeln!(
+        out,
+        "| Active blockers | {} |",
+        count_cell(report.blockers.len())
+    );
+    let _ = writeln!(
+        out,
+        "| Overdue tasks | {} |",
+        count_cell(report.overdue_tasks.len())
+    );
+    let _ = writeln!(out, "| Docs | {} |", docs);
+    match (&report.commits, &report.base) {
+        (Some(commits), _) => {
+            let mut status = format!("{} {}", commits.len(), plural(commits.len(), "commit"));
+            if let Some(untracked) = untracked.filter(|n| *n > 0) {
+                let _ = write!(status, ", **{} without a task**", untracked);
+            }
+            let _ = write!(
+                status,
+                ", {} {} logged",
+                report.decisions.len(),
+                plural(report.decisions.len(), "decision")
+            );
+            if !commits.is_empty() && report.decisions.is_empty() {
+                status.push_str(" **(none recorded)**");
+            }
+            let _ = writeln!(out, "| Commits on {} | {} |", branch_label, status);
+        }
+        (None, Some(base)) => {
+            let _ = writeln!(
+                out,
+                "| Commits on {} | Couldn't compare with `{}` (shallow clone? fetch with `fetch-depth: 0`) |",
+                branch_label, base
+            );
+        }
+        (None, None) => {
+            let _ = writeln!(
+                out,
+                "| Commits on {} | No base branch (pass `--base`) |",
+                branch_label
+            );
+        }
+    }
+
+    if !report.blockers.is_empty() {
+        let _ = writeln!(out, "\n### Blockers\n");
+        for b in &report.blockers {
+            let task = b
+                .task_id
+                .map(|t| format!(" (task `#{}`)", t))
+                .unwrap_or_default();
+            let _ = writeln!(out, "- `#{}` {}{}", b.id, b.description, task);
+        }
+    }
+
+    if !report.overdue_tasks.is_empty() {
+        let _ = writeln!(out, "\n### Overdue tasks\n");
+        for t in &report.overdue_tasks {
+            let _ = writeln!(
+                out,
+                "- `#{}` {} (due {}, {})",
+                t.id, t.description, t.due, t.priority
+            );
+        }
+    }
+
+    if let Some(commits) = report.commits.as_ref().filter(|c| !c.is_empty()) {
+        let _ = writeln!(
+            out,
+            "\n### Commits on {} vs `{}`\n",
+            branch_label,
+            report.base.as_deref().unwrap_or_default()
+        );
+        for c in commits {
+            let tasks = if c.tasks.is_empty() {
+                "no task".to_string()
+            } else {
+                let ids: Vec<String> = c.tasks.iter().map(|t| format!("`#{}`", t)).collect();
+                format!("{} {}", plural(ids.len(), "task"), ids.join(", "))
+            };
+            let _ = writeln!(
+                out,
+                "- `{}` {} ({})",
+                c.hash,
+                escape_markdown(&c.subject),
+                tasks
+            );
+        }
+    }
+
+    if !report.decisions.is_empty() {
+        let _ = writeln!(out, "\n### Decisions on {}\n", branch_label);
+        for d in &report.decisions {
+            let _ = writeln!(out, "- **{}**: {} (`#{}`)", d.topic, d.decision, d.id);
+        }
+    }
+
+    let _ = writeln!(out, "\n<sub>Generated by proj {}</sub>", crate::VERSION);
+    out
+}
+
+/// "None", or the count in bold
+fn count_cell(n: usize) -> String {
+    if n == 0 {
+        "None".to_string()
+    } else {
+        format!("**{}**", n)
+    }
+}
+
+/// Keep a commit subject from turning into markdown (or a mention) in the comment
+fn escape_markdown(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '*' | '_' | '`' | '[' | ']' | '<' | '>' | '|' | '@') {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn plural(n: usize, word: &str) -> String {
+    if n == 1 {
+        word.to_string()
+    } else {
+        format!("{}s", word)
+    }
+}
@@ -1,12 +1,87 @@
 // Cleanup command - clean up stale context items
+//
+// Besides reviewing open items that went stale, cleanup applies the retention
+// policies in config.json (`retention`), which say per table whether old
+// closed items are kept, archived to .tracking/archive.db, purged, or (for
+// sessions) compressed.
+
+use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::{Context, Result};
 use chrono::{Duration, Utc};
-use dialoguer::Select;
+use colored::Colorize;
+use dialoguer::{Confirm, Select};
 use rusqlite::Connection;
 
-use crate::database::open_database;
+use crate::commands::archive;
+use crate::commands::compress;
+use crate::config::{ProjectConfig, RetentionPolicy};
+use crate::database::{id_list, open_database, write_transaction};
+use crate::error::ProjError;
 use crate::paths::get_tracking_db_path;
+use crate::text::truncate;
+
+/// Tables a retention policy can name, with the actions each supports
+const RETENTION_TABLES: [(&str, &[&str]); 8] = [
+    ("tasks", &["keep", "archive", "purge"]),
+    ("decisions", &["keep", "archive", "purge"]),
+    ("blockers", &["keep", "archive", "purge"]),
+    ("questions", &["keep", "purge"]),
+    ("notes", &["keep", "purge"]),
+    ("sessions", &["keep", "compress"]),
+    ("commits", &["keep", "purge"]),
+    ("activity", &["keep", "purge"]),
+];
+
+/// A table without archive support, purged by a single condition
+struct PurgeTable {
+    name: &'static str,
+    table: &'static str,
+    id_column: &'static str,
+    summary: &'static str,
+    /// Rows old enough to purge, given a cutoff datetime as ?1
+    eligible: &'static str,
+    /// Rows in other tables that go with it: (table, column)
+    dependents: &'static [(&'static str, &'static str)],
+}
+
+const PURGE_TABLES: [PurgeTable; 4] = [
+    PurgeTable {
+        name: "questions",
+        table: "questions",
+        id_column: "question_id",
+        summary: "question",
+        eligible: "status IN ('answered', 'deferred') AND COALESCE(answered_at, created_at) < ?1",
+        dependents: &[],
+    },
+    PurgeTable {
+        name: "notes",
+        table: "context_notes",
+        id_column: "note_id",
+        summary: "title",
+        eligible: "status IN ('outdated', 'archived') AND COALESCE(updated_at, created_at) < ?1",
+        dependents: &[("item_tags", "note_id")],
+    },
+    PurgeTable {
+        name: "commits",
+        table: "git_commits",
+        id_column: "commit_id",
+        summary: "short_hash || ' ' || message",
+        eligible: "committed_at < ?1",
+        dependents: &[],
+    },
+    PurgeTable {
+        name: "activity",
+        table: "activity_log",
+        id_column: "log_id",
+        summary: "action_type || ': ' || summary",
+        eligible: "timestamp < ?1",
+        dependents: &[],
+    },
+];
+
+/// Items shown per table in the retention report
+const REPORT_LIMIT: usize = 10;
 
 /// Stale blocker info
 struct StaleBlocker {
@@ -52,7 +127,29 @@ impl StaleItems {
     }
 }
 
-pub fn run(auto: bool, days: u32) -> Result<()> {
+pub fn run(auto: bool, days: u32, optimize: bool, dry_run: bool) -> Result<()> {
+    let config = ProjectConfig::load()
+        .with_context(|| "No project found. Run 'proj init' to initialize.")?;
+    validate_policies(&config.retention)?;
+
+    review_stale_items(auto, days, dry_run)?;
+
+    if !config.retention.is_empty() {
+        println!();
+        apply_retention(&config.retention, auto, dry_run)?;
+    }
+
+    if optimize && !dry_run {
+        println!();
+        crate::commands::db::optimize()?;
+    }
+
+    Ok(())
+}
+
+/// Walk through stale items, archiving or resolving them (all of them with --auto).
+/// A dry run only lists them.
+fn review_stale_items(auto: bool, days: u32, dry_run: bool) -> Result<()> {
     // Open database
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
@@ -79,6 +176,9 @@ pub fn run(auto: bool, days: u32) -> Result<()> {
         for b in &stale.blockers {
             println!("  [{}] {}", b.blocker_id, b.description);
             println!("      Created: {}", format_date(&b.created_at));
+            if dry_run {
+                continue;
+            }
 
             if !auto {
                 let options = &["Keep active", "Mark resolved", "Archive"];
@@ -112,6 +212,9 @@ pub fn run(auto: bool, days: u32) -> Result<()> {
         for q in &stale.questions {
             println!("  [{}] {}", q.question_id, q.question);
             println!("      Created: {}", format_date(&q.created_at));
+            if dry_run {
+                continue;
+            }
 
             if !auto {
                 let options = &["Keep open", "Mark answered", "Mark deferred"];
@@ -145,6 +248,9 @@ pub fn run(auto: bool, days: u32) -> Result<()> {
         for t in &stale.tasks {
             println!("  [{}] {} ({})", t.task_id, t.description, t.status);
             println!("      Created: {}", format_date(&t.created_at));
+            if dry_run {
+                continue;
+            }
 
             if !auto {
                 let options = &["Keep as-is", "Mark completed", "Cancel"];
@@ -181,6 +287,9 @@ pub fn run(auto: bool, days: u32) -> Result<()> {
         for n in &stale.context_notes {
             println!("  [{}] [{}] {}", n.note_id, n.category, n.title);
             println!("      Created: {}", format_date(&n.created_at));
+            if dry_run {
+                continue;
+            }
 
             let options = &["Keep active", "Mark outdated", "Archive"];
             let selection = Select::new()
@@ -203,11 +312,250 @@ pub fn run(auto: bool, days: u32) -> Result<()> {
         }
     }
 
+    if dry_run {
+        println!("\nDry run: no stale items changed.");
+    } else {
+        println!(
+            "\nCleanup complete. {} item(s) archived/resolved.",
+            archived_count
+        );
+    }
+
+    Ok(())
+}
+
+/// Report what the retention policies cover, then apply them
+/// (after confirmation unless --auto; never on a dry run)
+fn apply_retention(
+    policies: &BTreeMap<String, RetentionPolicy>,
+    auto: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    // Select everything before changing anything, so items held back because
+    // an open item points at them stay consistent across actions
+    let mut to_archive = Vec::new();
+    let mut to_purge = Vec::new();
+    for (name, policy) in policies {
+        if policy.action == "archive" || policy.action == "purge" {
+            if let Ok(kind) = archive::parse_kind(name) {
+                let group = if policy.action == "archive" {
+                    &mut to_archive
+                } else {
+                    &mut to_purge
+                };
+                group.push((kind, policy.after_days));
+            }
+        }
+    }
+    let (archive_selection, archive_kept) = archive::select_closed(&conn, &to_archive)?;
+    let (purge_selection, purge_kept) = archive::select_closed(&conn, &to_purge)?;
+
+    let mut simple_purges: Vec<(&PurgeTable, BTreeSet<i64>)> = Vec::new();
+    for table in &PURGE_TABLES {
+        if let Some(policy) = policies.get(table.name).filter(|p| p.action == "purge") {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {} FROM {} WHERE {}",
+                table.id_column, table.table, table.eligible
+            ))?;
+            let ids = stmt
+                .query_map([cutoff(policy.after_days)], |row| row.get::<_, i64>(0))?
+                .collect::<Result<BTreeSet<_>, _>>()?;
+            simple_purges.push((table, ids));
+        }
+    }
+
+    let compress_after = policies
+        .get("sessions")
+        .filter(|p| p.action == "compress")
+        .map(|p| p.after_days as i64);
+    let sessions = match compress_after {
+        Some(days) => compress::eligible_sessions(&conn, days)?,
+        None => Vec::new(),
+    };
+
+    // Report
+    println!("{}", "Retention policies:".bold());
+    let mut pending = 0;
+    for (name, _) in &RETENTION_TABLES {
+        let Some(policy) = policies.get(*name) else {
+            continue;
+        };
+        if policy.action == "keep" {
+            println!("  {}: keep", name);
+            continue;
+        }
+        let label = format!(
+            "{}: {} after {} days",
+            name, policy.action, policy.after_days
+        );
+
+        if *name == "sessions" {
+            println!("  {} - {} session(s)", label, sessions.len());
+            if !sessions.is_empty() {
+                let shown = sessions
+                    .iter()
+                    .take(REPORT_LIMIT)
+                    .map(|id| format!("#{}", id))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                let more = sessions.len().saturating_sub(REPORT_LIMIT);
+                if more > 0 {
+                    println!("    {} and {} more", shown, more);
+                } else {
+                    println!("    {}", shown);
+                }
+            }
+            pending += sessions.len();
+            continue;
+        }
+
+        let rows = if let Ok(kind) = archive::parse_kind(name) {
+            let selection = if policy.action == "archive" {
+                &archive_selection
+            } else {
+                &purge_selection
+            };
+            let ids = selection
+                .iter()
+                .find(|(k, _)| k.name == kind.name)
+                .map(|(_, ids)| ids.clone())
+                .unwrap_or_default();
+            let rows = archive::summaries(&conn, "main", kind, &ids)?;
+            println!("  {} - {} item(s)", label, rows.len());
+            rows
+        } else {
+            let (table, ids) = simple_purges
+                .iter()
+                .find(|(t, _)| t.name == *name)
+                .expect("purge policies have a selection");
+            let rows = purge_summaries(&conn, table, ids)?;
+            println!("  {} - {} item(s)", label, rows.len());
+            rows
+        };
+        for (id, summary) in rows.iter().take(REPORT_LIMIT) {
+            println!("    #{} {}", id, truncate(summary, 60));
+        }
+        if rows.len() > REPORT_LIMIT {
+            println!("    ... and {} more", rows.len() - REPORT_LIMIT);
+        }
+        pending += rows.len();
+    }
+
+    let kept = archive_kept + purge_kept;
+    if kept > 0 {
+        println!(
+            "  {}",
+            format!("{} item(s) held back: still referenced by open items", kept).dimmed()
+        );
+    }
+
+    if pending == 0 {
+        println!("\nNothing is due under the retention policies.");
+        return Ok(());
+    }
+    if dry_run {
+        println!("\nDry run: nothing archived, purged, or compressed.");
+        return Ok(());
+    }
+    if !auto {
+        let confirm = Confirm::new()
+            .with_prompt("Apply these retention policies?")
+            .default(false)
+            .interact()?;
+        if !confirm {
+            println!("Retention policies not applied.");
+            return Ok(());
+        }
+    }
+
+    let archived: usize = archive_selection.iter().map(|(_, ids)| ids.len()).sum();
+    if archived > 0 {
+        archive::move_to_archive(&conn, &archive_selection)?;
+    }
+    let mut purged: usize = purge_selection.iter().map(|(_, ids)| ids.len()).sum();
+    if purged > 0 {
+        archive::purge(&conn, &purge_selection)?;
+    }
+    {
+        let tx = write_transaction(&conn)?;
+        for (table, ids) in &simple_purges {
+            if ids.is_empty() {
+                continue;
+            }
+            let in_list = id_list(ids);
+            for (dependent, column) in table.dependents {
+                tx.execute(
+                    &format!(
+                        "DELETE FROM {} WHERE {} IN ({})",
+                        dependent, column, in_list
+                    ),
+                    [],
+                )?;
+            }
+            tx.execute(
+                &format!(
+                    "DELETE FROM {} WHERE {} IN ({})",
+                    table.table, table.id_column, in_list
+                ),
+                [],
+            )?;
+            purged += ids.len();
+        }
+        tx.commit()?;
+    }
+
+    let mut compressed = 0;
+    if let Some(days) = compress_after {
+        // One batch per call; keep going until nothing is left
+        while !compress::eligible_sessions(&conn, days)?.is_empty() {
+            println!();
+            let batch = compress::compress_older_than(true, false, days)?;
+            if batch == 0 {
+                break;
+            }
+            compressed += batch;
+        }
+    }
+
     println!(
-        "\nCleanup complete. {} item(s) archived/resolved.",
-        archived_count
+        "\n{} Retention applied: {} archived, {} purged, {} session(s) compressed.",
+        "✓".green(),
+        archived,
+        purged,
+        compressed
     );
+    Ok(())
+}
 
+/// Reject policies naming unknown tables or actions a table doesn't support
+pub fn validate_policies(policies: &BTreeMap<String, RetentionPolicy>) -> Result<()> {
+    for (name, policy) in policies {
+        let Some((_, actions)) = RETENTION_TABLES.iter().find(|(t, _)| t == name) else {
+            return Err(ProjError::InvalidInput(format!(
+                "Unknown table '{}' in retention policies. Use: {}",
+                name,
+                RETENTION_TABLES
+                    .iter()
+                    .map(|(t, _)| *t)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))
+            .into());
+        };
+        if !actions.contains(&policy.action.as_str()) {
+            return Err(ProjError::InvalidInput(format!(
+                "Retention action '{}' isn't supported for {}. Use: {}",
+                policy.action,
+                name,
+                actions.join(", ")
+            ))
+            .into());
+        }
+    }
     Ok(())
 }
 
@@ -312,3 +660,32 @@ fn format_date(datetime_str: &str) -> &str {
         datetime_str
     }
 }
+
+/// Cutoff datetime for items closed more than `days` ago
+fn cutoff(days: u32) -> String {
+    (Utc::now() - Duration::days(days as i64))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+/// (id, summary) for rows of a purge-only table
+fn purge_summaries(
+    conn: &Connection,
+    table: &PurgeTable,
+    ids: &BTreeSet<i64>,
+) -> Result<Vec<(i64, String)>> {
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let mut stmt = conn.prepare(&format!(
+        "SELECT {id}, {summary} FROM {table} WHERE {id} IN ({ids}) ORDER BY {id}",
+        id = table.id_column,
+        summary = table.summary,
+        table = table.table,
+        ids = id_list(ids)
+    ))?;
+    let rows = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
@@ -2,11 +2,20 @@
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
 use dialoguer::Confirm;
 use rusqlite::Connection;
 
 use crate::database::open_database;
+use crate::llm;
 use crate::paths::get_tracking_db_path;
+use crate::text::truncate;
+
+/// Instructions for LLM summaries of old sessions
+const SUMMARY_PROMPT: &str = "You compress a software project's old work-session log into a short summary \
+for an AI assistant that will pick the project up later. Keep decisions and their reasons, what was \
+built or fixed, and anything left unfinished. Drop repetition and session bookkeeping. Reply with \
+plain text only, no preamble, in at most 150 words.";
 
 /// Session data for compression
 struct SessionInfo {
@@ -17,18 +26,24 @@ struct SessionInfo {
     agent: Option<String>,
 }
 
-pub fn run(auto: bool) -> Result<()> {
+/// Max sessions folded into one summary
+const BATCH_SIZE: usize = 10;
+
+pub fn run(auto: bool, use_llm: bool) -> Result<()> {
+    compress_older_than(auto, use_llm, 7)?;
+    Ok(())
+}
+
+/// Compress one batch of completed sessions that ended more than `min_age_days`
+/// ago. Returns how many sessions were compressed (0 if none or cancelled).
+pub fn compress_older_than(auto: bool, use_llm: bool, min_age_days: i64) -> Result<usize> {
     // Open database
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
         .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
 
-    // Default settings
-    let threshold = 10; // max sessions to compress at once
-    let min_age_days = 7;
-
     // Get sessions eligible for compression
-    let sessions = get_sessions_for_compression(&conn, threshold, min_age_days)?;
+    let sessions = get_sessions_for_compression(&conn, BATCH_SIZE, min_age_days)?;
 
     if sessions.is_empty() {
         println!("No sessions eligible for compression.");
@@ -36,7 +51,7 @@ pub fn run(auto: bool) -> Result<()> {
             "  (Looking for completed sessions older than {} days)",
             min_age_days
         );
-        return Ok(());
+        return Ok(0);
     }
 
     println!(
@@ -45,8 +60,7 @@ pub fn run(auto: bool) -> Result<()> {
     );
     for s in &sessions {
         let summary_preview = match &s.summary {
-            Some(sum) if sum.len() > 50 => format!("{}...", &sum[..50]),
-            Some(sum) => sum.clone(),
+            Some(sum) => truncate(sum, 53),
             None => "No summary".to_string(),
         };
         let ended_str = s
@@ -68,7 +82,7 @@ pub fn run(auto: bool) -> Result<()> {
 
         if !confirm {
             println!("Cancelled.");
-            return Ok(());
+            return Ok(0);
         }
     }
 
@@ -121,41 +135,41 @@ pub fn run(auto: bool) -> Result<()> {
         session_ids.last().unwrap_or(&0),
         date_range
     );
-    compressed.push_str(&format!("Covered {} work sessions. ", sessions.len()));
-
-    // Extract key summaries
-    let summaries: Vec<&str> = sessions
-        .iter()
-        .filter_map(|s| s.summary.as_deref())
-        .collect();
 
-    if !summaries.is_empty() {
-        compressed.push_str("Key accomplishments: ");
-        let preview: Vec<&str> = summaries.iter().take(3).copied().collect();
-        compressed.push_str(&preview.join("; "));
-        if summaries.len() > 3 {
-            compressed.push_str(&format!(" (+{} more)", summaries.len() - 3));
+    if use_llm {
+        println!("\nSummarizing with LLM...");
+        let prompt = format!(
+            "Sessions #{}-#{}, {}:\n{}",
+            session_ids.first().unwrap_or(&0),
+            session_ids.last().unwrap_or(&0),
+            date_range,
+            combined_summaries.join("\n")
+        );
+        let completion = llm::complete_or(SUMMARY_PROMPT, &prompt, || basic_summary(&sessions));
+        match (&completion.model, &completion.fallback_reason) {
+            (Some(model), _) => println!("  Summarized by {}", model),
+            (None, Some(reason)) => println!(
+                "{} LLM unavailable ({}). Using basic compression.",
+                "⚠".yellow(),
+                reason
+            ),
+            (None, None) => {}
         }
+        compressed.push_str(&completion.text);
+    } else {
+        compressed.push_str(&basic_summary(&sessions));
     }
 
     let compressed_tokens = estimate_tokens(&compressed);
     let savings = original_tokens.saturating_sub(compressed_tokens);
-    let savings_pct = if original_tokens > 0 {
-        (savings * 100) / original_tokens
-    } else {
-        0
-    };
+    let savings_pct = (savings * 100).checked_div(original_tokens).unwrap_or(0);
 
     println!("\nCompression result:");
     println!("  Original: ~{} tokens", original_tokens);
     println!("  Compressed: ~{} tokens", compressed_tokens);
     println!("  Savings: ~{} tokens ({}%)", savings, savings_pct);
 
-    let preview = if compressed.len() > 200 {
-        format!("{}...", &compressed[..200])
-    } else {
-        compressed.clone()
-    };
+    let preview = truncate(&compressed, 203);
     println!("\nCompressed summary:");
     println!("  {}", preview);
 
@@ -169,7 +183,34 @@ pub fn run(auto: bool) -> Result<()> {
     )?;
     println!("\nSaved compression. Original sessions preserved but marked as compressed.");
 
-    Ok(())
+    Ok(session_ids.len())
+}
+
+/// IDs of all sessions `compress_older_than` would pick up, oldest first
+pub fn eligible_sessions(conn: &Connection, min_age_days: i64) -> Result<Vec<i64>> {
+    let sessions = get_sessions_for_compression(conn, i64::MAX as usize, min_age_days)?;
+    Ok(sessions.into_iter().map(|s| s.session_id).collect())
+}
+
+/// Summary without an LLM: session count and the first few session summaries
+fn basic_summary(sessions: &[SessionInfo]) -> String {
+    let mut summary = format!("Covered {} work sessions. ", sessions.len());
+
+    // Extract key summaries
+    let summaries: Vec<&str> = sessions
+        .iter()
+        .filter_map(|s| s.summary.as_deref())
+        .collect();
+
+    if !summaries.is_empty() {
+        summary.push_str("Key accomplishments: ");
+        let preview: Vec<&str> = summaries.iter().take(3).copied().collect();
+        summary.push_str(&preview.join("; "));
+        if summaries.len() > 3 {
+            summary.push_str(&format!(" (+{} more)", summaries.len() - 3));
+        }
+    }
+    summary
 }
 
 /// Get sessions eligible for compression (completed, old enough, not already compressed)
@@ -0,0 +1,412 @@
+// Config command - view and change config.json, and the global defaults in ~/.proj/config.json
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use atty::Stream;
+use colored::Colorize;
+use dialoguer::Confirm;
+use serde_json::{Map, Value};
+
+use crate::cli::{ConfigCommands, ConfigSubcommand};
+use crate::config::{load_global_defaults, ProjectConfig, PROJECT_ONLY_KEYS};
+use crate::error::ProjError;
+use crate::paths::{get_config_path, get_global_config_path, get_global_dir};
+
+/// Decides whether commands run without a terminal are queued for `proj approve`,
+/// so only someone at a terminal may change it
+const APPROVAL_KEY: &str = "agent_writes";
+
+/// Settings whose value is a map with user-chosen keys, so `set` can add new entries
+const OPEN_MAPS: &[&str] = &["ranking.table_weights", "retention", "context_profiles"];
+
+pub fn run(cmd: ConfigCommands) -> Result<()> {
+    match cmd.command {
+        ConfigSubcommand::List { global } => list(global),
+        ConfigSubcommand::Get { key, global } => get(&key, global),
+        ConfigSubcommand::Set { key, value, global } => set(&key, &value, global),
+        ConfigSubcommand::Unset { key, global } => unset(&key, global),
+        ConfigSubcommand::Edit { global } => edit(global),
+    }
+}
+
+/// Show every setting with its value and where it comes from
+fn list(global: bool) -> Result<()> {
+    if global {
+        let path = get_global_config_path()?;
+        let defaults = load_global_defaults()?.unwrap_or_default();
+        println!("\nGlobal defaults ({}):\n", path.display());
+        if defaults.is_empty() {
+            println!("  (none set)");
+            println!(
+                "\nSet one with '{}'.",
+                "proj config set --global <key> <value>".cyan()
+            );
+        }
+        for (key, value) in flatten(&Value::Object(defaults)) {
+            println!("  {:<36} {}", key, display_value(value));
+        }
+        return Ok(());
+    }
+
+    let project = Value::Object(read_map(&get_config_path()?)?);
+    let global = Value::Object(load_global_defaults()?.unwrap_or_default());
+    let effective = effective_value(false)?;
+    let name = lookup(&project, "name")
+        .and_then(|v| v.as_str())
+        .unwrap_or("");
+
+    println!("\nConfig for {}:\n", name.bold());
+    for (key, value) in flatten(&effective) {
+        let source = if lookup(&project, &key).is_some() {
+            "project".normal()
+        } else if lookup(&global, &key).is_some() {
+            "global".cyan()
+        } else {
+            "default".dimmed()
+        };
+        println!("  {:<36} {:<40} {}", key, display_value(value), source);
+    }
+    println!();
+    Ok(())
+}
+
+/// Print one setting
+fn get(key: &str, global: bool) -> Result<()> {
+    let value = if global {
+        Value::Object(load_global_defaults()?.unwrap_or_default())
+    } else {
+        effective_value(false)?
+    };
+    let found = lookup(&value, key).ok_or_else(|| {
+        ProjError::InvalidInput(if global {
+            format!(
+                "'{}' isn't set in {}",
+                key,
+                get_global_config_path().unwrap_or_default().display()
+            )
+        } else {
+            format!(
+                "Unknown setting '{}'. Run 'proj config list' to see them all.",
+                key
+            )
+        })
+    })?;
+    match found {
+        Value::String(s) => println!("{}", s),
+        other => println!("{}", serde_json::to_string_pretty(other)?),
+    }
+    Ok(())
+}
+
+/// Change one setting, rejecting it if the result doesn't validate
+fn set(key: &str, raw: &str, global: bool) -> Result<()> {
+    check_known_key(key, global)?;
+    require_terminal_for(key)?;
+    let value = serde_json::from_str(raw).unwrap_or_else(|_| Value::String(raw.to_string()));
+
+    let path = config_file(global)?;
+    let mut fields = read_map(&path)?;
+    insert_path(&mut fields, key, value.clone());
+    let content = serde_json::to_string_pretty(&fields)?;
+    check_content(&content, global)?;
+    write_config(&path, &content)?;
+
+    println!(
+        "{} {} = {}{}",
+        "✓".green(),
+        key,
+        display_value(&value),
+        if global { " (global)" } else { "" }
+    );
+    Ok(())
+}
+
+/// Remove one setting so the global or built-in default applies again
+fn unset(key: &str, global: bool) -> Result<()> {
+    if !global && PROJECT_ONLY_KEYS.contains(&key) {
+        return Err(ProjError::InvalidInput(format!(
+            "'{}' belongs to this project and can't be unset",
+            key
+        ))
+        .into());
+    }
+    require_terminal_for(key)?;
+    let path = config_file(global)?;
+    let mut fields = read_map(&path)?;
+    if !remove_path(&mut fields, key) {
+        println!("'{}' isn't set in {}", key, path.display());
+        return Ok(());
+    }
+    let content = serde_json::to_string_pretty(&fields)?;
+    check_content(&content, global)?;
+    write_config(&path, &content)?;
+
+    let fallback = effective_value(global)
+        .ok()
+        .and_then(|v| lookup(&v, key).map(display_value));
+    match fallback {
+        Some(v) => println!("{} Unset {} (now {})", "✓".green(), key, v),
+        None => println!("{} Unset {}", "✓".green(), key),
+    }
+    Ok(())
+}
+
+/// Open the config in an editor, validate it on save, and offer to re-edit or restore it
+fn edit(global: bool) -> Result<()> {
+    let path = config_file(global)?;
+    let original = if path.exists() {
+        Some(std::fs::read_to_string(&path)?)
+    } else {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, "{}\n")?;
+        None
+    };
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let args: Vec<&str> = parts.collect();
+
+    loop {
+        let status = Command::new(program)
+            .args(&args)
+            .arg(&path)
+            .status()
+            .with_context(|| format!("Failed to start editor '{}'", editor))?;
+        if !status.success() {
+            restore(&path, original.as_deref())?;
+            anyhow::bail!("Editor exited with {}; config left unchanged", status);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        if changes_approval(original.as_deref(), &content) {
+            if let Err(e) = require_terminal_for(APPROVAL_KEY) {
+                restore(&path, original.as_deref())?;
+                return Err(e);
+            }
+        }
+        match check_content(&content, global) {
+            Ok(()) => {
+                if original.as_deref().unwrap_or("{}\n") == content {
+                    println!("No changes.");
+                } else {
+                    println!("{} Saved {}", "✓".green(), path.display());
+                }
+                return Ok(());
+            }
+            Err(e) => {
+                println!("{} {}", "✗".red(), e);
+                let again = Confirm::new()
+                    .with_prompt("Edit again? (no restores the previous config)")
+                    .default(true)
+                    .interact()?;
+                if !again {
+                    restore(&path, original.as_deref())?;
+                    return Err(ProjError::InvalidInput(format!("Config not saved: {}", e)).into());
+                }
+            }
+        }
+    }
+}
+
+/// Refuse to change agent_writes without a terminal, so an agent whose writes are
+/// queued can't turn the queue off
+fn require_terminal_for(key: &str) -> Result<()> {
+    if key == APPROVAL_KEY && !atty::is(Stream::Stdin) {
+        return Err(ProjError::InvalidInput(format!(
+            "'{}' can only be changed from a terminal",
+            APPROVAL_KEY
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// Whether an edit changed agent_writes (content that isn't valid JSON is left to
+/// check_content)
+fn changes_approval(original: Option<&str>, content: &str) -> bool {
+    let setting = |text: &str| {
+        serde_json::from_str::<Value>(text)
+            .ok()
+            .and_then(|v| v.get(APPROVAL_KEY).cloned())
+    };
+    setting(original.unwrap_or("{}")) != setting(content)
+}
+
+/// Put back the file as it was before `edit`, or remove it if it didn't exist
+fn restore(path: &Path, original: Option<&str>) -> Result<()> {
+    match original {
+        Some(content) => std::fs::write(path, content)?,
+        None => std::fs::remove_file(path)?,
+    }
+    Ok(())
+}
+
+/// Check a config file's content: known keys, types, and values proj accepts
+fn check_content(content: &str, global: bool) -> Result<()> {
+    let fields: Map<String, Value> = serde_json::from_str(content)
+        .map_err(|e| ProjError::InvalidInput(format!("Invalid JSON: {}", e)))?;
+
+    let known = serde_json::to_value(ProjectConfig::default())?;
+    for key in fields.keys() {
+        if known.get(key).is_none() {
+            return Err(ProjError::InvalidInput(format!("Unknown setting '{}'", key)).into());
+        }
+        if global && PROJECT_ONLY_KEYS.contains(&key.as_str()) {
+            return Err(ProjError::InvalidInput(format!(
+                "'{}' is per-project and can't be a global default",
+                key
+            ))
+            .into());
+        }
+    }
+
+    let config = if global {
+        let mut value = known;
+        if let Some(defaults) = value.as_object_mut() {
+            defaults.extend(fields);
+        }
+        serde_json::from_value::<ProjectConfig>(value).map_err(anyhow::Error::from)
+    } else {
+        ProjectConfig::from_json(content)
+    }
+    .map_err(|e| ProjError::InvalidInput(format!("Invalid config: {}", e)))?;
+
+    config.validate().map_err(ProjError::InvalidInput)?;
+    crate::commands::cleanup::validate_policies(&config.retention)?;
+    Ok(())
+}
+
+/// Reject keys that don't name an existing setting (new entries are allowed in open maps)
+fn check_known_key(key: &str, global: bool) -> Result<()> {
+    let effective = effective_value(global)?;
+    let (parent, _) = key.rsplit_once('.').unwrap_or(("", key));
+    let known = lookup(&effective, key).is_some()
+        || (!parent.is_empty()
+            && OPEN_MAPS.contains(&parent)
+            && lookup(&effective, parent).is_some_and(|v| v.is_object()));
+    if !known {
+        return Err(ProjError::InvalidInput(format!(
+            "Unknown setting '{}'. Run 'proj config list' to see them all.",
+            key
+        ))
+        .into());
+    }
+    if global && PROJECT_ONLY_KEYS.contains(&key) {
+        return Err(ProjError::InvalidInput(format!(
+            "'{}' is per-project and can't be a global default",
+            key
+        ))
+        .into());
+    }
+    Ok(())
+}
+
+/// The settings in effect: the project's config with inherited and built-in defaults
+/// filled in, or for --global, the built-in defaults with the global file on top
+fn effective_value(global: bool) -> Result<Value> {
+    if global {
+        let mut value = serde_json::to_value(ProjectConfig::default())?;
+        if let (Some(fields), Some(defaults)) = (value.as_object_mut(), load_global_defaults()?) {
+            fields.extend(defaults);
+        }
+        return Ok(value);
+    }
+    let config = ProjectConfig::load().with_context(|| "Failed to load config.json")?;
+    Ok(serde_json::to_value(config)?)
+}
+
+fn config_file(global: bool) -> Result<std::path::PathBuf> {
+    if global {
+        get_global_config_path()
+    } else {
+        get_config_path()
+    }
+}
+
+/// Read a config file as a JSON object (empty if it doesn't exist)
+fn read_map(path: &Path) -> Result<Map<String, Value>> {
+    if !path.exists() {
+        return Ok(Map::new());
+    }
+    let content = std::fs::read_to_string(path)?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+fn write_config(path: &Path, content: &str) -> Result<()> {
+    if path.starts_with(get_global_dir()?) {
+        std::fs::create_dir_all(get_global_dir()?)?;
+    }
+    std::fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Follow a dotted key through nested objects
+fn lookup<'a>(value: &'a Value, key: &str) -> Option<&'a Value> {
+    key.split('.').try_fold(value, |v, part| v.get(part))
+}
+
+/// Set a dotted key, creating intermediate objects as needed
+fn insert_path(fields: &mut Map<String, Value>, key: &str, value: Value) {
+    let mut parts: Vec<&str> = key.split('.').collect();
+    let last = parts.pop().unwrap_or(key);
+    let mut current = fields;
+    for part in parts {
+        let entry = current
+            .entry(part.to_string())
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            *entry = Value::Object(Map::new());
+        }
+        current = entry.as_object_mut().expect("just made an object");
+    }
+    current.insert(last.to_string(), value);
+}
+
+/// Remove a dotted key, dropping objects it leaves empty. Returns false if it wasn't set.
+fn remove_path(fields: &mut Map<String, Value>, key: &str) -> bool {
+    match key.split_once('.') {
+        None => fields.remove(key).is_some(),
+        Some((head, rest)) => {
+            let Some(child) = fields.get_mut(head).and_then(|v| v.as_object_mut()) else {
+                return false;
+            };
+            let removed = remove_path(child, rest);
+            if removed && child.is_empty() {
+                fields.remove(head);
+            }
+            removed
+        }
+    }
+}
+
+/// Every leaf setting as (dotted key, value); arrays and empty objects count as leaves
+fn flatten(value: &Value) -> Vec<(String, &Value)> {
+    let mut out = Vec::new();
+    if let Some(fields) = value.as_object() {
+        for (key, child) in fields {
+            match child.as_object() {
+                Some(map) if !map.is_empty() => {
+                    for (sub, leaf) in flatten(child) {
+                        out.push((format!("{}.{}", key, sub), leaf));
+                    }
+                }
+                _ => out.push((key.clone(), child)),
+            }
+        }
+    }
+    out
+}
+
+/// Strings without quotes, everything else as compact JSON
+fn display_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
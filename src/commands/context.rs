@@ -1,134 +1,692 @@
-// Context command - search decisions and notes
+// Context command - search decisions, notes, and the docs database
 
-use anyhow::{Context, Result};
+use std::fmt::Write;
+
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use rusqlite::Connection;
 
+use crate::budget::{self, Budget};
+use crate::config::{ProfileSettings, ProjectConfig, RankingConfig};
 use crate::database::open_database;
+use crate::docs_db;
+use crate::embeddings::{self, Provider};
+use crate::error::ProjError;
 use crate::git;
-use crate::paths::get_tracking_db_path;
+use crate::output_cache;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::tags::{self, Tagged};
+use crate::text::truncate;
+
+/// Filters and output options for `proj context`
+#[derive(Debug)]
+pub struct ContextOptions<'a> {
+    pub ranked: bool,
+    pub recent: bool,
+    /// Only items logged on this git branch
+    pub branch: Option<&'a str>,
+    /// Only items created on or after this date (YYYY-MM-DD)
+    pub since: Option<&'a str>,
+    /// Only items whose author contains this text (commits match on git author)
+    pub author: Option<&'a str>,
+    /// Maximum results (per section for basic search; default 20, or 10 with --recent)
+    pub limit: Option<usize>,
+    /// Show how each ranked result was scored
+    pub verbose: bool,
+    /// Pack the highest-ranked results into about this many tokens, as plain text
+    pub max_tokens: Option<usize>,
+    /// Rank by embedding similarity instead of keywords
+    pub semantic: bool,
+    /// Named profile from config choosing tables, limit, fields, and token budget
+    pub profile: Option<&'a str>,
+}
+
+/// Resolved filters passed to the table searches
+struct Filters<'a> {
+    branch: Option<&'a str>,
+    since: Option<&'a str>,
+    author: Option<&'a str>,
+    limit: usize,
+}
 
-pub fn run(topic: &str, ranked: bool, recent: bool) -> Result<()> {
+impl Filters<'_> {
+    /// Whether anything narrows results beyond the topic itself. Sources that
+    /// carry no branch, date, or author (FTS index, docs sections) are skipped then.
+    fn any(&self) -> bool {
+        self.branch.is_some() || self.since.is_some() || self.author.is_some()
+    }
+}
+
+pub fn run(topic: &str, opts: &ContextOptions) -> Result<()> {
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
         .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
 
-    if recent {
-        show_recent(&conn)
-    } else if ranked {
-        search_ranked(&conn, topic)
+    if let Some(since) = opts.since {
+        if chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_err() {
+            bail!("Invalid --since '{}'. Use YYYY-MM-DD.", since);
+        }
+    }
+
+    let config = ProjectConfig::load().unwrap_or_default();
+    let profile = match opts.profile {
+        Some(name) => Some(config.context_profile(name).ok_or_else(|| {
+            ProjError::InvalidInput(format!(
+                "Unknown context profile '{}'. Profiles: {}",
+                name,
+                config.context_profile_names().join(", ")
+            ))
+        })?),
+        None => None,
+    };
+
+    let filters = Filters {
+        branch: opts.branch,
+        since: opts.since,
+        author: opts.author,
+        limit: opts.limit.unwrap_or(match &profile {
+            Some(p) => p.limit,
+            None if opts.recent => 10,
+            None => 20,
+        }),
+    };
+
+    // --branch checks commits against live git history, which the cache key doesn't cover
+    let cache = match filters.branch {
+        Some(_) => None,
+        None => output_cache::Entry::new(&config, &format!("context {:?} {:?}", topic, opts)),
+    };
+    if let Some(output) = cache.as_ref().and_then(|c| c.hit()) {
+        print!("{}", output);
+        return Ok(());
+    }
+
+    let mut out = String::new();
+    render(&mut out, &conn, topic, opts, &config, profile, &filters)?;
+    print!("{}", out);
+    if let Some(cache) = &cache {
+        cache.store(&out);
+    }
+    Ok(())
+}
+
+/// The search results `run` prints
+fn render(
+    out: &mut String,
+    conn: &Connection,
+    topic: &str,
+    opts: &ContextOptions,
+    config: &ProjectConfig,
+    profile: Option<ProfileSettings>,
+    filters: &Filters,
+) -> Result<()> {
+    // Budgeted output is meant to be pasted as-is, so it skips the headers
+    if profile.is_some() || opts.max_tokens.is_some() {
+        let shape = profile.unwrap_or_default();
+        let max_tokens = opts.max_tokens.unwrap_or(shape.max_tokens);
+        let results = rank_results(conn, topic, filters, &config.ranking, &shape.tables)?;
+        write!(
+            out,
+            "{}",
+            pack_results(topic, &results, max_tokens, &shape.fields)
+        )?;
+        return Ok(());
+    }
+
+    if let Some(b) = filters.branch {
+        writeln!(out, "{}", format!("Branch: {}", b).dimmed())?;
+    }
+    if let Some(since) = filters.since {
+        writeln!(out, "{}", format!("Since: {}", since).dimmed())?;
+    }
+    if let Some(author) = filters.author {
+        writeln!(out, "{}", format!("Author: {}", author).dimmed())?;
+    }
+
+    if opts.recent {
+        show_recent(out, conn, filters)
+    } else if opts.semantic {
+        search_semantic(out, conn, topic, filters)
+    } else if opts.ranked {
+        search_ranked(out, conn, topic, filters, &config.ranking, opts.verbose)
     } else {
-        search_basic(&conn, topic)
+        search_basic(out, conn, topic, filters)
     }
 }
 
+/// Keep only commits reachable from the branch (all commits when no branch is given)
+fn commits_on_branch<T>(
+    commits: Vec<T>,
+    branch: Option<&str>,
+    short_hash: impl Fn(&T) -> &str,
+) -> Vec<T> {
+    let Some(branch) = branch else {
+        return commits;
+    };
+    let on_branch = get_project_root()
+        .ok()
+        .and_then(|root| git::branch_commits(&root, branch))
+        .unwrap_or_default();
+    commits
+        .into_iter()
+        .filter(|c| on_branch.iter().any(|h| h.starts_with(short_hash(c))))
+        .collect()
+}
+
+/// Keep only commits whose git author contains the filter (all commits when no author is given)
+fn commits_by_author<T>(
+    conn: &Connection,
+    commits: Vec<T>,
+    author: Option<&str>,
+    commit_id: impl Fn(&T) -> i64,
+) -> Vec<T> {
+    let Some(author) = author else {
+        return commits;
+    };
+    commits
+        .into_iter()
+        .filter(|c| {
+            conn.query_row(
+                "SELECT 1 FROM git_commits WHERE commit_id = ?1 AND author LIKE '%' || ?2 || '%'",
+                rusqlite::params![commit_id(c), author],
+                |_| Ok(()),
+            )
+            .is_ok()
+        })
+        .collect()
+}
+
 /// Basic search - search decisions, notes, and FTS
-fn search_basic(conn: &Connection, topic: &str) -> Result<()> {
-    println!("{}", format!("Searching for: {}", topic).bold());
-    println!("{}", "=".repeat(60));
+fn search_basic(out: &mut String, conn: &Connection, topic: &str, filters: &Filters) -> Result<()> {
+    writeln!(out, "{}", format!("Searching for: {}", topic).bold())?;
+    writeln!(out, "{}", "=".repeat(60))?;
 
     let mut found = false;
 
     // Search decisions
-    let decisions = search_decisions(conn, topic)?;
+    let decisions = search_decisions(conn, topic, filters)?;
     if !decisions.is_empty() {
-        println!();
-        println!("{}", "Decisions".underline());
+        writeln!(out)?;
+        writeln!(out, "{}", "Decisions".underline())?;
         for (id, topic_found, decision, rationale, created_at) in &decisions {
-            println!("  #{} {} ({})", id, topic_found.bold(), created_at);
-            println!("     Decision: {}", decision);
+            writeln!(
+                out,
+                "  #{} {} ({}){}",
+                id,
+                topic_found.bold(),
+                created_at,
+                tags_suffix(conn, Tagged::Decision(*id))
+            )?;
+            writeln!(out, "     Decision: {}", decision)?;
             if let Some(r) = rationale {
-                println!("     Rationale: {}", r.dimmed());
+                writeln!(out, "     Rationale: {}", r.dimmed())?;
             }
         }
         found = true;
     }
 
     // Search context notes
-    let notes = search_notes(conn, topic)?;
+    let notes = search_notes(conn, topic, filters)?;
     if !notes.is_empty() {
-        println!();
-        println!("{}", "Context Notes".underline());
+        writeln!(out)?;
+        writeln!(out, "{}", "Context Notes".underline())?;
         for (id, category, title, content, created_at) in &notes {
-            println!("  #{} [{}] {} ({})", id, category, title.bold(), created_at);
-            println!("     {}", truncate(content, 80));
+            writeln!(
+                out,
+                "  #{} [{}] {} ({}){}",
+                id,
+                category,
+                title.bold(),
+                created_at,
+                tags_suffix(conn, Tagged::Note(*id))
+            )?;
+            writeln!(out, "     {}", truncate(content, 80))?;
+        }
+        found = true;
+    }
+
+    // Search questions (open and answered)
+    let questions = search_questions(conn, topic, filters)?;
+    if !questions.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "{}", "Questions".underline())?;
+        for (id, question, answer, status, created_at) in &questions {
+            writeln!(
+                out,
+                "  #{} {} [{}] ({})",
+                id,
+                question.bold(),
+                status,
+                created_at
+            )?;
+            if let Some(a) = answer {
+                writeln!(out, "     Answer: {}", a)?;
+            }
         }
         found = true;
     }
 
     // Search git commits
-    let git_results = git::search_git_commits(conn, topic)?;
+    let git_results = commits_by_author(
+        conn,
+        commits_on_branch(
+            git::search_git_commits(conn, topic, filters.since, filters.limit)?,
+            filters.branch,
+            |c| &c.1,
+        ),
+        filters.author,
+        |c| c.0,
+    );
     if !git_results.is_empty() {
-        println!();
-        println!("{}", "Git Commits".underline());
+        writeln!(out)?;
+        writeln!(out, "{}", "Git Commits".underline())?;
         for (_id, short_hash, message, committed_at) in &git_results {
-            println!("  {} {} ({})", short_hash.dimmed(), message, committed_at);
+            writeln!(
+                out,
+                "  {} {} ({})",
+                short_hash.dimmed(),
+                message,
+                committed_at
+            )?;
         }
         found = true;
     }
 
-    // Search FTS index
-    let fts_results = search_fts(conn, topic)?;
+    // Search FTS index (no branch, date, or author, so skipped when filtering)
+    let fts_results = if filters.any() {
+        Vec::new()
+    } else {
+        search_fts(conn, topic, filters.limit)?
+    };
     if !fts_results.is_empty() {
-        println!();
-        println!("{}", "Full-Text Search Results".underline());
+        writeln!(out)?;
+        writeln!(out, "{}", "Full-Text Search Results".underline())?;
         for (table, record_id, content) in &fts_results {
-            println!("  [{}:{}] {}", table, record_id, truncate(content, 70));
+            writeln!(out, "  [{}:{}] {}", table, record_id, truncate(content, 70))?;
+        }
+        found = true;
+    }
+
+    // Search the docs database
+    let sections = search_sections(topic, filters);
+    if !sections.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "{}", "Documentation".underline())?;
+        for (section_id, title, snippet) in &sections {
+            writeln!(
+                out,
+                "  {} {}",
+                format!("§{}", section_id).cyan(),
+                title.bold()
+            )?;
+            writeln!(out, "     {}", truncate(snippet, 80))?;
         }
         found = true;
     }
 
     if !found {
-        println!();
-        println!("No results found for '{}'", topic);
+        writeln!(out)?;
+        writeln!(out, "No results found for '{}'", topic)?;
     }
 
     Ok(())
 }
 
 /// Ranked search - search with relevance scoring
-fn search_ranked(conn: &Connection, topic: &str) -> Result<()> {
-    println!("{}", format!("Ranked search for: {}", topic).bold());
-    println!("{}", "=".repeat(60));
+fn search_ranked(
+    out: &mut String,
+    conn: &Connection,
+    topic: &str,
+    filters: &Filters,
+    ranking: &RankingConfig,
+    verbose: bool,
+) -> Result<()> {
+    writeln!(out, "{}", format!("Ranked search for: {}", topic).bold())?;
+    writeln!(out, "{}", "=".repeat(60))?;
+
+    let tables = ProfileSettings::default().tables;
+    let results = rank_results(conn, topic, filters, ranking, &tables)?;
+    if results.is_empty() {
+        writeln!(out)?;
+        writeln!(out, "No results found for '{}'", topic)?;
+        return Ok(());
+    }
+
+    writeln!(out)?;
+    for (i, result) in results.iter().enumerate() {
+        let rank_indicator = if i < 3 {
+            format!("[{}]", "★".repeat(3 - i)).yellow()
+        } else {
+            format!("[{:.1}]", result.score.total()).dimmed()
+        };
+
+        writeln!(
+            out,
+            "{} {} {} - {}",
+            rank_indicator,
+            result.result_type.cyan(),
+            result.reference,
+            result.title.bold()
+        )?;
+        writeln!(out, "   {}", truncate(&result.content, 70))?;
+        if let Some(extra) = &result.extra {
+            writeln!(out, "   {}", extra.dimmed())?;
+        }
+        if verbose {
+            writeln!(out, "   {}", result.score.describe().dimmed())?;
+        }
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
 
+/// Semantic search - decisions, notes, and doc sections closest in meaning to the topic
+fn search_semantic(
+    out: &mut String,
+    conn: &Connection,
+    topic: &str,
+    filters: &Filters,
+) -> Result<()> {
+    embeddings::require_table(conn)?;
+    let provider = Provider::load()?;
+    let model_id = provider.model_id();
+
+    writeln!(out, "{}", format!("Semantic search for: {}", topic).bold())?;
+    writeln!(out, "{}", "=".repeat(60))?;
+
+    let query = provider
+        .embed(&[topic.to_string()])?
+        .pop()
+        .unwrap_or_default();
+    let nearest = embeddings::nearest(conn, &model_id, &query)?;
+    if nearest.is_empty() {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "No embeddings for {} yet. Run 'proj embed build' first.",
+            model_id
+        )?;
+        return Ok(());
+    }
+
+    // Sections live in the docs database and have no branch, date, or author
+    let docs = if !filters.any() {
+        get_project_root()
+            .ok()
+            .and_then(|root| docs_db::find_docs_db(&root))
+            .and_then(|path| docs_db::open_docs_db(&path).ok())
+    } else {
+        None
+    };
+
+    writeln!(out)?;
+    let mut shown = 0;
+    for (table_name, record_id, similarity) in nearest {
+        if shown >= filters.limit {
+            break;
+        }
+        // Items deleted or filtered out since they were embedded are skipped
+        let item = match table_name.as_str() {
+            "decisions" => conn
+                .query_row(
+                    "SELECT topic, decision FROM decisions
+                     WHERE decision_id = ?1 AND status = 'active'
+                       AND (?2 IS NULL OR branch = ?2) AND (?3 IS NULL OR created_at >= ?3)
+                       AND (?4 IS NULL OR author LIKE '%' || ?4 || '%')",
+                    rusqlite::params![record_id, filters.branch, filters.since, filters.author],
+                    |row| {
+                        Ok((
+                            "decision".to_string(),
+                            row.get::<_, String>(0)?,
+                            row.get::<_, String>(1)?,
+                        ))
+                    },
+                )
+                .ok(),
+            "context_notes" => conn
+                .query_row(
+                    "SELECT category, title, content FROM context_notes
+                     WHERE note_id = ?1 AND status = 'active'
+                       AND (?2 IS NULL OR branch = ?2) AND (?3 IS NULL OR created_at >= ?3)
+                       AND (?4 IS NULL OR author LIKE '%' || ?4 || '%')",
+                    rusqlite::params![record_id, filters.branch, filters.since, filters.author],
+                    |row| {
+                        Ok((
+                            format!("note:{}", row.get::<_, String>(0)?),
+                            row.get::<_, String>(1)?,
+                            row.get::<_, String>(2)?,
+                        ))
+                    },
+                )
+                .ok(),
+            "sections" => docs.as_ref().and_then(|d| {
+                d.query_row(
+                    "SELECT section_id, title, content FROM sections WHERE id = ?1",
+                    [record_id],
+                    |row| {
+                        Ok((
+                            format!("section {}", row.get::<_, String>(0)?),
+                            row.get::<_, String>(1)?,
+                            row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                        ))
+                    },
+                )
+                .ok()
+            }),
+            _ => None,
+        };
+        let Some((kind, title, content)) = item else {
+            continue;
+        };
+
+        writeln!(
+            out,
+            "{} {} #{} - {}",
+            format!("[{:.2}]", similarity).dimmed(),
+            kind.cyan(),
+            record_id,
+            title.bold()
+        )?;
+        writeln!(out, "   {}", truncate(&content.replace('\n', " "), 70))?;
+        writeln!(out)?;
+        shown += 1;
+    }
+
+    if shown == 0 {
+        writeln!(out, "No results found for '{}'", topic)?;
+    }
+    Ok(())
+}
+
+/// Matches across the given tables (decisions, notes, questions, tasks, blockers,
+/// commits, docs), best first
+fn rank_results(
+    conn: &Connection,
+    topic: &str,
+    filters: &Filters,
+    ranking: &RankingConfig,
+    tables: &[String],
+) -> Result<Vec<SearchResult>> {
     let mut results: Vec<SearchResult> = Vec::new();
+    let wants = |table: &str| tables.iter().any(|t| t == table);
 
     // Get all matches with scores
-    let decisions = search_decisions(conn, topic)?;
+    let decisions = if wants("decisions") {
+        search_decisions(conn, topic, filters)?
+    } else {
+        Vec::new()
+    };
     for (id, topic_found, decision, rationale, created_at) in decisions {
-        let score = calculate_score(&topic_found, topic, &created_at);
+        let item_tags = tags::tags_for(conn, Tagged::Decision(id))?;
+        let score = calculate_score(
+            ranking,
+            "decision",
+            &topic_found,
+            &decision,
+            topic,
+            &created_at,
+            &item_tags,
+        );
         results.push(SearchResult {
             result_type: "decision".to_string(),
-            id,
-            title: topic_found.clone(),
+            reference: format!("#{}", id),
+            title: with_tags(topic_found, &item_tags),
             content: decision,
             extra: rationale,
+            date: created_at,
             score,
         });
     }
 
-    let notes = search_notes(conn, topic)?;
+    let notes = if wants("notes") {
+        search_notes(conn, topic, filters)?
+    } else {
+        Vec::new()
+    };
     for (id, category, title, content, created_at) in notes {
-        let score = calculate_score(&title, topic, &created_at);
+        let item_tags = tags::tags_for(conn, Tagged::Note(id))?;
+        let score = calculate_score(
+            ranking,
+            "note",
+            &title,
+            &content,
+            topic,
+            &created_at,
+            &item_tags,
+        );
         results.push(SearchResult {
             result_type: format!("note:{}", category),
-            id,
-            title,
+            reference: format!("#{}", id),
+            title: with_tags(title, &item_tags),
             content: content.clone(),
             extra: None,
+            date: created_at,
+            score,
+        });
+    }
+
+    let questions = if wants("questions") {
+        search_questions(conn, topic, filters)?
+    } else {
+        Vec::new()
+    };
+    for (id, question, answer, status, created_at) in questions {
+        let score = calculate_score(
+            ranking,
+            "question",
+            &question,
+            answer.as_deref().unwrap_or(""),
+            topic,
+            &created_at,
+            &[],
+        );
+        results.push(SearchResult {
+            result_type: format!("question:{}", status),
+            reference: format!("#{}", id),
+            title: question.clone(),
+            content: answer.unwrap_or(question),
+            extra: None,
+            date: created_at,
             score,
         });
     }
 
-    let git_results = git::search_git_commits(conn, topic)?;
+    let tasks = if wants("tasks") {
+        search_tasks(conn, topic, filters)?
+    } else {
+        Vec::new()
+    };
+    for (id, description, status, notes, created_at) in tasks {
+        let item_tags = tags::tags_for(conn, Tagged::Task(id))?;
+        let score = calculate_score(
+            ranking,
+            "task",
+            &description,
+            notes.as_deref().unwrap_or(""),
+            topic,
+            &created_at,
+            &item_tags,
+        );
+        results.push(SearchResult {
+            result_type: format!("task:{}", status),
+            reference: format!("#{}", id),
+            title: with_tags(description.clone(), &item_tags),
+            content: notes.unwrap_or(description),
+            extra: None,
+            date: created_at,
+            score,
+        });
+    }
+
+    let blockers = if wants("blockers") {
+        search_blockers(conn, topic, filters)?
+    } else {
+        Vec::new()
+    };
+    for (id, description, created_at) in blockers {
+        let score = calculate_score(
+            ranking,
+            "blocker",
+            &description,
+            "",
+            topic,
+            &created_at,
+            &[],
+        );
+        results.push(SearchResult {
+            result_type: "blocker".to_string(),
+            reference: format!("#{}", id),
+            title: description.clone(),
+            content: description,
+            extra: None,
+            date: created_at,
+            score,
+        });
+    }
+
+    let git_results = if wants("commits") {
+        commits_by_author(
+            conn,
+            commits_on_branch(
+                git::search_git_commits(conn, topic, filters.since, filters.limit)?,
+                filters.branch,
+                |c| &c.1,
+            ),
+            filters.author,
+            |c| c.0,
+        )
+    } else {
+        Vec::new()
+    };
     for (id, short_hash, message, committed_at) in git_results {
-        let score = calculate_score(&message, topic, &committed_at);
+        let score = calculate_score(ranking, "commit", &message, "", topic, &committed_at, &[]);
         results.push(SearchResult {
             result_type: "commit".to_string(),
-            id,
+            reference: format!("#{}", id),
             title: format!("{}: {}", short_hash, message),
             content: message,
             extra: None,
+            date: committed_at,
+            score,
+        });
+    }
+
+    // Docs sections describe the code as it is now, so they get no recency bonus
+    let sections = if wants("docs") {
+        search_sections(topic, filters)
+    } else {
+        Vec::new()
+    };
+    for (section_id, title, snippet) in sections {
+        let score = calculate_score(ranking, "section", &title, &snippet, topic, "", &[]);
+        results.push(SearchResult {
+            result_type: "doc".to_string(),
+            reference: format!("§{}", section_id),
+            title,
+            content: snippet,
+            extra: None,
+            date: String::new(),
             score,
         });
     }
@@ -136,160 +694,463 @@ fn search_ranked(conn: &Connection, topic: &str) -> Result<()> {
     // Sort by score descending
     results.sort_by(|a, b| {
         b.score
-            .partial_cmp(&a.score)
+            .total()
+            .partial_cmp(&a.score.total())
             .unwrap_or(std::cmp::Ordering::Equal)
     });
+    results.truncate(filters.limit);
 
-    if results.is_empty() {
-        println!();
-        println!("No results found for '{}'", topic);
-        return Ok(());
-    }
+    Ok(results)
+}
 
-    println!();
-    for (i, result) in results.iter().enumerate() {
-        let rank_indicator = if i < 3 {
-            format!("[{}]", "★".repeat(3 - i)).yellow()
-        } else {
-            format!("[{:.1}]", result.score).dimmed()
-        };
+/// Longest rationale kept in budgeted output, in tokens
+const MAX_RATIONALE_TOKENS: usize = 60;
 
-        println!(
-            "{} {} #{} - {}",
-            rank_indicator,
-            result.result_type.cyan(),
-            result.id,
-            result.title.bold()
-        );
-        println!("   {}", truncate(&result.content, 70));
-        if let Some(extra) = &result.extra {
-            println!("   {}", extra.dimmed());
+/// Room kept for the "(N more not shown)" line
+const FOOTER_TOKENS: usize = 10;
+
+/// Plain-text context for an LLM: the highest-ranked results that fit in `max_tokens`,
+/// showing only `fields` (type, id, title, content, rationale, date) of each.
+/// Results that don't fit are skipped (a smaller one further down may still fit);
+/// rationales are cut short first.
+fn pack_results(
+    topic: &str,
+    results: &[SearchResult],
+    max_tokens: usize,
+    fields: &[String],
+) -> String {
+    let has = |field: &str| fields.iter().any(|f| f == field);
+    let mut budget = Budget::new(max_tokens);
+    let mut lines = Vec::new();
+    let header = format!("Context for \"{}\":", topic);
+    budget.take(budget::estimate_tokens(&header) + 1 + FOOTER_TOKENS);
+
+    for result in results {
+        let line = result_line(result, &has);
+        let line = match result.extra.as_ref().filter(|_| has("rationale")) {
+            Some(rationale) => {
+                let base = budget::estimate_tokens(&line) + 3;
+                let room = budget.remaining().saturating_sub(base);
+                if room < 5 {
+                    line
+                } else {
+                    let rationale = budget::ellipsize(rationale, room.min(MAX_RATIONALE_TOKENS));
+                    format!("{} (why: {})", line, rationale)
+                }
+            }
+            None => line,
+        };
+        if budget.take(budget::estimate_tokens(&line) + 1) {
+            lines.push(line);
         }
-        println!();
     }
 
-    Ok(())
+    let mut out = String::new();
+    if lines.is_empty() {
+        out.push_str(&format!(
+            "No results for \"{}\" within {} tokens.\n",
+            topic, max_tokens
+        ));
+        return out;
+    }
+    out.push_str(&header);
+    out.push('\n');
+    for line in &lines {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if lines.len() < results.len() {
+        // Counted in FOOTER_TOKENS
+        out.push_str(&format!(
+            "({} more not shown: token budget)\n",
+            results.len() - lines.len()
+        ));
+    }
+    out
+}
+
+/// One budgeted line: "- [type ref date] title: content", minus the fields left out
+fn result_line(result: &SearchResult, has: &impl Fn(&str) -> bool) -> String {
+    let mut line = "-".to_string();
+    let date = result.date.get(..10).unwrap_or_default();
+    let label: Vec<&str> = [
+        ("type", result.result_type.as_str()),
+        ("id", result.reference.as_str()),
+        ("date", date),
+    ]
+    .into_iter()
+    .filter(|(field, value)| has(field) && !value.is_empty())
+    .map(|(_, value)| value)
+    .collect();
+    if !label.is_empty() {
+        line.push_str(&format!(" [{}]", label.join(" ")));
+    }
+    let title = has("title").then_some(result.title.as_str());
+    let content = has("content")
+        .then_some(result.content.as_str())
+        .filter(|c| title != Some(*c));
+    match (title, content) {
+        (Some(title), Some(content)) => line.push_str(&format!(" {}: {}", title, content)),
+        (Some(text), None) | (None, Some(text)) => line.push_str(&format!(" {}", text)),
+        (None, None) => {}
+    }
+    line
 }
 
 struct SearchResult {
     result_type: String,
-    id: i64,
+    /// "#12" for tracking items, "§2.3" for docs sections
+    reference: String,
     title: String,
     content: String,
     extra: Option<String>,
-    score: f64,
+    /// When it was logged or committed (empty for docs sections)
+    date: String,
+    score: Score,
 }
 
-/// Calculate relevance score
-fn calculate_score(title: &str, query: &str, created_at: &str) -> f64 {
-    let mut score = 0.0;
+/// Parts of a ranked result's relevance score
+struct Score {
+    /// Title equals, starts with, or contains the query
+    title: f64,
+    /// Query words found in title words
+    words: f64,
+    /// Query word occurrences in the content
+    frequency: f64,
+    recency: f64,
+    tags: f64,
+    /// Multiplier for the item type
+    weight: f64,
+}
+
+impl Score {
+    fn total(&self) -> f64 {
+        (self.title + self.words + self.frequency + self.recency + self.tags) * self.weight
+    }
 
+    fn describe(&self) -> String {
+        format!(
+            "score: (title {:.1} + words {:.1} + frequency {:.1} + recency {:.1} + tags {:.1}) × {:.1} = {:.1}",
+            self.title,
+            self.words,
+            self.frequency,
+            self.recency,
+            self.tags,
+            self.weight,
+            self.total()
+        )
+    }
+}
+
+/// Calculate relevance score
+fn calculate_score(
+    ranking: &RankingConfig,
+    kind: &str,
+    title: &str,
+    content: &str,
+    query: &str,
+    created_at: &str,
+    item_tags: &[String],
+) -> Score {
     let title_lower = title.to_lowercase();
     let query_lower = query.to_lowercase();
 
-    // Exact match bonus
-    if title_lower == query_lower {
-        score += 10.0;
-    }
-    // Starts with query
-    else if title_lower.starts_with(&query_lower) {
-        score += 5.0;
-    }
-    // Contains query
-    else if title_lower.contains(&query_lower) {
-        score += 3.0;
-    }
+    // Exact match, starts with query, or contains query
+    let title_score = if title_lower == query_lower {
+        10.0
+    } else if title_lower.starts_with(&query_lower) {
+        5.0
+    } else if title_lower.contains(&query_lower) {
+        3.0
+    } else {
+        0.0
+    };
 
     // Word match bonus
     let query_words: Vec<&str> = query_lower.split_whitespace().collect();
     let title_words: Vec<&str> = title_lower.split_whitespace().collect();
+    let mut words = 0.0;
     for qw in &query_words {
         for tw in &title_words {
             if tw.contains(qw) {
-                score += 1.0;
+                words += 1.0;
             }
         }
     }
 
-    // Recency bonus (newer items score higher)
-    if let Ok(date) = chrono::NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S") {
+    // How often the query comes up in the body
+    let content_lower = content.to_lowercase();
+    let occurrences: usize = query_words
+        .iter()
+        .map(|qw| content_lower.matches(qw).count())
+        .sum();
+    let frequency = occurrences.min(5) as f64 * ranking.frequency_weight;
+
+    // Recency bonus (newer items score higher), halving every half-life
+    let mut recency = 0.0;
+    let date_part = created_at.get(..19).unwrap_or(created_at);
+    if let Ok(date) = chrono::NaiveDateTime::parse_from_str(date_part, "%Y-%m-%d %H:%M:%S") {
         let now = chrono::Utc::now().naive_utc();
-        let days_old = (now - date).num_days() as f64;
-        // Decay: half life of 30 days
-        let recency_bonus = 2.0 * (0.5_f64).powf(days_old / 30.0);
-        score += recency_bonus;
+        let days_old = (now - date).num_days().max(0) as f64;
+        if ranking.recency_half_life_days > 0.0 {
+            recency =
+                ranking.recency_weight * (0.5_f64).powf(days_old / ranking.recency_half_life_days);
+        }
     }
 
-    score
+    Score {
+        title: title_score,
+        words,
+        frequency,
+        recency,
+        tags: tag_score(ranking, item_tags, query),
+        weight: ranking.table_weights.get(kind).copied().unwrap_or(1.0),
+    }
 }
 
+/// Relevance bonus for tags matching the query (whole query or individual words)
+fn tag_score(ranking: &RankingConfig, item_tags: &[String], query: &str) -> f64 {
+    if item_tags.is_empty() {
+        return 0.0;
+    }
+    if tags::normalize(query).is_some_and(|q| item_tags.contains(&q)) {
+        return ranking.tag_boost;
+    }
+    query
+        .split_whitespace()
+        .filter_map(tags::normalize)
+        .filter(|w| item_tags.contains(w))
+        .count() as f64
+        * ranking.tag_word_boost
+}
+
+fn with_tags(title: String, item_tags: &[String]) -> String {
+    if item_tags.is_empty() {
+        title
+    } else {
+        format!("{} {}", title, tags::format_tags(item_tags))
+    }
+}
+
+/// " #a #b" for items that have tags
+fn tags_suffix(conn: &Connection, item: Tagged) -> String {
+    match tags::tags_for(conn, item) {
+        Ok(item_tags) if !item_tags.is_empty() => {
+            format!(" {}", tags::format_tags(&item_tags).cyan())
+        }
+        _ => String::new(),
+    }
+}
+
+/// Decision search row: (id, topic, decision, rationale, created_at)
+type DecisionRow = (i64, String, String, Option<String>, String);
+
+/// Note search row: (id, category, title, content, created_at)
+type NoteRow = (i64, String, String, String, String);
+
+/// Question search row: (id, question, answer, status, created_at)
+type QuestionRow = (i64, String, Option<String>, String, String);
+
+/// Task search row: (id, description, status, notes, created_at)
+type TaskRow = (i64, String, String, Option<String>, String);
+
+/// Blocker search row: (id, description, created_at)
+type BlockerRow = (i64, String, String);
+
 /// Search decisions table
-fn search_decisions(
-    conn: &Connection,
-    topic: &str,
-) -> Result<Vec<(i64, String, String, Option<String>, String)>> {
+fn search_decisions(conn: &Connection, topic: &str, filters: &Filters) -> Result<Vec<DecisionRow>> {
     let pattern = format!("%{}%", topic);
     let mut stmt = conn.prepare(
         "SELECT decision_id, topic, decision, rationale, created_at
          FROM decisions
-         WHERE status = 'active' AND (topic LIKE ?1 OR decision LIKE ?1 OR rationale LIKE ?1)
+         WHERE status = 'active'
+           AND (topic LIKE ?1 OR decision LIKE ?1 OR rationale LIKE ?1
+                OR decision_id IN (SELECT decision_id FROM item_tags WHERE tag LIKE ?1))
+           AND (?2 IS NULL OR branch = ?2)
+           AND (?3 IS NULL OR created_at >= ?3)
+           AND (?5 IS NULL OR author LIKE '%' || ?5 || '%')
          ORDER BY created_at DESC
-         LIMIT 20",
+         LIMIT ?4",
     )?;
 
-    let results = stmt.query_map([&pattern], |row| {
-        Ok((
-            row.get::<_, i64>(0)?,
-            row.get::<_, String>(1)?,
-            row.get::<_, String>(2)?,
-            row.get::<_, Option<String>>(3)?,
-            row.get::<_, String>(4)?,
-        ))
-    })?;
+    let results = stmt.query_map(
+        rusqlite::params![
+            pattern,
+            filters.branch,
+            filters.since,
+            filters.limit as i64,
+            filters.author
+        ],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        },
+    )?;
 
     results.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
 }
 
 /// Search context_notes table
-fn search_notes(
-    conn: &Connection,
-    topic: &str,
-) -> Result<Vec<(i64, String, String, String, String)>> {
+fn search_notes(conn: &Connection, topic: &str, filters: &Filters) -> Result<Vec<NoteRow>> {
     let pattern = format!("%{}%", topic);
     let mut stmt = conn.prepare(
         "SELECT note_id, category, title, content, created_at
          FROM context_notes
-         WHERE status = 'active' AND (title LIKE ?1 OR content LIKE ?1 OR category LIKE ?1)
+         WHERE status = 'active'
+           AND (title LIKE ?1 OR content LIKE ?1 OR category LIKE ?1
+                OR note_id IN (SELECT note_id FROM item_tags WHERE tag LIKE ?1))
+           AND (?2 IS NULL OR branch = ?2)
+           AND (?3 IS NULL OR created_at >= ?3)
+           AND (?5 IS NULL OR author LIKE '%' || ?5 || '%')
          ORDER BY created_at DESC
-         LIMIT 20",
+         LIMIT ?4",
     )?;
 
-    let results = stmt.query_map([&pattern], |row| {
-        Ok((
-            row.get::<_, i64>(0)?,
-            row.get::<_, String>(1)?,
-            row.get::<_, String>(2)?,
-            row.get::<_, String>(3)?,
-            row.get::<_, String>(4)?,
-        ))
-    })?;
+    let results = stmt.query_map(
+        rusqlite::params![
+            pattern,
+            filters.branch,
+            filters.since,
+            filters.limit as i64,
+            filters.author
+        ],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        },
+    )?;
+
+    results.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+}
+
+/// Search questions table (questions aren't attributed, so none match an author filter)
+fn search_questions(conn: &Connection, topic: &str, filters: &Filters) -> Result<Vec<QuestionRow>> {
+    if filters.author.is_some() {
+        return Ok(Vec::new());
+    }
+    let pattern = format!("%{}%", topic);
+    let mut stmt = conn.prepare(
+        "SELECT question_id, question, answer, status, created_at
+         FROM questions
+         WHERE (question LIKE ?1 OR context LIKE ?1 OR answer LIKE ?1)
+           AND (?2 IS NULL OR branch = ?2)
+           AND (?3 IS NULL OR created_at >= ?3)
+         ORDER BY created_at DESC
+         LIMIT ?4",
+    )?;
+
+    let results = stmt.query_map(
+        rusqlite::params![pattern, filters.branch, filters.since, filters.limit as i64],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        },
+    )?;
+
+    results.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+}
+
+/// Search open tasks (completed and cancelled ones are left out)
+fn search_tasks(conn: &Connection, topic: &str, filters: &Filters) -> Result<Vec<TaskRow>> {
+    let pattern = format!("%{}%", topic);
+    let mut stmt = conn.prepare(
+        "SELECT task_id, description, status, notes, created_at
+         FROM tasks
+         WHERE status NOT IN ('completed', 'cancelled')
+           AND (description LIKE ?1 OR notes LIKE ?1
+                OR task_id IN (SELECT task_id FROM item_tags WHERE tag LIKE ?1))
+           AND (?2 IS NULL OR branch = ?2)
+           AND (?3 IS NULL OR created_at >= ?3)
+           AND (?5 IS NULL OR author LIKE '%' || ?5 || '%')
+         ORDER BY created_at DESC
+         LIMIT ?4",
+    )?;
+
+    let results = stmt.query_map(
+        rusqlite::params![
+            pattern,
+            filters.branch,
+            filters.since,
+            filters.limit as i64,
+            filters.author
+        ],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, String>(4)?,
+            ))
+        },
+    )?;
+
+    results.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
+}
+
+/// Search active blockers
+fn search_blockers(conn: &Connection, topic: &str, filters: &Filters) -> Result<Vec<BlockerRow>> {
+    let pattern = format!("%{}%", topic);
+    let mut stmt = conn.prepare(
+        "SELECT blocker_id, description, created_at
+         FROM blockers
+         WHERE status = 'active'
+           AND description LIKE ?1
+           AND (?2 IS NULL OR branch = ?2)
+           AND (?3 IS NULL OR created_at >= ?3)
+           AND (?5 IS NULL OR author LIKE '%' || ?5 || '%')
+         ORDER BY created_at DESC
+         LIMIT ?4",
+    )?;
+
+    let results = stmt.query_map(
+        rusqlite::params![
+            pattern,
+            filters.branch,
+            filters.since,
+            filters.limit as i64,
+            filters.author
+        ],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        },
+    )?;
 
     results.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
 }
 
 /// Search FTS index
-fn search_fts(conn: &Connection, topic: &str) -> Result<Vec<(String, i64, String)>> {
+fn search_fts(conn: &Connection, topic: &str, limit: usize) -> Result<Vec<(String, i64, String)>> {
     // Try FTS match first, fall back gracefully if FTS fails or returns invalid data
     let stmt = conn.prepare(
         "SELECT table_name, record_id, content
          FROM tracking_fts
          WHERE tracking_fts MATCH ?1
-         LIMIT 20",
+         LIMIT ?2",
     );
 
     match stmt {
         Ok(mut s) => {
-            let results = s.query_map([topic], |row| {
+            let results = s.query_map(rusqlite::params![topic, limit as i64], |row| {
                 // Handle potential NULL values gracefully
                 let table_name: Option<String> = row.get(0).ok();
                 let record_id: Option<i64> = row.get(1).ok();
@@ -313,26 +1174,93 @@ fn search_fts(conn: &Connection, topic: &str) -> Result<Vec<(String, i64, String
     }
 }
 
+/// Docs section search row: (section_id, title, snippet)
+type SectionRow = (String, String, String);
+
+/// Search the docs database's full-text index, best matches first.
+/// Sections have no branch, date, or author, so they're left out when filtering by any,
+/// and a project without a docs database simply has no section results.
+fn search_sections(topic: &str, filters: &Filters) -> Vec<SectionRow> {
+    if filters.any() {
+        return Vec::new();
+    }
+    let Some(conn) = get_project_root()
+        .ok()
+        .and_then(|root| docs_db::find_docs_db(&root))
+        .and_then(|path| docs_db::open_docs_db(&path).ok())
+    else {
+        return Vec::new();
+    };
+
+    // Quote each word so punctuation in the topic isn't read as FTS syntax
+    let query = topic
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ");
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let rows = conn
+        .prepare(
+            "SELECT s.section_id, s.title, snippet(sections_fts, 1, '', '', '...', 24)
+             FROM sections s
+             JOIN sections_fts ON s.id = sections_fts.rowid
+             WHERE sections_fts MATCH ?1
+             ORDER BY rank
+             LIMIT ?2",
+        )
+        .and_then(|mut stmt| {
+            stmt.query_map(rusqlite::params![query, filters.limit as i64], |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                ))
+            })?
+            .collect::<Result<Vec<_>, _>>()
+        });
+    rows.map(|rows| {
+        rows.into_iter()
+            .map(|(id, title, snippet)| (id, title, snippet.replace('\n', " ")))
+            .collect()
+    })
+    .unwrap_or_default()
+}
+
 /// Show the last N items chronologically across all tables
-fn show_recent(conn: &Connection) -> Result<()> {
-    println!("{}", "Recent Activity (last 10 items):".bold());
-    println!("{}", "=".repeat(60));
+fn show_recent(out: &mut String, conn: &Connection, filters: &Filters) -> Result<()> {
+    writeln!(
+        out,
+        "{}",
+        format!("Recent Activity (last {} items):", filters.limit).bold()
+    )?;
+    writeln!(out, "{}", "=".repeat(60))?;
 
     // Collect items from multiple tables with a unified date
     let mut items: Vec<(String, String, String)> = Vec::new(); // (datetime, type, content)
 
     // Recent decisions
     let mut stmt = conn.prepare(
-        "SELECT created_at, topic, decision FROM decisions WHERE status = 'active' ORDER BY created_at DESC LIMIT 10",
+        "SELECT created_at, topic, decision FROM decisions WHERE status = 'active' AND (?1 IS NULL OR branch = ?1) AND (?2 IS NULL OR created_at >= ?2) AND (?4 IS NULL OR author LIKE '%' || ?4 || '%') ORDER BY created_at DESC LIMIT ?3",
     )?;
     let decisions: Vec<_> = stmt
-        .query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-            ))
-        })?
+        .query_map(
+            rusqlite::params![
+                filters.branch,
+                filters.since,
+                filters.limit as i64,
+                filters.author
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )?
         .filter_map(|r| r.ok())
         .collect();
     for (dt, topic, decision) in decisions {
@@ -345,16 +1273,24 @@ fn show_recent(conn: &Connection) -> Result<()> {
 
     // Recent tasks
     let mut stmt = conn.prepare(
-        "SELECT created_at, description, status FROM tasks ORDER BY created_at DESC LIMIT 10",
+        "SELECT created_at, description, status FROM tasks WHERE (?1 IS NULL OR branch = ?1) AND (?2 IS NULL OR created_at >= ?2) AND (?4 IS NULL OR author LIKE '%' || ?4 || '%') ORDER BY created_at DESC LIMIT ?3",
     )?;
     let tasks: Vec<_> = stmt
-        .query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-            ))
-        })?
+        .query_map(
+            rusqlite::params![
+                filters.branch,
+                filters.since,
+                filters.limit as i64,
+                filters.author
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )?
         .filter_map(|r| r.ok())
         .collect();
     for (dt, desc, status) in tasks {
@@ -363,16 +1299,24 @@ fn show_recent(conn: &Connection) -> Result<()> {
 
     // Recent notes
     let mut stmt = conn.prepare(
-        "SELECT created_at, category, title FROM context_notes WHERE status = 'active' ORDER BY created_at DESC LIMIT 10",
+        "SELECT created_at, category, title FROM context_notes WHERE status = 'active' AND (?1 IS NULL OR branch = ?1) AND (?2 IS NULL OR created_at >= ?2) AND (?4 IS NULL OR author LIKE '%' || ?4 || '%') ORDER BY created_at DESC LIMIT ?3",
     )?;
     let notes: Vec<_> = stmt
-        .query_map([], |row| {
-            Ok((
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?,
-                row.get::<_, String>(2)?,
-            ))
-        })?
+        .query_map(
+            rusqlite::params![
+                filters.branch,
+                filters.since,
+                filters.limit as i64,
+                filters.author
+            ],
+            |row| {
+                Ok((
+                    row.get::<_, String>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                ))
+            },
+        )?
         .filter_map(|r| r.ok())
         .collect();
     for (dt, category, title) in notes {
@@ -380,7 +1324,19 @@ fn show_recent(conn: &Connection) -> Result<()> {
     }
 
     // Recent git commits
-    let commits = git::get_recent_commits(conn, 10)?;
+    let commits = commits_on_branch(
+        git::get_recent_commits(conn, filters.limit)?,
+        filters.branch,
+        |c| c.short_hash.as_str(),
+    );
+    let commits = commits.into_iter().filter(|c| {
+        filters
+            .since
+            .map_or(true, |since| c.committed_at.as_str() >= since)
+            && filters.author.map_or(true, |author| {
+                c.author.to_lowercase().contains(&author.to_lowercase())
+            })
+    });
     for c in commits {
         items.push((
             c.committed_at,
@@ -389,17 +1345,17 @@ fn show_recent(conn: &Connection) -> Result<()> {
         ));
     }
 
-    // Sort by date descending, take top 10
+    // Sort by date descending, take the newest
     items.sort_by(|a, b| b.0.cmp(&a.0));
-    items.truncate(10);
+    items.truncate(filters.limit);
 
     if items.is_empty() {
-        println!();
-        println!("No recent activity found.");
+        writeln!(out)?;
+        writeln!(out, "No recent activity found.")?;
         return Ok(());
     }
 
-    println!();
+    writeln!(out)?;
     for (dt, item_type, content) in &items {
         let type_display = match item_type.as_str() {
             "decision" => "decision".cyan(),
@@ -410,17 +1366,14 @@ fn show_recent(conn: &Connection) -> Result<()> {
         };
         // Show just date portion for compact display
         let date_short = if dt.len() >= 10 { &dt[..10] } else { dt };
-        println!("  {} {} {}", date_short.dimmed(), type_display, content);
+        writeln!(
+            out,
+            "  {} {} {}",
+            date_short.dimmed(),
+            type_display,
+            content
+        )?;
     }
 
     Ok(())
 }
-
-/// Truncate a string to a maximum length
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
-    }
-}
@@ -5,10 +5,12 @@ use std::path::Path;
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use rusqlite::Connection;
 
 use crate::config::Registry;
-use crate::database::open_database;
+use crate::database::{get_schema_version, open_database};
 use crate::paths::get_registry_path;
+use crate::text::truncate;
 
 /// Project data for dashboard display
 struct ProjectData {
@@ -40,7 +42,32 @@ impl ProjectStatus {
     }
 }
 
-pub fn run() -> Result<()> {
+/// Days without a session before a project with open work counts as stalled
+const STALLED_DAYS: i64 = 7;
+
+/// Aggregated open work for one project (dashboard --all)
+struct ProjectSummary {
+    name: String,
+    path: String,
+    schema_version: Option<String>,
+    active_tasks: Vec<(i64, String, String)>,
+    blockers: Vec<(i64, String)>,
+    open_questions: i64,
+    recent_sessions: Vec<(i64, String, Option<String>)>,
+    days_idle: Option<i64>,
+}
+
+impl ProjectSummary {
+    fn has_open_work(&self) -> bool {
+        !self.active_tasks.is_empty() || !self.blockers.is_empty()
+    }
+
+    fn is_stalled(&self) -> bool {
+        self.has_open_work() && self.days_idle.map_or(true, |d| d >= STALLED_DAYS)
+    }
+}
+
+pub fn run(all: bool) -> Result<()> {
     let registry = load_registry()?;
 
     if registry.registered_projects.is_empty() {
@@ -48,6 +75,10 @@ pub fn run() -> Result<()> {
         return Ok(());
     }
 
+    if all {
+        return run_all(&registry);
+    }
+
     println!("\n{}", "=".repeat(70));
     println!("{}", "PROJECT DASHBOARD".bold());
     println!("{}\n", "=".repeat(70));
@@ -158,6 +189,174 @@ pub fn run() -> Result<()> {
     Ok(())
 }
 
+/// Aggregate view across all registered projects
+fn run_all(registry: &Registry) -> Result<()> {
+    println!("\n{}", "=".repeat(70));
+    println!("{}", "ALL PROJECTS".bold());
+    println!("{}\n", "=".repeat(70));
+
+    let mut summaries = Vec::new();
+    let mut missing = Vec::new();
+
+    for p in &registry.registered_projects {
+        let db_path = Path::new(&p.path).join(".tracking").join("tracking.db");
+        if !db_path.exists() {
+            missing.push(p.name.clone());
+            continue;
+        }
+        match open_database(&db_path).and_then(|conn| summarize_project(&conn, &p.name, &p.path)) {
+            Ok(summary) => summaries.push(summary),
+            Err(_) => missing.push(p.name.clone()),
+        }
+    }
+
+    // Stalled projects first, then by most open work
+    summaries.sort_by_key(|s| {
+        (
+            !s.is_stalled(),
+            std::cmp::Reverse(s.active_tasks.len() + s.blockers.len()),
+        )
+    });
+
+    let total_tasks: usize = summaries.iter().map(|s| s.active_tasks.len()).sum();
+    let total_blockers: usize = summaries.iter().map(|s| s.blockers.len()).sum();
+    let stalled = summaries.iter().filter(|s| s.is_stalled()).count();
+
+    for s in &summaries {
+        let icon = if s.is_stalled() {
+            "⚠".yellow()
+        } else if s.has_open_work() {
+            "●".green()
+        } else {
+            "○".white()
+        };
+        let idle = match s.days_idle {
+            Some(0) => "today".to_string(),
+            Some(d) => format!("{}d ago", d),
+            None => "never".to_string(),
+        };
+
+        print!("{} {}", icon, s.name.bold());
+        if s.is_stalled() {
+            print!(" {}", "STALLED".yellow().bold());
+        }
+        println!(" {}", s.path.dimmed());
+        println!(
+            "    Tasks: {}  Blockers: {}  Questions: {}  Last session: {}",
+            s.active_tasks.len(),
+            s.blockers.len(),
+            s.open_questions,
+            idle
+        );
+
+        for (id, description) in &s.blockers {
+            println!("    {} #{} {}", "✗".red(), id, truncate(description, 55));
+        }
+        for (id, description, status) in s.active_tasks.iter().take(3) {
+            let icon = match status.as_str() {
+                "in_progress" => "◐".yellow(),
+                "blocked" => "✗".red(),
+                _ => "○".white(),
+            };
+            println!("    {} #{} {}", icon, id, truncate(description, 55));
+        }
+        if s.active_tasks.len() > 3 {
+            println!("      ... and {} more", s.active_tasks.len() - 3);
+        }
+        for (id, started_at, summary) in &s.recent_sessions {
+            println!(
+                "    {} #{} {}",
+                format_date(started_at).dimmed(),
+                id,
+                truncate(summary.as_deref().unwrap_or("(active)"), 50).dimmed()
+            );
+        }
+        if let Some(ref v) = s.schema_version {
+            if v != crate::SCHEMA_VERSION {
+                println!(
+                    "    {} Schema v{} (run 'proj upgrade --all')",
+                    "!".yellow(),
+                    v
+                );
+            }
+        }
+        println!();
+    }
+
+    for name in &missing {
+        println!("{} {} {}", "✗".red(), name.bold(), "not found".dimmed());
+    }
+
+    println!("{}", "-".repeat(70));
+    println!(
+        "{} projects · {} active tasks · {} blockers · {} stalled (no session in {}+ days)",
+        summaries.len(),
+        total_tasks,
+        total_blockers,
+        stalled,
+        STALLED_DAYS
+    );
+
+    Ok(())
+}
+
+/// Gather open work and recent sessions for one project
+fn summarize_project(conn: &Connection, name: &str, path: &str) -> Result<ProjectSummary> {
+    let active_tasks = {
+        let mut stmt = conn.prepare(
+            "SELECT task_id, description, status FROM tasks
+             WHERE status NOT IN ('completed', 'cancelled')
+             ORDER BY CASE status WHEN 'blocked' THEN 0 WHEN 'in_progress' THEN 1 ELSE 2 END,
+                      CASE priority WHEN 'urgent' THEN 0 WHEN 'high' THEN 1 WHEN 'normal' THEN 2 ELSE 3 END,
+                      created_at",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let blockers = {
+        let mut stmt = conn.prepare(
+            "SELECT blocker_id, description FROM blockers WHERE status = 'active' ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let open_questions: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM questions WHERE status = 'open'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let recent_sessions = {
+        let mut stmt = conn.prepare(
+            "SELECT session_id, started_at, summary FROM sessions ORDER BY started_at DESC LIMIT 2",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let days_idle: Option<i64> = conn
+        .query_row(
+            "SELECT CAST(julianday('now') - julianday(MAX(COALESCE(ended_at, started_at))) AS INTEGER) FROM sessions",
+            [],
+            |row| row.get(0),
+        )
+        .ok()
+        .flatten();
+
+    Ok(ProjectSummary {
+        name: name.to_string(),
+        path: path.to_string(),
+        schema_version: get_schema_version(conn).ok().flatten(),
+        active_tasks,
+        blockers,
+        open_questions,
+        recent_sessions,
+        days_idle,
+    })
+}
+
 /// Load the global registry
 fn load_registry() -> Result<Registry> {
     let registry_path = get_registry_path()?;
@@ -0,0 +1,696 @@
+// Dashboard TUI - interactive view of the current project's tasks, blockers, decisions, and sessions
+
+use std::io::{self, Stdout, Write};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::execute;
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
+use ratatui::{Frame, Terminal};
+use rusqlite::Connection;
+
+use crate::cli::{
+    BlockerCommands, BlockerSubcommand, SessionCommands, SessionSubcommand, TaskCommands,
+    TaskSubcommand,
+};
+use crate::config::ProjectConfig;
+use crate::database::open_database;
+use crate::models::Session;
+use crate::paths::{get_config_path, get_tracking_db_path};
+use crate::session::get_active_session;
+
+type Tui = Terminal<CrosstermBackend<Stdout>>;
+
+/// Rows shown in the decisions and sessions panes
+const HISTORY_LIMIT: i64 = 30;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Pane {
+    Tasks,
+    Blockers,
+    Decisions,
+    Sessions,
+}
+
+impl Pane {
+    const ALL: [Pane; 4] = [Pane::Tasks, Pane::Blockers, Pane::Decisions, Pane::Sessions];
+
+    fn index(self) -> usize {
+        Pane::ALL.iter().position(|p| *p == self).unwrap_or(0)
+    }
+
+    fn next(self) -> Pane {
+        Pane::ALL[(self.index() + 1) % Pane::ALL.len()]
+    }
+
+    fn prev(self) -> Pane {
+        Pane::ALL[(self.index() + Pane::ALL.len() - 1) % Pane::ALL.len()]
+    }
+}
+
+struct TaskRow {
+    id: i64,
+    description: String,
+    status: String,
+    priority: String,
+    due: Option<String>,
+}
+
+struct BlockerRow {
+    id: i64,
+    description: String,
+    related_task_id: Option<i64>,
+}
+
+struct DecisionRow {
+    id: i64,
+    created_at: String,
+    topic: String,
+    decision: String,
+}
+
+struct SessionRow {
+    id: i64,
+    started_at: String,
+    ended_at: Option<String>,
+    status: String,
+    name: Option<String>,
+    summary: Option<String>,
+}
+
+/// Everything the dashboard displays, reloaded after each action
+struct Data {
+    active_session: Option<Session>,
+    tasks: Vec<TaskRow>,
+    blockers: Vec<BlockerRow>,
+    decisions: Vec<DecisionRow>,
+    sessions: Vec<SessionRow>,
+}
+
+/// Text being typed into the input line, and what it's for
+enum Prompt {
+    Resolution(i64),
+    Summary,
+}
+
+/// A change the dashboard hands off to the regular commands
+enum Action {
+    CompleteTask(i64),
+    ResolveBlocker {
+        id: i64,
+        resolution: String,
+        unblock: bool,
+    },
+    StartSession,
+    EndSession(String),
+}
+
+struct App {
+    project: String,
+    data: Data,
+    pane: Pane,
+    selected: [usize; 4],
+    prompt: Option<(Prompt, String)>,
+    message: Option<String>,
+    quit: bool,
+}
+
+pub fn run() -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let mut app = App {
+        project: load_config()?.name,
+        data: load_data(&conn)?,
+        pane: Pane::Tasks,
+        selected: [0; 4],
+        prompt: None,
+        message: None,
+        quit: false,
+    };
+    drop(conn);
+
+    let mut terminal = enter_tui()?;
+    let result = event_loop(&mut terminal, &mut app);
+    leave_tui(&mut terminal)?;
+    result
+}
+
+fn event_loop(terminal: &mut Tui, app: &mut App) -> Result<()> {
+    while !app.quit {
+        terminal.draw(|f| draw(f, app))?;
+
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        let Some(action) = handle_key(app, key) else {
+            continue;
+        };
+
+        // Hand the terminal back so the command's output, prompts, and hooks behave as usual
+        leave_tui(terminal)?;
+        if let Err(e) = perform(action) {
+            eprintln!("{} {:#}", "Error:".red(), e);
+        }
+        print!("\n{}", "Press Enter to return to the dashboard...".dimmed());
+        io::stdout().flush()?;
+        io::stdin().read_line(&mut String::new())?;
+        *terminal = enter_tui()?;
+
+        reload(app)?;
+    }
+    Ok(())
+}
+
+fn enter_tui() -> Result<Tui> {
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    Ok(Terminal::new(CrosstermBackend::new(stdout))?)
+}
+
+fn leave_tui(terminal: &mut Tui) -> Result<()> {
+    disable_raw_mode()?;
+    execute!(terminal.backend_mut(), LeaveAlternateScreen)?;
+    terminal.show_cursor()?;
+    Ok(())
+}
+
+fn reload(app: &mut App) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)?;
+    app.data = load_data(&conn)?;
+
+    let lens = [
+        app.data.tasks.len(),
+        app.data.blockers.len(),
+        app.data.decisions.len(),
+        app.data.sessions.len(),
+    ];
+    for (selected, len) in app.selected.iter_mut().zip(lens) {
+        *selected = (*selected).min(len.saturating_sub(1));
+    }
+    Ok(())
+}
+
+/// Update state for a key press, returning an action when one should run
+fn handle_key(app: &mut App, key: KeyEvent) -> Option<Action> {
+    app.message = None;
+
+    if let Some((prompt, mut text)) = app.prompt.take() {
+        match key.code {
+            KeyCode::Esc => {}
+            KeyCode::Enter => {
+                let text = text.trim().to_string();
+                if text.is_empty() {
+                    app.message = Some("Nothing entered - cancelled".to_string());
+                    return None;
+                }
+                return Some(match prompt {
+                    Prompt::Resolution(id) => {
+                        let unblock = app
+                            .data
+                            .blockers
+                            .iter()
+                            .any(|b| b.id == id && b.related_task_id.is_some());
+                        Action::ResolveBlocker {
+                            id,
+                            resolution: text,
+                            unblock,
+                        }
+                    }
+                    Prompt::Summary => Action::EndSession(text),
+                });
+            }
+            KeyCode::Backspace => {
+                text.pop();
+                app.prompt = Some((prompt, text));
+            }
+            KeyCode::Char(c) => {
+                text.push(c);
+                app.prompt = Some((prompt, text));
+            }
+            _ => app.prompt = Some((prompt, text)),
+        }
+        return None;
+    }
+
+    let index = app.pane.index();
+    let len = pane_len(app, app.pane);
+
+    match key.code {
+        KeyCode::Char('q') | KeyCode::Esc => app.quit = true,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => app.quit = true,
+        KeyCode::Tab | KeyCode::Right => app.pane = app.pane.next(),
+        KeyCode::BackTab | KeyCode::Left => app.pane = app.pane.prev(),
+        KeyCode::Char(c @ '1'..='4') => app.pane = Pane::ALL[c as usize - '1' as usize],
+        KeyCode::Down | KeyCode::Char('j') if app.selected[index] + 1 < len => {
+            app.selected[index] += 1;
+        }
+        KeyCode::Up | KeyCode::Char('k') => {
+            app.selected[index] = app.selected[index].saturating_sub(1);
+        }
+        KeyCode::Char('c') => match (app.pane, app.data.tasks.get(app.selected[index])) {
+            (Pane::Tasks, Some(task)) => return Some(Action::CompleteTask(task.id)),
+            (Pane::Tasks, None) => app.message = Some("No open tasks".to_string()),
+            _ => app.message = Some("Select a task in the Tasks pane to complete it".to_string()),
+        },
+        KeyCode::Char('r') => match (app.pane, app.data.blockers.get(app.selected[index])) {
+            (Pane::Blockers, Some(blocker)) => {
+                app.prompt = Some((Prompt::Resolution(blocker.id), String::new()));
+            }
+            (Pane::Blockers, None) => app.message = Some("No active blockers".to_string()),
+            _ => {
+                app.message =
+                    Some("Select a blocker in the Blockers pane to resolve it".to_string())
+            }
+        },
+        KeyCode::Char('s') => {
+            if app.data.active_session.is_some() {
+                app.message = Some("A session is already active".to_string());
+            } else {
+                return Some(Action::StartSession);
+            }
+        }
+        KeyCode::Char('e') => {
+            if app.data.active_session.is_some() {
+                app.prompt = Some((Prompt::Summary, String::new()));
+            } else {
+                app.message = Some("No active session to end".to_string());
+            }
+        }
+        _ => {}
+    }
+    None
+}
+
+/// Run an action through the same code paths as the CLI
+fn perform(action: Action) -> Result<()> {
+    match action {
+        Action::CompleteTask(id) => crate::commands::task::run(TaskCommands {
+            command: TaskSubcommand::Update {
+                id,
+                status: Some("completed".to_string()),
+                notes: None,
+                priority: None,
+                blocked_by: None,
+                verify_commits: false,
+            },
+        }),
+        Action::ResolveBlocker {
+            id,
+            resolution,
+            unblock,
+        } => crate::commands::blocker::run(BlockerCommands {
+            command: BlockerSubcommand::Resolve {
+                id,
+                resolution,
+                unblock,
+            },
+        }),
+        Action::StartSession => crate::commands::session::run(SessionCommands {
+            command: SessionSubcommand::Start {
+                name: None,
+                author: None,
+            },
+        }),
+        Action::EndSession(summary) => crate::commands::session::run(SessionCommands {
+            command: SessionSubcommand::End {
+                summary: Some(summary),
+                force: false,
+                no_auto_commit: false,
+                suggest: false,
+                accept: false,
+                llm: false,
+            },
+        }),
+    }
+}
+
+fn pane_len(app: &App, pane: Pane) -> usize {
+    match pane {
+        Pane::Tasks => app.data.tasks.len(),
+        Pane::Blockers => app.data.blockers.len(),
+        Pane::Decisions => app.data.decisions.len(),
+        Pane::Sessions => app.data.sessions.len(),
+    }
+}
+
+fn draw(f: &mut Frame, app: &App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(1),
+            Constraint::Min(4),
+            Constraint::Length(1),
+        ])
+        .split(f.size());
+
+    draw_header(f, app, rows[0]);
+
+    let halves = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(rows[1]);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(halves[0]);
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(halves[1]);
+
+    let tasks: Vec<ListItem> = app
+        .data
+        .tasks
+        .iter()
+        .map(|t| {
+            let mut spans = vec![
+                Span::styled(format!("#{:<4}", t.id), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("{:<7}", t.priority),
+                    Style::default().fg(priority_color(&t.priority)),
+                ),
+            ];
+            if t.status != "pending" {
+                spans.push(Span::styled(
+                    format!("[{}] ", t.status),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            spans.push(Span::raw(t.description.clone()));
+            if let Some(due) = &t.due {
+                spans.push(Span::styled(
+                    format!("  due {}", due),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    draw_list(f, app, Pane::Tasks, top[0], "Tasks", tasks);
+
+    let blockers: Vec<ListItem> = app
+        .data
+        .blockers
+        .iter()
+        .map(|b| {
+            let mut spans = vec![
+                Span::styled(format!("#{:<4}", b.id), Style::default().fg(Color::Red)),
+                Span::raw(b.description.clone()),
+            ];
+            if let Some(task_id) = b.related_task_id {
+                spans.push(Span::styled(
+                    format!("  (task #{})", task_id),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    draw_list(f, app, Pane::Blockers, top[1], "Blockers", blockers);
+
+    let decisions: Vec<ListItem> = app
+        .data
+        .decisions
+        .iter()
+        .map(|d| {
+            ListItem::new(Line::from(vec![
+                Span::styled(format!("#{:<4}", d.id), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("{} ", short_date(&d.created_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(
+                    format!("{}: ", d.topic),
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(d.decision.clone()),
+            ]))
+        })
+        .collect();
+    draw_list(
+        f,
+        app,
+        Pane::Decisions,
+        bottom[0],
+        "Recent decisions",
+        decisions,
+    );
+
+    let sessions: Vec<ListItem> = app
+        .data
+        .sessions
+        .iter()
+        .map(|s| {
+            let (marker, color) = match s.status.as_str() {
+                "active" => ("●", Color::Green),
+                "paused" => ("◐", Color::Yellow),
+                "abandoned" => ("✗", Color::DarkGray),
+                _ => ("○", Color::Reset),
+            };
+            let mut spans = vec![
+                Span::styled(format!("{} ", marker), Style::default().fg(color)),
+                Span::styled(format!("#{:<4}", s.id), Style::default().fg(Color::Cyan)),
+                Span::styled(
+                    format!("{} ", short_date(&s.started_at)),
+                    Style::default().fg(Color::DarkGray),
+                ),
+            ];
+            if let Some(minutes) = duration_minutes(s) {
+                spans.push(Span::styled(
+                    format!("{:>4} ", format_duration(minutes)),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            if let Some(name) = &s.name {
+                spans.push(Span::styled(
+                    format!("[{}] ", name),
+                    Style::default().fg(Color::Magenta),
+                ));
+            }
+            spans.push(Span::raw(s.summary.clone().unwrap_or_default()));
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    draw_list(f, app, Pane::Sessions, bottom[1], "Sessions", sessions);
+
+    draw_footer(f, app, rows[2]);
+}
+
+fn draw_header(f: &mut Frame, app: &App, area: Rect) {
+    let mut spans = vec![Span::styled(
+        format!(" {} ", app.project),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    match &app.data.active_session {
+        Some(s) => {
+            let label = match &s.name {
+                Some(name) => format!("#{} ({})", s.session_id, name),
+                None => format!("#{}", s.session_id),
+            };
+            spans.push(Span::styled(
+                format!(
+                    "● session {} since {}",
+                    label,
+                    s.started_at.format("%m-%d %H:%M")
+                ),
+                Style::default().fg(Color::Green),
+            ));
+        }
+        None => spans.push(Span::styled(
+            "○ no active session",
+            Style::default().fg(Color::DarkGray),
+        )),
+    }
+    f.render_widget(Paragraph::new(Line::from(spans)), area);
+}
+
+fn draw_footer(f: &mut Frame, app: &App, area: Rect) {
+    let line = if let Some((prompt, text)) = &app.prompt {
+        let label = match prompt {
+            Prompt::Resolution(id) => format!("Resolve blocker #{}: ", id),
+            Prompt::Summary => "Session summary: ".to_string(),
+        };
+        f.set_cursor(
+            area.x + (label.chars().count() + text.chars().count()) as u16,
+            area.y,
+        );
+        Line::from(vec![
+            Span::styled(label, Style::default().fg(Color::Yellow)),
+            Span::raw(text.clone()),
+        ])
+    } else if let Some(message) = &app.message {
+        Line::from(Span::styled(
+            message.clone(),
+            Style::default().fg(Color::Yellow),
+        ))
+    } else {
+        Line::from(Span::styled(
+            "Tab/1-4 pane  j/k move  c complete task  r resolve blocker  s start session  e end session  q quit",
+            Style::default().fg(Color::DarkGray),
+        ))
+    };
+    f.render_widget(Paragraph::new(line), area);
+}
+
+fn draw_list(f: &mut Frame, app: &App, pane: Pane, area: Rect, title: &str, items: Vec<ListItem>) {
+    let focused = app.pane == pane;
+    let border = if focused {
+        Style::default().fg(Color::Cyan)
+    } else {
+        Style::default().fg(Color::DarkGray)
+    };
+    let title = format!(" {} {} ({}) ", pane.index() + 1, title, items.len());
+
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(border)
+                .title(title),
+        )
+        .highlight_style(if focused {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        });
+
+    let mut state = ListState::default();
+    if pane_len(app, pane) > 0 {
+        state.select(Some(app.selected[pane.index()]));
+    }
+    f.render_stateful_widget(list, area, &mut state);
+}
+
+fn priority_color(priority: &str) -> Color {
+    match priority {
+        "urgent" => Color::Red,
+        "high" => Color::Yellow,
+        "low" => Color::DarkGray,
+        _ => Color::Reset,
+    }
+}
+
+/// "2024-01-15 10:30:00" -> "01-15 10:30"
+fn short_date(timestamp: &str) -> String {
+    timestamp.get(5..16).unwrap_or(timestamp).to_string()
+}
+
+fn duration_minutes(session: &SessionRow) -> Option<i64> {
+    let parse = |s: &str| chrono::NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok();
+    let start = parse(&session.started_at)?;
+    let end = match &session.ended_at {
+        Some(ended) => parse(ended)?,
+        None => chrono::Utc::now().naive_utc(),
+    };
+    Some((end - start).num_minutes().max(0))
+}
+
+fn format_duration(minutes: i64) -> String {
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}h", minutes / 60)
+    }
+}
+
+fn load_data(conn: &Connection) -> Result<Data> {
+    let mut stmt = conn.prepare(
+        "SELECT task_id, description, status, priority, due FROM tasks
+         WHERE status NOT IN ('completed', 'cancelled')
+         ORDER BY CASE priority WHEN 'urgent' THEN 0 WHEN 'high' THEN 1 WHEN 'normal' THEN 2 ELSE 3 END,
+                  CASE WHEN due IS NULL THEN 1 ELSE 0 END, due, task_id",
+    )?;
+    let tasks = stmt
+        .query_map([], |row| {
+            Ok(TaskRow {
+                id: row.get(0)?,
+                description: row.get(1)?,
+                status: row.get::<_, Option<String>>(2)?.unwrap_or_default(),
+                priority: row
+                    .get::<_, Option<String>>(3)?
+                    .unwrap_or_else(|| "normal".to_string()),
+                due: row.get(4)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT blocker_id, description, related_task_id FROM blockers
+         WHERE status = 'active' ORDER BY created_at, blocker_id",
+    )?;
+    let blockers = stmt
+        .query_map([], |row| {
+            Ok(BlockerRow {
+                id: row.get(0)?,
+                description: row.get(1)?,
+                related_task_id: row.get(2)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT decision_id, created_at, topic, decision FROM decisions
+         WHERE status = 'active' ORDER BY created_at DESC, decision_id DESC LIMIT ?1",
+    )?;
+    let decisions = stmt
+        .query_map([HISTORY_LIMIT], |row| {
+            Ok(DecisionRow {
+                id: row.get(0)?,
+                created_at: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                topic: row.get(2)?,
+                decision: row.get(3)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut stmt = conn.prepare(
+        "SELECT session_id, started_at, ended_at, status, name, summary FROM sessions
+         ORDER BY started_at DESC, session_id DESC LIMIT ?1",
+    )?;
+    let sessions = stmt
+        .query_map([HISTORY_LIMIT], |row| {
+            Ok(SessionRow {
+                id: row.get(0)?,
+                started_at: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                ended_at: row.get(2)?,
+                status: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+                name: row.get(4)?,
+                summary: row.get(5)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(Data {
+        active_session: get_active_session(conn)?,
+        tasks,
+        blockers,
+        decisions,
+        sessions,
+    })
+}
+
+/// Load project configuration
+fn load_config() -> Result<ProjectConfig> {
+    let config_path = get_config_path()?;
+    let content = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read config at {:?}", config_path))?;
+    let config: ProjectConfig =
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
+    Ok(config)
+}
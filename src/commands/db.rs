@@ -0,0 +1,148 @@
+// Db command - database maintenance (WAL mode, VACUUM/ANALYZE, search index rebuilds)
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::cli::{DbCommands, DbSubcommand};
+use crate::database::{get_schema_version, open_database};
+use crate::docs_db::{find_docs_db, open_docs_db};
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::schema::rebuild_fts_index;
+use crate::SCHEMA_VERSION;
+
+/// FTS tables in docs databases (external content, so 'rebuild' re-reads their source tables)
+const DOCS_FTS_TABLES: [&str; 2] = ["sections_fts", "terminology_fts"];
+
+pub fn run(cmd: DbCommands) -> Result<()> {
+    match cmd.command {
+        DbSubcommand::Optimize => optimize(),
+    }
+}
+
+/// Optimize the tracking database and, if present, the docs database
+pub fn optimize() -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    optimize_database(&db_path, "tracking.db", open_database, |conn| {
+        let version = get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string());
+        if version != SCHEMA_VERSION {
+            println!(
+                "  {} Skipped search index (schema v{} - run 'proj upgrade')",
+                "⚠".yellow(),
+                version
+            );
+            return Ok(());
+        }
+        let count = rebuild_fts_index(conn).with_context(|| "Failed to rebuild search index")?;
+        println!("  {} Rebuilt search index ({} entries)", "✓".green(), count);
+        Ok(())
+    })?;
+
+    if let Some(docs_path) = find_docs_db(&get_project_root()?) {
+        let name = docs_path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        println!();
+        optimize_database(&docs_path, &name, open_docs_db, |conn| {
+            for table in DOCS_FTS_TABLES {
+                let exists = conn
+                    .query_row(
+                        "SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1",
+                        [table],
+                        |_| Ok(()),
+                    )
+                    .is_ok();
+                if exists {
+                    conn.execute(
+                        &format!("INSERT INTO {0}({0}) VALUES ('rebuild')", table),
+                        [],
+                    )?;
+                }
+            }
+            println!("  {} Rebuilt search indexes", "✓".green());
+            Ok(())
+        })?;
+    }
+
+    Ok(())
+}
+
+/// WAL mode, index rebuild, ANALYZE, VACUUM, and a WAL checkpoint, reporting the size change
+fn optimize_database(
+    path: &Path,
+    name: &str,
+    open: fn(&Path) -> Result<Connection>,
+    rebuild_indexes: impl Fn(&Connection) -> Result<()>,
+) -> Result<()> {
+    println!("{}", name.bold());
+    let before = database_size(path);
+
+    // Read the mode before switching, to report the change
+    let previous_mode: String = Connection::open(path)?
+        .query_row("PRAGMA journal_mode", [], |row| row.get(0))
+        .unwrap_or_default();
+
+    let conn = open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+
+    let mode: String = conn.query_row("PRAGMA journal_mode = WAL", [], |row| row.get(0))?;
+    if previous_mode.eq_ignore_ascii_case("wal") {
+        println!("  {} Journal mode: WAL", "✓".green());
+    } else {
+        println!(
+            "  {} Journal mode: {} → {}",
+            "✓".green(),
+            previous_mode,
+            mode.to_uppercase()
+        );
+    }
+
+    rebuild_indexes(&conn)?;
+
+    conn.execute_batch("ANALYZE;")?;
+    println!("  {} Updated query planner statistics", "✓".green());
+
+    conn.execute_batch("VACUUM;")
+        .with_context(|| "VACUUM failed (is another proj process using the database?)")?;
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    drop(conn);
+    println!("  {} Vacuumed and checkpointed", "✓".green());
+
+    let after = database_size(path);
+    let saved = before.saturating_sub(after);
+    let percent = if before > 0 {
+        saved as f64 * 100.0 / before as f64
+    } else {
+        0.0
+    };
+    println!(
+        "  Size: {} → {} ({} saved, {:.0}%)",
+        format_size(before),
+        format_size(after),
+        format_size(saved),
+        percent
+    );
+    Ok(())
+}
+
+/// Size of the database plus its WAL and shared-memory files
+fn database_size(path: &Path) -> u64 {
+    ["", "-wal", "-shm"]
+        .iter()
+        .map(|suffix| {
+            let mut file = path.as_os_str().to_owned();
+            file.push(suffix);
+            std::fs::metadata(&file).map(|m| m.len()).unwrap_or(0)
+        })
+        .sum()
+}
+
+fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
@@ -0,0 +1,594 @@
+// Decision commands - list by topic, list topics, rename and merge topics, and
+// ADR export and import (decision_adr.rs)
+//
+// Topics are kept in decision_topics. Each topic is registered the first time a
+// decision uses it, matched without regard to case, so "Database" lands on
+// "database". Renaming or merging a topic retags the decisions already logged and
+// leaves the old name behind as an alias, so later decisions logged under it land on
+// the new topic too.
+
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::cli::{DecisionCommands, DecisionSubcommand, PageArgs};
+use crate::commands::decision_adr;
+use crate::database::{get_schema_version, open_database, write_transaction};
+use crate::error::ProjError;
+use crate::pager;
+use crate::paths::get_tracking_db_path;
+use crate::porcelain;
+use crate::session::get_or_create_session_with_info;
+use crate::text::truncate;
+
+const DECISION_STATUSES: [&str; 3] = ["active", "superseded", "reversed"];
+
+/// SQL expression for a decision's topic after aliases are followed
+const RESOLVED_TOPIC: &str = "COALESCE(
+    (SELECT COALESCE(t.alias_of, t.topic) FROM decision_topics t WHERE t.topic = trim(decisions.topic)),
+    trim(decisions.topic))";
+
+pub fn run(cmd: DecisionCommands) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    require_decision_topics(&conn)?;
+
+    match cmd.command {
+        DecisionSubcommand::List {
+            by_topic,
+            topic,
+            status,
+            page,
+        } => cmd_decision_list(&conn, by_topic, topic.as_deref(), &status, &page),
+        DecisionSubcommand::Topics => cmd_decision_topics(&conn),
+        DecisionSubcommand::Rename { from, to } => {
+            let session_id = current_session(&conn)?;
+            cmd_topic_rename(&conn, session_id, &from, &to)
+        }
+        DecisionSubcommand::Merge { from, into } => {
+            let session_id = current_session(&conn)?;
+            cmd_topic_merge(&conn, session_id, &from, &into)
+        }
+        DecisionSubcommand::ExportAdr { dir, dry_run } => decision_adr::export(&conn, dir, dry_run),
+        DecisionSubcommand::ImportAdr {
+            dir,
+            topic,
+            dry_run,
+        } => {
+            let session_id = current_session(&conn)?;
+            decision_adr::import(&conn, session_id, dir, &topic, dry_run)
+        }
+    }
+}
+
+/// The topic a new decision is logged under: an existing topic spelled as it was
+/// registered, the topic an alias points at, or this one, registered now. Databases
+/// from before topics were tracked get the topic as given, trimmed.
+pub fn resolve_topic(conn: &Connection, topic: &str) -> Result<String> {
+    let topic = normalize(topic);
+    if topic.is_empty() || !has_decision_topics(conn)? {
+        return Ok(topic);
+    }
+    if let Some((name, alias_of)) = find_topic(conn, &topic)? {
+        return Ok(alias_of.unwrap_or(name));
+    }
+    conn.execute("INSERT INTO decision_topics (topic) VALUES (?1)", [&topic])?;
+    Ok(topic)
+}
+
+/// Surrounding whitespace trimmed and inner runs of it collapsed to one space
+fn normalize(topic: &str) -> String {
+    topic.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn has_decision_topics(conn: &Connection) -> Result<bool> {
+    Ok(conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='decision_topics')",
+        [],
+        |row| row.get(0),
+    )?)
+}
+
+/// Fail with an upgrade hint if the tracking database predates decision topics
+fn require_decision_topics(conn: &Connection) -> Result<()> {
+    if !has_decision_topics(conn)? {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.20".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// The current session, started if needed
+fn current_session(conn: &Connection) -> Result<i64> {
+    let session_result = get_or_create_session_with_info(conn)?;
+    let session = session_result.session;
+
+    // Notify if a stale session was closed
+    if let Some(closed) = session_result.auto_closed_session {
+        eprintln!(
+            "{} Previous session #{} was stale. Started new session #{}",
+            "⚠".yellow(),
+            closed.session_id,
+            session.session_id
+        );
+    }
+    Ok(session.session_id)
+}
+
+/// A registered topic as spelled when registered, and the topic it's an alias of
+fn find_topic(conn: &Connection, topic: &str) -> Result<Option<(String, Option<String>)>> {
+    Ok(conn
+        .query_row(
+            "SELECT topic, alias_of FROM decision_topics WHERE topic = ?1",
+            [topic],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?)
+}
+
+/// A topic to rename or merge. One that's only on decisions (merged or imported from
+/// another database) is registered first; one that's nowhere is an error.
+fn existing_topic(conn: &Connection, topic: &str) -> Result<(String, Option<String>)> {
+    let topic = normalize(topic);
+    if let Some(found) = find_topic(conn, &topic)? {
+        return Ok(found);
+    }
+    let used: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM decisions WHERE trim(topic) = ?1 COLLATE NOCASE)",
+        [&topic],
+        |row| row.get(0),
+    )?;
+    if !used {
+        return Err(ProjError::InvalidInput(format!(
+            "Unknown topic '{}'. See 'proj decision topics'.",
+            topic
+        ))
+        .into());
+    }
+    conn.execute("INSERT INTO decision_topics (topic) VALUES (?1)", [&topic])?;
+    Ok((topic, None))
+}
+
+/// List decisions newest first, or grouped by topic
+fn cmd_decision_list(
+    conn: &Connection,
+    by_topic: bool,
+    topic: Option<&str>,
+    status: &str,
+    page: &PageArgs,
+) -> Result<()> {
+    if status != "all" && !DECISION_STATUSES.contains(&status) {
+        return Err(ProjError::InvalidInput(format!(
+            "Invalid status '{}'. Valid statuses: {}, all",
+            status,
+            DECISION_STATUSES.join(", ")
+        ))
+        .into());
+    }
+    let status = (status != "all").then_some(status);
+    // An alias lists the decisions of the topic it points at
+    let topic = match topic {
+        Some(t) => {
+            let t = normalize(t);
+            Some(match find_topic(conn, &t)? {
+                Some((name, alias_of)) => alias_of.unwrap_or(name),
+                None => t,
+            })
+        }
+        None => None,
+    };
+
+    let filter = format!(
+        "FROM decisions
+         WHERE (?1 IS NULL OR {} = ?1 COLLATE NOCASE) AND (?2 IS NULL OR status = ?2)",
+        RESOLVED_TOPIC
+    );
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) {}", filter),
+        rusqlite::params![topic, status],
+        |row| row.get(0),
+    )?;
+
+    let order = if by_topic {
+        "ORDER BY 2 COLLATE NOCASE, created_at DESC, decision_id DESC"
+    } else {
+        "ORDER BY created_at DESC, decision_id DESC"
+    };
+    let mut stmt = conn.prepare(&format!(
+        "SELECT decision_id, {}, decision, status, created_at {} {} LIMIT ?3 OFFSET ?4",
+        RESOLVED_TOPIC, filter, order
+    ))?;
+    let limit = page.limit.map_or(-1, |l| l as i64);
+    let decisions = stmt
+        .query_map(
+            rusqlite::params![topic, status, limit, page.offset as i64],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                ))
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Porcelain: ID, topic, status, created, decision
+    if porcelain::enabled() {
+        for (decision_id, topic, decision, status, created_at) in &decisions {
+            porcelain::print(&[
+                &decision_id.to_string(),
+                topic,
+                status,
+                created_at,
+                decision,
+            ]);
+        }
+        return Ok(());
+    }
+
+    if decisions.is_empty() {
+        if total > 0 {
+            println!("No decisions past the first {} (--offset).", total);
+        } else if let Some(t) = &topic {
+            println!("No decisions on '{}'.", t);
+        } else {
+            println!("No decisions found.");
+        }
+        return Ok(());
+    }
+
+    let mut out = String::new();
+    writeln!(out, "{}", "Decisions:".bold())?;
+    writeln!(out, "{}", "-".repeat(60))?;
+
+    let shown = decisions.len();
+    let mut current_topic: Option<String> = None;
+    for (decision_id, topic, decision, decision_status, created_at) in decisions {
+        let date = created_at.split(' ').next().unwrap_or(&created_at);
+        let status_suffix = if decision_status == "active" {
+            String::new()
+        } else {
+            format!(" ({})", decision_status)
+        };
+        if by_topic {
+            if !current_topic
+                .as_deref()
+                .is_some_and(|t| t.eq_ignore_ascii_case(&topic))
+            {
+                if current_topic.is_some() {
+                    writeln!(out)?;
+                }
+                writeln!(out, "[{}]", topic.bold())?;
+                current_topic = Some(topic);
+            }
+            writeln!(
+                out,
+                "  #{:<4} {}{} {}",
+                decision_id,
+                truncate(&decision, 70),
+                status_suffix.yellow(),
+                date.dimmed()
+            )?;
+        } else {
+            writeln!(
+                out,
+                "  #{:<4} {} {}{} {}",
+                decision_id,
+                format!("[{}]", topic).cyan(),
+                truncate(&decision, 60),
+                status_suffix.yellow(),
+                date.dimmed()
+            )?;
+        }
+    }
+
+    if (shown as i64) < total {
+        writeln!(
+            out,
+            "{}",
+            format!(
+                "\nShowing {}-{} of {}. Use --limit and --offset for more.",
+                page.offset + 1,
+                page.offset + shown,
+                total
+            )
+            .dimmed()
+        )?;
+    }
+
+    pager::page(&out);
+    Ok(())
+}
+
+/// List topics with how many decisions each has and the aliases pointing at it
+fn cmd_decision_topics(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT name, SUM(active), SUM(total) FROM (
+             SELECT {} AS name, SUM(status = 'active') AS active, COUNT(*) AS total
+             FROM decisions GROUP BY 1 COLLATE NOCASE
+             UNION ALL
+             SELECT topic, 0, 0 FROM decision_topics WHERE alias_of IS NULL
+         )
+         GROUP BY name COLLATE NOCASE
+         ORDER BY name COLLATE NOCASE",
+        RESOLVED_TOPIC
+    ))?;
+    let topics = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut aliases: HashMap<String, Vec<String>> = HashMap::new();
+    let mut stmt = conn.prepare(
+        "SELECT topic, alias_of FROM decision_topics
+         WHERE alias_of IS NOT NULL ORDER BY topic COLLATE NOCASE",
+    )?;
+    for row in stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })? {
+        let (alias, topic) = row?;
+        aliases.entry(topic.to_lowercase()).or_default().push(alias);
+    }
+
+    // Porcelain: topic, active decisions, all decisions, aliases (comma-separated)
+    if porcelain::enabled() {
+        for (topic, active, total) in &topics {
+            let topic_aliases = aliases
+                .get(&topic.to_lowercase())
+                .map(|a| a.join(","))
+                .unwrap_or_default();
+            porcelain::print(&[
+                topic,
+                &active.to_string(),
+                &total.to_string(),
+                &topic_aliases,
+            ]);
+        }
+        return Ok(());
+    }
+
+    if topics.is_empty() {
+        println!("No decision topics yet.");
+        return Ok(());
+    }
+
+    println!("{}", "Decision topics:".bold());
+    println!("{}", "-".repeat(60));
+    let width = topics
+        .iter()
+        .map(|(t, _, _)| t.chars().count())
+        .max()
+        .unwrap_or(0)
+        .min(30);
+    for (topic, active, total) in &topics {
+        let count = if active == total {
+            format!("{} {}", active, plural(*active, "decision"))
+        } else {
+            format!(
+                "{} {} ({} active)",
+                total,
+                plural(*total, "decision"),
+                active
+            )
+        };
+        let alias_list = aliases
+            .get(&topic.to_lowercase())
+            .map(|a| format!("  aliases: {}", a.join(", ")).dimmed().to_string())
+            .unwrap_or_default();
+        println!(
+            "  {:<width$}  {}{}",
+            topic.bold(),
+            count,
+            alias_list,
+            width = width
+        );
+    }
+    Ok(())
+}
+
+/// Rename a topic on its decisions and keep the old name as an alias. A change of
+/// case only respells the topic.
+fn cmd_topic_rename(conn: &Connection, session_id: i64, from: &str, to: &str) -> Result<()> {
+    let to = normalize(to);
+    if to.is_empty() {
+        return Err(ProjError::InvalidInput("Topic cannot be empty".to_string()).into());
+    }
+
+    let tx = write_transaction(conn)?;
+    let (from, alias_of) = existing_topic(&tx, from)?;
+    if let Some(target) = alias_of {
+        return Err(ProjError::InvalidInput(format!(
+            "'{}' is an alias of '{}'; rename '{}' instead",
+            from, target, target
+        ))
+        .into());
+    }
+    if from == to {
+        return Err(ProjError::NothingToDo(format!("Topic '{}' unchanged", from)).into());
+    }
+    let case_only = from.eq_ignore_ascii_case(&to);
+    if !case_only {
+        match find_topic(&tx, &to)? {
+            // Renaming a topic to one of its own aliases swaps the two
+            Some((_, Some(target))) if target.eq_ignore_ascii_case(&from) => {
+                tx.execute("DELETE FROM decision_topics WHERE topic = ?1", [&to])?;
+            }
+            Some((name, alias_of)) => {
+                return Err(ProjError::InvalidInput(format!(
+                    "Topic '{}' already exists; use 'proj decision merge \"{}\" --into \"{}\"'",
+                    name,
+                    from,
+                    alias_of.unwrap_or_else(|| name.clone())
+                ))
+                .into());
+            }
+            None => {}
+        }
+    }
+
+    let retagged = tx.execute(
+        &format!(
+            "UPDATE decisions SET topic = ?1 WHERE {} = ?2 COLLATE NOCASE",
+            RESOLVED_TOPIC
+        ),
+        [&to, &from],
+    )?;
+    if case_only {
+        tx.execute(
+            "UPDATE decision_topics SET topic = ?1 WHERE topic = ?2",
+            [&to, &from],
+        )?;
+        tx.execute(
+            "UPDATE decision_topics SET alias_of = ?1 WHERE alias_of = ?2",
+            [&to, &from],
+        )?;
+    } else {
+        tx.execute("INSERT INTO decision_topics (topic) VALUES (?1)", [&to])?;
+        tx.execute(
+            "UPDATE decision_topics SET alias_of = ?1 WHERE topic = ?2 OR alias_of = ?2",
+            [&to, &from],
+        )?;
+    }
+
+    let summary = format!(
+        "Topic renamed: {} → {} ({} {})",
+        from,
+        to,
+        retagged,
+        plural(retagged as i64, "decision")
+    );
+    tx.execute(
+        "INSERT INTO activity_log (session_id, action_type, summary) VALUES (?1, 'topic_rename', ?2)",
+        rusqlite::params![session_id, summary],
+    )?;
+    tx.commit()?;
+
+    if porcelain::enabled() {
+        porcelain::print(&[&to, &retagged.to_string()]);
+        return Ok(());
+    }
+    println!(
+        "{} Renamed topic '{}' to '{}' ({} {} retagged)",
+        "✓".green(),
+        from,
+        to,
+        retagged,
+        plural(retagged as i64, "decision")
+    );
+    if !case_only {
+        println!(
+            "  {}",
+            format!("Decisions logged under '{}' now go to '{}'.", from, to).dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Merge topics into one: their decisions are retagged and they (and their aliases)
+/// become aliases of it
+fn cmd_topic_merge(conn: &Connection, session_id: i64, from: &[String], into: &str) -> Result<()> {
+    let into = normalize(into);
+    if into.is_empty() {
+        return Err(ProjError::InvalidInput("Topic cannot be empty".to_string()).into());
+    }
+
+    let tx = write_transaction(conn)?;
+    let into = match find_topic(&tx, &into)? {
+        Some((name, alias_of)) => alias_of.unwrap_or(name),
+        None => {
+            tx.execute("INSERT INTO decision_topics (topic) VALUES (?1)", [&into])?;
+            into
+        }
+    };
+
+    let mut merged = Vec::new();
+    let mut retagged = 0;
+    for topic in from {
+        let (name, alias_of) = existing_topic(&tx, topic)?;
+        if name.eq_ignore_ascii_case(&into) {
+            continue;
+        }
+        if let Some(target) = alias_of {
+            if target.eq_ignore_ascii_case(&into) {
+                continue;
+            }
+            return Err(ProjError::InvalidInput(format!(
+                "'{}' is an alias of '{}'; merge '{}' instead",
+                name, target, target
+            ))
+            .into());
+        }
+        retagged += tx.execute(
+            &format!(
+                "UPDATE decisions SET topic = ?1 WHERE {} = ?2 COLLATE NOCASE",
+                RESOLVED_TOPIC
+            ),
+            [&into, &name],
+        )?;
+        tx.execute(
+            "UPDATE decision_topics SET alias_of = ?1 WHERE topic = ?2 OR alias_of = ?2",
+            [&into, &name],
+        )?;
+        merged.push(name);
+    }
+    if merged.is_empty() {
+        return Err(ProjError::NothingToDo(format!(
+            "Nothing to merge; already part of '{}'",
+            into
+        ))
+        .into());
+    }
+
+    let summary = format!(
+        "Topics merged into {}: {} ({} {})",
+        into,
+        merged.join(", "),
+        retagged,
+        plural(retagged as i64, "decision")
+    );
+    tx.execute(
+        "INSERT INTO activity_log (session_id, action_type, summary) VALUES (?1, 'topic_merge', ?2)",
+        rusqlite::params![session_id, summary],
+    )?;
+    tx.commit()?;
+
+    if porcelain::enabled() {
+        porcelain::print(&[&into, &retagged.to_string()]);
+        return Ok(());
+    }
+    println!(
+        "{} Merged {} into '{}' ({} {} retagged)",
+        "✓".green(),
+        merged
+            .iter()
+            .map(|t| format!("'{}'", t))
+            .collect::<Vec<_>>()
+            .join(", "),
+        into,
+        retagged,
+        plural(retagged as i64, "decision")
+    );
+    Ok(())
+}
+
+fn plural(n: i64, word: &str) -> String {
+    if n == 1 {
+        word.to_string()
+    } else {
+        format!("{}s", word)
+    }
+}
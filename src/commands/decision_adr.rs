@@ -0,0 +1,639 @@
+// ADR export and import - decisions as Architecture Decision Records
+//
+// `proj decision export-adr` writes each decision to a numbered MADR file
+// (0001-use-sqlite.md) with its status, date, and topic in the front matter, and a
+// proj-id line so the next export rewrites the same file instead of adding another.
+// `proj decision import-adr` reads an ADR directory back: MADR front matter,
+// "* Status:" bullets, or a Nygard-style "## Status" section. ADRs proj already has
+// (same proj-id, or a decision with the same text) only get their status synced.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::commands::decision::resolve_topic;
+use crate::database::write_transaction;
+use crate::error::ProjError;
+use crate::paths::get_project_root;
+
+/// An ADR file as read from the directory
+struct Adr {
+    number: u32,
+    file_name: String,
+    title: String,
+    /// As written, e.g. "superseded by [ADR-0005](0005-use-postgres.md)"
+    status: String,
+    date: Option<String>,
+    topic: Option<String>,
+    proj_id: Option<String>,
+    deciders: Option<String>,
+    rationale: Option<String>,
+    options: Vec<String>,
+}
+
+/// A decision as exported
+struct DecisionRow {
+    decision_id: i64,
+    created_at: String,
+    topic: String,
+    decision: String,
+    rationale: Option<String>,
+    alternatives: Option<String>,
+    status: String,
+    superseded_by: Option<i64>,
+    author: Option<String>,
+    uuid: Option<String>,
+}
+
+/// What export does with a decision's file
+#[derive(PartialEq)]
+enum Write {
+    New,
+    Updated,
+    Unchanged,
+    /// An ADR proj didn't write has the same title; it's left as it is
+    Kept,
+}
+
+/// The ADR directory: as given (relative to the current directory), or docs/adr
+fn adr_dir(dir: Option<PathBuf>) -> Result<PathBuf> {
+    match dir {
+        Some(dir) => Ok(dir),
+        None => Ok(get_project_root()?.join("docs").join("adr")),
+    }
+}
+
+/// Write every decision as an ADR file
+pub fn export(conn: &Connection, dir: Option<PathBuf>, dry_run: bool) -> Result<()> {
+    let dir = adr_dir(dir)?;
+    let existing = if dir.is_dir() {
+        read_dir(&dir)?
+    } else {
+        Vec::new()
+    };
+
+    let mut stmt = conn.prepare(
+        "SELECT decision_id, created_at, topic, decision, rationale, alternatives, status,
+                superseded_by, author, uuid
+         FROM decisions ORDER BY created_at, decision_id",
+    )?;
+    let decisions = stmt
+        .query_map([], |row| {
+            Ok(DecisionRow {
+                decision_id: row.get(0)?,
+                created_at: row.get(1)?,
+                topic: row.get(2)?,
+                decision: row.get(3)?,
+                rationale: row.get(4)?,
+                alternatives: row.get(5)?,
+                status: row.get(6)?,
+                superseded_by: row.get(7)?,
+                author: row.get(8)?,
+                uuid: row.get(9)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    if decisions.is_empty() {
+        return Err(ProjError::NothingToDo("No decisions to export.".to_string()).into());
+    }
+
+    // Number every decision first, so superseded ones can link to their replacement
+    let mut next = existing.iter().map(|a| a.number).max().unwrap_or(0) + 1;
+    let mut files: HashMap<i64, (u32, String, bool)> = HashMap::new();
+    for d in &decisions {
+        let title = title_of(&d.decision);
+        let by_id = d
+            .uuid
+            .as_ref()
+            .and_then(|id| existing.iter().find(|a| a.proj_id.as_ref() == Some(id)));
+        let by_title = || {
+            existing
+                .iter()
+                .find(|a| a.proj_id.is_none() && a.title.eq_ignore_ascii_case(&title))
+        };
+        let file = match by_id.or_else(by_title) {
+            Some(adr) => (adr.number, adr.file_name.clone(), adr.proj_id.is_some()),
+            None => {
+                let number = next;
+                next += 1;
+                (number, format!("{:04}-{}.md", number, slug(&title)), true)
+            }
+        };
+        files.insert(d.decision_id, file);
+    }
+
+    if !dry_run {
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    for d in &decisions {
+        let (number, file_name, ours) = &files[&d.decision_id];
+        let path = dir.join(file_name);
+        let write = if !ours {
+            Write::Kept
+        } else {
+            let superseded_by = d.superseded_by.and_then(|id| files.get(&id));
+            let content = render(d, *number, superseded_by.map(|(n, f, _)| (*n, f.as_str())));
+            match fs::read_to_string(&path) {
+                Ok(current) if current == content => Write::Unchanged,
+                current => {
+                    if !dry_run {
+                        fs::write(&path, &content)
+                            .with_context(|| format!("Failed to write {}", path.display()))?;
+                    }
+                    if current.is_ok() {
+                        Write::Updated
+                    } else {
+                        Write::New
+                    }
+                }
+            }
+        };
+        let label = match write {
+            Write::New => "new",
+            Write::Updated => "updated",
+            Write::Unchanged => "unchanged",
+            Write::Kept => "kept",
+        };
+        if dry_run && write != Write::Unchanged {
+            println!("  {:<9} {}", label, file_name);
+        }
+        *counts.entry(label).or_default() += 1;
+    }
+
+    let summary = ["new", "updated", "unchanged", "kept"]
+        .iter()
+        .filter_map(|label| counts.get(label).map(|n| format!("{} {}", n, label)))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if dry_run {
+        println!(
+            "{} decisions would be exported to {} ({}). Nothing was written.",
+            decisions.len(),
+            dir.display(),
+            summary
+        );
+    } else {
+        println!(
+            "{} Exported {} decisions to {} ({})",
+            "✓".green(),
+            decisions.len(),
+            dir.display(),
+            summary
+        );
+    }
+    if counts.contains_key("kept") {
+        println!(
+            "  {}",
+            "Kept: ADRs with the decision's title that proj didn't write are left as they are."
+                .dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// A decision as a MADR file
+fn render(d: &DecisionRow, number: u32, superseded_by: Option<(u32, &str)>) -> String {
+    let status = match (d.status.as_str(), superseded_by) {
+        ("superseded", Some((n, file))) => format!("superseded by [ADR-{:04}]({})", n, file),
+        ("superseded", None) => "superseded".to_string(),
+        ("reversed", _) => "deprecated".to_string(),
+        _ => "accepted".to_string(),
+    };
+    let date = d.created_at.split(' ').next().unwrap_or(&d.created_at);
+    let title = title_of(&d.decision);
+
+    let mut out = String::from("---\n");
+    out.push_str(&format!("status: {}\n", status));
+    out.push_str(&format!("date: {}\n", date));
+    if let Some(author) = &d.author {
+        out.push_str(&format!("deciders: {}\n", author));
+    }
+    out.push_str(&format!("topic: {}\n", d.topic));
+    if let Some(uuid) = &d.uuid {
+        out.push_str(&format!("proj-id: {}\n", uuid));
+    }
+    out.push_str("---\n\n");
+    out.push_str(&format!("# {}. {}\n", number, title));
+
+    let options: Vec<&str> = d
+        .alternatives
+        .as_deref()
+        .map(|a| a.lines().map(str::trim).filter(|l| !l.is_empty()).collect())
+        .unwrap_or_default();
+    if !options.is_empty() {
+        out.push_str("\n## Considered Options\n\n");
+        out.push_str(&format!("* {}\n", title));
+        for option in options {
+            out.push_str(&format!("* {}\n", option));
+        }
+    }
+
+    out.push_str("\n## Decision Outcome\n\n");
+    match d
+        .rationale
+        .as_deref()
+        .map(str::trim)
+        .filter(|r| !r.is_empty())
+    {
+        Some(rationale) => out.push_str(&format!(
+            "Chosen option: \"{}\", because {}\n",
+            title, rationale
+        )),
+        None => out.push_str(&format!("Chosen option: \"{}\"\n", title)),
+    }
+    // The rest of a multi-line decision
+    let rest = d
+        .decision
+        .trim()
+        .lines()
+        .skip(1)
+        .collect::<Vec<_>>()
+        .join("\n");
+    if !rest.trim().is_empty() {
+        out.push_str(&format!("\n{}\n", rest.trim()));
+    }
+    out
+}
+
+/// What an ADR's status means for a decision, with the number of the ADR that
+/// supersedes it. None: not decided yet (proposed, draft) or a status proj doesn't know.
+fn decision_status(status: &str) -> Option<(&'static str, Option<u32>)> {
+    let status = status.trim().to_lowercase();
+    let first = status
+        .split(|c: char| !c.is_alphanumeric())
+        .find(|w| !w.is_empty())
+        .unwrap_or("");
+    match first {
+        "accepted" | "approved" | "adopted" | "decided" | "done" | "amended" => {
+            Some(("active", None))
+        }
+        "superseded" | "replaced" => {
+            let number = status
+                .split(|c: char| !c.is_ascii_digit())
+                .find(|n| !n.is_empty())
+                .and_then(|n| n.parse().ok());
+            Some(("superseded", number))
+        }
+        "deprecated" | "rejected" | "reversed" | "retired" | "obsolete" => Some(("reversed", None)),
+        _ => None,
+    }
+}
+
+/// Import an ADR directory: new ADRs become decisions, known ones have their status synced
+pub fn import(
+    conn: &Connection,
+    session_id: i64,
+    dir: Option<PathBuf>,
+    default_topic: &str,
+    dry_run: bool,
+) -> Result<()> {
+    let dir = adr_dir(dir)?;
+    if !dir.is_dir() {
+        return Err(
+            ProjError::InvalidInput(format!("No ADR directory at {}", dir.display())).into(),
+        );
+    }
+    let adrs = read_dir(&dir)?;
+    if adrs.is_empty() {
+        return Err(ProjError::NothingToDo(format!(
+            "No ADRs (numbered .md files) in {}",
+            dir.display()
+        ))
+        .into());
+    }
+
+    let tx = write_transaction(conn)?;
+    let mut decision_ids: HashMap<u32, i64> = HashMap::new();
+    let mut supersedes: Vec<(i64, u32)> = Vec::new();
+    let (mut imported, mut updated, mut unchanged, mut skipped) = (0, 0, 0, 0);
+
+    for adr in &adrs {
+        let Some((status, superseded_by)) = decision_status(&adr.status) else {
+            let why = if adr.status.is_empty() {
+                "no status".to_string()
+            } else {
+                format!("status '{}'", adr.status)
+            };
+            println!("  {} {} ({})", "skip".yellow(), adr.file_name, why.dimmed());
+            skipped += 1;
+            continue;
+        };
+
+        let existing: Option<(i64, String)> = tx
+            .query_row(
+                "SELECT decision_id, status FROM decisions
+                 WHERE uuid = ?1 OR (?1 IS NULL AND lower(trim(decision)) = lower(?2))
+                 ORDER BY decision_id LIMIT 1",
+                rusqlite::params![adr.proj_id, adr.title],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()?;
+
+        let decision_id = match existing {
+            Some((decision_id, current)) if current == status => {
+                unchanged += 1;
+                decision_id
+            }
+            Some((decision_id, current)) => {
+                tx.execute(
+                    "UPDATE decisions SET status = ?1 WHERE decision_id = ?2",
+                    rusqlite::params![status, decision_id],
+                )?;
+                println!(
+                    "  {} {} #{}: {} → {}",
+                    "sync".cyan(),
+                    adr.file_name,
+                    decision_id,
+                    current,
+                    status
+                );
+                updated += 1;
+                decision_id
+            }
+            None => {
+                let topic = resolve_topic(&tx, adr.topic.as_deref().unwrap_or(default_topic))?;
+                let created_at = adr
+                    .date
+                    .as_deref()
+                    .filter(|d| chrono::NaiveDate::parse_from_str(d, "%Y-%m-%d").is_ok())
+                    .map(|d| format!("{} 00:00:00", d));
+                let alternatives = (!adr.options.is_empty()).then(|| adr.options.join("\n"));
+                tx.execute(
+                    "INSERT INTO decisions (session_id, topic, decision, rationale, alternatives, status, created_at, author)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, COALESCE(?7, datetime('now')), ?8)",
+                    rusqlite::params![
+                        session_id,
+                        topic,
+                        adr.title,
+                        adr.rationale,
+                        alternatives,
+                        status,
+                        created_at,
+                        adr.deciders
+                    ],
+                )?;
+                let decision_id = tx.last_insert_rowid();
+                println!(
+                    "  {} {} → #{} [{}] {}",
+                    "new".green(),
+                    adr.file_name,
+                    decision_id,
+                    topic,
+                    status
+                );
+                imported += 1;
+                decision_id
+            }
+        };
+        decision_ids.insert(adr.number, decision_id);
+        if let Some(number) = superseded_by {
+            supersedes.push((decision_id, number));
+        }
+    }
+
+    // "superseded by ADR-0005" links to the decision ADR 5 became
+    for (decision_id, number) in supersedes {
+        if let Some(by) = decision_ids.get(&number) {
+            tx.execute(
+                "UPDATE decisions SET superseded_by = ?1
+                 WHERE decision_id = ?2 AND superseded_by IS NOT ?1",
+                rusqlite::params![by, decision_id],
+            )?;
+        }
+    }
+
+    if dry_run {
+        tx.rollback()?;
+        println!(
+            "{} new, {} status changes, {} unchanged, {} skipped. Nothing was imported (--dry-run).",
+            imported, updated, unchanged, skipped
+        );
+        return Ok(());
+    }
+
+    if imported + updated > 0 {
+        let summary = format!(
+            "ADRs imported from {}: {} new, {} status changes",
+            dir.display(),
+            imported,
+            updated
+        );
+        tx.execute(
+            "INSERT INTO activity_log (session_id, action_type, summary) VALUES (?1, 'adr_import', ?2)",
+            rusqlite::params![session_id, summary],
+        )?;
+    }
+    tx.commit()?;
+
+    if imported + updated == 0 {
+        return Err(ProjError::NothingToDo(format!(
+            "Decisions already match the ADRs in {} ({} unchanged, {} skipped)",
+            dir.display(),
+            unchanged,
+            skipped
+        ))
+        .into());
+    }
+    println!(
+        "{} Imported ADRs from {}: {} new, {} status changes, {} unchanged, {} skipped",
+        "✓".green(),
+        dir.display(),
+        imported,
+        updated,
+        unchanged,
+        skipped
+    );
+    Ok(())
+}
+
+/// The ADRs in a directory: .md files whose name starts with a number, in number order
+fn read_dir(dir: &Path) -> Result<Vec<Adr>> {
+    let mut adrs = Vec::new();
+    for entry in fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))? {
+        let path = entry?.path();
+        let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+            continue;
+        };
+        if !file_name.ends_with(".md") {
+            continue;
+        }
+        let digits: String = file_name.chars().take_while(char::is_ascii_digit).collect();
+        let Ok(number) = digits.parse::<u32>() else {
+            continue;
+        };
+        let text = fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        adrs.push(parse(number, file_name, &text));
+    }
+    adrs.sort_by_key(|a| a.number);
+    Ok(adrs)
+}
+
+/// Read an ADR: front matter, "* Key: value" lines before the first section, and
+/// the Status, Decision Outcome (or Decision), and Considered Options sections
+fn parse(number: u32, file_name: &str, text: &str) -> Adr {
+    let mut meta: HashMap<String, String> = HashMap::new();
+    let mut body = text;
+    if let Some(rest) = text.strip_prefix("---\n") {
+        if let Some(end) = rest.find("\n---") {
+            for line in rest[..end].lines() {
+                if let Some((key, value)) = line.split_once(':') {
+                    meta.insert(key.trim().to_lowercase(), unquote(value.trim()));
+                }
+            }
+            body = rest[end + 4..].trim_start_matches(['-', '\n']);
+        }
+    }
+
+    let mut title = String::new();
+    let mut sections: HashMap<String, String> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in body.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            current = Some(heading.trim().to_lowercase());
+            continue;
+        }
+        if let Some(heading) = line.strip_prefix("# ") {
+            if title.is_empty() {
+                title = strip_number(heading.trim());
+            }
+            continue;
+        }
+        match &current {
+            Some(section) => {
+                let text = sections.entry(section.clone()).or_default();
+                text.push_str(line);
+                text.push('\n');
+            }
+            // "* Status: accepted", "Date: 2024-01-01" under the title
+            None => {
+                let line = line.trim().trim_start_matches(['*', '-']).trim();
+                if let Some((key, value)) = line.split_once(':') {
+                    let key = key.trim().to_lowercase();
+                    if matches!(key.as_str(), "status" | "date" | "deciders") {
+                        meta.entry(key).or_insert_with(|| unquote(value.trim()));
+                    }
+                }
+            }
+        }
+    }
+
+    let status = meta
+        .get("status")
+        .cloned()
+        .or_else(|| {
+            sections.get("status").and_then(|s| {
+                s.lines()
+                    .map(|l| l.trim().trim_start_matches(['*', '-']).trim())
+                    .find(|l| !l.is_empty())
+                    .map(str::to_string)
+            })
+        })
+        .unwrap_or_default();
+
+    let outcome = sections
+        .get("decision outcome")
+        .or_else(|| sections.get("decision"))
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty());
+    let rationale = outcome.map(|text| match text.find("because") {
+        Some(at) if text[..at].contains("Chosen option") => {
+            let reason = &text[at + "because".len()..];
+            reason
+                .split("\n\n")
+                .next()
+                .unwrap_or_default()
+                .trim()
+                .to_string()
+        }
+        _ => text,
+    });
+
+    let options = sections
+        .get("considered options")
+        .map(|s| {
+            s.lines()
+                .filter_map(|l| {
+                    l.trim()
+                        .strip_prefix("* ")
+                        .or_else(|| l.trim().strip_prefix("- "))
+                })
+                .map(|o| o.trim().to_string())
+                .filter(|o| !o.eq_ignore_ascii_case(&title))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Adr {
+        number,
+        file_name: file_name.to_string(),
+        title: if title.is_empty() {
+            file_name.trim_end_matches(".md").to_string()
+        } else {
+            title
+        },
+        status,
+        date: meta.get("date").cloned(),
+        topic: meta.get("topic").cloned().filter(|t| !t.is_empty()),
+        proj_id: meta.get("proj-id").cloned().filter(|t| !t.is_empty()),
+        deciders: meta.get("deciders").cloned().filter(|t| !t.is_empty()),
+        rationale: rationale.filter(|r| !r.is_empty()),
+        options,
+    }
+}
+
+/// "12. Use SQLite", "ADR-0012: Use SQLite" → "Use SQLite"
+fn strip_number(heading: &str) -> String {
+    let rest = heading
+        .strip_prefix("ADR")
+        .or_else(|| heading.strip_prefix("adr"))
+        .map(|r| r.trim_start_matches(['-', ' ']))
+        .unwrap_or(heading);
+    let digits = rest.chars().take_while(char::is_ascii_digit).count();
+    if digits == 0 {
+        return heading.to_string();
+    }
+    rest[digits..]
+        .trim_start_matches(['.', ':', ' ', '-'])
+        .trim()
+        .to_string()
+}
+
+fn unquote(value: &str) -> String {
+    value.trim_matches(|c| c == '"' || c == '\'').to_string()
+}
+
+/// The first line of a decision
+fn title_of(decision: &str) -> String {
+    decision
+        .trim()
+        .lines()
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_string()
+}
+
+/// File name part for a title: lowercase words joined by dashes
+fn slug(title: &str) -> String {
+    let slug = title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .collect::<Vec<_>>()
+        .join("-");
+    let mut slug: String = slug.chars().take(50).collect();
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    if slug.is_empty() {
+        "decision".to_string()
+    } else {
+        slug
+    }
+}
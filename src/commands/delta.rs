@@ -1,20 +1,118 @@
-// Delta command - show only what changed since last status check
+// Delta command - show only what changed since the last check
+//
+// Each run compares the tracking database against the state saved in the
+// last delta snapshot: new, changed, and closed tasks, new decisions, new and
+// resolved blockers, and new commits. Reading the state and saving the new
+// snapshot happen in one transaction, so nothing logged in between is lost.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet};
 
 use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
+use colored::Colorize;
 use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
-use crate::database::open_database;
-use crate::paths::get_tracking_db_path;
+use crate::database::{open_database, write_transaction};
+use crate::git;
+use crate::paths::{get_project_root, get_tracking_db_path};
 use crate::session::get_or_create_session;
+use crate::text::truncate;
+
+/// Delta snapshots kept; older ones are pruned as new ones are saved
+const SNAPSHOTS_KEPT: i64 = 10;
+
+/// What a snapshot remembers about the database
+#[derive(Serialize, Deserialize, PartialEq)]
+struct State {
+    counts: BTreeMap<String, i64>,
+    tasks: BTreeMap<i64, TaskState>,
+    active_blockers: BTreeSet<i64>,
+    max_decision_id: i64,
+    max_blocker_id: i64,
+    max_commit_id: i64,
+}
+
+#[derive(Serialize, Deserialize, PartialEq)]
+struct TaskState {
+    status: String,
+    priority: String,
+    /// Hash of the description, notes, due date, and blocked-by text
+    details: String,
+}
+
+/// Everything that changed since the last snapshot
+#[derive(Serialize, Default)]
+struct Delta {
+    /// When the previous snapshot was taken (None on the first run)
+    since: Option<String>,
+    new_tasks: Vec<TaskChange>,
+    changed_tasks: Vec<TaskChange>,
+    closed_tasks: Vec<TaskChange>,
+    new_decisions: Vec<DecisionEntry>,
+    new_blockers: Vec<BlockerEntry>,
+    resolved_blockers: Vec<BlockerEntry>,
+    commits: Vec<CommitEntry>,
+}
+
+impl Delta {
+    fn is_empty(&self) -> bool {
+        self.new_tasks.is_empty()
+            && self.changed_tasks.is_empty()
+            && self.closed_tasks.is_empty()
+            && self.new_decisions.is_empty()
+            && self.new_blockers.is_empty()
+            && self.resolved_blockers.is_empty()
+            && self.commits.is_empty()
+    }
+}
 
-/// Counts of various item types for snapshot comparison
-type ItemCounts = HashMap<String, i64>;
+#[derive(Serialize)]
+struct TaskChange {
+    task_id: i64,
+    description: String,
+    status: String,
+    priority: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    previous_status: Option<String>,
+    /// Fields that changed: status, priority, details
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    changes: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct DecisionEntry {
+    decision_id: i64,
+    topic: String,
+    decision: String,
+}
+
+#[derive(Serialize)]
+struct BlockerEntry {
+    blocker_id: i64,
+    description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    resolution: Option<String>,
+}
+
+#[derive(Serialize)]
+struct CommitEntry {
+    short_hash: String,
+    message: String,
+    committed_at: String,
+}
 
-pub fn run() -> Result<()> {
+/// A task row as read for comparison
+struct TaskRow {
+    task_id: i64,
+    description: String,
+    status: String,
+    priority: String,
+    details: String,
+}
+
+pub fn run(json: bool) -> Result<()> {
     // Open database
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
@@ -27,55 +125,51 @@ pub fn run() -> Result<()> {
     let is_new_session =
         !session.full_context_shown && get_session_activity_count(&conn, session.session_id)? == 0;
 
-    if is_new_session {
+    if is_new_session && !json {
         println!("New session started. Run 'proj status' for full context.");
         return Ok(());
     }
 
-    // Get current counts
-    let current_counts = get_current_counts(&conn)?;
+    if let Ok(root) = get_project_root() {
+        let _ = git::sync_recent_commits(&conn, &root, 20);
+    }
 
-    // Get last snapshot
-    let (last_hash, last_counts) = get_last_snapshot(&conn, session.session_id, "delta")?;
+    // Read, compare, and save in one transaction
+    let tx = write_transaction(&conn)?;
+    let previous = get_last_snapshot(&tx)?;
+    let (state, tasks) = get_current_state(&tx)?;
+    let delta = match &previous {
+        Some((created_at, last)) => {
+            let mut delta = compare(&tx, last, &state, &tasks)?;
+            delta.since = Some(created_at.clone());
+            delta
+        }
+        None => Delta::default(),
+    };
+    if previous.as_ref().map(|(_, last)| last) != Some(&state) {
+        save_snapshot(&tx, session.session_id, &state)?;
+    }
+    tx.commit()?;
 
-    // Compute current hash
-    let current_hash = compute_content_hash(&current_counts);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&delta)?);
+        return Ok(());
+    }
 
-    // Compare hashes
-    if let Some(ref lh) = last_hash {
-        if lh == &current_hash {
-            println!("No changes since last check.");
-            return Ok(());
-        }
+    if previous.is_none() {
+        println!("No earlier check to compare with. Run 'proj delta' again to see what changed.");
+        return Ok(());
+    }
+    if delta.is_empty() {
+        println!("No changes since last check.");
+        return Ok(());
     }
 
-    // Calculate and display deltas
     println!(
-        "Changes since last check (Session #{}):\n",
+        "Changes since last check (Session #{}):",
         session.session_id
     );
-
-    let mut changes = Vec::new();
-    for (key, current) in &current_counts {
-        let last = last_counts.get(key).copied().unwrap_or(0);
-        if *current != last {
-            let diff = *current - last;
-            let label = key.replace('_', " ");
-            if diff > 0 {
-                changes.push(format!("  + {} new {}", diff, label));
-            } else {
-                changes.push(format!("  - {} {} resolved/removed", diff.abs(), label));
-            }
-        }
-    }
-
-    if changes.is_empty() {
-        println!("  (counts unchanged, but content may have changed)");
-    } else {
-        for c in &changes {
-            println!("{}", c);
-        }
-    }
+    print_delta(&delta);
 
     // Show recent activity
     let activity = get_recent_activity(&conn, session.session_id, 5)?;
@@ -92,137 +186,329 @@ pub fn run() -> Result<()> {
         }
     }
 
-    // Save new snapshot
-    save_snapshot(
-        &conn,
-        session.session_id,
-        "delta",
-        &current_hash,
-        &current_counts,
-    )?;
-
     Ok(())
 }
 
-/// Get current counts of all tracked items
-fn get_current_counts(conn: &Connection) -> Result<ItemCounts> {
-    let mut counts = HashMap::new();
+fn print_delta(delta: &Delta) {
+    let task_line = |t: &TaskChange| format!("#{} {}", t.task_id, truncate(&t.description, 60));
 
-    // Active tasks
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tasks WHERE status NOT IN ('completed', 'cancelled')",
-        [],
-        |row| row.get(0),
-    )?;
-    counts.insert("active_tasks".to_string(), count);
+    if !delta.new_tasks.is_empty() {
+        println!("\n{}", "New tasks".underline());
+        for t in &delta.new_tasks {
+            println!("  {} {} [{}]", "+".green(), task_line(t), t.status);
+        }
+    }
+    if !delta.changed_tasks.is_empty() {
+        println!("\n{}", "Changed tasks".underline());
+        for t in &delta.changed_tasks {
+            let detail = match &t.previous_status {
+                Some(previous) if previous != &t.status => {
+                    format!("{} → {}", previous, t.status)
+                }
+                _ => t.changes.join(", "),
+            };
+            println!("  {} {} ({})", "~".yellow(), task_line(t), detail);
+        }
+    }
+    if !delta.closed_tasks.is_empty() {
+        println!("\n{}", "Closed tasks".underline());
+        for t in &delta.closed_tasks {
+            println!("  {} {} [{}]", "✓".green(), task_line(t), t.status);
+        }
+    }
+    if !delta.new_decisions.is_empty() {
+        println!("\n{}", "New decisions".underline());
+        for d in &delta.new_decisions {
+            println!(
+                "  {} #{} {}: {}",
+                "+".green(),
+                d.decision_id,
+                d.topic.bold(),
+                truncate(&d.decision, 60)
+            );
+        }
+    }
+    if !delta.new_blockers.is_empty() {
+        println!("\n{}", "New blockers".underline());
+        for b in &delta.new_blockers {
+            println!("  {} #{} {}", "!".red(), b.blocker_id, b.description);
+        }
+    }
+    if !delta.resolved_blockers.is_empty() {
+        println!("\n{}", "Resolved blockers".underline());
+        for b in &delta.resolved_blockers {
+            match &b.resolution {
+                Some(r) => println!(
+                    "  {} #{} {} - {}",
+                    "✓".green(),
+                    b.blocker_id,
+                    b.description,
+                    r.dimmed()
+                ),
+                None => println!("  {} #{} {}", "✓".green(), b.blocker_id, b.description),
+            }
+        }
+    }
+    if !delta.commits.is_empty() {
+        println!("\n{}", "Commits".underline());
+        for c in &delta.commits {
+            println!("  {} {}", c.short_hash.dimmed(), c.message);
+        }
+    }
+}
 
-    // Completed tasks
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM tasks WHERE status = 'completed'",
-        [],
-        |row| row.get(0),
+/// Current state, plus the task rows it was built from
+fn get_current_state(conn: &Connection) -> Result<(State, Vec<TaskRow>)> {
+    let mut stmt = conn.prepare(
+        "SELECT task_id, description, status, priority, notes, due, blocked_by
+         FROM tasks ORDER BY task_id",
     )?;
-    counts.insert("completed_tasks".to_string(), count);
+    let tasks = stmt
+        .query_map([], |row| {
+            let description: String = row.get(1)?;
+            let mut hasher = Sha256::new();
+            hasher.update(description.as_bytes());
+            for i in 4..7 {
+                hasher.update(b"\0");
+                hasher.update(row.get::<_, Option<String>>(i)?.unwrap_or_default());
+            }
+            Ok(TaskRow {
+                task_id: row.get(0)?,
+                description,
+                status: row
+                    .get::<_, Option<String>>(2)?
+                    .unwrap_or_else(|| "pending".to_string()),
+                priority: row
+                    .get::<_, Option<String>>(3)?
+                    .unwrap_or_else(|| "normal".to_string()),
+                details: format!("{:x}", hasher.finalize())[..16].to_string(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut active_stmt =
+        conn.prepare("SELECT blocker_id FROM blockers WHERE status = 'active'")?;
+    let active_blockers = active_stmt
+        .query_map([], |row| row.get::<_, i64>(0))?
+        .collect::<Result<BTreeSet<_>, _>>()?;
+
+    let max_id = |sql: &str| -> Result<i64> { Ok(conn.query_row(sql, [], |row| row.get(0))?) };
+
+    let state = State {
+        counts: get_current_counts(conn)?,
+        tasks: tasks
+            .iter()
+            .map(|t| {
+                (
+                    t.task_id,
+                    TaskState {
+                        status: t.status.clone(),
+                        priority: t.priority.clone(),
+                        details: t.details.clone(),
+                    },
+                )
+            })
+            .collect(),
+        active_blockers,
+        max_decision_id: max_id("SELECT COALESCE(MAX(decision_id), 0) FROM decisions")?,
+        max_blocker_id: max_id("SELECT COALESCE(MAX(blocker_id), 0) FROM blockers")?,
+        max_commit_id: max_id("SELECT COALESCE(MAX(commit_id), 0) FROM git_commits")?,
+    };
+    Ok((state, tasks))
+}
 
-    // Active blockers
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM blockers WHERE status = 'active'",
-        [],
-        |row| row.get(0),
-    )?;
-    counts.insert("blockers".to_string(), count);
+/// What changed between two states
+fn compare(conn: &Connection, last: &State, current: &State, tasks: &[TaskRow]) -> Result<Delta> {
+    let mut delta = Delta::default();
+    let is_closed = |status: &str| matches!(status, "completed" | "cancelled");
+
+    for task in tasks {
+        let mut change = TaskChange {
+            task_id: task.task_id,
+            description: task.description.clone(),
+            status: task.status.clone(),
+            priority: task.priority.clone(),
+            previous_status: None,
+            changes: Vec::new(),
+        };
+        let Some(before) = last.tasks.get(&task.task_id) else {
+            delta.new_tasks.push(change);
+            continue;
+        };
+        if before.status != task.status {
+            change.changes.push("status");
+            change.previous_status = Some(before.status.clone());
+        }
+        if before.priority != task.priority {
+            change.changes.push("priority");
+        }
+        if before.details != task.details {
+            change.changes.push("details");
+        }
+        if change.changes.is_empty() {
+            continue;
+        }
+        if is_closed(&task.status) && !is_closed(&before.status) {
+            delta.closed_tasks.push(change);
+        } else {
+            delta.changed_tasks.push(change);
+        }
+    }
 
-    // Active decisions
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM decisions WHERE status = 'active'",
-        [],
-        |row| row.get(0),
+    // Deleted tasks count as closed
+    for (task_id, before) in &last.tasks {
+        if !current.tasks.contains_key(task_id) {
+            delta.closed_tasks.push(TaskChange {
+                task_id: *task_id,
+                description: String::new(),
+                status: "deleted".to_string(),
+                priority: before.priority.clone(),
+                previous_status: Some(before.status.clone()),
+                changes: vec!["status"],
+            });
+        }
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT decision_id, topic, decision FROM decisions
+         WHERE decision_id > ?1 AND status = 'active' ORDER BY decision_id",
     )?;
-    counts.insert("decisions".to_string(), count);
+    delta.new_decisions = stmt
+        .query_map([last.max_decision_id], |row| {
+            Ok(DecisionEntry {
+                decision_id: row.get(0)?,
+                topic: row.get(1)?,
+                decision: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
 
-    // Open questions
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM questions WHERE status = 'open'",
-        [],
-        |row| row.get(0),
+    let mut stmt = conn.prepare(
+        "SELECT blocker_id, description, resolution, status FROM blockers
+         WHERE blocker_id > ?1 OR status != 'active' ORDER BY blocker_id",
     )?;
-    counts.insert("questions".to_string(), count);
+    let blockers = stmt
+        .query_map([last.max_blocker_id], |row| {
+            Ok((
+                BlockerEntry {
+                    blocker_id: row.get(0)?,
+                    description: row.get(1)?,
+                    resolution: row.get(2)?,
+                },
+                row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (blocker, status) in blockers {
+        let id = blocker.blocker_id;
+        if id > last.max_blocker_id {
+            if status == "active" {
+                delta.new_blockers.push(BlockerEntry {
+                    resolution: None,
+                    ..blocker
+                });
+            }
+        } else if last.active_blockers.contains(&id) {
+            delta.resolved_blockers.push(blocker);
+        }
+    }
 
-    // Active notes
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM context_notes WHERE status = 'active'",
-        [],
-        |row| row.get(0),
+    let mut stmt = conn.prepare(
+        "SELECT short_hash, message, committed_at FROM git_commits
+         WHERE commit_id > ?1 ORDER BY committed_at",
     )?;
-    counts.insert("notes".to_string(), count);
+    delta.commits = stmt
+        .query_map([last.max_commit_id], |row| {
+            Ok(CommitEntry {
+                short_hash: row.get(0)?,
+                message: row.get(1)?,
+                committed_at: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(delta)
+}
 
+/// Get current counts of all tracked items
+fn get_current_counts(conn: &Connection) -> Result<BTreeMap<String, i64>> {
+    let queries = [
+        (
+            "active_tasks",
+            "SELECT COUNT(*) FROM tasks WHERE status NOT IN ('completed', 'cancelled')",
+        ),
+        (
+            "completed_tasks",
+            "SELECT COUNT(*) FROM tasks WHERE status = 'completed'",
+        ),
+        (
+            "blockers",
+            "SELECT COUNT(*) FROM blockers WHERE status = 'active'",
+        ),
+        (
+            "decisions",
+            "SELECT COUNT(*) FROM decisions WHERE status = 'active'",
+        ),
+        (
+            "questions",
+            "SELECT COUNT(*) FROM questions WHERE status = 'open'",
+        ),
+        (
+            "notes",
+            "SELECT COUNT(*) FROM context_notes WHERE status = 'active'",
+        ),
+    ];
+
+    let mut counts = BTreeMap::new();
+    for (key, sql) in queries {
+        let count: i64 = conn.query_row(sql, [], |row| row.get(0))?;
+        counts.insert(key.to_string(), count);
+    }
     Ok(counts)
 }
 
-/// Get the last context snapshot for comparison
-fn get_last_snapshot(
-    conn: &Connection,
-    session_id: i64,
-    snapshot_type: &str,
-) -> Result<(Option<String>, ItemCounts)> {
-    let mut stmt = conn.prepare(
-        "SELECT content_hash, item_counts FROM context_snapshots
-         WHERE session_id = ? AND snapshot_type = ?
-         ORDER BY created_at DESC LIMIT 1",
-    )?;
-
-    let result = stmt.query_row(rusqlite::params![session_id, snapshot_type], |row| {
-        let hash: String = row.get(0)?;
-        let counts_json: Option<String> = row.get(1)?;
-        Ok((hash, counts_json))
-    });
+/// Get the last delta snapshot: when it was taken and the state it saved.
+/// Snapshots from older versions only stored counts and can't be compared.
+fn get_last_snapshot(conn: &Connection) -> Result<Option<(String, State)>> {
+    let result = conn.query_row(
+        "SELECT created_at, item_counts FROM context_snapshots
+         WHERE snapshot_type = 'delta'
+         ORDER BY snapshot_id DESC LIMIT 1",
+        [],
+        |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                row.get::<_, Option<String>>(1)?,
+            ))
+        },
+    );
 
     match result {
-        Ok((hash, counts_json)) => {
-            let counts: ItemCounts = match counts_json {
-                Some(json) => serde_json::from_str(&json).unwrap_or_default(),
-                None => HashMap::new(),
-            };
-            Ok((Some(hash), counts))
-        }
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok((None, HashMap::new())),
+        Ok((created_at, Some(json))) => Ok(serde_json::from_str(&json)
+            .ok()
+            .map(|state| (created_at, state))),
+        Ok((_, None)) | Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
         Err(e) => Err(e.into()),
     }
 }
 
-/// Save a context snapshot for delta tracking
-fn save_snapshot(
-    conn: &Connection,
-    session_id: i64,
-    snapshot_type: &str,
-    content_hash: &str,
-    item_counts: &ItemCounts,
-) -> Result<()> {
-    let counts_json = serde_json::to_string(item_counts)?;
+/// Save a delta snapshot and prune old ones
+fn save_snapshot(conn: &Connection, session_id: i64, state: &State) -> Result<()> {
+    let state_json = serde_json::to_string(state)?;
+    let hash = format!("{:x}", Sha256::digest(state_json.as_bytes()));
+    conn.execute(
+        "INSERT INTO context_snapshots (session_id, snapshot_type, content_hash, item_counts) VALUES (?, 'delta', ?, ?)",
+        rusqlite::params![session_id, hash, state_json],
+    )?;
     conn.execute(
-        "INSERT INTO context_snapshots (session_id, snapshot_type, content_hash, item_counts) VALUES (?, ?, ?, ?)",
-        rusqlite::params![session_id, snapshot_type, content_hash, counts_json],
+        "DELETE FROM context_snapshots WHERE snapshot_type = 'delta' AND snapshot_id NOT IN
+         (SELECT snapshot_id FROM context_snapshots WHERE snapshot_type = 'delta'
+          ORDER BY snapshot_id DESC LIMIT ?)",
+        [SNAPSHOTS_KEPT],
     )?;
     Ok(())
 }
 
-/// Compute a content hash from item counts
-fn compute_content_hash(counts: &ItemCounts) -> String {
-    // Sort keys for deterministic hashing
-    let mut keys: Vec<_> = counts.keys().collect();
-    keys.sort();
-
-    let mut hasher = Sha256::new();
-    for key in keys {
-        hasher.update(key.as_bytes());
-        hasher.update(b":");
-        hasher.update(counts[key].to_string().as_bytes());
-        hasher.update(b";");
-    }
-
-    format!("{:x}", hasher.finalize())
-}
-
 /// Get the count of activity in a session
 fn get_session_activity_count(conn: &Connection, session_id: i64) -> Result<i64> {
     let count: i64 = conn.query_row(
@@ -271,12 +557,3 @@ fn parse_datetime(s: String) -> DateTime<Utc> {
         .map(|dt| dt.and_utc())
         .unwrap_or_else(|_| Utc::now())
 }
-
-/// Truncate a string to a maximum length
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
-    }
-}
@@ -1,28 +1,57 @@
 // Documentation database commands
 
+use std::collections::{HashMap, HashSet};
+use std::fmt::Write;
+
 use anyhow::{bail, Result};
 use colored::Colorize;
+use dialoguer::FuzzySelect;
+use rusqlite::Connection;
 
-use crate::cli::{DocsCommands, DocsSubcommand, DocsTermSubcommand};
+use crate::cli::{DocsCommands, DocsSubcommand, DocsTermSubcommand, PageArgs};
+use crate::commands::{docs_import, docs_openapi, docs_snapshot, docs_terms};
+use crate::database::{get_schema_version, open_database};
 use crate::docs_db;
-use crate::paths::get_project_root;
+use crate::error::ProjError;
+use crate::pager;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::projignore::ProjIgnore;
 use crate::schema_docs::DocType;
 
+/// Matches shown at once in the section picker
+const PICK_ROWS: usize = 15;
+
 pub fn run(cmd: DocsCommands) -> Result<()> {
     match cmd.command {
         DocsSubcommand::Init {
             generate,
             import,
             new,
+            import_openapi,
             doc_type,
             name,
             description,
-        } => cmd_init(generate, import, new, &doc_type, name, description),
+        } => match import_openapi {
+            Some(spec) => docs_openapi::init(&get_project_root()?, &spec, name),
+            None => cmd_init(generate, import, new, &doc_type, name, description),
+        },
         DocsSubcommand::Status => cmd_status(),
         DocsSubcommand::Refresh { force } => cmd_refresh(force),
+        DocsSubcommand::Watch { debounce } => crate::commands::docs_watch::run(debounce),
         DocsSubcommand::Search { query } => cmd_search(&query),
-        DocsSubcommand::Export { format, output } => cmd_export(&format, output),
-        DocsSubcommand::Show { section } => cmd_show(section),
+        DocsSubcommand::Export {
+            format,
+            output,
+            link_terms,
+            decisions,
+        } => cmd_export(&format, output, link_terms, decisions),
+        DocsSubcommand::Show { section, page } => cmd_show(section, &page),
+        DocsSubcommand::Pick { print } => cmd_pick(print),
+        DocsSubcommand::CheckLinks => cmd_check_links(),
+        DocsSubcommand::Snapshot { name, list, delete } => {
+            docs_snapshot::snapshot(name, list, delete)
+        }
+        DocsSubcommand::Diff { from, to, stat } => docs_snapshot::diff(&from, to.as_deref(), stat),
         DocsSubcommand::Term(term_cmd) => match term_cmd.command {
             DocsTermSubcommand::Add {
                 term,
@@ -31,6 +60,12 @@ pub fn run(cmd: DocsCommands) -> Result<()> {
             } => cmd_term_add(&term, &def, category.as_deref()),
             DocsTermSubcommand::List => cmd_term_list(),
             DocsTermSubcommand::Search { query } => cmd_term_search(&query),
+            DocsTermSubcommand::Extract {
+                yes,
+                dry_run,
+                limit,
+                min_count,
+            } => docs_terms::extract(yes, dry_run, limit, min_count),
         },
     }
 }
@@ -98,7 +133,7 @@ fn cmd_init(
     // Ask how they want to set up docs
     let options = &[
         "None        - Skip documentation database",
-        "Import      - Import from existing markdown files",
+        "Import      - Import from existing docs (md, rst, adoc, org)",
         "Generate    - Analyze codebase and generate documentation",
         "New Project - Create from project description",
     ];
@@ -135,7 +170,7 @@ fn cmd_init_generate_auto(
     println!(
         "{} Detected {} project ({} files, {} lines)",
         "✓".green(),
-        structure.language.as_str(),
+        structure.language_label(),
         structure.file_count,
         structure.total_lines
     );
@@ -156,7 +191,7 @@ fn cmd_init_generate_auto(
     let conn = docs_db::create_docs_db(&db_path, &project_name, doc_type)?;
 
     // Generate sections
-    let sections = crate::source_analyzer::generate_sections(&structure);
+    let sections = crate::source_analyzer::generate_sections_for(&structure, doc_type);
 
     // Insert sections
     for section in &sections {
@@ -175,7 +210,7 @@ fn cmd_init_generate_auto(
 
     // Record generation info
     crate::schema_docs::set_meta(&conn, "generated_from", "source_analysis")?;
-    crate::schema_docs::set_meta(&conn, "language", structure.language.as_str())?;
+    crate::schema_docs::set_meta(&conn, "language", &structure.language_label())?;
     crate::schema_docs::set_meta(
         &conn,
         "generated_at",
@@ -194,7 +229,7 @@ fn cmd_init_generate_auto(
     Ok(())
 }
 
-/// Import from markdown files (non-interactive)
+/// Import from existing documentation files (non-interactive)
 fn cmd_init_import_auto(
     project_root: &std::path::Path,
     doc_type: DocType,
@@ -202,42 +237,18 @@ fn cmd_init_import_auto(
 ) -> Result<()> {
     println!("{}", "Scanning for documentation files...".cyan());
 
-    // Find markdown files
-    let mut md_files: Vec<std::path::PathBuf> = Vec::new();
-
-    let check_files = [
-        "README.md",
-        "ARCHITECTURE.md",
-        "CONTRIBUTING.md",
-        "API.md",
-        "GUIDE.md",
-    ];
-
-    for file in &check_files {
-        let path = project_root.join(file);
-        if path.exists() {
-            md_files.push(path);
-        }
-    }
-
-    // Check docs/ folder
-    let docs_dir = project_root.join("docs");
-    if docs_dir.is_dir() {
-        if let Ok(entries) = std::fs::read_dir(&docs_dir) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "md") {
-                    md_files.push(path);
-                }
-            }
-        }
-    }
+    // Markdown, reStructuredText, AsciiDoc, and Org files
+    let md_files = docs_import::find_doc_files(project_root);
 
     if md_files.is_empty() {
-        bail!("No markdown files found to import.");
+        bail!("No documentation files found to import (.md, .rst, .adoc, .org).");
     }
 
-    println!("{} Found {} markdown files", "✓".green(), md_files.len());
+    println!(
+        "{} Found {} documentation files",
+        "✓".green(),
+        md_files.len()
+    );
 
     // Get project name
     let project_name = name.unwrap_or_else(|| {
@@ -301,46 +312,20 @@ fn cmd_init_import_auto(
     Ok(())
 }
 
-/// Import existing markdown files (interactive)
+/// Import existing documentation files (interactive)
 fn cmd_init_import(project_root: &std::path::Path) -> Result<()> {
     use dialoguer::{Input, MultiSelect, Select};
 
     println!("\n{}", "Scanning for documentation files...".cyan());
 
-    // Find markdown files
-    let mut md_files: Vec<std::path::PathBuf> = Vec::new();
-
-    // Check common locations
-    let check_files = [
-        "README.md",
-        "ARCHITECTURE.md",
-        "CONTRIBUTING.md",
-        "API.md",
-        "GUIDE.md",
-    ];
-
-    for file in &check_files {
-        let path = project_root.join(file);
-        if path.exists() {
-            md_files.push(path);
-        }
-    }
-
-    // Check docs/ folder
-    let docs_dir = project_root.join("docs");
-    if docs_dir.is_dir() {
-        if let Ok(entries) = std::fs::read_dir(&docs_dir) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "md") {
-                    md_files.push(path);
-                }
-            }
-        }
-    }
+    // Markdown, reStructuredText, AsciiDoc, and Org files
+    let md_files = docs_import::find_doc_files(project_root);
 
     if md_files.is_empty() {
-        println!("{} No markdown files found.", "!".yellow());
+        println!(
+            "{} No documentation files found (.md, .rst, .adoc, .org).",
+            "!".yellow()
+        );
         println!("Consider using 'Generate' or 'New Project' instead.");
         return Ok(());
     }
@@ -471,13 +456,13 @@ fn cmd_init_import(project_root: &std::path::Path) -> Result<()> {
     Ok(())
 }
 
-/// Import a markdown file into the database
-fn import_markdown_file(
+/// Import a documentation file into the database (converted to markdown first)
+pub fn import_markdown_file(
     conn: &rusqlite::Connection,
     file_path: &std::path::Path,
     project_root: &std::path::Path,
 ) -> Result<usize> {
-    let content = std::fs::read_to_string(file_path)?;
+    let content = docs_import::read_as_markdown(file_path)?;
     let relative_path = file_path
         .strip_prefix(project_root)
         .unwrap_or(file_path)
@@ -487,9 +472,18 @@ fn import_markdown_file(
     let mut sections = Vec::new();
     let mut current_section: Option<(i32, String, String)> = None; // (level, title, content)
     let mut section_counter: Vec<i32> = vec![0; 6]; // Track section numbers at each level
+    let mut in_code_block = false;
 
     for line in content.lines() {
-        if let Some(heading_level) = detect_heading(line) {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+        }
+        let heading = if in_code_block {
+            None
+        } else {
+            detect_heading(line)
+        };
+        if let Some(heading_level) = heading {
             // Save previous section if exists
             if let Some((level, title, content)) = current_section.take() {
                 sections.push((level, title, content.trim().to_string()));
@@ -498,8 +492,8 @@ fn import_markdown_file(
             // Update section counter
             section_counter[heading_level as usize - 1] += 1;
             // Reset lower level counters
-            for i in heading_level as usize..6 {
-                section_counter[i] = 0;
+            for counter in section_counter.iter_mut().skip(heading_level as usize) {
+                *counter = 0;
             }
 
             let title = line.trim_start_matches('#').trim().to_string();
@@ -516,8 +510,12 @@ fn import_markdown_file(
         sections.push((level, title, content.trim().to_string()));
     }
 
-    // Insert sections into database
-    let mut sort_order = 0;
+    // Insert sections into database, numbered after any earlier imported files
+    let mut sort_order: i32 = conn.query_row(
+        "SELECT COALESCE(MAX(sort_order), 0) FROM sections",
+        [],
+        |row| row.get(0),
+    )?;
     let mut section_ids: Vec<String> = Vec::new();
 
     for (level, title, content) in &sections {
@@ -663,7 +661,7 @@ fn cmd_init_generate(project_root: &std::path::Path) -> Result<()> {
     println!(
         "\n{} Detected {} project",
         "✓".green(),
-        structure.language.as_str()
+        structure.language_label()
     );
     println!("  {} source files", structure.file_count);
     println!("  {} lines of code", structure.total_lines);
@@ -757,7 +755,7 @@ fn cmd_init_generate(project_root: &std::path::Path) -> Result<()> {
     let conn = docs_db::create_docs_db(&db_path, &project_name, doc_type)?;
 
     // Generate sections
-    let sections = crate::source_analyzer::generate_sections(&structure);
+    let sections = crate::source_analyzer::generate_sections_for(&structure, doc_type);
 
     // Insert sections
     for section in &sections {
@@ -776,7 +774,7 @@ fn cmd_init_generate(project_root: &std::path::Path) -> Result<()> {
 
     // Record generation info
     crate::schema_docs::set_meta(&conn, "generated_from", "source_analysis")?;
-    crate::schema_docs::set_meta(&conn, "language", structure.language.as_str())?;
+    crate::schema_docs::set_meta(&conn, "language", &structure.language_label())?;
     crate::schema_docs::set_meta(
         &conn,
         "generated_at",
@@ -1097,6 +1095,23 @@ fn cmd_status() -> Result<()> {
             }
         }
 
+        // Spec-based docs are stale when the spec's content changes
+        if generated_from == docs_openapi::GENERATED_FROM {
+            let spec = docs_openapi::spec_path(&conn, &project_root)?;
+            println!(
+                "  Spec: {}",
+                spec.strip_prefix(&project_root).unwrap_or(&spec).display()
+            );
+            match docs_openapi::is_stale(&conn, &project_root)? {
+                Some(true) => {
+                    println!("\n  {} Spec changed since last update", "!".yellow());
+                    println!("  Run 'proj docs refresh' to update.");
+                }
+                Some(false) => {}
+                None => println!("\n  {} Spec file not found", "!".yellow()),
+            }
+        }
+
         // Check staleness for source-generated docs
         if generated_from == "source_analysis" {
             if let Ok(Some(refreshed)) = crate::schema_docs::get_meta(&conn, "refreshed_at") {
@@ -1107,26 +1122,14 @@ fn cmd_status() -> Result<()> {
             }
 
             // Check if source files have changed since generation
-            let last_update = crate::schema_docs::get_meta(&conn, "refreshed_at")
-                .ok()
-                .flatten()
-                .or_else(|| {
-                    crate::schema_docs::get_meta(&conn, "generated_at")
-                        .ok()
-                        .flatten()
-                });
-
-            if let Some(last_update) = last_update {
-                // Check if any source files are newer than last update
-                if let Ok(stale_count) = check_staleness(&project_root, &last_update) {
-                    if stale_count > 0 {
-                        println!(
-                            "\n  {} {} source files changed since last update",
-                            "!".yellow(),
-                            stale_count
-                        );
-                        println!("  Run 'proj docs refresh' to update.");
-                    }
+            if let Some(stale_count) = stale_source_files(&conn, &project_root) {
+                if stale_count > 0 {
+                    println!(
+                        "\n  {} {} source files changed since last update",
+                        "!".yellow(),
+                        stale_count
+                    );
+                    println!("  Run 'proj docs refresh' to update.");
                 }
             }
         }
@@ -1135,6 +1138,20 @@ fn cmd_status() -> Result<()> {
     Ok(())
 }
 
+/// How many source files changed since source-generated docs were last generated or
+/// refreshed (None when that isn't recorded)
+pub fn stale_source_files(conn: &Connection, project_root: &std::path::Path) -> Option<usize> {
+    let last_update = crate::schema_docs::get_meta(conn, "refreshed_at")
+        .ok()
+        .flatten()
+        .or_else(|| {
+            crate::schema_docs::get_meta(conn, "generated_at")
+                .ok()
+                .flatten()
+        })?;
+    check_staleness(project_root, &last_update).ok()
+}
+
 /// Check how many source files have changed since a given timestamp
 fn check_staleness(project_root: &std::path::Path, since: &str) -> Result<usize> {
     use chrono::{DateTime, NaiveDateTime, Utc};
@@ -1151,16 +1168,25 @@ fn check_staleness(project_root: &std::path::Path, since: &str) -> Result<usize>
     let since_secs = since_time.timestamp();
     let mut stale_count = 0;
 
-    // Check src directory for Rust files
+    // Check src directory for Rust files, minus anything in .projignore
+    let ignore = ProjIgnore::load(project_root);
     let src_dir = project_root.join("src");
     if src_dir.is_dir() {
-        fn check_dir(dir: &std::path::Path, since_secs: i64, count: &mut usize) {
+        fn check_dir(
+            dir: &std::path::Path,
+            ignore: &ProjIgnore,
+            since_secs: i64,
+            count: &mut usize,
+        ) {
             if let Ok(entries) = std::fs::read_dir(dir) {
                 for entry in entries.filter_map(|e| e.ok()) {
                     let path = entry.path();
+                    if ignore.is_ignored(&path, path.is_dir()) {
+                        continue;
+                    }
                     if path.is_dir() {
-                        check_dir(&path, since_secs, count);
-                    } else if path.extension().map_or(false, |e| e == "rs") {
+                        check_dir(&path, ignore, since_secs, count);
+                    } else if path.extension().is_some_and(|e| e == "rs") {
                         if let Ok(meta) = std::fs::metadata(&path) {
                             if let Ok(modified) = meta.modified() {
                                 if let Ok(duration) = modified.duration_since(std::time::UNIX_EPOCH)
@@ -1176,7 +1202,7 @@ fn check_staleness(project_root: &std::path::Path, since: &str) -> Result<usize>
             }
         }
 
-        check_dir(&src_dir, since_secs, &mut stale_count);
+        check_dir(&src_dir, &ignore, since_secs, &mut stale_count);
     }
 
     Ok(stale_count)
@@ -1188,19 +1214,24 @@ fn cmd_refresh(force: bool) -> Result<()> {
 
     let db_path = match docs_db::find_docs_db(&project_root) {
         Some(path) => path,
-        None => bail!("No documentation database found. Run 'proj docs init' first."),
+        None => return Err(ProjError::DocsDbMissing.into()),
     };
 
     let conn = docs_db::open_docs_db(&db_path)?;
 
     // Check if this was generated from source analysis
     let generated_from = crate::schema_docs::get_meta(&conn, "generated_from")?;
+    if generated_from.as_deref() == Some(docs_openapi::GENERATED_FROM) {
+        return docs_openapi::refresh(&conn, &project_root, force);
+    }
     if generated_from.as_deref() != Some("source_analysis") {
         println!(
-            "{} This database was not generated from source analysis.",
+            "{} This database was not generated from source analysis or an OpenAPI spec.",
             "!".yellow()
         );
-        println!("Refresh only works for databases created with 'proj docs init --generate'.");
+        println!(
+            "Refresh only works for databases created with 'proj docs init --generate' or '--import-openapi'."
+        );
         return Ok(());
     }
 
@@ -1224,7 +1255,7 @@ fn cmd_refresh(force: bool) -> Result<()> {
     println!(
         "{} Detected {} changes ({} files, {} lines)",
         "✓".green(),
-        structure.language.as_str(),
+        structure.language_label(),
         structure.file_count,
         structure.total_lines
     );
@@ -1240,8 +1271,11 @@ fn cmd_refresh(force: bool) -> Result<()> {
 
     println!("  Removed {} old sections", deleted);
 
-    // Generate new sections
-    let sections = crate::source_analyzer::generate_sections(&structure);
+    // Generate new sections (API databases get the API reference layout)
+    let doc_type = crate::schema_docs::get_meta(&conn, "doc_type")?
+        .and_then(|t| DocType::from_str(&t))
+        .unwrap_or(DocType::Architecture);
+    let sections = crate::source_analyzer::generate_sections_for(&structure, doc_type);
 
     // Insert new sections
     for section in &sections {
@@ -1278,7 +1312,7 @@ fn cmd_search(query: &str) -> Result<()> {
 
     let db_path = match docs_db::find_docs_db(&project_root) {
         Some(path) => path,
-        None => bail!("No documentation database found. Run 'proj docs init' first."),
+        None => return Err(ProjError::DocsDbMissing.into()),
     };
 
     let conn = docs_db::open_docs_db(&db_path)?;
@@ -1317,25 +1351,58 @@ fn cmd_search(query: &str) -> Result<()> {
 }
 
 /// Export documentation
-fn cmd_export(format: &str, output: Option<String>) -> Result<()> {
+fn cmd_export(
+    format: &str,
+    output: Option<String>,
+    link_terms: bool,
+    with_decisions: bool,
+) -> Result<()> {
     let project_root = get_project_root()?;
 
     let db_path = match docs_db::find_docs_db(&project_root) {
         Some(path) => path,
-        None => bail!("No documentation database found. Run 'proj docs init' first."),
+        None => return Err(ProjError::DocsDbMissing.into()),
     };
 
     let conn = docs_db::open_docs_db(&db_path)?;
     let sections = docs_db::get_all_sections(&conn)?;
+    let terms = if link_terms {
+        docs_db::get_all_terms(&conn)?
+    } else {
+        Vec::new()
+    };
+    if link_terms && terms.is_empty() {
+        eprintln!(
+            "{} No glossary terms to link - add some with 'proj docs term add' or 'proj docs term extract'",
+            "!".yellow()
+        );
+    }
+    let decisions = if with_decisions {
+        let tracking = open_database(&get_tracking_db_path()?)?;
+        related_decisions(&tracking)?
+    } else {
+        HashMap::new()
+    };
 
+    let mut dangling = 0;
     let content = match format {
-        "md" | "markdown" => export_markdown(&sections),
+        "md" | "markdown" => export_markdown(&sections, &terms, &decisions, &mut dangling),
         "html" => {
-            println!("{} HTML export not yet implemented.", "!".yellow());
-            return Ok(());
+            let title = docs_db::get_docs_info(&conn)
+                .map(|info| info.project_name)
+                .unwrap_or_else(|_| "Documentation".to_string());
+            export_html(&title, &sections, &terms, &decisions, &mut dangling)
         }
         _ => bail!("Unknown format: {}. Use 'md' or 'html'.", format),
     };
+    if dangling > 0 {
+        eprintln!(
+            "{} {} cross-reference{} point to missing sections - run 'proj docs check-links'",
+            "⚠".yellow(),
+            dangling,
+            if dangling == 1 { "" } else { "s" }
+        );
+    }
 
     match output {
         Some(path) => {
@@ -1351,35 +1418,315 @@ fn cmd_export(format: &str, output: Option<String>) -> Result<()> {
 }
 
 /// Export sections to markdown
-fn export_markdown(sections: &[docs_db::Section]) -> String {
+pub fn export_markdown(
+    sections: &[docs_db::Section],
+    terms: &[docs_db::TermEntry],
+    decisions: &HashMap<String, Vec<RelatedDecision>>,
+    dangling: &mut usize,
+) -> String {
     let mut output = String::new();
 
+    // Only referenced sections get an anchor, so plain exports stay unchanged
+    let referenced: HashSet<String> = sections
+        .iter()
+        .flat_map(|s| docs_db::find_crossrefs(&s.content))
+        .map(|r| r.target)
+        .collect();
+
     for section in sections {
+        if referenced.contains(&section.section_id) {
+            output.push_str(&format!(
+                "<a id=\"{}\"></a>\n\n",
+                docs_db::section_anchor(&section.section_id)
+            ));
+        }
+
         // Add heading
         let hashes = "#".repeat(section.level as usize);
         output.push_str(&format!("{} {}\n\n", hashes, section.title));
 
         // Add content
         if !section.content.is_empty() {
-            output.push_str(&section.content);
+            output.push_str(&section_markdown(section, sections, terms, dangling));
             output.push_str("\n\n");
         }
+        if let Some(related) = decisions.get(&section.section_id) {
+            output.push_str(&related_markdown(related));
+            output.push('\n');
+        }
+    }
+
+    if !terms.is_empty() {
+        output.push_str(&docs_terms::glossary_markdown(terms));
     }
 
     output
 }
 
+/// Export sections to a standalone HTML page
+fn export_html(
+    title: &str,
+    sections: &[docs_db::Section],
+    terms: &[docs_db::TermEntry],
+    decisions: &HashMap<String, Vec<RelatedDecision>>,
+    dangling: &mut usize,
+) -> String {
+    use pulldown_cmark::{html, Options, Parser};
+
+    let mut body = String::new();
+    for section in sections {
+        let level = section.level.clamp(1, 6);
+        body.push_str(&format!(
+            "<h{0} id=\"{1}\">{2}</h{0}>\n",
+            level,
+            docs_db::section_anchor(&section.section_id),
+            escape_html(&section.title)
+        ));
+        if !section.content.is_empty() {
+            let content = section_markdown(section, sections, terms, dangling);
+            html::push_html(&mut body, Parser::new_ext(&content, Options::all()));
+        }
+        if let Some(related) = decisions.get(&section.section_id) {
+            let content = related_markdown(related);
+            html::push_html(&mut body, Parser::new_ext(&content, Options::all()));
+        }
+    }
+    if !terms.is_empty() {
+        let glossary = docs_terms::glossary_markdown(terms);
+        html::push_html(&mut body, Parser::new_ext(&glossary, Options::all()));
+    }
+
+    format!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{}</title>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_html(title),
+        body
+    )
+}
+
+/// Section content with cross-references (and glossary terms, if any) linked
+fn section_markdown(
+    section: &docs_db::Section,
+    sections: &[docs_db::Section],
+    terms: &[docs_db::TermEntry],
+    dangling: &mut usize,
+) -> String {
+    let content = link_crossrefs(&section.content, sections, dangling);
+    if terms.is_empty() {
+        content
+    } else {
+        docs_terms::link_terms(&content, terms)
+    }
+}
+
+/// Turn `[[section:ID]]` references into markdown links to the section's anchor.
+/// References to missing sections become plain text and are counted.
+fn link_crossrefs(content: &str, sections: &[docs_db::Section], dangling: &mut usize) -> String {
+    let missing = std::cell::Cell::new(0);
+    let linked = docs_db::replace_crossrefs(content, |r| {
+        match sections.iter().find(|s| s.section_id == r.target) {
+            Some(target) => format!(
+                "[{}](#{})",
+                r.label.as_deref().unwrap_or(&target.title),
+                docs_db::section_anchor(&target.section_id)
+            ),
+            None => {
+                missing.set(missing.get() + 1);
+                r.label
+                    .clone()
+                    .unwrap_or_else(|| format!("section {}", r.target))
+            }
+        }
+    });
+    *dangling += missing.get();
+    linked
+}
+
+/// A decision linked to a docs section (`proj log decision --section`)
+pub struct RelatedDecision {
+    decision_id: i64,
+    topic: String,
+    decision: String,
+    rationale: Option<String>,
+    status: String,
+    created_at: String,
+}
+
+/// Decisions linked to docs sections, by section ID, oldest first
+fn related_decisions(tracking: &Connection) -> Result<HashMap<String, Vec<RelatedDecision>>> {
+    let exists: bool = tracking.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='decision_sections')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(tracking)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.21".to_string(),
+        }
+        .into());
+    }
+    let mut stmt = tracking.prepare(
+        "SELECT l.section_id, d.decision_id, d.topic, d.decision, d.rationale, d.status, d.created_at
+         FROM decision_sections l JOIN decisions d ON d.decision_id = l.decision_id
+         ORDER BY d.created_at, d.decision_id",
+    )?;
+    let mut related: HashMap<String, Vec<RelatedDecision>> = HashMap::new();
+    for row in stmt.query_map([], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            RelatedDecision {
+                decision_id: row.get(1)?,
+                topic: row.get(2)?,
+                decision: row.get(3)?,
+                rationale: row.get(4)?,
+                status: row.get(5)?,
+                created_at: row.get(6)?,
+            },
+        ))
+    })? {
+        let (section_id, decision) = row?;
+        related.entry(section_id).or_default().push(decision);
+    }
+    Ok(related)
+}
+
+/// The "Related decisions" list exported after a section
+fn related_markdown(decisions: &[RelatedDecision]) -> String {
+    let mut out = String::from("**Related decisions**\n\n");
+    for d in decisions {
+        let date = d.created_at.split(' ').next().unwrap_or(&d.created_at);
+        let status = if d.status == "active" {
+            String::new()
+        } else {
+            format!(", {}", d.status)
+        };
+        out.push_str(&format!(
+            "- **{}** ({}, {}{})",
+            d.decision, d.topic, date, status
+        ));
+        if let Some(rationale) = d.rationale.as_deref().filter(|r| !r.trim().is_empty()) {
+            out.push_str(&format!(": {}", rationale.trim()));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// The docs sections with these IDs, as (ID, title). Fails if there's no docs
+/// database or one of them isn't in it.
+pub fn find_sections(section_ids: &[String]) -> Result<Vec<(String, String)>> {
+    if section_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let project_root = get_project_root()?;
+    let db_path = docs_db::find_docs_db(&project_root).ok_or(ProjError::DocsDbMissing)?;
+    let sections = docs_db::get_all_sections(&docs_db::open_docs_db(&db_path)?)?;
+    section_ids
+        .iter()
+        .map(|id| {
+            let id = id.trim();
+            sections
+                .iter()
+                .find(|s| s.section_id == id)
+                .map(|s| (s.section_id.clone(), s.title.clone()))
+                .ok_or_else(|| {
+                    ProjError::InvalidInput(format!(
+                        "Docs section '{}' not found. See 'proj docs show'.",
+                        id
+                    ))
+                    .into()
+                })
+        })
+        .collect()
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Check cross-references and generated sections' source files
+fn cmd_check_links() -> Result<()> {
+    let project_root = get_project_root()?;
+
+    let db_path = match docs_db::find_docs_db(&project_root) {
+        Some(path) => path,
+        None => return Err(ProjError::DocsDbMissing.into()),
+    };
+
+    let conn = docs_db::open_docs_db(&db_path)?;
+    let sections = docs_db::get_all_sections(&conn)?;
+
+    let mut checked = 0;
+    let mut problems = 0;
+    for section in &sections {
+        for crossref in docs_db::find_crossrefs(&section.content) {
+            checked += 1;
+            if sections.iter().any(|s| s.section_id == crossref.target) {
+                continue;
+            }
+            problems += 1;
+
+            // A label that still matches a title usually means the section was renumbered
+            let renamed = crossref.label.as_deref().and_then(|label| {
+                sections
+                    .iter()
+                    .find(|s| s.title.eq_ignore_ascii_case(label))
+            });
+            let hint = match renamed {
+                Some(target) => format!(" (now {}?)", target.section_id),
+                None => String::new(),
+            };
+            println!(
+                "  {} {} {}: [[section:{}]] points to a missing section{}",
+                "✗".red(),
+                section.section_id.cyan(),
+                section.title,
+                crossref.target,
+                hint
+            );
+        }
+
+        if let Some(source) = &section.source_file {
+            checked += 1;
+            let path = project_root.join(source);
+            if !path.exists() {
+                problems += 1;
+                println!(
+                    "  {} {} {}: source file {} no longer exists",
+                    "✗".red(),
+                    section.section_id.cyan(),
+                    section.title,
+                    path.strip_prefix(&project_root).unwrap_or(&path).display()
+                );
+            }
+        }
+    }
+
+    if problems > 0 {
+        println!();
+        println!("Fix the references, or run 'proj docs refresh' for generated sections.");
+        bail!("{} of {} link(s) broken", problems, checked);
+    }
+
+    println!("{} All {} link(s) OK", "✓".green(), checked);
+    Ok(())
+}
+
 /// Show a section
-fn cmd_show(section_id: Option<String>) -> Result<()> {
+fn cmd_show(section_id: Option<String>, page: &PageArgs) -> Result<()> {
     let project_root = get_project_root()?;
 
     let db_path = match docs_db::find_docs_db(&project_root) {
         Some(path) => path,
-        None => bail!("No documentation database found. Run 'proj docs init' first."),
+        None => return Err(ProjError::DocsDbMissing.into()),
     };
 
     let conn = docs_db::open_docs_db(&db_path)?;
 
+    let mut out = String::new();
     match section_id {
         Some(id) => {
             // Show specific section
@@ -1390,35 +1737,142 @@ fn cmd_show(section_id: Option<String>) -> Result<()> {
                 .ok_or_else(|| anyhow::anyhow!("Section '{}' not found", id))?;
 
             let hashes = "#".repeat(section.level as usize);
-            println!("{} {}", hashes, section.title.bold());
+            writeln!(out, "{} {}", hashes, section.title.bold())?;
             if !section.content.is_empty() {
-                println!("\n{}", section.content);
+                writeln!(out, "\n{}", section.content)?;
+            }
+
+            // Projects without a tracking database, or one from before links, have none
+            let related = get_tracking_db_path()
+                .and_then(|path| open_database(&path))
+                .and_then(|tracking| related_decisions(&tracking))
+                .ok()
+                .and_then(|mut related| related.remove(&id))
+                .unwrap_or_default();
+            if !related.is_empty() {
+                writeln!(out, "\n{}", "Related decisions:".bold())?;
+                for d in related {
+                    let date = d.created_at.split(' ').next().unwrap_or(&d.created_at);
+                    let status = if d.status == "active" {
+                        String::new()
+                    } else {
+                        format!(" ({})", d.status)
+                    };
+                    writeln!(
+                        out,
+                        "  #{:<4} {} {}{} {}",
+                        d.decision_id,
+                        format!("[{}]", d.topic).cyan(),
+                        d.decision,
+                        status.yellow(),
+                        date.dimmed()
+                    )?;
+                }
             }
         }
         None => {
             // Show table of contents
             let sections = docs_db::get_all_sections(&conn)?;
+            let total = sections.len();
 
-            println!("\n{}", "Table of Contents".bold());
-            println!("{}\n", "─".repeat(40));
+            writeln!(out, "\n{}", "Table of Contents".bold())?;
+            writeln!(out, "{}\n", "─".repeat(40))?;
 
-            for section in sections {
+            let shown: Vec<_> = sections
+                .into_iter()
+                .skip(page.offset)
+                .take(page.limit.unwrap_or(usize::MAX))
+                .collect();
+            for section in &shown {
                 let indent = "  ".repeat((section.level - 1) as usize);
-                println!("{}{} {}", indent, section.section_id.cyan(), section.title);
+                writeln!(
+                    out,
+                    "{}{} {}",
+                    indent,
+                    section.section_id.cyan(),
+                    section.title
+                )?;
+            }
+            if shown.is_empty() && total > 0 {
+                writeln!(out, "No sections past the first {} (--offset).", total)?;
+            } else if shown.len() < total {
+                writeln!(
+                    out,
+                    "{}",
+                    format!(
+                        "\nShowing {}-{} of {} sections. Use --limit and --offset for more.",
+                        page.offset + 1,
+                        page.offset + shown.len(),
+                        total
+                    )
+                    .dimmed()
+                )?;
             }
         }
     }
 
+    pager::page(&out);
     Ok(())
 }
 
+/// Choose a section with a fuzzy search over IDs and titles, then show it
+fn cmd_pick(print: bool) -> Result<()> {
+    if !atty::is(atty::Stream::Stdin) || !atty::is(atty::Stream::Stderr) {
+        return Err(ProjError::InvalidInput(
+            "proj docs pick needs a terminal. Use 'proj docs show' for the table of contents."
+                .to_string(),
+        )
+        .into());
+    }
+    let project_root = get_project_root()?;
+    let db_path = match docs_db::find_docs_db(&project_root) {
+        Some(path) => path,
+        None => return Err(ProjError::DocsDbMissing.into()),
+    };
+    let conn = docs_db::open_docs_db(&db_path)?;
+    let sections = docs_db::get_all_sections(&conn)?;
+    if sections.is_empty() {
+        return Err(ProjError::NothingToDo("No documentation sections.".to_string()).into());
+    }
+
+    let items: Vec<String> = sections
+        .iter()
+        .map(|s| {
+            let indent = "  ".repeat((s.level - 1).max(0) as usize);
+            format!("{}{} {}", indent, s.section_id, s.title)
+        })
+        .collect();
+    let Some(index) = FuzzySelect::new()
+        .with_prompt("Section (type to filter)")
+        .items(&items)
+        .default(0)
+        .max_length(PICK_ROWS)
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    let section_id = sections[index].section_id.clone();
+
+    if print {
+        println!("{}", section_id);
+        return Ok(());
+    }
+    cmd_show(
+        Some(section_id),
+        &PageArgs {
+            limit: None,
+            offset: 0,
+        },
+    )
+}
+
 /// Add a term to the glossary
 fn cmd_term_add(term: &str, definition: &str, category: Option<&str>) -> Result<()> {
     let project_root = get_project_root()?;
 
     let db_path = match docs_db::find_docs_db(&project_root) {
         Some(path) => path,
-        None => bail!("No documentation database found. Run 'proj docs init' first."),
+        None => return Err(ProjError::DocsDbMissing.into()),
     };
 
     let conn = docs_db::open_docs_db(&db_path)?;
@@ -1434,7 +1888,7 @@ fn cmd_term_list() -> Result<()> {
 
     let db_path = match docs_db::find_docs_db(&project_root) {
         Some(path) => path,
-        None => bail!("No documentation database found. Run 'proj docs init' first."),
+        None => return Err(ProjError::DocsDbMissing.into()),
     };
 
     let conn = docs_db::open_docs_db(&db_path)?;
@@ -1469,7 +1923,7 @@ fn cmd_term_search(query: &str) -> Result<()> {
 
     let db_path = match docs_db::find_docs_db(&project_root) {
         Some(path) => path,
-        None => bail!("No documentation database found. Run 'proj docs init' first."),
+        None => return Err(ProjError::DocsDbMissing.into()),
     };
 
     let conn = docs_db::open_docs_db(&db_path)?;
@@ -0,0 +1,731 @@
+// Docs import formats - finds importable files and converts them to markdown
+//
+// Markdown is imported as is. reStructuredText and Org go through pandoc
+// when it's installed; otherwise (and always for AsciiDoc, which pandoc
+// can't read) a built-in converter handles headings, code blocks, links,
+// and common inline markup.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+
+use crate::diagnostics;
+
+/// Documents checked for in the project root, with any importable extension
+const ROOT_DOCS: &[&str] = &["README", "ARCHITECTURE", "CONTRIBUTING", "API", "GUIDE"];
+
+/// Documentation folders searched (recursively) for importable files
+const DOC_DIRS: &[&str] = &["docs", "doc"];
+
+/// Build output and assets in documentation folders (Sphinx, Antora)
+const SKIP_DIRS: &[&str] = &["_build", "_static", "_templates", "build", "node_modules"];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Markdown,
+    Rst,
+    AsciiDoc,
+    Org,
+}
+
+impl Format {
+    fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "md" | "markdown" => Some(Format::Markdown),
+            "rst" => Some(Format::Rst),
+            "adoc" | "asciidoc" | "asc" => Some(Format::AsciiDoc),
+            "org" => Some(Format::Org),
+            _ => None,
+        }
+    }
+
+    /// pandoc reader name, for formats pandoc can read
+    fn pandoc_reader(&self) -> Option<&'static str> {
+        match self {
+            Format::Rst => Some("rst"),
+            Format::Org => Some("org"),
+            Format::Markdown | Format::AsciiDoc => None,
+        }
+    }
+}
+
+/// Markdown, reStructuredText, AsciiDoc, and Org files in the usual places
+pub fn find_doc_files(project_root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+
+    for name in ROOT_DOCS {
+        for ext in ["md", "rst", "adoc", "org"] {
+            let path = project_root.join(format!("{}.{}", name, ext));
+            if path.exists() {
+                files.push(path);
+            }
+        }
+    }
+
+    for dir in DOC_DIRS {
+        let mut found = Vec::new();
+        walk(&project_root.join(dir), &mut found, 0);
+        found.sort();
+        files.extend(found);
+    }
+
+    files
+}
+
+fn walk(dir: &Path, files: &mut Vec<PathBuf>, depth: usize) {
+    if depth > 4 {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.filter_map(|e| e.ok()) {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            if !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_str()) {
+                walk(&path, files, depth + 1);
+            }
+        } else if Format::from_path(&path).is_some() {
+            files.push(path);
+        }
+    }
+}
+
+/// Read a documentation file as markdown
+pub fn read_as_markdown(path: &Path) -> Result<String> {
+    let content =
+        std::fs::read_to_string(path).with_context(|| format!("Failed to read {:?}", path))?;
+    let format = Format::from_path(path).unwrap_or(Format::Markdown);
+
+    if let Some(reader) = format.pandoc_reader() {
+        if let Some(markdown) = pandoc(path, reader) {
+            return Ok(markdown);
+        }
+    }
+
+    Ok(match format {
+        Format::Markdown => content,
+        Format::Rst => rst_to_markdown(&content),
+        Format::AsciiDoc => asciidoc_to_markdown(&content),
+        Format::Org => org_to_markdown(&content),
+    })
+}
+
+/// Convert with pandoc, or None if it isn't installed or fails
+fn pandoc(path: &Path, reader: &str) -> Option<String> {
+    let output = diagnostics::output(
+        Command::new("pandoc")
+            .args(["--from", reader, "--to", "gfm", "--wrap=none"])
+            .arg(path),
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+// ---------------------------------------------------------------------------
+// reStructuredText
+
+/// Heading underline/overline characters
+const RST_ADORNMENTS: &str = "=-~^\"'`#*+:._";
+
+fn is_rst_adornment(line: &str) -> Option<char> {
+    let line = line.trim_end();
+    let first = line.chars().next()?;
+    (line.len() >= 2 && RST_ADORNMENTS.contains(first) && line.chars().all(|c| c == first))
+        .then_some(first)
+}
+
+fn rst_to_markdown(content: &str) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut output = Vec::new();
+    // Heading styles in order of first use; rst levels are positional
+    let mut styles: Vec<(char, bool)> = Vec::new();
+    let mut level_of = |style: (char, bool)| -> usize {
+        match styles.iter().position(|s| *s == style) {
+            Some(index) => index + 1,
+            None => {
+                styles.push(style);
+                styles.len()
+            }
+        }
+    };
+
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+
+        // Overlined heading: ===== / Title / =====
+        if let (Some(over), Some(title), Some(under)) = (
+            is_rst_adornment(line),
+            lines.get(i + 1),
+            lines.get(i + 2).and_then(|l| is_rst_adornment(l)),
+        ) {
+            if over == under && !title.trim().is_empty() && is_rst_adornment(title).is_none() {
+                let level = level_of((over, true)).min(6);
+                output.push(format!("{} {}", "#".repeat(level), title.trim()));
+                i += 3;
+                continue;
+            }
+        }
+
+        // Underlined heading: Title / -----
+        if let Some(under) = lines.get(i + 1).and_then(|l| is_rst_adornment(l)) {
+            let title = line.trim();
+            if !title.is_empty()
+                && !line.starts_with(' ')
+                && is_rst_adornment(line).is_none()
+                && lines[i + 1].trim_end().chars().count() >= title.chars().count().min(3)
+            {
+                let level = level_of((under, false)).min(6);
+                output.push(format!("{} {}", "#".repeat(level), title));
+                i += 2;
+                continue;
+            }
+        }
+
+        // Transition
+        if is_rst_adornment(line).is_some() && line.trim().len() >= 4 {
+            output.push("---".to_string());
+            i += 1;
+            continue;
+        }
+
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+
+        if let Some(directive) = trimmed.strip_prefix(".. ") {
+            let (name, argument) = match directive.split_once("::") {
+                Some((name, argument)) => (name.trim().to_lowercase(), argument.trim()),
+                None => (String::new(), ""),
+            };
+            let (body, next) = indented_block(&lines, i + 1, indent);
+            match name.as_str() {
+                "code-block" | "code" | "sourcecode" => {
+                    output.push(format!("```{}", argument));
+                    output.extend(
+                        body.iter()
+                            .filter(|l| !l.trim_start().starts_with(':'))
+                            .map(|l| l.to_string()),
+                    );
+                    output.push("```".to_string());
+                }
+                "note" | "warning" | "tip" | "important" | "caution" | "attention" | "hint"
+                | "danger" | "seealso" => {
+                    let mut label = name.clone();
+                    label[..1].make_ascii_uppercase();
+                    let first = if argument.is_empty() {
+                        String::new()
+                    } else {
+                        format!(" {}", rst_inline(argument))
+                    };
+                    output.push(format!("> **{}:**{}", label, first));
+                    for line in body.iter().filter(|l| !l.trim().is_empty()) {
+                        output.push(format!("> {}", rst_inline(line.trim())));
+                    }
+                }
+                "image" | "figure" => output.push(format!("![]({})", argument)),
+                // toctree, automodule, comments, link targets: nothing to show
+                _ => {}
+            }
+            output.push(String::new());
+            i = next;
+            continue;
+        }
+
+        // Literal block introduced by "::"
+        if trimmed.ends_with("::") {
+            let text = trimmed.trim_end_matches("::").trim_end();
+            if !text.is_empty() {
+                output.push(format!("{}{}:", " ".repeat(indent), rst_inline(text)));
+            }
+            let (body, next) = indented_block(&lines, i + 1, indent);
+            output.push("```".to_string());
+            output.extend(body.into_iter().map(str::to_string));
+            output.push("```".to_string());
+            output.push(String::new());
+            i = next;
+            continue;
+        }
+
+        // Enumerated lists: "#." becomes "1."
+        let line = match trimmed.strip_prefix("#. ") {
+            Some(rest) => format!("{}1. {}", " ".repeat(indent), rest),
+            None => line.to_string(),
+        };
+        output.push(rst_inline(&line));
+        i += 1;
+    }
+
+    output.join("\n")
+}
+
+/// Lines indented deeper than `indent`, starting at `start` (leading blank
+/// lines skipped, common indentation removed), and the index after them
+fn indented_block<'a>(lines: &[&'a str], start: usize, indent: usize) -> (Vec<&'a str>, usize) {
+    let mut end = start;
+    while end < lines.len() {
+        let line = lines[end];
+        if !line.trim().is_empty() && line.len() - line.trim_start().len() <= indent {
+            break;
+        }
+        end += 1;
+    }
+    let mut block: Vec<&str> = lines[start..end].to_vec();
+    while block.first().is_some_and(|l| l.trim().is_empty()) {
+        block.remove(0);
+    }
+    while block.last().is_some_and(|l| l.trim().is_empty()) {
+        block.pop();
+    }
+    let common = block
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let block = block
+        .into_iter()
+        .map(|l| l.get(common..).unwrap_or(""))
+        .collect();
+    (block, end)
+}
+
+/// ``code``, `text <url>`_, :role:`text`, and `text`_
+fn rst_inline(line: &str) -> String {
+    let mut output = String::new();
+    let mut rest = line;
+    while let Some(start) = rest.find('`') {
+        let (before, after) = rest.split_at(start);
+
+        // ``literal``
+        if let Some(body) = after.strip_prefix("``") {
+            if let Some(end) = body.find("``") {
+                output.push_str(before);
+                output.push_str(&format!("`{}`", &body[..end]));
+                rest = &body[end + 2..];
+                continue;
+            }
+        }
+
+        let Some(end) = after[1..].find('`') else {
+            break;
+        };
+        let text = &after[1..end + 1];
+        let mut tail = &after[end + 2..];
+
+        // :role:`text` - drop the role, keep the text (or the title of "Title <target>")
+        let mut before = before;
+        if before.ends_with(':') {
+            if let Some(role_start) = before[..before.len() - 1].rfind(':') {
+                let role = &before[role_start + 1..before.len() - 1];
+                if !role.is_empty()
+                    && role
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == ':' || c == '-')
+                {
+                    before = &before[..role_start];
+                }
+            }
+        }
+        output.push_str(before);
+
+        let (label, target) = match (text.rfind('<'), text.ends_with('>')) {
+            (Some(open), true) => (text[..open].trim(), Some(&text[open + 1..text.len() - 1])),
+            _ => (text, None),
+        };
+        let is_reference = tail.starts_with('_');
+        tail = tail.trim_start_matches('_');
+        match target {
+            Some(url) if is_reference && url.contains("://") => {
+                output.push_str(&format!("[{}]({})", label, url))
+            }
+            Some(_) => output.push_str(label),
+            None if is_reference => output.push_str(label),
+            None => output.push_str(&format!("`{}`", label)),
+        }
+        rest = tail;
+    }
+    output.push_str(rest);
+    output
+}
+
+// ---------------------------------------------------------------------------
+// AsciiDoc
+
+fn asciidoc_to_markdown(content: &str) -> String {
+    let mut output = Vec::new();
+    let mut block_attribute: Option<String> = None;
+    // Closing delimiter and whether lines are quoted (vs. fenced)
+    let mut open_block: Option<(&str, bool)> = None;
+    let mut in_comment = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim_end();
+
+        if trimmed == "////" {
+            in_comment = !in_comment;
+            continue;
+        }
+        if in_comment {
+            continue;
+        }
+
+        if let Some((delimiter, quoted)) = open_block {
+            if trimmed == delimiter {
+                if !quoted {
+                    output.push("```".to_string());
+                }
+                open_block = None;
+            } else if quoted {
+                output.push(format!("> {}", asciidoc_inline(line)));
+            } else {
+                output.push(line.to_string());
+            }
+            continue;
+        }
+
+        match trimmed {
+            "----" | "...." => {
+                let lang = block_attribute
+                    .take()
+                    .and_then(|a| {
+                        let mut parts = a.split(',');
+                        (parts.next() == Some("source")).then(|| parts.next().map(str::to_string))
+                    })
+                    .flatten()
+                    .unwrap_or_default();
+                output.push(format!("```{}", lang));
+                open_block = Some((if trimmed == "----" { "----" } else { "...." }, false));
+                continue;
+            }
+            "____" | "====" | "****" => {
+                if let Some(label) = block_attribute.take().filter(|a| is_admonition(a)) {
+                    output.push(format!("> **{}:**", title_case(&label)));
+                }
+                open_block = Some((
+                    match trimmed {
+                        "____" => "____",
+                        "====" => "====",
+                        _ => "****",
+                    },
+                    true,
+                ));
+                continue;
+            }
+            _ => {}
+        }
+
+        // [source,rust], [NOTE], [[anchor]]
+        if trimmed.starts_with('[') && trimmed.ends_with(']') && !trimmed.contains(' ') {
+            if !trimmed.starts_with("[[") {
+                block_attribute = Some(trimmed[1..trimmed.len() - 1].to_string());
+            }
+            continue;
+        }
+        block_attribute = None;
+
+        // Document attributes, comments, and includes
+        let is_attribute = trimmed
+            .strip_prefix(':')
+            .and_then(|rest| rest.split_once(':'))
+            .is_some_and(|(name, value)| {
+                !name.is_empty()
+                    && name
+                        .chars()
+                        .all(|c| c.is_alphanumeric() || c == '-' || c == '!')
+                    && (value.is_empty() || value.starts_with(' '))
+            });
+        if is_attribute || trimmed.starts_with("//") || trimmed.starts_with("include::") {
+            continue;
+        }
+
+        // = Title, == Section, ...
+        let equals = trimmed.chars().take_while(|&c| c == '=').count();
+        if equals > 0 && trimmed[equals..].starts_with(' ') {
+            output.push(format!(
+                "{} {}",
+                "#".repeat(equals.min(6)),
+                asciidoc_inline(trimmed[equals..].trim())
+            ));
+            continue;
+        }
+
+        // .Block title
+        if let Some(title) = trimmed.strip_prefix('.') {
+            if title.starts_with(|c: char| c.is_alphanumeric()) {
+                output.push(format!("**{}**", asciidoc_inline(title)));
+                continue;
+            }
+        }
+
+        // NOTE: paragraph
+        if let Some((label, text)) = trimmed.split_once(": ") {
+            if is_admonition(label) {
+                output.push(format!(
+                    "> **{}:** {}",
+                    title_case(label),
+                    asciidoc_inline(text)
+                ));
+                continue;
+            }
+        }
+
+        // Nested list markers: "** item" / ".. item"
+        let stars = trimmed.chars().take_while(|&c| c == '*').count();
+        let dots = trimmed.chars().take_while(|&c| c == '.').count();
+        let line = if stars > 0 && trimmed[stars..].starts_with(' ') {
+            format!("{}- {}", "  ".repeat(stars - 1), &trimmed[stars + 1..])
+        } else if dots > 0 && trimmed[dots..].starts_with(' ') {
+            format!("{}1. {}", "   ".repeat(dots - 1), &trimmed[dots + 1..])
+        } else {
+            line.to_string()
+        };
+        output.push(asciidoc_inline(&line));
+    }
+
+    if open_block.is_some_and(|(_, quoted)| !quoted) {
+        output.push("```".to_string());
+    }
+    output.join("\n")
+}
+
+fn is_admonition(label: &str) -> bool {
+    matches!(label, "NOTE" | "TIP" | "IMPORTANT" | "WARNING" | "CAUTION")
+}
+
+fn title_case(word: &str) -> String {
+    let lower = word.to_lowercase();
+    let mut chars = lower.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// *bold*, link:url[text], https://url[text], <<anchor,text>>, xref:file[text]
+fn asciidoc_inline(line: &str) -> String {
+    let mut line = emphasis(line, '*', "**");
+
+    // <<anchor,text>> and <<anchor>>
+    while let Some(start) = line.find("<<") {
+        let Some(len) = line[start..].find(">>") else {
+            break;
+        };
+        let inner = &line[start + 2..start + len];
+        let text = inner
+            .split_once(',')
+            .map_or(inner, |(_, text)| text)
+            .trim()
+            .to_string();
+        line.replace_range(start..start + len + 2, &text);
+    }
+
+    // macro:target[text] for links and cross-references
+    let mut output = String::new();
+    let mut rest = line.as_str();
+    while let Some(open) = rest.find('[') {
+        let Some(close) = rest[open..].find(']').map(|c| open + c) else {
+            break;
+        };
+        let target_start = rest[..open]
+            .rfind(|c: char| c.is_whitespace() || c == '(')
+            .map_or(0, |i| i + 1);
+        let target = &rest[target_start..open];
+        let text = &rest[open + 1..close];
+
+        let link = if let Some(url) = target.strip_prefix("link:") {
+            Some(format!("[{}]({})", text, url))
+        } else if target.starts_with("xref:") {
+            Some(text.to_string())
+        } else if target.contains("://") || target.starts_with("mailto:") {
+            Some(format!(
+                "[{}]({})",
+                if text.is_empty() { target } else { text },
+                target
+            ))
+        } else {
+            None
+        };
+        match link {
+            Some(link) => {
+                output.push_str(&rest[..target_start]);
+                output.push_str(&link);
+            }
+            None => output.push_str(&rest[..=close]),
+        }
+        rest = &rest[close + 1..];
+    }
+    output.push_str(rest);
+    output
+}
+
+// ---------------------------------------------------------------------------
+// Org
+
+fn org_to_markdown(content: &str) -> String {
+    let mut output = Vec::new();
+    let has_title = content
+        .lines()
+        .any(|l| l.to_uppercase().starts_with("#+TITLE:"));
+    let mut in_block: Option<bool> = None; // Some(quoted)
+    let mut in_drawer = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let upper = trimmed.to_uppercase();
+
+        if let Some(quoted) = in_block {
+            if upper.starts_with("#+END_") {
+                if !quoted {
+                    output.push("```".to_string());
+                }
+                in_block = None;
+            } else if quoted {
+                output.push(format!("> {}", org_inline(trimmed)));
+            } else {
+                output.push(line.to_string());
+            }
+            continue;
+        }
+
+        if in_drawer {
+            in_drawer = upper != ":END:";
+            continue;
+        }
+        if upper == ":PROPERTIES:" || upper == ":LOGBOOK:" {
+            in_drawer = true;
+            continue;
+        }
+
+        if let Some(title) = trimmed
+            .get(..8)
+            .filter(|p| p.eq_ignore_ascii_case("#+TITLE:"))
+            .map(|_| trimmed[8..].trim())
+        {
+            output.push(format!("# {}", org_inline(title)));
+            continue;
+        }
+        if upper.starts_with("#+BEGIN_SRC") || upper.starts_with("#+BEGIN_EXAMPLE") {
+            let lang = trimmed.split_whitespace().nth(1).unwrap_or("");
+            let lang = if upper.starts_with("#+BEGIN_SRC") {
+                lang
+            } else {
+                ""
+            };
+            output.push(format!("```{}", lang));
+            in_block = Some(false);
+            continue;
+        }
+        if upper.starts_with("#+BEGIN_") {
+            in_block = Some(true);
+            continue;
+        }
+        // Other keywords and comments
+        if trimmed.starts_with("#+") || trimmed == "#" || trimmed.starts_with("# ") {
+            continue;
+        }
+
+        // * Heading :tags:
+        let stars = line.chars().take_while(|&c| c == '*').count();
+        if stars > 0 && line[stars..].starts_with(' ') {
+            let mut title = line[stars..].trim();
+            if title.ends_with(':') {
+                if let Some(tags) = title.rfind(char::is_whitespace) {
+                    if title[tags..].trim().starts_with(':') {
+                        title = title[..tags].trim_end();
+                    }
+                }
+            }
+            let level = (stars + usize::from(has_title)).min(6);
+            output.push(format!("{} {}", "#".repeat(level), org_inline(title)));
+            continue;
+        }
+
+        // "+ item" lists
+        let line = match line.trim_start().strip_prefix("+ ") {
+            Some(rest) => format!(
+                "{}- {}",
+                &line[..line.len() - line.trim_start().len()],
+                rest
+            ),
+            None => line.to_string(),
+        };
+        output.push(org_inline(&line));
+    }
+
+    if in_block == Some(false) {
+        output.push("```".to_string());
+    }
+    output.join("\n")
+}
+
+/// =code=, ~code~, *bold*, [[url][text]], and [[url]]
+fn org_inline(line: &str) -> String {
+    let mut line = emphasis(line, '=', "`");
+    line = emphasis(&line, '~', "`");
+    line = emphasis(&line, '*', "**");
+
+    while let Some(start) = line.find("[[") {
+        let Some(len) = line[start..].find("]]") else {
+            break;
+        };
+        let inner = &line[start + 2..start + len];
+        let link = match inner.split_once("][") {
+            Some((url, text)) => format!("[{}]({})", text, url),
+            None => format!("<{}>", inner),
+        };
+        line.replace_range(start..start + len + 2, &link);
+    }
+    line
+}
+
+/// Replace `marker`-delimited spans at word boundaries (e.g. *bold*) with `with`
+fn emphasis(line: &str, marker: char, with: &str) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let boundary = |c: Option<&char>| c.map_or(true, |c| !c.is_alphanumeric());
+    let mut output = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let opens = chars[i] == marker
+            && boundary(i.checked_sub(1).and_then(|p| chars.get(p)))
+            && chars
+                .get(i + 1)
+                .is_some_and(|c| !c.is_whitespace() && *c != marker);
+        if opens {
+            let close = (i + 2..chars.len()).find(|&j| {
+                chars[j] == marker && !chars[j - 1].is_whitespace() && boundary(chars.get(j + 1))
+            });
+            if let Some(close) = close {
+                output.push_str(with);
+                output.extend(&chars[i + 1..close]);
+                output.push_str(with);
+                i = close + 1;
+                continue;
+            }
+        }
+        output.push(chars[i]);
+        i += 1;
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn asciidoc_keeps_non_ascii_lines_ending_in_a_colon() {
+        let markdown = asciidoc_to_markdown(
+            "= Handbuch\n:toc:\n:author: Jörg\n\nÜberblick:\n\nÉtat: stable\n",
+        );
+        assert!(markdown.contains("Überblick:"));
+        assert!(markdown.contains("État: stable"));
+        assert!(!markdown.contains(":toc:"));
+        assert!(!markdown.contains("Jörg"));
+    }
+}
@@ -0,0 +1,832 @@
+// OpenAPI import - API docs generated from an OpenAPI 3 or Swagger 2 spec
+//
+// The spec (YAML or JSON) becomes an Overview section, a Paths section with
+// one section per path and per operation, and a Schemas section with one
+// section per schema. Schema references become [[section:...]] links. The
+// database remembers the spec, so `proj docs refresh`, `status`, and
+// `watch` treat it the way they treat source files for generated docs.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+use serde_json::Value;
+
+use crate::docs_db;
+use crate::error::ProjError;
+use crate::schema_docs::{self, DocType};
+use crate::source_analyzer::GeneratedSection;
+
+/// `generated_from` meta value for databases built from a spec
+pub const GENERATED_FROM: &str = "openapi";
+
+/// Meta keys: the spec's path (relative to the project root) and content hash
+const SPEC_KEY: &str = "openapi_spec";
+const HASH_KEY: &str = "openapi_hash";
+
+/// Operations in the order they're listed under a path
+const METHODS: &[&str] = &[
+    "get", "put", "post", "patch", "delete", "options", "head", "trace",
+];
+
+/// Create an api docs database from a spec (`proj docs init --import-openapi`)
+pub fn init(project_root: &Path, spec: &Path, name: Option<String>) -> Result<()> {
+    let spec_path = if spec.is_absolute() {
+        spec.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(spec)
+    };
+    let relative = relative_path(project_root, &spec_path);
+
+    println!("{}", format!("Reading {}...", relative).cyan());
+    let (content, sections) = load(&spec_path, &relative)?;
+
+    let project_name = name.unwrap_or_else(|| {
+        project_root
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("project")
+            .to_string()
+    });
+    let db_filename = schema_docs::docs_db_filename(&project_name, DocType::Api);
+    let db_path = project_root.join(&db_filename);
+
+    println!("{}", format!("Creating {}...", db_filename).cyan());
+    let conn = docs_db::create_docs_db(&db_path, &project_name, DocType::Api)?;
+
+    for section in &sections {
+        insert(&conn, section)?;
+    }
+
+    schema_docs::set_meta(&conn, "generated_from", GENERATED_FROM)?;
+    schema_docs::set_meta(&conn, SPEC_KEY, &relative)?;
+    record_hash(&conn, &content)?;
+    schema_docs::set_meta(
+        &conn,
+        "generated_at",
+        &chrono::Utc::now()
+            .format("%Y-%m-%dT%H:%M:%S%.6f")
+            .to_string(),
+    )?;
+
+    println!(
+        "{} Created {} with {} sections",
+        "✓".green(),
+        db_filename,
+        sections.len()
+    );
+    println!("Run 'proj docs refresh' after changing the spec (or 'proj docs watch').");
+    Ok(())
+}
+
+/// Regenerate the sections of a spec-based database (`proj docs refresh`)
+pub fn refresh(conn: &Connection, project_root: &Path, force: bool) -> Result<()> {
+    let spec_path = spec_path(conn, project_root)?;
+    let relative = relative_path(project_root, &spec_path);
+
+    let (_generated, manual_count) = docs_db::get_section_counts(conn)?;
+    if manual_count > 0 && !force {
+        println!(
+            "{} Found {} manually edited sections that will be preserved.",
+            "ℹ".blue(),
+            manual_count
+        );
+        println!("Use --force to regenerate all sections.");
+    }
+
+    println!("{}", format!("Re-reading {}...", relative).cyan());
+    let (content, sections) = load(&spec_path, &relative)?;
+
+    let deleted = if force {
+        conn.execute("DELETE FROM sections", [])?
+    } else {
+        docs_db::delete_generated_sections(conn)?
+    };
+    println!("  Removed {} old sections", deleted);
+
+    let manual_ids: Vec<String> = docs_db::get_all_sections(conn)?
+        .into_iter()
+        .map(|s| s.section_id)
+        .collect();
+    let mut inserted = 0;
+    for section in &sections {
+        if manual_ids.contains(&section.section_id) {
+            println!(
+                "  {} Skipped {} {} (number taken by a manual section - use --force)",
+                "!".yellow(),
+                section.section_id,
+                section.title
+            );
+            continue;
+        }
+        insert(conn, section)?;
+        inserted += 1;
+    }
+
+    record_hash(conn, &content)?;
+    schema_docs::set_meta(
+        conn,
+        "refreshed_at",
+        &chrono::Utc::now()
+            .format("%Y-%m-%dT%H:%M:%S%.6f")
+            .to_string(),
+    )?;
+
+    println!("{} Refreshed with {} sections", "✓".green(), inserted);
+    Ok(())
+}
+
+/// Absolute path of the spec a database was generated from
+pub fn spec_path(conn: &Connection, project_root: &Path) -> Result<PathBuf> {
+    match schema_docs::get_meta(conn, SPEC_KEY)? {
+        Some(spec) => Ok(project_root.join(spec)),
+        None => Err(ProjError::InvalidInput(
+            "This database has no OpenAPI spec recorded. Recreate it with 'proj docs init --import-openapi <spec>'.".to_string(),
+        )
+        .into()),
+    }
+}
+
+/// Remember which version of the spec the sections were generated from
+pub fn record_hash(conn: &Connection, content: &str) -> Result<()> {
+    schema_docs::set_meta(conn, HASH_KEY, &docs_db::hash_content(content))
+}
+
+/// Whether the spec changed since the sections were last generated
+/// (None when the spec can't be read)
+pub fn is_stale(conn: &Connection, project_root: &Path) -> Result<Option<bool>> {
+    let spec_path = spec_path(conn, project_root)?;
+    let Ok(content) = std::fs::read_to_string(&spec_path) else {
+        return Ok(None);
+    };
+    let hash = schema_docs::get_meta(conn, HASH_KEY)?;
+    Ok(Some(
+        hash.as_deref() != Some(&docs_db::hash_content(&content)),
+    ))
+}
+
+/// Read and convert a spec; returns the raw content (for hashing) and the sections
+pub fn load(spec_path: &Path, source_file: &str) -> Result<(String, Vec<GeneratedSection>)> {
+    let content = std::fs::read_to_string(spec_path)
+        .with_context(|| format!("Failed to read {}", spec_path.display()))?;
+    let spec = parse(spec_path, &content)?;
+    Ok((content, generate(&spec, source_file)))
+}
+
+fn parse(spec_path: &Path, content: &str) -> Result<Value> {
+    let is_json = spec_path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    let spec: Value = if is_json {
+        serde_json::from_str(content).map_err(|e| invalid(spec_path, e))?
+    } else {
+        serde_yaml::from_str(content).map_err(|e| invalid(spec_path, e))?
+    };
+
+    if spec.get("openapi").is_none() && spec.get("swagger").is_none() {
+        return Err(invalid(
+            spec_path,
+            "missing 'openapi' or 'swagger' version field",
+        ));
+    }
+    Ok(spec)
+}
+
+fn invalid(spec_path: &Path, error: impl std::fmt::Display) -> anyhow::Error {
+    ProjError::InvalidInput(format!(
+        "{} is not a valid OpenAPI spec: {}",
+        spec_path.display(),
+        error
+    ))
+    .into()
+}
+
+fn insert(conn: &Connection, section: &GeneratedSection) -> Result<()> {
+    docs_db::insert_section(
+        conn,
+        &section.section_id,
+        &section.title,
+        None,
+        section.level,
+        section.sort_order,
+        &section.content,
+        section.generated,
+        section.source_file.as_deref(),
+    )?;
+    Ok(())
+}
+
+fn relative_path(project_root: &Path, path: &Path) -> String {
+    path.strip_prefix(project_root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Sections for a parsed spec
+fn generate(spec: &Value, source_file: &str) -> Vec<GeneratedSection> {
+    let mut sections = Vec::new();
+    let mut sort_order = 0;
+    let mut push = |section_id: String, title: String, level: i32, content: String| {
+        sort_order += 1;
+        sections.push(GeneratedSection {
+            section_id,
+            title,
+            level,
+            sort_order,
+            content,
+            generated: true,
+            source_file: Some(source_file.to_string()),
+        });
+    };
+
+    let schemas = schemas(spec);
+    let paths = spec.get("paths").and_then(Value::as_object);
+
+    // Schema sections come last, but operations link to them
+    let has_paths = paths.is_some_and(|p| !p.is_empty());
+    let schemas_id = if has_paths { "3" } else { "2" };
+    let schema_ids: BTreeMap<&str, String> = schemas
+        .keys()
+        .enumerate()
+        .map(|(index, name)| (name.as_str(), format!("{}.{}", schemas_id, index + 1)))
+        .collect();
+    let types = TypeNamer {
+        spec,
+        schema_ids: &schema_ids,
+    };
+
+    push(
+        "1".to_string(),
+        "Overview".to_string(),
+        1,
+        overview(spec, paths, schemas.len()),
+    );
+
+    if let Some(paths) = paths.filter(|p| !p.is_empty()) {
+        push(
+            "2".to_string(),
+            "Paths".to_string(),
+            1,
+            "HTTP endpoints, one section per path with its operations beneath it.".to_string(),
+        );
+
+        for (path_index, (path, item)) in paths.iter().enumerate() {
+            let path_id = format!("2.{}", path_index + 1);
+            let item = types.resolve(item);
+            let operations: Vec<(&str, &Value)> = METHODS
+                .iter()
+                .filter_map(|m| item.get(*m).map(|op| (*m, op)))
+                .collect();
+
+            push(
+                path_id.clone(),
+                path.clone(),
+                2,
+                path_content(item, &operations),
+            );
+
+            for (op_index, (method, operation)) in operations.iter().enumerate() {
+                push(
+                    format!("{}.{}", path_id, op_index + 1),
+                    format!("{} {}", method.to_uppercase(), path),
+                    3,
+                    operation_content(&types, item, operation),
+                );
+            }
+        }
+    }
+
+    if !schemas.is_empty() {
+        push(
+            schemas_id.to_string(),
+            "Schemas".to_string(),
+            1,
+            "Data models used in requests and responses.".to_string(),
+        );
+        for (name, schema) in &schemas {
+            push(
+                schema_ids[name.as_str()].clone(),
+                name.clone(),
+                2,
+                schema_content(&types, schema),
+            );
+        }
+    }
+
+    sections
+}
+
+/// Named schemas: `components.schemas` (OpenAPI 3) or `definitions` (Swagger 2)
+fn schemas(spec: &Value) -> BTreeMap<String, &Value> {
+    spec.pointer("/components/schemas")
+        .or_else(|| spec.get("definitions"))
+        .and_then(Value::as_object)
+        .map(|schemas| schemas.iter().map(|(k, v)| (k.clone(), v)).collect())
+        .unwrap_or_default()
+}
+
+fn overview(
+    spec: &Value,
+    paths: Option<&serde_json::Map<String, Value>>,
+    schema_count: usize,
+) -> String {
+    let info = spec.get("info");
+    let text = |key: &str| info.and_then(|i| i.get(key)).and_then(Value::as_str);
+    let mut parts = Vec::new();
+
+    let title = text("title").unwrap_or("API");
+    parts.push(match text("version") {
+        Some(version) => format!("**{}** (version {})", title, version),
+        None => format!("**{}**", title),
+    });
+    if let Some(description) = text("description") {
+        parts.push(description.trim().to_string());
+    }
+
+    let spec_version = spec
+        .get("openapi")
+        .or_else(|| spec.get("swagger"))
+        .map(scalar)
+        .unwrap_or_default();
+    let operation_count: usize = paths
+        .map(|p| {
+            p.values()
+                .map(|item| METHODS.iter().filter(|m| item.get(**m).is_some()).count())
+                .sum()
+        })
+        .unwrap_or(0);
+    let mut facts = vec![
+        format!("- **Spec**: {} {}", spec_format(spec), spec_version),
+        format!(
+            "- **Paths**: {}, **Operations**: {}, **Schemas**: {}",
+            paths.map_or(0, |p| p.len()),
+            operation_count,
+            schema_count
+        ),
+    ];
+    let servers = servers(spec);
+    if !servers.is_empty() {
+        facts.push(format!(
+            "- **Servers**: {}",
+            servers
+                .iter()
+                .map(|s| format!("`{}`", s))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+    parts.push(facts.join("\n"));
+
+    let security = spec
+        .pointer("/components/securitySchemes")
+        .or_else(|| spec.get("securityDefinitions"))
+        .and_then(Value::as_object);
+    if let Some(schemes) = security.filter(|s| !s.is_empty()) {
+        let mut table = String::from(
+            "**Authentication**\n\n| Name | Type | Details |\n|------|------|---------|\n",
+        );
+        for (name, scheme) in schemes {
+            let field = |key: &str| scheme.get(key).and_then(Value::as_str);
+            let details = match field("type") {
+                Some("apiKey") => format!(
+                    "`{}` in {}",
+                    field("name").unwrap_or("?"),
+                    field("in").unwrap_or("?")
+                ),
+                Some("http") => field("scheme").unwrap_or("").to_string(),
+                Some("oauth2") => field("flow")
+                    .map(str::to_string)
+                    .or_else(|| {
+                        scheme
+                            .get("flows")
+                            .and_then(Value::as_object)
+                            .map(|f| f.keys().cloned().collect::<Vec<_>>().join(", "))
+                    })
+                    .unwrap_or_default(),
+                Some("openIdConnect") => field("openIdConnectUrl").unwrap_or("").to_string(),
+                _ => String::new(),
+            };
+            table.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                name,
+                field("type").unwrap_or(""),
+                cell(&details)
+            ));
+        }
+        parts.push(table.trim_end().to_string());
+    }
+
+    parts.join("\n\n")
+}
+
+fn spec_format(spec: &Value) -> &'static str {
+    if spec.get("openapi").is_some() {
+        "OpenAPI"
+    } else {
+        "Swagger"
+    }
+}
+
+/// Server URLs: `servers` (OpenAPI 3) or scheme + host + basePath (Swagger 2)
+fn servers(spec: &Value) -> Vec<String> {
+    if let Some(servers) = spec.get("servers").and_then(Value::as_array) {
+        return servers
+            .iter()
+            .filter_map(|s| s.get("url").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect();
+    }
+    let Some(host) = spec.get("host").and_then(Value::as_str) else {
+        return Vec::new();
+    };
+    let base = spec.get("basePath").and_then(Value::as_str).unwrap_or("");
+    let schemes: Vec<&str> = spec
+        .get("schemes")
+        .and_then(Value::as_array)
+        .map(|s| s.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_else(|| vec!["https"]);
+    schemes
+        .iter()
+        .map(|scheme| format!("{}://{}{}", scheme, host, base))
+        .collect()
+}
+
+fn path_content(item: &Value, operations: &[(&str, &Value)]) -> String {
+    let mut parts = Vec::new();
+    for key in ["summary", "description"] {
+        if let Some(text) = item.get(key).and_then(Value::as_str) {
+            parts.push(text.trim().to_string());
+        }
+    }
+    let mut table = String::from("| Method | Summary |\n|--------|---------|\n");
+    for (method, operation) in operations {
+        table.push_str(&format!(
+            "| `{}` | {} |\n",
+            method.to_uppercase(),
+            cell(
+                operation
+                    .get("summary")
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+            )
+        ));
+    }
+    parts.push(table.trim_end().to_string());
+    parts.join("\n\n")
+}
+
+fn operation_content(types: &TypeNamer, path_item: &Value, operation: &Value) -> String {
+    let mut parts = Vec::new();
+    for key in ["summary", "description"] {
+        if let Some(text) = operation.get(key).and_then(Value::as_str) {
+            parts.push(text.trim().to_string());
+        }
+    }
+
+    let mut facts = Vec::new();
+    if let Some(id) = operation.get("operationId").and_then(Value::as_str) {
+        facts.push(format!("- **Operation ID**: `{}`", id));
+    }
+    if let Some(tags) = operation.get("tags").and_then(Value::as_array) {
+        let tags: Vec<String> = tags.iter().map(scalar).collect();
+        if !tags.is_empty() {
+            facts.push(format!("- **Tags**: {}", tags.join(", ")));
+        }
+    }
+    if let Some(security) = operation.get("security").and_then(Value::as_array) {
+        let schemes: Vec<String> = security
+            .iter()
+            .filter_map(Value::as_object)
+            .flat_map(|requirement| requirement.keys().map(|k| format!("`{}`", k)))
+            .collect();
+        facts.push(format!(
+            "- **Auth**: {}",
+            if schemes.is_empty() {
+                "none".to_string()
+            } else {
+                schemes.join(", ")
+            }
+        ));
+    }
+    if operation.get("deprecated").and_then(Value::as_bool) == Some(true) {
+        facts.push("- **Deprecated**".to_string());
+    }
+    if !facts.is_empty() {
+        parts.push(facts.join("\n"));
+    }
+
+    // Path-level parameters apply to every operation unless overridden
+    let mut parameters: Vec<&Value> = Vec::new();
+    let mut body: Option<&Value> = None;
+    let declared = path_item
+        .get("parameters")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .chain(
+            operation
+                .get("parameters")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten(),
+        );
+    for parameter in declared.map(|p| types.resolve(p)) {
+        let key = |p: &Value| (scalar_field(p, "name"), scalar_field(p, "in"));
+        if scalar_field(parameter, "in") == "body" {
+            body = Some(parameter);
+            continue;
+        }
+        parameters.retain(|existing| key(existing) != key(parameter));
+        parameters.push(parameter);
+    }
+
+    if !parameters.is_empty() {
+        let mut table = String::from(
+            "**Parameters**\n\n| Name | In | Type | Required | Description |\n|------|----|------|----------|-------------|\n",
+        );
+        for parameter in &parameters {
+            // OpenAPI 3 puts the type under `schema`; Swagger 2 on the parameter
+            let schema = parameter.get("schema").unwrap_or(parameter);
+            table.push_str(&format!(
+                "| `{}` | {} | {} | {} | {} |\n",
+                scalar_field(parameter, "name"),
+                scalar_field(parameter, "in"),
+                cell(&types.name(schema)),
+                if parameter.get("required").and_then(Value::as_bool) == Some(true) {
+                    "yes"
+                } else {
+                    "no"
+                },
+                cell(&scalar_field(parameter, "description"))
+            ));
+        }
+        parts.push(table.trim_end().to_string());
+    }
+
+    if let Some(request) = operation.get("requestBody").map(|b| types.resolve(b)) {
+        parts.push(body_line(
+            "Request body",
+            request,
+            &content_types(types, request),
+        ));
+    } else if let Some(parameter) = body {
+        let schema = parameter.get("schema").map(|s| types.name(s));
+        parts.push(body_line(
+            "Request body",
+            parameter,
+            &schema.map(|s| vec![(String::new(), s)]).unwrap_or_default(),
+        ));
+    }
+
+    if let Some(responses) = operation.get("responses").and_then(Value::as_object) {
+        let mut table = String::from(
+            "**Responses**\n\n| Status | Description | Body |\n|--------|-------------|------|\n",
+        );
+        for (status, response) in responses {
+            let response = types.resolve(response);
+            let body = match response.get("schema") {
+                Some(schema) => types.name(schema),
+                None => content_types(types, response)
+                    .into_iter()
+                    .map(|(media, schema)| format!("{} ({})", schema, media))
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            table.push_str(&format!(
+                "| `{}` | {} | {} |\n",
+                status,
+                cell(&scalar_field(response, "description")),
+                cell(&body)
+            ));
+        }
+        parts.push(table.trim_end().to_string());
+    }
+
+    parts.join("\n\n")
+}
+
+/// (media type, schema name) for each entry of an OpenAPI 3 `content` map
+fn content_types(types: &TypeNamer, body: &Value) -> Vec<(String, String)> {
+    body.get("content")
+        .and_then(Value::as_object)
+        .map(|content| {
+            content
+                .iter()
+                .map(|(media, entry)| {
+                    let schema = entry
+                        .get("schema")
+                        .map(|s| types.name(s))
+                        .unwrap_or_else(|| "-".to_string());
+                    (media.clone(), schema)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn body_line(label: &str, body: &Value, content: &[(String, String)]) -> String {
+    let required = if body.get("required").and_then(Value::as_bool) == Some(true) {
+        " (required)"
+    } else {
+        ""
+    };
+    let mut line = format!("**{}**{}:", label, required);
+    for (media, schema) in content {
+        if media.is_empty() {
+            line.push_str(&format!(" {}", schema));
+        } else {
+            line.push_str(&format!("\n- `{}`: {}", media, schema));
+        }
+    }
+    if let Some(description) = body.get("description").and_then(Value::as_str) {
+        line.push_str(&format!("\n\n{}", description.trim()));
+    }
+    line
+}
+
+fn schema_content(types: &TypeNamer, schema: &Value) -> String {
+    let mut parts = Vec::new();
+    if let Some(description) = schema.get("description").and_then(Value::as_str) {
+        parts.push(description.trim().to_string());
+    }
+
+    let composed: Vec<String> = ["allOf", "oneOf", "anyOf"]
+        .iter()
+        .filter_map(|key| {
+            let members = schema.get(*key)?.as_array()?;
+            let names: Vec<String> = members
+                .iter()
+                .filter(|m| m.get("$ref").is_some())
+                .map(|m| types.name(m))
+                .collect();
+            let verb = match *key {
+                "allOf" => "Includes",
+                "oneOf" => "One of",
+                _ => "Any of",
+            };
+            (!names.is_empty()).then(|| format!("**{}:** {}", verb, names.join(", ")))
+        })
+        .collect();
+    parts.extend(composed);
+
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        parts.push(format!(
+            "**Type:** {}\n\n**Values:** {}",
+            types.name(&without_enum(schema)),
+            values
+                .iter()
+                .map(|v| format!("`{}`", scalar(v)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+
+    // Properties, including those of inline allOf members
+    let property_sources = std::iter::once(schema).chain(
+        schema
+            .get("allOf")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter(|m| m.get("$ref").is_none()),
+    );
+    let mut rows = Vec::new();
+    for source in property_sources {
+        let required: Vec<String> = source
+            .get("required")
+            .and_then(Value::as_array)
+            .map(|r| r.iter().map(scalar).collect())
+            .unwrap_or_default();
+        let Some(properties) = source.get("properties").and_then(Value::as_object) else {
+            continue;
+        };
+        for (name, property) in properties {
+            rows.push(format!(
+                "| `{}` | {} | {} | {} |",
+                name,
+                cell(&types.name(property)),
+                if required.contains(name) { "yes" } else { "no" },
+                cell(&scalar_field(property, "description"))
+            ));
+        }
+    }
+    if !rows.is_empty() {
+        parts.push(format!(
+            "| Property | Type | Required | Description |\n|----------|------|----------|-------------|\n{}",
+            rows.join("\n")
+        ));
+    } else if schema.get("enum").is_none() && parts.len() <= 1 {
+        parts.push(format!("**Type:** {}", types.name(schema)));
+    }
+
+    if parts.is_empty() {
+        return "No description.".to_string();
+    }
+    parts.join("\n\n")
+}
+
+fn without_enum(schema: &Value) -> Value {
+    let mut schema = schema.clone();
+    if let Some(object) = schema.as_object_mut() {
+        object.remove("enum");
+    }
+    schema
+}
+
+/// Names schema types, linking named schemas to their sections
+struct TypeNamer<'a> {
+    spec: &'a Value,
+    schema_ids: &'a BTreeMap<&'a str, String>,
+}
+
+impl<'a> TypeNamer<'a> {
+    /// Follow a local `$ref` (parameters, responses, request bodies, path items)
+    fn resolve<'v>(&self, value: &'v Value) -> &'v Value
+    where
+        'a: 'v,
+    {
+        let mut value = value;
+        // Bounded so a reference cycle can't loop forever
+        for _ in 0..8 {
+            let Some(target) = value
+                .get("$ref")
+                .and_then(Value::as_str)
+                .and_then(|r| r.strip_prefix('#'))
+                .and_then(|pointer| self.spec.pointer(pointer))
+            else {
+                break;
+            };
+            value = target;
+        }
+        value
+    }
+
+    fn name(&self, schema: &Value) -> String {
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let name = reference.rsplit('/').next().unwrap_or(reference);
+            return match self.schema_ids.get(name) {
+                Some(id) => format!("[[section:{}]]", id),
+                None => format!("`{}`", name),
+            };
+        }
+
+        for (key, separator) in [("oneOf", " or "), ("anyOf", " or "), ("allOf", " and ")] {
+            if let Some(members) = schema.get(key).and_then(Value::as_array) {
+                return members
+                    .iter()
+                    .map(|m| self.name(m))
+                    .collect::<Vec<_>>()
+                    .join(separator);
+            }
+        }
+
+        let ty = match schema.get("type") {
+            Some(Value::String(ty)) => ty.clone(),
+            // OpenAPI 3.1 allows a list of types, e.g. ["string", "null"]
+            Some(Value::Array(types)) => types.iter().map(scalar).collect::<Vec<_>>().join(" or "),
+            _ if schema.get("properties").is_some() => "object".to_string(),
+            _ => return "any".to_string(),
+        };
+        match ty.as_str() {
+            "array" => {
+                let items = schema
+                    .get("items")
+                    .map(|i| self.name(i))
+                    .unwrap_or_else(|| "any".to_string());
+                format!("array of {}", items)
+            }
+            "object" => match schema.get("additionalProperties") {
+                Some(values) if values.is_object() => {
+                    format!("map of {}", self.name(values))
+                }
+                _ => "`object`".to_string(),
+            },
+            _ => match schema.get("format").and_then(Value::as_str) {
+                Some(format) => format!("`{}` ({})", ty, format),
+                None => format!("`{}`", ty),
+            },
+        }
+    }
+}
+
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn scalar_field(value: &Value, key: &str) -> String {
+    value.get(key).map(scalar).unwrap_or_default()
+}
+
+/// Table cell text: one line, pipes escaped
+fn cell(text: &str) -> String {
+    text.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
@@ -0,0 +1,262 @@
+// Docs snapshot and diff commands - review what a refresh (or an edit) changed
+//
+// A snapshot stores every section's content. Sections are matched between
+// two versions by title and source file, so renumbering alone isn't a change.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::Local;
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::docs_db::{self, SnapshotSection};
+use crate::error::ProjError;
+use crate::paths::get_project_root;
+
+/// Name accepted by `proj docs diff` for the docs as they are now
+const CURRENT: &str = "current";
+
+/// Line changes shown per section before the rest is summarized
+const MAX_DIFF_LINES: usize = 40;
+
+pub fn snapshot(name: Option<String>, list: bool, delete: Option<String>) -> Result<()> {
+    let conn = open()?;
+
+    if list {
+        return list_snapshots(&conn);
+    }
+    if let Some(name) = delete {
+        if !docs_db::delete_snapshot(&conn, &name)? {
+            return Err(unknown_snapshot(&conn, &name));
+        }
+        println!("{} Deleted snapshot {}", "✓".green(), name);
+        return Ok(());
+    }
+
+    let name = name.unwrap_or_else(|| Local::now().format("%Y-%m-%d-%H%M%S").to_string());
+    if name == CURRENT {
+        return Err(ProjError::InvalidInput(format!(
+            "'{}' is reserved for the current docs in 'proj docs diff'",
+            CURRENT
+        ))
+        .into());
+    }
+    let snapshot = docs_db::create_snapshot(&conn, &name)?;
+
+    println!(
+        "{} Saved snapshot {} ({} sections)",
+        "✓".green(),
+        snapshot.name.cyan(),
+        snapshot.section_count
+    );
+    println!("Compare later with 'proj docs diff {}'", snapshot.name);
+    Ok(())
+}
+
+pub fn diff(from: &str, to: Option<&str>, stat: bool) -> Result<()> {
+    let conn = open()?;
+
+    let to = to.unwrap_or(CURRENT);
+    let old = load(&conn, from)?;
+    let new = load(&conn, to)?;
+
+    println!("Comparing {} → {}\n", from.cyan(), to.cyan());
+
+    let mut previous: HashMap<(String, Option<String>), Vec<&SnapshotSection>> = HashMap::new();
+    for section in &old {
+        previous
+            .entry((section.title.clone(), section.source_file.clone()))
+            .or_default()
+            .push(section);
+    }
+    // Match in document order, so duplicates pair up first-to-first
+    for sections in previous.values_mut() {
+        sections.reverse();
+    }
+
+    let (mut added, mut changed, mut unchanged) = (0, 0, 0);
+    for section in &new {
+        let key = (section.title.clone(), section.source_file.clone());
+        let Some(before) = previous.get_mut(&key).and_then(|found| found.pop()) else {
+            added += 1;
+            println!("  {} {} {}", "+".green(), section.section_id, section.title);
+            continue;
+        };
+        if before.content_hash == section.content_hash {
+            unchanged += 1;
+            continue;
+        }
+
+        changed += 1;
+        let lines = line_diff(&before.content, &section.content);
+        let (plus, minus) = lines.iter().fold((0, 0), |(p, m), line| match line {
+            Line::Added(_) => (p + 1, m),
+            Line::Removed(_) => (p, m + 1),
+            Line::Same => (p, m),
+        });
+        println!(
+            "  {} {} {} {}",
+            "~".yellow(),
+            section.section_id,
+            section.title,
+            format!("(+{} -{})", plus, minus).dimmed()
+        );
+        if !stat {
+            print_lines(&lines);
+        }
+    }
+
+    let mut removed: Vec<&SnapshotSection> = previous.into_values().flatten().collect();
+    removed.sort_by_key(|s| s.sort_order);
+    for section in &removed {
+        println!("  {} {} {}", "-".red(), section.section_id, section.title);
+    }
+
+    if added + changed + removed.len() == 0 {
+        println!("{} No differences ({} sections)", "✓".green(), unchanged);
+    } else {
+        println!(
+            "\n{} changed, {} added, {} removed, {} unchanged",
+            changed,
+            added,
+            removed.len(),
+            unchanged
+        );
+    }
+    Ok(())
+}
+
+fn open() -> Result<Connection> {
+    let project_root = get_project_root()?;
+    let db_path = match docs_db::find_docs_db(&project_root) {
+        Some(path) => path,
+        None => return Err(ProjError::DocsDbMissing.into()),
+    };
+    docs_db::open_docs_db(&db_path)
+}
+
+fn list_snapshots(conn: &Connection) -> Result<()> {
+    let snapshots = docs_db::list_snapshots(conn)?;
+    if snapshots.is_empty() {
+        println!("No snapshots. Save one with 'proj docs snapshot [name]'.");
+        return Ok(());
+    }
+
+    println!("\n{}", "Snapshots".bold());
+    println!("{}\n", "─".repeat(40));
+    for snapshot in snapshots {
+        println!(
+            "  {} {} {}",
+            snapshot.name.cyan(),
+            snapshot
+                .created_at
+                .get(..16)
+                .unwrap_or(&snapshot.created_at)
+                .replace('T', " "),
+            format!("({} sections)", snapshot.section_count).dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// Sections of a snapshot, or of the live docs for "current"
+fn load(conn: &Connection, name: &str) -> Result<Vec<SnapshotSection>> {
+    if name == CURRENT {
+        return Ok(docs_db::get_all_sections(conn)?
+            .into_iter()
+            .map(|s| SnapshotSection {
+                content_hash: docs_db::hash_content(&s.content),
+                section_id: s.section_id,
+                title: s.title,
+                level: s.level,
+                sort_order: s.sort_order,
+                content: s.content,
+                generated: s.generated,
+                source_file: s.source_file,
+            })
+            .collect());
+    }
+
+    match docs_db::list_snapshots(conn)?
+        .into_iter()
+        .find(|s| s.name == name)
+    {
+        Some(snapshot) => docs_db::get_snapshot_sections(conn, snapshot.id),
+        None => Err(unknown_snapshot(conn, name)),
+    }
+}
+
+fn unknown_snapshot(conn: &Connection, name: &str) -> anyhow::Error {
+    let names: Vec<String> = docs_db::list_snapshots(conn)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| s.name)
+        .collect();
+    let hint = if names.is_empty() {
+        "no snapshots saved yet".to_string()
+    } else {
+        format!("available: {}", names.join(", "))
+    };
+    ProjError::InvalidInput(format!("Snapshot '{}' not found ({})", name, hint)).into()
+}
+
+enum Line<'a> {
+    Same,
+    Added(&'a str),
+    Removed(&'a str),
+}
+
+/// Line diff via longest common subsequence (sections are small)
+fn line_diff<'a>(old: &'a str, new: &'a str) -> Vec<Line<'a>> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut lines = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            lines.push(Line::Same);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            lines.push(Line::Removed(a[i]));
+            i += 1;
+        } else {
+            lines.push(Line::Added(b[j]));
+            j += 1;
+        }
+    }
+    lines.extend(a[i..].iter().map(|l| Line::Removed(l)));
+    lines.extend(b[j..].iter().map(|l| Line::Added(l)));
+    lines
+}
+
+/// Print added and removed lines (unchanged lines are left out)
+fn print_lines(lines: &[Line]) {
+    let changes: Vec<&Line> = lines.iter().filter(|l| !matches!(l, Line::Same)).collect();
+    for line in changes.iter().take(MAX_DIFF_LINES) {
+        match line {
+            Line::Added(text) => println!("      {}", format!("+ {}", text).green()),
+            Line::Removed(text) => println!("      {}", format!("- {}", text).red()),
+            Line::Same => {}
+        }
+    }
+    if changes.len() > MAX_DIFF_LINES {
+        println!(
+            "      {}",
+            format!("... {} more changed lines", changes.len() - MAX_DIFF_LINES).dimmed()
+        );
+    }
+}
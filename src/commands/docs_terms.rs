@@ -0,0 +1,436 @@
+// Glossary helpers - term extraction (proj docs term extract) and term linking on export
+//
+// Candidates come from two places: public types in the source (CamelCase
+// names, defined by their doc comments) and capitalized phrases that keep
+// recurring in section content (e.g. "Ralph Loop").
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+use colored::Colorize;
+use dialoguer::MultiSelect;
+
+use crate::docs_db::{self, Section, TermEntry};
+use crate::error::ProjError;
+use crate::paths::get_project_root;
+use crate::source_analyzer::{self, ItemKind, SourceItem, Visibility};
+use crate::text::truncate;
+
+/// Words that start sentences or headings but aren't part of a term
+const STOPWORDS: &[&str] = &[
+    "A", "An", "And", "As", "At", "By", "For", "From", "If", "In", "It", "Its", "No", "Of", "On",
+    "Or", "See", "So", "The", "Then", "These", "This", "To", "Use", "When", "With",
+];
+
+/// A proposed glossary entry
+struct Candidate {
+    term: String,
+    definition: Option<String>,
+    /// Spaced form of a CamelCase name, so prose mentions link too
+    variants: Vec<String>,
+    mentions: usize,
+    /// Where it was found, for the listing
+    origin: String,
+}
+
+/// Find candidate terms and add the chosen ones to the glossary
+pub fn extract(yes: bool, dry_run: bool, limit: usize, min_count: usize) -> Result<()> {
+    let project_root = get_project_root()?;
+
+    let db_path = match docs_db::find_docs_db(&project_root) {
+        Some(path) => path,
+        None => return Err(ProjError::DocsDbMissing.into()),
+    };
+
+    let conn = docs_db::open_docs_db(&db_path)?;
+    let sections = docs_db::get_all_sections(&conn)?;
+    let existing = docs_db::get_all_terms(&conn)?;
+
+    // Source analysis is best-effort; docs may have been imported for a project without code
+    let items = source_analyzer::analyze_project(&project_root)
+        .map(|s| s.modules)
+        .unwrap_or_default();
+
+    let mut candidates = find_candidates(&sections, &items, &project_root, min_count);
+    candidates.retain(|c| !is_known(&existing, &c.term));
+    candidates.truncate(limit);
+
+    if candidates.is_empty() {
+        println!("{} No new term candidates found.", "✓".green());
+        return Ok(());
+    }
+
+    let labels: Vec<String> = candidates.iter().map(describe).collect();
+    let interactive = atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout);
+
+    let selected: Vec<usize> = if dry_run || (!yes && !interactive) {
+        println!("\n{}", "Term candidates".bold());
+        println!("{}\n", "─".repeat(40));
+        for label in &labels {
+            println!("  {}", label);
+        }
+        println!();
+        if !dry_run {
+            println!("Run with --yes to add them all, or from a terminal to pick.");
+        }
+        return Ok(());
+    } else if yes {
+        (0..candidates.len()).collect()
+    } else {
+        let defaults = vec![true; labels.len()];
+        MultiSelect::new()
+            .with_prompt("Select terms to add to the glossary")
+            .items(&labels)
+            .defaults(&defaults)
+            .interact()?
+    };
+
+    if selected.is_empty() {
+        println!("{} No terms selected.", "!".yellow());
+        return Ok(());
+    }
+
+    for &index in &selected {
+        let candidate = &candidates[index];
+        let variants: Vec<&str> = candidate.variants.iter().map(String::as_str).collect();
+        docs_db::insert_term(
+            &conn,
+            &candidate.term,
+            candidate.definition.as_deref(),
+            None,
+            &variants,
+        )?;
+    }
+
+    println!(
+        "{} Added {} term{}. Review them with 'proj docs term list'.",
+        "✓".green(),
+        selected.len(),
+        if selected.len() == 1 { "" } else { "s" }
+    );
+    Ok(())
+}
+
+fn find_candidates(
+    sections: &[Section],
+    items: &[SourceItem],
+    project_root: &Path,
+    min_count: usize,
+) -> Vec<Candidate> {
+    let content: Vec<&str> = sections.iter().map(|s| s.content.as_str()).collect();
+    let mut candidates: Vec<Candidate> = Vec::new();
+
+    // Public types, defined by their doc comments
+    let mut types: HashMap<String, &SourceItem> = HashMap::new();
+    for item in items {
+        let is_type = matches!(
+            item.kind,
+            ItemKind::Struct | ItemKind::Enum | ItemKind::Trait | ItemKind::Type
+        );
+        if is_type && item.visibility == Visibility::Public && is_camel_case(&item.name) {
+            types.entry(item.name.clone()).or_insert(item);
+        }
+    }
+    for (name, item) in &types {
+        let spaced = split_camel_case(name);
+        let mentions = content
+            .iter()
+            .map(|c| count_word(c, name) + count_word(c, &spaced))
+            .sum();
+        let origin = item
+            .file_path
+            .strip_prefix(project_root)
+            .unwrap_or(&item.file_path)
+            .display()
+            .to_string();
+        candidates.push(Candidate {
+            term: name.clone(),
+            definition: item.doc_comment.as_deref().and_then(first_sentence),
+            variants: vec![spaced],
+            mentions,
+            origin,
+        });
+    }
+
+    // Recurring phrases in the docs themselves
+    let mut phrases: HashMap<String, usize> = HashMap::new();
+    for text in &content {
+        for phrase in capitalized_phrases(text) {
+            *phrases.entry(phrase).or_insert(0) += 1;
+        }
+    }
+    for (phrase, mentions) in phrases {
+        let squashed = phrase.replace(' ', "");
+        if mentions < min_count || types.contains_key(&squashed) {
+            continue;
+        }
+        candidates.push(Candidate {
+            definition: first_sentence_mentioning(&content, &phrase),
+            term: phrase,
+            variants: Vec::new(),
+            mentions,
+            origin: "docs".to_string(),
+        });
+    }
+
+    candidates.sort_by(|a, b| b.mentions.cmp(&a.mentions).then(a.term.cmp(&b.term)));
+    candidates
+}
+
+fn describe(candidate: &Candidate) -> String {
+    let mut label = format!(
+        "{} ({}, {} mention{})",
+        candidate.term,
+        candidate.origin,
+        candidate.mentions,
+        if candidate.mentions == 1 { "" } else { "s" }
+    );
+    if let Some(definition) = &candidate.definition {
+        label.push_str(&format!(" - {}", truncate(definition, 60)));
+    }
+    label
+}
+
+fn is_known(terms: &[TermEntry], candidate: &str) -> bool {
+    terms.iter().any(|t| {
+        t.canonical.eq_ignore_ascii_case(candidate)
+            || t.variants.iter().any(|v| v.eq_ignore_ascii_case(candidate))
+    })
+}
+
+/// `SessionManager`, `HTTPClient` - at least two capitals and a lowercase letter
+fn is_camel_case(word: &str) -> bool {
+    word.len() >= 4
+        && word.starts_with(|c: char| c.is_ascii_uppercase())
+        && word.chars().all(|c| c.is_ascii_alphanumeric())
+        && word.chars().filter(|c| c.is_ascii_uppercase()).count() >= 2
+        && word.chars().any(|c| c.is_ascii_lowercase())
+}
+
+/// `SessionManager` -> `Session Manager`, `HTTPClient` -> `HTTP Client`
+fn split_camel_case(word: &str) -> String {
+    let chars: Vec<char> = word.chars().collect();
+    let mut output = String::new();
+    for (i, &c) in chars.iter().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            let prev_lower = chars[i - 1].is_ascii_lowercase() || chars[i - 1].is_ascii_digit();
+            let next_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if prev_lower || (chars[i - 1].is_ascii_uppercase() && next_lower) {
+                output.push(' ');
+            }
+        }
+        output.push(c);
+    }
+    output
+}
+
+/// Runs of two or three Capitalized words in prose, minus sentence-initial stopwords
+fn capitalized_phrases(text: &str) -> Vec<String> {
+    let mut phrases = Vec::new();
+    let mut in_code = false;
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let mut run: Vec<&str> = Vec::new();
+        // Splitting on anything but letters keeps punctuation from joining runs
+        let mut flush = |run: &mut Vec<&str>| {
+            while run.first().is_some_and(|w| STOPWORDS.contains(w)) {
+                run.remove(0);
+            }
+            if (2..=3).contains(&run.len()) {
+                phrases.push(run.join(" "));
+            }
+            run.clear();
+        };
+        for (word, separator) in words_with_separators(line) {
+            if separator.chars().any(|c| !c.is_whitespace()) {
+                flush(&mut run);
+            }
+            let capitalized = word.starts_with(|c: char| c.is_ascii_uppercase())
+                && word.len() > 1
+                && word.chars().skip(1).all(|c| c.is_ascii_lowercase());
+            if capitalized {
+                run.push(word);
+            } else {
+                flush(&mut run);
+            }
+        }
+        flush(&mut run);
+    }
+    phrases
+}
+
+/// Alphabetic words with the text that came before each one
+fn words_with_separators(line: &str) -> Vec<(&str, &str)> {
+    let mut words = Vec::new();
+    let mut last_end = 0;
+    let mut start = None;
+    for (i, c) in line
+        .char_indices()
+        .chain(std::iter::once((line.len(), ' ')))
+    {
+        match (start, c.is_alphabetic()) {
+            (None, true) => start = Some(i),
+            (Some(s), false) => {
+                words.push((&line[s..i], &line[last_end..s]));
+                last_end = i;
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    words
+}
+
+/// Whole-word occurrences of `word` in `text`
+fn count_word(text: &str, word: &str) -> usize {
+    find_word(text, word, 0).map_or(0, |first| {
+        let mut count = 1;
+        let mut pos = first + word.len();
+        while let Some(next) = find_word(text, word, pos) {
+            count += 1;
+            pos = next + word.len();
+        }
+        count
+    })
+}
+
+/// Byte offset of the next whole-word occurrence of `word` at or after `from`
+fn find_word(text: &str, word: &str, from: usize) -> Option<usize> {
+    let mut pos = from;
+    while let Some(offset) = text.get(pos..)?.find(word) {
+        let start = pos + offset;
+        let end = start + word.len();
+        let before = text[..start].chars().next_back();
+        let after = text[end..].chars().next();
+        let boundary = |c: Option<char>| !c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+        if boundary(before) && boundary(after) {
+            return Some(start);
+        }
+        pos = end;
+    }
+    None
+}
+
+/// First sentence of a doc comment
+fn first_sentence(doc: &str) -> Option<String> {
+    let line = doc.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let sentence = match line.find(". ") {
+        Some(end) => &line[..=end],
+        None => line,
+    };
+    Some(sentence.trim().to_string())
+}
+
+/// First sentence in the docs that uses the phrase, as a starting definition
+fn first_sentence_mentioning(content: &[&str], phrase: &str) -> Option<String> {
+    content.iter().flat_map(|c| c.lines()).find_map(|line| {
+        let start = find_word(line, phrase, 0)?;
+        let sentence_start = line[..start].rfind(". ").map(|i| i + 2).unwrap_or(0);
+        let sentence_end = line[start..]
+            .find(". ")
+            .map(|i| start + i + 1)
+            .unwrap_or(line.len());
+        let sentence = line[sentence_start..sentence_end]
+            .trim()
+            .trim_start_matches(['-', '*', ' '])
+            .trim();
+        (!sentence.is_empty()).then(|| sentence.to_string())
+    })
+}
+
+/// Anchor of a term's glossary entry, e.g. `term-session-manager`
+pub fn term_anchor(term: &str) -> String {
+    let mut slug = String::new();
+    for c in term.chars() {
+        if c.is_alphanumeric() {
+            slug.extend(c.to_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    format!("term-{}", slug.trim_matches('-'))
+}
+
+/// Link the first mention of each glossary term in a section's markdown.
+/// Code blocks, inline code, headings, and existing links are left alone.
+pub fn link_terms(content: &str, terms: &[TermEntry]) -> String {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    for term in terms {
+        let mut forms = vec![term.canonical.as_str()];
+        forms.extend(term.variants.iter().map(String::as_str));
+
+        let mut in_code = false;
+        'lines: for line in lines.iter_mut() {
+            let trimmed = line.trim_start();
+            if trimmed.starts_with("```") {
+                in_code = !in_code;
+                continue;
+            }
+            if in_code || trimmed.starts_with('#') {
+                continue;
+            }
+            for form in &forms {
+                if form.is_empty() {
+                    continue;
+                }
+                let mut from = 0;
+                while let Some(start) = find_word(line, form, from) {
+                    let end = start + form.len();
+                    if is_linkable(line, start) {
+                        let linked =
+                            format!("[{}](#{})", &line[start..end], term_anchor(&term.canonical));
+                        line.replace_range(start..end, &linked);
+                        break 'lines;
+                    }
+                    from = end;
+                }
+            }
+        }
+    }
+
+    let mut output = lines.join("\n");
+    if content.ends_with('\n') {
+        output.push('\n');
+    }
+    output
+}
+
+/// Whether a position is outside inline code and link text/targets
+fn is_linkable(line: &str, pos: usize) -> bool {
+    let before = &line[..pos];
+    let backticks = before.matches('`').count();
+    let open_brackets = before.matches('[').count() > before.matches(']').count();
+    let open_parens = before
+        .rfind("](")
+        .is_some_and(|i| !before[i..].contains(')'));
+    backticks % 2 == 0 && !open_brackets && !open_parens
+}
+
+/// Markdown glossary appended to exports that link terms
+pub fn glossary_markdown(terms: &[TermEntry]) -> String {
+    let mut output = String::from("# Glossary\n\n");
+    for term in terms {
+        output.push_str(&format!(
+            "<a id=\"{}\"></a>\n\n",
+            term_anchor(&term.canonical)
+        ));
+        output.push_str(&format!("**{}**", term.canonical));
+        if let Some(category) = &term.category {
+            output.push_str(&format!(" _({})_", category));
+        }
+        output.push_str("\n\n");
+        if let Some(definition) = &term.definition {
+            output.push_str(&format!("{}\n\n", definition));
+        }
+    }
+    output
+}
@@ -0,0 +1,405 @@
+// Docs watch command - keep generated docs sections current while source files change
+//
+// Changed files are re-parsed (everything else comes from an in-memory
+// cache), sections are regenerated, and only the generated sections that
+// actually differ are updated. Manual sections are never touched. Databases
+// imported from an OpenAPI spec are regenerated whenever the spec changes.
+
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use notify::{EventKind, RecursiveMode, Watcher};
+use rusqlite::{params, Connection};
+
+use crate::commands::docs_openapi;
+use crate::database::write_transaction;
+use crate::docs_db::{self, Section};
+use crate::error::ProjError;
+use crate::paths::get_project_root;
+use crate::projignore::{ProjIgnore, PROJIGNORE_FILE};
+use crate::schema_docs::{self, DocType};
+use crate::source_analyzer::{self, AnalysisCache, GeneratedSection, Language};
+
+/// Directories whose changes never affect generated docs
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".tracking",
+    "target",
+    "node_modules",
+    "vendor",
+    "__pycache__",
+    "build",
+    "dist",
+];
+
+/// Source extensions the analyzer understands
+const LANGUAGES: [Language; 4] = [
+    Language::Rust,
+    Language::Python,
+    Language::TypeScript,
+    Language::Go,
+];
+
+/// What one update did to the docs database
+#[derive(Default)]
+struct Update {
+    /// (section_id, title)
+    added: Vec<(String, String)>,
+    updated: Vec<(String, String)>,
+    removed: Vec<(String, String)>,
+    /// Generated sections whose number is taken by a manual section
+    skipped: Vec<(String, String)>,
+}
+
+impl Update {
+    fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.updated.is_empty()
+            && self.removed.is_empty()
+            && self.skipped.is_empty()
+    }
+}
+
+pub fn run(debounce_ms: u64) -> Result<()> {
+    let project_root = get_project_root()?;
+    let db_path = match docs_db::find_docs_db(&project_root) {
+        Some(path) => path,
+        None => return Err(ProjError::DocsDbMissing.into()),
+    };
+    let conn = docs_db::open_docs_db(&db_path)?;
+    let db_name = db_path
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let debounce = Duration::from_millis(debounce_ms.max(50));
+
+    let generated_from = schema_docs::get_meta(&conn, "generated_from")?;
+    match generated_from.as_deref() {
+        Some("source_analysis") => watch_sources(&project_root, &conn, &db_name, debounce),
+        Some(docs_openapi::GENERATED_FROM) => watch_spec(&project_root, &conn, &db_name, debounce),
+        _ => Err(ProjError::InvalidInput(
+            "This database was not generated from source analysis or an OpenAPI spec. Watch only works for databases created with 'proj docs init --generate' or '--import-openapi'."
+                .to_string(),
+        )
+        .into()),
+    }
+}
+
+fn watch_sources(
+    project_root: &Path,
+    conn: &Connection,
+    db_name: &str,
+    debounce: Duration,
+) -> Result<()> {
+    let doc_type = schema_docs::get_meta(conn, "doc_type")?
+        .and_then(|t| DocType::from_str(&t))
+        .unwrap_or(DocType::Architecture);
+
+    // Bring the database up to date before waiting for changes
+    let mut cache = AnalysisCache::new();
+    let structure = source_analyzer::analyze_project_cached(project_root, &mut cache)?;
+    let update = apply(
+        conn,
+        &source_analyzer::generate_sections_for(&structure, doc_type),
+    )?;
+
+    eprintln!(
+        "Watching {} source files for {} (Ctrl-C to stop)",
+        structure.file_count,
+        db_name.cyan()
+    );
+    if !update.is_empty() {
+        print_update("initial sync", &update);
+    }
+
+    let mut ignore = ProjIgnore::load(project_root);
+    watch(
+        project_root,
+        project_root,
+        RecursiveMode::Recursive,
+        conn,
+        debounce,
+        |changed| {
+            if changed.iter().any(|p| p.ends_with(PROJIGNORE_FILE)) {
+                ignore = ProjIgnore::load(project_root);
+            }
+            changed
+                .into_iter()
+                .filter(|p| is_relevant(project_root, &ignore, p))
+                .collect()
+        },
+        || {
+            let structure = source_analyzer::analyze_project_cached(project_root, &mut cache)?;
+            Ok(source_analyzer::generate_sections_for(&structure, doc_type))
+        },
+    )
+}
+
+fn watch_spec(
+    project_root: &Path,
+    conn: &Connection,
+    db_name: &str,
+    debounce: Duration,
+) -> Result<()> {
+    let spec_path = docs_openapi::spec_path(conn, project_root)?;
+    let relative = spec_path
+        .strip_prefix(project_root)
+        .unwrap_or(&spec_path)
+        .to_string_lossy()
+        .to_string();
+    let regenerate = || {
+        let (content, sections) = docs_openapi::load(&spec_path, &relative)?;
+        docs_openapi::record_hash(conn, &content)?;
+        Ok(sections)
+    };
+
+    let update = apply(conn, &regenerate()?)?;
+    eprintln!(
+        "Watching {} for {} (Ctrl-C to stop)",
+        relative,
+        db_name.cyan()
+    );
+    if !update.is_empty() {
+        print_update("initial sync", &update);
+    }
+
+    // Watch the directory: editors often save by replacing the file
+    let spec_dir = spec_path.parent().unwrap_or(project_root);
+    watch(
+        project_root,
+        spec_dir,
+        RecursiveMode::NonRecursive,
+        conn,
+        debounce,
+        |changed| changed.into_iter().filter(|p| *p == spec_path).collect(),
+        regenerate,
+    )
+}
+
+/// Regenerate and apply sections whenever relevant files change, until interrupted
+fn watch(
+    project_root: &Path,
+    target: &Path,
+    mode: RecursiveMode,
+    conn: &Connection,
+    debounce: Duration,
+    mut relevant: impl FnMut(BTreeSet<PathBuf>) -> Vec<PathBuf>,
+    mut regenerate: impl FnMut() -> Result<Vec<GeneratedSection>>,
+) -> Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher =
+        notify::recommended_watcher(tx).with_context(|| "Failed to start file watcher")?;
+    watcher
+        .watch(target, mode)
+        .with_context(|| format!("Failed to watch {}", target.display()))?;
+
+    // Each recv blocks until something happens; the inner loop collects the
+    // burst of events a single save produces
+    while let Ok(event) = rx.recv() {
+        let mut changed = BTreeSet::new();
+        let mut next = Some(event);
+        while let Some(event) = next {
+            if let Ok(event) = event {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    changed.extend(event.paths);
+                }
+            }
+            next = rx.recv_timeout(debounce).ok();
+        }
+
+        let relevant = relevant(changed);
+        if relevant.is_empty() {
+            continue;
+        }
+
+        let result = regenerate().and_then(|sections| apply(conn, &sections));
+        let label = describe(project_root, &relevant);
+        match result {
+            Ok(update) if update.is_empty() => {
+                println!("{} {} {}", timestamp(), label, "(no doc changes)".dimmed())
+            }
+            Ok(update) => print_update(&label, &update),
+            // Keep watching; the next save may well fix it
+            Err(e) => eprintln!("{} {} {} {:#}", timestamp(), label, "✗".red(), e),
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether a changed path can affect generated docs
+fn is_relevant(project_root: &Path, ignore: &ProjIgnore, path: &Path) -> bool {
+    let Ok(relative) = path.strip_prefix(project_root) else {
+        return false;
+    };
+    if relative == Path::new(PROJIGNORE_FILE) {
+        return true;
+    }
+    let skipped = relative.components().any(|c| {
+        let name = c.as_os_str().to_string_lossy();
+        SKIP_DIRS.contains(&name.as_ref())
+    });
+    let is_source = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| LANGUAGES.iter().any(|l| l.extensions().contains(&ext)));
+    is_source && !skipped && !ignore.is_ignored(relative, false)
+}
+
+/// Write regenerated sections, matching them to existing generated sections
+/// by title and source file so renumbering alone doesn't count as a change
+fn apply(conn: &Connection, sections: &[GeneratedSection]) -> Result<Update> {
+    let existing = docs_db::get_all_sections(conn)?;
+    let manual_ids: BTreeSet<&str> = existing
+        .iter()
+        .filter(|s| !s.generated)
+        .map(|s| s.section_id.as_str())
+        .collect();
+    let mut previous: HashMap<(String, Option<String>), Vec<&Section>> = HashMap::new();
+    for section in existing.iter().filter(|s| s.generated) {
+        previous
+            .entry((section.title.clone(), section.source_file.clone()))
+            .or_default()
+            .push(section);
+    }
+
+    let mut update = Update::default();
+    let mut matched = Vec::new();
+    let mut added = Vec::new();
+    for section in sections {
+        let key = (section.title.clone(), section.source_file.clone());
+        match previous.get_mut(&key).and_then(|found| found.pop()) {
+            Some(old) => matched.push((old, section)),
+            None => added.push(section),
+        }
+    }
+
+    let tx = write_transaction(conn)?;
+
+    let mut unmatched: Vec<&Section> = previous.into_values().flatten().collect();
+    unmatched.sort_by_key(|s| s.sort_order);
+    for old in unmatched {
+        tx.execute("DELETE FROM sections WHERE id = ?1", [old.id])?;
+        update
+            .removed
+            .push((old.section_id.clone(), old.title.clone()));
+    }
+
+    // Park renumbered sections on temporary ids so the swaps don't collide
+    for (old, new) in &matched {
+        if old.section_id != new.section_id {
+            tx.execute(
+                "UPDATE sections SET section_id = '~' || section_id WHERE id = ?1",
+                [old.id],
+            )?;
+        }
+    }
+
+    let now = Utc::now().format("%Y-%m-%dT%H:%M:%S%.6f").to_string();
+    for (old, new) in &matched {
+        let content_changed = old.content != new.content;
+        if !content_changed
+            && old.section_id == new.section_id
+            && old.level == new.level
+            && old.sort_order == new.sort_order
+        {
+            continue;
+        }
+        tx.execute(
+            r#"UPDATE sections
+               SET section_id = ?1, level = ?2, sort_order = ?3, content = ?4, word_count = ?5, updated_at = ?6
+               WHERE id = ?7"#,
+            params![
+                new.section_id,
+                new.level,
+                new.sort_order,
+                new.content,
+                new.content.split_whitespace().count() as i32,
+                now,
+                old.id,
+            ],
+        )?;
+        if content_changed {
+            update
+                .updated
+                .push((new.section_id.clone(), new.title.clone()));
+        }
+    }
+
+    for section in added {
+        if manual_ids.contains(section.section_id.as_str()) {
+            update
+                .skipped
+                .push((section.section_id.clone(), section.title.clone()));
+            continue;
+        }
+        docs_db::insert_section(
+            &tx,
+            &section.section_id,
+            &section.title,
+            None,
+            section.level,
+            section.sort_order,
+            &section.content,
+            section.generated,
+            section.source_file.as_deref(),
+        )?;
+        update
+            .added
+            .push((section.section_id.clone(), section.title.clone()));
+    }
+
+    if !update.is_empty() {
+        schema_docs::set_meta(&tx, "refreshed_at", &now)?;
+    }
+    tx.commit()?;
+
+    Ok(update)
+}
+
+fn print_update(label: &str, update: &Update) {
+    println!("{} {}", timestamp(), label);
+    for (id, title) in &update.added {
+        println!("  {} {} {}", "+".green(), id, title);
+    }
+    for (id, title) in &update.updated {
+        println!("  {} {} {}", "~".yellow(), id, title);
+    }
+    for (id, title) in &update.removed {
+        println!("  {} {} {}", "-".red(), id, title);
+    }
+    for (id, title) in &update.skipped {
+        println!(
+            "  {} {} {} {}",
+            "!".yellow(),
+            id,
+            title,
+            "(number taken by a manual section - run 'proj docs refresh --force')".dimmed()
+        );
+    }
+}
+
+/// The changed file, or a count when several changed together
+fn describe(project_root: &Path, paths: &[PathBuf]) -> String {
+    match paths {
+        [path] => path
+            .strip_prefix(project_root)
+            .unwrap_or(path)
+            .display()
+            .to_string(),
+        _ => format!("{} files changed", paths.len()),
+    }
+}
+
+fn timestamp() -> String {
+    Utc::now()
+        .format("%H:%M:%S")
+        .to_string()
+        .dimmed()
+        .to_string()
+}
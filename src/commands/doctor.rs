@@ -0,0 +1,555 @@
+// Doctor command - environment and database health check, with safe repairs
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::config::{ProjectConfig, Registry};
+use crate::database::{get_schema_version, open_database, write_transaction};
+use crate::docs_db::{is_valid_docs_db, open_docs_db};
+use crate::paths::{ensure_dir, get_project_root, get_registry_path};
+use crate::SCHEMA_VERSION;
+
+/// Docs database suffixes, as recognized by find_docs_db
+const DOC_TYPES: [&str; 5] = ["architecture", "framework", "guide", "api", "spec"];
+
+/// Rows shown per problem before summarizing the rest
+const SHOW_LIMIT: usize = 5;
+
+#[derive(Clone, Copy, PartialEq)]
+enum Level {
+    Ok,
+    Info,
+    Warn,
+    Error,
+}
+
+/// A repair `--fix` knows how to make safely
+enum Fix {
+    UpgradeSchema,
+    ClearOrphans(Vec<Orphan>),
+    PruneRegistry(Vec<String>),
+}
+
+/// A row whose foreign key points at a missing parent
+struct Orphan {
+    table: String,
+    rowid: i64,
+    column: String,
+    parent: String,
+}
+
+struct Finding {
+    level: Level,
+    message: String,
+    hint: Option<String>,
+    fix: Option<Fix>,
+}
+
+impl Finding {
+    fn new(level: Level, message: impl Into<String>) -> Self {
+        Self {
+            level,
+            message: message.into(),
+            hint: None,
+            fix: None,
+        }
+    }
+
+    fn hint(mut self, hint: impl Into<String>) -> Self {
+        self.hint = Some(hint.into());
+        self
+    }
+
+    fn fix(mut self, fix: Fix) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+}
+
+pub fn run(fix: bool) -> Result<()> {
+    let root = get_project_root().ok();
+
+    let mut sections = Vec::new();
+    match &root {
+        Some(root) => {
+            sections.push(("Project", check_project(root)));
+            sections.push(("Documentation", check_docs(root)));
+        }
+        None => sections.push((
+            "Project",
+            vec![Finding::new(Level::Info, "Not in a proj-tracked project")
+                .hint("Run 'proj doctor' inside a project to check its databases")],
+        )),
+    }
+    sections.push(("Registry", check_registry()));
+    sections.push(("Environment", check_environment(root.as_deref())));
+
+    for (title, findings) in &sections {
+        println!("{}", title.bold());
+        for finding in findings {
+            let icon = match finding.level {
+                Level::Ok => "✓".green(),
+                Level::Info => "○".white(),
+                Level::Warn => "⚠".yellow(),
+                Level::Error => "✗".red(),
+            };
+            println!("  {} {}", icon, finding.message);
+            if let Some(hint) = &finding.hint {
+                println!("      {}", hint.dimmed());
+            }
+        }
+        println!();
+    }
+
+    let findings: Vec<Finding> = sections.into_iter().flat_map(|(_, f)| f).collect();
+    let count = |level| findings.iter().filter(|f| f.level == level).count();
+    let (errors, warnings) = (count(Level::Error), count(Level::Warn));
+    let fixable = findings.iter().filter(|f| f.fix.is_some()).count();
+
+    if errors == 0 && warnings == 0 {
+        println!("{} Everything looks healthy", "✓".green());
+        return Ok(());
+    }
+
+    println!("Found {} error(s) and {} warning(s)", errors, warnings);
+
+    if !fix {
+        if fixable > 0 {
+            println!(
+                "Run '{}' to repair {} of them.",
+                "proj doctor --fix".cyan(),
+                fixable
+            );
+        }
+    } else if fixable == 0 {
+        println!("Nothing here can be repaired automatically.");
+    } else {
+        println!("\n{}", "Repairing".bold());
+        let mut unfixed_errors = errors;
+        for finding in findings {
+            let Some(repair) = finding.fix else {
+                continue;
+            };
+            match apply(repair, root.as_deref()) {
+                Ok(done) => {
+                    println!("  {} {}", "✓".green(), done);
+                    if finding.level == Level::Error {
+                        unfixed_errors -= 1;
+                    }
+                }
+                Err(e) => println!("  {} {}: {:#}", "✗".red(), finding.message, e),
+            }
+        }
+        if unfixed_errors == 0 {
+            return Ok(());
+        }
+        bail!("{} error(s) need manual attention", unfixed_errors);
+    }
+
+    if errors > 0 {
+        bail!("{} error(s) found", errors);
+    }
+    Ok(())
+}
+
+/// Config, tracking database integrity, schema version, and orphaned references
+fn check_project(root: &Path) -> Vec<Finding> {
+    let mut findings = Vec::new();
+    let tracking = root.join(".tracking");
+
+    let config_path = tracking.join("config.json");
+    match std::fs::read_to_string(&config_path)
+        .map_err(anyhow::Error::from)
+        .and_then(|c| ProjectConfig::from_json(&c))
+    {
+        Ok(config) => findings.push(Finding::new(
+            Level::Ok,
+            format!("config.json is valid ({})", config.name),
+        )),
+        Err(e) => findings.push(
+            Finding::new(Level::Error, format!("config.json can't be read: {}", e))
+                .hint(format!("Check {}", config_path.display())),
+        ),
+    }
+
+    let db_path = tracking.join("tracking.db");
+    if !db_path.exists() {
+        findings.push(
+            Finding::new(Level::Error, "tracking.db is missing")
+                .hint("Restore one with 'proj rollback --list', or re-create with 'proj init'"),
+        );
+        return findings;
+    }
+
+    let conn = match open_database(&db_path) {
+        Ok(conn) => conn,
+        Err(e) => {
+            findings.push(Finding::new(
+                Level::Error,
+                format!("tracking.db can't be opened: {:#}", e),
+            ));
+            return findings;
+        }
+    };
+
+    findings.push(integrity(&conn, "tracking.db"));
+
+    let version = get_schema_version(&conn)
+        .ok()
+        .flatten()
+        .unwrap_or_else(|| "1.0".to_string());
+    findings.push(match compare_versions(&version, SCHEMA_VERSION) {
+        std::cmp::Ordering::Equal => Finding::new(
+            Level::Ok,
+            format!("Schema v{} matches this binary", version),
+        ),
+        std::cmp::Ordering::Less => Finding::new(
+            Level::Warn,
+            format!(
+                "Schema v{} is behind this binary (v{})",
+                version, SCHEMA_VERSION
+            ),
+        )
+        .hint("'proj upgrade' brings it up to date (a backup is made first)")
+        .fix(Fix::UpgradeSchema),
+        std::cmp::Ordering::Greater => Finding::new(
+            Level::Error,
+            format!(
+                "Schema v{} is newer than this binary (v{})",
+                version, SCHEMA_VERSION
+            ),
+        )
+        .hint("Run 'proj update' - older binaries can misread newer databases"),
+    });
+
+    match orphans(&conn) {
+        Ok(orphans) if orphans.is_empty() => {
+            findings.push(Finding::new(Level::Ok, "No orphaned references"))
+        }
+        Ok(orphans) => {
+            let (fixable, stuck): (Vec<Orphan>, Vec<Orphan>) =
+                orphans.into_iter().partition(|o| is_nullable(&conn, o));
+            if !fixable.is_empty() {
+                findings.push(
+                    Finding::new(
+                        Level::Warn,
+                        format!(
+                            "{} orphaned reference(s): {}",
+                            fixable.len(),
+                            describe_orphans(&fixable)
+                        ),
+                    )
+                    .hint("--fix clears the dangling references; the rows themselves are kept")
+                    .fix(Fix::ClearOrphans(fixable)),
+                );
+            }
+            if !stuck.is_empty() {
+                findings.push(Finding::new(
+                    Level::Error,
+                    format!(
+                        "{} orphaned reference(s) in required columns: {}",
+                        stuck.len(),
+                        describe_orphans(&stuck)
+                    ),
+                ));
+            }
+        }
+        Err(e) => findings.push(Finding::new(
+            Level::Warn,
+            format!("Foreign key check failed: {:#}", e),
+        )),
+    }
+
+    findings
+}
+
+/// Every docs database in the project root must open, pass integrity, and have the docs schema
+fn check_docs(root: &Path) -> Vec<Finding> {
+    let mut candidates: Vec<PathBuf> = std::fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name().and_then(|n| n.to_str()).is_some_and(|n| {
+                        DOC_TYPES.iter().any(|t| n.ends_with(&format!("_{}.db", t)))
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    candidates.sort();
+
+    if candidates.is_empty() {
+        return vec![Finding::new(Level::Info, "No documentation database")
+            .hint("Optional - see 'proj docs init'")];
+    }
+
+    let mut findings = Vec::new();
+    for path in candidates {
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        match open_docs_db(&path) {
+            Ok(conn) => {
+                findings.push(integrity(&conn, &name));
+                if is_valid_docs_db(&conn) {
+                    findings.push(Finding::new(
+                        Level::Ok,
+                        format!("{} has the docs schema", name),
+                    ));
+                } else {
+                    findings.push(
+                        Finding::new(Level::Warn, format!("{} is missing the docs tables", name))
+                            .hint("proj ignores it; re-import with 'proj docs init --import'"),
+                    );
+                }
+            }
+            Err(e) => findings.push(Finding::new(
+                Level::Error,
+                format!("{} can't be opened: {:#}", name, e),
+            )),
+        }
+    }
+    findings
+}
+
+/// Registry entries whose project has moved or been deleted
+fn check_registry() -> Vec<Finding> {
+    let registry = match load_registry() {
+        Ok(Some(registry)) => registry,
+        Ok(None) => return vec![Finding::new(Level::Info, "No projects registered")],
+        Err(e) => {
+            return vec![Finding::new(
+                Level::Error,
+                format!("registry.json can't be read: {:#}", e),
+            )]
+        }
+    };
+
+    let stale: Vec<String> = registry
+        .registered_projects
+        .iter()
+        .filter(|p| !Path::new(&p.path).join(".tracking").is_dir())
+        .map(|p| p.path.clone())
+        .collect();
+
+    let total = registry.registered_projects.len();
+    if stale.is_empty() {
+        return vec![Finding::new(
+            Level::Ok,
+            format!("{} registered project(s), all present", total),
+        )];
+    }
+
+    let mut message = format!(
+        "{} of {} registered project(s) no longer exist:",
+        stale.len(),
+        total
+    );
+    for path in stale.iter().take(SHOW_LIMIT) {
+        message.push_str(&format!("\n      {}", path));
+    }
+    if stale.len() > SHOW_LIMIT {
+        message.push_str(&format!(
+            "\n      ... and {} more",
+            stale.len() - SHOW_LIMIT
+        ));
+    }
+    vec![Finding::new(Level::Warn, message)
+        .hint("--fix removes them from the registry (nothing on disk is touched)")
+        .fix(Fix::PruneRegistry(stale))]
+}
+
+/// git on PATH and shell integration
+fn check_environment(root: Option<&Path>) -> Vec<Finding> {
+    let mut findings = Vec::new();
+
+    let git = Command::new("git")
+        .arg("--version")
+        .output()
+        .ok()
+        .filter(|o| o.status.success())
+        .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string());
+    let uses_git = root.is_some_and(crate::git::is_repo);
+    findings.push(match git {
+        Some(version) => Finding::new(Level::Ok, version),
+        None if uses_git => Finding::new(Level::Error, "git is not on PATH")
+            .hint("Commit tracking and auto-commit need git"),
+        None => Finding::new(Level::Info, "git is not on PATH"),
+    });
+
+    findings.push(if crate::commands::shell::is_installed() {
+        Finding::new(Level::Ok, "Shell integration installed")
+    } else {
+        Finding::new(Level::Info, "Shell integration not installed")
+            .hint("Optional - 'proj shell install' starts sessions when you cd into a project")
+    });
+
+    findings
+}
+
+fn apply(fix: Fix, root: Option<&Path>) -> Result<String> {
+    match fix {
+        Fix::UpgradeSchema => {
+            crate::commands::upgrade::run(false, false, false, None)?;
+            Ok(format!("Upgraded schema to v{}", SCHEMA_VERSION))
+        }
+        Fix::ClearOrphans(orphans) => {
+            let root = root.context("Not in a project")?;
+            let conn = open_database(&root.join(".tracking").join("tracking.db"))?;
+            let tx = write_transaction(&conn)?;
+            for o in &orphans {
+                tx.execute(
+                    &format!(
+                        "UPDATE \"{}\" SET \"{}\" = NULL WHERE rowid = ?1",
+                        o.table, o.column
+                    ),
+                    [o.rowid],
+                )?;
+            }
+            tx.commit()?;
+            Ok(format!("Cleared {} orphaned reference(s)", orphans.len()))
+        }
+        Fix::PruneRegistry(stale) => {
+            let mut registry = load_registry()?.unwrap_or_default();
+            registry
+                .registered_projects
+                .retain(|p| !stale.contains(&p.path));
+            save_registry(&registry)?;
+            Ok(format!(
+                "Removed {} missing project(s) from the registry",
+                stale.len()
+            ))
+        }
+    }
+}
+
+fn integrity(conn: &Connection, name: &str) -> Finding {
+    let problems: Result<Vec<String>, _> =
+        conn.prepare("PRAGMA integrity_check").and_then(|mut stmt| {
+            stmt.query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()
+        });
+
+    match problems {
+        Ok(rows) if rows.len() == 1 && rows[0] == "ok" => {
+            Finding::new(Level::Ok, format!("{} integrity check passed", name))
+        }
+        Ok(rows) => {
+            let mut message = format!("{} failed integrity check:", name);
+            for row in rows.iter().take(SHOW_LIMIT) {
+                message.push_str(&format!("\n      {}", row));
+            }
+            Finding::new(Level::Error, message)
+                .hint("Restore a backup with 'proj rollback --list', or 'sqlite3 .recover'")
+        }
+        Err(e) => Finding::new(
+            Level::Error,
+            format!("{} integrity check failed: {}", name, e),
+        ),
+    }
+}
+
+fn orphans(conn: &Connection) -> Result<Vec<Orphan>> {
+    let mut stmt = conn.prepare("PRAGMA foreign_key_check")?;
+    let violations = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut orphans = Vec::new();
+    for (table, rowid, parent, fk_id) in violations {
+        // WITHOUT ROWID tables can't be addressed by rowid; none of ours are
+        let Some(rowid) = rowid else {
+            continue;
+        };
+        let column: String = conn.query_row(
+            &format!(
+                "SELECT \"from\" FROM pragma_foreign_key_list('{}') WHERE id = ?1",
+                table
+            ),
+            [fk_id],
+            |row| row.get(0),
+        )?;
+        orphans.push(Orphan {
+            table,
+            rowid,
+            column,
+            parent,
+        });
+    }
+    Ok(orphans)
+}
+
+fn is_nullable(conn: &Connection, orphan: &Orphan) -> bool {
+    let notnull: Option<i64> = conn
+        .query_row(
+            &format!(
+                "SELECT \"notnull\" FROM pragma_table_info('{}') WHERE name = ?1",
+                orphan.table
+            ),
+            [&orphan.column],
+            |row| row.get(0),
+        )
+        .ok();
+    notnull == Some(0)
+}
+
+/// "tasks.session_id → sessions (3), blockers.related_task_id → tasks (1)"
+fn describe_orphans(orphans: &[Orphan]) -> String {
+    let mut groups: Vec<(String, usize)> = Vec::new();
+    for o in orphans {
+        let key = format!("{}.{} → {}", o.table, o.column, o.parent);
+        match groups.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, n)) => *n += 1,
+            None => groups.push((key, 1)),
+        }
+    }
+    groups
+        .iter()
+        .map(|(k, n)| format!("{} ({})", k, n))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Compare dotted versions numerically ("1.9" < "1.10")
+fn compare_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    let parse = |v: &str| -> Vec<u32> { v.split('.').filter_map(|s| s.parse().ok()).collect() };
+    parse(a).cmp(&parse(b))
+}
+
+/// Load the global registry (None if it doesn't exist yet)
+fn load_registry() -> Result<Option<Registry>> {
+    let registry_path = get_registry_path()?;
+    if !registry_path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(&registry_path)
+        .with_context(|| format!("Failed to read {:?}", registry_path))?;
+    let registry: Registry =
+        serde_json::from_str(&content).with_context(|| "Failed to parse registry.json")?;
+    Ok(Some(registry))
+}
+
+/// Save registry to disk
+fn save_registry(registry: &Registry) -> Result<()> {
+    let registry_path = get_registry_path()?;
+    if let Some(parent) = registry_path.parent() {
+        ensure_dir(&parent.to_path_buf())?;
+    }
+    let content = serde_json::to_string_pretty(registry)?;
+    std::fs::write(&registry_path, content).with_context(|| "Failed to write registry.json")?;
+    Ok(())
+}
@@ -0,0 +1,146 @@
+// Embed command - compute vectors for decisions, notes, and doc sections for semantic search
+
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use crate::config::EmbeddingConfig;
+use crate::database::{open_database, write_transaction};
+use crate::embeddings::{self, Provider};
+use crate::paths::{get_project_root, get_tracking_db_path};
+
+/// Embed new and changed items; with `rebuild`, embed everything again
+pub fn build(rebuild: bool) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    embeddings::require_table(&conn)?;
+    let root = get_project_root()?;
+
+    let provider = Provider::load()?;
+    let model_id = provider.model_id();
+    let items = embeddings::embeddable_items(&conn, &root)?;
+
+    let existing = embeddings::stored(&conn)?;
+
+    let pending: Vec<_> = items
+        .iter()
+        .map(|item| (item, embeddings::content_hash(&item.text)))
+        .filter(|(item, hash)| {
+            rebuild
+                || existing
+                    .get(&(item.table_name.to_string(), item.record_id))
+                    .map_or(true, |(model, old_hash)| {
+                        *model != model_id || old_hash != hash
+                    })
+        })
+        .collect();
+
+    if !pending.is_empty() {
+        println!(
+            "Embedding {} item(s) with {}...",
+            pending.len(),
+            model_id.cyan()
+        );
+        let texts: Vec<String> = pending.iter().map(|(item, _)| item.text.clone()).collect();
+        let vectors = provider.embed(&texts)?;
+
+        let tx = write_transaction(&conn)?;
+        for ((item, hash), vector) in pending.iter().zip(&vectors) {
+            tx.execute(
+                "INSERT OR REPLACE INTO embeddings (table_name, record_id, model, content_hash, dimensions, vector)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    item.table_name,
+                    item.record_id,
+                    model_id,
+                    hash,
+                    vector.len() as i64,
+                    embeddings::to_blob(vector)
+                ],
+            )?;
+        }
+        tx.commit()?;
+    }
+
+    // Drop vectors for items that were deleted, superseded, or archived
+    let current: HashSet<(String, i64)> = items
+        .iter()
+        .map(|item| (item.table_name.to_string(), item.record_id))
+        .collect();
+    let mut removed = 0;
+    for key in existing.keys().filter(|k| !current.contains(*k)) {
+        removed += conn.execute(
+            "DELETE FROM embeddings WHERE table_name = ?1 AND record_id = ?2",
+            rusqlite::params![key.0, key.1],
+        )?;
+    }
+
+    println!(
+        "{} Embedded {} item(s), {} unchanged, {} removed.",
+        "✓".green(),
+        pending.len(),
+        items.len() - pending.len(),
+        removed
+    );
+    if !items.is_empty() {
+        println!("Search with: proj context \"<topic>\" --semantic");
+    }
+    Ok(())
+}
+
+pub fn status() -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    embeddings::require_table(&conn)?;
+    let root = get_project_root()?;
+
+    println!("{}", "Embedding Status".bold());
+    println!();
+
+    let model_id = match EmbeddingConfig::load()? {
+        Some(config) => {
+            let provider = Provider::from_config(&config)?;
+            let model_id = provider.model_id();
+            println!("  Provider: {}", model_id);
+            Some(model_id)
+        }
+        None => {
+            println!("  Provider: {}", "not configured".yellow());
+            println!("  Create ~/.proj/embeddings.json to enable 'proj embed build'.");
+            None
+        }
+    };
+
+    let items = embeddings::embeddable_items(&conn, &root)?;
+    let stored = embeddings::stored(&conn)?;
+
+    let is_current = |item: &embeddings::Embeddable| {
+        stored
+            .get(&(item.table_name.to_string(), item.record_id))
+            .is_some_and(|(model, hash)| {
+                Some(model) == model_id.as_ref() && *hash == embeddings::content_hash(&item.text)
+            })
+    };
+
+    println!();
+    let mut out_of_date = 0;
+    for table in ["decisions", "context_notes", "sections"] {
+        let in_table: Vec<_> = items.iter().filter(|i| i.table_name == table).collect();
+        let current = in_table.iter().filter(|item| is_current(item)).count();
+        out_of_date += in_table.len() - current;
+        println!("  {:<14} {}/{} embedded", table, current, in_table.len());
+    }
+
+    if out_of_date > 0 && model_id.is_some() {
+        println!();
+        println!(
+            "{} {} item(s) missing or out of date. Run 'proj embed build'.",
+            "⚠".yellow(),
+            out_of_date
+        );
+    }
+    Ok(())
+}
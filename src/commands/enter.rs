@@ -7,17 +7,13 @@
 // This enables autonomous tracking via shell hooks without cluttering output.
 
 use anyhow::{Context, Result};
-use chrono::{Duration, Utc};
 use colored::Colorize;
 
 use crate::commands::status;
 use crate::commands::update_check;
 use crate::database::open_database;
 use crate::paths::get_tracking_db_path;
-use crate::session::{get_active_session, get_or_create_session_with_info};
-
-/// Stale session threshold in hours (matches session.rs)
-const STALE_SESSION_HOURS: i64 = 8;
+use crate::session::{get_active_session, get_or_create_session_with_info, StalePolicy};
 
 pub fn run() -> Result<()> {
     // Open the tracking database
@@ -28,10 +24,7 @@ pub fn run() -> Result<()> {
     // First, check if there's an active session that's NOT stale
     // We do this check separately to avoid creating a session unnecessarily
     if let Some(session) = get_active_session(&conn)? {
-        let now = Utc::now();
-        let session_age = now - session.started_at;
-
-        if session_age <= Duration::hours(STALE_SESSION_HOURS) {
+        if !StalePolicy::current().is_stale(&session) {
             // Active, non-stale session exists - exit silently
             return Ok(());
         }
@@ -42,11 +35,7 @@ pub fn run() -> Result<()> {
 
     // If a stale session was auto-closed, notify the user
     if let Some(closed) = session_result.auto_closed_session {
-        println!(
-            "{} Previous session #{} was stale (8+ hours). Auto-closed.",
-            "⚠".yellow(),
-            closed.session_id
-        );
+        println!("{} {}", "⚠".yellow(), crate::session::stale_notice(&closed));
         println!(
             "{} Started new session #{}",
             "✓".green(),
@@ -57,7 +46,7 @@ pub fn run() -> Result<()> {
 
     // Show full context for the new session (reuse status command logic)
     // Pass full=true to ensure full context is shown
-    status::run(false, false, true)?;
+    status::run(false, false, true, false)?;
 
     // Check for updates (cached, runs at most once per day)
     update_check::check_and_notify();
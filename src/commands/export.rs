@@ -1,20 +1,43 @@
-// Export command - export session history
+// Export command - export session history and tracking data
 
-use anyhow::{Context, Result};
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
+use rusqlite::types::Value;
+use rusqlite::Connection;
 use serde::Serialize;
 
 use crate::config::ProjectConfig;
-use crate::database::open_database;
+use crate::database::{open_database, table_columns};
+use crate::encoding::hex;
 use crate::paths::{get_config_path, get_tracking_db_path};
+use crate::schema::init_tracking_schema;
+use crate::text::truncate;
+
+/// Exportable tables: (name used on the command line, table, column used for --since).
+/// Session-local bookkeeping (activity_log, audit_log, context_snapshots, the FTS index) is never exported.
+const EXPORT_TABLES: &[(&str, &str, &str)] = &[
+    ("sessions", "sessions", "started_at"),
+    ("tasks", "tasks", "created_at"),
+    ("decisions", "decisions", "created_at"),
+    ("notes", "context_notes", "created_at"),
+    ("blockers", "blockers", "created_at"),
+    ("questions", "questions", "created_at"),
+    ("commits", "git_commits", "committed_at"),
+    ("tags", "item_tags", "created_at"),
+    ("attachments", "attachments", "created_at"),
+];
 
 #[derive(Serialize)]
 struct ExportData {
     project: ProjectInfo,
     exported_at: String,
-    sessions: Vec<SessionExport>,
-    decisions: Vec<DecisionExport>,
-    tasks: Vec<TaskExport>,
+    schema_version: String,
+    since: Option<String>,
+    #[serde(flatten)]
+    tables: BTreeMap<String, Vec<serde_json::Map<String, serde_json::Value>>>,
 }
 
 #[derive(Serialize)]
@@ -23,34 +46,62 @@ struct ProjectInfo {
     project_type: String,
 }
 
-#[derive(Serialize)]
+/// Rows of one table, in column order
+struct TableRows {
+    name: &'static str,
+    columns: Vec<String>,
+    rows: Vec<Vec<Value>>,
+}
+
 struct SessionExport {
     session_id: i64,
     started_at: String,
     ended_at: Option<String>,
     summary: Option<String>,
-    agent: Option<String>,
     status: String,
 }
 
-#[derive(Serialize)]
 struct DecisionExport {
     topic: String,
     decision: String,
     rationale: Option<String>,
-    created_at: String,
 }
 
-#[derive(Serialize)]
-struct TaskExport {
+/// A finished session, for the iCalendar export
+struct CalendarSession {
+    session_id: i64,
+    uuid: Option<String>,
+    started_at: String,
+    ended_at: String,
+    summary: Option<String>,
+    name: Option<String>,
+    agent: Option<String>,
+}
+
+/// A task with a due date, for the iCalendar export
+struct CalendarTask {
+    task_id: i64,
+    uuid: Option<String>,
     description: String,
     status: String,
     priority: Option<String>,
-    created_at: String,
+    due: String,
+    notes: Option<String>,
     completed_at: Option<String>,
 }
 
-pub fn run(format: String) -> Result<()> {
+struct TaskExport {
+    description: String,
+    status: String,
+    priority: Option<String>,
+}
+
+pub fn run(
+    format: String,
+    tables: Vec<String>,
+    since: Option<String>,
+    output: Option<String>,
+) -> Result<()> {
     // Load config
     let config = load_config()?;
 
@@ -58,110 +109,583 @@ pub fn run(format: String) -> Result<()> {
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)?;
 
-    // Gather data
-    let mut stmt = conn.prepare(
-        "SELECT session_id, started_at, ended_at, summary, agent, status
-         FROM sessions ORDER BY started_at",
-    )?;
-    let sessions: Vec<SessionExport> = stmt
-        .query_map([], |row| {
-            Ok(SessionExport {
-                session_id: row.get(0)?,
-                started_at: row.get(1)?,
-                ended_at: row.get(2)?,
-                summary: row.get(3)?,
-                agent: row.get(4)?,
-                status: row.get(5)?,
-            })
-        })?
-        .filter_map(|r| r.ok())
-        .collect();
+    let selected = select_tables(&tables)?;
+    let since = since.map(|s| normalize_since(&s)).transpose()?;
 
-    let mut stmt = conn.prepare(
-        "SELECT topic, decision, rationale, created_at
-         FROM decisions WHERE status = 'active' ORDER BY created_at",
-    )?;
-    let decisions: Vec<DecisionExport> = stmt
-        .query_map([], |row| {
-            Ok(DecisionExport {
-                topic: row.get(0)?,
-                decision: row.get(1)?,
-                rationale: row.get(2)?,
-                created_at: row.get(3)?,
-            })
+    match format.as_str() {
+        "json" => {
+            let json = render_json(&conn, &config, &selected, since.as_deref())?;
+            write_output(output.as_deref(), &json)?;
+        }
+        "csv" => export_csv(&conn, &selected, since.as_deref(), output.as_deref())?,
+        "sqlite" => {
+            let Some(output) = output else {
+                bail!("SQLite export needs --output <file>");
+            };
+            export_sqlite(&conn, &selected, since.as_deref(), Path::new(&output))?;
+        }
+        "md" => {
+            let md = render_markdown(&conn, &config, &selected, since.as_deref())?;
+            write_output(output.as_deref(), &md)?;
+        }
+        "ics" => {
+            let ics = render_ics(&conn, &config, &selected, since.as_deref())?;
+            write_output(output.as_deref(), &ics)?;
+        }
+        other => bail!(
+            "Unknown format '{}'. Use md, json, csv, sqlite, or ics.",
+            other
+        ),
+    }
+
+    Ok(())
+}
+
+/// Every exportable table as a JSON export, the format 'proj import' reads
+pub fn full_json(conn: &Connection, config: &ProjectConfig) -> Result<String> {
+    render_json(conn, config, EXPORT_TABLES, None)
+}
+
+fn render_json(
+    conn: &Connection,
+    config: &ProjectConfig,
+    selected: &[(&'static str, &'static str, &'static str)],
+    since: Option<&str>,
+) -> Result<String> {
+    let mut data = ExportData {
+        project: ProjectInfo {
+            name: config.name.clone(),
+            project_type: config.project_type.clone(),
+        },
+        exported_at: Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        schema_version: crate::SCHEMA_VERSION.to_string(),
+        since: since.map(|s| s.to_string()),
+        tables: BTreeMap::new(),
+    };
+    for &(name, table, date_column) in selected {
+        let t = read_table(conn, name, table, date_column, since)?;
+        data.tables.insert(name.to_string(), to_json_rows(&t));
+    }
+    Ok(serde_json::to_string_pretty(&data)? + "\n")
+}
+
+/// Resolve --tables into table specs (all tables when none are given)
+fn select_tables(tables: &[String]) -> Result<Vec<(&'static str, &'static str, &'static str)>> {
+    if tables.is_empty() {
+        return Ok(EXPORT_TABLES.to_vec());
+    }
+
+    let mut selected = Vec::new();
+    for requested in tables {
+        let requested = requested.trim();
+        let Some(spec) = EXPORT_TABLES
+            .iter()
+            .find(|(name, table, _)| *name == requested || *table == requested)
+        else {
+            let names: Vec<&str> = EXPORT_TABLES.iter().map(|(n, _, _)| *n).collect();
+            bail!(
+                "Unknown table '{}'. Available: {}",
+                requested,
+                names.join(", ")
+            );
+        };
+        if !selected.contains(spec) {
+            selected.push(*spec);
+        }
+    }
+    Ok(selected)
+}
+
+/// Accept YYYY-MM-DD or YYYY-MM-DD HH:MM:SS
+fn normalize_since(input: &str) -> Result<String> {
+    let input = input.trim();
+    if chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d").is_ok() {
+        return Ok(format!("{} 00:00:00", input));
+    }
+    if chrono::NaiveDateTime::parse_from_str(input, "%Y-%m-%d %H:%M:%S").is_ok() {
+        return Ok(input.to_string());
+    }
+    bail!("Invalid --since '{}'. Use YYYY-MM-DD.", input)
+}
+
+/// Read every column of a table, optionally limited to rows on or after `since`
+fn read_table(
+    conn: &Connection,
+    name: &'static str,
+    table: &str,
+    date_column: &str,
+    since: Option<&str>,
+) -> Result<TableRows> {
+    let sql = format!(
+        "SELECT * FROM {} WHERE ?1 IS NULL OR {} >= ?1 ORDER BY rowid",
+        table, date_column
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+    let count = columns.len();
+
+    let rows = stmt
+        .query_map([since], |row| {
+            (0..count)
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<Result<Vec<_>, _>>()
         })?
-        .filter_map(|r| r.ok())
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(TableRows {
+        name,
+        columns,
+        rows,
+    })
+}
+
+/// Column names of a table in the source database
+fn to_json_rows(t: &TableRows) -> Vec<serde_json::Map<String, serde_json::Value>> {
+    t.rows
+        .iter()
+        .map(|row| {
+            t.columns
+                .iter()
+                .zip(row)
+                .map(|(col, value)| {
+                    let v = match value {
+                        Value::Null => serde_json::Value::Null,
+                        Value::Integer(i) => serde_json::Value::from(*i),
+                        Value::Real(f) => serde_json::Value::from(*f),
+                        Value::Text(s) => serde_json::Value::from(s.as_str()),
+                        Value::Blob(b) => serde_json::Value::from(hex(b)),
+                    };
+                    (col.clone(), v)
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Write one CSV per table: to stdout for a single table, or <dir>/<table>.csv
+fn export_csv(
+    conn: &Connection,
+    selected: &[(&'static str, &'static str, &'static str)],
+    since: Option<&str>,
+    output: Option<&str>,
+) -> Result<()> {
+    let Some(dir) = output else {
+        if selected.len() != 1 {
+            bail!("CSV export of several tables needs --output <dir> (or a single --tables entry)");
+        }
+        let (name, table, date_column) = selected[0];
+        print!(
+            "{}",
+            to_csv(&read_table(conn, name, table, date_column, since)?)
+        );
+        return Ok(());
+    };
+
+    let dir = Path::new(dir);
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+
+    for &(name, table, date_column) in selected {
+        let t = read_table(conn, name, table, date_column, since)?;
+        let path = dir.join(format!("{}.csv", t.name));
+        std::fs::write(&path, to_csv(&t)).with_context(|| format!("Failed to write {:?}", path))?;
+        eprintln!("  {}: {} rows → {}", t.name, t.rows.len(), path.display());
+    }
+    Ok(())
+}
 
-    let mut stmt = conn.prepare(
-        "SELECT description, status, priority, created_at, completed_at
-         FROM tasks ORDER BY created_at",
-    )?;
-    let tasks: Vec<TaskExport> = stmt
-        .query_map([], |row| {
-            Ok(TaskExport {
-                description: row.get(0)?,
-                status: row.get(1)?,
-                priority: row.get(2)?,
-                created_at: row.get(3)?,
-                completed_at: row.get(4)?,
+fn to_csv(t: &TableRows) -> String {
+    let mut out = String::new();
+    let header: Vec<String> = t.columns.iter().map(|c| csv_field(c)).collect();
+    out.push_str(&header.join(","));
+    out.push('\n');
+
+    for row in &t.rows {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|value| match value {
+                Value::Null => String::new(),
+                Value::Integer(i) => i.to_string(),
+                Value::Real(f) => f.to_string(),
+                Value::Text(s) => csv_field(s),
+                Value::Blob(b) => hex(b),
             })
-        })?
-        .filter_map(|r| r.ok())
-        .collect();
+            .collect();
+        out.push_str(&fields.join(","));
+        out.push('\n');
+    }
+    out
+}
 
-    // Output based on format
-    match format.as_str() {
-        "json" => {
-            let export_data = ExportData {
-                project: ProjectInfo {
-                    name: config.name,
-                    project_type: config.project_type,
-                },
-                exported_at: Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
-                sessions,
-                decisions,
-                tasks,
-            };
-            println!("{}", serde_json::to_string_pretty(&export_data)?);
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Write a standalone tracking database containing only the selected data
+fn export_sqlite(
+    conn: &Connection,
+    selected: &[(&'static str, &'static str, &'static str)],
+    since: Option<&str>,
+    output: &Path,
+) -> Result<()> {
+    if output.exists() {
+        bail!("{} already exists", output.display());
+    }
+
+    // Full schema so the dump can be opened by proj; plain journal so it's a single file
+    {
+        let dump = Connection::open(output)
+            .with_context(|| format!("Failed to create {}", output.display()))?;
+        init_tracking_schema(&dump)?;
+    }
+
+    conn.execute("ATTACH DATABASE ?1 AS dump", [output.to_string_lossy()])?;
+    // Partial exports may reference rows (e.g., sessions) that were filtered out
+    conn.execute_batch("PRAGMA foreign_keys = OFF;")?;
+
+    let result = (|| -> Result<()> {
+        for &(name, table, date_column) in selected {
+            let cols = table_columns(conn, "main", table)?.join(", ");
+            let copied = conn.execute(
+                &format!(
+                    "INSERT INTO dump.{t} ({c}) SELECT {c} FROM main.{t} WHERE ?1 IS NULL OR {d} >= ?1",
+                    t = table,
+                    c = cols,
+                    d = date_column
+                ),
+                [since],
+            )?;
+            eprintln!("  {}: {} rows", name, copied);
+        }
+        // The dump's own audit triggers logged the copy
+        conn.execute("DELETE FROM dump.audit_log", [])?;
+        Ok(())
+    })();
+
+    conn.execute_batch("PRAGMA foreign_keys = ON;")?;
+    conn.execute("DETACH DATABASE dump", [])?;
+    result?;
+
+    eprintln!("Wrote {}", output.display());
+    Ok(())
+}
+
+/// Human-readable export of sessions, decisions, and tasks
+fn render_markdown(
+    conn: &Connection,
+    config: &ProjectConfig,
+    selected: &[(&'static str, &'static str, &'static str)],
+    since: Option<&str>,
+) -> Result<String> {
+    let includes = |name: &str| selected.iter().any(|(n, _, _)| *n == name);
+    let mut md = String::new();
+
+    md.push_str(&format!("# Project: {}\n\n", config.name));
+    md.push_str(&format!("Type: {}\n", config.project_type));
+    md.push_str(&format!(
+        "Exported: {}\n\n",
+        Utc::now().format("%Y-%m-%d %H:%M")
+    ));
+    if let Some(s) = since {
+        md.push_str(&format!("Since: {}\n\n", s));
+    }
+
+    if includes("sessions") {
+        let mut stmt = conn.prepare(
+            "SELECT session_id, started_at, ended_at, summary, status
+             FROM sessions WHERE ?1 IS NULL OR started_at >= ?1 ORDER BY started_at",
+        )?;
+        let sessions: Vec<SessionExport> = stmt
+            .query_map([since], |row| {
+                Ok(SessionExport {
+                    session_id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    ended_at: row.get(2)?,
+                    summary: row.get(3)?,
+                    status: row.get(4)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        md.push_str(&format!("## Sessions ({} total)\n\n", sessions.len()));
+        for s in &sessions {
+            let ended = s.ended_at.as_deref().unwrap_or("ongoing");
+            let summary = s.summary.as_deref().unwrap_or("No summary");
+            md.push_str(&format!("### Session #{} ({})\n\n", s.session_id, s.status));
+            md.push_str(&format!("- Started: {}\n", s.started_at));
+            md.push_str(&format!("- Ended: {}\n", ended));
+            md.push_str(&format!("- Summary: {}\n\n", summary));
         }
-        "md" | _ => {
-            println!("# Project: {}\n", config.name);
-            println!("Type: {}", config.project_type);
-            println!("Exported: {}\n", Utc::now().format("%Y-%m-%d %H:%M"));
-
-            println!("## Sessions ({} total)\n", sessions.len());
-            for s in &sessions {
-                let ended = s.ended_at.as_deref().unwrap_or("ongoing");
-                let summary = s.summary.as_deref().unwrap_or("No summary");
-                println!("### Session #{} ({})\n", s.session_id, s.status);
-                println!("- Started: {}", s.started_at);
-                println!("- Ended: {}", ended);
-                println!("- Summary: {}\n", summary);
+    }
+
+    if includes("decisions") {
+        let mut stmt = conn.prepare(
+            "SELECT topic, decision, rationale
+             FROM decisions WHERE status = 'active' AND (?1 IS NULL OR created_at >= ?1)
+             ORDER BY created_at",
+        )?;
+        let decisions: Vec<DecisionExport> = stmt
+            .query_map([since], |row| {
+                Ok(DecisionExport {
+                    topic: row.get(0)?,
+                    decision: row.get(1)?,
+                    rationale: row.get(2)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        md.push_str(&format!("## Decisions ({} active)\n\n", decisions.len()));
+        for d in &decisions {
+            md.push_str(&format!("### {}\n\n", d.topic));
+            md.push_str(&format!("{}\n", d.decision));
+            if let Some(rationale) = &d.rationale {
+                md.push_str(&format!("\n*Rationale: {}*\n", rationale));
             }
+            md.push('\n');
+        }
+    }
+
+    if includes("tasks") {
+        let mut stmt = conn.prepare(
+            "SELECT description, status, priority
+             FROM tasks WHERE ?1 IS NULL OR created_at >= ?1 ORDER BY created_at",
+        )?;
+        let tasks: Vec<TaskExport> = stmt
+            .query_map([since], |row| {
+                Ok(TaskExport {
+                    description: row.get(0)?,
+                    status: row.get(1)?,
+                    priority: row.get(2)?,
+                })
+            })?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        md.push_str(&format!("## Tasks ({} total)\n\n", tasks.len()));
+        for t in &tasks {
+            let status_marker = match t.status.as_str() {
+                "completed" => "[x]",
+                _ => "[ ]",
+            };
+            let priority = t.priority.as_deref().unwrap_or("normal");
+            md.push_str(&format!(
+                "- {} {} ({})\n",
+                status_marker, t.description, priority
+            ));
+        }
+    }
 
-            println!("## Decisions ({} active)\n", decisions.len());
-            for d in &decisions {
-                println!("### {}\n", d.topic);
-                println!("{}", d.decision);
-                if let Some(rationale) = &d.rationale {
-                    println!("\n*Rationale: {}*", rationale);
-                }
-                println!();
+    Ok(md)
+}
+
+/// iCalendar (RFC 5545): finished sessions as events, tasks with a due date as to-dos.
+/// UIDs are the records' UUIDs, so re-importing the file updates entries instead of
+/// duplicating them, even when the file comes from a merged or synced copy of the project.
+fn render_ics(
+    conn: &Connection,
+    config: &ProjectConfig,
+    selected: &[(&'static str, &'static str, &'static str)],
+    since: Option<&str>,
+) -> Result<String> {
+    let includes = |name: &str| selected.iter().any(|(n, _, _)| *n == name);
+    if !includes("sessions") && !includes("tasks") {
+        bail!("iCalendar export covers sessions and tasks. Add one of them to --tables.");
+    }
+
+    let slug: String = config
+        .name
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let stamp = Utc::now().format("%Y%m%dT%H%M%SZ").to_string();
+
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        format!("PRODID:-//proj//proj {}//EN", crate::VERSION),
+        "CALSCALE:GREGORIAN".to_string(),
+        format!(
+            "X-WR-CALNAME:{}",
+            ics_text(&format!("proj: {}", config.name))
+        ),
+    ];
+
+    if includes("sessions") {
+        let mut stmt = conn.prepare(
+            "SELECT session_id, started_at, ended_at, summary, name, agent, uuid
+             FROM sessions
+             WHERE ended_at IS NOT NULL AND status != 'abandoned' AND (?1 IS NULL OR started_at >= ?1)
+             ORDER BY started_at",
+        )?;
+        let sessions = stmt
+            .query_map([since], |row| {
+                Ok(CalendarSession {
+                    session_id: row.get(0)?,
+                    started_at: row.get(1)?,
+                    ended_at: row.get(2)?,
+                    summary: row.get(3)?,
+                    name: row.get(4)?,
+                    agent: row.get(5)?,
+                    uuid: row.get(6)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for s in sessions {
+            let (Some(start), Some(end)) = (ics_datetime(&s.started_at), ics_datetime(&s.ended_at))
+            else {
+                continue;
+            };
+            let session_id = s.session_id;
+            let title = match (&s.name, &s.summary) {
+                (Some(name), _) => format!("{}: {}", config.name, name),
+                (None, Some(summary)) => format!(
+                    "{}: {}",
+                    config.name,
+                    truncate(summary.lines().next().unwrap_or(""), 60)
+                ),
+                (None, None) => format!("{}: session #{}", config.name, session_id),
+            };
+            let mut description = format!("Session #{}", session_id);
+            if let Some(agent) = &s.agent {
+                description.push_str(&format!(" ({})", agent));
+            }
+            if let Some(summary) = &s.summary {
+                description.push_str(&format!("\n\n{}", summary));
             }
 
-            println!("## Tasks ({} total)\n", tasks.len());
-            for t in &tasks {
-                let status_marker = match t.status.as_str() {
-                    "completed" => "[x]",
-                    _ => "[ ]",
-                };
-                let priority = t.priority.as_deref().unwrap_or("normal");
-                println!("- {} {} ({})", status_marker, t.description, priority);
+            lines.push("BEGIN:VEVENT".to_string());
+            let uid = s.uuid.unwrap_or_else(|| format!("session-{}", session_id));
+            lines.push(format!("UID:{}@{}.proj", uid, slug));
+            lines.push(format!("DTSTAMP:{}", stamp));
+            lines.push(format!("DTSTART:{}", start));
+            lines.push(format!("DTEND:{}", end));
+            lines.push(format!("SUMMARY:{}", ics_text(&title)));
+            lines.push(format!("DESCRIPTION:{}", ics_text(&description)));
+            lines.push("END:VEVENT".to_string());
+        }
+    }
+
+    if includes("tasks") {
+        let mut stmt = conn.prepare(
+            "SELECT task_id, description, status, priority, due, notes, completed_at, uuid
+             FROM tasks
+             WHERE due IS NOT NULL AND (?1 IS NULL OR created_at >= ?1)
+             ORDER BY due",
+        )?;
+        let tasks = stmt
+            .query_map([since], |row| {
+                Ok(CalendarTask {
+                    task_id: row.get(0)?,
+                    description: row.get(1)?,
+                    status: row.get(2)?,
+                    priority: row.get(3)?,
+                    due: row.get(4)?,
+                    notes: row.get(5)?,
+                    completed_at: row.get(6)?,
+                    uuid: row.get(7)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for t in tasks {
+            let Ok(due) = chrono::NaiveDate::parse_from_str(&t.due, "%Y-%m-%d") else {
+                continue;
+            };
+            lines.push("BEGIN:VTODO".to_string());
+            let uid = t
+                .uuid
+                .clone()
+                .unwrap_or_else(|| format!("task-{}", t.task_id));
+            lines.push(format!("UID:{}@{}.proj", uid, slug));
+            lines.push(format!("DTSTAMP:{}", stamp));
+            lines.push(format!("DUE;VALUE=DATE:{}", due.format("%Y%m%d")));
+            lines.push(format!(
+                "SUMMARY:{}",
+                ics_text(&format!("{}: {}", config.name, t.description))
+            ));
+            if let Some(notes) = &t.notes {
+                lines.push(format!("DESCRIPTION:{}", ics_text(notes)));
             }
+            // iCalendar priority: 1 highest, 9 lowest
+            let priority = match t.priority.as_deref() {
+                Some("urgent") => 1,
+                Some("high") => 3,
+                Some("low") => 9,
+                _ => 5,
+            };
+            lines.push(format!("PRIORITY:{}", priority));
+            let ics_status = match t.status.as_str() {
+                "completed" => "COMPLETED",
+                "cancelled" => "CANCELLED",
+                "in_progress" => "IN-PROCESS",
+                _ => "NEEDS-ACTION",
+            };
+            lines.push(format!("STATUS:{}", ics_status));
+            if let Some(completed) = t.completed_at.as_deref().and_then(ics_datetime) {
+                lines.push(format!("COMPLETED:{}", completed));
+            }
+            lines.push("END:VTODO".to_string());
+        }
+    }
+
+    lines.push("END:VCALENDAR".to_string());
+
+    let mut ics = String::new();
+    for line in lines {
+        ics.push_str(&fold_ics_line(&line));
+        ics.push_str("\r\n");
+    }
+    Ok(ics)
+}
+
+/// SQLite UTC timestamp ("YYYY-MM-DD HH:MM:SS") as an iCalendar UTC date-time
+fn ics_datetime(timestamp: &str) -> Option<String> {
+    chrono::NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S")
+        .ok()
+        .map(|dt| dt.format("%Y%m%dT%H%M%SZ").to_string())
+}
+
+/// Escape a TEXT value: backslashes, semicolons, commas, and newlines
+fn ics_text(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace("\r\n", "\\n")
+        .replace('\n', "\\n")
+}
+
+/// Fold lines longer than 75 octets, without splitting a UTF-8 character
+fn fold_ics_line(line: &str) -> String {
+    let mut out = String::new();
+    let mut width = 0;
+    for c in line.chars() {
+        let len = c.len_utf8();
+        if width + len > 75 {
+            out.push_str("\r\n ");
+            width = 1;
         }
+        out.push(c);
+        width += len;
     }
+    out
+}
 
+/// Print to stdout, or write to a file when --output is given
+fn write_output(output: Option<&str>, content: &str) -> Result<()> {
+    match output {
+        Some(path) => {
+            std::fs::write(path, content).with_context(|| format!("Failed to write {}", path))?;
+            eprintln!("Wrote {}", path);
+        }
+        None => print!("{}", content),
+    }
     Ok(())
 }
 
@@ -171,6 +695,6 @@ fn load_config() -> Result<ProjectConfig> {
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
@@ -4,7 +4,7 @@ use anyhow::{bail, Context, Result};
 use colored::Colorize;
 
 use crate::config::ProjectConfig;
-use crate::database::open_database;
+use crate::database::{lock_for_migration, open_database};
 use crate::paths::{get_config_path, get_tracking_db_path};
 
 /// Valid extension types
@@ -122,6 +122,7 @@ pub fn run(extension_type: String) -> Result<()> {
 
     // Check if already applied (by checking for a marker table)
     let db_path = get_tracking_db_path()?;
+    let _lock = lock_for_migration(&db_path)?;
     let conn = open_database(&db_path)?;
 
     // Check if extension already exists
@@ -194,6 +195,6 @@ fn load_config() -> Result<ProjectConfig> {
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
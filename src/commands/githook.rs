@@ -0,0 +1,278 @@
+// Git hook integration - install/uninstall git hooks that record commits and branch switches
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use crate::database::open_database;
+use crate::error::ProjError;
+use crate::git;
+use crate::paths::get_project_root;
+use crate::session::{get_active_session, track_branch};
+
+/// Hook marker - used to detect and remove the proj block inside a hook file
+const HOOK_MARKER_START: &str = "# >>> proj git hook >>>";
+const HOOK_MARKER_END: &str = "# <<< proj git hook <<<";
+
+/// Hooks managed by proj
+const HOOKS: &[&str] = &["post-commit", "post-checkout"];
+
+pub fn install() -> Result<()> {
+    let root = get_project_root()?;
+    let hooks_dir = hooks_dir(&root)?;
+    fs::create_dir_all(&hooks_dir)
+        .with_context(|| format!("Failed to create {}", hooks_dir.display()))?;
+
+    for hook in HOOKS {
+        let path = hooks_dir.join(hook);
+        if is_hook_installed(&path) {
+            println!("{} {} already installed", "✓".green(), hook);
+            continue;
+        }
+
+        install_hook(&path, hook)?;
+        println!("{} Installed {}", "✓".green(), hook);
+    }
+
+    println!();
+    println!("Commits are now recorded as they happen, and branch switches are noted in the active session.");
+    if !proj_on_path() {
+        println!(
+            "{} 'proj' is not on PATH, so the hooks will do nothing until it is.",
+            "⚠".yellow()
+        );
+    }
+    Ok(())
+}
+
+pub fn uninstall() -> Result<()> {
+    let root = get_project_root()?;
+    let removed = remove_hooks(&root)?;
+
+    for hook in &removed {
+        println!("{} Removed {}", "✓".green(), hook);
+    }
+    if removed.is_empty() {
+        println!("Git hooks were not installed.");
+    }
+    Ok(())
+}
+
+/// Remove proj's blocks from the repository's hooks; returns the hooks that had one
+pub fn remove_hooks(root: &Path) -> Result<Vec<&'static str>> {
+    let hooks_dir = hooks_dir(root)?;
+
+    let mut removed = Vec::new();
+    for hook in HOOKS {
+        let path = hooks_dir.join(hook);
+        if is_hook_installed(&path) {
+            remove_hook(&path)?;
+            removed.push(*hook);
+        }
+    }
+    Ok(removed)
+}
+
+/// The hooks proj manages that don't have its block yet
+pub fn missing_hooks(root: &Path) -> Result<Vec<&'static str>> {
+    let hooks_dir = hooks_dir(root)?;
+    Ok(HOOKS
+        .iter()
+        .copied()
+        .filter(|hook| !is_hook_installed(&hooks_dir.join(hook)))
+        .collect())
+}
+
+pub fn status() -> Result<()> {
+    let root = get_project_root()?;
+    let hooks_dir = hooks_dir(&root)?;
+
+    println!("{}", "Git Hook Status".bold());
+    println!();
+
+    let installed: Vec<&str> = HOOKS
+        .iter()
+        .copied()
+        .filter(|hook| is_hook_installed(&hooks_dir.join(hook)))
+        .collect();
+
+    if installed.is_empty() {
+        println!("{} Git hooks are not installed.", "○".white());
+        println!();
+        println!("Run 'proj githook install' to record commits and branch switches automatically.");
+        return Ok(());
+    }
+
+    for hook in HOOKS {
+        if installed.contains(hook) {
+            println!("  {} {}", "✓".green(), hook);
+        } else {
+            println!("  {} {} (not installed)", "○".white(), hook);
+        }
+    }
+    println!("  Location: {}", hooks_dir.display());
+    if !proj_on_path() {
+        println!();
+        println!(
+            "{} 'proj' is not on PATH; hooks are currently no-ops.",
+            "⚠".yellow()
+        );
+    }
+    Ok(())
+}
+
+/// Entry point for the hook scripts. Never fails loudly: a broken hook must not block git.
+pub fn run(hook: &str, args: &[String]) -> Result<()> {
+    let Ok(root) = get_project_root() else {
+        return Ok(());
+    };
+    let Ok(conn) = open_database(&root.join(".tracking").join("tracking.db")) else {
+        return Ok(());
+    };
+
+    match hook {
+        "post-commit" => git::record_commit(&conn, &root, "HEAD"),
+        "post-checkout" => {
+            // Args: previous HEAD, new HEAD, 1 for a branch checkout (0 for a file checkout)
+            if args.get(2).map(String::as_str) != Some("1") {
+                return Ok(());
+            }
+            record_branch_switch(&conn)
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Note a branch switch in the active session's activity (no session is started for it)
+fn record_branch_switch(conn: &rusqlite::Connection) -> Result<()> {
+    if let Some(session) = get_active_session(conn)? {
+        track_branch(conn, session.session_id)?;
+    }
+    Ok(())
+}
+
+/// The repository's hooks directory (respects core.hooksPath and worktrees)
+fn hooks_dir(root: &Path) -> Result<PathBuf> {
+    let output = git::run(
+        Command::new("git")
+            .args(["rev-parse", "--git-path", "hooks"])
+            .current_dir(root),
+    )
+    .with_context(|| "Failed to run git")?;
+    if !output.status.success() {
+        return Err(ProjError::GitUnavailable(
+            "not a git repository (git hooks need a git repo)".to_string(),
+        )
+        .into());
+    }
+    let path = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Ok(if path.is_absolute() {
+        path
+    } else {
+        root.join(path)
+    })
+}
+
+/// Whether proj can be found by the hook scripts
+fn proj_on_path() -> bool {
+    Command::new("sh")
+        .args(["-c", "command -v proj"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Hook block; exits quietly when proj isn't installed so git is never blocked
+fn hook_block(hook: &str) -> String {
+    format!(
+        r#"{start}
+if command -v proj >/dev/null 2>&1; then
+    proj githook run {hook} "$@" >/dev/null 2>&1 || true
+fi
+{end}"#,
+        start = HOOK_MARKER_START,
+        hook = hook,
+        end = HOOK_MARKER_END
+    )
+}
+
+/// Check if the proj block is present in a hook file
+fn is_hook_installed(path: &Path) -> bool {
+    fs::read_to_string(path)
+        .map(|c| c.contains(HOOK_MARKER_START))
+        .unwrap_or(false)
+}
+
+/// Add the proj block to a hook, creating the hook if needed
+fn install_hook(path: &Path, hook: &str) -> Result<()> {
+    let mut content = fs::read_to_string(path).unwrap_or_default();
+
+    if content.is_empty() {
+        content.push_str("#!/bin/sh\n");
+    } else if !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push('\n');
+    content.push_str(&hook_block(hook));
+    content.push('\n');
+
+    fs::write(path, content).with_context(|| format!("Failed to write {:?}", path))?;
+    make_executable(path)?;
+    Ok(())
+}
+
+/// Remove the proj block from a hook; delete the hook if nothing else is left
+fn remove_hook(path: &Path) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+
+    let mut new_content = String::new();
+    let mut in_hook_block = false;
+
+    for line in content.lines() {
+        if line.contains(HOOK_MARKER_START) {
+            in_hook_block = true;
+            continue;
+        }
+        if line.contains(HOOK_MARKER_END) {
+            in_hook_block = false;
+            continue;
+        }
+        if !in_hook_block {
+            new_content.push_str(line);
+            new_content.push('\n');
+        }
+    }
+
+    let only_shebang = new_content
+        .lines()
+        .all(|l| l.trim().is_empty() || l.starts_with("#!"));
+    if only_shebang {
+        fs::remove_file(path).with_context(|| format!("Failed to remove {:?}", path))?;
+        return Ok(());
+    }
+
+    // Clean up any double blank lines
+    while new_content.contains("\n\n\n") {
+        new_content = new_content.replace("\n\n\n", "\n\n");
+    }
+
+    fs::write(path, new_content).with_context(|| format!("Failed to write {:?}", path))?;
+    Ok(())
+}
+
+#[cfg(unix)]
+fn make_executable(path: &Path) -> Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    let mut perms = fs::metadata(path)?.permissions();
+    perms.set_mode(perms.mode() | 0o755);
+    fs::set_permissions(path, perms)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn make_executable(_path: &Path) -> Result<()> {
+    Ok(())
+}
@@ -11,6 +11,8 @@ pub fn run() -> Result<()> {
 
 {}
   proj init              Initialize new project (interactive)
+  proj init --template rust-cli   Start with template tasks, notes, and docs
+  proj init --workspace  Group the projects below this one (monorepo)
   proj migrate           Convert existing project to proj format
 
 {} (Tiered Verbosity)
@@ -18,51 +20,135 @@ pub fn run() -> Result<()> {
   proj status -q         Micro context (~10 tokens) - one line
   proj status -v         Working context (~200 tokens)
   proj status --full     Full context (~500+ tokens)
+  proj status --workspace  Every project in this workspace
+  proj prompt            Prompt segment: name, session, blockers (cached)
   proj resume            Detailed context for resuming work
   proj resume --for-ai   Compact JSON output for AI
+  proj resume --for-ai --max-tokens <n>  JSON trimmed to a token budget
+  proj prime --format claude|openai|plain  System prompt for a new AI conversation
   proj context <topic>   Search decisions/notes about a topic
+  proj context <topic> --branch <name>   Only items from a git branch
+  proj context <topic> --author <name>   Only items logged by someone
+  proj context <topic> --ranked -v       Ranked, with score breakdown
+  proj context <topic> --max-tokens <n>  Best matches within a token budget
+  proj context <topic> --semantic        Match by meaning (after proj embed build)
+  proj context <topic> --profile <name>  Shaped for a tool: codegen, review, planning
   proj snapshot          Generate AI context snapshot (JSON)
+  proj snapshot --bundle         Bundle tracking, docs, and config into one .tar.zst
+  proj snapshot --restore <file> Unpack a bundle into this project
 
 {} (Token Optimization)
   proj delta             Show only changes since last check
   proj compress          Compress old sessions into summaries
   proj compress --auto   Auto-compress without prompts
+  proj compress --llm    Summarize old sessions with an LLM
   proj cleanup           Interactive review of stale items
   proj cleanup --auto    Auto-archive stale items
   proj cleanup --days N  Set staleness threshold (default: 30)
+  proj cleanup --optimize  Also compact the database afterwards
+  proj cleanup --dry-run   Report what would change (incl. retention policies)
+  proj db optimize       WAL, rebuild indexes, ANALYZE, VACUUM
 
 {}
-  proj log decision <topic> <decision> [rationale]
-  proj log note <category> <title> <content>
+  proj log decision <topic> <decision> [rationale] [--tag <tag>] [--section <id>]
+  proj log note <category> <title> <content> [--tag <tag>]
   proj log blocker <description>
   proj log question <question> [context]
+  proj log batch --json <file> [--dry-run]   Mixed entries, all or nothing
+  proj log commits [--since <date>] [--task <id>] [--files]
+  proj log decision -    Entries from stdin (JSON or tab-separated); '-' as a field reads it
+  proj blocker resolve <id> <resolution> [--unblock]
+  proj blocker list
+  proj question answer <id> <answer>
+  proj question list
+  proj note add <title> [content] [-c <category>]   Content from stdin or $EDITOR
+  proj note add --url <link> [--readable]    Reference note titled from the page
+  proj note add --from-clipboard             Note from the clipboard
+  proj note list [-c <category>] [--status <status>]
+  proj note show|edit|archive <id>
+  proj decision list [--by-topic] [--topic <topic>]
+  proj decision topics   Topics with decision counts and aliases
+  proj decision rename <from> <to>
+  proj decision merge <topic>... --into <topic>   Retag decisions; old names become aliases
+  proj decision export-adr [--dir docs/adr]   Decisions as numbered MADR files
+  proj decision import-adr [--dir docs/adr]   ADRs into decisions (statuses mapped)
+  proj attachment add <kind> <id> <path>...   Attach files (or --attach <path> on proj log)
+  proj attachment list|open|save|remove       Browse, open, or save attached files
 
 {}
-  proj task add <description> [--priority high]
+  proj task add <description> [--priority high] [--tag <tag>]
   proj task update <id> --status <status>
+  proj task show <id>    Details and linked commits (Proj-Task: <id> or #t<id> in messages)
+  proj task pick         Fuzzy-find a task, then show/start/complete it (--print for the ID)
   proj task list
+  proj task sync github  Sync tasks tagged 'publish' with GitHub Issues
+  proj task import --from jira --jql <query>   Mirror Jira tickets as tasks
+  proj task import --from linear --team <key>  Mirror Linear issues as tasks
   proj tasks             (shortcut for task list)
+  proj tasks --tag <tag> Only tasks with a tag
+  proj tasks --status completed --since <date>  Filter by status, priority, or date
+  proj tasks --limit 20 --offset 40  One page of a long list
+  proj scan-todos        Sync tasks with TODO/FIXME/HACK comments
 
 {}
   proj session start     Start new session explicitly
+  proj session start --name <name>   Parallel session (pauses current)
+  proj session pause / resume <id>   Switch between sessions
+  proj session gc        Close stale sessions in all registered projects
+  proj review            Logged items vs git activity, with suggestions
+  proj review --accept 1,3   Log suggested items by number
   proj session end <summary>   (1-3 substantive sentences)
+  proj session end --suggest   Draft a summary from this session (--accept to use it)
 
 {}
   proj register          Add current project to global registry
-  proj registered        List all registered projects
+  proj registered        List all registered projects (--prune to tidy)
+  proj register --move <old> <new>   Update a moved project's path
   proj dashboard         Multi-project overview
+  proj dashboard --tui   Interactive dashboard (tasks, blockers, sessions)
+  proj stats             Weekly velocity and activity metrics (--json)
+  proj stats --usage     Calls, duration, and output tokens per command (usage_metrics)
+  proj approve           Apply or reject queued agent writes (agent_writes: approval)
+  proj audit             Review every change to tracked records (--since, --table, --id)
+  proj watch             Stream tracking changes live (--json for editors)
 
 {}
   proj upgrade           Upgrade current project schema
-  proj upgrade --info    Preview upgrade without applying
-  proj upgrade --all     Upgrade all registered projects
+  proj upgrade --info    Preview upgrade and its SQL without applying
+  proj rollback --schema Undo the last upgrade (down-migrations)
+  proj upgrade --all     Upgrade all registered projects (--jobs N in parallel)
   proj backup            Manual backup of tracking database
-  proj check             Verify database integrity
+  proj backup --remote <name>   Also upload to S3/WebDAV/SFTP
+  proj backup --encrypt  Encrypt the backup (key in OS keychain)
+  proj check [--json]    Preflight: database, AGENTS.md links, hooks, docs, registry
+  proj ci report         Health report for a pull request (--post to comment it)
+  proj doctor [--fix]    Health check of databases, registry, and environment
   proj archive           Archive a completed project
+  proj archive items     Move old closed tasks, decisions, blockers to archive.db
+  proj archive restore task <id>   Bring an archived item back
   proj export --format md|json   Export session history
+  proj export --format ics       Sessions and due tasks as iCalendar
+  proj merge <db>        Merge a teammate's tracking.db into this one
+  proj sync              Sync tracking data via .tracking/sync/
+  proj githook install   Record commits/branch switches via git hooks
+  proj agents diff       Compare AGENTS.md's proj instructions with the template
+  proj agents update     Update them, keeping <!-- custom --> sections
+  proj agents sync       Regenerate Cursor/Windsurf/Aider rules files from AGENTS.md
+  proj config list       Settings and where each comes from
+  proj config set <k> <v>  Change a setting (--global for defaults)
+  proj config edit       Edit config.json in $EDITOR with validation
+  proj config set --global offline true  No network calls (or $PROJ_OFFLINE=1)
 
 {}
   proj help              Show this help message
+  proj <cmd> --debug     Log DB, git, and network activity to stderr
+  proj <cmd> --trace     Also log every SQL statement with timing
+  proj <cmd> --porcelain Tab-separated output for scripts
+  proj <cmd> --no-pager  Print long listings without $PAGER
+  proj <cmd> --project <path>  Use another project (or $PROJ_PROJECT_ROOT)
+  proj <cmd> --no-self-update  Don't install proj updates this run
+  proj update --channel beta   Update channel: stable, beta, or none
+  proj update --defer 7d       Hold off automatic updates (off resumes)
 
 {}
   {}    Active session indicator
@@ -0,0 +1,548 @@
+// Import command - restore or merge tracking data from a JSON export
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use crate::database::{open_database, table_columns, write_transaction};
+use crate::paths::get_tracking_db_path;
+
+/// How one exported table maps back into the database
+struct ImportSpec {
+    /// Key in the export JSON
+    key: &'static str,
+    table: &'static str,
+    pk: &'static str,
+    /// Columns that identify the same record across databases
+    identity: &'static [&'static str],
+    /// Foreign keys into tables imported earlier: (column, export key)
+    refs: &'static [(&'static str, &'static str)],
+    /// Foreign keys into this same table, fixed up after all rows are in
+    self_refs: &'static [&'static str],
+    /// Drop rows whose references all point outside the export (e.g. tags of unexported items)
+    needs_ref: bool,
+    /// Binary columns, exported as hex
+    blobs: &'static [&'static str],
+}
+
+/// Import order matters: referenced tables come first
+const IMPORT_SPECS: &[ImportSpec] = &[
+    ImportSpec {
+        key: "sessions",
+        table: "sessions",
+        pk: "session_id",
+        identity: &["started_at"],
+        refs: &[],
+        self_refs: &[],
+        needs_ref: false,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "tasks",
+        table: "tasks",
+        pk: "task_id",
+        identity: &["description", "created_at"],
+        refs: &[("session_id", "sessions")],
+        self_refs: &["parent_task_id"],
+        needs_ref: false,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "decisions",
+        table: "decisions",
+        pk: "decision_id",
+        identity: &["topic", "decision", "created_at"],
+        refs: &[("session_id", "sessions")],
+        self_refs: &["superseded_by"],
+        needs_ref: false,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "notes",
+        table: "context_notes",
+        pk: "note_id",
+        identity: &["category", "title", "created_at"],
+        refs: &[("session_id", "sessions")],
+        self_refs: &[],
+        needs_ref: false,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "blockers",
+        table: "blockers",
+        pk: "blocker_id",
+        identity: &["description", "created_at"],
+        refs: &[("session_id", "sessions"), ("related_task_id", "tasks")],
+        self_refs: &[],
+        needs_ref: false,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "questions",
+        table: "questions",
+        pk: "question_id",
+        identity: &["question", "created_at"],
+        refs: &[("session_id", "sessions")],
+        self_refs: &[],
+        needs_ref: false,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "commits",
+        table: "git_commits",
+        pk: "commit_id",
+        identity: &["hash"],
+        refs: &[],
+        self_refs: &[],
+        needs_ref: false,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "tags",
+        table: "item_tags",
+        pk: "item_tag_id",
+        identity: &["tag", "task_id", "decision_id", "note_id"],
+        refs: &[
+            ("task_id", "tasks"),
+            ("decision_id", "decisions"),
+            ("note_id", "notes"),
+        ],
+        self_refs: &[],
+        needs_ref: true,
+        blobs: &[],
+    },
+    ImportSpec {
+        key: "attachments",
+        table: "attachments",
+        pk: "attachment_id",
+        identity: &[
+            "sha256",
+            "file_name",
+            "task_id",
+            "decision_id",
+            "note_id",
+            "blocker_id",
+            "question_id",
+        ],
+        refs: &[
+            ("task_id", "tasks"),
+            ("decision_id", "decisions"),
+            ("note_id", "notes"),
+            ("blocker_id", "blockers"),
+            ("question_id", "questions"),
+        ],
+        self_refs: &[],
+        needs_ref: true,
+        blobs: &["content"],
+    },
+];
+
+/// Per-table import outcome
+#[derive(Default)]
+struct TableResult {
+    imported: usize,
+    existing: usize,
+    conflicts: Vec<String>,
+}
+
+pub fn run(file: &str, merge: bool, dry_run: bool) -> Result<()> {
+    let content =
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?;
+    import_json(file, &content, merge, dry_run)
+}
+
+/// Import the contents of a JSON export; `file` names it in messages
+pub fn import_json(file: &str, content: &str, merge: bool, dry_run: bool) -> Result<()> {
+    let export: serde_json::Value = serde_json::from_str(content)
+        .with_context(|| format!("{} is not a proj JSON export", file))?;
+
+    if !IMPORT_SPECS.iter().any(|s| export.get(s.key).is_some()) {
+        bail!(
+            "{} has no tracking data. Create one with 'proj export --format json'.",
+            file
+        );
+    }
+
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    if !merge && has_tracking_data(&conn)? {
+        bail!("This project already has tracking data. Use --merge to combine the export with it.");
+    }
+
+    if let Some(version) = export.get("schema_version").and_then(|v| v.as_str()) {
+        if version != crate::SCHEMA_VERSION {
+            eprintln!(
+                "{} Export is from schema v{} (current: v{}). Unknown columns are ignored.",
+                "⚠".yellow(),
+                version,
+                crate::SCHEMA_VERSION
+            );
+        }
+    }
+
+    let tx = write_transaction(&conn)?;
+    let mut id_maps: HashMap<&'static str, HashMap<i64, i64>> = HashMap::new();
+    let mut results = Vec::new();
+
+    for spec in IMPORT_SPECS {
+        let rows = export
+            .get(spec.key)
+            .and_then(|v| v.as_array())
+            .map(|a| a.as_slice())
+            .unwrap_or(&[]);
+        if rows.is_empty() {
+            continue;
+        }
+        let result = import_table(&tx, spec, rows, &mut id_maps)
+            .with_context(|| format!("Failed to import {}", spec.key))?;
+        results.push((spec.key, result));
+    }
+
+    let total: usize = results.iter().map(|(_, r)| r.imported).sum();
+
+    if !dry_run && total > 0 {
+        let summary = format!("Imported {} records from {}", total, file);
+        tx.execute(
+            "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (NULL, 'import', NULL, ?1)",
+            [&summary],
+        )?;
+    }
+
+    // Print summary
+    println!(
+        "{}",
+        if dry_run {
+            format!("Import preview: {}", file)
+        } else {
+            format!("Import: {}", file)
+        }
+        .bold()
+    );
+    println!("{}", "-".repeat(60));
+    for (key, r) in &results {
+        println!(
+            "  {:<12} {} new, {} already present{}",
+            key,
+            r.imported,
+            r.existing,
+            if r.conflicts.is_empty() {
+                String::new()
+            } else {
+                format!(", {} conflicts", r.conflicts.len())
+                    .yellow()
+                    .to_string()
+            }
+        );
+    }
+
+    let conflicts: Vec<&String> = results.iter().flat_map(|(_, r)| &r.conflicts).collect();
+    if !conflicts.is_empty() {
+        println!();
+        println!("{} Conflicts (kept the existing record):", "⚠".yellow());
+        for c in conflicts.iter().take(10) {
+            println!("  {}", c);
+        }
+        if conflicts.len() > 10 {
+            println!("  ... and {} more", conflicts.len() - 10);
+        }
+    }
+
+    if dry_run {
+        tx.rollback()?;
+        println!();
+        println!("Dry run - nothing was written.");
+    } else {
+        tx.commit()?;
+        println!();
+        println!("{} Imported {} records", "✓".green(), total);
+    }
+
+    Ok(())
+}
+
+/// Insert rows that aren't already present, recording old → new ID mappings
+fn import_table(
+    conn: &Connection,
+    spec: &ImportSpec,
+    rows: &[serde_json::Value],
+    id_maps: &mut HashMap<&'static str, HashMap<i64, i64>>,
+) -> Result<TableResult> {
+    let columns = table_columns(conn, "main", spec.table)?;
+    let mut result = TableResult::default();
+    let mut map: HashMap<i64, i64> = HashMap::new();
+    let mut deferred: Vec<(i64, &'static str, i64)> = Vec::new();
+
+    for row in rows {
+        let Some(obj) = row.as_object() else {
+            continue;
+        };
+        let old_id = obj.get(spec.pk).and_then(|v| v.as_i64());
+
+        if spec.needs_ref
+            && !spec.refs.iter().any(|(col, target)| {
+                obj.get(*col)
+                    .and_then(|v| v.as_i64())
+                    .is_some_and(|old| id_maps.get(target).is_some_and(|m| m.contains_key(&old)))
+            })
+        {
+            continue;
+        }
+
+        if let Some((existing_id, differing)) =
+            find_existing(conn, spec, obj, &columns, &map, id_maps)?
+        {
+            if let Some(old) = old_id {
+                map.insert(old, existing_id);
+            }
+            result.existing += 1;
+            if !differing.is_empty() {
+                result.conflicts.push(format!(
+                    "{} #{}: {} differs",
+                    spec.key,
+                    existing_id,
+                    differing.join(", ")
+                ));
+            }
+            continue;
+        }
+
+        let mut insert_cols = Vec::new();
+        let mut values: Vec<Value> = Vec::new();
+        let mut self_refs: Vec<(&'static str, i64)> = Vec::new();
+
+        for col in &columns {
+            if col == spec.pk {
+                continue;
+            }
+            let Some(json) = obj.get(col.as_str()) else {
+                continue;
+            };
+            let mut value = column_value(spec, col, json)?;
+
+            if let Some((_, target)) = spec.refs.iter().find(|(c, _)| c == col) {
+                value = match value {
+                    Value::Integer(old) => id_maps
+                        .get(target)
+                        .and_then(|m| m.get(&old))
+                        .map_or(Value::Null, |new| Value::Integer(*new)),
+                    other => other,
+                };
+            } else if let Some(self_col) = spec.self_refs.iter().find(|c| *c == col) {
+                if let Value::Integer(old) = value {
+                    // Filled in once every row of this table has an ID
+                    self_refs.push((self_col, old));
+                    value = Value::Null;
+                }
+            }
+
+            // Imported history shouldn't leave a second session open or resumable
+            if spec.table == "sessions" && col == "status" {
+                if let Value::Text(ref s) = value {
+                    if s == "active" || s == "paused" {
+                        value = Value::Text("completed".to_string());
+                    }
+                }
+            }
+
+            insert_cols.push(col.as_str());
+            values.push(value);
+        }
+
+        let placeholders: Vec<String> = (1..=values.len()).map(|i| format!("?{}", i)).collect();
+        conn.execute(
+            &format!(
+                "INSERT INTO {} ({}) VALUES ({})",
+                spec.table,
+                insert_cols.join(", "),
+                placeholders.join(", ")
+            ),
+            rusqlite::params_from_iter(values.iter()),
+        )?;
+        let new_id = conn.last_insert_rowid();
+        if let Some(old) = old_id {
+            map.insert(old, new_id);
+        }
+        deferred.extend(self_refs.into_iter().map(|(col, old)| (new_id, col, old)));
+        result.imported += 1;
+    }
+
+    for (row_id, col, old_ref) in deferred {
+        if let Some(new_ref) = map.get(&old_ref) {
+            conn.execute(
+                &format!(
+                    "UPDATE {} SET {} = ?1 WHERE {} = ?2",
+                    spec.table, col, spec.pk
+                ),
+                [*new_ref, row_id],
+            )?;
+        }
+    }
+
+    id_maps.insert(spec.key, map);
+    Ok(result)
+}
+
+/// Look up a record with the same identity; returns its ID and any columns whose values differ
+fn find_existing(
+    conn: &Connection,
+    spec: &ImportSpec,
+    obj: &serde_json::Map<String, serde_json::Value>,
+    columns: &[String],
+    matched: &HashMap<i64, i64>,
+    id_maps: &HashMap<&'static str, HashMap<i64, i64>>,
+) -> Result<Option<(i64, Vec<String>)>> {
+    let mut identity_values = Vec::new();
+    let mut conditions = Vec::new();
+
+    // A known UUID is the same record, whatever else changed; otherwise match on content
+    let uuid = obj.get("uuid").and_then(|v| v.as_str()).filter(|uuid| {
+        columns.iter().any(|c| c == "uuid")
+            && conn
+                .query_row(
+                    &format!("SELECT 1 FROM {} WHERE uuid = ?1", spec.table),
+                    [uuid],
+                    |_| Ok(()),
+                )
+                .is_ok()
+    });
+    let identity: &[&str] = if uuid.is_some() {
+        &["uuid"]
+    } else {
+        spec.identity
+    };
+
+    for col in identity {
+        let target = spec.refs.iter().find(|(c, _)| c == col).map(|(_, t)| *t);
+        let value = match (obj.get(*col), target) {
+            (Some(v), None) if !v.is_null() => json_to_value(v),
+            // References are compared once mapped to this database's IDs
+            (Some(v), Some(target)) if !v.is_null() => {
+                match v
+                    .as_i64()
+                    .and_then(|old| id_maps.get(target).and_then(|m| m.get(&old)))
+                {
+                    Some(new) => Value::Integer(*new),
+                    None => return Ok(None),
+                }
+            }
+            (_, Some(_)) => {
+                conditions.push(format!("{} IS NULL", col));
+                continue;
+            }
+            // Without a full identity we can't tell, so treat it as new
+            _ => return Ok(None),
+        };
+        identity_values.push(value);
+        conditions.push(format!("{} = ?{}", col, identity_values.len()));
+    }
+
+    // Each existing record can stand in for at most one imported row
+    if !matched.is_empty() {
+        let ids: Vec<String> = matched.values().map(|id| id.to_string()).collect();
+        conditions.push(format!("{} NOT IN ({})", spec.pk, ids.join(", ")));
+    }
+
+    // Compare plain data columns only; IDs and references differ between databases by design
+    let compared: Vec<&String> = columns
+        .iter()
+        .filter(|c| {
+            *c != spec.pk
+                && *c != "uuid"
+                && !identity.contains(&c.as_str())
+                && !spec.refs.iter().any(|(r, _)| r == c)
+                && !spec.self_refs.contains(&c.as_str())
+                && obj.contains_key(c.as_str())
+        })
+        .collect();
+
+    let mut select_cols = vec![spec.pk.to_string()];
+    select_cols.extend(compared.iter().map(|c| c.to_string()));
+
+    let sql = format!(
+        "SELECT {} FROM {} WHERE {} ORDER BY {} LIMIT 1",
+        select_cols.join(", "),
+        spec.table,
+        conditions.join(" AND "),
+        spec.pk
+    );
+
+    let found = conn.query_row(
+        &sql,
+        rusqlite::params_from_iter(identity_values.iter()),
+        |row| {
+            let id: i64 = row.get(0)?;
+            let mut existing = Vec::new();
+            for i in 0..compared.len() {
+                existing.push(row.get::<_, Value>(i + 1)?);
+            }
+            Ok((id, existing))
+        },
+    );
+
+    match found {
+        Ok((id, existing)) => {
+            let differing = compared
+                .iter()
+                .zip(existing)
+                .filter(|(col, value)| {
+                    obj.get(col.as_str())
+                        .map(|j| column_value(spec, col, j).map_or(true, |v| v != *value))
+                        .unwrap_or(false)
+                })
+                .map(|(col, _)| col.to_string())
+                .collect();
+            Ok(Some((id, differing)))
+        }
+        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Whether the project already has any tracked records
+fn has_tracking_data(conn: &Connection) -> Result<bool> {
+    let count: i64 = conn.query_row(
+        "SELECT (SELECT COUNT(*) FROM sessions) + (SELECT COUNT(*) FROM tasks) + (SELECT COUNT(*) FROM decisions)
+              + (SELECT COUNT(*) FROM context_notes) + (SELECT COUNT(*) FROM blockers) + (SELECT COUNT(*) FROM questions)",
+        [],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// An exported value as it's stored in `col`: hex decoded for binary columns
+fn column_value(spec: &ImportSpec, col: &str, json: &serde_json::Value) -> Result<Value> {
+    match json.as_str() {
+        Some(hex) if spec.blobs.contains(&col) => Ok(Value::Blob(
+            unhex(hex).with_context(|| format!("{} is not hex", col))?,
+        )),
+        _ => Ok(json_to_value(json)),
+    }
+}
+
+fn unhex(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+fn json_to_value(json: &serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Real(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        other => Value::Text(other.to_string()),
+    }
+}
@@ -1,19 +1,22 @@
 // Init command - initialize a new project
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{bail, Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use dialoguer::{Confirm, Input, Select};
 
-use crate::config::{ProjectConfig, Registry, RegistryEntry};
+use crate::commands::{agents, docs, docs_import};
+use crate::config::{ProjectConfig, RankingConfig, Registry, RegistryEntry};
 use crate::database::open_database;
 use crate::docs_db;
-use crate::paths::{ensure_dir, get_registry_path};
+use crate::paths::{ensure_dir, get_registry_path, get_start_dir, same_path};
 use crate::schema::init_tracking_schema;
 use crate::schema_docs::DocType;
 use crate::source_analyzer;
+use crate::templates::{self, TemplateSection};
+use crate::workspace;
 use crate::SCHEMA_VERSION;
 
 /// Project types
@@ -57,6 +60,8 @@ pub fn run(
     commit_mode: String,
     no_agents: bool,
     shell_hook: bool,
+    template: Option<String>,
+    workspace: bool,
 ) -> Result<()> {
     // Determine project root - use --path if provided, otherwise current directory
     let project_root = if let Some(ref p) = path {
@@ -68,7 +73,7 @@ pub fn run(
             std::env::current_dir()?.join(path_buf)
         }
     } else {
-        std::env::current_dir()?
+        get_start_dir()?
     };
 
     // Create directory if it doesn't exist
@@ -80,14 +85,19 @@ pub fn run(
     let tracking_path = project_root.join(".tracking");
 
     if tracking_path.exists() {
+        if workspace {
+            return make_workspace(&project_root);
+        }
         println!("Project already initialized. Use 'proj status' to see current state.");
         return Ok(());
     }
 
     // Determine if we're in non-interactive mode
-    // Non-interactive if: name and type are provided, OR path is provided, OR we're not in a terminal
-    let non_interactive =
-        (name.is_some() && project_type.is_some()) || path.is_some() || !is_interactive();
+    // Non-interactive if: name and type are provided, OR path or template is provided, OR we're not in a terminal
+    let non_interactive = (name.is_some() && project_type.is_some())
+        || path.is_some()
+        || template.is_some()
+        || !is_interactive();
 
     if non_interactive {
         run_non_interactive(
@@ -105,9 +115,53 @@ pub fn run(
             commit_mode,
             no_agents,
             shell_hook,
+            template,
+            workspace,
         )
     } else {
-        run_interactive(project_root, tracking_path)
+        run_interactive(project_root, tracking_path, workspace)
+    }
+}
+
+/// Turn an existing project into a workspace and record the projects below it
+fn make_workspace(project_root: &Path) -> Result<()> {
+    let mut config = workspace::read_config(project_root)?;
+    let mut members = workspace::members_of(&config);
+    let found = workspace::discover_members(project_root);
+    let added = found.iter().filter(|m| !members.contains(m)).count();
+    members.extend(found);
+    members.sort();
+    members.dedup();
+
+    let was_workspace = config.get("workspace").and_then(|v| v.as_bool()) == Some(true);
+    config["workspace"] = true.into();
+    config["workspace_members"] = members.clone().into();
+    workspace::write_config(project_root, &config)?;
+
+    if was_workspace {
+        println!(
+            "{} Workspace already set up; {} new member(s) found",
+            "✓".green(),
+            added
+        );
+    } else {
+        println!("{} Project is now a workspace", "✓".green());
+    }
+    for member in &members {
+        println!("  • {}", member);
+    }
+    if members.is_empty() {
+        println!("  No member projects yet. 'proj init' in a subdirectory adds one.");
+    }
+    Ok(())
+}
+
+/// Add a newly created project to the workspace above it, if there is one
+fn join_workspace(project_root: &Path) {
+    match workspace::join_parent(project_root) {
+        Ok(Some(name)) => println!("  {} Added to workspace '{}'", "✓".green(), name),
+        Ok(None) => {}
+        Err(e) => println!("  {} Could not add to workspace: {}", "⚠".yellow(), e),
     }
 }
 
@@ -127,7 +181,13 @@ fn run_non_interactive(
     commit_mode: String,
     no_agents: bool,
     shell_hook: bool,
+    template: Option<String>,
+    workspace: bool,
 ) -> Result<()> {
+    // Load the template first so a bad name or file fails before anything is created
+    let template = template.as_deref().map(templates::load).transpose()?;
+    let description = description.or_else(|| template.as_ref().and_then(|t| t.description.clone()));
+
     // Validate required fields
     let project_name = name.unwrap_or_else(|| {
         project_root
@@ -137,9 +197,11 @@ fn run_non_interactive(
             .to_string()
     });
 
-    let project_type_str = project_type.unwrap_or_else(|| {
-        detect_project_type(&project_root).unwrap_or_else(|| "other".to_string())
-    });
+    let project_type_str = project_type
+        .or_else(|| template.as_ref().and_then(|t| t.project_type.clone()))
+        .unwrap_or_else(|| {
+            detect_project_type(&project_root).unwrap_or_else(|| "other".to_string())
+        });
 
     // Validate project type
     if !PROJECT_TYPES.contains(&project_type_str.as_str()) {
@@ -161,13 +223,9 @@ fn run_non_interactive(
     println!("Initializing project in: {}", project_root.display());
 
     // Check if this is a git repository (for auto-commit)
-    let is_git_repo = project_root.join(".git").exists();
+    let is_git_repo = crate::git::is_repo(&project_root);
     let effective_auto_commit = auto_commit && is_git_repo;
 
-    // Create .tracking directory
-    println!("\nCreating project structure...");
-    ensure_dir(&tracking_path)?;
-
     // Create config.json
     let config = ProjectConfig {
         name: project_name.clone(),
@@ -179,10 +237,49 @@ fn run_non_interactive(
         auto_commit: effective_auto_commit,
         auto_commit_mode: commit_mode,
         auto_commit_on_task: true,
+        session_timeout_hours: 8,
+        auto_close_policy: "close".to_string(),
+        commit_template: "[proj] {summary}".to_string(),
+        task_commit_template: "[proj] Completed task #{task_id}: {task}".to_string(),
+        scan_todos_on_status: false,
+        ranking: RankingConfig::default(),
+        webhooks: Vec::new(),
+        task_imports: Vec::new(),
+        task_import_refresh_hours: 24,
+        retention: Default::default(),
+        nudges: Default::default(),
+        agent_writes: "direct".to_string(),
+        context_profiles: Default::default(),
+        usage_metrics: false,
+        output_cache: true,
+        offline: false,
+        ca_bundle: None,
+        trash_retention_days: 30,
+        workspace_members: if workspace {
+            workspace::discover_members(&project_root)
+        } else {
+            Vec::new()
+        },
+        workspace,
+    };
+    let config = match template {
+        Some(ref t) => templates::apply_config(config, t)?,
+        None => config,
     };
 
-    config.save()?;
+    // Create .tracking directory
+    println!("\nCreating project structure...");
+    ensure_dir(&tracking_path)?;
+
+    config.save_to(&tracking_path.join("config.json"))?;
     println!("  {} config.json", "✓".green());
+    if config.workspace {
+        println!(
+            "  {} Workspace with {} member project(s)",
+            "✓".green(),
+            config.workspace_members.len()
+        );
+    }
 
     // Create tracking.db
     let db_path = tracking_path.join("tracking.db");
@@ -192,14 +289,25 @@ fn run_non_interactive(
     init_tracking_schema(&conn).with_context(|| "Failed to initialize tracking database schema")?;
     println!("  {} tracking.db", "✓".green());
 
+    if let Some(ref t) = template {
+        let (tasks, notes) = templates::populate(&conn, t)?;
+        println!(
+            "  {} Template: {} task(s), {} note(s)",
+            "✓".green(),
+            tasks,
+            notes
+        );
+    }
+
     // Register project in global registry
     if let Err(e) = register_project(&project_root, &project_name, &project_type_str) {
         println!("  {} Could not register project: {}", "⚠".yellow(), e);
     } else {
         println!("  {} Registered in global registry", "✓".green());
     }
+    join_workspace(&project_root);
 
-    // Create project-local AGENTS.md with CLAUDE.md/GEMINI.md symlinks
+    // Create project-local AGENTS.md, its CLAUDE.md/GEMINI.md links, and other tools' rules files
     if !no_agents {
         if let Err(e) = setup_project_agents(&project_root) {
             println!(
@@ -208,20 +316,33 @@ fn run_non_interactive(
                 e
             );
         } else {
-            println!("  {} Project AGENTS.md + symlinks created", "✓".green());
+            println!(
+                "  {} Project AGENTS.md + agent rules files created",
+                "✓".green()
+            );
         }
     }
 
     // Documentation setup
     let doc_type = parse_doc_type(&docs_type);
+    let template_sections = template
+        .as_ref()
+        .map(|t| t.doc_sections.as_slice())
+        .filter(|s| !s.is_empty());
 
     if !skip_docs {
         if docs_generate {
             setup_docs_generate(&project_root, &project_name, doc_type)?;
         } else if docs_import {
             setup_docs_import(&project_root, &project_name, doc_type)?;
-        } else if docs_new {
-            setup_docs_skeleton(&project_root, &project_name, doc_type, description)?;
+        } else if docs_new || template_sections.is_some() {
+            setup_docs_skeleton(
+                &project_root,
+                &project_name,
+                doc_type,
+                description,
+                template_sections,
+            )?;
         }
         // If none specified, skip docs silently in non-interactive mode
     }
@@ -254,9 +375,13 @@ fn install_shell_hook_silent() -> Result<()> {
     crate::commands::shell::install(true)
 }
 
-fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Result<()> {
+fn run_interactive(
+    mut project_root: PathBuf,
+    mut tracking_path: PathBuf,
+    workspace: bool,
+) -> Result<()> {
     // Ask for project directory
-    let current_dir = std::env::current_dir()?;
+    let current_dir = get_start_dir()?;
     let current_dir_str = current_dir.to_string_lossy().to_string();
 
     let chosen_path: String = Input::new()
@@ -338,7 +463,7 @@ fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Res
     let description_for_docs = description.clone();
 
     // Check if this is a git repository
-    let is_git_repo = project_root.join(".git").exists();
+    let is_git_repo = crate::git::is_repo(&project_root);
 
     // Ask about auto-commit if it's a git repo
     let (auto_commit, auto_commit_mode) = if is_git_repo {
@@ -387,10 +512,41 @@ fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Res
         auto_commit,
         auto_commit_mode,
         auto_commit_on_task: true,
+        session_timeout_hours: 8,
+        auto_close_policy: "close".to_string(),
+        commit_template: "[proj] {summary}".to_string(),
+        task_commit_template: "[proj] Completed task #{task_id}: {task}".to_string(),
+        scan_todos_on_status: false,
+        ranking: RankingConfig::default(),
+        webhooks: Vec::new(),
+        task_imports: Vec::new(),
+        task_import_refresh_hours: 24,
+        retention: Default::default(),
+        nudges: Default::default(),
+        agent_writes: "direct".to_string(),
+        context_profiles: Default::default(),
+        usage_metrics: false,
+        output_cache: true,
+        offline: false,
+        ca_bundle: None,
+        trash_retention_days: 30,
+        workspace_members: if workspace {
+            workspace::discover_members(&project_root)
+        } else {
+            Vec::new()
+        },
+        workspace,
     };
 
-    config.save()?;
+    config.save_to(&tracking_path.join("config.json"))?;
     println!("  {} config.json", "✓".green());
+    if config.workspace {
+        println!(
+            "  {} Workspace with {} member project(s)",
+            "✓".green(),
+            config.workspace_members.len()
+        );
+    }
 
     // Create tracking.db
     let db_path = tracking_path.join("tracking.db");
@@ -406,8 +562,9 @@ fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Res
     } else {
         println!("  {} Registered in global registry", "✓".green());
     }
+    join_workspace(&project_root);
 
-    // Create project-local AGENTS.md with CLAUDE.md/GEMINI.md symlinks
+    // Create project-local AGENTS.md, its CLAUDE.md/GEMINI.md links, and other tools' rules files
     if let Err(e) = setup_project_agents(&project_root) {
         println!(
             "  {} Could not setup project AGENTS.md: {}",
@@ -415,7 +572,10 @@ fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Res
             e
         );
     } else {
-        println!("  {} Project AGENTS.md + symlinks created", "✓".green());
+        println!(
+            "  {} Project AGENTS.md + agent rules files created",
+            "✓".green()
+        );
     }
 
     // Documentation database setup
@@ -425,7 +585,7 @@ fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Res
     let docs_options = &[
         "Skip        - Set up documentation later",
         "Generate    - Analyze codebase and generate docs",
-        "Import      - Import from existing markdown files",
+        "Import      - Import existing Markdown, reST, AsciiDoc, or Org docs",
         "New Project - Create documentation skeleton",
     ];
 
@@ -450,7 +610,13 @@ fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Res
         }
         3 => {
             let doc_type = select_doc_type()?;
-            setup_docs_skeleton(&project_root, &project_name, doc_type, description_for_docs)?;
+            setup_docs_skeleton(
+                &project_root,
+                &project_name,
+                doc_type,
+                description_for_docs,
+                None,
+            )?;
         }
         _ => {}
     }
@@ -502,11 +668,7 @@ fn run_interactive(mut project_root: PathBuf, mut tracking_path: PathBuf) -> Res
 }
 
 /// Setup docs by generating from source
-fn setup_docs_generate(
-    project_root: &PathBuf,
-    project_name: &str,
-    doc_type: DocType,
-) -> Result<()> {
+fn setup_docs_generate(project_root: &Path, project_name: &str, doc_type: DocType) -> Result<()> {
     println!("\n  {}", "Analyzing codebase...".cyan());
     match source_analyzer::analyze_project(project_root) {
         Ok(structure) => {
@@ -516,7 +678,7 @@ fn setup_docs_generate(
                 println!(
                     "  {} Detected {} ({} files, {} items)",
                     "✓".green(),
-                    structure.language.as_str(),
+                    structure.language_label(),
                     structure.file_count,
                     structure.modules.len()
                 );
@@ -525,7 +687,7 @@ fn setup_docs_generate(
                 let db_path = project_root.join(&db_filename);
 
                 let doc_conn = docs_db::create_docs_db(&db_path, project_name, doc_type)?;
-                let sections = source_analyzer::generate_sections(&structure);
+                let sections = source_analyzer::generate_sections_for(&structure, doc_type);
 
                 for section in &sections {
                     docs_db::insert_section(
@@ -542,7 +704,7 @@ fn setup_docs_generate(
                 }
 
                 crate::schema_docs::set_meta(&doc_conn, "generated_from", "source_analysis")?;
-                crate::schema_docs::set_meta(&doc_conn, "language", structure.language.as_str())?;
+                crate::schema_docs::set_meta(&doc_conn, "language", &structure.language_label())?;
 
                 println!(
                     "  {} {} ({} sections)",
@@ -559,13 +721,16 @@ fn setup_docs_generate(
     Ok(())
 }
 
-/// Setup docs by importing markdown
-fn setup_docs_import(project_root: &PathBuf, project_name: &str, doc_type: DocType) -> Result<()> {
-    let md_files = find_markdown_files(project_root);
-    if md_files.is_empty() {
-        println!("  {} No markdown files found, skipping.", "!".yellow());
+/// Setup docs by importing Markdown, reStructuredText, AsciiDoc, and Org files
+fn setup_docs_import(project_root: &Path, project_name: &str, doc_type: DocType) -> Result<()> {
+    let doc_files = docs_import::find_doc_files(project_root);
+    if doc_files.is_empty() {
+        println!(
+            "  {} No documentation files found (.md, .rst, .adoc, .org), skipping.",
+            "!".yellow()
+        );
     } else {
-        println!("  Found {} markdown files", md_files.len());
+        println!("  Found {} documentation files", doc_files.len());
 
         let db_filename = crate::schema_docs::docs_db_filename(project_name, doc_type);
         let db_path = project_root.join(&db_filename);
@@ -573,8 +738,8 @@ fn setup_docs_import(project_root: &PathBuf, project_name: &str, doc_type: DocTy
         let doc_conn = docs_db::create_docs_db(&db_path, project_name, doc_type)?;
 
         let mut total_sections = 0;
-        for file_path in &md_files {
-            match import_markdown_to_db(&doc_conn, file_path, project_root) {
+        for file_path in &doc_files {
+            match docs::import_markdown_file(&doc_conn, file_path, project_root) {
                 Ok(count) => total_sections += count,
                 Err(e) => println!(
                     "  {} Failed to import {:?}: {}",
@@ -596,18 +761,44 @@ fn setup_docs_import(project_root: &PathBuf, project_name: &str, doc_type: DocTy
     Ok(())
 }
 
-/// Setup docs with skeleton
+/// Setup docs with skeleton, using a template's sections when given
 fn setup_docs_skeleton(
-    project_root: &PathBuf,
+    project_root: &Path,
     project_name: &str,
     doc_type: DocType,
     description: Option<String>,
+    template_sections: Option<&[TemplateSection]>,
 ) -> Result<()> {
     let db_filename = crate::schema_docs::docs_db_filename(project_name, doc_type);
     let db_path = project_root.join(&db_filename);
 
     let doc_conn = docs_db::create_docs_db(&db_path, project_name, doc_type)?;
 
+    if let Some(template_sections) = template_sections {
+        for (i, section) in template_sections.iter().enumerate() {
+            let section_id = (i + 1).to_string();
+            docs_db::insert_section(
+                &doc_conn,
+                &section_id,
+                &section.title,
+                None,
+                1,
+                i as i32 + 1,
+                &section.content,
+                true,
+                None,
+            )?;
+        }
+        crate::schema_docs::set_meta(&doc_conn, "generated_from", "template")?;
+        println!(
+            "  {} {} ({} sections)",
+            "✓".green(),
+            db_filename,
+            template_sections.len()
+        );
+        return Ok(());
+    }
+
     let desc = description.unwrap_or_else(|| format!("Documentation for {}", project_name));
     let sections = vec![
         ("1", "Overview", 1, desc),
@@ -693,105 +884,8 @@ fn select_doc_type() -> Result<DocType> {
     })
 }
 
-/// Find markdown files in project
-fn find_markdown_files(project_root: &std::path::Path) -> Vec<std::path::PathBuf> {
-    let mut files = Vec::new();
-
-    let check_files = [
-        "README.md",
-        "ARCHITECTURE.md",
-        "CONTRIBUTING.md",
-        "API.md",
-        "GUIDE.md",
-    ];
-    for file in &check_files {
-        let path = project_root.join(file);
-        if path.exists() {
-            files.push(path);
-        }
-    }
-
-    let docs_dir = project_root.join("docs");
-    if docs_dir.is_dir() {
-        if let Ok(entries) = std::fs::read_dir(&docs_dir) {
-            for entry in entries.filter_map(|e| e.ok()) {
-                let path = entry.path();
-                if path.extension().map_or(false, |ext| ext == "md") {
-                    files.push(path);
-                }
-            }
-        }
-    }
-
-    files
-}
-
-/// Import a markdown file into docs database
-fn import_markdown_to_db(
-    conn: &rusqlite::Connection,
-    file_path: &std::path::Path,
-    project_root: &std::path::Path,
-) -> Result<usize> {
-    let content = std::fs::read_to_string(file_path)?;
-    let relative_path = file_path
-        .strip_prefix(project_root)
-        .unwrap_or(file_path)
-        .to_string_lossy();
-
-    let mut sections = Vec::new();
-    let mut current_section: Option<(i32, String, String)> = None;
-
-    for line in content.lines() {
-        if let Some(level) = detect_heading(line) {
-            if let Some((lvl, title, content)) = current_section.take() {
-                sections.push((lvl, title, content.trim().to_string()));
-            }
-            let title = line.trim_start_matches('#').trim().to_string();
-            current_section = Some((level, title, String::new()));
-        } else if let Some((_, _, ref mut content)) = current_section {
-            content.push_str(line);
-            content.push('\n');
-        }
-    }
-
-    if let Some((level, title, content)) = current_section {
-        sections.push((level, title, content.trim().to_string()));
-    }
-
-    let mut sort_order = 0;
-    for (level, title, content) in &sections {
-        sort_order += 1;
-        let section_id = format!("{}", sort_order);
-        docs_db::insert_section(
-            conn,
-            &section_id,
-            title,
-            None,
-            *level,
-            sort_order,
-            content,
-            false,
-            Some(&relative_path),
-        )?;
-    }
-
-    Ok(sections.len())
-}
-
-/// Detect markdown heading level
-fn detect_heading(line: &str) -> Option<i32> {
-    let trimmed = line.trim_start();
-    if trimmed.starts_with('#') {
-        let level = trimmed.chars().take_while(|&c| c == '#').count();
-        if level <= 6 && trimmed.chars().nth(level) == Some(' ') {
-            return Some(level as i32);
-        }
-    }
-    None
-}
-
 /// Detect project type from files in directory
-fn detect_project_type(path: &PathBuf) -> Option<String> {
+fn detect_project_type(path: &Path) -> Option<String> {
     if path.join("Cargo.toml").exists() {
         return Some("rust".to_string());
     }
@@ -826,7 +920,7 @@ fn prompt_project_type() -> Result<String> {
 }
 
 /// Register project in global registry
-fn register_project(path: &PathBuf, name: &str, project_type: &str) -> Result<()> {
+fn register_project(path: &Path, name: &str, project_type: &str) -> Result<()> {
     let registry_path = get_registry_path()?;
 
     // Ensure registry directory exists
@@ -848,7 +942,7 @@ fn register_project(path: &PathBuf, name: &str, project_type: &str) -> Result<()
     if registry
         .registered_projects
         .iter()
-        .any(|p| p.path == path_str)
+        .any(|p| same_path(Path::new(&p.path), path))
     {
         return Ok(()); // Already registered
     }
@@ -869,10 +963,14 @@ fn register_project(path: &PathBuf, name: &str, project_type: &str) -> Result<()
     Ok(())
 }
 
+/// Version of PROJECT_AGENTS_TEMPLATE, recorded in the AGENTS.md marker comment.
+/// Bump it with every change to the template so `proj agents diff` flags older files.
+pub const PROJECT_AGENTS_TEMPLATE_VERSION: u32 = 2;
+
 /// Template for project-local AGENTS.md file with complete proj instructions
 /// This is the single source of truth for AI agent instructions
 pub const PROJECT_AGENTS_TEMPLATE: &str = r#"# Project Context & Rules
-This file is the single source of truth for Gemini, Claude, and Codex agents.
+This file is the single source of truth for Gemini, Claude, Codex, Cursor, Windsurf, and Aider agents.
 
 ## Project Tracking
 
@@ -883,7 +981,7 @@ This project uses `proj` for session and decision tracking. Follow these instruc
 **At the start of every conversation**, if the current directory has a `.tracking/` folder:
 1. Run `proj status` BEFORE responding to the user's first message
 2. This loads project context and starts session tracking
-3. Stale sessions (8+ hours) auto-close automatically
+3. Stale sessions (8+ hours by default) auto-close automatically
 
 If no `.tracking/` folder exists, skip this step.
 
@@ -923,6 +1021,8 @@ proj task add "<description>" --priority <urgent|high|normal|low>
 proj log blocker "<what is blocking progress>"
 ```
 
+If a command answers `Queued for approval (#N)`, the project has the user review agent writes before they're saved (`agent_writes: approval`). The entry is saved once the user runs `proj approve`, so don't retry it or write it another way. A queued task has no ID yet, so leave follow-up updates to it until it's approved.
+
 ### Two-Pass Logging
 
 Use a two-pass approach to minimize data loss:
@@ -934,9 +1034,9 @@ Use a two-pass approach to minimize data loss:
 proj review
 ```
 
-This shows what was logged vs git activity, with suggestions for items that may need logging.
+This shows what was logged vs git activity, and suggests decisions, tasks, and blockers found in the diff (dependency changes, new TODO/FIXME comments, disabled tests, reverts). Use `proj review --json` to read the suggestions and `proj review --accept 1,3` to log the ones that apply.
 
-If `proj status` shows a nudge like "Session active 30+ min, 0 decisions logged", either log decisions or run `proj review`.
+If `proj status` shows a nudge (💡), act on it: each one names the command to run, such as `proj review` when decisions haven't been logged or `proj blocker resolve` for a blocker that's been open for days. `proj resume --for-ai` lists the same nudges under `nudges`, each with `rule`, `message`, and `action`.
 
 ### Ending Sessions
 
@@ -971,13 +1071,13 @@ proj init --name "<name>" --type <type> --description "<desc>" [--auto-commit] [
 ```
 
 **proj uninstall --project** (removing tracking):
-Confirm with user: "Remove proj tracking from this project? This deletes all session history, decisions, and tasks."
+Confirm with user: "Remove proj tracking from this project? Its data is exported to ~/.proj/exports and kept in the trash (`proj trash`) for 30 days."
 ```bash
 proj uninstall --project --force
 ```
 
 **proj uninstall --all** (complete removal):
-Confirm with user: "Remove proj from ALL registered projects? This cannot be undone."
+Confirm with user: "Remove proj from ALL registered projects? Their data is exported to ~/.proj/exports and kept in the trash (`proj trash`) for 30 days."
 ```bash
 proj uninstall --all --force
 ```
@@ -1015,6 +1115,7 @@ When you need to recall previous decisions, check context, or understand project
 - Use `proj context "<topic>"` to search decisions, notes, and git history
 - Use `proj context "<topic>" --ranked` for relevance-scored results
 - Use `proj context recent --recent` for the last 10 logged items
+- Use `proj context "<topic>" --profile codegen` (or `review`, `planning`) for output sized for the job
 - Prefer `proj context` over re-reading files - it uses fewer tokens
 
 Before making a decision that might duplicate or contradict a previous one, check:
@@ -1030,6 +1131,8 @@ Mark the task as completed and proj will automatically commit the changes:
 proj task update <id> --status completed
 ```
 
+When a commit you write works on a task, name it in the message (`#t12`, or a `Proj-Task: 12` trailer); `proj task show 12` then lists it.
+
 **Before ending a session:**
 Run `git status` to verify no uncommitted changes remain.
 
@@ -1043,8 +1146,9 @@ Run `git status` to verify no uncommitted changes remain.
 | `proj context <topic> --ranked` | Relevance-scored search results |
 | `proj context recent --recent` | Last 10 items across all tables |
 | `proj tasks` | List current tasks |
-| `proj review` | Cleanup pass - shows logged items vs git activity |
+| `proj review` | Cleanup pass - shows logged items vs git activity, with suggestions (`--json`, `--accept`) |
 | `proj log decision "topic" "decision" "rationale"` | Record a decision |
+| `proj log commits --since <date>` | Recorded commits with files changed and linked tasks |
 | `proj session end "summary"` | Close session with summary |
 
 ### Database Queries (for AI agents)
@@ -1079,186 +1183,134 @@ Tracking database: `.tracking/tracking.db`
 5. **Query before re-reading** - a SQL query uses fewer tokens than re-reading files
 "#;
 
-/// Setup project-local AGENTS.md with CLAUDE.md and GEMINI.md symlinks
-/// This creates the unified agent configuration in the project directory
-/// Setup project-local AGENTS.md with CLAUDE.md and GEMINI.md symlinks
+/// Setup project-local AGENTS.md with CLAUDE.md and GEMINI.md symlinks, and the
+/// Cursor, Windsurf, and Aider rules files generated from it
 /// This creates the unified agent configuration in the project directory
 /// Public so it can be called from status.rs to ensure AGENTS.md exists
 pub fn setup_project_agents(project_root: &std::path::Path) -> Result<()> {
     let agents_path = project_root.join("AGENTS.md");
-    let claude_path = project_root.join("CLAUDE.md");
-    let gemini_path = project_root.join("GEMINI.md");
-
-    // Check if AGENTS.md already exists
-    if agents_path.exists() {
-        // Check if it already has proj tracking section
-        let content = std::fs::read_to_string(&agents_path)?;
-        if !content.contains("## Project Tracking") {
-            // Append the project tracking section
-            let new_content = format!(
-                "{}\n\n{}",
-                content.trim_end(),
-                PROJECT_AGENTS_TEMPLATE
-                    .trim_start_matches("# Project Context & Rules\n")
-                    .trim_start_matches("This file is the single source of truth for Gemini, Claude, and Codex agents.\n\n")
-            );
-            std::fs::write(&agents_path, new_content)?;
-        }
-    } else {
-        // Check if CLAUDE.md or GEMINI.md exist as real files (not symlinks)
-        // If so, promote one to AGENTS.md (like unify-agents does)
-        if claude_path.exists() && !claude_path.is_symlink() {
-            std::fs::rename(&claude_path, &agents_path)?;
-            // Append proj tracking if not present
-            let content = std::fs::read_to_string(&agents_path)?;
-            if !content.contains("## Project Tracking") {
-                let new_content = format!(
-                    "{}\n\n{}",
-                    content.trim_end(),
-                    PROJECT_AGENTS_TEMPLATE
-                        .trim_start_matches("# Project Context & Rules\n")
-                        .trim_start_matches("This file is the single source of truth for Gemini, Claude, and Codex agents.\n\n")
-                );
-                std::fs::write(&agents_path, new_content)?;
-            }
-        } else if gemini_path.exists() && !gemini_path.is_symlink() {
-            std::fs::rename(&gemini_path, &agents_path)?;
-            // Append proj tracking if not present
-            let content = std::fs::read_to_string(&agents_path)?;
-            if !content.contains("## Project Tracking") {
-                let new_content = format!(
-                    "{}\n\n{}",
-                    content.trim_end(),
-                    PROJECT_AGENTS_TEMPLATE
-                        .trim_start_matches("# Project Context & Rules\n")
-                        .trim_start_matches("This file is the single source of truth for Gemini, Claude, and Codex agents.\n\n")
-                );
-                std::fs::write(&agents_path, new_content)?;
-            }
-        } else {
-            // Create fresh AGENTS.md
-            std::fs::write(&agents_path, PROJECT_AGENTS_TEMPLATE)?;
+    let template = agents::Template::load()?;
+
+    if !agents_path.exists() {
+        // A real CLAUDE.md or GEMINI.md (not a symlink) is promoted to AGENTS.md
+        // (like unify-agents does)
+        let existing = AGENT_ALIASES
+            .iter()
+            .map(|name| project_root.join(name))
+            .find(|path| path.exists() && !path.is_symlink());
+        match existing {
+            Some(path) => std::fs::rename(&path, &agents_path)?,
+            None => std::fs::write(&agents_path, template.fresh_file())?,
         }
     }
 
-    // Create/update symlinks for CLAUDE.md and GEMINI.md
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::symlink;
-
-        // Handle CLAUDE.md
-        if claude_path.exists() {
-            if claude_path.is_symlink() {
-                // Already a symlink, update it
-                std::fs::remove_file(&claude_path)?;
-            } else {
-                // Real file - back it up
-                let backup = project_root.join("CLAUDE.md.bak");
-                std::fs::rename(&claude_path, &backup)?;
-            }
-        }
-        symlink("AGENTS.md", &claude_path)?;
-
-        // Handle GEMINI.md
-        if gemini_path.exists() {
-            if gemini_path.is_symlink() {
-                // Already a symlink, update it
-                std::fs::remove_file(&gemini_path)?;
-            } else {
-                // Real file - back it up
-                let backup = project_root.join("GEMINI.md.bak");
-                std::fs::rename(&gemini_path, &backup)?;
-            }
-        }
-        symlink("AGENTS.md", &gemini_path)?;
+    // Append the proj instructions if the file doesn't have them yet
+    let content = std::fs::read_to_string(&agents_path)?;
+    if !agents::has_instructions(&content) {
+        std::fs::write(
+            &agents_path,
+            format!("{}\n\n{}", content.trim_end(), template.block()),
+        )?;
     }
 
-    #[cfg(windows)]
-    {
-        // On Windows, create copies instead of symlinks (symlinks require admin)
-        if !claude_path.exists() {
-            std::fs::copy(&agents_path, &claude_path)?;
-        }
-        if !gemini_path.exists() {
-            std::fs::copy(&agents_path, &gemini_path)?;
-        }
-    }
+    link_agent_aliases(project_root)?;
+    agents::write_rules_files(project_root)?;
 
     Ok(())
 }
 
-/// Update project-local AGENTS.md if its proj instructions are outdated
-/// Called during `proj upgrade` to ensure AI logging instructions are current
-/// Returns list of updated file paths
-pub fn update_agents_rules_if_outdated() -> Result<Vec<String>> {
-    let mut updated_files = Vec::new();
-
-    // Only update the current project's AGENTS.md
-    if let Ok(cwd) = std::env::current_dir() {
-        let agents_path = cwd.join("AGENTS.md");
-        if agents_path.exists() {
-            if update_single_agents_file(&agents_path)? {
-                updated_files.push(agents_path.display().to_string());
-            }
-        }
-    }
+/// Tool-specific instruction files that mirror AGENTS.md
+pub const AGENT_ALIASES: [&str; 2] = ["CLAUDE.md", "GEMINI.md"];
 
-    Ok(updated_files)
+/// Point CLAUDE.md and GEMINI.md at AGENTS.md
+pub fn link_agent_aliases(project_root: &Path) -> Result<()> {
+    for name in AGENT_ALIASES {
+        link_agent_file(project_root, name)?;
+    }
+    Ok(())
 }
 
-/// Update a single AGENTS.md file if its proj instructions are outdated
-/// Returns true if the file was updated
-fn update_single_agents_file(path: &std::path::Path) -> Result<bool> {
-    let content = std::fs::read_to_string(path)?;
-
-    // Check if the file has proj tracking section at all
-    if !content.contains("## Project Tracking") {
-        return Ok(false); // No proj section to update
+/// Replace an alias with a link to AGENTS.md, backing up a real file with other content.
+/// Windows needs Developer Mode (or admin) for symlinks, so it falls back to a hard
+/// link, then a copy; sync_agent_files keeps those from drifting.
+fn link_agent_file(project_root: &Path, name: &str) -> Result<()> {
+    let agents_path = project_root.join("AGENTS.md");
+    let path = project_root.join(name);
+
+    if path.is_symlink() {
+        // Already a symlink, update it
+        std::fs::remove_file(&path)?;
+    } else if path.exists() {
+        if std::fs::read(&path)? == std::fs::read(&agents_path)? {
+            // An earlier link or copy of AGENTS.md
+            std::fs::remove_file(&path)?;
+        } else {
+            // Real file - back it up
+            std::fs::rename(&path, project_root.join(format!("{}.bak", name)))?;
+        }
     }
 
-    // Check if it has the latest instructions by looking for key sections
-    // v1.8.3+: "### Two-Pass Logging" and "### Mid-Session Context Recall"
-    if content.contains("### Two-Pass Logging")
-        && content.contains("### Mid-Session Context Recall")
+    #[cfg(unix)]
+    std::os::unix::fs::symlink("AGENTS.md", &path)?;
+
+    #[cfg(windows)]
     {
-        return Ok(false); // Already has latest instructions
+        if std::os::windows::fs::symlink_file("AGENTS.md", &path).is_err()
+            && std::fs::hard_link(&agents_path, &path).is_err()
+        {
+            std::fs::copy(&agents_path, &path)?;
+        }
     }
 
-    // Instructions are outdated - need to replace the entire Project Tracking section
-    let section_start = match content.find("## Project Tracking") {
-        Some(pos) => pos,
-        None => return Ok(false),
+    Ok(())
+}
+
+/// Re-sync CLAUDE.md/GEMINI.md that are hard links or copies rather than symlinks.
+/// Git checkouts and editors that save by replacing the file turn links into copies;
+/// an alias edited more recently than AGENTS.md wins and the rest are relinked.
+/// Returns the alias whose edits were taken, if any.
+pub fn sync_agent_files(project_root: &Path) -> Result<Option<&'static str>> {
+    let agents_path = project_root.join("AGENTS.md");
+    let Ok(agents) = std::fs::read(&agents_path) else {
+        return Ok(None);
     };
 
-    // Find where the section ends (next ## heading or end of file)
-    let after_header = section_start + "## Project Tracking".len();
-    let section_end = content[after_header..]
-        .find("\n## ")
-        .map(|pos| after_header + pos)
-        .unwrap_or(content.len());
-
-    // Extract just the Project Tracking section from the template
-    let tracking_section = PROJECT_AGENTS_TEMPLATE
-        .trim_start_matches("# Project Context & Rules\n")
-        .trim_start_matches(
-            "This file is the single source of truth for Gemini, Claude, and Codex agents.\n\n",
-        );
+    let modified = |path: &Path| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    let drifted: Vec<(&'static str, PathBuf)> = AGENT_ALIASES
+        .into_iter()
+        .map(|name| (name, project_root.join(name)))
+        .filter(|(_, path)| {
+            !path.is_symlink()
+                && path.is_file()
+                && std::fs::read(path).map_or(true, |content| content != agents)
+        })
+        .collect();
+    if drifted.is_empty() {
+        return Ok(None);
+    }
 
-    // Build new content
-    let before_section = &content[..section_start];
-    let after_section = &content[section_end..];
+    let agents_modified = modified(&agents_path);
+    let newest = drifted
+        .iter()
+        .filter(|(_, path)| modified(path) > agents_modified)
+        .max_by_key(|(_, path)| modified(path));
+
+    let adopted = match newest {
+        Some((name, path)) => {
+            // Write in place so aliases still hard-linked to AGENTS.md follow along
+            std::fs::write(&agents_path, std::fs::read(path)?)?;
+            Some(*name)
+        }
+        None => None,
+    };
 
-    let new_content = format!(
-        "{}\n\n{}{}",
-        before_section.trim_end(),
-        tracking_section.trim(),
-        if after_section.trim().is_empty() {
-            String::from("\n")
-        } else {
-            format!("\n\n{}", after_section.trim_start())
+    for (name, path) in &drifted {
+        // Stale copies hold nothing new; only competing edits get a backup
+        if modified(path) <= agents_modified {
+            std::fs::remove_file(path)?;
         }
-    );
+        link_agent_file(project_root, name)?;
+    }
 
-    std::fs::write(path, new_content)?;
-    Ok(true)
+    Ok(adopted)
 }
@@ -1,102 +1,770 @@
-// Log commands - decision, note, blocker, question
+// Log commands - decision, note, blocker, question, and browsing recorded commits
 
-use anyhow::{Context, Result};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use rusqlite::Connection;
+use rusqlite::{Connection, OptionalExtension};
+use serde::Deserialize;
 
-use crate::cli::{LogCommands, LogSubcommand};
-use crate::database::open_database;
-use crate::paths::get_tracking_db_path;
-use crate::session::get_or_create_session_with_info;
+use crate::cli::{parse_note_category, LogCommands, LogSubcommand, NOTE_CATEGORIES};
+use crate::commands::attachment::{self, Item};
+use crate::commands::task::TaskEntry;
+use crate::commands::{decision, docs};
+use crate::database::{get_schema_version, open_database, record_uuid, write_transaction};
+use crate::error::ProjError;
+use crate::git;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::porcelain;
+use crate::session::{
+    find_session_at, get_or_create_session_with_info, get_session, resolve_author, track_branch,
+};
+use crate::stdin_input;
+use crate::tags::{self, Tagged};
+use crate::text::truncate;
+use crate::webhooks;
+
+/// Fields of an entry on stdin, in argument order (for tab-separated lines)
+const DECISION_FIELDS: &[&str] = &["topic", "decision", "rationale"];
+const NOTE_FIELDS: &[&str] = &["category", "title", "content"];
+const BLOCKER_FIELDS: &[&str] = &["description"];
+const QUESTION_FIELDS: &[&str] = &["question", "context"];
 
 pub fn run(cmd: LogCommands) -> Result<()> {
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
         .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
 
-    // Get or create session for all log operations
-    let session_result = get_or_create_session_with_info(&conn)?;
-    let session = session_result.session;
+    // Browsing commits logs nothing, so it needs no session
+    if let LogSubcommand::Commits {
+        since,
+        task,
+        files,
+        limit,
+    } = &cmd.command
+    {
+        if cmd.session.is_some() || cmd.at.is_some() || !cmd.attach.is_empty() {
+            return Err(ProjError::InvalidInput(
+                "--session, --at, and --attach don't apply to 'proj log commits'".to_string(),
+            )
+            .into());
+        }
+        let filters = CommitFilters {
+            since: since.as_deref(),
+            author: cmd.author.as_deref(),
+            task: *task,
+            limit: *limit,
+        };
+        return cmd_log_commits(&conn, &filters, *files);
+    }
 
-    // Notify if a new session was started or stale session was closed
-    if let Some(closed) = session_result.auto_closed_session {
-        eprintln!(
-            "{} Previous session #{} was stale. Started new session #{}",
-            "⚠".yellow(),
-            closed.session_id,
-            session.session_id
-        );
+    stdin_input::set_saved(cmd.stdin_text.clone());
+
+    // A batch is checked in full before anything, the session included, is touched
+    if let LogSubcommand::Batch { file, dry_run } = &cmd.command {
+        if !cmd.attach.is_empty() {
+            return Err(ProjError::InvalidInput(
+                "--attach doesn't apply to 'proj log batch'; use 'proj attachment add' afterwards"
+                    .to_string(),
+            )
+            .into());
+        }
+        let entries = read_batch(&conn, file)?;
+        if *dry_run {
+            return report_batch(&entries);
+        }
+        let target = log_target(&conn, &cmd)?;
+        return cmd_log_batch(&conn, &target, &entries);
     }
 
+    let target = log_target(&conn, &cmd)?;
+
+    // '-' alone in place of the first field: entries from stdin. Otherwise '-' reads
+    // just that field.
     match cmd.command {
+        LogSubcommand::Decision {
+            mut topic,
+            decision,
+            mut rationale,
+            tags,
+            sections,
+        } => {
+            if topic == "-" && decision.is_none() && rationale.is_none() {
+                let entries: Vec<DecisionEntry> = stdin_input::entries(DECISION_FIELDS)?;
+                return log_all(&conn, &entries, |tx, e| {
+                    let tags = with_flags(&e.tags, &tags);
+                    let sections = with_flags(&e.sections, &sections);
+                    cmd_log_decision(
+                        tx,
+                        &target,
+                        &e.topic,
+                        &e.decision,
+                        e.rationale.as_deref(),
+                        &tags,
+                        &sections,
+                    )
+                });
+            }
+            let mut decision = required(decision, "DECISION")?;
+            let mut fields = vec![&mut topic, &mut decision];
+            fields.extend(rationale.as_mut());
+            stdin_input::resolve_fields(&mut fields)?;
+            cmd_log_decision(
+                &conn,
+                &target,
+                &topic,
+                &decision,
+                rationale.as_deref(),
+                &tags,
+                &sections,
+            )
+            .map(Logged::report)
+        }
+        LogSubcommand::Note {
+            mut category,
+            title,
+            content,
+            tags,
+        } => {
+            if category == "-" && title.is_none() && content.is_none() {
+                let entries = note_entries(NOTE_FIELDS, None)?;
+                return log_all(&conn, &entries, |tx, e| {
+                    let tags = with_flags(&e.tags, &tags);
+                    cmd_log_note(tx, &target, &e.category, &e.title, &e.content, &tags)
+                });
+            }
+            let (mut title, mut content) =
+                (required(title, "TITLE")?, required(content, "CONTENT")?);
+            stdin_input::resolve_fields(&mut [&mut category, &mut title, &mut content])?;
+            cmd_log_note(&conn, &target, &category, &title, &content, &tags).map(Logged::report)
+        }
+        LogSubcommand::Blocker {
+            mut description,
+            task,
+        } => {
+            if description == "-" {
+                let entries: Vec<BlockerEntry> = stdin_input::entries(BLOCKER_FIELDS)?;
+                return log_all(&conn, &entries, |tx, e| {
+                    cmd_log_blocker(tx, &target, &e.description, e.task.or(task))
+                });
+            }
+            stdin_input::resolve_fields(&mut [&mut description])?;
+            cmd_log_blocker(&conn, &target, &description, task).map(Logged::report)
+        }
+        LogSubcommand::Question {
+            question,
+            mut context,
+        } => {
+            if question == "-" && context.is_none() {
+                let entries: Vec<QuestionEntry> = stdin_input::entries(QUESTION_FIELDS)?;
+                return log_all(&conn, &entries, |tx, e| {
+                    cmd_log_question(tx, &target, &e.question, e.context.as_deref())
+                });
+            }
+            let mut question = question;
+            let mut fields = vec![&mut question];
+            fields.extend(context.as_mut());
+            stdin_input::resolve_fields(&mut fields)?;
+            cmd_log_question(&conn, &target, &question, context.as_deref()).map(Logged::report)
+        }
+        LogSubcommand::Batch { .. } | LogSubcommand::Commits { .. } => {
+            unreachable!("handled above")
+        }
+    }
+}
+
+/// The session, time, branch, author, and attachments new entries are logged with
+fn log_target(conn: &Connection, cmd: &LogCommands) -> Result<LogTarget> {
+    // Files first, so a missing one doesn't leave a new session behind
+    let attachments = attachment::read_files(&cmd.attach)?;
+    Ok(LogTarget {
+        author: resolve_author(cmd.author.as_deref()),
+        attachments,
+        ..resolve_log_target(conn, cmd.session, cmd.at.as_deref())?
+    })
+}
+
+/// A decision read from stdin
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DecisionEntry {
+    topic: String,
+    decision: String,
+    rationale: Option<String>,
+    #[serde(default)]
+    tags: Vec<String>,
+    /// Docs section IDs
+    #[serde(default)]
+    sections: Vec<String>,
+}
+
+/// A context note read from stdin (`proj log note -`, `proj note add -`)
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NoteEntry {
+    #[serde(default)]
+    pub category: String,
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// A blocker read from stdin
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct BlockerEntry {
+    description: String,
+    task: Option<i64>,
+}
+
+/// A question read from stdin
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct QuestionEntry {
+    question: String,
+    context: Option<String>,
+}
+
+/// An entry in `proj log batch`
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum BatchEntry {
+    Decision(DecisionEntry),
+    Note(NoteEntry),
+    Blocker(BlockerEntry),
+    Question(QuestionEntry),
+    Task(TaskEntry),
+}
+
+impl BatchEntry {
+    fn kind(&self) -> &'static str {
+        match self {
+            BatchEntry::Decision(_) => "decision",
+            BatchEntry::Note(_) => "note",
+            BatchEntry::Blocker(_) => "blocker",
+            BatchEntry::Question(_) => "question",
+            BatchEntry::Task(_) => "task",
+        }
+    }
+
+    fn label(&self) -> &str {
+        match self {
+            BatchEntry::Decision(d) => &d.topic,
+            BatchEntry::Note(n) => &n.title,
+            BatchEntry::Blocker(b) => &b.description,
+            BatchEntry::Question(q) => &q.question,
+            BatchEntry::Task(t) => &t.description,
+        }
+    }
+
+    /// What serde can't check: note categories, task priorities and due dates, and
+    /// the tasks blockers point at
+    fn validate(&mut self, conn: &Connection) -> Result<()> {
+        match self {
+            BatchEntry::Note(note) => {
+                note.category = parse_note_category(&note.category)
+                    .map_err(|e| anyhow::anyhow!("invalid category '{}' ({})", note.category, e))?;
+            }
+            BatchEntry::Task(task) => task.validate()?,
+            BatchEntry::Decision(decision) => {
+                docs::find_sections(&decision.sections)?;
+            }
+            BatchEntry::Blocker(BlockerEntry {
+                task: Some(task_id),
+                ..
+            }) => {
+                let exists: bool = conn
+                    .query_row("SELECT 1 FROM tasks WHERE task_id = ?1", [*task_id], |_| {
+                        Ok(true)
+                    })
+                    .optional()?
+                    .unwrap_or(false);
+                if !exists {
+                    bail!("Task #{} not found", task_id);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// Read and check a batch file ('-' for stdin). Every entry is checked and every
+/// problem reported, so one run finds them all.
+fn read_batch(conn: &Connection, file: &str) -> Result<Vec<BatchEntry>> {
+    let text = if file == "-" {
+        stdin_input::read()?
+    } else if let Some(saved) = stdin_input::saved() {
+        // Queued for approval: the file as it was then
+        saved
+    } else {
+        std::fs::read_to_string(file).with_context(|| format!("Failed to read {}", file))?
+    };
+    let values: Vec<serde_json::Value> = serde_json::from_str(&text)
+        .map_err(|e| ProjError::InvalidInput(format!("Expected a JSON array of entries: {}", e)))?;
+    if values.is_empty() {
+        return Err(ProjError::NothingToDo("No entries in the batch.".to_string()).into());
+    }
+
+    let total = values.len();
+    let mut entries = Vec::with_capacity(total);
+    let mut problems = 0;
+    for (i, value) in values.into_iter().enumerate() {
+        let entry = serde_json::from_value::<BatchEntry>(value)
+            .map_err(anyhow::Error::from)
+            .and_then(|mut entry| entry.validate(conn).map(|_| entry));
+        match entry {
+            Ok(entry) => entries.push(entry),
+            Err(e) => {
+                problems += 1;
+                eprintln!("{} Entry {}: {:#}", "✗".red(), i + 1, e);
+            }
+        }
+    }
+    if problems > 0 {
+        return Err(ProjError::InvalidInput(format!(
+            "{} of {} entries invalid; nothing was logged",
+            problems, total
+        ))
+        .into());
+    }
+    Ok(entries)
+}
+
+/// --dry-run: what would be logged
+fn report_batch(entries: &[BatchEntry]) -> Result<()> {
+    if porcelain::enabled() {
+        for (i, entry) in entries.iter().enumerate() {
+            porcelain::print(&[&(i + 1).to_string(), entry.kind(), entry.label()]);
+        }
+        return Ok(());
+    }
+    for (i, entry) in entries.iter().enumerate() {
+        println!(
+            "{} Entry {} ({}): {}",
+            "✓".green(),
+            i + 1,
+            entry.kind(),
+            truncate(entry.label(), 50)
+        );
+    }
+    println!(
+        "{} entries valid; nothing logged (--dry-run).",
+        entries.len()
+    );
+    Ok(())
+}
+
+/// Log a checked batch in one transaction
+fn cmd_log_batch(conn: &Connection, target: &LogTarget, entries: &[BatchEntry]) -> Result<()> {
+    log_all(conn, entries, |tx, entry| match entry {
+        BatchEntry::Decision(d) => cmd_log_decision(
+            tx,
+            target,
+            &d.topic,
+            &d.decision,
+            d.rationale.as_deref(),
+            &d.tags,
+            &d.sections,
+        ),
+        BatchEntry::Note(n) => cmd_log_note(tx, target, &n.category, &n.title, &n.content, &n.tags),
+        BatchEntry::Blocker(b) => cmd_log_blocker(tx, target, &b.description, b.task),
+        BatchEntry::Question(q) => cmd_log_question(tx, target, &q.question, q.context.as_deref()),
+        BatchEntry::Task(t) => t.add(
+            tx,
+            target.session_id,
+            target.branch.as_deref(),
+            target.author.as_deref(),
+            target.created_at.as_deref(),
+        ),
+    })?;
+
+    if !porcelain::enabled() {
+        let mut counts: Vec<(&str, usize)> = Vec::new();
+        for entry in entries {
+            match counts.iter_mut().find(|(kind, _)| *kind == entry.kind()) {
+                Some((_, count)) => *count += 1,
+                None => counts.push((entry.kind(), 1)),
+            }
+        }
+        let counts: Vec<String> = counts
+            .iter()
+            .map(|(kind, count)| {
+                format!("{} {}{}", count, kind, if *count == 1 { "" } else { "s" })
+            })
+            .collect();
+        println!("Logged {} entries: {}", entries.len(), counts.join(", "));
+    }
+    Ok(())
+}
+
+/// Read a command's stdin input (or batch file) the way running it would, without
+/// logging anything, so `proj approve` isn't handed entries that can only fail
+pub fn check_input(conn: &Connection, cmd: &LogCommands) -> Result<()> {
+    stdin_input::set_saved(cmd.stdin_text.clone());
+    match &cmd.command {
+        LogSubcommand::Batch { file, .. } => read_batch(conn, file).map(|_| ()),
         LogSubcommand::Decision {
             topic,
             decision,
             rationale,
-        } => cmd_log_decision(
-            &conn,
-            session.session_id,
-            &topic,
-            &decision,
-            rationale.as_deref(),
-        ),
+            ..
+        } => {
+            if topic == "-" && decision.is_none() && rationale.is_none() {
+                return stdin_input::entries::<DecisionEntry>(DECISION_FIELDS).map(|_| ());
+            }
+            let (mut topic, mut decision) =
+                (topic.clone(), required(decision.clone(), "DECISION")?);
+            let mut rationale = rationale.clone();
+            let mut fields = vec![&mut topic, &mut decision];
+            fields.extend(rationale.as_mut());
+            stdin_input::resolve_fields(&mut fields)
+        }
         LogSubcommand::Note {
             category,
             title,
             content,
-        } => cmd_log_note(&conn, session.session_id, &category, &title, &content),
-        LogSubcommand::Blocker { description } => {
-            cmd_log_blocker(&conn, session.session_id, &description)
+            ..
+        } => {
+            if category == "-" && title.is_none() && content.is_none() {
+                return note_entries(NOTE_FIELDS, None).map(|_| ());
+            }
+            let (mut title, mut content) = (
+                required(title.clone(), "TITLE")?,
+                required(content.clone(), "CONTENT")?,
+            );
+            stdin_input::resolve_fields(&mut [&mut category.clone(), &mut title, &mut content])
+        }
+        LogSubcommand::Blocker { description, .. } if description == "-" => {
+            stdin_input::entries::<BlockerEntry>(BLOCKER_FIELDS).map(|_| ())
         }
         LogSubcommand::Question { question, context } => {
-            cmd_log_question(&conn, session.session_id, &question, context.as_deref())
+            if question == "-" && context.is_none() {
+                return stdin_input::entries::<QuestionEntry>(QUESTION_FIELDS).map(|_| ());
+            }
+            let mut context = context.clone();
+            let mut question = question.clone();
+            let mut fields = vec![&mut question];
+            fields.extend(context.as_mut());
+            stdin_input::resolve_fields(&mut fields)
         }
+        LogSubcommand::Blocker { .. } | LogSubcommand::Commits { .. } => Ok(()),
     }
 }
 
-/// Log a decision
-fn cmd_log_decision(
+/// Notes on stdin with their categories checked (any prefix works), so a bad one
+/// stops the command before anything is logged. `default_category` fills in
+/// entries that leave it out.
+pub fn note_entries(fields: &[&str], default_category: Option<&str>) -> Result<Vec<NoteEntry>> {
+    let mut entries: Vec<NoteEntry> = stdin_input::entries(fields)?;
+    for (i, entry) in entries.iter_mut().enumerate() {
+        if entry.category.is_empty() {
+            entry.category = default_category.unwrap_or_default().to_string();
+        }
+        entry.category = parse_note_category(&entry.category).map_err(|e| {
+            ProjError::InvalidInput(format!(
+                "Entry {}: invalid category '{}' ({})",
+                i + 1,
+                entry.category,
+                e
+            ))
+        })?;
+    }
+    Ok(entries)
+}
+
+/// Log every entry in one transaction, so a failure leaves none of them behind.
+/// Nothing is printed or sent to webhooks until the transaction has committed.
+fn log_all<'a, T>(
     conn: &Connection,
+    entries: &'a [T],
+    mut log: impl FnMut(&Connection, &'a T) -> Result<Logged<'a>>,
+) -> Result<()> {
+    let tx = write_transaction(conn)?;
+    let mut logged = Vec::with_capacity(entries.len());
+    for (i, entry) in entries.iter().enumerate() {
+        logged.push(
+            log(&tx, entry).with_context(|| format!("Entry {} (nothing was logged)", i + 1))?,
+        );
+    }
+    tx.commit()?;
+    logged.into_iter().for_each(Logged::report);
+    Ok(())
+}
+
+/// What a logged entry prints and sends to webhooks, held back until it's committed
+#[must_use]
+pub struct Logged<'a>(Box<dyn FnOnce() + 'a>);
+
+impl<'a> Logged<'a> {
+    pub fn new(report: impl FnOnce() + 'a) -> Self {
+        Logged(Box::new(report))
+    }
+
+    pub fn report(self) {
+        (self.0)()
+    }
+}
+
+/// An entry's own tags (or sections) plus the ones given with --tag (--section)
+fn with_flags(own: &[String], flags: &[String]) -> Vec<String> {
+    own.iter().chain(flags).cloned().collect()
+}
+
+/// A field clap leaves optional so that '-' alone can stand in for all of them
+fn required(value: Option<String>, name: &str) -> Result<String> {
+    value.ok_or_else(|| {
+        ProjError::InvalidInput(format!(
+            "Missing <{}> (or give '-' alone to read entries from stdin)",
+            name
+        ))
+        .into()
+    })
+}
+
+/// Session and timestamp a log entry is attributed to
+struct LogTarget {
     session_id: i64,
+    /// Explicit created_at ("YYYY-MM-DD HH:MM:SS", UTC); None means now
+    created_at: Option<String>,
+    /// Git branch to tag the entry with (only known for entries logged now)
+    branch: Option<String>,
+    /// Who the entry is attributed to (questions don't record one)
+    author: Option<String>,
+    /// Files attached to every entry logged (--attach)
+    attachments: Vec<attachment::File>,
+}
+
+/// Work out which session (and timestamp) a log entry belongs to.
+///
+/// - No flags: the current session (created if needed)
+/// - `--session`: that session, timestamped at its end if it has ended
+/// - `--at`: the session running at that time, falling back to the current one
+fn resolve_log_target(
+    conn: &Connection,
+    session_id: Option<i64>,
+    at: Option<&str>,
+) -> Result<LogTarget> {
+    let created_at = at.map(parse_log_timestamp).transpose()?;
+
+    if let Some(id) = session_id {
+        let Some(session) = get_session(conn, id)? else {
+            bail!("Session #{} not found", id);
+        };
+
+        if let Some(ref ts) = created_at {
+            let started = session.started_at.format("%Y-%m-%d %H:%M:%S").to_string();
+            let ended = session
+                .ended_at
+                .map(|e| e.format("%Y-%m-%d %H:%M:%S").to_string());
+            if *ts < started || ended.as_ref().is_some_and(|e| ts > e) {
+                eprintln!(
+                    "{} {} is outside session #{} ({} – {})",
+                    "⚠".yellow(),
+                    ts,
+                    id,
+                    started,
+                    ended.as_deref().unwrap_or("now")
+                );
+            }
+        }
+
+        let created_at = created_at.or_else(|| {
+            session
+                .ended_at
+                .map(|e| e.format("%Y-%m-%d %H:%M:%S").to_string())
+        });
+        return Ok(LogTarget {
+            session_id: id,
+            created_at,
+            branch: None,
+            author: None,
+            attachments: Vec::new(),
+        });
+    }
+
+    if let Some(ref ts) = created_at {
+        if let Some(session) = find_session_at(conn, ts)? {
+            return Ok(LogTarget {
+                session_id: session.session_id,
+                created_at,
+                branch: None,
+                author: None,
+                attachments: Vec::new(),
+            });
+        }
+    }
+
+    // Get or create session for all log operations
+    let session_result = get_or_create_session_with_info(conn)?;
+    let session = session_result.session;
+
+    // Notify if a new session was started or stale session was closed
+    if let Some(closed) = session_result.auto_closed_session {
+        eprintln!(
+            "{} Previous session #{} was stale. Started new session #{}",
+            "⚠".yellow(),
+            closed.session_id,
+            session.session_id
+        );
+    }
+
+    // Backdated entries can't know which branch was checked out at the time
+    let branch = if created_at.is_none() {
+        track_branch(conn, session.session_id)?
+    } else {
+        None
+    };
+
+    Ok(LogTarget {
+        session_id: session.session_id,
+        created_at,
+        branch,
+        author: None,
+        attachments: Vec::new(),
+    })
+}
+
+/// Parse a user-supplied timestamp (local time unless it carries an offset)
+/// into SQLite's UTC "YYYY-MM-DD HH:MM:SS" format
+fn parse_log_timestamp(input: &str) -> Result<String> {
+    let input = input.trim();
+    let format = "%Y-%m-%d %H:%M:%S";
+
+    if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(input) {
+        return Ok(dt.with_timezone(&chrono::Utc).format(format).to_string());
+    }
+
+    let naive = [
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%Y-%m-%dT%H:%M:%S",
+        "%Y-%m-%dT%H:%M",
+    ]
+    .iter()
+    .find_map(|f| chrono::NaiveDateTime::parse_from_str(input, f).ok())
+    .or_else(|| {
+        chrono::NaiveDate::parse_from_str(input, "%Y-%m-%d")
+            .ok()
+            .and_then(|d| d.and_hms_opt(12, 0, 0))
+    });
+
+    let Some(naive) = naive else {
+        bail!(
+            "Invalid timestamp '{}'. Use \"YYYY-MM-DD HH:MM[:SS]\", \"YYYY-MM-DD\", or RFC 3339",
+            input
+        );
+    };
+
+    let local = naive
+        .and_local_timezone(chrono::Local)
+        .earliest()
+        .with_context(|| format!("Timestamp '{}' does not exist in local time", input))?;
+    let utc = local.with_timezone(&chrono::Utc);
+
+    if utc > chrono::Utc::now() {
+        bail!("Timestamp '{}' is in the future", input);
+    }
+
+    Ok(utc.format(format).to_string())
+}
+
+/// Log a decision
+fn cmd_log_decision<'a>(
+    conn: &Connection,
+    target: &'a LogTarget,
     topic: &str,
     decision: &str,
     rationale: Option<&str>,
-) -> Result<()> {
+    tag_names: &[String],
+    section_ids: &[String],
+) -> Result<Logged<'a>> {
+    if !section_ids.is_empty() {
+        require_decision_sections(conn)?;
+    }
+    let sections = docs::find_sections(section_ids)?;
+    let (session_id, at, branch) = (
+        target.session_id,
+        target.created_at.as_deref(),
+        target.branch.as_deref(),
+    );
+
+    // Under the topic it's registered as, or the one an alias points at
+    let topic = decision::resolve_topic(conn, topic)?;
+
     // Insert decision
     conn.execute(
-        "INSERT INTO decisions (session_id, topic, decision, rationale, status) VALUES (?1, ?2, ?3, ?4, 'active')",
-        rusqlite::params![session_id, topic, decision, rationale],
+        "INSERT INTO decisions (session_id, topic, decision, rationale, status, created_at, branch, author) VALUES (?1, ?2, ?3, ?4, 'active', COALESCE(?5, datetime('now')), ?6, ?7)",
+        rusqlite::params![session_id, topic, decision, rationale, at, branch, target.author],
     )?;
 
     let decision_id = conn.last_insert_rowid();
+    let decision_tags = tags::add_tags(conn, Tagged::Decision(decision_id), tag_names)?;
+    for (section_id, title) in &sections {
+        conn.execute(
+            "INSERT OR IGNORE INTO decision_sections (decision_id, section_id, title) VALUES (?1, ?2, ?3)",
+            rusqlite::params![decision_id, section_id, title],
+        )?;
+    }
+
+    let attached = attachment::attach(
+        conn,
+        Item::Decision(decision_id),
+        &target.attachments,
+        target.author.as_deref(),
+        at,
+    )?;
 
     // Insert into activity_log
     let summary = format!("Decision: {} - {}", topic, truncate(decision, 50));
-    insert_activity_log(conn, session_id, "decision", decision_id, &summary)?;
+    insert_activity_log(conn, session_id, at, "decision", decision_id, &summary)?;
 
-    // Update FTS index
-    let fts_content = format!("{} {} {}", topic, decision, rationale.unwrap_or(""));
-    insert_fts_entry(conn, &fts_content, "decisions", decision_id)?;
+    Ok(Logged::new(move || {
+        if porcelain::enabled() {
+            porcelain::print(&[&decision_id.to_string()]);
+            return;
+        }
+        println!(
+            "{} Logged decision #{}: {}{}",
+            "✓".green(),
+            decision_id,
+            topic,
+            tags_suffix(&decision_tags)
+        );
+        for (section_id, title) in &sections {
+            println!("  {} {} {}", "→".dimmed(), section_id.cyan(), title);
+        }
+        attachment::print_attached(&target.attachments, &attached);
+    }))
+}
 
-    println!(
-        "{} Logged decision #{}: {}",
-        "✓".green(),
-        decision_id,
-        topic
-    );
-    Ok(())
+/// Log context notes in the current session (`proj note add`)
+pub fn add_notes(conn: &Connection, author: Option<&str>, notes: &[NoteEntry]) -> Result<()> {
+    let target = LogTarget {
+        author: resolve_author(author),
+        ..resolve_log_target(conn, None, None)?
+    };
+    log_all(conn, notes, |tx, n| {
+        cmd_log_note(tx, &target, &n.category, &n.title, &n.content, &n.tags)
+    })
 }
 
 /// Log a context note
-fn cmd_log_note(
+fn cmd_log_note<'a>(
     conn: &Connection,
-    session_id: i64,
-    category: &str,
-    title: &str,
+    target: &'a LogTarget,
+    category: &'a str,
+    title: &'a str,
     content: &str,
-) -> Result<()> {
+    tag_names: &[String],
+) -> Result<Logged<'a>> {
+    let (session_id, at, branch) = (
+        target.session_id,
+        target.created_at.as_deref(),
+        target.branch.as_deref(),
+    );
+
     // Validate category
-    let valid_categories = ["goal", "constraint", "assumption", "requirement", "note"];
+    let valid_categories = NOTE_CATEGORIES;
     if !valid_categories.contains(&category) {
         println!(
             "{} Invalid category '{}'. Valid categories: {}",
@@ -104,127 +772,408 @@ fn cmd_log_note(
             category,
             valid_categories.join(", ")
         );
-        return Ok(());
+        return Ok(Logged::new(|| {}));
     }
 
     // Insert note
     conn.execute(
-        "INSERT INTO context_notes (session_id, category, title, content, status) VALUES (?1, ?2, ?3, ?4, 'active')",
-        rusqlite::params![session_id, category, title, content],
+        "INSERT INTO context_notes (session_id, category, title, content, status, created_at, updated_at, branch, author) VALUES (?1, ?2, ?3, ?4, 'active', COALESCE(?5, datetime('now')), COALESCE(?5, datetime('now')), ?6, ?7)",
+        rusqlite::params![session_id, category, title, content, at, branch, target.author],
     )?;
 
     let note_id = conn.last_insert_rowid();
+    let note_tags = tags::add_tags(conn, Tagged::Note(note_id), tag_names)?;
+    let attached = attachment::attach(
+        conn,
+        Item::Note(note_id),
+        &target.attachments,
+        target.author.as_deref(),
+        at,
+    )?;
 
     // Insert into activity_log
     let summary = format!("Note [{}]: {} - {}", category, title, truncate(content, 40));
-    insert_activity_log(conn, session_id, "note", note_id, &summary)?;
-
-    // Update FTS index
-    let fts_content = format!("{} {} {}", category, title, content);
-    insert_fts_entry(conn, &fts_content, "context_notes", note_id)?;
+    insert_activity_log(conn, session_id, at, "note", note_id, &summary)?;
 
-    println!(
-        "{} Logged note #{} [{}]: {}",
-        "✓".green(),
-        note_id,
-        category,
-        title
-    );
-    Ok(())
+    Ok(Logged::new(move || {
+        if porcelain::enabled() {
+            porcelain::print(&[&note_id.to_string()]);
+            return;
+        }
+        println!(
+            "{} Logged note #{} [{}]: {}{}",
+            "✓".green(),
+            note_id,
+            category,
+            title,
+            tags_suffix(&note_tags)
+        );
+        attachment::print_attached(&target.attachments, &attached);
+    }))
 }
 
 /// Log a blocker
-fn cmd_log_blocker(conn: &Connection, session_id: i64, description: &str) -> Result<()> {
+fn cmd_log_blocker<'a>(
+    conn: &Connection,
+    target: &'a LogTarget,
+    description: &'a str,
+    task_id: Option<i64>,
+) -> Result<Logged<'a>> {
+    let (session_id, at, branch) = (
+        target.session_id,
+        target.created_at.as_deref(),
+        target.branch.as_deref(),
+    );
+
+    if let Some(id) = task_id {
+        let exists: bool = conn
+            .query_row("SELECT 1 FROM tasks WHERE task_id = ?1", [id], |_| Ok(true))
+            .unwrap_or(false);
+        if !exists {
+            bail!("Task #{} not found", id);
+        }
+    }
+
     // Insert blocker
     conn.execute(
-        "INSERT INTO blockers (session_id, description, status) VALUES (?1, ?2, 'active')",
-        rusqlite::params![session_id, description],
+        "INSERT INTO blockers (session_id, description, status, related_task_id, created_at, branch, author) VALUES (?1, ?2, 'active', ?3, COALESCE(?4, datetime('now')), ?5, ?6)",
+        rusqlite::params![session_id, description, task_id, at, branch, target.author],
     )?;
 
     let blocker_id = conn.last_insert_rowid();
+    let attached = attachment::attach(
+        conn,
+        Item::Blocker(blocker_id),
+        &target.attachments,
+        target.author.as_deref(),
+        at,
+    )?;
+
+    // Mark the linked task as blocked
+    if let Some(id) = task_id {
+        conn.execute(
+            "UPDATE tasks SET status = 'blocked', blocked_by = ?1 WHERE task_id = ?2",
+            rusqlite::params![format!("blocker #{}", blocker_id), id],
+        )?;
+    }
 
     // Insert into activity_log
     let summary = format!("Blocker: {}", truncate(description, 60));
-    insert_activity_log(conn, session_id, "blocker", blocker_id, &summary)?;
+    insert_activity_log(conn, session_id, at, "blocker", blocker_id, &summary)?;
 
-    // Update FTS index
-    insert_fts_entry(conn, description, "blockers", blocker_id)?;
+    let uuid = record_uuid(conn, "blockers", "blocker_id", blocker_id);
+    Ok(Logged::new(move || {
+        if porcelain::enabled() {
+            porcelain::print(&[&blocker_id.to_string()]);
+        } else {
+            println!(
+                "{} Logged blocker #{}: {}",
+                "✗".red(),
+                blocker_id,
+                truncate(description, 50)
+            );
+            if let Some(id) = task_id {
+                println!("  {} Task #{} marked blocked", "→".red(), id);
+            }
+            attachment::print_attached(&target.attachments, &attached);
+        }
 
-    println!(
-        "{} Logged blocker #{}: {}",
-        "✗".red(),
-        blocker_id,
-        truncate(description, 50)
-    );
-    Ok(())
+        webhooks::notify(
+            webhooks::BLOCKER_ADDED,
+            &format!("Blocker #{}: {}", blocker_id, description),
+            serde_json::json!({
+                "blocker_id": blocker_id,
+                "uuid": uuid,
+                "description": description,
+                "task_id": task_id,
+            }),
+        );
+    }))
 }
 
 /// Log a question
-fn cmd_log_question(
+fn cmd_log_question<'a>(
     conn: &Connection,
-    session_id: i64,
-    question: &str,
+    target: &'a LogTarget,
+    question: &'a str,
     context: Option<&str>,
-) -> Result<()> {
+) -> Result<Logged<'a>> {
+    let (session_id, at, branch) = (
+        target.session_id,
+        target.created_at.as_deref(),
+        target.branch.as_deref(),
+    );
+
     // Insert question
     conn.execute(
-        "INSERT INTO questions (session_id, question, context, status) VALUES (?1, ?2, ?3, 'open')",
-        rusqlite::params![session_id, question, context],
+        "INSERT INTO questions (session_id, question, context, status, created_at, branch) VALUES (?1, ?2, ?3, 'open', COALESCE(?4, datetime('now')), ?5)",
+        rusqlite::params![session_id, question, context, at, branch],
     )?;
 
     let question_id = conn.last_insert_rowid();
+    let attached = attachment::attach(
+        conn,
+        Item::Question(question_id),
+        &target.attachments,
+        target.author.as_deref(),
+        at,
+    )?;
 
     // Insert into activity_log
     let summary = format!("Question: {}", truncate(question, 60));
-    insert_activity_log(conn, session_id, "question", question_id, &summary)?;
+    insert_activity_log(conn, session_id, at, "question", question_id, &summary)?;
 
-    // Update FTS index
-    let fts_content = format!("{} {}", question, context.unwrap_or(""));
-    insert_fts_entry(conn, &fts_content, "questions", question_id)?;
+    Ok(Logged::new(move || {
+        if porcelain::enabled() {
+            porcelain::print(&[&question_id.to_string()]);
+            return;
+        }
+        println!(
+            "{} Logged question #{}: {}",
+            "?".cyan(),
+            question_id,
+            truncate(question, 50)
+        );
+        attachment::print_attached(&target.attachments, &attached);
+    }))
+}
 
-    println!(
-        "{} Logged question #{}: {}",
-        "?".cyan(),
-        question_id,
-        truncate(question, 50)
-    );
+/// Which recorded commits `proj log commits` shows
+struct CommitFilters<'a> {
+    /// YYYY-MM-DD
+    since: Option<&'a str>,
+    /// Part of the commit author's name
+    author: Option<&'a str>,
+    task: Option<i64>,
+    limit: usize,
+}
+
+/// A recorded commit with its stats and linked tasks
+struct CommitRow {
+    commit_id: i64,
+    short_hash: String,
+    author: Option<String>,
+    message: String,
+    committed_at: String,
+    files_changed: i64,
+    insertions: i64,
+    deletions: i64,
+    branch: Option<String>,
+    task_ids: Vec<i64>,
+}
+
+/// List recorded commits, syncing recent ones from git first
+fn cmd_log_commits(conn: &Connection, filters: &CommitFilters, show_files: bool) -> Result<()> {
+    require_commit_details(conn)?;
+    if let Some(since) = filters.since {
+        if chrono::NaiveDate::parse_from_str(since, "%Y-%m-%d").is_err() {
+            return Err(ProjError::InvalidInput(format!(
+                "Invalid --since '{}'. Use YYYY-MM-DD.",
+                since
+            ))
+            .into());
+        }
+    }
+    if let Ok(root) = get_project_root() {
+        let _ = git::sync_recent_commits(conn, &root, 50);
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT c.commit_id, c.short_hash, c.author, c.message, c.committed_at,
+                c.files_changed, c.insertions, c.deletions, c.branch,
+                (SELECT GROUP_CONCAT(t.task_id) FROM git_commit_tasks t WHERE t.commit_id = c.commit_id)
+         FROM git_commits c
+         WHERE (?1 IS NULL OR c.committed_at >= ?1)
+           AND (?2 IS NULL OR c.author LIKE '%' || ?2 || '%')
+           AND (?3 IS NULL OR EXISTS (
+                SELECT 1 FROM git_commit_tasks t WHERE t.commit_id = c.commit_id AND t.task_id = ?3))
+         ORDER BY c.committed_at DESC, c.commit_id DESC
+         LIMIT ?4",
+    )?;
+    let commits = stmt
+        .query_map(
+            rusqlite::params![
+                filters.since,
+                filters.author,
+                filters.task,
+                filters.limit as i64
+            ],
+            |row| {
+                let tasks: Option<String> = row.get(9)?;
+                let mut task_ids: Vec<i64> = tasks
+                    .unwrap_or_default()
+                    .split(',')
+                    .filter_map(|id| id.parse().ok())
+                    .collect();
+                task_ids.sort_unstable();
+                Ok(CommitRow {
+                    commit_id: row.get(0)?,
+                    short_hash: row.get(1)?,
+                    author: row.get(2)?,
+                    message: row.get(3)?,
+                    committed_at: row.get(4)?,
+                    files_changed: row.get(5)?,
+                    insertions: row.get(6)?,
+                    deletions: row.get(7)?,
+                    branch: row.get(8)?,
+                    task_ids,
+                })
+            },
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    if porcelain::enabled() {
+        for c in &commits {
+            if show_files {
+                for (path, ins, del) in commit_files(conn, c.commit_id)? {
+                    porcelain::print(&[&c.short_hash, &path, &ins.to_string(), &del.to_string()]);
+                }
+                continue;
+            }
+            let tasks: Vec<String> = c.task_ids.iter().map(|id| id.to_string()).collect();
+            porcelain::print(&[
+                &c.short_hash,
+                &c.committed_at,
+                c.author.as_deref().unwrap_or_default(),
+                c.branch.as_deref().unwrap_or_default(),
+                &c.files_changed.to_string(),
+                &c.insertions.to_string(),
+                &c.deletions.to_string(),
+                &tasks.join(","),
+                &c.message,
+            ]);
+        }
+        return Ok(());
+    }
+
+    if commits.is_empty() {
+        println!(
+            "No commits recorded{}.",
+            if filters.since.is_some() || filters.author.is_some() || filters.task.is_some() {
+                " that match"
+            } else {
+                ""
+            }
+        );
+        return Ok(());
+    }
+
+    println!("\n{} ({}):\n", "Commits".bold(), commits.len());
+    for c in &commits {
+        let tasks: Vec<String> = c
+            .task_ids
+            .iter()
+            .map(|id| format!("#task-{}", id))
+            .collect();
+        println!(
+            "  {}  {}  {}{}",
+            c.short_hash.yellow(),
+            c.committed_at.get(..16).unwrap_or(&c.committed_at),
+            c.message,
+            if tasks.is_empty() {
+                String::new()
+            } else {
+                format!("  {}", tasks.join(" ").cyan())
+            }
+        );
+        let mut detail = c.author.clone().unwrap_or_default();
+        if let Some(branch) = &c.branch {
+            detail.push_str(&format!(" on {}", branch));
+        }
+        println!(
+            "           {}",
+            format!(
+                "{} · {} file{}, +{} -{}",
+                detail,
+                c.files_changed,
+                if c.files_changed == 1 { "" } else { "s" },
+                c.insertions,
+                c.deletions
+            )
+            .dimmed()
+        );
+        if show_files {
+            for (path, ins, del) in commit_files(conn, c.commit_id)? {
+                println!(
+                    "             {} {}",
+                    path,
+                    format!("+{} -{}", ins, del).dimmed()
+                );
+            }
+        }
+    }
+    println!();
+    Ok(())
+}
+
+/// Files a recorded commit changed: (path, insertions, deletions)
+fn commit_files(conn: &Connection, commit_id: i64) -> Result<Vec<(String, i64, i64)>> {
+    let mut stmt = conn.prepare(
+        "SELECT path, insertions, deletions FROM git_commit_files WHERE commit_id = ?1 ORDER BY path",
+    )?;
+    let files = stmt
+        .query_map([commit_id], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(files)
+}
+
+/// Fail with an upgrade hint if the tracking database predates per-file commit stats
+fn require_commit_details(conn: &Connection) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='git_commit_files')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.16".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Fail with an upgrade hint if the tracking database predates decision-section links
+fn require_decision_sections(conn: &Connection) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='decision_sections')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.21".to_string(),
+        }
+        .into());
+    }
     Ok(())
 }
 
+/// " #a #b" after a confirmation line, or nothing when untagged
+fn tags_suffix(item_tags: &[String]) -> String {
+    if item_tags.is_empty() {
+        String::new()
+    } else {
+        format!(" {}", tags::format_tags(item_tags).cyan())
+    }
+}
+
 /// Insert an entry into the activity log
 fn insert_activity_log(
     conn: &Connection,
     session_id: i64,
+    at: Option<&str>,
     action_type: &str,
     action_id: i64,
     summary: &str,
 ) -> Result<()> {
     conn.execute(
-        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, ?2, ?3, ?4)",
-        rusqlite::params![session_id, action_type, action_id, summary],
-    )?;
-    Ok(())
-}
-
-/// Insert an entry into the FTS index
-fn insert_fts_entry(
-    conn: &Connection,
-    content: &str,
-    table_name: &str,
-    record_id: i64,
-) -> Result<()> {
-    conn.execute(
-        "INSERT INTO tracking_fts (content, table_name, record_id) VALUES (?1, ?2, ?3)",
-        rusqlite::params![content, table_name, record_id],
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary, timestamp) VALUES (?1, ?2, ?3, ?4, COALESCE(?5, datetime('now')))",
+        rusqlite::params![session_id, action_type, action_id, summary, at],
     )?;
     Ok(())
 }
-
-/// Truncate a string to a maximum length
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
-    }
-}
@@ -0,0 +1,448 @@
+// Merge command - fold another contributor's tracking database into this one
+//
+// Records are matched by UUID, which a record keeps in every copy of a database.
+// Records missing here are added under their UUID, entries with the same content
+// are kept once (both people logged them, or a copied database gave them a second
+// UUID when it was upgraded), and incoming decisions that disagree with one here
+// on the same topic become open questions so someone settles them.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::types::Value;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::database::{get_schema_version, open_database, write_transaction};
+use crate::error::ProjError;
+use crate::paths::get_tracking_db_path;
+use crate::records::{
+    self, fill_self_refs, last_touched, read_records, value_text, write_record, DeferredRef,
+    Record, TableSpec,
+};
+use crate::text::truncate;
+use crate::SCHEMA_VERSION;
+
+/// How one table's records are recognized and carried over by a merge
+struct MergeSpec {
+    records: &'static TableSpec,
+    /// Columns that make two separately logged records the same entry (empty: key only)
+    content: &'static [&'static str],
+    /// item_tags column pointing at this table, so tags come along with new records
+    tag_col: Option<&'static str>,
+}
+
+/// Merge order matters: referenced tables come first
+const MERGE_SPECS: &[MergeSpec] = &[
+    MergeSpec {
+        records: &records::SESSIONS,
+        content: &["started_at"],
+        tag_col: None,
+    },
+    MergeSpec {
+        records: &records::TASKS,
+        content: &["description"],
+        tag_col: Some("task_id"),
+    },
+    MergeSpec {
+        records: &records::DECISIONS,
+        content: &["topic", "decision"],
+        tag_col: Some("decision_id"),
+    },
+    MergeSpec {
+        records: &records::NOTES,
+        content: &["category", "title", "content"],
+        tag_col: Some("note_id"),
+    },
+    MergeSpec {
+        records: &records::BLOCKERS,
+        content: &["description"],
+        tag_col: None,
+    },
+    MergeSpec {
+        records: &records::QUESTIONS,
+        content: &["question"],
+        tag_col: None,
+    },
+    MergeSpec {
+        records: &records::COMMITS,
+        content: &[],
+        tag_col: None,
+    },
+];
+
+/// Per-table merge outcome
+#[derive(Default)]
+struct TableResult {
+    added: usize,
+    updated: usize,
+    /// Same key, same content
+    present: usize,
+    /// Different key, same content: logged by both contributors
+    duplicates: usize,
+}
+
+/// An incoming decision that disagrees with one already here
+struct DecisionConflict {
+    topic: String,
+    local: (i64, String, Option<String>),
+    incoming: (i64, String, Option<String>),
+}
+
+pub fn run(file: &Path, dry_run: bool) -> Result<()> {
+    let label = file.display().to_string();
+    if !file.is_file() {
+        return Err(ProjError::InvalidInput(format!("{} doesn't exist", label)).into());
+    }
+
+    let db_path = get_tracking_db_path()?;
+    let same_file = match (file.canonicalize(), db_path.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    };
+    if same_file {
+        return Err(ProjError::InvalidInput(
+            "That's this project's own tracking database.".to_string(),
+        )
+        .into());
+    }
+
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    let version = get_schema_version(&conn)?.unwrap_or_else(|| "1.0".to_string());
+    if version != SCHEMA_VERSION {
+        return Err(ProjError::SchemaMismatch {
+            found: version,
+            required: SCHEMA_VERSION.to_string(),
+        }
+        .into());
+    }
+
+    // Someone else's file: only ever read, in one transaction for a consistent view
+    let other = Connection::open_with_flags(
+        file,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )
+    .with_context(|| format!("Failed to open {}", label))?;
+    let other_version = get_schema_version(&other).ok().flatten().ok_or_else(|| {
+        ProjError::InvalidInput(format!("{} isn't a proj tracking database", label))
+    })?;
+    if other_version != SCHEMA_VERSION {
+        return Err(ProjError::InvalidInput(format!(
+            "{} is at schema v{} (this project: v{}). Run 'proj upgrade' on whichever is older first.",
+            label, other_version, SCHEMA_VERSION
+        ))
+        .into());
+    }
+
+    let other_tx = other.unchecked_transaction()?;
+    let mut incoming = Vec::new();
+    for spec in MERGE_SPECS {
+        let table = spec.records;
+        incoming.push(
+            read_records(&other_tx, table, table.filter)
+                .with_context(|| format!("Failed to read {} from {}", table.name, label))?,
+        );
+    }
+
+    let tx = write_transaction(&conn)?;
+
+    let last_decision: i64 = tx.query_row(
+        "SELECT COALESCE(MAX(decision_id), 0) FROM decisions",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let mut aliases: HashMap<&'static str, HashMap<String, String>> = HashMap::new();
+    let mut results = Vec::new();
+    for (spec, records) in MERGE_SPECS.iter().zip(&incoming) {
+        let result = merge_table(&tx, &other_tx, spec, records, &mut aliases)
+            .with_context(|| format!("Failed to merge {}", spec.records.name))?;
+        results.push((spec.records.name, result));
+    }
+    other_tx.rollback()?;
+
+    let conflicts = find_decision_conflicts(&tx, last_decision)?;
+
+    let added: usize = results.iter().map(|(_, r)| r.added).sum();
+    let updated: usize = results.iter().map(|(_, r)| r.updated).sum();
+
+    // Print summary
+    println!(
+        "{}",
+        if dry_run {
+            format!("Merge preview: {}", label)
+        } else {
+            format!("Merge: {}", label)
+        }
+        .bold()
+    );
+    println!("{}", "-".repeat(60));
+    for (name, r) in &results {
+        if r.added + r.updated + r.present + r.duplicates == 0 {
+            continue;
+        }
+        println!(
+            "  {:<10} {} new, {} updated, {} already here, {} duplicates",
+            name, r.added, r.updated, r.present, r.duplicates
+        );
+    }
+    if added + updated == 0 {
+        println!("  Nothing new to merge.");
+    }
+
+    let mut question_ids = Vec::new();
+    if !conflicts.is_empty() {
+        println!();
+        println!(
+            "{} Conflicting decisions (both kept; each raised as an open question):",
+            "⚠".yellow()
+        );
+        for c in &conflicts {
+            println!(
+                "  {}: #{} {}{} vs #{} {}{}",
+                c.topic.bold(),
+                c.local.0,
+                truncate(&c.local.1, 30),
+                by(&c.local.2),
+                c.incoming.0,
+                truncate(&c.incoming.1, 30),
+                by(&c.incoming.2)
+            );
+            question_ids.push(raise_question(&tx, c, &label)?);
+        }
+    }
+
+    if dry_run {
+        tx.rollback()?;
+        println!();
+        println!("Dry run - nothing was written.");
+        return Ok(());
+    }
+
+    if added + updated > 0 {
+        let summary = format!("Merged {}: {} added, {} updated", label, added, updated);
+        tx.execute(
+            "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (NULL, 'merge', NULL, ?1)",
+            [&summary],
+        )?;
+    }
+    tx.commit()?;
+
+    if added + updated > 0 {
+        println!();
+        println!(
+            "{} Merged {} new and {} updated records",
+            "✓".green(),
+            added,
+            updated
+        );
+    }
+    if !question_ids.is_empty() {
+        let ids: Vec<String> = question_ids.iter().map(|id| format!("#{}", id)).collect();
+        println!(
+            "  Review questions {} and answer each with 'proj question answer <id> \"...\"'.",
+            ids.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+/// Add the other database's records of one table that aren't here yet
+fn merge_table(
+    conn: &Connection,
+    other: &Connection,
+    spec: &MergeSpec,
+    incoming: &[(i64, String, Record)],
+    aliases: &mut HashMap<&'static str, HashMap<String, String>>,
+) -> Result<TableResult> {
+    let table = spec.records;
+    let local = read_records(conn, table, "1 = 1")?;
+    let by_key: HashMap<&str, (i64, &Record)> = local
+        .iter()
+        .map(|(pk, key, record)| (key.as_str(), (*pk, record)))
+        .collect();
+
+    // Records still open here (a running session) are never overwritten
+    let open: HashSet<i64> = conn
+        .prepare(&format!(
+            "SELECT {} FROM {} WHERE NOT ({})",
+            table.pk, table.table, table.filter
+        ))?
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+
+    // Records matched by key can't also stand in for a duplicate
+    let mut claimed: HashSet<i64> = incoming
+        .iter()
+        .filter_map(|(_, key, _)| by_key.get(key.as_str()).map(|(pk, _)| *pk))
+        .collect();
+
+    let mut result = TableResult::default();
+    let mut deferred: Vec<DeferredRef> = Vec::new();
+    let mut table_aliases: HashMap<String, String> = HashMap::new();
+
+    for (other_pk, key, record) in incoming {
+        let record = with_aliases(table, record, aliases);
+
+        if let Some(&(pk, existing)) = by_key.get(key.as_str()) {
+            if *existing == record || open.contains(&pk) {
+                result.present += 1;
+            } else if last_touched(table, &record) > last_touched(table, existing) {
+                write_record(conn, table, Some(pk), &record, &mut deferred)?;
+                result.updated += 1;
+            } else {
+                result.present += 1;
+            }
+            continue;
+        }
+
+        if !spec.content.is_empty() {
+            let content = content_of(spec, &record);
+            let duplicate = local.iter().find(|(pk, _, existing)| {
+                !claimed.contains(pk) && content_of(spec, existing) == content
+            });
+            if let Some((pk, local_key, _)) = duplicate {
+                claimed.insert(*pk);
+                table_aliases.insert(key.clone(), local_key.clone());
+                result.duplicates += 1;
+                continue;
+            }
+        }
+
+        let new_pk = write_record(conn, table, None, &record, &mut deferred)?;
+        if let Some(tag_col) = spec.tag_col {
+            copy_tags(other, conn, tag_col, *other_pk, new_pk)?;
+        }
+        result.added += 1;
+    }
+
+    // Self-references to a duplicate point at the record it matched here
+    let deferred = deferred
+        .into_iter()
+        .map(|(pk, col, target)| {
+            let target = table_aliases.get(&target).cloned().unwrap_or(target);
+            (pk, col, target)
+        })
+        .collect();
+    fill_self_refs(conn, table, deferred)?;
+    aliases.insert(table.name, table_aliases);
+
+    Ok(result)
+}
+
+/// Point references at the records they were merged into
+fn with_aliases(
+    table: &TableSpec,
+    record: &Record,
+    aliases: &HashMap<&'static str, HashMap<String, String>>,
+) -> Record {
+    let mut record = record.clone();
+    for (col, target) in table.refs {
+        if let Some(Value::Text(key)) = record.get_mut(*col) {
+            if let Some(alias) = aliases.get(target.name).and_then(|m| m.get(key.as_str())) {
+                *key = alias.clone();
+            }
+        }
+    }
+    record
+}
+
+/// Copy the tags of a merged record
+fn copy_tags(
+    other: &Connection,
+    conn: &Connection,
+    tag_col: &str,
+    other_pk: i64,
+    new_pk: i64,
+) -> Result<()> {
+    let mut stmt = other.prepare(&format!(
+        "SELECT tag, created_at FROM item_tags WHERE {} = ?1",
+        tag_col
+    ))?;
+    let tags = stmt
+        .query_map([other_pk], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    for (tag, created_at) in tags {
+        conn.execute(
+            &format!(
+                "INSERT INTO item_tags (tag, {}, created_at) VALUES (?1, ?2, COALESCE(?3, datetime('now')))",
+                tag_col
+            ),
+            rusqlite::params![tag, new_pk, created_at],
+        )?;
+    }
+    Ok(())
+}
+
+/// Active decisions added by the merge whose topic already had a different active decision
+fn find_decision_conflicts(conn: &Connection, last_before: i64) -> Result<Vec<DecisionConflict>> {
+    let mut stmt = conn.prepare(
+        "SELECT l.decision_id, l.decision, l.author, n.decision_id, n.decision, n.author, n.topic
+         FROM decisions n
+         JOIN decisions l ON LOWER(l.topic) = LOWER(n.topic)
+         WHERE n.decision_id > ?1 AND n.status = 'active'
+           AND l.decision_id <= ?1 AND l.status = 'active'
+           AND l.decision != n.decision
+         ORDER BY n.decision_id, l.decision_id",
+    )?;
+    let rows = stmt
+        .query_map([last_before], |row| {
+            Ok(DecisionConflict {
+                local: (row.get(0)?, row.get(1)?, row.get(2)?),
+                incoming: (row.get(3)?, row.get(4)?, row.get(5)?),
+                topic: row.get(6)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// Log an open question asking which of two conflicting decisions stands
+fn raise_question(conn: &Connection, conflict: &DecisionConflict, source: &str) -> Result<i64> {
+    let question = format!(
+        "Which decision on '{}' stands: #{} or #{}?",
+        conflict.topic, conflict.local.0, conflict.incoming.0
+    );
+    let context = format!(
+        "Merged from {}. #{}: {}{}. #{}: {}{}.",
+        source,
+        conflict.local.0,
+        conflict.local.1,
+        by(&conflict.local.2),
+        conflict.incoming.0,
+        conflict.incoming.1,
+        by(&conflict.incoming.2)
+    );
+    conn.execute(
+        "INSERT INTO questions (session_id, question, context, status) VALUES (NULL, ?1, ?2, 'open')",
+        rusqlite::params![question, context],
+    )?;
+    Ok(conn.last_insert_rowid())
+}
+
+/// The columns that make two records the same entry, trimmed and case-folded
+fn content_of(spec: &MergeSpec, record: &Record) -> Vec<String> {
+    spec.content
+        .iter()
+        .map(|col| {
+            record
+                .get(*col)
+                .map(value_text)
+                .unwrap_or_default()
+                .trim()
+                .to_lowercase()
+        })
+        .collect()
+}
+
+/// " (author)" when the author is known
+fn by(author: &Option<String>) -> String {
+    author
+        .as_ref()
+        .map_or(String::new(), |a| format!(" ({})", a))
+}
@@ -1,20 +1,20 @@
 // Migrate command - convert existing project to proj format
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 use dialoguer::{Confirm, Input, Select};
 
-use crate::config::{ProjectConfig, Registry, RegistryEntry};
+use crate::config::{ProjectConfig, RankingConfig, Registry, RegistryEntry};
 use crate::database::open_database;
-use crate::paths::{ensure_dir, get_registry_path};
-use crate::schema::{FTS_SCHEMA, TRACKING_SCHEMA};
+use crate::paths::{ensure_dir, get_registry_path, get_start_dir, same_path};
+use crate::schema::{FTS_SCHEMA, FTS_TRIGGERS, TRACKING_SCHEMA};
 use crate::SCHEMA_VERSION;
 
 pub fn run() -> Result<()> {
-    let project_root = std::env::current_dir()?;
+    let project_root = get_start_dir()?;
     let tracking_path = project_root.join(".tracking");
 
     if tracking_path.exists() {
@@ -101,6 +101,26 @@ pub fn run() -> Result<()> {
         auto_commit: false,
         auto_commit_mode: "prompt".to_string(),
         auto_commit_on_task: true,
+        session_timeout_hours: 8,
+        auto_close_policy: "close".to_string(),
+        commit_template: "[proj] {summary}".to_string(),
+        task_commit_template: "[proj] Completed task #{task_id}: {task}".to_string(),
+        scan_todos_on_status: false,
+        ranking: RankingConfig::default(),
+        webhooks: Vec::new(),
+        task_imports: Vec::new(),
+        task_import_refresh_hours: 24,
+        retention: Default::default(),
+        nudges: Default::default(),
+        agent_writes: "direct".to_string(),
+        context_profiles: Default::default(),
+        usage_metrics: false,
+        output_cache: true,
+        offline: false,
+        ca_bundle: None,
+        trash_retention_days: 30,
+        workspace: false,
+        workspace_members: Vec::new(),
     };
 
     let config_path = tracking_path.join("config.json");
@@ -116,6 +136,8 @@ pub fn run() -> Result<()> {
         .with_context(|| "Failed to initialize tracking database schema")?;
     conn.execute_batch(FTS_SCHEMA)
         .with_context(|| "Failed to initialize FTS5 search")?;
+    conn.execute_batch(FTS_TRIGGERS)
+        .with_context(|| "Failed to initialize FTS5 search")?;
 
     conn.execute(
         "INSERT INTO project_meta (key, value) VALUES ('schema_version', ?1)",
@@ -181,7 +203,7 @@ fn detect_existing_content(path: &PathBuf) -> ExistingContent {
 }
 
 /// Detect project type from files
-fn detect_project_type(path: &PathBuf) -> Option<String> {
+fn detect_project_type(path: &Path) -> Option<String> {
     if path.join("Cargo.toml").exists() {
         return Some("rust".to_string());
     }
@@ -213,7 +235,7 @@ fn prompt_project_type() -> Result<String> {
 }
 
 /// Register project in global registry
-fn register_project(path: &PathBuf, name: &str, project_type: &str) -> Result<()> {
+fn register_project(path: &Path, name: &str, project_type: &str) -> Result<()> {
     let registry_path = get_registry_path()?;
 
     if let Some(parent) = registry_path.parent() {
@@ -232,7 +254,7 @@ fn register_project(path: &PathBuf, name: &str, project_type: &str) -> Result<()
     if registry
         .registered_projects
         .iter()
-        .any(|p| p.path == path_str)
+        .any(|p| same_path(Path::new(&p.path), path))
     {
         return Ok(());
     }
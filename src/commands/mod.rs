@@ -1,32 +1,70 @@
 // Command implementations - each will be fully implemented in Phase 2+
 
+pub mod agents;
+pub mod approve;
 pub mod archive;
+pub mod attachment;
+pub mod audit;
 pub mod backup;
+pub mod backup_verify;
+pub mod blocker;
 pub mod check;
+pub mod ci;
 pub mod cleanup;
 pub mod compress;
+pub mod config;
 pub mod context;
 pub mod dashboard;
+pub mod dashboard_tui;
+pub mod db;
+pub mod decision;
+pub mod decision_adr;
 pub mod delta;
 pub mod docs;
+pub mod docs_import;
+pub mod docs_openapi;
+pub mod docs_snapshot;
+pub mod docs_terms;
+pub mod docs_watch;
+pub mod doctor;
+pub mod embed;
 pub mod enter;
 pub mod export;
 pub mod extend;
+pub mod githook;
 pub mod help;
+pub mod import;
 pub mod init;
 pub mod log;
+pub mod merge;
 pub mod migrate;
+pub mod note;
+pub mod note_capture;
+pub mod prime;
+pub mod prompt;
+pub mod question;
 pub mod register;
 pub mod registered;
+pub mod reindex;
 pub mod release;
+pub mod release_target;
 pub mod resume;
 pub mod review;
 pub mod rollback;
+pub mod scan_todos;
+pub mod search;
 pub mod session;
 pub mod shell;
 pub mod snapshot;
+pub mod standup;
+pub mod stats;
 pub mod status;
+pub mod sync;
 pub mod task;
+pub mod task_import;
+pub mod task_sync;
+pub mod trash;
 pub mod uninstall;
 pub mod update_check;
 pub mod upgrade;
+pub mod watch;
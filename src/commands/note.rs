@@ -0,0 +1,575 @@
+// Note commands - add, list, show, edit, archive
+//
+// Context notes record the goals, constraints, assumptions, and requirements a
+// project works under. Content is markdown: given inline, piped on stdin (or '-'),
+// or written in $EDITOR when neither is there.
+
+use std::fmt::Write as _;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::cli::{NoteCommands, NoteSubcommand, PageArgs, NOTE_CATEGORIES};
+use crate::commands::attachment::{self, Item};
+use crate::commands::log::{self, NoteEntry};
+use crate::commands::note_capture;
+use crate::database::{open_database, write_transaction};
+use crate::error::ProjError;
+use crate::pager;
+use crate::paths::get_tracking_db_path;
+use crate::porcelain;
+use crate::session::get_or_create_session_with_info;
+use crate::stdin_input;
+use crate::tags::{self, Tagged};
+use crate::text::truncate;
+
+const NOTE_STATUSES: [&str; 3] = ["active", "outdated", "archived"];
+
+/// Fields of a note on stdin, in argument order (for tab-separated lines)
+const STDIN_FIELDS: &[&str] = &["title", "content"];
+
+pub fn run(cmd: NoteCommands) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    stdin_input::set_saved(cmd.stdin_text);
+
+    match cmd.command {
+        NoteSubcommand::Add {
+            title,
+            content,
+            from_clipboard,
+            url,
+            readable,
+            category,
+            mut tags,
+            author,
+        } => {
+            // '-' alone: notes from stdin, in this category unless they give their own
+            if title.as_deref() == Some("-")
+                && content.is_none()
+                && url.is_none()
+                && !from_clipboard
+            {
+                let mut entries = log::note_entries(STDIN_FIELDS, Some(&category))?;
+                for entry in &mut entries {
+                    entry.tags.extend(tags.iter().cloned());
+                }
+                return log::add_notes(&conn, author.as_deref(), &entries);
+            }
+            let (title, content) = if let Some(url) = url {
+                tags.push("reference".to_string());
+                let comment = content.as_deref().map(content_arg).transpose()?;
+                url_note(&url, title, comment, readable)?
+            } else if from_clipboard {
+                clipboard_note(title)?
+            } else {
+                let Some(title) = title else {
+                    return Err(ProjError::InvalidInput(
+                        "Missing <TITLE> (or give --url or --from-clipboard)".to_string(),
+                    )
+                    .into());
+                };
+                let content = match content.as_deref() {
+                    Some(text) => content_arg(text)?,
+                    None => content_from_stdin_or_editor("")?,
+                };
+                (title, content)
+            };
+            if title.trim().is_empty() {
+                return Err(
+                    ProjError::InvalidInput("Note title cannot be empty".to_string()).into(),
+                );
+            }
+            if content.is_empty() {
+                return Err(ProjError::NothingToDo(
+                    "Empty note content; nothing saved.".to_string(),
+                )
+                .into());
+            }
+            let note = NoteEntry {
+                category,
+                title,
+                content,
+                tags,
+            };
+            log::add_notes(&conn, author.as_deref(), &[note])
+        }
+        NoteSubcommand::List {
+            category,
+            status,
+            page,
+        } => cmd_note_list(&conn, category.as_deref(), &status, &page),
+        NoteSubcommand::Show { id } => cmd_note_show(&conn, id),
+        NoteSubcommand::Edit {
+            id,
+            title,
+            category,
+            content,
+        } => {
+            let session_id = current_session(&conn)?;
+            cmd_note_edit(&conn, session_id, id, title, category, content)
+        }
+        NoteSubcommand::Archive { id } => {
+            let session_id = current_session(&conn)?;
+            cmd_note_archive(&conn, session_id, id)
+        }
+    }
+}
+
+/// Read a `note add`'s stdin input the way running it would, without saving anything,
+/// so `proj approve` isn't handed notes that can only fail
+pub fn check_input(cmd: &NoteCommands) -> Result<()> {
+    stdin_input::set_saved(cmd.stdin_text.clone());
+    let NoteSubcommand::Add {
+        title,
+        content,
+        from_clipboard: false,
+        url: None,
+        category,
+        ..
+    } = &cmd.command
+    else {
+        return Ok(());
+    };
+    if title.as_deref() == Some("-") && content.is_none() {
+        return log::note_entries(STDIN_FIELDS, Some(category)).map(|_| ());
+    }
+    if title.as_deref().map_or(true, |t| t.trim().is_empty()) {
+        return Err(ProjError::InvalidInput("Note title cannot be empty".to_string()).into());
+    }
+    let content = match content.as_deref() {
+        Some(text) => content_arg(text)?,
+        None => stdin_input::read()?.trim().to_string(),
+    };
+    if content.is_empty() {
+        return Err(
+            ProjError::NothingToDo("Empty note content; nothing saved.".to_string()).into(),
+        );
+    }
+    Ok(())
+}
+
+/// A reference note for a link: titled with the page's title unless one is given,
+/// holding the link, any comment, and with `readable` the page's text
+fn url_note(
+    url: &str,
+    title: Option<String>,
+    comment: Option<String>,
+    readable: bool,
+) -> Result<(String, String)> {
+    let url = url.trim();
+    let page = if title.is_none() || readable {
+        Some(note_capture::fetch_page(url, readable)?)
+    } else {
+        None
+    };
+    let title = title
+        .or_else(|| page.as_ref().and_then(|p| p.title.clone()))
+        .unwrap_or_else(|| url.to_string());
+
+    let mut parts = Vec::new();
+    parts.extend(comment.filter(|c| !c.is_empty()));
+    parts.push(format!("Source: <{}>", url));
+    parts.extend(page.and_then(|p| p.text).filter(|t| !t.is_empty()));
+    Ok((title, parts.join("\n\n")))
+}
+
+/// A note holding the clipboard (or the copy saved when the command was queued),
+/// titled with its first line unless a title is given
+fn clipboard_note(title: Option<String>) -> Result<(String, String)> {
+    let text = match stdin_input::saved() {
+        Some(saved) => saved,
+        None => note_capture::read_clipboard()?,
+    };
+    let content = text.trim().to_string();
+    if content.is_empty() {
+        return Err(
+            ProjError::NothingToDo("The clipboard is empty; nothing saved.".to_string()).into(),
+        );
+    }
+    let title = title.unwrap_or_else(|| {
+        let first = content.lines().next().unwrap_or_default().trim();
+        truncate(first.trim_start_matches('#').trim(), 60)
+    });
+    Ok((title, content))
+}
+
+/// The current session, started if needed
+fn current_session(conn: &Connection) -> Result<i64> {
+    let session_result = get_or_create_session_with_info(conn)?;
+    let session = session_result.session;
+
+    // Notify if a stale session was closed
+    if let Some(closed) = session_result.auto_closed_session {
+        eprintln!(
+            "{} Previous session #{} was stale. Started new session #{}",
+            "⚠".yellow(),
+            closed.session_id,
+            session.session_id
+        );
+    }
+    Ok(session.session_id)
+}
+
+/// List notes, grouped by category in the order categories are listed in
+fn cmd_note_list(
+    conn: &Connection,
+    category: Option<&str>,
+    status: &str,
+    page: &PageArgs,
+) -> Result<()> {
+    if status != "all" && !NOTE_STATUSES.contains(&status) {
+        return Err(ProjError::InvalidInput(format!(
+            "Invalid status '{}'. Valid statuses: {}, all",
+            status,
+            NOTE_STATUSES.join(", ")
+        ))
+        .into());
+    }
+    let status = (status != "all").then_some(status);
+
+    let filter = "FROM context_notes
+         WHERE (?1 IS NULL OR category = ?1) AND (?2 IS NULL OR status = ?2)";
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) {}", filter),
+        rusqlite::params![category, status],
+        |row| row.get(0),
+    )?;
+
+    let order = NOTE_CATEGORIES
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!("WHEN '{}' THEN {}", c, i))
+        .collect::<Vec<_>>()
+        .join(" ");
+    let mut stmt = conn.prepare(&format!(
+        "SELECT note_id, category, title, content, status, created_at {}
+         ORDER BY CASE category {} ELSE {} END, created_at
+         LIMIT ?3 OFFSET ?4",
+        filter,
+        order,
+        NOTE_CATEGORIES.len()
+    ))?;
+    let limit = page.limit.map_or(-1, |l| l as i64);
+    let notes = stmt
+        .query_map(
+            rusqlite::params![category, status, limit, page.offset as i64],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, String>(2)?,
+                    row.get::<_, String>(3)?,
+                    row.get::<_, String>(4)?,
+                    row.get::<_, String>(5)?,
+                ))
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Porcelain: ID, category, status, created, title
+    if porcelain::enabled() {
+        for (note_id, category, title, _, status, created_at) in &notes {
+            porcelain::print(&[&note_id.to_string(), category, status, created_at, title]);
+        }
+        return Ok(());
+    }
+
+    if notes.is_empty() {
+        if total > 0 {
+            println!("No notes past the first {} (--offset).", total);
+        } else {
+            println!("No notes found.");
+        }
+        return Ok(());
+    }
+
+    let mut out = String::new();
+    writeln!(out, "{}", "Context Notes:".bold())?;
+    writeln!(out, "{}", "-".repeat(60))?;
+
+    let shown = notes.len();
+    let mut current_category = String::new();
+    for (note_id, category, title, content, note_status, created_at) in notes {
+        if category != current_category {
+            if !current_category.is_empty() {
+                writeln!(out)?;
+            }
+            writeln!(out, "[{}]", category.to_uppercase())?;
+            current_category = category;
+        }
+        let date = created_at.split(' ').next().unwrap_or(&created_at);
+        let status_suffix = if note_status == "active" {
+            String::new()
+        } else {
+            format!(" ({})", note_status)
+        };
+        writeln!(
+            out,
+            "  #{:<4} {}{} {}",
+            note_id,
+            title.bold(),
+            status_suffix.yellow(),
+            date.dimmed()
+        )?;
+        let first_line = content.lines().find(|l| !l.trim().is_empty());
+        if let Some(line) = first_line {
+            writeln!(out, "        {}", truncate(line.trim(), 70))?;
+        }
+    }
+
+    if (shown as i64) < total {
+        writeln!(
+            out,
+            "{}",
+            format!(
+                "\nShowing {}-{} of {}. Use --limit and --offset for more.",
+                page.offset + 1,
+                page.offset + shown,
+                total
+            )
+            .dimmed()
+        )?;
+    }
+
+    pager::page(&out);
+    Ok(())
+}
+
+/// A note as stored
+struct NoteRow {
+    category: String,
+    title: String,
+    content: String,
+    status: String,
+    created_at: String,
+    updated_at: String,
+    author: Option<String>,
+    branch: Option<String>,
+}
+
+fn load_note(conn: &Connection, note_id: i64) -> Result<NoteRow> {
+    conn.query_row(
+        "SELECT category, title, content, status, created_at, updated_at, author, branch
+         FROM context_notes WHERE note_id = ?1",
+        [note_id],
+        |row| {
+            Ok(NoteRow {
+                category: row.get(0)?,
+                title: row.get(1)?,
+                content: row.get(2)?,
+                status: row.get(3)?,
+                created_at: row.get(4)?,
+                updated_at: row.get(5)?,
+                author: row.get(6)?,
+                branch: row.get(7)?,
+            })
+        },
+    )
+    .optional()?
+    .ok_or_else(|| ProjError::InvalidInput(format!("Note #{} not found", note_id)).into())
+}
+
+/// Show a note with its full content
+fn cmd_note_show(conn: &Connection, note_id: i64) -> Result<()> {
+    let note = load_note(conn, note_id)?;
+    let note_tags = tags::tags_for(conn, Tagged::Note(note_id))?;
+
+    println!(
+        "{} [{}] {}",
+        format!("Note #{}", note_id).bold(),
+        note.category,
+        note.title.bold()
+    );
+    println!("  Status:  {}", note.status);
+    println!("  Created: {}", note.created_at);
+    if note.updated_at != note.created_at {
+        println!("  Updated: {}", note.updated_at);
+    }
+    if let Some(author) = &note.author {
+        println!("  Author:  {}", author);
+    }
+    if let Some(branch) = &note.branch {
+        println!("  Branch:  {}", branch);
+    }
+    if !note_tags.is_empty() {
+        println!("  Tags:    {}", tags::format_tags(&note_tags).cyan());
+    }
+    let files = attachment::attachments_for(conn, Item::Note(note_id))?;
+    if !files.is_empty() {
+        let files: Vec<String> = files
+            .iter()
+            .map(|(id, name)| format!("{} (#{})", name, id))
+            .collect();
+        println!("  Files:   {}", files.join(", "));
+    }
+    println!();
+    println!("{}", note.content);
+    Ok(())
+}
+
+/// Change a note's title, category, or content. With none given, the content is
+/// read from stdin or edited in $EDITOR.
+fn cmd_note_edit(
+    conn: &Connection,
+    session_id: i64,
+    note_id: i64,
+    title: Option<String>,
+    category: Option<String>,
+    content: Option<String>,
+) -> Result<()> {
+    let current = load_note(conn, note_id)?;
+
+    if title.as_deref().is_some_and(|t| t.trim().is_empty()) {
+        return Err(ProjError::InvalidInput("Note title cannot be empty".to_string()).into());
+    }
+    let content = match content.as_deref() {
+        Some(text) => Some(content_arg(text)?),
+        None if title.is_none() && category.is_none() => {
+            Some(content_from_stdin_or_editor(&current.content)?)
+        }
+        None => None,
+    };
+    if content.as_deref().is_some_and(str::is_empty) {
+        return Err(ProjError::InvalidInput(
+            "Note content cannot be empty (use 'proj note archive' to retire a note)".to_string(),
+        )
+        .into());
+    }
+
+    // Only what actually differs counts as an edit
+    let title = title.filter(|t| *t != current.title);
+    let category = category.filter(|c| *c != current.category);
+    let content = content.filter(|c| *c != current.content);
+    if title.is_none() && category.is_none() && content.is_none() {
+        return Err(ProjError::NothingToDo(format!("Note #{} unchanged", note_id)).into());
+    }
+
+    conn.execute(
+        "UPDATE context_notes SET
+           title = COALESCE(?1, title),
+           category = COALESCE(?2, category),
+           content = COALESCE(?3, content),
+           updated_at = datetime('now')
+         WHERE note_id = ?4",
+        rusqlite::params![title, category, content, note_id],
+    )?;
+
+    let mut changes = Vec::new();
+    if let Some(ref t) = title {
+        changes.push(format!(
+            "title: \"{}\" → \"{}\"",
+            truncate(&current.title, 30),
+            truncate(t, 30)
+        ));
+    }
+    if let Some(ref c) = category {
+        changes.push(format!("category → {}", c));
+    }
+    if content.is_some() {
+        changes.push("content updated".to_string());
+    }
+
+    let summary = format!("Note #{} edited: {}", note_id, changes.join(", "));
+    conn.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'note_update', ?2, ?3)",
+        rusqlite::params![session_id, note_id, summary],
+    )?;
+
+    if porcelain::enabled() {
+        porcelain::print(&[&note_id.to_string()]);
+    } else {
+        println!(
+            "{} Edited note #{}: {}",
+            "✓".green(),
+            note_id,
+            changes.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Archive a note
+fn cmd_note_archive(conn: &Connection, session_id: i64, note_id: i64) -> Result<()> {
+    let note = load_note(conn, note_id)?;
+    if note.status == "archived" {
+        return Err(
+            ProjError::NothingToDo(format!("Note #{} is already archived", note_id)).into(),
+        );
+    }
+
+    let tx = write_transaction(conn)?;
+    tx.execute(
+        "UPDATE context_notes SET status = 'archived', updated_at = datetime('now') WHERE note_id = ?1",
+        [note_id],
+    )?;
+    let summary = format!("Note archived: {}", truncate(&note.title, 50));
+    tx.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'note_archive', ?2, ?3)",
+        rusqlite::params![session_id, note_id, summary],
+    )?;
+    tx.commit()?;
+
+    if porcelain::enabled() {
+        porcelain::print(&[&note_id.to_string()]);
+    } else {
+        println!(
+            "{} Archived note #{}: {}",
+            "✓".green(),
+            note_id,
+            truncate(&note.title, 50)
+        );
+    }
+    Ok(())
+}
+
+/// Content given on the command line, where '-' means stdin
+fn content_arg(text: &str) -> Result<String> {
+    let text = if text == "-" {
+        stdin_input::read()?
+    } else {
+        text.to_string()
+    };
+    Ok(text.trim().to_string())
+}
+
+/// Content piped on stdin, or written in $EDITOR starting from `initial`
+fn content_from_stdin_or_editor(initial: &str) -> Result<String> {
+    if stdin_input::is_piped() {
+        return Ok(stdin_input::read()?.trim().to_string());
+    }
+    edit_in_editor(initial)
+}
+
+/// Open $VISUAL or $EDITOR on a temporary markdown file and return what was saved
+fn edit_in_editor(initial: &str) -> Result<String> {
+    let path = std::env::temp_dir().join(format!("proj-note-{}.md", std::process::id()));
+    let initial = if initial.is_empty() {
+        String::new()
+    } else {
+        format!("{}\n", initial)
+    };
+    std::fs::write(&path, initial)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let editor = std::env::var("VISUAL")
+        .or_else(|_| std::env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let args: Vec<&str> = parts.collect();
+
+    let status = Command::new(program).args(&args).arg(&path).status();
+    let content = std::fs::read_to_string(&path);
+    let _ = std::fs::remove_file(&path);
+
+    let status = status.with_context(|| format!("Failed to start editor '{}'", editor))?;
+    if !status.success() {
+        anyhow::bail!("Editor exited with {}; note not saved", status);
+    }
+    Ok(content?.trim().to_string())
+}
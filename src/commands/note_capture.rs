@@ -0,0 +1,312 @@
+// Note capture - note content from the clipboard or a web page
+//
+// `proj note add --from-clipboard` takes the note from the clipboard through the
+// platform's own tool (pbpaste, PowerShell, or wl-paste/xclip/xsel), so no clipboard
+// library is linked in. `proj note add --url` fetches the page for its title and,
+// with --readable, the text of its main content, turned from HTML into plain
+// paragraphs. That's a rough cut, not a reader view: it's there so the gist of the
+// page is in `proj context` without pasting it by hand.
+
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::error::ProjError;
+use crate::http;
+
+/// Readable text kept from a page; past this it's cut at a paragraph
+const MAX_TEXT: usize = 8000;
+
+/// Tags that start or end a paragraph of readable text
+const BLOCK_TAGS: [&str; 16] = [
+    "p",
+    "br",
+    "div",
+    "li",
+    "tr",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "pre",
+    "blockquote",
+    "section",
+    "dd",
+    "dt",
+];
+
+/// A fetched web page
+pub struct Page {
+    pub title: Option<String>,
+    /// Main text, when asked for
+    pub text: Option<String>,
+}
+
+/// Everything on the clipboard
+pub fn read_clipboard() -> Result<String> {
+    let tools: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbpaste", &[])]
+    } else if cfg!(windows) {
+        &[(
+            "powershell",
+            &["-NoProfile", "-Command", "Get-Clipboard -Raw"],
+        )]
+    } else {
+        &[
+            ("wl-paste", &["--no-newline"]),
+            ("xclip", &["-selection", "clipboard", "-o"]),
+            ("xsel", &["--clipboard", "--output"]),
+        ]
+    };
+
+    // wl-paste is installed on X11 desktops too, where it fails, so keep trying
+    let mut failed = None;
+    for (program, args) in tools {
+        match Command::new(program).args(*args).output() {
+            Ok(output) if output.status.success() => {
+                return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+            }
+            Ok(output) => {
+                failed = Some(format!(
+                    "{} failed: {}",
+                    program,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ));
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => failed = Some(format!("{} failed: {}", program, e)),
+        }
+    }
+    match failed {
+        Some(message) => bail!("Couldn't read the clipboard ({})", message),
+        None => bail!(
+            "Couldn't read the clipboard: no clipboard tool found (tried {})",
+            tools
+                .iter()
+                .map(|(program, _)| *program)
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+/// Fetch a page for its title, and its readable text if `readable`
+pub fn fetch_page(url: &str, readable: bool) -> Result<Page> {
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        return Err(ProjError::InvalidInput(format!("'{}' isn't an http(s) URL", url)).into());
+    }
+
+    let response = http::agent(url, Duration::from_secs(15))
+        .get(url)
+        .set("User-Agent", &format!("proj/{}", crate::VERSION))
+        .set("Accept", "text/html, text/plain;q=0.9, */*;q=0.1")
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(code, _) => anyhow!("{}: HTTP {}", url, code),
+            e => anyhow!("{}: {}", url, e),
+        })?;
+    let content_type = response.content_type().to_lowercase();
+    let body = response
+        .into_string()
+        .with_context(|| format!("Failed to read {}", url))?;
+
+    if content_type == "text/plain" {
+        return Ok(Page {
+            title: None,
+            text: readable.then(|| cap(&collapse_blank_lines(&body))),
+        });
+    }
+    if !content_type.contains("html") {
+        return Ok(Page {
+            title: None,
+            text: None,
+        });
+    }
+    Ok(Page {
+        title: html_title(&body),
+        text: readable.then(|| cap(&readable_text(&body))),
+    })
+}
+
+/// The page's <title>, or its og:title when that's missing
+fn html_title(html: &str) -> Option<String> {
+    let lower = html.to_ascii_lowercase();
+    let from_tag = lower.find("<title").and_then(|start| {
+        let open_end = start + lower[start..].find('>')? + 1;
+        let close = open_end + lower[open_end..].find("</title")?;
+        Some(html[open_end..close].to_string())
+    });
+    let from_meta = || {
+        let at = lower.find("property=\"og:title\"")?;
+        let tag_start = lower[..at].rfind('<')?;
+        let tag_end = at + lower[at..].find('>')?;
+        let tag = &html[tag_start..tag_end];
+        let content = tag.find("content=\"")? + "content=\"".len();
+        let end = content + tag[content..].find('"')?;
+        Some(tag[content..end].to_string())
+    };
+    from_tag
+        .or_else(from_meta)
+        .map(|t| {
+            decode_entities(&t)
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|t| !t.is_empty())
+}
+
+/// The text of a page's main content: <article> or <main> when there is one, else
+/// <body>, without scripts, styles, and navigation, one paragraph per block
+fn readable_text(html: &str) -> String {
+    let mut html = html.to_string();
+    for tag in [
+        "script", "style", "noscript", "svg", "nav", "header", "footer", "aside", "form",
+    ] {
+        html = remove_elements(&html, tag);
+    }
+    let main = ["article", "main", "body"]
+        .iter()
+        .find_map(|tag| inner(&html, tag))
+        .unwrap_or(&html)
+        .to_string();
+
+    // Block tags become line breaks and every other tag goes; line breaks in the
+    // source are only spaces
+    let mut text = String::with_capacity(main.len());
+    let mut rest = main.as_str();
+    while let Some(open) = rest.find('<') {
+        text.push_str(&rest[..open].replace(['\n', '\r'], " "));
+        let Some(close) = rest[open..].find('>') else {
+            rest = "";
+            break;
+        };
+        let tag = rest[open + 1..open + close]
+            .trim_start_matches('/')
+            .split(|c: char| c.is_whitespace() || c == '/')
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if BLOCK_TAGS.contains(&tag.as_str()) {
+            text.push('\n');
+        }
+        rest = &rest[open + close + 1..];
+    }
+    text.push_str(&rest.replace(['\n', '\r'], " "));
+
+    let lines: Vec<String> = decode_entities(&text)
+        .lines()
+        .map(|line| line.split_whitespace().collect::<Vec<_>>().join(" "))
+        .collect();
+    lines
+        .iter()
+        .filter(|line| !line.is_empty())
+        .cloned()
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// `html` without any <tag>...</tag> elements (case-insensitive)
+fn remove_elements(html: &str, tag: &str) -> String {
+    let lower = html.to_ascii_lowercase();
+    let (open, close) = (format!("<{}", tag), format!("</{}>", tag));
+    let mut out = String::with_capacity(html.len());
+    let mut pos = 0;
+    while let Some(found) = lower[pos..].find(&open) {
+        let start = pos + found;
+        // Only this tag, not a longer name starting the same ("<nav" in "<navbar")
+        let next = lower[start + open.len()..].chars().next();
+        if !next.is_some_and(|c| c == '>' || c.is_whitespace() || c == '/') {
+            out.push_str(&html[pos..start + open.len()]);
+            pos = start + open.len();
+            continue;
+        }
+        out.push_str(&html[pos..start]);
+        pos = match lower[start..].find(&close) {
+            Some(end) => start + end + close.len(),
+            None => html.len(),
+        };
+    }
+    out.push_str(&html[pos..]);
+    out
+}
+
+/// What's between the first <tag ...> and the last </tag>
+fn inner<'a>(html: &'a str, tag: &str) -> Option<&'a str> {
+    let lower = html.to_ascii_lowercase();
+    let open = lower.find(&format!("<{}", tag))?;
+    let content = open + lower[open..].find('>')? + 1;
+    let close = lower.rfind(&format!("</{}>", tag))?;
+    (close >= content).then(|| &html[content..close])
+}
+
+/// Replace the common named entities and numeric ones
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(amp) = rest.find('&') {
+        out.push_str(&rest[..amp]);
+        rest = &rest[amp..];
+        let decoded = rest.find(';').filter(|end| *end <= 10).and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                "mdash" => Some('—'),
+                "ndash" => Some('–'),
+                "hellip" => Some('…'),
+                _ => entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Trim each line and squeeze runs of blank lines into one
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out: Vec<&str> = Vec::new();
+    for line in text.lines().map(str::trim_end) {
+        if line.trim().is_empty() && out.last().map_or(true, |l| l.is_empty()) {
+            continue;
+        }
+        out.push(if line.trim().is_empty() { "" } else { line });
+    }
+    out.join("\n").trim().to_string()
+}
+
+/// At most MAX_TEXT bytes, cut at a paragraph (or a character) boundary
+fn cap(text: &str) -> String {
+    if text.len() <= MAX_TEXT {
+        return text.to_string();
+    }
+    let mut end = MAX_TEXT;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    let end = text[..end].rfind("\n\n").unwrap_or(end);
+    format!("{}\n\n…", text[..end].trim_end())
+}
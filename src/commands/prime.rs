@@ -0,0 +1,284 @@
+// Prime command - a ready-to-paste system prompt for a new AI conversation
+//
+// Assembles the project overview (config.json and the docs database), the
+// instructions in AGENTS.md, the last session's summary, open tasks, and recent
+// decisions under a token budget. The output is wrapped for the model it's meant
+// for: XML tags (claude), markdown headings (openai), or plain text.
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::budget::{self, Budget};
+use crate::config::ProjectConfig;
+use crate::database::open_database;
+use crate::docs_db;
+use crate::error::ProjError;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::session::get_last_completed_session;
+
+/// Values accepted for --format
+const FORMATS: &[&str] = &["claude", "openai", "plain"];
+
+/// Longest docs overview kept, in tokens
+const MAX_OVERVIEW_TOKENS: usize = 200;
+
+/// Longest decision rationale kept, in tokens
+const MAX_RATIONALE_TOKENS: usize = 40;
+
+/// Recent decisions considered
+const DECISION_LIMIT: i64 = 15;
+
+/// One part of the prompt
+struct Section {
+    heading: &'static str,
+    /// Tag name for --format claude
+    tag: &'static str,
+    lines: Vec<String>,
+}
+
+impl Section {
+    fn new(heading: &'static str, tag: &'static str) -> Self {
+        Self {
+            heading,
+            tag,
+            lines: Vec::new(),
+        }
+    }
+
+    fn render(&self, format: &str) -> String {
+        let body = self.lines.join("\n");
+        match format {
+            "claude" => format!("<{}>\n{}\n</{}>\n", self.tag, body, self.tag),
+            "openai" => format!("## {}\n\n{}\n", self.heading, body),
+            _ => format!(
+                "{}\n{}\n{}\n",
+                self.heading.to_uppercase(),
+                "-".repeat(self.heading.chars().count()),
+                body
+            ),
+        }
+    }
+
+    /// Tokens the heading or tags cost, plus the blank line between sections
+    fn overhead(&self, format: &str) -> usize {
+        budget::estimate_tokens(&Section::new(self.heading, self.tag).render(format)) + 1
+    }
+
+    /// Add a line if it fits, paying for the heading with the first one
+    fn push(&mut self, budget: &mut Budget, format: &str, line: String) -> bool {
+        let mut cost = budget::estimate_tokens(&line) + 1;
+        if self.lines.is_empty() {
+            cost += self.overhead(format);
+        }
+        if budget.take(cost) {
+            self.lines.push(line);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+pub fn run(format: &str, max_tokens: usize) -> Result<()> {
+    if !FORMATS.contains(&format) {
+        return Err(ProjError::InvalidInput(format!(
+            "Unknown format '{}'. Use {}.",
+            format,
+            FORMATS.join(", ")
+        ))
+        .into());
+    }
+    let config = ProjectConfig::load().with_context(|| "Failed to load config.json")?;
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let mut budget = Budget::new(max_tokens);
+    let mut omitted = 0;
+
+    // The overview always goes in
+    let mut project = Section::new("Project", "project");
+    for line in overview(&config) {
+        budget.used += budget::estimate_tokens(&line) + 1;
+        project.lines.push(line);
+    }
+    budget.used += project.overhead(format);
+
+    // Instructions get up to half of what's left, cut at a line so the rest still fits
+    let mut instructions = Section::new("Instructions", "instructions");
+    let agents = get_project_root()
+        .ok()
+        .and_then(|root| std::fs::read_to_string(root.join("AGENTS.md")).ok())
+        .unwrap_or_default();
+    let mut share = Budget::new(budget.remaining() / 2);
+    let lines: Vec<&str> = agents
+        .trim()
+        .lines()
+        .filter(|line| !line.starts_with("<!-- proj:agents"))
+        .collect();
+    let cut_note = "(Cut short to fit; the rest is in AGENTS.md.)";
+    share.max_tokens = share
+        .max_tokens
+        .saturating_sub(budget::estimate_tokens(cut_note) + 1);
+    for (i, line) in lines.iter().enumerate() {
+        if !instructions.push(&mut share, format, line.to_string()) {
+            if i > 0 {
+                instructions.lines.push(cut_note.to_string());
+                share.used += budget::estimate_tokens(cut_note) + 1;
+            }
+            omitted += 1;
+            break;
+        }
+    }
+    budget.used += share.used;
+
+    let mut last = Section::new("Last session", "last_session");
+    if let Some(session) = get_last_completed_session(&conn)? {
+        let ended = session
+            .ended_at
+            .map(|e| e.format(" (ended %Y-%m-%d)").to_string())
+            .unwrap_or_default();
+        let line = format!(
+            "Session #{}{}: {}",
+            session.session_id,
+            ended,
+            session.summary.as_deref().unwrap_or("(no summary)")
+        );
+        if !last.push(&mut budget, format, line) {
+            omitted += 1;
+        }
+    }
+
+    let mut tasks = Section::new("Active tasks", "active_tasks");
+    for line in active_tasks(&conn)? {
+        if !tasks.push(&mut budget, format, line) {
+            omitted += 1;
+        }
+    }
+
+    let mut decisions = Section::new("Decisions", "decisions");
+    for (topic, decision, rationale) in recent_decisions(&conn)? {
+        let line = format!("- {}: {}", topic, decision);
+        let line = match rationale {
+            Some(r) => {
+                let room = budget
+                    .remaining()
+                    .saturating_sub(budget::estimate_tokens(&line) + 3);
+                if room < 5 {
+                    line
+                } else {
+                    let r = budget::ellipsize(&r, room.min(MAX_RATIONALE_TOKENS));
+                    format!("{} (why: {})", line, r)
+                }
+            }
+            None => line,
+        };
+        if !decisions.push(&mut budget, format, line) {
+            omitted += 1;
+        }
+    }
+
+    let prompt: Vec<String> = [project, instructions, last, tasks, decisions]
+        .iter()
+        .filter(|s| !s.lines.is_empty())
+        .map(|s| s.render(format))
+        .collect();
+    print!("{}", prompt.join("\n"));
+
+    if omitted > 0 {
+        eprintln!(
+            "{}",
+            format!(
+                "{} item(s) left out to stay within {} tokens (about {} used)",
+                omitted, max_tokens, budget.used
+            )
+            .dimmed()
+        );
+    }
+    Ok(())
+}
+
+/// What the project is, from config.json and the docs database's overview section
+fn overview(config: &ProjectConfig) -> Vec<String> {
+    let mut lines = vec![format!(
+        "You're working on {}, a {} project tracked with proj.",
+        config.name, config.project_type
+    )];
+    if let Some(description) = config.description.as_deref().filter(|d| !d.is_empty()) {
+        lines.push(description.to_string());
+    }
+
+    let docs = get_project_root()
+        .ok()
+        .and_then(|root| docs_db::find_docs_db(&root))
+        .and_then(|path| docs_db::open_docs_db(&path).ok())
+        .and_then(|conn| docs_db::get_all_sections(&conn).ok())
+        .unwrap_or_default();
+    let section = docs
+        .iter()
+        .filter(|s| !s.content.trim().is_empty())
+        .find(|s| {
+            let title = s.title.to_lowercase();
+            title.contains("overview") || title.contains("introduction")
+        })
+        .or_else(|| docs.iter().find(|s| !s.content.trim().is_empty()));
+    if let Some(section) = section {
+        let text = section.content.split_whitespace().collect::<Vec<_>>();
+        lines.push(budget::ellipsize(&text.join(" "), MAX_OVERVIEW_TOKENS));
+    }
+    lines
+}
+
+/// Open tasks, overdue and high-priority first
+fn active_tasks(conn: &Connection) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT task_id, description, status, priority, due
+         FROM tasks
+         WHERE status NOT IN ('completed', 'cancelled')
+         ORDER BY
+           CASE WHEN due < date('now') THEN 0 WHEN due <= date('now', '+3 days') THEN 1 ELSE 2 END,
+           CASE priority WHEN 'urgent' THEN 1 WHEN 'high' THEN 2 WHEN 'normal' THEN 3 ELSE 4 END,
+           created_at",
+    )?;
+    let tasks = stmt
+        .query_map([], |row| {
+            let (id, description, status, priority, due): (
+                i64,
+                String,
+                String,
+                String,
+                Option<String>,
+            ) = (
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?,
+                row.get(4)?,
+            );
+            let mut labels = vec![priority, status];
+            if let Some(due) = due {
+                labels.push(format!("due {}", due));
+            }
+            Ok(format!("- #{} [{}] {}", id, labels.join(", "), description))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(tasks)
+}
+
+/// Active decisions, newest first: (topic, decision, rationale)
+fn recent_decisions(conn: &Connection) -> Result<Vec<(String, String, Option<String>)>> {
+    let mut stmt = conn.prepare(
+        "SELECT topic, decision, rationale
+         FROM decisions
+         WHERE status = 'active'
+         ORDER BY created_at DESC
+         LIMIT ?1",
+    )?;
+    let decisions = stmt
+        .query_map([DECISION_LIMIT], |row| {
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(decisions)
+}
@@ -0,0 +1,148 @@
+// Prompt command - compact segment for PS1/starship: project name, active session, open blockers
+//
+// Runs on every prompt, so it reads the database only when tracking.db, its WAL, or
+// config.json changed since the last call, and otherwise prints from a small cache file.
+// It never starts a session and never fails: outside a project, or on any error, it
+// prints nothing.
+
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+use anyhow::Result;
+use chrono::Duration;
+use rusqlite::{Connection, OpenFlags};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ProjectConfig;
+use crate::paths::get_project_root;
+use crate::session::{get_active_session, StalePolicy};
+
+/// Cache file inside .tracking/
+const CACHE_FILE: &str = ".prompt_cache";
+
+/// Files whose changes invalidate the cache
+const WATCHED: [&str; 3] = ["tracking.db", "tracking.db-wal", "config.json"];
+
+/// What the prompt shows, as of `stamp`
+#[derive(Serialize, Deserialize)]
+struct PromptState {
+    /// Size and modification time of each watched file when this was read
+    stamp: String,
+    name: String,
+    session_id: Option<i64>,
+    /// Unix time the active session goes stale (None = never)
+    session_stale_at: Option<i64>,
+    blockers: i64,
+    open_tasks: i64,
+}
+
+pub fn run(format: Option<String>) -> Result<()> {
+    let Ok(root) = get_project_root() else {
+        return Ok(());
+    };
+    let tracking = root.join(".tracking");
+    let stamp = stamp(&tracking);
+
+    let cached = std::fs::read_to_string(tracking.join(CACHE_FILE))
+        .ok()
+        .and_then(|c| serde_json::from_str::<PromptState>(&c).ok())
+        .filter(|s| s.stamp == stamp);
+    let state = match cached {
+        Some(state) => state,
+        None => {
+            let Some(state) = read_state(&tracking, stamp) else {
+                return Ok(());
+            };
+            if let Ok(content) = serde_json::to_string(&state) {
+                let _ = std::fs::write(tracking.join(CACHE_FILE), content);
+            }
+            state
+        }
+    };
+
+    let line = render(&state, format.as_deref());
+    if !line.is_empty() {
+        println!("{}", line);
+    }
+    Ok(())
+}
+
+/// Size and mtime of the watched files, so any write shows up as a different stamp
+fn stamp(tracking: &Path) -> String {
+    WATCHED
+        .iter()
+        .map(|file| {
+            std::fs::metadata(tracking.join(file))
+                .ok()
+                .and_then(|m| {
+                    let modified = m.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+                    Some(format!("{}.{}", modified.as_nanos(), m.len()))
+                })
+                .unwrap_or_else(|| "-".to_string())
+        })
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Read the prompt values from the database without opening it for writing
+fn read_state(tracking: &Path, stamp: String) -> Option<PromptState> {
+    let content = std::fs::read_to_string(tracking.join("config.json")).ok()?;
+    let config = ProjectConfig::from_json(&content).ok()?;
+    let conn = Connection::open_with_flags(
+        tracking.join("tracking.db"),
+        OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )
+    .ok()?;
+
+    let session = get_active_session(&conn).ok()?;
+    let policy = StalePolicy::from_config(&config);
+    let session_stale_at = session.as_ref().and_then(|s| {
+        (policy.timeout_hours > 0).then(|| {
+            (s.resumed_at.unwrap_or(s.started_at) + Duration::hours(policy.timeout_hours))
+                .timestamp()
+        })
+    });
+    let count = |sql: &str| -> i64 { conn.query_row(sql, [], |row| row.get(0)).unwrap_or(0) };
+
+    Some(PromptState {
+        stamp,
+        name: config.name,
+        session_id: session.map(|s| s.session_id),
+        session_stale_at,
+        blockers: count("SELECT COUNT(*) FROM blockers WHERE status = 'active'"),
+        open_tasks: count(
+            "SELECT COUNT(*) FROM tasks WHERE status NOT IN ('completed', 'cancelled')",
+        ),
+    })
+}
+
+/// Default: `name #12 ⚠2` (session and blockers only when there are any).
+/// A custom format fills {name}, {session}, {blockers}, and {tasks}; empty values
+/// leave no double spaces behind.
+fn render(state: &PromptState, format: Option<&str>) -> String {
+    let now = chrono::Utc::now().timestamp();
+    let session = state
+        .session_id
+        .filter(|_| !matches!(state.session_stale_at, Some(at) if now >= at));
+
+    let Some(format) = format else {
+        let mut line = state.name.clone();
+        if let Some(id) = session {
+            line.push_str(&format!(" #{}", id));
+        }
+        if state.blockers > 0 {
+            line.push_str(&format!(" ⚠{}", state.blockers));
+        }
+        return line;
+    };
+
+    let line = format
+        .replace("{name}", &state.name)
+        .replace(
+            "{session}",
+            &session.map(|id| id.to_string()).unwrap_or_default(),
+        )
+        .replace("{blockers}", &state.blockers.to_string())
+        .replace("{tasks}", &state.open_tasks.to_string());
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
@@ -0,0 +1,124 @@
+// Question commands - answer, list
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::cli::{QuestionCommands, QuestionSubcommand};
+use crate::database::{open_database, write_transaction};
+use crate::paths::get_tracking_db_path;
+use crate::session::get_or_create_session_with_info;
+use crate::text::truncate;
+
+pub fn run(cmd: QuestionCommands) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    match cmd.command {
+        QuestionSubcommand::Answer { id, answer } => {
+            let session_result = get_or_create_session_with_info(&conn)?;
+            let session = session_result.session;
+
+            // Notify if a stale session was closed
+            if let Some(closed) = session_result.auto_closed_session {
+                eprintln!(
+                    "{} Previous session #{} was stale. Started new session #{}",
+                    "⚠".yellow(),
+                    closed.session_id,
+                    session.session_id
+                );
+            }
+
+            cmd_question_answer(&conn, session.session_id, id, &answer)
+        }
+        QuestionSubcommand::List => cmd_question_list(&conn),
+    }
+}
+
+/// Record the answer to an open question
+fn cmd_question_answer(
+    conn: &Connection,
+    session_id: i64,
+    question_id: i64,
+    answer: &str,
+) -> Result<()> {
+    let question: Option<(String, String)> = conn
+        .query_row(
+            "SELECT question, status FROM questions WHERE question_id = ?1",
+            [question_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .ok();
+
+    let Some((question, status)) = question else {
+        bail!("Question #{} not found", question_id);
+    };
+
+    if status == "answered" {
+        bail!("Question #{} is already answered", question_id);
+    }
+
+    let tx = write_transaction(conn)?;
+
+    tx.execute(
+        "UPDATE questions SET status = 'answered', answer = ?1, answered_at = datetime('now') WHERE question_id = ?2",
+        rusqlite::params![answer, question_id],
+    )?;
+
+    let summary = format!("Question answered: {}", truncate(&question, 50));
+    tx.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'question_answer', ?2, ?3)",
+        rusqlite::params![session_id, question_id, summary],
+    )?;
+
+    tx.commit()?;
+
+    println!(
+        "{} Answered question #{}: {}",
+        "✓".green(),
+        question_id,
+        truncate(&question, 50)
+    );
+
+    Ok(())
+}
+
+/// List open questions
+fn cmd_question_list(conn: &Connection) -> Result<()> {
+    let mut stmt = conn.prepare(
+        "SELECT question_id, question, context, created_at
+         FROM questions
+         WHERE status = 'open'
+         ORDER BY created_at",
+    )?;
+
+    let questions = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    if questions.is_empty() {
+        println!("No open questions.");
+        return Ok(());
+    }
+
+    println!("{}", "Open Questions:".bold());
+    println!("{}", "-".repeat(60));
+
+    for (question_id, question, context, created_at) in questions {
+        let date = created_at.split(' ').next().unwrap_or(&created_at);
+        println!("? #{:<4} {} {}", question_id, question, date.dimmed());
+        if let Some(ctx) = context {
+            println!("         {}", ctx.dimmed());
+        }
+    }
+
+    Ok(())
+}
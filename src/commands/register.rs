@@ -1,31 +1,56 @@
-// Register command - add current project to global registry
+// Register command - add current project to global registry, or record that one moved
 
 use anyhow::{Context, Result};
 use chrono::Utc;
 use colored::Colorize;
 
+use crate::commands::registered::canonical_path;
 use crate::config::{ProjectConfig, Registry, RegistryEntry};
-use crate::paths::{ensure_dir, get_config_path, get_project_root, get_registry_path};
+use crate::error::ProjError;
+use std::path::{Path, PathBuf};
 
-pub fn run() -> Result<()> {
-    // Load project config
-    let config = load_config()?;
-    let project_root = get_project_root()?;
+use crate::paths::{ensure_dir, get_project_root, get_registry_path, same_path};
+
+pub fn run(move_paths: Option<Vec<String>>) -> Result<()> {
+    if let Some(paths) = move_paths {
+        return move_project(&paths[0], &paths[1]);
+    }
+
+    register_at(&get_project_root()?)
+}
+
+/// Add the project at `project_root` to the registry
+pub fn register_at(project_root: &Path) -> Result<()> {
+    let config = load_config(project_root)?;
     let project_path = project_root.to_string_lossy().to_string();
 
     // Load or create registry
     let mut registry = load_or_create_registry()?;
 
     // Check if already registered
-    if registry
-        .registered_projects
-        .iter()
-        .any(|p| p.path == project_path)
-    {
+    if find_entry(&registry, project_root).is_some() {
         println!("Already registered: {}", config.name);
         return Ok(());
     }
 
+    // A registered project with this name whose directory is gone was most likely moved here
+    if let Some(entry) = registry
+        .registered_projects
+        .iter_mut()
+        .find(|p| p.name == config.name && !Path::new(&p.path).exists())
+    {
+        println!(
+            "{} Moved: {} ({} → {})",
+            "✓".green(),
+            config.name,
+            entry.path,
+            project_path
+        );
+        entry.path = project_path;
+        entry.schema_version = config.schema_version.clone();
+        return save_registry(&registry);
+    }
+
     // Add to registry
     registry.registered_projects.push(RegistryEntry {
         path: project_path,
@@ -42,13 +67,73 @@ pub fn run() -> Result<()> {
     Ok(())
 }
 
-/// Load project configuration
-fn load_config() -> Result<ProjectConfig> {
-    let config_path = get_config_path()?;
+/// Point the registry entry for `old` at `new`, where the project now lives
+fn move_project(old: &str, new: &str) -> Result<()> {
+    let mut registry = load_or_create_registry()?;
+    let old_path = absolute_path(old);
+    let new_path = absolute_path(new);
+
+    let content =
+        std::fs::read_to_string(new_path.join(".tracking").join("config.json")).map_err(|_| {
+            ProjError::InvalidInput(format!("{} isn't a proj project", new_path.display()))
+        })?;
+    let config: ProjectConfig =
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
+
+    let index = find_entry(&registry, &old_path).ok_or_else(|| {
+        ProjError::InvalidInput(format!("{} isn't registered", old_path.display()))
+    })?;
+    if let Some(other) = find_entry(&registry, &new_path).filter(|&i| i != index) {
+        return Err(ProjError::InvalidInput(format!(
+            "{} is already registered as '{}'",
+            new_path.display(),
+            registry.registered_projects[other].name
+        ))
+        .into());
+    }
+
+    let entry = &mut registry.registered_projects[index];
+    println!(
+        "{} Moved: {} ({} → {})",
+        "✓".green(),
+        config.name,
+        entry.path,
+        new_path.display()
+    );
+    entry.path = new_path.to_string_lossy().to_string();
+    entry.name = config.name;
+    entry.schema_version = config.schema_version;
+    save_registry(&registry)
+}
+
+/// Index of the registry entry for a directory, comparing canonical paths
+fn find_entry(registry: &Registry, path: &Path) -> Option<usize> {
+    registry.registered_projects.iter().position(|p| {
+        same_path(Path::new(&p.path), path)
+            || same_path(
+                &canonical_path(&p.path),
+                &canonical_path(&path.to_string_lossy()),
+            )
+    })
+}
+
+/// Canonical form of a path given on the command line; paths that no longer exist are
+/// made absolute against the current directory
+fn absolute_path(path: &str) -> PathBuf {
+    Path::new(path).canonicalize().unwrap_or_else(|_| {
+        std::env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| PathBuf::from(path))
+    })
+}
+
+/// Load the configuration of the project at `project_root`
+fn load_config(project_root: &Path) -> Result<ProjectConfig> {
+    let config_path = project_root.join(".tracking").join("config.json");
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
 
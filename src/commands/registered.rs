@@ -1,15 +1,26 @@
-// Registered command - list all registered projects
+// Registered command - list all registered projects and tidy the registry
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use colored::Colorize;
+use rusqlite::{Connection, OpenFlags};
 
-use crate::config::Registry;
-use crate::paths::get_registry_path;
+use crate::config::{ProjectConfig, Registry};
+use crate::paths::{get_registry_path, same_path};
 
-pub fn run() -> Result<()> {
-    let registry = load_registry()?;
+/// What's wrong with a registry entry, if anything
+enum EntryIssue {
+    /// The path no longer exists
+    Missing,
+    /// The path exists but has no .tracking directory
+    NoTracking,
+    /// Same directory as an earlier entry (index into registered_projects)
+    Duplicate(usize),
+}
+
+pub fn run(prune: bool) -> Result<()> {
+    let mut registry = load_registry()?;
 
     if registry.registered_projects.is_empty() {
         println!("No projects registered.");
@@ -17,40 +28,173 @@ pub fn run() -> Result<()> {
         return Ok(());
     }
 
+    if prune {
+        return prune_registry(&mut registry);
+    }
+
     println!(
         "\nRegistered Projects ({}):\n",
         registry.registered_projects.len()
     );
 
-    for p in &registry.registered_projects {
-        let path = Path::new(&p.path);
-        let exists = path.exists();
-        let has_tracking = if exists {
-            path.join(".tracking").exists()
-        } else {
-            false
-        };
+    let issues = find_issues(&registry);
+    let mut needs_prune = false;
 
-        let status = if exists && has_tracking {
-            "✓".green()
-        } else if exists {
-            "?".yellow()
-        } else {
-            "✗".red()
+    for (p, issue) in registry.registered_projects.iter().zip(&issues) {
+        let status = match issue {
+            None => "✓".green(),
+            Some(EntryIssue::NoTracking) => "?".yellow(),
+            Some(EntryIssue::Duplicate(_)) => "=".yellow(),
+            Some(EntryIssue::Missing) => "✗".red(),
         };
 
         println!("  {} {}", status, p.name.bold());
         println!("      Type: {}", p.project_type);
         println!("      Path: {}", p.path);
         println!("      Schema: v{}", p.schema_version);
+        if issue.is_none() {
+            println!(
+                "      Last activity: {}",
+                last_activity(Path::new(&p.path)).unwrap_or_else(|| "none".to_string())
+            );
+            if let Some(name) = config_name(Path::new(&p.path)).filter(|n| n != &p.name) {
+                println!(
+                    "      {}",
+                    format!("Renamed to '{}' in config.json", name).yellow()
+                );
+                needs_prune = true;
+            }
+        }
+        match issue {
+            Some(EntryIssue::Missing) => println!("      {}", "Path not found".red()),
+            Some(EntryIssue::NoTracking) => println!("      {}", "No .tracking directory".yellow()),
+            Some(EntryIssue::Duplicate(first)) => println!(
+                "      {}",
+                format!("Duplicate of {}", registry.registered_projects[*first].path).yellow()
+            ),
+            None => {}
+        }
         println!();
+        needs_prune |= matches!(issue, Some(EntryIssue::Missing | EntryIssue::Duplicate(_)));
+    }
+
+    if needs_prune {
+        println!(
+            "Run '{}' to remove missing and duplicate entries and pick up renames.",
+            "proj registered --prune".cyan()
+        );
+        println!(
+            "Moved a project? '{}' keeps its entry.",
+            "proj register --move <old> <new>".cyan()
+        );
+    }
+
+    Ok(())
+}
+
+/// Remove entries whose path is gone, drop duplicates of the same directory, and
+/// refresh names changed in a project's config.json
+fn prune_registry(registry: &mut Registry) -> Result<()> {
+    let issues = find_issues(registry);
+    let mut removed = Vec::new();
+    let mut kept = Vec::new();
+
+    for (mut entry, issue) in registry.registered_projects.drain(..).zip(issues) {
+        match issue {
+            Some(EntryIssue::Missing) => removed.push((entry, "path not found")),
+            Some(EntryIssue::Duplicate(_)) => removed.push((entry, "duplicate")),
+            _ => {
+                if let Some(name) = config_name(Path::new(&entry.path)) {
+                    if name != entry.name {
+                        println!("  {} Renamed: {} → {}", "~".yellow(), entry.name, name);
+                        entry.name = name;
+                    }
+                }
+                kept.push(entry);
+            }
+        }
+    }
+    registry.registered_projects = kept;
+
+    for (entry, reason) in &removed {
+        println!(
+            "  {} Removed {} ({}): {}",
+            "✗".red(),
+            entry.name,
+            reason,
+            entry.path
+        );
     }
+    save_registry(registry)?;
 
+    println!(
+        "\n{} Registry pruned: {} removed, {} kept",
+        "✓".green(),
+        removed.len(),
+        registry.registered_projects.len()
+    );
     Ok(())
 }
 
+/// Check every entry for missing paths and duplicates (compared by canonical path)
+fn find_issues(registry: &Registry) -> Vec<Option<EntryIssue>> {
+    let canonical: Vec<PathBuf> = registry
+        .registered_projects
+        .iter()
+        .map(|p| canonical_path(&p.path))
+        .collect();
+
+    canonical
+        .iter()
+        .enumerate()
+        .map(|(i, path)| {
+            if !path.exists() {
+                Some(EntryIssue::Missing)
+            } else if let Some(first) = canonical[..i].iter().position(|c| same_path(c, path)) {
+                Some(EntryIssue::Duplicate(first))
+            } else if !path.join(".tracking").is_dir() {
+                Some(EntryIssue::NoTracking)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// Resolve symlinks and relative segments so one directory always compares equal
+pub fn canonical_path(path: &str) -> PathBuf {
+    Path::new(path)
+        .canonicalize()
+        .unwrap_or_else(|_| PathBuf::from(path))
+}
+
+/// Newest session or activity log timestamp in a project's tracking database
+fn last_activity(project_root: &Path) -> Option<String> {
+    let db_path = project_root.join(".tracking").join("tracking.db");
+    let conn = Connection::open_with_flags(db_path, OpenFlags::SQLITE_OPEN_READ_ONLY).ok()?;
+    conn.query_row(
+        "SELECT MAX(t) FROM (
+            SELECT MAX(COALESCE(ended_at, started_at)) AS t FROM sessions
+            UNION ALL
+            SELECT MAX(timestamp) FROM activity_log
+        )",
+        [],
+        |row| row.get::<_, Option<String>>(0),
+    )
+    .ok()
+    .flatten()
+}
+
+/// Project name from a project's config.json
+fn config_name(project_root: &Path) -> Option<String> {
+    let content =
+        std::fs::read_to_string(project_root.join(".tracking").join("config.json")).ok()?;
+    let config = ProjectConfig::from_json(&content).ok()?;
+    Some(config.name)
+}
+
 /// Load the global registry
-fn load_registry() -> Result<Registry> {
+pub fn load_registry() -> Result<Registry> {
     let registry_path = get_registry_path()?;
 
     if !registry_path.exists() {
@@ -63,3 +207,11 @@ fn load_registry() -> Result<Registry> {
         serde_json::from_str(&content).with_context(|| "Failed to parse registry.json")?;
     Ok(registry)
 }
+
+/// Save the global registry
+fn save_registry(registry: &Registry) -> Result<()> {
+    let registry_path = get_registry_path()?;
+    let content = serde_json::to_string_pretty(registry)?;
+    std::fs::write(&registry_path, content).with_context(|| "Failed to write registry.json")?;
+    Ok(())
+}
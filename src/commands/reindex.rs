@@ -0,0 +1,30 @@
+// Reindex command - rebuild the full-text search index
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+
+use crate::database::{get_schema_version, open_database};
+use crate::error::ProjError;
+use crate::paths::get_tracking_db_path;
+use crate::schema::rebuild_fts_index;
+use crate::SCHEMA_VERSION;
+
+pub fn run() -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let version = get_schema_version(&conn)?.unwrap_or_else(|| "1.0".to_string());
+    if version != SCHEMA_VERSION {
+        return Err(ProjError::SchemaMismatch {
+            found: version,
+            required: SCHEMA_VERSION.to_string(),
+        }
+        .into());
+    }
+
+    let count = rebuild_fts_index(&conn).with_context(|| "Failed to rebuild search index")?;
+
+    println!("{} Rebuilt search index ({} entries)", "✓".green(), count);
+    Ok(())
+}
@@ -1,5 +1,9 @@
 // Release command - fully automated release process
+//
+// Distribution channels (Homebrew, npm, ...) are release targets configured in
+// release.toml; see release_target.rs.
 
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use anyhow::{bail, Context, Result};
@@ -7,6 +11,9 @@ use chrono::Local;
 use colored::Colorize;
 use dialoguer::{Confirm, Editor, Select};
 
+use crate::commands::release_target::{Release, ReleaseConfig};
+use crate::git;
+
 /// Release types for version bumping
 const VERSION_TYPES: &[&str] = &["patch (x.x.X)", "minor (x.X.0)", "major (X.0.0)"];
 
@@ -20,18 +27,17 @@ pub fn run(version: Option<String>, check_only: bool) -> Result<()> {
 
     let current_version = extract_version(&cargo_toml)?;
     println!("Current version: {}", current_version.cyan());
+    let config = ReleaseConfig::load(Path::new("."))?;
 
     if check_only {
         println!("\nChecking release status...");
-        check_release_status(&current_version)?;
+        check_release_status(&config, &current_version)?;
         return Ok(());
     }
 
     // Check for uncommitted changes
     println!("\n{}", "Checking git status...".bold());
-    let status_output = Command::new("git")
-        .args(["status", "--porcelain"])
-        .output()
+    let status_output = git::run(Command::new("git").args(["status", "--porcelain"]))
         .context("Failed to run git status")?;
 
     let uncommitted = String::from_utf8_lossy(&status_output.stdout);
@@ -60,22 +66,24 @@ pub fn run(version: Option<String>, check_only: bool) -> Result<()> {
     println!("\n{}", "=== Release Wizard ===".bold());
 
     // Reminder about npm token (7-day expiry with bypass 2FA)
-    println!();
-    println!("{}", "📦 npm Token Reminder".yellow().bold());
-    println!("The npm token expires every 7 days. If publishing fails:");
-    println!("  1. Go to npmjs.com → Access Tokens → Generate New Token");
-    println!("  2. Create Granular token with 'Bypass 2FA' checked");
-    println!("  3. Permissions: Read and write for 'create-aiproj' package");
-    println!("  4. Run: gh secret set NPM_TOKEN");
-    println!();
+    if config.has_npm() {
+        println!();
+        println!("{}", "📦 npm Token Reminder".yellow().bold());
+        println!("The npm token expires every 7 days. If publishing fails:");
+        println!("  1. Go to npmjs.com → Access Tokens → Generate New Token");
+        println!("  2. Create Granular token with 'Bypass 2FA' checked");
+        println!("  3. Permissions: Read and write for 'create-aiproj' package");
+        println!("  4. Run: gh secret set NPM_TOKEN");
+        println!();
 
-    if !Confirm::new()
-        .with_prompt("Is your npm token current (created within 7 days)?")
-        .default(true)
-        .interact()?
-    {
-        println!("Please update the npm token first, then run 'proj release' again.");
-        return Ok(());
+        if !Confirm::new()
+            .with_prompt("Is your npm token current (created within 7 days)?")
+            .default(true)
+            .interact()?
+        {
+            println!("Please update the npm token first, then run 'proj release' again.");
+            return Ok(());
+        }
     }
 
     // Step 1: Determine version
@@ -199,32 +207,36 @@ pub fn run(version: Option<String>, check_only: bool) -> Result<()> {
         new_version
     );
 
-    // Step 4b: Update version in VS Code extension
-    println!("\n{}", "Step 4b: Updating VS Code extension".bold());
-    if std::path::Path::new("vscode/package.json").exists() {
-        update_vscode_version(&new_version)?;
-        update_vscode_readme_version(&current_version, &new_version)?;
-        println!(
-            "{} Updated vscode/package.json and README.md to version {}",
-            "✓".green(),
-            new_version
-        );
-    } else {
-        println!("{} vscode/package.json not found, skipping", "⚠".yellow());
-    }
-
-    // Step 4c: Update version in npm package
-    println!("\n{}", "Step 4c: Updating npm package".bold());
-    if std::path::Path::new("packaging/npm/package.json").exists() {
-        update_npm_package_version(&new_version)?;
-        update_npm_install_version(&new_version)?;
+    // Step 4b: Bump the version in each release target's files
+    println!("\n{}", "Step 4b: Updating release targets".bold());
+    let release = Release {
+        version: &new_version,
+        previous: Some(&current_version),
+        repo: &config.repo,
+    };
+    let mut release_files: Vec<PathBuf> = vec![
+        "Cargo.toml".into(),
+        "Cargo.lock".into(),
+        "CHANGELOG.md".into(),
+    ];
+    for target in config.targets() {
+        let written = target
+            .prepare(&release)
+            .with_context(|| format!("Failed to update {}", target.name()))?;
+        if written.is_empty() {
+            continue;
+        }
         println!(
-            "{} Updated npm package to version {}",
+            "{} {}: {}",
             "✓".green(),
-            new_version
+            target.name(),
+            written
+                .iter()
+                .map(|f| f.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
         );
-    } else {
-        println!("{} npm package not found, skipping", "⚠".yellow());
+        release_files.extend(written);
     }
 
     // Step 5: Commit changes
@@ -232,18 +244,11 @@ pub fn run(version: Option<String>, check_only: bool) -> Result<()> {
     let commit_msg = format!("Release v{}", new_version);
 
     // Add all relevant files
+    let mut add_args = vec!["add".to_string()];
+    add_args.extend(release_files.iter().map(|f| f.display().to_string()));
     run_command(
         "git",
-        &[
-            "add",
-            "Cargo.toml",
-            "Cargo.lock",
-            "CHANGELOG.md",
-            "vscode/package.json",
-            "vscode/README.md",
-            "packaging/npm/package.json",
-            "packaging/npm/scripts/install.js",
-        ],
+        &add_args.iter().map(String::as_str).collect::<Vec<_>>(),
     )?;
 
     // Also add any other uncommitted changes if user approved
@@ -273,11 +278,15 @@ pub fn run(version: Option<String>, check_only: bool) -> Result<()> {
 
         println!("\n{}", "=== Release Complete ===".bold().green());
         println!("GitHub Actions is now building release binaries.");
-        println!("Monitor progress: https://github.com/victorysightsound/aiproject/actions");
+        println!(
+            "Monitor progress: https://github.com/{}/actions",
+            config.repo
+        );
         println!("\nThe workflow will automatically:");
         println!("  • Build binaries for all platforms");
         println!("  • Create the GitHub release");
         println!("  • Update the Homebrew formula");
+        println!("\nAfterwards, 'proj release --check' updates the release targets.");
     } else {
         println!("\nTo complete the release later:");
         println!("  git push && git push --tags");
@@ -382,9 +391,17 @@ fn update_changelog(_version: &str, entry: &str) -> Result<()> {
     Ok(())
 }
 
-/// Check release status and update formulas
-fn check_release_status(version: &str) -> Result<()> {
+/// Check release status and update the release targets
+fn check_release_status(config: &ReleaseConfig, version: &str) -> Result<()> {
     let tag = format!("v{}", version);
+    // Release targets hash the published assets, so there's nothing to do offline
+    if crate::config::offline() {
+        println!(
+            "{} Offline mode is on; run --check on a machine with network access.",
+            "ℹ".dimmed()
+        );
+        return Ok(());
+    }
 
     // Check if release exists on GitHub
     println!("\nChecking GitHub release...");
@@ -401,7 +418,7 @@ fn check_release_status(version: &str) -> Result<()> {
                 if assets.is_empty() {
                     println!("{} Release exists but has no assets yet.", "⏳".yellow());
                     println!("The build may still be in progress.");
-                    println!("Check: https://github.com/victorysightsound/aiproject/actions");
+                    println!("Check: https://github.com/{}/actions", config.repo);
                     return Ok(());
                 }
 
@@ -419,21 +436,24 @@ fn check_release_status(version: &str) -> Result<()> {
                     }
                 }
 
-                // Update Homebrew formula
-                println!("\n{}", "Updating Homebrew formula...".bold());
-                update_homebrew_formula(version)?;
+                println!("\n{}", "Updating release targets...".bold());
+                update_release_targets(config, version)?;
 
                 println!("\n{}", "=== Release Complete ===".bold().green());
                 println!("\nUsers can now install/upgrade via:");
-                println!("  • brew upgrade proj");
-                println!("  • npm update -g create-aiproj");
-                println!("  • Download from: https://github.com/victorysightsound/aiproject/releases/tag/{}", tag);
+                for hint in config.targets().iter().filter_map(|t| t.install_hint()) {
+                    println!("  • {}", hint);
+                }
+                println!(
+                    "  • Download from: https://github.com/{}/releases/tag/{}",
+                    config.repo, tag
+                );
             }
         }
         Ok(_) => {
             println!("{} Release {} not found yet.", "⏳".yellow(), tag);
             println!("The GitHub Action may still be running.");
-            println!("Check: https://github.com/victorysightsound/aiproject/actions");
+            println!("Check: https://github.com/{}/actions", config.repo);
         }
         Err(e) => {
             println!("{} Could not check release: {}", "⚠".yellow(), e);
@@ -444,110 +464,70 @@ fn check_release_status(version: &str) -> Result<()> {
     Ok(())
 }
 
-/// Update Homebrew formula with new SHA256 hashes
-fn update_homebrew_formula(version: &str) -> Result<()> {
-    let formula_path = "packaging/homebrew/aiproject.rb";
-    let tag = format!("v{}", version);
-
-    // Download URLs for each platform
-    let platforms = [
-        ("aarch64-apple-darwin", "proj-aarch64-apple-darwin.tar.gz"),
-        ("x86_64-apple-darwin", "proj-x86_64-apple-darwin.tar.gz"),
-        (
-            "aarch64-unknown-linux-gnu",
-            "proj-aarch64-unknown-linux-gnu.tar.gz",
-        ),
-        (
-            "x86_64-unknown-linux-gnu",
-            "proj-x86_64-unknown-linux-gnu.tar.gz",
-        ),
-    ];
-
-    let mut hashes: std::collections::HashMap<String, String> = std::collections::HashMap::new();
-
-    for (platform, filename) in platforms {
-        print!("  Fetching hash for {}... ", platform);
-
-        // Download the file and compute hash
-        let url = format!(
-            "https://github.com/victorysightsound/aiproject/releases/download/{}/{}",
-            tag, filename
-        );
+/// Verify and update every release target, then commit and push the files they wrote.
+/// A target that can't be verified or fails to update is reported and skipped.
+fn update_release_targets(config: &ReleaseConfig, version: &str) -> Result<()> {
+    let release = Release {
+        version,
+        previous: None,
+        repo: &config.repo,
+    };
 
-        match compute_remote_sha256(&url) {
-            Ok(hash) => {
-                println!("{}", "✓".green());
-                hashes.insert(platform.to_string(), hash);
-            }
-            Err(e) => {
-                println!("{} ({})", "✗".red(), e);
+    let mut changed: Vec<PathBuf> = Vec::new();
+    for target in config.targets() {
+        if let Err(e) = target.verify(&release) {
+            println!("  {} {}: skipped ({:#})", "⚠".yellow(), target.name(), e);
+            continue;
+        }
+        match target.update(&release) {
+            Ok(written) => {
+                println!("  {} {}", "✓".green(), target.name());
+                changed.extend(written);
             }
+            Err(e) => println!("  {} {}: {:#}", "✗".red(), target.name(), e),
         }
     }
 
-    if hashes.len() < 4 {
-        println!(
-            "{} Could not fetch all hashes. Formula not updated.",
-            "⚠".yellow()
-        );
+    if changed.is_empty() {
         return Ok(());
     }
-
-    // Read and update the formula
-    let mut formula = std::fs::read_to_string(formula_path)
-        .with_context(|| format!("Could not read {}", formula_path))?;
-
-    // Update version
-    formula = update_formula_field(&formula, "version", version);
-
-    // Update SHA256 hashes (this is a bit fragile, but works for our formula structure)
-    for (platform, hash) in &hashes {
-        formula = update_formula_sha256(&formula, platform, hash);
+    // Only files that really changed (the release workflow may have updated them already)
+    let mut diff_args = vec![
+        "diff".to_string(),
+        "--name-only".to_string(),
+        "--".to_string(),
+    ];
+    diff_args.extend(changed.iter().map(|f| f.display().to_string()));
+    let diff = git::run(Command::new("git").args(&diff_args))?;
+    let changed: Vec<String> = String::from_utf8_lossy(&diff.stdout)
+        .lines()
+        .map(String::from)
+        .collect();
+    if changed.is_empty() {
+        return Ok(());
     }
 
-    std::fs::write(formula_path, &formula)?;
-    println!("{} Updated {}", "✓".green(), formula_path);
-
-    // Commit the formula update
-    run_command("git", &["add", formula_path])?;
+    let mut add_args = vec!["add"];
+    add_args.extend(changed.iter().map(String::as_str));
+    run_command("git", &add_args)?;
     run_command(
         "git",
         &[
             "commit",
             "-m",
-            &format!("Update Homebrew formula for v{}", version),
+            &format!("Update release targets for v{}", version),
         ],
     )?;
     run_command("git", &["push"])?;
-    println!("{} Committed and pushed formula update", "✓".green());
+    println!(
+        "{} Committed and pushed {}",
+        "✓".green(),
+        changed.join(", ")
+    );
 
     Ok(())
 }
 
-/// Compute SHA256 of a remote file
-fn compute_remote_sha256(url: &str) -> Result<String> {
-    use sha2::{Digest, Sha256};
-
-    let response = ureq::get(url)
-        .call()
-        .with_context(|| format!("Failed to download {}", url))?;
-
-    let mut reader = response.into_reader();
-    let mut hasher = Sha256::new();
-    let mut buffer = [0u8; 8192];
-
-    loop {
-        let bytes_read = std::io::Read::read(&mut reader, &mut buffer)?;
-        if bytes_read == 0 {
-            break;
-        }
-        hasher.update(&buffer[..bytes_read]);
-    }
-
-    let result = hasher.finalize();
-    Ok(format!("{:x}", result))
-}
-
 /// Extract version from Cargo.toml content
 fn extract_version(content: &str) -> Result<String> {
     for line in content.lines() {
@@ -609,166 +589,6 @@ fn update_cargo_version(new_version: &str) -> Result<()> {
     Ok(())
 }
 
-/// Update version in VS Code extension package.json
-fn update_vscode_version(new_version: &str) -> Result<()> {
-    let path = "vscode/package.json";
-    let content = std::fs::read_to_string(path)?;
-
-    // Parse as JSON, update version, write back
-    let mut json: serde_json::Value =
-        serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path))?;
-
-    if let Some(obj) = json.as_object_mut() {
-        obj.insert(
-            "version".to_string(),
-            serde_json::Value::String(new_version.to_string()),
-        );
-    }
-
-    let new_content = serde_json::to_string_pretty(&json)?;
-    std::fs::write(path, new_content + "\n")?;
-
-    Ok(())
-}
-
-/// Update version in npm package.json
-fn update_npm_package_version(new_version: &str) -> Result<()> {
-    let path = "packaging/npm/package.json";
-    let content = std::fs::read_to_string(path)?;
-
-    let mut json: serde_json::Value =
-        serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path))?;
-
-    if let Some(obj) = json.as_object_mut() {
-        obj.insert(
-            "version".to_string(),
-            serde_json::Value::String(new_version.to_string()),
-        );
-    }
-
-    let new_content = serde_json::to_string_pretty(&json)?;
-    std::fs::write(path, new_content + "\n")?;
-
-    Ok(())
-}
-
-/// Update VERSION constant in npm install.js
-fn update_npm_install_version(new_version: &str) -> Result<()> {
-    let path = "packaging/npm/scripts/install.js";
-    let content = std::fs::read_to_string(path)?;
-
-    // Replace VERSION = 'x.y.z' with new version
-    let new_content = content
-        .lines()
-        .map(|line| {
-            if line.starts_with("const VERSION = '") {
-                format!("const VERSION = '{}';", new_version)
-            } else {
-                line.to_string()
-            }
-        })
-        .collect::<Vec<_>>()
-        .join("\n");
-
-    std::fs::write(path, new_content + "\n")?;
-
-    Ok(())
-}
-
-/// Update version references in VS Code README.md
-fn update_vscode_readme_version(current_version: &str, new_version: &str) -> Result<()> {
-    let path = "vscode/README.md";
-    if !std::path::Path::new(path).exists() {
-        return Ok(());
-    }
-
-    let content = std::fs::read_to_string(path)?;
-
-    // Update version patterns by replacing current version with new version:
-    // 1. "proj-X.Y.Z.vsix" -> "proj-NEW_VERSION.vsix"
-    // 2. "version number like `X.Y.Z`" -> "version number like `NEW_VERSION`"
-    let mut new_content = content.clone();
-
-    // Replace VSIX filenames
-    let old_vsix = format!("proj-{}.vsix", current_version);
-    let new_vsix = format!("proj-{}.vsix", new_version);
-    new_content = new_content.replace(&old_vsix, &new_vsix);
-
-    // Replace "version number like" references
-    let old_like = format!("version number like `{}`", current_version);
-    let new_like = format!("version number like `{}`", new_version);
-    new_content = new_content.replace(&old_like, &new_like);
-
-    // Only write if changes were made
-    if new_content != content {
-        std::fs::write(path, new_content)?;
-    }
-
-    Ok(())
-}
-
-/// Update a field in the Homebrew formula
-fn update_formula_field(formula: &str, field: &str, value: &str) -> String {
-    let mut result = String::new();
-    for line in formula.lines() {
-        if line.trim().starts_with(field) && line.contains('"') {
-            // Find the pattern and replace
-            if let Some(start) = line.find('"') {
-                if let Some(end) = line.rfind('"') {
-                    let prefix = &line[..start + 1];
-                    let suffix = &line[end..];
-                    result.push_str(&format!("{}{}{}\n", prefix, value, suffix));
-                    continue;
-                }
-            }
-        }
-        result.push_str(line);
-        result.push('\n');
-    }
-    result
-}
-
-/// Update SHA256 hash in formula for a specific platform
-fn update_formula_sha256(formula: &str, platform: &str, hash: &str) -> String {
-    let lines: Vec<&str> = formula.lines().collect();
-    let mut result = String::new();
-    let mut i = 0;
-
-    while i < lines.len() {
-        let line = lines[i];
-        result.push_str(line);
-        result.push('\n');
-
-        // Look for URL line containing our platform
-        if line.contains("url") && line.contains(platform) {
-            // Next non-empty line should be sha256
-            i += 1;
-            while i < lines.len() {
-                let next_line = lines[i];
-                if next_line.trim().starts_with("sha256") {
-                    // Replace the hash
-                    if let Some(start) = next_line.find('"') {
-                        let prefix = &next_line[..start + 1];
-                        result.push_str(&format!("{}{}\"\n", prefix, hash));
-                        i += 1;
-                        continue;
-                    }
-                }
-                result.push_str(next_line);
-                result.push('\n');
-                i += 1;
-                if !next_line.trim().is_empty() {
-                    break;
-                }
-            }
-            continue;
-        }
-        i += 1;
-    }
-
-    result
-}
-
 /// Run a command and check for success
 fn run_command(cmd: &str, args: &[&str]) -> Result<()> {
     let status = Command::new(cmd)
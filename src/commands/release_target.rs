@@ -0,0 +1,690 @@
+// Release targets - the distribution channels `proj release` keeps up to date
+//
+// Each channel (Homebrew formula, npm package, Scoop manifest, AUR PKGBUILD, winget
+// manifests, crates.io, or any shell command) is a `ReleaseTarget` with three steps:
+// `prepare` bumps versions in the repo before the release commit, and once the
+// release is built, `proj release --check` runs `verify` (can this target be updated
+// now?) and then `update`. Which targets run, and their paths, come from release.toml
+// at the repository root; without one, the Homebrew formula, npm package, and VS Code
+// extension under packaging/ and vscode/ are used, as before the file existed.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+
+/// Where the release configuration lives, relative to the repository root
+pub const CONFIG_FILE: &str = "release.toml";
+
+/// GitHub repository whose releases hold the built assets, unless release.toml says otherwise
+const DEFAULT_REPO: &str = "victorysightsound/aiproject";
+
+/// The release being made
+pub struct Release<'a> {
+    pub version: &'a str,
+    /// The version before this release, for replacing it in text files (known when
+    /// preparing the release, not when checking it afterwards)
+    pub previous: Option<&'a str>,
+    /// owner/name on GitHub
+    pub repo: &'a str,
+}
+
+impl Release<'_> {
+    pub fn tag(&self) -> String {
+        format!("v{}", self.version)
+    }
+
+    /// Download URL of a release asset
+    pub fn asset_url(&self, asset: &str) -> String {
+        format!(
+            "https://github.com/{}/releases/download/{}/{}",
+            self.repo,
+            self.tag(),
+            asset
+        )
+    }
+}
+
+/// A distribution channel. Steps a channel doesn't need are no-ops.
+pub trait ReleaseTarget {
+    /// Shown in progress output
+    fn name(&self) -> String;
+
+    /// Before the release commit: set the new version in files kept in the repo.
+    /// Returns the files written, which go into the release commit.
+    fn prepare(&self, _release: &Release) -> Result<Vec<PathBuf>> {
+        Ok(Vec::new())
+    }
+
+    /// After the release is built: fail with the reason if this target can't be
+    /// updated now (a missing file or tool, say)
+    fn verify(&self, _release: &Release) -> Result<()> {
+        Ok(())
+    }
+
+    /// After `verify`: update the channel. Returns the files written, which are
+    /// committed together once every target has run.
+    fn update(&self, _release: &Release) -> Result<Vec<PathBuf>> {
+        Ok(Vec::new())
+    }
+
+    /// How users upgrade through this channel, for the summary
+    fn install_hint(&self) -> Option<String> {
+        None
+    }
+}
+
+/// release.toml
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ReleaseConfig {
+    /// owner/name of the GitHub repository the release assets are attached to
+    #[serde(default = "default_repo")]
+    pub repo: String,
+    #[serde(default, rename = "target")]
+    targets: Vec<TargetConfig>,
+}
+
+fn default_repo() -> String {
+    DEFAULT_REPO.to_string()
+}
+
+/// One [[target]] table; `type` picks the channel
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum TargetConfig {
+    Homebrew(Homebrew),
+    Npm(Npm),
+    Vscode(Vscode),
+    Scoop(Scoop),
+    Aur(Aur),
+    Winget(Winget),
+    Cargo(Cargo),
+    Command(ShellCommand),
+}
+
+impl ReleaseConfig {
+    /// release.toml in `root`, or the built-in defaults when there isn't one
+    pub fn load(root: &Path) -> Result<Self> {
+        let path = root.join(CONFIG_FILE);
+        if !path.exists() {
+            return Ok(Self::defaults());
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        toml::from_str(&content).with_context(|| format!("Failed to parse {}", CONFIG_FILE))
+    }
+
+    /// The channels proj had before release.toml
+    fn defaults() -> Self {
+        let assets = [
+            "aarch64-apple-darwin",
+            "x86_64-apple-darwin",
+            "aarch64-unknown-linux-gnu",
+            "x86_64-unknown-linux-gnu",
+        ]
+        .into_iter()
+        .map(|platform| (platform.to_string(), format!("proj-{}.tar.gz", platform)))
+        .collect();
+        Self {
+            repo: default_repo(),
+            targets: vec![
+                TargetConfig::Vscode(Vscode {
+                    package: "vscode/package.json".into(),
+                    readme: Some("vscode/README.md".into()),
+                }),
+                TargetConfig::Npm(Npm {
+                    package: "packaging/npm/package.json".into(),
+                    install_script: Some("packaging/npm/scripts/install.js".into()),
+                    publish: false,
+                }),
+                TargetConfig::Homebrew(Homebrew {
+                    formula: "packaging/homebrew/aiproject.rb".into(),
+                    assets,
+                }),
+            ],
+        }
+    }
+
+    /// The configured targets, in file order
+    pub fn targets(&self) -> Vec<&dyn ReleaseTarget> {
+        self.targets
+            .iter()
+            .map(|target| -> &dyn ReleaseTarget {
+                match target {
+                    TargetConfig::Homebrew(t) => t,
+                    TargetConfig::Npm(t) => t,
+                    TargetConfig::Vscode(t) => t,
+                    TargetConfig::Scoop(t) => t,
+                    TargetConfig::Aur(t) => t,
+                    TargetConfig::Winget(t) => t,
+                    TargetConfig::Cargo(t) => t,
+                    TargetConfig::Command(t) => t,
+                }
+            })
+            .collect()
+    }
+
+    /// Whether an npm target is configured (its token expires and needs a reminder)
+    pub fn has_npm(&self) -> bool {
+        self.targets
+            .iter()
+            .any(|t| matches!(t, TargetConfig::Npm(_)))
+    }
+}
+
+/// Homebrew formula with a url and sha256 per platform
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Homebrew {
+    formula: PathBuf,
+    /// Platform (as it appears in the formula's url lines) -> release asset
+    assets: BTreeMap<String, String>,
+}
+
+impl ReleaseTarget for Homebrew {
+    fn name(&self) -> String {
+        format!("Homebrew ({})", self.formula.display())
+    }
+
+    fn verify(&self, _release: &Release) -> Result<()> {
+        require_file(&self.formula)
+    }
+
+    fn update(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        let mut formula = std::fs::read_to_string(&self.formula)
+            .with_context(|| format!("Could not read {}", self.formula.display()))?;
+        formula = update_formula_field(&formula, "version", release.version);
+        // Every hash first, so a missing asset leaves the formula untouched
+        for (platform, asset) in &self.assets {
+            let hash = remote_sha256(&release.asset_url(asset))?;
+            formula = update_formula_sha256(&formula, platform, &hash);
+        }
+        std::fs::write(&self.formula, &formula)?;
+        Ok(vec![self.formula.clone()])
+    }
+
+    fn install_hint(&self) -> Option<String> {
+        let name = self.formula.file_stem()?.to_string_lossy().to_string();
+        Some(format!("brew upgrade {}", name))
+    }
+}
+
+/// npm package that downloads the release binary on install
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Npm {
+    package: PathBuf,
+    /// Script with a `const VERSION = '...'` line to keep in step
+    install_script: Option<PathBuf>,
+    /// Run `npm publish` here instead of leaving it to the release workflow
+    #[serde(default)]
+    publish: bool,
+}
+
+impl Npm {
+    fn package_name(&self) -> Option<String> {
+        let content = std::fs::read_to_string(&self.package).ok()?;
+        let json: serde_json::Value = serde_json::from_str(&content).ok()?;
+        json["name"].as_str().map(String::from)
+    }
+}
+
+impl ReleaseTarget for Npm {
+    fn name(&self) -> String {
+        format!("npm ({})", self.package.display())
+    }
+
+    fn prepare(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        if !self.package.exists() {
+            return Ok(Vec::new());
+        }
+        set_json_version(&self.package, release.version)?;
+        let mut written = vec![self.package.clone()];
+        if let Some(script) = &self.install_script {
+            replace_lines(script, |line| {
+                line.starts_with("const VERSION = '")
+                    .then(|| format!("const VERSION = '{}';", release.version))
+            })?;
+            written.push(script.clone());
+        }
+        Ok(written)
+    }
+
+    fn verify(&self, _release: &Release) -> Result<()> {
+        require_file(&self.package)?;
+        if self.publish {
+            require_tool("npm")?;
+        }
+        Ok(())
+    }
+
+    fn update(&self, _release: &Release) -> Result<Vec<PathBuf>> {
+        if self.publish {
+            let dir = self.package.parent().unwrap_or(Path::new("."));
+            run(Command::new("npm").arg("publish").current_dir(dir))?;
+        }
+        Ok(Vec::new())
+    }
+
+    fn install_hint(&self) -> Option<String> {
+        Some(format!("npm update -g {}", self.package_name()?))
+    }
+}
+
+/// VS Code extension (only its version is kept in step; it's published separately)
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Vscode {
+    package: PathBuf,
+    readme: Option<PathBuf>,
+}
+
+impl ReleaseTarget for Vscode {
+    fn name(&self) -> String {
+        format!("VS Code extension ({})", self.package.display())
+    }
+
+    fn prepare(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        if !self.package.exists() {
+            return Ok(Vec::new());
+        }
+        set_json_version(&self.package, release.version)?;
+        let mut written = vec![self.package.clone()];
+        let readme = self.readme.as_ref().filter(|r| r.exists());
+        if let (Some(readme), Some(previous)) = (readme, release.previous) {
+            // The .vsix file name and the "version number like `X.Y.Z`" example
+            let content = std::fs::read_to_string(readme)?;
+            let updated = content
+                .replace(
+                    &format!("proj-{}.vsix", previous),
+                    &format!("proj-{}.vsix", release.version),
+                )
+                .replace(
+                    &format!("version number like `{}`", previous),
+                    &format!("version number like `{}`", release.version),
+                );
+            if updated != content {
+                std::fs::write(readme, updated)?;
+                written.push(readme.clone());
+            }
+        }
+        Ok(written)
+    }
+}
+
+/// Scoop manifest (JSON) for a Windows zip
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Scoop {
+    manifest: PathBuf,
+    asset: String,
+}
+
+impl ReleaseTarget for Scoop {
+    fn name(&self) -> String {
+        format!("Scoop ({})", self.manifest.display())
+    }
+
+    fn verify(&self, _release: &Release) -> Result<()> {
+        require_file(&self.manifest)
+    }
+
+    fn update(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        let content = std::fs::read_to_string(&self.manifest)?;
+        let mut json: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", self.manifest.display()))?;
+        let url = release.asset_url(&self.asset);
+        let hash = remote_sha256(&url)?;
+
+        json["version"] = serde_json::json!(release.version);
+        // Per-architecture manifests keep url and hash under architecture.64bit
+        let download = if json["architecture"]["64bit"].is_object() {
+            &mut json["architecture"]["64bit"]
+        } else {
+            &mut json
+        };
+        download["url"] = serde_json::json!(url);
+        download["hash"] = serde_json::json!(hash);
+
+        std::fs::write(&self.manifest, serde_json::to_string_pretty(&json)? + "\n")?;
+        Ok(vec![self.manifest.clone()])
+    }
+
+    fn install_hint(&self) -> Option<String> {
+        let name = self.manifest.file_stem()?.to_string_lossy().to_string();
+        Some(format!("scoop update {}", name))
+    }
+}
+
+/// Arch User Repository PKGBUILD, and its .SRCINFO when it sits alongside
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Aur {
+    pkgbuild: PathBuf,
+    asset: String,
+}
+
+impl ReleaseTarget for Aur {
+    fn name(&self) -> String {
+        format!("AUR ({})", self.pkgbuild.display())
+    }
+
+    fn verify(&self, _release: &Release) -> Result<()> {
+        require_file(&self.pkgbuild)
+    }
+
+    fn update(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        let hash = remote_sha256(&release.asset_url(&self.asset))?;
+        let old_version = std::fs::read_to_string(&self.pkgbuild)?
+            .lines()
+            .find_map(|line| line.strip_prefix("pkgver=").map(String::from))
+            .unwrap_or_default();
+
+        replace_lines(&self.pkgbuild, |line| {
+            if line.starts_with("pkgver=") {
+                Some(format!("pkgver={}", release.version))
+            } else if line.starts_with("pkgrel=") {
+                Some("pkgrel=1".to_string())
+            } else if line.starts_with("sha256sums=") {
+                Some(format!("sha256sums=('{}')", hash))
+            } else {
+                None
+            }
+        })?;
+        let mut written = vec![self.pkgbuild.clone()];
+
+        let srcinfo = self.pkgbuild.with_file_name(".SRCINFO");
+        if srcinfo.exists() {
+            replace_lines(&srcinfo, |line| {
+                let key = line.split('=').next().unwrap_or_default().trim();
+                match key {
+                    "pkgver" => Some(format!("\tpkgver = {}", release.version)),
+                    "pkgrel" => Some("\tpkgrel = 1".to_string()),
+                    "sha256sums" => Some(format!("\tsha256sums = {}", hash)),
+                    _ if key.starts_with("source") && !old_version.is_empty() => {
+                        Some(line.replace(&old_version, release.version))
+                    }
+                    _ => None,
+                }
+            })?;
+            written.push(srcinfo);
+        }
+        Ok(written)
+    }
+}
+
+/// winget manifests (YAML): the version, installer, and locale files
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Winget {
+    /// Every manifest file; all get the new PackageVersion
+    manifests: Vec<PathBuf>,
+    asset: String,
+}
+
+impl ReleaseTarget for Winget {
+    fn name(&self) -> String {
+        "winget".to_string()
+    }
+
+    fn verify(&self, _release: &Release) -> Result<()> {
+        self.manifests.iter().try_for_each(|m| require_file(m))
+    }
+
+    fn update(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        let url = release.asset_url(&self.asset);
+        let hash = remote_sha256(&url)?.to_uppercase();
+        for manifest in &self.manifests {
+            replace_lines(manifest, |line| {
+                let indent = &line[..line.len() - line.trim_start().len()];
+                let key = line.trim_start().split(':').next().unwrap_or_default();
+                let value = match key {
+                    "PackageVersion" => release.version,
+                    "InstallerUrl" => &url,
+                    "InstallerSha256" => &hash,
+                    _ => return None,
+                };
+                Some(format!("{}{}: {}", indent, key, value))
+            })?;
+        }
+        Ok(self.manifests.clone())
+    }
+}
+
+/// crates.io: `cargo publish` for each package, in order
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Cargo {
+    packages: Vec<String>,
+}
+
+impl ReleaseTarget for Cargo {
+    fn name(&self) -> String {
+        format!("crates.io ({})", self.packages.join(", "))
+    }
+
+    fn verify(&self, _release: &Release) -> Result<()> {
+        require_tool("cargo")
+    }
+
+    fn update(&self, _release: &Release) -> Result<Vec<PathBuf>> {
+        for package in &self.packages {
+            run(Command::new("cargo").args(["publish", "-p", package]))?;
+        }
+        Ok(Vec::new())
+    }
+
+    fn install_hint(&self) -> Option<String> {
+        Some(format!("cargo install {}", self.packages.last()?))
+    }
+}
+
+/// Any channel proj doesn't know: shell commands for each step, with {version},
+/// {previous} (empty after the release), and {tag} filled in
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShellCommand {
+    name: String,
+    prepare: Option<String>,
+    verify: Option<String>,
+    update: Option<String>,
+    /// Files the commands write, to commit with the release or the update
+    #[serde(default)]
+    files: Vec<PathBuf>,
+}
+
+impl ShellCommand {
+    fn run_step(&self, step: &Option<String>, release: &Release) -> Result<bool> {
+        let Some(script) = step else {
+            return Ok(false);
+        };
+        let script = script
+            .replace("{version}", release.version)
+            .replace("{previous}", release.previous.unwrap_or_default())
+            .replace("{tag}", &release.tag());
+        let mut command = if cfg!(windows) {
+            let mut c = Command::new("cmd");
+            c.args(["/C", &script]);
+            c
+        } else {
+            let mut c = Command::new("sh");
+            c.args(["-c", &script]);
+            c
+        };
+        run(&mut command)?;
+        Ok(true)
+    }
+}
+
+impl ReleaseTarget for ShellCommand {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    fn prepare(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        Ok(if self.run_step(&self.prepare, release)? {
+            self.files.clone()
+        } else {
+            Vec::new()
+        })
+    }
+
+    fn verify(&self, release: &Release) -> Result<()> {
+        self.run_step(&self.verify, release).map(|_| ())
+    }
+
+    fn update(&self, release: &Release) -> Result<Vec<PathBuf>> {
+        Ok(if self.run_step(&self.update, release)? {
+            self.files.clone()
+        } else {
+            Vec::new()
+        })
+    }
+}
+
+fn require_file(path: &Path) -> Result<()> {
+    if !path.exists() {
+        bail!("{} not found", path.display());
+    }
+    Ok(())
+}
+
+fn require_tool(program: &str) -> Result<()> {
+    match Command::new(program).arg("--version").output() {
+        Ok(output) if output.status.success() => Ok(()),
+        _ => bail!("'{}' is not installed", program),
+    }
+}
+
+/// Run a command, failing with its exit status if it doesn't succeed
+fn run(command: &mut Command) -> Result<()> {
+    let status = command
+        .status()
+        .with_context(|| format!("Failed to run {:?}", command.get_program()))?;
+    if !status.success() {
+        bail!("{:?} failed ({})", command.get_program(), status);
+    }
+    Ok(())
+}
+
+/// Set the top-level "version" of a package.json
+fn set_json_version(path: &Path, version: &str) -> Result<()> {
+    let content = std::fs::read_to_string(path)?;
+    let mut json: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    if let Some(obj) = json.as_object_mut() {
+        obj.insert(
+            "version".to_string(),
+            serde_json::Value::String(version.to_string()),
+        );
+    }
+    std::fs::write(path, serde_json::to_string_pretty(&json)? + "\n")?;
+    Ok(())
+}
+
+/// Rewrite a text file line by line; `replace` returns a new line or None to keep it
+fn replace_lines(path: &Path, replace: impl Fn(&str) -> Option<String>) -> Result<()> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Could not read {}", path.display()))?;
+    let mut updated = content
+        .lines()
+        .map(|line| replace(line).unwrap_or_else(|| line.to_string()))
+        .collect::<Vec<_>>()
+        .join("\n");
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    std::fs::write(path, updated)?;
+    Ok(())
+}
+
+/// SHA256 of a remote file
+fn remote_sha256(url: &str) -> Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let response = crate::http::agent(url, Duration::from_secs(120))
+        .get(url)
+        .call()
+        .map_err(|e| match e {
+            ureq::Error::Status(code, _) => anyhow!("{}: HTTP {}", url, code),
+            e => anyhow!("Failed to download {}: {}", url, e),
+        })?;
+
+    let mut reader = response.into_reader();
+    let mut hasher = Sha256::new();
+    let mut buffer = [0u8; 8192];
+    loop {
+        let bytes_read = std::io::Read::read(&mut reader, &mut buffer)?;
+        if bytes_read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..bytes_read]);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// Update a field in the Homebrew formula
+fn update_formula_field(formula: &str, field: &str, value: &str) -> String {
+    let mut result = String::new();
+    for line in formula.lines() {
+        if line.trim().starts_with(field) && line.contains('"') {
+            // Find the pattern and replace
+            if let Some(start) = line.find('"') {
+                if let Some(end) = line.rfind('"') {
+                    let prefix = &line[..start + 1];
+                    let suffix = &line[end..];
+                    result.push_str(&format!("{}{}{}\n", prefix, value, suffix));
+                    continue;
+                }
+            }
+        }
+        result.push_str(line);
+        result.push('\n');
+    }
+    result
+}
+
+/// Update SHA256 hash in formula for a specific platform
+fn update_formula_sha256(formula: &str, platform: &str, hash: &str) -> String {
+    let lines: Vec<&str> = formula.lines().collect();
+    let mut result = String::new();
+    let mut i = 0;
+
+    while i < lines.len() {
+        let line = lines[i];
+        result.push_str(line);
+        result.push('\n');
+
+        // Look for URL line containing our platform
+        if line.contains("url") && line.contains(platform) {
+            // Next non-empty line should be sha256
+            i += 1;
+            while i < lines.len() {
+                let next_line = lines[i];
+                if next_line.trim().starts_with("sha256") {
+                    // Replace the hash
+                    if let Some(start) = next_line.find('"') {
+                        let prefix = &next_line[..start + 1];
+                        result.push_str(&format!("{}{}\"\n", prefix, hash));
+                        i += 1;
+                        continue;
+                    }
+                }
+                result.push_str(next_line);
+                result.push('\n');
+                i += 1;
+                if !next_line.trim().is_empty() {
+                    break;
+                }
+            }
+            continue;
+        }
+        i += 1;
+    }
+
+    result
+}
@@ -5,13 +5,20 @@ use colored::Colorize;
 use rusqlite::Connection;
 use serde::Serialize;
 
+use crate::budget::{self, Budget};
+use crate::commands::task_import;
 use crate::config::ProjectConfig;
 use crate::database::open_database;
-use crate::models::{Blocker, Decision, Question, Task};
+use crate::models::DueStatus;
+use crate::nudges::{self, Nudge};
 use crate::paths::{get_config_path, get_tracking_db_path};
+use crate::repository::Repository;
 use crate::session::{get_last_completed_session, get_or_create_session};
 
-pub fn run(for_ai: bool) -> Result<()> {
+/// Longest decision rationale kept in budgeted output, in tokens
+const MAX_RATIONALE_TOKENS: usize = 60;
+
+pub fn run(for_ai: bool, max_tokens: Option<usize>) -> Result<()> {
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
         .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
@@ -19,7 +26,7 @@ pub fn run(for_ai: bool) -> Result<()> {
     let config = load_config()?;
 
     if for_ai {
-        output_json(&conn, &config)
+        output_json(&conn, &config, max_tokens)
     } else {
         output_human(&conn, &config)
     }
@@ -31,7 +38,7 @@ fn load_config() -> Result<ProjectConfig> {
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| format!("Failed to read config at {:?}", config_path))?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
 
@@ -45,6 +52,20 @@ struct ResumeContext {
     active_tasks: Vec<TaskInfo>,
     recent_decisions: Vec<DecisionInfo>,
     open_questions: Vec<QuestionInfo>,
+    /// Reminders to log what this session is doing, each with the command that acts on it
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    nudges: Vec<Nudge>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    budget: Option<BudgetInfo>,
+}
+
+/// How a --max-tokens budget was spent
+#[derive(Serialize)]
+struct BudgetInfo {
+    max_tokens: usize,
+    estimated_tokens: usize,
+    /// Items left out to stay under the budget
+    omitted: usize,
 }
 
 #[derive(Serialize)]
@@ -77,6 +98,11 @@ struct TaskInfo {
     status: String,
     priority: String,
     blocked_by: Option<String>,
+    due: Option<String>,
+    overdue: bool,
+    /// Tracker ticket an imported task mirrors, e.g. "Jira ENG-12"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    origin: Option<String>,
 }
 
 #[derive(Serialize)]
@@ -94,11 +120,13 @@ struct QuestionInfo {
     context: Option<String>,
 }
 
-fn output_json(conn: &Connection, config: &ProjectConfig) -> Result<()> {
+fn output_json(conn: &Connection, config: &ProjectConfig, max_tokens: Option<usize>) -> Result<()> {
     let session = get_or_create_session(conn)?;
-    let last_session = get_last_completed_session(conn)?;
+    let repo = Repository::begin(conn)?;
+    let last_session = get_last_completed_session(repo.conn())?;
+    let origins = task_import::origins(repo.conn()).unwrap_or_default();
 
-    let context = ResumeContext {
+    let mut context = ResumeContext {
         project: ProjectInfo {
             name: config.name.clone(),
             project_type: config.project_type.clone(),
@@ -128,25 +156,31 @@ fn output_json(conn: &Connection, config: &ProjectConfig) -> Result<()> {
                 .as_ref()
                 .and_then(|ss| serde_json::from_str(ss).ok()),
         }),
-        active_blockers: get_active_blockers(conn)?
-            .into_iter()
+        active_blockers: repo
+            .active_blockers()?
+            .iter()
             .map(|b| BlockerInfo {
                 blocker_id: b.blocker_id,
-                description: b.description,
+                description: b.description.clone(),
                 created_at: b.created_at.format("%Y-%m-%d %H:%M:%S").to_string(),
             })
             .collect(),
-        active_tasks: get_active_tasks(conn)?
-            .into_iter()
+        active_tasks: repo
+            .active_tasks()?
+            .iter()
             .map(|t| TaskInfo {
+                overdue: t.due_status() == DueStatus::Overdue,
+                origin: origins.get(&t.task_id).cloned(),
                 task_id: t.task_id,
-                description: t.description,
-                status: t.status,
-                priority: t.priority,
-                blocked_by: t.blocked_by,
+                description: t.description.clone(),
+                status: t.status.clone(),
+                priority: t.priority.clone(),
+                blocked_by: t.blocked_by.clone(),
+                due: t.due.clone(),
             })
             .collect(),
-        recent_decisions: get_recent_decisions(conn, 10)?
+        recent_decisions: repo
+            .recent_decisions(10)?
             .into_iter()
             .map(|d| DecisionInfo {
                 decision_id: d.decision_id,
@@ -155,7 +189,8 @@ fn output_json(conn: &Connection, config: &ProjectConfig) -> Result<()> {
                 rationale: d.rationale,
             })
             .collect(),
-        open_questions: get_open_questions(conn)?
+        open_questions: repo
+            .open_questions()?
             .into_iter()
             .map(|q| QuestionInfo {
                 question_id: q.question_id,
@@ -163,14 +198,102 @@ fn output_json(conn: &Connection, config: &ProjectConfig) -> Result<()> {
                 context: q.context,
             })
             .collect(),
+        nudges: nudges::evaluate(repo.conn(), &session, config)?,
+        budget: None,
     };
+    repo.finish()?;
 
-    println!("{}", serde_json::to_string_pretty(&context)?);
+    // Budgeted output is compact, since every token counts
+    if let Some(max_tokens) = max_tokens {
+        fit_to_budget(&mut context, max_tokens);
+        println!("{}", serde_json::to_string(&context)?);
+    } else {
+        println!("{}", serde_json::to_string_pretty(&context)?);
+    }
     Ok(())
 }
 
+/// Keep the most important items that fit in `max_tokens`: blockers, then tasks
+/// (already in priority order), decisions, and questions. Items that don't fit are
+/// skipped; decision rationales are shortened before a decision is dropped.
+fn fit_to_budget(context: &mut ResumeContext, max_tokens: usize) {
+    let blockers = std::mem::take(&mut context.active_blockers);
+    let tasks = std::mem::take(&mut context.active_tasks);
+    let decisions = std::mem::take(&mut context.recent_decisions);
+    let questions = std::mem::take(&mut context.open_questions);
+    context.budget = Some(BudgetInfo {
+        max_tokens,
+        estimated_tokens: 0,
+        omitted: 0,
+    });
+
+    // Project and sessions always go in; structured summaries only if they leave room
+    if json_tokens(context) > max_tokens {
+        for session in [&mut context.current_session, &mut context.last_session]
+            .into_iter()
+            .flatten()
+        {
+            session.structured_summary = None;
+        }
+    }
+    let mut budget = Budget::new(max_tokens);
+    budget.used = json_tokens(context);
+    let mut omitted = 0;
+
+    for b in blockers {
+        if budget.take(json_tokens(&b)) {
+            context.active_blockers.push(b);
+        } else {
+            omitted += 1;
+        }
+    }
+    for t in tasks {
+        if budget.take(json_tokens(&t)) {
+            context.active_tasks.push(t);
+        } else {
+            omitted += 1;
+        }
+    }
+    for mut d in decisions {
+        let rationale = d.rationale.take();
+        let base = json_tokens(&d);
+        if let Some(r) = rationale {
+            let room = budget.remaining().saturating_sub(base);
+            if room >= 5 {
+                d.rationale = Some(budget::ellipsize(&r, room.min(MAX_RATIONALE_TOKENS)));
+            }
+        }
+        if budget.take(json_tokens(&d)) {
+            context.recent_decisions.push(d);
+        } else {
+            omitted += 1;
+        }
+    }
+    for q in questions {
+        if budget.take(json_tokens(&q)) {
+            context.open_questions.push(q);
+        } else {
+            omitted += 1;
+        }
+    }
+
+    context.budget = Some(BudgetInfo {
+        max_tokens,
+        estimated_tokens: budget.used,
+        omitted,
+    });
+}
+
+/// Estimated tokens of a value as compact JSON
+fn json_tokens<T: Serialize>(value: &T) -> usize {
+    serde_json::to_string(value)
+        .map(|s| budget::estimate_tokens(&s) + 1)
+        .unwrap_or(0)
+}
+
 fn output_human(conn: &Connection, config: &ProjectConfig) -> Result<()> {
     let session = get_or_create_session(conn)?;
+    let repo = Repository::begin(conn)?;
 
     println!("{}", "=".repeat(60));
     println!("{}", "RESUME CONTEXT".bold());
@@ -194,7 +317,7 @@ fn output_human(conn: &Connection, config: &ProjectConfig) -> Result<()> {
     println!();
 
     // Last session summary
-    if let Some(last) = get_last_completed_session(conn)? {
+    if let Some(last) = get_last_completed_session(repo.conn())? {
         println!("{}", "Last Session".underline());
         println!(
             "#{} ended {}",
@@ -235,20 +358,20 @@ fn output_human(conn: &Connection, config: &ProjectConfig) -> Result<()> {
     }
 
     // Active blockers - these are critical
-    let blockers = get_active_blockers(conn)?;
+    let blockers = repo.active_blockers()?;
     if !blockers.is_empty() {
         println!("{}", "BLOCKERS (resolve these first!)".red().bold());
-        for b in &blockers {
+        for b in blockers {
             println!("  {} {}", "✗".red(), b.description);
         }
         println!();
     }
 
     // Active tasks
-    let tasks = get_active_tasks(conn)?;
+    let tasks = repo.active_tasks()?;
     if !tasks.is_empty() {
         println!("{}", "Active Tasks".underline());
-        for t in &tasks {
+        for t in tasks {
             let status_icon = match t.status.as_str() {
                 "in_progress" => "◐".yellow(),
                 "blocked" => "✗".red(),
@@ -259,16 +382,19 @@ fn output_human(conn: &Connection, config: &ProjectConfig) -> Result<()> {
                 "high" => " [high]".yellow(),
                 _ => "".white(),
             };
+            let due_display = crate::commands::task::format_due(t)
+                .map(|d| format!(" ({})", d))
+                .unwrap_or_default();
             println!(
-                "  {} [{}] {}{}",
-                status_icon, t.task_id, t.description, priority_marker
+                "  {} [{}] {}{}{}",
+                status_icon, t.task_id, t.description, priority_marker, due_display
             );
         }
         println!();
     }
 
     // Recent decisions - important for context
-    let decisions = get_recent_decisions(conn, 5)?;
+    let decisions = repo.recent_decisions(5)?;
     if !decisions.is_empty() {
         println!("{}", "Recent Decisions".underline());
         for d in &decisions {
@@ -281,7 +407,7 @@ fn output_human(conn: &Connection, config: &ProjectConfig) -> Result<()> {
     }
 
     // Open questions
-    let questions = get_open_questions(conn)?;
+    let questions = repo.open_questions()?;
     if !questions.is_empty() {
         println!("{}", "Open Questions".underline());
         for q in &questions {
@@ -310,122 +436,5 @@ fn output_human(conn: &Connection, config: &ProjectConfig) -> Result<()> {
         println!("  No pending tasks. Check if there's anything to add.");
     }
 
-    Ok(())
-}
-
-// Helper functions for database queries
-
-fn get_active_tasks(conn: &Connection) -> Result<Vec<Task>> {
-    let mut stmt = conn.prepare(
-        "SELECT task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes
-         FROM tasks
-         WHERE status NOT IN ('completed', 'cancelled')
-         ORDER BY
-           CASE priority WHEN 'urgent' THEN 1 WHEN 'high' THEN 2 WHEN 'normal' THEN 3 ELSE 4 END,
-           created_at"
-    )?;
-
-    let tasks = stmt.query_map([], |row| {
-        Ok(Task {
-            task_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            description: row.get(4)?,
-            status: row.get(5)?,
-            priority: row.get(6)?,
-            blocked_by: row.get(7)?,
-            parent_task_id: row.get(8)?,
-            notes: row.get(9)?,
-        })
-    })?;
-
-    tasks.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
-}
-
-fn get_active_blockers(conn: &Connection) -> Result<Vec<Blocker>> {
-    let mut stmt = conn.prepare(
-        "SELECT blocker_id, session_id, created_at, resolved_at, description, status, resolution, related_task_id
-         FROM blockers
-         WHERE status = 'active'
-         ORDER BY created_at DESC"
-    )?;
-
-    let blockers = stmt.query_map([], |row| {
-        Ok(Blocker {
-            blocker_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            resolved_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            description: row.get(4)?,
-            status: row.get(5)?,
-            resolution: row.get(6)?,
-            related_task_id: row.get(7)?,
-        })
-    })?;
-
-    blockers
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.into())
-}
-
-fn get_recent_decisions(conn: &Connection, limit: usize) -> Result<Vec<Decision>> {
-    let mut stmt = conn.prepare(
-        "SELECT decision_id, session_id, created_at, topic, decision, rationale, alternatives, status, superseded_by
-         FROM decisions
-         WHERE status = 'active'
-         ORDER BY created_at DESC
-         LIMIT ?1"
-    )?;
-
-    let decisions = stmt.query_map([limit as i64], |row| {
-        Ok(Decision {
-            decision_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            topic: row.get(3)?,
-            decision: row.get(4)?,
-            rationale: row.get(5)?,
-            alternatives: row.get(6)?,
-            status: row.get(7)?,
-            superseded_by: row.get(8)?,
-        })
-    })?;
-
-    decisions
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.into())
-}
-
-fn get_open_questions(conn: &Connection) -> Result<Vec<Question>> {
-    let mut stmt = conn.prepare(
-        "SELECT question_id, session_id, created_at, answered_at, question, context, answer, status
-         FROM questions
-         WHERE status = 'open'
-         ORDER BY created_at DESC",
-    )?;
-
-    let questions = stmt.query_map([], |row| {
-        Ok(Question {
-            question_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            answered_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            question: row.get(4)?,
-            context: row.get(5)?,
-            answer: row.get(6)?,
-            status: row.get(7)?,
-        })
-    })?;
-
-    questions
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.into())
-}
-
-/// Parse datetime string from SQLite
-fn parse_datetime(s: String) -> chrono::DateTime<chrono::Utc> {
-    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
-        .map(|dt| dt.and_utc())
-        .unwrap_or_else(|_| chrono::Utc::now())
+    repo.finish()
 }
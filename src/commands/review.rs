@@ -2,16 +2,66 @@
 //
 // Shows session activity alongside git commits to help identify
 // decisions, tasks, or context that wasn't logged during the session.
+// The diff since the session started is scanned for likely candidates
+// (dependency changes, new TODO/FIXME comments, reverts and disabled
+// tests), which can be logged interactively or with --accept.
 
-use anyhow::{Context, Result};
+use std::collections::BTreeSet;
+use std::path::Path;
+
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
+use dialoguer::Confirm;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::commands::decision;
+use crate::database::{open_database, record_uuid};
+use crate::git::{self, GitCommit};
+use crate::models::Session;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::session::{get_active_session, resolve_author, track_branch};
+use crate::text::truncate;
+use crate::webhooks;
+
+/// Something the diff suggests should have been logged
+#[derive(Serialize)]
+struct Suggestion {
+    id: usize,
+    /// decision, task, or blocker
+    kind: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    topic: Option<String>,
+    text: String,
+    /// File or commit the suggestion came from
+    source: String,
+    /// Command that logs it
+    command: String,
+}
+
+#[derive(Serialize)]
+struct ReviewReport {
+    session_id: i64,
+    logged: LoggedCounts,
+    commits: Vec<ReviewCommit>,
+    suggestions: Vec<Suggestion>,
+}
+
+#[derive(Serialize)]
+struct LoggedCounts {
+    decisions: i64,
+    tasks: i64,
+    notes: i64,
+    blockers: i64,
+}
 
-use crate::database::open_database;
-use crate::git;
-use crate::paths::get_tracking_db_path;
-use crate::session::get_active_session;
+#[derive(Serialize)]
+struct ReviewCommit {
+    hash: String,
+    message: String,
+}
 
-pub fn run() -> Result<()> {
+pub fn run(json: bool, accept: &[usize]) -> Result<()> {
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
         .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
@@ -19,6 +69,7 @@ pub fn run() -> Result<()> {
     // Get active session
     let session = match get_active_session(&conn)? {
         Some(s) => s,
+        None if json || !accept.is_empty() => bail!("No active session to review."),
         None => {
             println!("{} No active session to review.", "○".white());
             println!("Run 'proj status' to start a session first.");
@@ -28,9 +79,8 @@ pub fn run() -> Result<()> {
 
     let session_start = session.started_at.format("%Y-%m-%d %H:%M:%S").to_string();
 
-    println!("{}", "Session Review".bold());
-    println!("{}", "═".repeat(50));
-    println!();
+    let root = get_project_root()?;
+    let _ = git::sync_recent_commits(&conn, &root, 50);
 
     // Count logged items
     let decision_count: i64 = conn
@@ -69,6 +119,38 @@ pub fn run() -> Result<()> {
     let commits = git::get_commits_since(&conn, &session_start)?;
     let commit_count = commits.len();
 
+    let suggestions = collect_suggestions(&conn, &root, &session, &commits)?;
+
+    if !accept.is_empty() {
+        return accept_suggestions(&conn, &session, &suggestions, accept);
+    }
+
+    if json {
+        let report = ReviewReport {
+            session_id: session.session_id,
+            logged: LoggedCounts {
+                decisions: decision_count,
+                tasks: task_count,
+                notes: note_count,
+                blockers: blocker_count,
+            },
+            commits: commits
+                .iter()
+                .map(|c| ReviewCommit {
+                    hash: c.short_hash.clone(),
+                    message: c.message.clone(),
+                })
+                .collect(),
+            suggestions,
+        };
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+
+    println!("{}", "Session Review".bold());
+    println!("{}", "═".repeat(50));
+    println!();
+
     // Show what's been logged
     println!("{}", "Logged This Session:".bold());
     println!(
@@ -170,6 +252,8 @@ pub fn run() -> Result<()> {
     }
 
     println!();
+    review_suggestions(&conn, &session, &suggestions)?;
+
     println!(
         "{}",
         "Review the commits and log any missed decisions/tasks.".dimmed()
@@ -182,10 +266,478 @@ pub fn run() -> Result<()> {
     Ok(())
 }
 
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
+/// Show suggestions and offer to log each one (prints the commands when not interactive)
+fn review_suggestions(
+    conn: &Connection,
+    session: &Session,
+    suggestions: &[Suggestion],
+) -> Result<()> {
+    if suggestions.is_empty() {
+        return Ok(());
+    }
+
+    println!("{} ({})", "Suggested From Diff:".bold(), suggestions.len());
+    for s in suggestions {
+        println!(
+            "  {} {} {}",
+            format!("[{}]", s.id).cyan(),
+            s.kind.bold(),
+            describe(s)
+        );
+        println!("      {}", format!("from {}", s.source).dimmed());
+    }
+    println!();
+
+    if !(atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout)) {
+        println!("  Log with: {}", "proj review --accept 1,2,...".cyan());
+        println!();
+        return Ok(());
+    }
+
+    let mut accepted = Vec::new();
+    for s in suggestions {
+        let log_it = Confirm::new()
+            .with_prompt(format!(
+                "Log {} \"{}\"?",
+                s.kind,
+                truncate(&describe(s), 60)
+            ))
+            .default(false)
+            .interact()?;
+        if log_it {
+            accepted.push(s.id);
+        }
+    }
+    if !accepted.is_empty() {
+        accept_suggestions(conn, session, suggestions, &accepted)?;
+    }
+    println!();
+    Ok(())
+}
+
+/// Log the suggestions with the given ids to the session
+fn accept_suggestions(
+    conn: &Connection,
+    session: &Session,
+    suggestions: &[Suggestion],
+    ids: &[usize],
+) -> Result<()> {
+    if let Some(id) = ids
+        .iter()
+        .find(|id| !suggestions.iter().any(|s| s.id == **id))
+    {
+        bail!(
+            "No suggestion #{}. Run 'proj review' to see the current suggestions.",
+            id
+        );
+    }
+
+    let branch = track_branch(conn, session.session_id)?;
+    let branch = branch.as_deref();
+    let author = resolve_author(None);
+    for s in suggestions.iter().filter(|s| ids.contains(&s.id)) {
+        match s.kind {
+            "decision" => {
+                let topic =
+                    &decision::resolve_topic(conn, s.topic.as_deref().unwrap_or("general"))?;
+                conn.execute(
+                    "INSERT INTO decisions (session_id, topic, decision, status, branch, author) VALUES (?1, ?2, ?3, 'active', ?4, ?5)",
+                    rusqlite::params![session.session_id, topic, s.text, branch, author],
+                )?;
+                let id = conn.last_insert_rowid();
+                let summary = format!("Decision: {} - {}", topic, truncate(&s.text, 50));
+                insert_activity_log(conn, session.session_id, "decision", id, &summary)?;
+                println!("{} Logged decision #{}: {}", "✓".green(), id, topic);
+            }
+            "task" => {
+                conn.execute(
+                    "INSERT INTO tasks (session_id, description, status, priority, branch, author) VALUES (?1, ?2, 'pending', 'normal', ?3, ?4)",
+                    rusqlite::params![session.session_id, s.text, branch, author],
+                )?;
+                let id = conn.last_insert_rowid();
+                let summary = format!("Task added: {}", truncate(&s.text, 50));
+                insert_activity_log(conn, session.session_id, "task_update", id, &summary)?;
+                println!(
+                    "{} Added task #{}: {}",
+                    "✓".green(),
+                    id,
+                    truncate(&s.text, 50)
+                );
+            }
+            _ => {
+                conn.execute(
+                    "INSERT INTO blockers (session_id, description, status, branch, author) VALUES (?1, ?2, 'active', ?3, ?4)",
+                    rusqlite::params![session.session_id, s.text, branch, author],
+                )?;
+                let id = conn.last_insert_rowid();
+                let summary = format!("Blocker: {}", truncate(&s.text, 60));
+                insert_activity_log(conn, session.session_id, "blocker", id, &summary)?;
+                println!(
+                    "{} Logged blocker #{}: {}",
+                    "✗".red(),
+                    id,
+                    truncate(&s.text, 50)
+                );
+                webhooks::notify(
+                    webhooks::BLOCKER_ADDED,
+                    &format!("Blocker #{}: {}", id, s.text),
+                    serde_json::json!({
+                        "blocker_id": id,
+                        "uuid": record_uuid(conn, "blockers", "blocker_id", id),
+                        "description": s.text,
+                        "task_id": null,
+                    }),
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+fn insert_activity_log(
+    conn: &Connection,
+    session_id: i64,
+    action_type: &str,
+    action_id: i64,
+    summary: &str,
+) -> Result<()> {
+    conn.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, ?2, ?3, ?4)",
+        rusqlite::params![session_id, action_type, action_id, summary],
+    )?;
+    Ok(())
+}
+
+fn describe(s: &Suggestion) -> String {
+    match &s.topic {
+        Some(topic) => format!("{}: {}", topic, s.text),
+        None => s.text.clone(),
+    }
+}
+
+/// Scan the diff and commits since the session started for unlogged items
+fn collect_suggestions(
+    conn: &Connection,
+    root: &Path,
+    session: &Session,
+    commits: &[GitCommit],
+) -> Result<Vec<Suggestion>> {
+    if !git::is_repo(root) {
+        return Ok(Vec::new());
+    }
+
+    let session_start = session.started_at.format("%Y-%m-%d %H:%M:%S").to_string();
+    let base = git::commit_before(root, &session_start);
+    let diff = git::diff_since(root, base.as_deref()).unwrap_or_default();
+
+    // Already-logged text, lowercased, to avoid suggesting duplicates
+    let logged_decisions = logged_text(
+        conn,
+        "SELECT topic || ' ' || decision FROM decisions WHERE session_id = ?1",
+        [session.session_id],
+    )?;
+    let logged_tasks = logged_text(conn, "SELECT description FROM tasks", [])?;
+    let logged_blockers = logged_text(conn, "SELECT description FROM blockers", [])?;
+
+    let mut found: Vec<(&'static str, Option<String>, String, String)> = Vec::new();
+
+    // Dependencies added, removed, or swapped in a manifest
+    for path in changed_files(&diff) {
+        let name = path.rsplit('/').next().unwrap_or(&path);
+        if !is_manifest(name) {
+            continue;
+        }
+        let old = base
+            .as_deref()
+            .and_then(|rev| git::file_at(root, rev, &path))
+            .unwrap_or_default();
+        let new = std::fs::read_to_string(root.join(&path)).unwrap_or_default();
+        let old_deps = parse_dependencies(name, &old);
+        let new_deps = parse_dependencies(name, &new);
+        let removed: Vec<&String> = old_deps.difference(&new_deps).collect();
+        let added: Vec<&String> = new_deps.difference(&old_deps).collect();
+
+        let mut changes = Vec::new();
+        if removed.len() == 1 && added.len() == 1 {
+            changes.push((
+                format!("Switched dependency {} → {}", removed[0], added[0]),
+                vec![removed[0], added[0]],
+            ));
+        } else {
+            for dep in &added {
+                changes.push((format!("Added dependency {}", dep), vec![*dep]));
+            }
+            for dep in &removed {
+                changes.push((format!("Removed dependency {}", dep), vec![*dep]));
+            }
+        }
+        for (text, deps) in changes {
+            let already = deps.iter().all(|d| {
+                let d = d.to_lowercase();
+                logged_decisions.iter().any(|l| l.contains(&d))
+            });
+            if !already {
+                found.push((
+                    "decision",
+                    Some("dependencies".to_string()),
+                    text,
+                    path.clone(),
+                ));
+            }
+        }
+    }
+
+    // TODO/FIXME comments and disabled tests added in the diff
+    let mut file = String::new();
+    let mut disabled_tests = BTreeSet::new();
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("+++ ") {
+            file = path.strip_prefix("b/").unwrap_or(path).to_string();
+            continue;
+        }
+        if file.starts_with(".tracking/") {
+            continue;
+        }
+        let Some(added) = line.strip_prefix('+') else {
+            continue;
+        };
+
+        if let Some(todo) = todo_text(added) {
+            let lower = todo.to_lowercase();
+            let already = logged_tasks.iter().any(|l| l.contains(&lower))
+                || found.iter().any(|f| f.0 == "task" && f.2 == todo);
+            if !already {
+                found.push(("task", None, todo, file.clone()));
+            }
+        }
+        if is_disabled_test(added) {
+            disabled_tests.insert(file.clone());
+        }
+    }
+    for path in disabled_tests {
+        let text = format!("Test disabled in {}", path);
+        if !logged_blockers
+            .iter()
+            .any(|l| l.contains(&text.to_lowercase()))
+        {
+            found.push(("blocker", None, text, path));
+        }
+    }
+
+    // Reverts and workarounds in commit messages
+    for commit in commits {
+        let lower = commit.message.to_lowercase();
+        let signals = [
+            "revert",
+            "workaround",
+            "work around",
+            "blocked",
+            "temporarily disable",
+        ];
+        if signals.iter().any(|s| lower.contains(s))
+            && !logged_blockers.iter().any(|l| l.contains(&lower))
+        {
+            found.push((
+                "blocker",
+                None,
+                commit.message.clone(),
+                format!("commit {}", commit.short_hash),
+            ));
+        }
+    }
+
+    Ok(found
+        .into_iter()
+        .enumerate()
+        .map(|(i, (kind, topic, text, source))| {
+            let command = match kind {
+                "decision" => format!(
+                    "proj log decision {} {}",
+                    shell_quote(topic.as_deref().unwrap_or("general")),
+                    shell_quote(&text)
+                ),
+                "task" => format!("proj task add {}", shell_quote(&text)),
+                _ => format!("proj log blocker {}", shell_quote(&text)),
+            };
+            Suggestion {
+                id: i + 1,
+                kind,
+                topic,
+                text,
+                source,
+                command,
+            }
+        })
+        .collect())
+}
+
+fn logged_text(conn: &Connection, sql: &str, params: impl rusqlite::Params) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt
+        .query_map(params, |row| row.get::<_, String>(0))?
+        .filter_map(|r| r.ok())
+        .map(|s| s.to_lowercase())
+        .collect();
+    Ok(rows)
+}
+
+/// Files touched by a diff (new paths; deleted files keep their old path)
+fn changed_files(diff: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut old_path = None;
+    for line in diff.lines() {
+        if let Some(path) = line.strip_prefix("--- ") {
+            old_path = path.strip_prefix("a/").map(str::to_string);
+        } else if let Some(path) = line.strip_prefix("+++ ") {
+            let path = path
+                .strip_prefix("b/")
+                .map(str::to_string)
+                .or(old_path.take());
+            if let Some(path) = path {
+                files.push(path);
+            }
+        }
+    }
+    files
+}
+
+fn is_manifest(name: &str) -> bool {
+    matches!(name, "Cargo.toml" | "package.json" | "go.mod")
+        || (name.starts_with("requirements") && name.ends_with(".txt"))
+}
+
+/// Dependency names declared in a manifest
+fn parse_dependencies(name: &str, content: &str) -> BTreeSet<String> {
+    let mut deps = BTreeSet::new();
+    match name {
+        "Cargo.toml" => {
+            let mut in_deps = false;
+            for line in content.lines().map(str::trim) {
+                if let Some(section) = line.strip_prefix('[') {
+                    let section = section.trim_end_matches(']');
+                    in_deps = section.ends_with("dependencies");
+                    // [dependencies.foo] tables
+                    if let Some((table, dep)) = section.rsplit_once('.') {
+                        if table.ends_with("dependencies") {
+                            deps.insert(dep.trim_matches('"').to_string());
+                        }
+                    }
+                    continue;
+                }
+                if in_deps && !line.starts_with('#') {
+                    if let Some((dep, _)) = line.split_once('=') {
+                        deps.insert(dep.trim().trim_matches('"').to_string());
+                    }
+                }
+            }
+        }
+        "package.json" => {
+            if let Ok(json) = serde_json::from_str::<serde_json::Value>(content) {
+                for key in [
+                    "dependencies",
+                    "devDependencies",
+                    "peerDependencies",
+                    "optionalDependencies",
+                ] {
+                    if let Some(map) = json.get(key).and_then(|v| v.as_object()) {
+                        deps.extend(map.keys().cloned());
+                    }
+                }
+            }
+        }
+        "go.mod" => {
+            let mut in_block = false;
+            for line in content.lines().map(str::trim) {
+                let module = if in_block {
+                    if line == ")" {
+                        in_block = false;
+                        continue;
+                    }
+                    line
+                } else if line == "require (" {
+                    in_block = true;
+                    continue;
+                } else if let Some(rest) = line.strip_prefix("require ") {
+                    rest
+                } else {
+                    continue;
+                };
+                if let Some(module) = module.split_whitespace().next() {
+                    if !module.starts_with("//") {
+                        deps.insert(module.to_string());
+                    }
+                }
+            }
+        }
+        _ => {
+            // requirements*.txt
+            for line in content.lines().map(str::trim) {
+                if line.is_empty() || line.starts_with('#') || line.starts_with('-') {
+                    continue;
+                }
+                let dep: String = line
+                    .chars()
+                    .take_while(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+                    .collect();
+                if !dep.is_empty() {
+                    deps.insert(dep.to_lowercase());
+                }
+            }
+        }
+    }
+    deps
+}
+
+/// Text of a TODO/FIXME comment on an added line
+fn todo_text(line: &str) -> Option<String> {
+    let (pos, marker) = ["TODO", "FIXME"]
+        .iter()
+        .filter_map(|m| line.find(m).map(|p| (p, *m)))
+        .min()?;
+
+    // Only whole-word markers ("TODOS" or "MYTODO" don't count)
+    let before = line[..pos].chars().next_back();
+    let rest = &line[pos + marker.len()..];
+    if before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+        || rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')
+    {
+        return None;
+    }
+
+    // Skip "TODO(name):" attribution and the separator
+    let rest = match rest.strip_prefix('(') {
+        Some(r) => r.split_once(')').map(|(_, r)| r).unwrap_or(r),
+        None => rest,
+    };
+    let text = rest
+        .trim_start_matches([':', '-', ' ', '\t'])
+        .trim_end_matches(['*', '/', '>', '-', '#', ' '])
+        .trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some(text.to_string())
+}
+
+fn is_disabled_test(line: &str) -> bool {
+    let line = line.trim();
+    line.starts_with("#[ignore")
+        || line.starts_with("@pytest.mark.skip")
+        || line.starts_with("@unittest.skip")
+        || ["it.skip(", "test.skip(", "describe.skip(", "xit("]
+            .iter()
+            .any(|p| line.starts_with(p))
+}
+
+/// Double-quote a string for the suggested shell command
+fn shell_quote(s: &str) -> String {
+    let mut out = String::from("\"");
+    for c in s.chars() {
+        if matches!(c, '"' | '\\' | '$' | '`') {
+            out.push('\\');
+        }
+        out.push(c);
     }
+    out.push('"');
+    out
 }
@@ -1,4 +1,4 @@
-// Rollback command - undo a release or restore schema from backup
+// Rollback command - undo a release, run schema migrations down, or restore schema from backup
 
 use std::path::{Path, PathBuf};
 use std::process::Command;
@@ -7,22 +7,168 @@ use anyhow::{bail, Context, Result};
 use chrono::{DateTime, Utc};
 use colored::Colorize;
 use dialoguer::{Confirm, Select};
-
-use crate::paths::{get_global_dir, get_project_root};
-
-pub fn run(version: Option<String>, schema: bool, list: bool) -> Result<()> {
+use sha2::{Digest, Sha256};
+
+use crate::commands::upgrade::{apply_rollback, migration_history, print_sql, rollback_plan};
+use crate::commands::{backup_verify, trash};
+use crate::database::{get_schema_version, open_database};
+use crate::error::ProjError;
+use crate::paths::{
+    get_config_path, get_global_dir, get_project_root, get_tracking_db_path, same_path,
+};
+use crate::SCHEMA_VERSION;
+
+pub fn run(
+    version: Option<String>,
+    schema: bool,
+    list: bool,
+    backup: bool,
+    info: bool,
+    dry_run: bool,
+) -> Result<()> {
     if list {
+        list_applied_migrations()?;
         return list_schema_backups();
     }
 
     if schema {
-        return restore_schema_backup();
+        if backup {
+            return restore_schema_backup(dry_run);
+        }
+        return rollback_schema_migrations(version, info || dry_run);
     }
 
     // Default: release rollback
     release_rollback(version)
 }
 
+// ============================================================================
+// Schema Migrations
+// ============================================================================
+
+/// Undo schema migrations with their down SQL, back to `version` or, by default,
+/// to where the most recent `proj upgrade` started
+fn rollback_schema_migrations(version: Option<String>, info_mode: bool) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let config_path = get_config_path()?;
+    let conn = open_database(&db_path)?;
+    let current = get_schema_version(&conn)?.unwrap_or_else(|| "1.0".to_string());
+    let history = migration_history(&conn)?;
+    drop(conn);
+
+    let target = match version {
+        Some(v) => v.trim_start_matches('v').to_string(),
+        None => {
+            let last_batch = history.last().map(|m| m.batch);
+            match history.iter().find(|m| Some(m.batch) == last_batch) {
+                Some(first) => first.from_version.clone(),
+                None => {
+                    return Err(ProjError::InvalidInput(
+                        "No recorded upgrades to undo. Name a version (e.g. 'proj rollback --schema 1.14') or restore a backup with --backup".to_string(),
+                    )
+                    .into())
+                }
+            }
+        }
+    };
+    let plan = rollback_plan(&current, &target)?;
+
+    println!("{}", "Schema Rollback".bold());
+    println!();
+    println!("Roll back: v{} → v{}", current, target);
+    println!();
+    println!("Changes to undo:");
+    for migration in &plan {
+        for step in migration.steps.iter().rev().filter(|s| !s.down.is_empty()) {
+            println!("  {} {}", "-".red(), step.description);
+        }
+    }
+
+    if info_mode {
+        println!();
+        println!("SQL plan:");
+        for migration in &plan {
+            println!();
+            println!(
+                "{}",
+                format!("-- v{} → v{}", migration.to_version, migration.from_version).bold()
+            );
+            for step in migration.steps.iter().rev().filter(|s| !s.down.is_empty()) {
+                println!("{}", format!("-- Undo: {}", step.description).dimmed());
+                print_sql(step.down);
+            }
+        }
+        println!("\n[DRY-RUN] No changes made.");
+        return Ok(());
+    }
+
+    println!();
+    println!(
+        "{}",
+        "Warning: data in the dropped columns and tables will be lost!".red()
+    );
+    println!();
+
+    if !Confirm::new()
+        .with_prompt("Are you sure you want to roll back the schema?")
+        .default(false)
+        .interact()?
+    {
+        println!("Cancelled.");
+        return Ok(());
+    }
+
+    apply_rollback(&db_path, &config_path, &target)?;
+
+    println!();
+    println!("{} Schema rolled back to v{}", "✓".green(), target);
+    println!();
+    println!(
+        "{}",
+        format!(
+            "This proj expects v{}; use a release that matches v{}, or run 'proj upgrade' to migrate forward again.",
+            SCHEMA_VERSION, target
+        )
+        .dimmed()
+    );
+
+    Ok(())
+}
+
+/// List the migrations recorded for the current project, if in one
+fn list_applied_migrations() -> Result<()> {
+    let db_path = match get_tracking_db_path() {
+        Ok(path) if path.exists() => path,
+        _ => return Ok(()),
+    };
+    let conn = open_database(&db_path)?;
+    let current = get_schema_version(&conn)?.unwrap_or_else(|| "1.0".to_string());
+    let history = migration_history(&conn)?;
+    if history.is_empty() {
+        return Ok(());
+    }
+
+    println!("{} (schema v{})", "Applied Migrations".bold(), current);
+    println!();
+    for migration in &history {
+        println!(
+            "  {} v{} → v{}  {}",
+            format!("[{}]", migration.batch).dimmed(),
+            migration.from_version,
+            migration.to_version,
+            migration.applied_at
+        );
+    }
+    println!();
+    println!(
+        "To undo the last upgrade: {}",
+        "proj rollback --schema".cyan()
+    );
+    println!();
+
+    Ok(())
+}
+
 // ============================================================================
 // Schema Backup/Restore
 // ============================================================================
@@ -33,29 +179,60 @@ fn get_backups_dir() -> Result<PathBuf> {
     Ok(global_dir.join("backups"))
 }
 
-/// Get the project backup directory name
-fn backup_dir_name(project_name: &str) -> String {
+/// Resolve symlinks and relative segments so each project has one backup key
+fn canonical_project_path(project_root: &Path) -> PathBuf {
+    project_root
+        .canonicalize()
+        .unwrap_or_else(|_| project_root.to_path_buf())
+}
+
+/// Get the project backup directory name. The path hash keeps projects that
+/// share a name from writing into (or clearing out) each other's backups.
+fn backup_dir_name(project_name: &str, project_path: &Path) -> String {
+    let digest = Sha256::digest(project_path.to_string_lossy().as_bytes());
+    let hash: String = digest[..4].iter().map(|b| format!("{:02x}", b)).collect();
     let timestamp = Utc::now().format("%Y%m%d_%H%M%S");
-    format!("{}-{}", project_name, timestamp)
+    format!("{}-{}-{}", project_name, hash, timestamp)
+}
+
+/// Whether a backup was taken from the project at `project_path`
+fn is_backup_of(metadata: &BackupMetadata, project_path: &Path) -> bool {
+    same_path(
+        &canonical_project_path(Path::new(&metadata.project_path)),
+        project_path,
+    )
 }
 
 /// Create a backup of the current project's .tracking directory
 /// Only keeps the most recent backup per project (deletes older ones)
 pub fn create_backup(project_name: &str) -> Result<PathBuf> {
-    let project_root = get_project_root()?;
+    create_backup_at(&get_project_root()?, project_name)
+}
+
+/// Create a backup of the .tracking directory of the project at `project_root`
+pub fn create_backup_at(project_root: &Path, project_name: &str) -> Result<PathBuf> {
+    create_backup_in(&get_backups_dir()?, project_root, project_name)
+}
+
+/// Create a backup of the project at `project_root` under `backups_dir`
+fn create_backup_in(
+    backups_dir: &Path,
+    project_root: &Path,
+    project_name: &str,
+) -> Result<PathBuf> {
     let tracking_path = project_root.join(".tracking");
 
     if !tracking_path.exists() {
         bail!("No .tracking directory found in current project");
     }
 
-    let backups_dir = get_backups_dir()?;
-    std::fs::create_dir_all(&backups_dir)?;
+    std::fs::create_dir_all(backups_dir)?;
+    let project_path = canonical_project_path(project_root);
 
     // Delete existing backups for this project (keep only 1)
-    delete_old_backups_for_project(&backups_dir, project_name)?;
+    delete_old_backups_for_project(backups_dir, &project_path)?;
 
-    let backup_name = backup_dir_name(project_name);
+    let backup_name = backup_dir_name(project_name, &project_path);
     let backup_path = backups_dir.join(&backup_name);
 
     // Copy the entire .tracking directory
@@ -65,7 +242,7 @@ pub fn create_backup(project_name: &str) -> Result<PathBuf> {
     // Write a metadata file
     let metadata = BackupMetadata {
         project_name: project_name.to_string(),
-        project_path: project_root.to_string_lossy().to_string(),
+        project_path: project_path.to_string_lossy().to_string(),
         created_at: Utc::now(),
         schema_version: get_current_schema_version(&tracking_path)?,
     };
@@ -98,7 +275,7 @@ fn get_current_schema_version(tracking_path: &Path) -> Result<String> {
 }
 
 /// Delete old backups for a project (keep only the newest)
-fn delete_old_backups_for_project(backups_dir: &Path, project_name: &str) -> Result<()> {
+fn delete_old_backups_for_project(backups_dir: &Path, project_path: &Path) -> Result<()> {
     if !backups_dir.exists() {
         return Ok(());
     }
@@ -111,7 +288,7 @@ fn delete_old_backups_for_project(backups_dir: &Path, project_name: &str) -> Res
             if metadata_path.exists() {
                 if let Ok(content) = std::fs::read_to_string(&metadata_path) {
                     if let Ok(metadata) = serde_json::from_str::<BackupMetadata>(&content) {
-                        if metadata.project_name == project_name {
+                        if is_backup_of(&metadata, project_path) {
                             // This is an old backup for the same project - delete it
                             let _ = std::fs::remove_dir_all(&path);
                         }
@@ -125,7 +302,7 @@ fn delete_old_backups_for_project(backups_dir: &Path, project_name: &str) -> Res
 }
 
 /// Copy a directory recursively
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+pub fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
     std::fs::create_dir_all(dst)?;
 
     for entry in std::fs::read_dir(src)? {
@@ -195,17 +372,20 @@ fn list_schema_backups() -> Result<()> {
     }
 
     // Sort by date, newest first
-    backups.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at));
+    backups.sort_by_key(|b| std::cmp::Reverse(b.1.created_at));
 
     // Filter to current project if in a project
     if let Some(ref project_name) = current_project_name {
         println!("Backups for current project ({}):", project_name.cyan());
         println!();
 
-        let project_backups: Vec<_> = backups
-            .iter()
-            .filter(|(_, m)| &m.project_name == project_name)
-            .collect();
+        let project_path = project_root.as_deref().map(canonical_project_path);
+        let is_current = |m: &BackupMetadata| {
+            project_path
+                .as_deref()
+                .is_some_and(|path| is_backup_of(m, path))
+        };
+        let project_backups: Vec<_> = backups.iter().filter(|(_, m)| is_current(m)).collect();
 
         if project_backups.is_empty() {
             println!("  No backups found for this project.");
@@ -225,10 +405,7 @@ fn list_schema_backups() -> Result<()> {
         }
 
         // Also show other projects
-        let other_backups: Vec<_> = backups
-            .iter()
-            .filter(|(_, m)| &m.project_name != project_name)
-            .collect();
+        let other_backups: Vec<_> = backups.iter().filter(|(_, m)| !is_current(m)).collect();
 
         if !other_backups.is_empty() {
             println!();
@@ -265,13 +442,14 @@ fn list_schema_backups() -> Result<()> {
     }
 
     println!();
-    println!("To restore: {}", "proj rollback --schema".cyan());
+    println!("To restore: {}", "proj rollback --schema --backup".cyan());
 
     Ok(())
 }
 
-/// Restore schema from a backup
-fn restore_schema_backup() -> Result<()> {
+/// Restore schema from a backup; with `dry_run`, only show what would change
+/// (from the newest backup)
+fn restore_schema_backup(dry_run: bool) -> Result<()> {
     let project_root = get_project_root()?;
     let tracking_path = project_root.join(".tracking");
 
@@ -290,6 +468,7 @@ fn restore_schema_backup() -> Result<()> {
     };
 
     // Find backups for this project
+    let project_path = canonical_project_path(&project_root);
     let backups_dir = get_backups_dir()?;
     if !backups_dir.exists() {
         bail!("No backups found. Backups are created automatically before schema upgrades.");
@@ -305,7 +484,7 @@ fn restore_schema_backup() -> Result<()> {
             if metadata_path.exists() {
                 if let Ok(content) = std::fs::read_to_string(&metadata_path) {
                     if let Ok(metadata) = serde_json::from_str::<BackupMetadata>(&content) {
-                        if metadata.project_name == project_name {
+                        if is_backup_of(&metadata, &project_path) {
                             backups.push((path, metadata));
                         }
                     }
@@ -322,7 +501,7 @@ fn restore_schema_backup() -> Result<()> {
     }
 
     // Sort by date, newest first
-    backups.sort_by(|a, b| b.1.created_at.cmp(&a.1.created_at));
+    backups.sort_by_key(|b| std::cmp::Reverse(b.1.created_at));
 
     // Let user select which backup to restore
     let options: Vec<String> = backups
@@ -340,11 +519,16 @@ fn restore_schema_backup() -> Result<()> {
     println!("{}", "Schema Rollback".bold());
     println!();
 
-    let selection = Select::new()
-        .with_prompt("Select backup to restore")
-        .items(&options)
-        .default(0)
-        .interact()?;
+    let selection = if dry_run {
+        println!("Backup: {}", options[0]);
+        0
+    } else {
+        Select::new()
+            .with_prompt("Select backup to restore")
+            .items(&options)
+            .default(0)
+            .interact()?
+    };
 
     let (backup_path, backup_metadata) = &backups[selection];
 
@@ -358,7 +542,20 @@ fn restore_schema_backup() -> Result<()> {
         backup_metadata.schema_version.yellow()
     );
     println!();
-    println!("{}", "Warning: Current .tracking/ data will be lost!".red());
+    backup_verify::print_restore_changes(
+        &tracking_path.join("tracking.db"),
+        &backup_path.join("tracking.db"),
+    )?;
+    println!();
+
+    if dry_run {
+        println!("[DRY-RUN] No changes made.");
+        return Ok(());
+    }
+    println!(
+        "The current .tracking/ is moved to the trash ({}).",
+        "proj trash list".cyan()
+    );
     println!();
 
     if !Confirm::new()
@@ -370,15 +567,16 @@ fn restore_schema_backup() -> Result<()> {
         return Ok(());
     }
 
-    // Remove current .tracking
-    if tracking_path.exists() {
-        std::fs::remove_dir_all(&tracking_path)
-            .with_context(|| "Failed to remove current .tracking directory")?;
-    }
+    // Set the current .tracking aside
+    let trashed = trash::move_to_trash(&project_root, &project_name, "rollback")?;
 
     // Copy backup to .tracking
-    copy_dir_recursive(backup_path, &tracking_path)
-        .with_context(|| "Failed to restore from backup")?;
+    copy_dir_recursive(backup_path, &tracking_path).with_context(|| {
+        format!(
+            "Failed to restore from backup. Get the previous .tracking/ back with 'proj trash restore {}'",
+            trashed
+        )
+    })?;
 
     // Remove the backup metadata file from the restored directory
     let restored_metadata = tracking_path.join("backup_metadata.json");
@@ -392,6 +590,14 @@ fn restore_schema_backup() -> Result<()> {
         "✓".green(),
         backup_metadata.schema_version
     );
+    println!(
+        "{}",
+        format!(
+            "The previous .tracking/ is in the trash as {} ('proj trash restore {}' undoes this).",
+            trashed, trashed
+        )
+        .dimmed()
+    );
 
     Ok(())
 }
@@ -509,3 +715,43 @@ fn run_command(cmd: &str, args: &[&str]) -> Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(root: &Path) -> PathBuf {
+        let project = root.join("app");
+        std::fs::create_dir_all(project.join(".tracking")).unwrap();
+        std::fs::write(
+            project.join(".tracking").join("config.json"),
+            r#"{"name": "app", "schema_version": "1.0"}"#,
+        )
+        .unwrap();
+        project
+    }
+
+    #[test]
+    fn same_name_projects_keep_their_own_backups() {
+        let dir = tempfile::tempdir().unwrap();
+        let backups_dir = dir.path().join("backups");
+        let first = project(&dir.path().join("one"));
+        let second = project(&dir.path().join("two"));
+
+        let first_backup = create_backup_in(&backups_dir, &first, "app").unwrap();
+        let second_backup = create_backup_in(&backups_dir, &second, "app").unwrap();
+        assert_ne!(first_backup, second_backup);
+        assert!(first_backup.join("config.json").exists());
+        assert!(second_backup.join("config.json").exists());
+
+        // A new backup replaces only the same project's older one
+        let first_backup = create_backup_in(&backups_dir, &first, "app").unwrap();
+        let remaining: Vec<PathBuf> = std::fs::read_dir(&backups_dir)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .collect();
+        assert_eq!(remaining.len(), 2);
+        assert!(remaining.contains(&first_backup));
+        assert!(remaining.contains(&second_backup));
+    }
+}
@@ -0,0 +1,424 @@
+// Scan TODOs command - keep tasks in sync with TODO/FIXME/HACK comments
+//
+// Each comment becomes a task with source = 'source-todo' and source_ref =
+// "path:line". Tasks are matched to comments by file and text, so moving a
+// comment only updates its location; removing it completes the task.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::database::{open_database, record_uuid};
+use crate::diagnostics;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::projignore::ProjIgnore;
+use crate::session::{get_or_create_session_with_info, track_branch};
+use crate::tags::{self, Tagged};
+use crate::text::truncate;
+use crate::webhooks;
+
+/// Source (and tag) of tasks created from comments
+pub const SOURCE_TODO: &str = "source-todo";
+
+/// Files larger than this are skipped (generated or vendored code)
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Directories never scanned when walking without git
+const SKIP_DIRS: &[&str] = &[
+    ".git",
+    ".tracking",
+    "target",
+    "node_modules",
+    "vendor",
+    "dist",
+];
+
+/// A TODO-style comment found in the source tree
+struct TodoComment {
+    path: String,
+    line: usize,
+    marker: &'static str,
+    text: String,
+}
+
+/// Changes made (or that would be made) by a scan
+#[derive(Default)]
+pub struct ScanResult {
+    pub comments: usize,
+    /// (task_id, priority, description, location)
+    pub created: Vec<(i64, &'static str, String, String)>,
+    /// (task_id, new location)
+    pub moved: Vec<(i64, String)>,
+    /// (task_id, description)
+    pub closed: Vec<(i64, String)>,
+}
+
+impl ScanResult {
+    pub fn has_changes(&self) -> bool {
+        !self.created.is_empty() || !self.moved.is_empty() || !self.closed.is_empty()
+    }
+}
+
+pub fn run(dry_run: bool) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    let root = get_project_root()?;
+
+    let session = get_or_create_session_with_info(&conn)?.session;
+    let branch = track_branch(&conn, session.session_id)?;
+
+    let result = scan(&conn, &root, session.session_id, branch.as_deref(), dry_run)?;
+
+    println!(
+        "Found {} TODO/FIXME/HACK comment{}",
+        result.comments,
+        if result.comments == 1 { "" } else { "s" }
+    );
+    if !result.has_changes() {
+        println!("{} Tasks already match the source comments.", "✓".green());
+        return Ok(());
+    }
+    println!();
+
+    for (task_id, priority, description, location) in &result.created {
+        let id = if dry_run {
+            "new".to_string()
+        } else {
+            format!("#{}", task_id)
+        };
+        let priority = if *priority == "high" {
+            format!(" [{}]", "high".yellow())
+        } else {
+            String::new()
+        };
+        println!(
+            "  {} {}{} {} {}",
+            "+".green(),
+            id,
+            priority,
+            truncate(description, 60),
+            format!("({})", location).dimmed()
+        );
+    }
+    for (task_id, location) in &result.moved {
+        println!("  {} #{} moved to {}", "~".yellow(), task_id, location);
+    }
+    for (task_id, description) in &result.closed {
+        println!(
+            "  {} #{} {} {}",
+            "✓".green(),
+            task_id,
+            truncate(description, 60),
+            "(comment removed)".dimmed()
+        );
+    }
+
+    println!();
+    let (create, update, close) = if dry_run {
+        ("Would create", "update", "close")
+    } else {
+        ("Created", "updated", "closed")
+    };
+    println!(
+        "{} {}, {} {}, {} {} task(s).",
+        create,
+        result.created.len(),
+        update,
+        result.moved.len(),
+        close,
+        result.closed.len()
+    );
+    if dry_run {
+        println!("{}", "Dry run - no changes made.".dimmed());
+    }
+    Ok(())
+}
+
+/// Scan the project and create, move, or close source-todo tasks
+pub fn scan(
+    conn: &Connection,
+    root: &Path,
+    session_id: i64,
+    branch: Option<&str>,
+    dry_run: bool,
+) -> Result<ScanResult> {
+    let comments = find_comments(root);
+
+    // Existing source-todo tasks: (task_id, description, status, source_ref)
+    let mut stmt = conn.prepare(
+        "SELECT task_id, description, status, source_ref FROM tasks WHERE source = ?1 ORDER BY task_id",
+    )?;
+    let existing: Vec<(i64, String, String, String)> = stmt
+        .query_map([SOURCE_TODO], |row| {
+            Ok((
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut result = ScanResult {
+        comments: comments.len(),
+        ..Default::default()
+    };
+    let mut matched = HashSet::new();
+
+    for comment in &comments {
+        let location = format!("{}:{}", comment.path, comment.line);
+        let found = existing.iter().find(|(id, description, _, source_ref)| {
+            !matched.contains(id)
+                && *description == comment.text
+                && ref_path(source_ref) == comment.path
+        });
+
+        match found {
+            Some((task_id, _, _, source_ref)) => {
+                matched.insert(*task_id);
+                if !dry_run {
+                    // Tasks scanned before tags existed pick up the tag here
+                    tags::add_tags(conn, Tagged::Task(*task_id), &[SOURCE_TODO.to_string()])?;
+                }
+                if *source_ref != location {
+                    if !dry_run {
+                        conn.execute(
+                            "UPDATE tasks SET source_ref = ?1 WHERE task_id = ?2",
+                            rusqlite::params![location, task_id],
+                        )?;
+                    }
+                    result.moved.push((*task_id, location));
+                }
+            }
+            None => {
+                let priority = if comment.marker == "FIXME" {
+                    "high"
+                } else {
+                    "normal"
+                };
+                let mut task_id = 0;
+                if !dry_run {
+                    conn.execute(
+                        "INSERT INTO tasks (session_id, description, status, priority, branch, source, source_ref) VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6)",
+                        rusqlite::params![session_id, comment.text, priority, branch, SOURCE_TODO, location],
+                    )?;
+                    task_id = conn.last_insert_rowid();
+                    tags::add_tags(conn, Tagged::Task(task_id), &[SOURCE_TODO.to_string()])?;
+                    let summary = format!("Task added: {}", truncate(&comment.text, 50));
+                    conn.execute(
+                        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
+                        rusqlite::params![session_id, task_id, summary],
+                    )?;
+                }
+                result
+                    .created
+                    .push((task_id, priority, comment.text.clone(), location));
+            }
+        }
+    }
+
+    // Open tasks whose comment is gone were done in the code
+    for (task_id, description, status, _) in &existing {
+        if matched.contains(task_id) || status == "completed" || status == "cancelled" {
+            continue;
+        }
+        if !dry_run {
+            conn.execute(
+                "UPDATE tasks SET status = 'completed', completed_at = datetime('now') WHERE task_id = ?1",
+                [task_id],
+            )?;
+            let summary = format!(
+                "Task completed: {} (comment removed)",
+                truncate(description, 40)
+            );
+            conn.execute(
+                "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
+                rusqlite::params![session_id, task_id, summary],
+            )?;
+            webhooks::notify(
+                webhooks::TASK_COMPLETED,
+                &format!(
+                    "Task #{} completed: {} (comment removed)",
+                    task_id, description
+                ),
+                serde_json::json!({
+                    "task_id": task_id,
+                    "uuid": record_uuid(conn, "tasks", "task_id", *task_id),
+                    "description": description,
+                }),
+            );
+        }
+        result.closed.push((*task_id, description.clone()));
+    }
+
+    Ok(result)
+}
+
+/// File part of a "path:line" reference
+fn ref_path(source_ref: &str) -> &str {
+    source_ref
+        .rsplit_once(':')
+        .map(|(path, _)| path)
+        .unwrap_or(source_ref)
+}
+
+/// Find TODO/FIXME/HACK comments in the project's source files
+fn find_comments(root: &Path) -> Vec<TodoComment> {
+    let ignore = ProjIgnore::load(root);
+    let mut comments = Vec::new();
+    for path in source_files(root) {
+        if ignore.is_ignored(Path::new(&path), false) {
+            continue;
+        }
+        let full = root.join(&path);
+        let size = std::fs::metadata(&full).map(|m| m.len()).unwrap_or(0);
+        if size > MAX_FILE_SIZE {
+            continue;
+        }
+        // Non-UTF-8 files are binary for our purposes
+        let Ok(content) = std::fs::read_to_string(&full) else {
+            continue;
+        };
+
+        let mut seen = HashSet::new();
+        for (i, line) in content.lines().enumerate() {
+            if let Some((marker, text)) = parse_todo(line) {
+                // Identical comments in one file would be indistinguishable; keep the first
+                if seen.insert(text.clone()) {
+                    comments.push(TodoComment {
+                        path: path.clone(),
+                        line: i + 1,
+                        marker,
+                        text,
+                    });
+                }
+            }
+        }
+    }
+    comments
+}
+
+/// Files to scan, relative to the root: git's tracked and unignored files,
+/// or a directory walk when the project isn't a git repo
+fn source_files(root: &Path) -> Vec<String> {
+    if crate::git::is_repo(root) {
+        let output = diagnostics::output(
+            Command::new("git")
+                .args([
+                    "ls-files",
+                    "-z",
+                    "--cached",
+                    "--others",
+                    "--exclude-standard",
+                ])
+                .current_dir(root),
+        );
+        if let Ok(output) = output {
+            if output.status.success() {
+                let mut nested = HashMap::new();
+                let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                    .split('\0')
+                    .filter(|f| !f.is_empty() && !f.starts_with(".tracking/"))
+                    .filter(|f| !in_nested_project(root, f, &mut nested))
+                    .map(str::to_string)
+                    .collect();
+                files.sort();
+                files.dedup();
+                return files;
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(root, root, &mut files);
+    files.sort();
+    files
+}
+
+/// Whether a file (relative to the root) belongs to a project nested below it.
+/// Caches the answer per directory.
+fn in_nested_project(root: &Path, file: &str, cache: &mut HashMap<String, bool>) -> bool {
+    let Some((parent, _)) = file.rsplit_once('/') else {
+        return false;
+    };
+    let mut dir = String::new();
+    for part in parent.split('/') {
+        if !dir.is_empty() {
+            dir.push('/');
+        }
+        dir.push_str(part);
+        let nested = *cache
+            .entry(dir.clone())
+            .or_insert_with(|| root.join(&dir).join(".tracking").is_dir());
+        if nested {
+            return true;
+        }
+    }
+    false
+}
+
+fn walk(root: &Path, dir: &Path, files: &mut Vec<String>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if path.is_dir() {
+            // A nested project (monorepo package) scans its own TODOs
+            if !SKIP_DIRS.contains(&name.as_str())
+                && !name.starts_with('.')
+                && !path.join(".tracking").is_dir()
+            {
+                walk(root, &path, files);
+            }
+        } else if let Ok(rel) = path.strip_prefix(root) {
+            files.push(rel.to_string_lossy().replace('\\', "/"));
+        }
+    }
+}
+
+/// Marker and text of a TODO-style comment, e.g. `// TODO(ann): handle retries`.
+/// The marker must directly follow a comment leader, so prose and strings
+/// that merely mention "TODO" are ignored.
+fn parse_todo(line: &str) -> Option<(&'static str, String)> {
+    const LEADERS: &[&str] = &["//", "#", "/*", "*", "--", "<!--", ";", "%"];
+
+    let (pos, marker) = ["TODO", "FIXME", "HACK"]
+        .iter()
+        .filter_map(|m| line.find(m).map(|p| (p, *m)))
+        .min()?;
+
+    let before = line[..pos].trim_end();
+    let leader = LEADERS.iter().any(|l| before.ends_with(l))
+        // "* TODO" inside a block comment, but not "x * TODO"
+        && (!before.ends_with('*') || before.ends_with("/*") || before.trim() == "*");
+    if !leader {
+        return None;
+    }
+
+    let rest = &line[pos + marker.len()..];
+    if rest.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+        return None;
+    }
+    // Skip "TODO(name):" attribution and the separator
+    let rest = match rest.strip_prefix('(') {
+        Some(r) => r.split_once(')').map(|(_, r)| r).unwrap_or(r),
+        None => rest,
+    };
+    let text = rest
+        .trim_start_matches([':', '-', ' ', '\t'])
+        .trim_end()
+        .trim_end_matches("*/")
+        .trim_end_matches("-->")
+        .trim();
+    if text.is_empty() {
+        return None;
+    }
+    Some((marker, text.to_string()))
+}
@@ -0,0 +1,321 @@
+// Search command - full-text search across the tracking database
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::config::Registry;
+use crate::database::open_database;
+use crate::paths::{get_registry_path, get_tracking_db_path};
+use crate::text::truncate;
+
+#[derive(Serialize)]
+struct SearchHit {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    project: Option<String>,
+    kind: String,
+    id: i64,
+    title: String,
+    snippet: String,
+    date: String,
+    score: f64,
+}
+
+pub fn run(query: &str, all: bool, json: bool, limit: usize) -> Result<()> {
+    if all {
+        return run_all(query, json, limit);
+    }
+
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let mut hits = search(&conn, query, limit)?;
+
+    if json {
+        for hit in &mut hits {
+            hit.snippet = hit.snippet.replace("[[", "").replace("]]", "");
+        }
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    println!("{}", format!("Search: {}", query).bold());
+    println!("{}", "=".repeat(60));
+
+    if hits.is_empty() {
+        println!();
+        println!("No results found for '{}'", query);
+        return Ok(());
+    }
+
+    println!();
+    print_hits(&hits, "");
+
+    println!();
+    println!("{} result(s)", hits.len());
+
+    Ok(())
+}
+
+/// Search every registered project, grouping results by project
+fn run_all(query: &str, json: bool, limit: usize) -> Result<()> {
+    let registry = load_registry()?;
+
+    let mut groups: Vec<(String, Vec<SearchHit>)> = Vec::new();
+    let mut skipped = Vec::new();
+
+    for p in &registry.registered_projects {
+        let db_path = Path::new(&p.path).join(".tracking").join("tracking.db");
+        if !db_path.exists() {
+            continue;
+        }
+        let hits = open_database(&db_path).and_then(|conn| {
+            if !index_has_content(&conn) {
+                anyhow::bail!("search index needs upgrade");
+            }
+            search(&conn, query, limit)
+        });
+        match hits {
+            Ok(mut hits) if !hits.is_empty() => {
+                for hit in &mut hits {
+                    hit.project = Some(p.name.clone());
+                }
+                groups.push((p.name.clone(), hits));
+            }
+            Ok(_) => {}
+            Err(_) => skipped.push(p.name.clone()),
+        }
+    }
+
+    // Projects with the strongest match first
+    groups.sort_by(|a, b| {
+        let best = |hits: &[SearchHit]| hits.first().map_or(0.0, |h| h.score);
+        best(&b.1)
+            .partial_cmp(&best(&a.1))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+
+    if json {
+        let mut hits: Vec<SearchHit> = groups.into_iter().flat_map(|(_, hits)| hits).collect();
+        for hit in &mut hits {
+            hit.snippet = hit.snippet.replace("[[", "").replace("]]", "");
+        }
+        println!("{}", serde_json::to_string_pretty(&hits)?);
+        return Ok(());
+    }
+
+    println!("{}", format!("Search (all projects): {}", query).bold());
+    println!("{}", "=".repeat(60));
+
+    if groups.is_empty() {
+        println!();
+        println!("No results found for '{}'", query);
+    }
+
+    let mut total = 0;
+    for (name, hits) in &groups {
+        println!();
+        println!("{} ({})", name.bold().underline(), hits.len());
+        print_hits(hits, "  ");
+        total += hits.len();
+    }
+
+    println!();
+    println!("{} result(s) in {} project(s)", total, groups.len());
+
+    if !skipped.is_empty() {
+        println!(
+            "{} Skipped (run 'proj upgrade --all'): {}",
+            "!".yellow(),
+            skipped.join(", ")
+        );
+    }
+
+    Ok(())
+}
+
+fn print_hits(hits: &[SearchHit], indent: &str) {
+    for hit in hits {
+        println!(
+            "{}{} #{} {} {}",
+            indent,
+            format!("[{}]", hit.kind).cyan(),
+            hit.id,
+            hit.title.bold(),
+            format!("({})", hit.date).dimmed()
+        );
+        println!("{}   {}", indent, highlight(&hit.snippet));
+    }
+}
+
+/// Older schemas (before v1.6) have a contentless index that can't return matches
+fn index_has_content(conn: &Connection) -> bool {
+    conn.query_row(
+        "SELECT sql FROM sqlite_master WHERE type = 'table' AND name = 'tracking_fts'",
+        [],
+        |row| row.get::<_, String>(0),
+    )
+    .map(|sql| !sql.contains("content=''"))
+    .unwrap_or(false)
+}
+
+/// Run an FTS query, best match first, one hit per record
+fn search(conn: &Connection, query: &str, limit: usize) -> Result<Vec<SearchHit>> {
+    let fts_query = build_fts_query(query);
+    if fts_query.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    // A record can be indexed more than once (e.g., after an edit), so over-fetch and dedupe
+    let mut stmt = conn.prepare(
+        "SELECT table_name, record_id, snippet(tracking_fts, 0, '[[', ']]', '…', 12), bm25(tracking_fts)
+         FROM tracking_fts
+         WHERE tracking_fts MATCH ?1
+         ORDER BY bm25(tracking_fts)
+         LIMIT ?2",
+    )
+    .with_context(|| "Search index unavailable. Run 'proj upgrade' to rebuild it.")?;
+
+    let rows = stmt
+        .query_map(rusqlite::params![fts_query, (limit * 4) as i64], |row| {
+            Ok((
+                row.get::<_, Option<String>>(0)?,
+                row.get::<_, Option<i64>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, f64>(3)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut seen = HashSet::new();
+    let mut hits = Vec::new();
+
+    for (table, record_id, snippet, score) in rows {
+        let (Some(table), Some(record_id)) = (table, record_id) else {
+            continue;
+        };
+        if !seen.insert((table.clone(), record_id)) {
+            continue;
+        }
+
+        // Skip records that no longer exist
+        let Some((kind, title, date)) = describe_record(conn, &table, record_id) else {
+            continue;
+        };
+
+        hits.push(SearchHit {
+            project: None,
+            kind: kind.to_string(),
+            id: record_id,
+            title,
+            snippet: snippet.unwrap_or_default(),
+            date,
+            // bm25 is negative (lower is better); flip so higher is better
+            score: -score,
+        });
+
+        if hits.len() >= limit {
+            break;
+        }
+    }
+
+    Ok(hits)
+}
+
+/// Turn free text into an FTS5 query: each word quoted, all words required
+fn build_fts_query(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|word| format!("\"{}\"", word.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Look up a display title and date for an indexed record
+fn describe_record(
+    conn: &Connection,
+    table: &str,
+    record_id: i64,
+) -> Option<(&'static str, String, String)> {
+    let (kind, sql) = match table {
+        "decisions" => (
+            "decision",
+            "SELECT topic || ': ' || decision, created_at FROM decisions WHERE decision_id = ?1",
+        ),
+        "tasks" => (
+            "task",
+            "SELECT description, created_at FROM tasks WHERE task_id = ?1",
+        ),
+        "context_notes" => (
+            "note",
+            "SELECT '[' || category || '] ' || title, created_at FROM context_notes WHERE note_id = ?1",
+        ),
+        "blockers" => (
+            "blocker",
+            "SELECT description, created_at FROM blockers WHERE blocker_id = ?1",
+        ),
+        "questions" => (
+            "question",
+            "SELECT question, created_at FROM questions WHERE question_id = ?1",
+        ),
+        "sessions" => (
+            "session",
+            "SELECT 'Session #' || session_id, started_at FROM sessions WHERE session_id = ?1",
+        ),
+        "git_commits" => (
+            "commit",
+            "SELECT short_hash || ' ' || message, committed_at FROM git_commits WHERE commit_id = ?1",
+        ),
+        _ => return None,
+    };
+
+    conn.query_row(sql, [record_id], |row| {
+        Ok((
+            kind,
+            truncate(&row.get::<_, String>(0)?, 60),
+            row.get::<_, String>(1)?,
+        ))
+    })
+    .ok()
+}
+
+/// Render [[match]] markers from snippet() as highlighted text
+fn highlight(snippet: &str) -> String {
+    let mut out = String::new();
+    let mut rest = snippet;
+    while let Some(start) = rest.find("[[") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        match after.find("]]") {
+            Some(end) => {
+                out.push_str(&after[..end].yellow().bold().to_string());
+                rest = &after[end + 2..];
+            }
+            None => {
+                rest = after;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Load the global registry
+fn load_registry() -> Result<Registry> {
+    let registry_path = get_registry_path()?;
+
+    if !registry_path.exists() {
+        return Ok(Registry::default());
+    }
+
+    let content =
+        std::fs::read_to_string(&registry_path).with_context(|| "Failed to read registry.json")?;
+    let registry: Registry =
+        serde_json::from_str(&content).with_context(|| "Failed to parse registry.json")?;
+    Ok(registry)
+}
@@ -1,72 +1,256 @@
-// Session commands - start, end, list
+// Session commands - start, pause, resume, end, list, gc
 
+use std::fmt::Write;
+use std::path::Path;
 use std::process::Command;
 
-use anyhow::{bail, Context, Result};
+use anyhow::{Context, Result};
 use colored::Colorize;
 
-use crate::cli::{SessionCommands, SessionSubcommand};
-use crate::config::ProjectConfig;
-use crate::database::open_database;
+use crate::cli::{PageArgs, SessionCommands, SessionSubcommand};
+use crate::config::{ProjectConfig, Registry};
+use crate::database::{open_database, record_uuid};
+use crate::diagnostics;
+use crate::error::ProjError;
 use crate::git;
-use crate::paths::get_tracking_db_path;
+use crate::llm;
+use crate::models::Session;
+use crate::pager;
+use crate::paths::{get_registry_path, get_tracking_db_path};
+use crate::porcelain;
 use crate::session::{
-    create_session, end_session_with_structured, get_active_session, get_recent_sessions,
+    create_session_by, end_session_with_structured, get_active_session, get_paused_sessions,
+    get_session, handle_stale_session, list_sessions, pause_session, resume_session, SessionFilter,
+    StaleOutcome, StalePolicy,
 };
+use crate::text::truncate;
+use crate::webhooks;
+
+/// Sessions listed when --limit isn't given
+const DEFAULT_LIST_LIMIT: usize = 10;
+
+const SESSION_STATUSES: [&str; 4] = ["active", "paused", "completed", "abandoned"];
 
 pub fn run(cmd: SessionCommands) -> Result<()> {
-    let db_path = get_tracking_db_path()?;
-    let conn = open_database(&db_path)
-        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    // gc works across registered projects, so it doesn't open the current one
+    let open = || -> Result<rusqlite::Connection> {
+        let db_path = get_tracking_db_path()?;
+        open_database(&db_path)
+            .with_context(|| format!("Failed to open tracking database at {:?}", db_path))
+    };
 
     match cmd.command {
-        SessionSubcommand::Start => cmd_start(&conn),
-        SessionSubcommand::End { summary, force } => cmd_end(&conn, &summary, force),
-        SessionSubcommand::List => cmd_list(&conn),
+        SessionSubcommand::Start { name, author } => {
+            cmd_start(&open()?, name.as_deref(), author.as_deref())
+        }
+        SessionSubcommand::Pause => cmd_pause(&open()?),
+        SessionSubcommand::Resume { id } => cmd_resume(&open()?, id),
+        SessionSubcommand::End {
+            summary,
+            force,
+            no_auto_commit,
+            suggest,
+            accept,
+            llm,
+        } => {
+            if llm && !suggest && !accept {
+                return Err(ProjError::InvalidInput(
+                    "--llm polishes the draft from --suggest or --accept".to_string(),
+                )
+                .into());
+            }
+            let conn = open()?;
+            if suggest {
+                return cmd_suggest(&conn, llm);
+            }
+            let summary = match summary {
+                Some(summary) => summary,
+                None => draft_summary(&conn, llm)?,
+            };
+            cmd_end(&conn, &summary, force, no_auto_commit)
+        }
+        SessionSubcommand::List {
+            status,
+            since,
+            page,
+        } => cmd_list(&open()?, status.as_deref(), since.as_deref(), &page),
+        SessionSubcommand::Gc { dry_run } => cmd_gc(dry_run),
     }
 }
 
-/// Start a new session
-fn cmd_start(conn: &rusqlite::Connection) -> Result<()> {
+/// Start a new session, or a named parallel one alongside the current session
+fn cmd_start(conn: &rusqlite::Connection, name: Option<&str>, author: Option<&str>) -> Result<()> {
+    let name = name.map(str::trim).filter(|n| !n.is_empty());
+
     // Check if there's already an active session
     if let Some(active) = get_active_session(conn)? {
-        println!(
-            "Session #{} is already active (started {})",
-            active.session_id,
-            active.started_at.format("%Y-%m-%d %H:%M")
-        );
-        return Ok(());
+        if name.is_none() {
+            return Err(ProjError::NothingToDo(format!(
+                "Session {} is already active (started {}). Use 'proj session start --name <name>' to work on something else in parallel.",
+                session_label(&active),
+                active.started_at.format("%Y-%m-%d %H:%M")
+            ))
+            .into());
+        }
+
+        // Park the current session so the new one gets its own activity
+        pause_session(conn, active.session_id)?;
+        porcelain::note(format!(
+            "{} Session {} paused",
+            "⏸".yellow(),
+            session_label(&active)
+        ));
     }
 
     // Create a new session
-    let session = create_session(conn)?;
-    println!("{} Session #{} started", "✓".green(), session.session_id);
+    let session = create_session_by(conn, name, author)?;
+    if porcelain::enabled() {
+        porcelain::print(&[&session.session_id.to_string()]);
+        return Ok(());
+    }
+    println!(
+        "{} Session {} started",
+        "✓".green(),
+        session_label(&session)
+    );
+    if name.is_some() {
+        print_paused_hint(conn)?;
+    }
+    Ok(())
+}
+
+/// Pause the active session
+fn cmd_pause(conn: &rusqlite::Connection) -> Result<()> {
+    let session = match get_active_session(conn)? {
+        Some(s) => s,
+        None => {
+            return Err(ProjError::NothingToDo("No active session to pause".to_string()).into())
+        }
+    };
+
+    pause_session(conn, session.session_id)?;
+    if porcelain::enabled() {
+        porcelain::print(&[&session.session_id.to_string()]);
+        return Ok(());
+    }
+    println!(
+        "{} Session {} paused",
+        "⏸".yellow(),
+        session_label(&session)
+    );
+    println!(
+        "  Resume with: {}",
+        format!("proj session resume {}", session.session_id).cyan()
+    );
+    Ok(())
+}
+
+/// Resume a paused session, pausing whichever session is active
+fn cmd_resume(conn: &rusqlite::Connection, session_id: i64) -> Result<()> {
+    let session = match get_session(conn, session_id)? {
+        Some(s) => s,
+        None => {
+            return Err(
+                ProjError::InvalidInput(format!("Session #{} not found", session_id)).into(),
+            )
+        }
+    };
+
+    match session.status.as_str() {
+        "paused" => {}
+        "active" => {
+            return Err(ProjError::NothingToDo(format!(
+                "Session {} is already active",
+                session_label(&session)
+            ))
+            .into());
+        }
+        other => {
+            return Err(ProjError::InvalidInput(format!(
+            "Session #{} is {} and can't be resumed. Start a new one with 'proj session start'.",
+            session_id, other
+        ))
+            .into())
+        }
+    }
+
+    if let Some(active) = get_active_session(conn)? {
+        pause_session(conn, active.session_id)?;
+        porcelain::note(format!(
+            "{} Session {} paused",
+            "⏸".yellow(),
+            session_label(&active)
+        ));
+    }
+
+    resume_session(conn, session_id)?;
+    if porcelain::enabled() {
+        porcelain::print(&[&session_id.to_string()]);
+        return Ok(());
+    }
+    println!(
+        "{} Session {} resumed",
+        "✓".green(),
+        session_label(&session)
+    );
+    Ok(())
+}
+
+/// "#12" or "#12 (hotfix)" for named sessions
+fn session_label(session: &Session) -> String {
+    match &session.name {
+        Some(name) => format!("#{} ({})", session.session_id, name),
+        None => format!("#{}", session.session_id),
+    }
+}
+
+/// Remind the user about paused sessions waiting to be resumed
+fn print_paused_hint(conn: &rusqlite::Connection) -> Result<()> {
+    let paused = get_paused_sessions(conn)?;
+    if paused.is_empty() {
+        return Ok(());
+    }
+
+    let labels: Vec<String> = paused.iter().map(session_label).collect();
+    println!(
+        "  {} Paused: {} - resume with '{}'",
+        "ℹ".blue(),
+        labels.join(", "),
+        "proj session resume <id>".cyan()
+    );
     Ok(())
 }
 
 /// End the current session with a summary
-fn cmd_end(conn: &rusqlite::Connection, summary: &str, force: bool) -> Result<()> {
+fn cmd_end(
+    conn: &rusqlite::Connection,
+    summary: &str,
+    force: bool,
+    no_auto_commit: bool,
+) -> Result<()> {
     // Get active session
     let session = match get_active_session(conn)? {
         Some(s) => s,
-        None => bail!("No active session to end"),
+        None => return Err(ProjError::NothingToDo("No active session to end".to_string()).into()),
     };
 
     // Check if any activity was logged
     let has_activity = check_session_has_activity(conn, session.session_id)?;
 
-    // Display session activity
-    display_session_activity(conn, session.session_id)?;
+    if !porcelain::enabled() {
+        // Display session activity
+        display_session_activity(conn, session.session_id)?;
 
-    // Display session review hints
-    display_session_hints(
-        conn,
-        session.session_id,
-        &session.started_at.format("%Y-%m-%d %H:%M:%S").to_string(),
-    )?;
+        // Display session review hints
+        display_session_hints(
+            conn,
+            session.session_id,
+            &session.started_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+        )?;
+    }
 
     // If no activity and not forced, show options and exit
-    if !has_activity && !force {
+    if !has_activity && !force && !porcelain::enabled() {
         println!();
         println!("{}", "─".repeat(50));
         println!();
@@ -91,8 +275,12 @@ fn cmd_end(conn: &rusqlite::Connection, summary: &str, force: bool) -> Result<()
             summary
         );
         println!();
-        println!("{}", "Session not ended. Choose an option above.".dimmed());
-        return Ok(());
+    }
+    if !has_activity && !force {
+        return Err(ProjError::InvalidInput(
+            "Session not ended: no activity was logged. Use --force to end it anyway.".to_string(),
+        )
+        .into());
     }
 
     // Build structured summary
@@ -101,19 +289,43 @@ fn cmd_end(conn: &rusqlite::Connection, summary: &str, force: bool) -> Result<()
     // End the session with structured summary
     end_session_with_structured(conn, session.session_id, summary, &structured)?;
 
-    println!(
-        "\n{} Session #{} ended. Summary: {}",
-        "✓".green(),
-        session.session_id,
-        summary
-    );
+    if porcelain::enabled() {
+        porcelain::print(&[&session.session_id.to_string()]);
+    } else {
+        println!(
+            "\n{} Session {} ended. Summary: {}",
+            "✓".green(),
+            session_label(&session),
+            summary
+        );
+        print_paused_hint(conn)?;
+    }
 
     // Handle auto-commit if enabled
-    if let Err(e) = handle_auto_commit(summary) {
-        // Don't fail the session end, just warn
-        println!("  {} Auto-commit skipped: {}", "⚠".yellow(), e);
+    if !no_auto_commit {
+        if let Err(e) = handle_auto_commit(conn, &session, summary) {
+            // Don't fail the session end, just warn
+            porcelain::note(format!("  {} Auto-commit skipped: {}", "⚠".yellow(), e));
+        }
+    }
+
+    // Push backups off-machine if configured
+    if let Err(e) = crate::commands::backup::push_after_session_end() {
+        porcelain::note(format!("  {} Remote backup skipped: {}", "⚠".yellow(), e));
     }
 
+    webhooks::notify(
+        webhooks::SESSION_END,
+        &format!("Session {} ended: {}", session_label(&session), summary),
+        serde_json::json!({
+            "session_id": session.session_id,
+            "uuid": record_uuid(conn, "sessions", "session_id", session.session_id),
+            "name": session.name,
+            "summary": summary,
+            "structured_summary": serde_json::from_str::<serde_json::Value>(&structured).ok(),
+        }),
+    );
+
     Ok(())
 }
 
@@ -287,6 +499,22 @@ fn display_session_activity(conn: &rusqlite::Connection, session_id: i64) -> Res
         }
     }
 
+    // Branch switches (don't count as logged activity)
+    let mut stmt = conn.prepare(
+        "SELECT summary FROM activity_log WHERE session_id = ? AND action_type = 'branch_switch' ORDER BY timestamp",
+    )?;
+    let switches: Vec<String> = stmt
+        .query_map([session_id], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    if !switches.is_empty() {
+        println!("\n{} Branches", "◆".cyan());
+        for summary in &switches {
+            println!("  • {}", summary);
+        }
+    }
+
     if !has_activity {
         println!(
             "\n  {} No decisions, tasks, or blockers logged this session.",
@@ -380,67 +608,64 @@ fn display_session_hints(
     Ok(())
 }
 
-/// Build a structured JSON summary of session activity
-fn build_structured_summary(
-    conn: &rusqlite::Connection,
-    session_id: i64,
-    summary: &str,
-) -> Result<String> {
-    // Gather decisions
-    let mut stmt = conn.prepare(
-        "SELECT topic, decision FROM decisions WHERE session_id = ? ORDER BY created_at",
-    )?;
-    let decisions: Vec<String> = stmt
-        .query_map([session_id], |row| {
-            Ok(format!(
-                "{}: {}",
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?
-            ))
-        })?
-        .filter_map(|r| r.ok())
-        .collect();
+/// What a session logged and committed, as recorded in its structured summary
+struct SessionActivity {
+    /// (topic, decision)
+    decisions: Vec<(String, String)>,
+    tasks_created: Vec<String>,
+    tasks_completed: Vec<String>,
+    blockers: Vec<String>,
+    /// (category, title)
+    notes: Vec<(String, String)>,
+    /// (short hash, message)
+    commits: Vec<(String, String)>,
+    files_touched: Vec<String>,
+}
 
-    // Gather tasks created
-    let mut stmt = conn.prepare(
+impl SessionActivity {
+    fn is_empty(&self) -> bool {
+        self.decisions.is_empty()
+            && self.tasks_created.is_empty()
+            && self.tasks_completed.is_empty()
+            && self.blockers.is_empty()
+            && self.notes.is_empty()
+            && self.commits.is_empty()
+    }
+}
+
+/// Gather a session's decisions, tasks, blockers, notes, commits, and files touched
+fn gather_activity(conn: &rusqlite::Connection, session_id: i64) -> Result<SessionActivity> {
+    let pairs = |sql: &str| -> Result<Vec<(String, String)>> {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt
+            .query_map([session_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    };
+    let strings = |sql: &str| -> Result<Vec<String>> {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt
+            .query_map([session_id], |row| row.get(0))?
+            .filter_map(|r| r.ok())
+            .collect();
+        Ok(rows)
+    };
+
+    let decisions =
+        pairs("SELECT topic, decision FROM decisions WHERE session_id = ? ORDER BY created_at")?;
+    let tasks_created = strings(
         "SELECT description FROM tasks WHERE session_id = ? AND status != 'completed' ORDER BY created_at",
     )?;
-    let tasks_created: Vec<String> = stmt
-        .query_map([session_id], |row| row.get(0))?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    // Gather tasks completed (any task marked completed during this session period)
-    let mut stmt = conn.prepare(
+    // Any task marked completed during this session period
+    let tasks_completed = strings(
         "SELECT description FROM tasks WHERE session_id = ? AND status = 'completed' ORDER BY created_at",
     )?;
-    let tasks_completed: Vec<String> = stmt
-        .query_map([session_id], |row| row.get(0))?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    // Gather blockers
-    let mut stmt =
-        conn.prepare("SELECT description FROM blockers WHERE session_id = ? ORDER BY created_at")?;
-    let blockers: Vec<String> = stmt
-        .query_map([session_id], |row| row.get(0))?
-        .filter_map(|r| r.ok())
-        .collect();
-
-    // Gather notes
-    let mut stmt = conn.prepare(
+    let blockers =
+        strings("SELECT description FROM blockers WHERE session_id = ? ORDER BY created_at")?;
+    let notes = pairs(
         "SELECT category, title FROM context_notes WHERE session_id = ? ORDER BY created_at",
     )?;
-    let notes: Vec<String> = stmt
-        .query_map([session_id], |row| {
-            Ok(format!(
-                "{}: {}",
-                row.get::<_, String>(0)?,
-                row.get::<_, String>(1)?
-            ))
-        })?
-        .filter_map(|r| r.ok())
-        .collect();
 
     // Get session start time for git commit query
     let started_at: String = conn.query_row(
@@ -449,31 +674,220 @@ fn build_structured_summary(
         |row| row.get(0),
     )?;
 
-    // Gather git commits since session start
-    let git_commits_data = git::get_commits_since(conn, &started_at)?;
-    let git_commits: Vec<String> = git_commits_data
-        .iter()
-        .map(|c| format!("{}: {}", c.short_hash, c.message))
+    // Pick up commits made since the last sync, then gather those since session start
+    if let Ok(root) = crate::paths::get_project_root() {
+        let _ = git::sync_recent_commits(conn, &root, 50);
+    }
+    let commits = git::get_commits_since(conn, &started_at)?
+        .into_iter()
+        .map(|c| (c.short_hash, c.message))
         .collect();
 
-    // Get files from git diff since session start
-    let files_touched = get_files_touched_since(&started_at);
+    Ok(SessionActivity {
+        decisions,
+        tasks_created,
+        tasks_completed,
+        blockers,
+        notes,
+        commits,
+        files_touched: get_files_touched_since(&started_at),
+    })
+}
+
+/// Build a structured JSON summary of session activity
+fn build_structured_summary(
+    conn: &rusqlite::Connection,
+    session_id: i64,
+    summary: &str,
+) -> Result<String> {
+    let activity = gather_activity(conn, session_id)?;
+    let joined = |items: &[(String, String)]| -> Vec<String> {
+        items.iter().map(|(a, b)| format!("{}: {}", a, b)).collect()
+    };
 
-    // Build JSON
     let structured = serde_json::json!({
         "summary": summary,
-        "decisions": decisions,
-        "tasks_created": tasks_created,
-        "tasks_completed": tasks_completed,
-        "blockers": blockers,
-        "notes": notes,
-        "git_commits": git_commits,
-        "files_touched": files_touched,
+        "decisions": joined(&activity.decisions),
+        "tasks_created": activity.tasks_created,
+        "tasks_completed": activity.tasks_completed,
+        "blockers": activity.blockers,
+        "notes": joined(&activity.notes),
+        "git_commits": joined(&activity.commits),
+        "files_touched": activity.files_touched,
     });
 
     Ok(structured.to_string())
 }
 
+/// Instructions for LLM polish of a drafted session summary
+const SUMMARY_PROMPT: &str = "You write the summary saved when a software work session ends, read \
+later by the developer and by AI assistants picking the project up. From the draft and activity \
+given, say specifically what was accomplished and decided, and what is blocked. Skip bookkeeping. \
+Reply with 1-3 plain sentences only, no preamble.";
+
+/// Print a draft summary for the active session without ending it
+fn cmd_suggest(conn: &rusqlite::Connection, use_llm: bool) -> Result<()> {
+    let draft = draft_summary(conn, use_llm)?;
+    if porcelain::enabled() {
+        porcelain::print(&[&draft]);
+        return Ok(());
+    }
+    println!("{}", "Suggested summary:".bold());
+    println!("  {}", draft);
+    println!();
+    println!("End with it:        {}", "proj session end --accept".cyan());
+    println!(
+        "Or write your own:  {}",
+        "proj session end \"<summary>\"".cyan()
+    );
+    Ok(())
+}
+
+/// Draft a summary for the active session from what it logged and committed,
+/// optionally reworded by the configured LLM
+fn draft_summary(conn: &rusqlite::Connection, use_llm: bool) -> Result<String> {
+    let session = get_active_session(conn)?
+        .ok_or_else(|| ProjError::NothingToDo("No active session to end".to_string()))?;
+    let activity = gather_activity(conn, session.session_id)?;
+    let Some(draft) = compose_draft(&activity) else {
+        return Err(ProjError::NothingToDo(
+            "Nothing logged or committed this session to draft a summary from. Pass a summary instead."
+                .to_string(),
+        )
+        .into());
+    };
+    if !use_llm {
+        return Ok(draft);
+    }
+
+    let mut prompt = format!("Draft: {}\n", draft);
+    let mut section = |label: &str, items: Vec<String>| {
+        if !items.is_empty() {
+            prompt.push_str(&format!("{}:\n- {}\n", label, items.join("\n- ")));
+        }
+    };
+    let joined = |items: &[(String, String)]| -> Vec<String> {
+        items.iter().map(|(a, b)| format!("{}: {}", a, b)).collect()
+    };
+    section("Decisions", joined(&activity.decisions));
+    section("Tasks completed", activity.tasks_completed.clone());
+    section("Tasks added", activity.tasks_created.clone());
+    section("Blockers", activity.blockers.clone());
+    section("Notes", joined(&activity.notes));
+    section(
+        "Commits",
+        activity.commits.iter().map(|(_, m)| m.clone()).collect(),
+    );
+    section("Files touched", activity.files_touched.clone());
+
+    let completion = llm::complete_or(SUMMARY_PROMPT, &prompt, || draft.clone());
+    match (&completion.model, &completion.fallback_reason) {
+        (Some(model), _) => porcelain::note(format!("  Polished by {}", model)),
+        (None, Some(reason)) => porcelain::note(format!(
+            "{} LLM unavailable ({}). Using the plain draft.",
+            "⚠".yellow(),
+            reason
+        )),
+        (None, None) => {}
+    }
+    Ok(completion.text)
+}
+
+/// Plain-text summary: completed tasks, decisions, commits, and files touched first,
+/// then blockers, new tasks, and notes. None if the session has nothing to go on.
+fn compose_draft(activity: &SessionActivity) -> Option<String> {
+    if activity.is_empty() {
+        return None;
+    }
+    let mut sentences = Vec::new();
+
+    if !activity.tasks_completed.is_empty() {
+        sentences.push(format!(
+            "Completed {}.",
+            first_few(&activity.tasks_completed, "tasks")
+        ));
+    }
+    if !activity.decisions.is_empty() {
+        let topics: Vec<String> = activity.decisions.iter().map(|(t, _)| t.clone()).collect();
+        sentences.push(format!("Decided on {}.", first_few(&topics, "topics")));
+    }
+    if !activity.commits.is_empty() {
+        let messages: Vec<String> = activity.commits.iter().map(|(_, m)| m.clone()).collect();
+        let mut sentence = format!(
+            "{} commit{} ({})",
+            activity.commits.len(),
+            if activity.commits.len() == 1 { "" } else { "s" },
+            first_few(&messages, "more")
+        );
+        // proj's own files say nothing about the work
+        let files: Vec<String> = activity
+            .files_touched
+            .iter()
+            .filter(|f| !f.starts_with(".tracking/"))
+            .cloned()
+            .collect();
+        if !files.is_empty() {
+            sentence.push_str(&format!(
+                " touching {} file{}{}",
+                files.len(),
+                if files.len() == 1 { "" } else { "s" },
+                main_dirs(&files)
+                    .map(|dirs| format!(" in {}", dirs))
+                    .unwrap_or_default()
+            ));
+        }
+        sentences.push(format!("{}.", sentence));
+    }
+    if !activity.blockers.is_empty() {
+        sentences.push(format!(
+            "Blocked on {}.",
+            first_few(&activity.blockers, "blockers")
+        ));
+    }
+    if !activity.tasks_created.is_empty() {
+        sentences.push(format!(
+            "Added {}.",
+            first_few(&activity.tasks_created, "tasks")
+        ));
+    }
+    if sentences.is_empty() {
+        let titles: Vec<String> = activity.notes.iter().map(|(_, t)| t.clone()).collect();
+        sentences.push(format!("Noted {}.", first_few(&titles, "notes")));
+    }
+    Some(sentences.join(" "))
+}
+
+/// Up to three items joined with "; ", then how many more there are
+fn first_few(items: &[String], noun: &str) -> String {
+    let mut text = items
+        .iter()
+        .take(3)
+        .map(|item| truncate(item.trim_end_matches('.'), 60))
+        .collect::<Vec<_>>()
+        .join("; ");
+    if items.len() > 3 {
+        text.push_str(&format!("; and {} {}", items.len() - 3, noun));
+    }
+    text
+}
+
+/// The (up to two) directories with the most files touched, e.g. "src/commands, docs"
+fn main_dirs(files: &[String]) -> Option<String> {
+    let mut counts: Vec<(String, usize)> = Vec::new();
+    for file in files {
+        let Some((dir, _)) = file.rsplit_once('/') else {
+            continue;
+        };
+        match counts.iter_mut().find(|(d, _)| d == dir) {
+            Some((_, n)) => *n += 1,
+            None => counts.push((dir.to_string(), 1)),
+        }
+    }
+    counts.sort_by_key(|(_, n)| std::cmp::Reverse(*n));
+    let dirs: Vec<String> = counts.into_iter().take(2).map(|(d, _)| d).collect();
+    (!dirs.is_empty()).then(|| dirs.join(", "))
+}
+
 /// Get list of files changed since a given datetime via git
 fn get_files_touched_since(since: &str) -> Vec<String> {
     let project_root = match crate::paths::get_project_root() {
@@ -481,15 +895,16 @@ fn get_files_touched_since(since: &str) -> Vec<String> {
         Err(_) => return Vec::new(),
     };
 
-    if !project_root.join(".git").exists() {
+    if !crate::git::is_repo(&project_root) {
         return Vec::new();
     }
 
     // Use git diff to find files changed
-    let output = Command::new("git")
-        .args(["diff", "--name-only", &format!("--since={}", since), "HEAD"])
-        .current_dir(&project_root)
-        .output();
+    let output = diagnostics::output(
+        Command::new("git")
+            .args(["diff", "--name-only", &format!("--since={}", since), "HEAD"])
+            .current_dir(&project_root),
+    );
 
     match output {
         Ok(o) if o.status.success() => String::from_utf8_lossy(&o.stdout)
@@ -499,15 +914,16 @@ fn get_files_touched_since(since: &str) -> Vec<String> {
             .collect(),
         _ => {
             // Fallback: try git log --name-only
-            let output = Command::new("git")
-                .args([
-                    "log",
-                    "--name-only",
-                    "--pretty=format:",
-                    &format!("--since={}", since),
-                ])
-                .current_dir(&project_root)
-                .output();
+            let output = diagnostics::output(
+                Command::new("git")
+                    .args([
+                        "log",
+                        "--name-only",
+                        "--pretty=format:",
+                        &format!("--since={}", since),
+                    ])
+                    .current_dir(&project_root),
+            );
 
             match output {
                 Ok(o) if o.status.success() => {
@@ -527,40 +943,106 @@ fn get_files_touched_since(since: &str) -> Vec<String> {
 }
 
 /// Handle auto-commit on session end
-fn handle_auto_commit(summary: &str) -> Result<()> {
+fn handle_auto_commit(conn: &rusqlite::Connection, session: &Session, summary: &str) -> Result<()> {
     let config = ProjectConfig::load()?;
 
     if !config.auto_commit {
         return Ok(());
     }
 
-    let commit_message = format!("[proj] {}", summary);
+    let started = session.started_at.format("%Y-%m-%d %H:%M:%S").to_string();
+
+    // Tasks completed and decisions logged during the session
+    let mut stmt = conn.prepare(
+        "SELECT task_id FROM tasks WHERE status = 'completed' AND completed_at >= ?1 ORDER BY task_id",
+    )?;
+    let tasks: Vec<String> = stmt
+        .query_map([&started], |row| row.get::<_, i64>(0))?
+        .filter_map(|r| r.ok())
+        .map(|id| format!("#{}", id))
+        .collect();
+    let mut stmt =
+        conn.prepare("SELECT topic FROM decisions WHERE session_id = ?1 ORDER BY created_at")?;
+    let decisions: Vec<String> = stmt
+        .query_map([session.session_id], |row| row.get(0))?
+        .filter_map(|r| r.ok())
+        .collect();
+
+    let branch = crate::paths::get_project_root()
+        .ok()
+        .and_then(|root| git::current_branch(&root))
+        .unwrap_or_default();
+
+    let commit_message = crate::commit::render_template(
+        &config.commit_template,
+        &[
+            ("summary", summary.to_string()),
+            ("session_id", session.session_id.to_string()),
+            ("tasks", tasks.join(", ")),
+            ("decisions", decisions.join(", ")),
+            ("branch", branch),
+            ("date", chrono::Local::now().format("%Y-%m-%d").to_string()),
+        ],
+    );
     crate::commit::auto_commit(&commit_message, &config)?;
 
     Ok(())
 }
 
 /// List recent sessions
-fn cmd_list(conn: &rusqlite::Connection) -> Result<()> {
-    let sessions = get_recent_sessions(conn, 10)?;
+fn cmd_list(
+    conn: &rusqlite::Connection,
+    status: Option<&str>,
+    since: Option<&str>,
+    page: &PageArgs,
+) -> Result<()> {
+    let filter = SessionFilter {
+        statuses: parse_statuses(status)?,
+        since: since.map(validate_since).transpose()?,
+        limit: Some(page.limit.unwrap_or(DEFAULT_LIST_LIMIT)),
+        offset: page.offset,
+    };
+    let (sessions, total) = list_sessions(conn, &filter)?;
+
+    // Porcelain: ID, status, started, ended, name, summary
+    if porcelain::enabled() {
+        for session in &sessions {
+            porcelain::print(&[
+                &session.session_id.to_string(),
+                &session.status,
+                &session.started_at.to_rfc3339(),
+                &session.ended_at.map(|e| e.to_rfc3339()).unwrap_or_default(),
+                session.name.as_deref().unwrap_or_default(),
+                session.summary.as_deref().unwrap_or_default(),
+            ]);
+        }
+        return Ok(());
+    }
 
     if sessions.is_empty() {
-        println!("No sessions found.");
+        if total > 0 {
+            println!("No sessions past the first {} (--offset).", total);
+        } else {
+            println!("No sessions found.");
+        }
         return Ok(());
     }
 
-    println!("{}", "Recent Sessions:".bold());
-    println!("{}", "-".repeat(60));
+    let mut out = String::new();
+    writeln!(out, "{}", "Recent Sessions:".bold())?;
+    writeln!(out, "{}", "-".repeat(60))?;
 
+    let shown = sessions.len();
     for session in sessions {
         let status_indicator = match session.status.as_str() {
             "active" => "(active)".green(),
+            "paused" => "(paused)".blue(),
             "completed" => "(completed)".white(),
             "abandoned" => "(abandoned)".yellow(),
             _ => session.status.clone().white(),
         };
 
-        let date_str = if session.status == "active" {
+        let date_str = if session.status == "active" || session.status == "paused" {
             format!("started {}", session.started_at.format("%Y-%m-%d %H:%M"))
         } else {
             session
@@ -569,15 +1051,173 @@ fn cmd_list(conn: &rusqlite::Connection) -> Result<()> {
                 .unwrap_or_else(|| session.started_at.format("%Y-%m-%d %H:%M").to_string())
         };
 
-        println!(
-            "#{:<4} {} {}",
-            session.session_id, date_str, status_indicator
-        );
+        let name = session
+            .name
+            .as_deref()
+            .map(|n| format!(" [{}]", n))
+            .unwrap_or_default();
+        writeln!(
+            out,
+            "#{:<4} {} {}{}",
+            session.session_id,
+            date_str,
+            status_indicator,
+            name.cyan()
+        )?;
 
         if let Some(summary) = &session.summary {
-            println!("      {}", summary);
+            writeln!(out, "      {}", summary)?;
         }
     }
 
+    if (shown as i64) < total {
+        writeln!(
+            out,
+            "{}",
+            format!(
+                "\nShowing {}-{} of {}. Use --limit and --offset for more.",
+                page.offset + 1,
+                page.offset + shown,
+                total
+            )
+            .dimmed()
+        )?;
+    }
+    pager::page(&out);
     Ok(())
 }
+
+/// Comma-separated --status values, checked against the session statuses
+fn parse_statuses(value: Option<&str>) -> Result<Vec<String>> {
+    let Some(value) = value.filter(|v| v.trim() != "all") else {
+        return Ok(Vec::new());
+    };
+    let statuses: Vec<String> = value
+        .split(',')
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect();
+    if let Some(bad) = statuses
+        .iter()
+        .find(|s| !SESSION_STATUSES.contains(&s.as_str()))
+    {
+        return Err(ProjError::InvalidInput(format!(
+            "Invalid --status '{}'. Use {}, or all.",
+            bad,
+            SESSION_STATUSES.join(", ")
+        ))
+        .into());
+    }
+    Ok(statuses)
+}
+
+/// Validate a --since argument, returning it normalized as YYYY-MM-DD
+fn validate_since(since: &str) -> Result<String> {
+    chrono::NaiveDate::parse_from_str(since.trim(), "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| {
+            ProjError::InvalidInput(format!("Invalid --since '{}'. Use YYYY-MM-DD.", since)).into()
+        })
+}
+
+/// Sweep stale sessions in every registered project, using each project's policy
+fn cmd_gc(dry_run: bool) -> Result<()> {
+    let registry = load_registry()?;
+    if registry.registered_projects.is_empty() {
+        println!("No registered projects. Run 'proj register' in a project first.");
+        return Ok(());
+    }
+
+    let mut swept = 0;
+    for project in &registry.registered_projects {
+        let tracking = Path::new(&project.path).join(".tracking");
+        let db_path = tracking.join("tracking.db");
+        if !db_path.exists() {
+            continue;
+        }
+
+        let config: ProjectConfig = match std::fs::read_to_string(tracking.join("config.json"))
+            .ok()
+            .and_then(|c| ProjectConfig::from_json(&c).ok())
+        {
+            Some(c) => c,
+            None => {
+                println!(
+                    "  {} {}: can't read config.json, skipped",
+                    "⚠".yellow(),
+                    project.name
+                );
+                continue;
+            }
+        };
+        let policy = StalePolicy::from_config(&config);
+
+        let conn = match open_database(&db_path) {
+            Ok(c) => c,
+            Err(e) => {
+                println!("  {} {}: {}", "⚠".yellow(), project.name, e);
+                continue;
+            }
+        };
+        let Some(session) = get_active_session(&conn)? else {
+            continue;
+        };
+        if !policy.is_stale(&session) {
+            continue;
+        }
+
+        let started = session.started_at.format("%Y-%m-%d %H:%M");
+        if dry_run {
+            println!(
+                "  {} session {} (started {}) → {}",
+                project.name.bold(),
+                session_label(&session),
+                started,
+                policy.action
+            );
+            swept += 1;
+            continue;
+        }
+
+        let outcome = match handle_stale_session(&conn, &session, &policy)? {
+            StaleOutcome::Closed => "closed (auto-closed)".normal(),
+            StaleOutcome::Summarized => "closed".normal(),
+            StaleOutcome::Abandoned => "abandoned".yellow(),
+            StaleOutcome::KeptOpen => "kept open".dimmed(),
+        };
+        println!(
+            "  {} {} session {} (started {}): {}",
+            "✓".green(),
+            project.name.bold(),
+            session_label(&session),
+            started,
+            outcome
+        );
+        swept += 1;
+    }
+
+    if swept == 0 {
+        println!("No stale sessions.");
+    } else if dry_run {
+        println!(
+            "\n{} stale session(s). Run without --dry-run to close them.",
+            swept
+        );
+    }
+    Ok(())
+}
+
+/// Load the global registry
+fn load_registry() -> Result<Registry> {
+    let registry_path = get_registry_path()?;
+
+    if !registry_path.exists() {
+        return Ok(Registry::default());
+    }
+
+    let content =
+        std::fs::read_to_string(&registry_path).with_context(|| "Failed to read registry.json")?;
+    let registry: Registry =
+        serde_json::from_str(&content).with_context(|| "Failed to parse registry.json")?;
+    Ok(registry)
+}
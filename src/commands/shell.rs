@@ -1,27 +1,34 @@
-// Shell integration commands - install/uninstall shell hooks for automatic tracking
+// Shell integration commands - install/uninstall shell hooks (zsh, bash, PowerShell) for automatic tracking
 
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::Confirm;
 
+use crate::text::truncate;
+
 /// Shell hook marker - used to detect if hook is already installed
 const HOOK_MARKER_START: &str = "# >>> proj shell integration >>>";
 const HOOK_MARKER_END: &str = "# <<< proj shell integration <<<";
 
 /// Zsh hook code - uses precmd (every prompt) and chpwd (directory change)
 const ZSH_HOOK: &str = r#"# >>> proj shell integration >>>
-# Runs proj enter on directory change, checks for stale sessions on every prompt
+# Runs proj enter on directory change, checks for stale sessions on every prompt,
+# and keeps $PROJ_PROMPT (project, session, blockers) current for your prompt
 _proj_auto_enter() {
     if [[ -d ".tracking" ]] && command -v proj &> /dev/null; then
         proj enter
     fi
+    export PROJ_PROMPT="$(command -v proj &> /dev/null && proj prompt 2>/dev/null)"
 }
 _proj_check_stale() {
     if [[ -d ".tracking" ]] && command -v proj &> /dev/null; then
         proj shell check 2>/dev/null
+        if [[ ".tracking/tracking.db" -nt ".tracking/.prompt_cache" || ".tracking/tracking.db-wal" -nt ".tracking/.prompt_cache" ]]; then
+            export PROJ_PROMPT="$(proj prompt 2>/dev/null)"
+        fi
     fi
 }
 # Run enter on directory change
@@ -36,12 +43,16 @@ fi
 
 /// Bash hook code - uses PROMPT_COMMAND for both directory change and stale check
 const BASH_HOOK: &str = r#"# >>> proj shell integration >>>
-# Runs proj enter on directory change, checks for stale sessions on every prompt
+# Runs proj enter on directory change, checks for stale sessions on every prompt,
+# and keeps $PROJ_PROMPT (project, session, blockers) current for your prompt
 _proj_last_dir=""
 _proj_prompt_hook() {
     # Check for stale session on every prompt
     if [[ -d ".tracking" ]] && command -v proj &> /dev/null; then
         proj shell check 2>/dev/null
+        if [[ ".tracking/tracking.db" -nt ".tracking/.prompt_cache" || ".tracking/tracking.db-wal" -nt ".tracking/.prompt_cache" ]]; then
+            export PROJ_PROMPT="$(proj prompt 2>/dev/null)"
+        fi
     fi
     # Run enter on directory change
     if [[ "$PWD" != "$_proj_last_dir" ]]; then
@@ -49,6 +60,7 @@ _proj_prompt_hook() {
         if [[ -d ".tracking" ]] && command -v proj &> /dev/null; then
             proj enter
         fi
+        export PROJ_PROMPT="$(command -v proj &> /dev/null && proj prompt 2>/dev/null)"
     fi
 }
 # Add to PROMPT_COMMAND if not already present
@@ -57,18 +69,126 @@ if [[ "$PROMPT_COMMAND" != *"_proj_prompt_hook"* ]]; then
 fi
 # <<< proj shell integration <<<"#;
 
-pub fn install(force: bool) -> Result<()> {
+/// PowerShell hook code - wraps the prompt function for both directory change and stale check
+const POWERSHELL_HOOK: &str = r#"# >>> proj shell integration >>>
+# Runs proj enter on directory change, checks for stale sessions on every prompt,
+# and keeps $env:PROJ_PROMPT (project, session, blockers) current for your prompt
+$global:_ProjLastDir = $null
+if (-not (Test-Path Function:\_ProjOriginalPrompt)) {
+    $function:_ProjOriginalPrompt = $function:prompt
+}
+function prompt {
+    $proj = Get-Command proj -ErrorAction SilentlyContinue
+    if ((Test-Path .tracking -PathType Container) -and $proj) {
+        # Check for stale session on every prompt
+        proj shell check 2>$null
+        # Run enter on directory change
+        if ($PWD.Path -ne $global:_ProjLastDir) {
+            proj enter
+        }
+    }
+    if ($proj -and $PWD.Path -ne $global:_ProjLastDir) {
+        $env:PROJ_PROMPT = proj prompt 2>$null
+    }
+    $global:_ProjLastDir = $PWD.Path
+    _ProjOriginalPrompt
+}
+# <<< proj shell integration <<<"#;
+
+/// A shell config file the hook can be installed in
+struct ShellConfig {
+    shell: &'static str,
+    path: PathBuf,
+    /// Path for display, relative to ~ where possible
+    label: String,
+    hook: &'static str,
+    /// Create the file if missing (PowerShell profiles usually don't exist yet)
+    create: bool,
+}
+
+impl ShellConfig {
+    fn new(home: &Path, shell: &'static str, path: PathBuf, hook: &'static str) -> Self {
+        let label = match path.strip_prefix(home) {
+            Ok(relative) => format!("~/{}", relative.display()),
+            Err(_) => path.display().to_string(),
+        };
+        ShellConfig {
+            shell,
+            path,
+            label,
+            hook,
+            create: false,
+        }
+    }
+
+    fn available(&self) -> bool {
+        self.create || self.path.exists()
+    }
+
+    fn installed(&self) -> bool {
+        self.path.exists() && is_hook_installed(&self.path).unwrap_or(false)
+    }
+
+    /// Installed, but not the hook this version of proj writes
+    fn outdated(&self) -> bool {
+        self.installed()
+            && !fs::read_to_string(&self.path)
+                .unwrap_or_default()
+                .contains(self.hook)
+    }
+
+    /// How to load the hook into the current shell
+    fn reload_hint(&self) -> String {
+        match self.shell {
+            "powershell" => ". $PROFILE".to_string(),
+            _ => format!("source {}", self.label),
+        }
+    }
+}
+
+/// Every config file proj knows how to hook: zsh, bash, and PowerShell profiles
+fn shell_configs() -> Result<Vec<ShellConfig>> {
     let home = dirs::home_dir().context("Could not determine home directory")?;
-    let zshrc = home.join(".zshrc");
-    let bashrc = home.join(".bashrc");
+    let mut configs = vec![
+        ShellConfig::new(&home, "zsh", home.join(".zshrc"), ZSH_HOOK),
+        ShellConfig::new(&home, "bash", home.join(".bashrc"), BASH_HOOK),
+    ];
+
+    if cfg!(windows) {
+        // PowerShell 7 and Windows PowerShell 5.1 keep separate profiles; 5.1 ships with Windows
+        let documents = dirs::document_dir().unwrap_or_else(|| home.join("Documents"));
+        for (dir, create) in [("PowerShell", false), ("WindowsPowerShell", true)] {
+            let mut config = ShellConfig::new(
+                &home,
+                "powershell",
+                documents.join(dir).join("Microsoft.PowerShell_profile.ps1"),
+                POWERSHELL_HOOK,
+            );
+            config.create = create;
+            configs.push(config);
+        }
+    } else {
+        configs.push(ShellConfig::new(
+            &home,
+            "powershell",
+            home.join(".config")
+                .join("powershell")
+                .join("Microsoft.PowerShell_profile.ps1"),
+            POWERSHELL_HOOK,
+        ));
+    }
+    Ok(configs)
+}
 
-    // Check what shells are available
-    let zsh_exists = zshrc.exists();
-    let bash_exists = bashrc.exists();
+pub fn install(force: bool) -> Result<()> {
+    let configs: Vec<ShellConfig> = shell_configs()?
+        .into_iter()
+        .filter(ShellConfig::available)
+        .collect();
 
-    if !zsh_exists && !bash_exists {
+    if configs.is_empty() {
         println!(
-            "{} No shell config files found (.zshrc or .bashrc)",
+            "{} No shell config files found (.zshrc, .bashrc, or a PowerShell profile)",
             "⚠".yellow()
         );
         println!("Please create ~/.zshrc or ~/.bashrc and run this command again.");
@@ -76,16 +196,21 @@ pub fn install(force: bool) -> Result<()> {
     }
 
     // Check if already installed
-    let zsh_installed = zsh_exists && is_hook_installed(&zshrc)?;
-    let bash_installed = bash_exists && is_hook_installed(&bashrc)?;
-
-    if zsh_installed || bash_installed {
+    let installed: Vec<&ShellConfig> = configs.iter().filter(|c| c.installed()).collect();
+    if !installed.is_empty() {
         println!("{} Shell integration already installed:", "✓".green());
-        if zsh_installed {
-            println!("  • ~/.zshrc");
-        }
-        if bash_installed {
-            println!("  • ~/.bashrc");
+        for config in &installed {
+            if config.outdated() {
+                remove_hook(&config.path)?;
+                install_hook(&config.path, config.hook)?;
+                println!(
+                    "  • {} (updated; run '{}' to load it)",
+                    config.label,
+                    config.reload_hint()
+                );
+            } else {
+                println!("  • {}", config.label);
+            }
         }
         if !force {
             println!();
@@ -106,49 +231,40 @@ pub fn install(force: bool) -> Result<()> {
         println!();
     }
 
-    let mut installed_shells = Vec::new();
+    let mut installed_configs = Vec::new();
 
-    // Install for zsh
-    if zsh_exists && !zsh_installed {
+    for config in &configs {
         let should_install = force
             || Confirm::new()
-                .with_prompt("Install for zsh (~/.zshrc)?")
+                .with_prompt(format!("Install for {} ({})?", config.shell, config.label))
                 .default(true)
                 .interact()?;
 
         if should_install {
-            install_hook(&zshrc, ZSH_HOOK)?;
-            installed_shells.push("zsh");
-            println!("{} Installed in ~/.zshrc", "✓".green());
+            install_hook(&config.path, config.hook)?;
+            installed_configs.push(config);
+            println!("{} Installed in {}", "✓".green(), config.label);
         }
     }
 
-    // Install for bash
-    if bash_exists && !bash_installed {
-        let should_install = force
-            || Confirm::new()
-                .with_prompt("Install for bash (~/.bashrc)?")
-                .default(true)
-                .interact()?;
-
-        if should_install {
-            install_hook(&bashrc, BASH_HOOK)?;
-            installed_shells.push("bash");
-            println!("{} Installed in ~/.bashrc", "✓".green());
-        }
-    }
-
-    if installed_shells.is_empty() {
+    let Some(first) = installed_configs.first() else {
         println!("No changes made.");
         return Ok(());
-    }
+    };
 
     println!();
     println!("{}", "Setup complete!".bold().green());
     println!();
     println!("To activate, either:");
     println!("  • Open a new terminal window, or");
-    println!("  • Run: source ~/.{}rc", installed_shells[0]);
+    println!("  • Run: {}", first.reload_hint());
+    if cfg!(windows) && installed_configs.iter().any(|c| c.shell == "powershell") {
+        println!();
+        println!(
+            "If PowerShell refuses to load your profile, allow local scripts with:\n  {}",
+            "Set-ExecutionPolicy -Scope CurrentUser RemoteSigned".cyan()
+        );
+    }
     println!();
     println!("Then cd into any project with proj tracking - session will start automatically.");
 
@@ -159,24 +275,14 @@ pub fn uninstall() -> Result<()> {
     println!("{}", "Removing Shell Integration".bold());
     println!();
 
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    let zshrc = home.join(".zshrc");
-    let bashrc = home.join(".bashrc");
-
     let mut removed = false;
 
-    // Remove from zsh
-    if zshrc.exists() && is_hook_installed(&zshrc)? {
-        remove_hook(&zshrc)?;
-        println!("{} Removed from ~/.zshrc", "✓".green());
-        removed = true;
-    }
-
-    // Remove from bash
-    if bashrc.exists() && is_hook_installed(&bashrc)? {
-        remove_hook(&bashrc)?;
-        println!("{} Removed from ~/.bashrc", "✓".green());
-        removed = true;
+    for config in shell_configs()? {
+        if config.installed() {
+            remove_hook(&config.path)?;
+            println!("{} Removed from {}", "✓".green(), config.label);
+            removed = true;
+        }
     }
 
     if removed {
@@ -190,23 +296,18 @@ pub fn uninstall() -> Result<()> {
 }
 
 pub fn status() -> Result<()> {
-    let home = dirs::home_dir().context("Could not determine home directory")?;
-    let zshrc = home.join(".zshrc");
-    let bashrc = home.join(".bashrc");
-
     println!("{}", "Shell Integration Status".bold());
     println!();
 
-    let zsh_installed = zshrc.exists() && is_hook_installed(&zshrc)?;
-    let bash_installed = bashrc.exists() && is_hook_installed(&bashrc)?;
+    let installed: Vec<ShellConfig> = shell_configs()?
+        .into_iter()
+        .filter(ShellConfig::installed)
+        .collect();
 
-    if zsh_installed || bash_installed {
+    if !installed.is_empty() {
         println!("{} Shell integration is installed:", "✓".green());
-        if zsh_installed {
-            println!("  • ~/.zshrc");
-        }
-        if bash_installed {
-            println!("  • ~/.bashrc");
+        for config in &installed {
+            println!("  • {}", config.label);
         }
     } else {
         println!("{} Shell integration is not installed.", "○".white());
@@ -218,13 +319,17 @@ pub fn status() -> Result<()> {
 }
 
 /// Check if hook is already installed in a file
-fn is_hook_installed(path: &PathBuf) -> Result<bool> {
+fn is_hook_installed(path: &Path) -> Result<bool> {
     let content = fs::read_to_string(path).unwrap_or_default();
     Ok(content.contains(HOOK_MARKER_START))
 }
 
-/// Install hook at the end of a shell config file
-fn install_hook(path: &PathBuf, hook: &str) -> Result<()> {
+/// Install hook at the end of a shell config file, creating it if needed
+fn install_hook(path: &Path, hook: &str) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
     let mut content = fs::read_to_string(path).unwrap_or_default();
 
     // Add a newline if the file doesn't end with one
@@ -240,7 +345,7 @@ fn install_hook(path: &PathBuf, hook: &str) -> Result<()> {
 }
 
 /// Remove hook from a shell config file
-fn remove_hook(path: &PathBuf) -> Result<()> {
+fn remove_hook(path: &Path) -> Result<()> {
     let content = fs::read_to_string(path)?;
 
     // Find and remove the hook block
@@ -276,8 +381,8 @@ fn remove_hook(path: &PathBuf) -> Result<()> {
 pub fn check() -> Result<()> {
     use crate::database::open_database;
     use crate::paths::get_tracking_db_path;
-    use crate::session::get_active_session;
-    use chrono::{Duration, Utc};
+    use crate::session::{get_active_session, StalePolicy};
+    use chrono::Utc;
 
     // Quick exit if not in a proj directory
     let tracking_dir = std::path::Path::new(".tracking");
@@ -301,14 +406,11 @@ pub fn check() -> Result<()> {
         _ => return Ok(()), // No active session, nothing to warn about
     };
 
-    // Check if session is stale (8+ hours)
-    let stale_hours: i64 = 8;
-    let now = Utc::now();
-    let session_age = now - session.started_at;
-
-    if session_age <= Duration::hours(stale_hours) {
+    // Check if session is stale (session_timeout_hours, default 8)
+    if !StalePolicy::current().is_stale(&session) {
         return Ok(()); // Session is fine, exit silently
     }
+    let session_age = Utc::now() - session.resumed_at.unwrap_or(session.started_at);
 
     // Session is stale - check if we've already warned
     let warned_marker = tracking_dir.join(format!(".warned_stale_{}", session.session_id));
@@ -329,7 +431,7 @@ pub fn check() -> Result<()> {
         hours_stale
     );
     if summary != "(no summary)" && summary != "(auto-closed)" {
-        eprintln!("  Last: \"{}\"", truncate_str(summary, 60));
+        eprintln!("  Last: \"{}\"", truncate(summary, 60));
     }
     eprintln!("  Run '{}' to start a new session.", "proj status".cyan());
     eprintln!();
@@ -340,24 +442,9 @@ pub fn check() -> Result<()> {
     Ok(())
 }
 
-/// Truncate a string to max length with ellipsis
-fn truncate_str(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len.saturating_sub(3)])
-    }
-}
-
 /// Check if shell integration is installed (for use by other commands)
 pub fn is_installed() -> bool {
-    let home = match dirs::home_dir() {
-        Some(h) => h,
-        None => return false,
-    };
-
-    let zshrc = home.join(".zshrc");
-    let bashrc = home.join(".bashrc");
-
-    is_hook_installed(&zshrc).unwrap_or(false) || is_hook_installed(&bashrc).unwrap_or(false)
+    shell_configs()
+        .map(|configs| configs.iter().any(ShellConfig::installed))
+        .unwrap_or(false)
 }
@@ -1,12 +1,43 @@
-// Snapshot command - generate AI context snapshot
+// Snapshot command - generate AI context snapshot, or bundle the whole project
+//
+// `proj snapshot --bundle` writes one .tar.zst with everything a teammate needs
+// to pick the project up: the tracking data as a JSON export (the format
+// 'proj import' reads), the docs database plus a markdown export of it,
+// config.json, and a SUMMARY.md for people. `proj snapshot --restore` unpacks
+// one into the current directory's project, creating the project if needed.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use chrono::Utc;
-use serde::Serialize;
+use colored::Colorize;
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
 
+use crate::commands::{docs, export, import, register};
 use crate::config::ProjectConfig;
 use crate::database::open_database;
-use crate::paths::{get_config_path, get_tracking_db_path};
+use crate::docs_db;
+use crate::error::ProjError;
+use crate::paths::{get_config_path, get_project_root, get_tracking_db_path};
+use crate::schema::init_tracking_schema;
+
+/// Bundle layout version; bumped when restore can't read older bundles the same way
+const BUNDLE_FORMAT: u32 = 1;
+
+/// manifest.json at the root of a bundle
+#[derive(Serialize, Deserialize)]
+struct Manifest {
+    format: u32,
+    project: String,
+    created_at: String,
+    schema_version: String,
+    proj_version: String,
+    /// File name of the docs database under docs/, if the project has one
+    docs_db: Option<String>,
+}
 
 #[derive(Serialize)]
 struct ContextSnapshot {
@@ -55,7 +86,16 @@ struct DecisionSnapshot {
     rationale: Option<String>,
 }
 
-pub fn run() -> Result<()> {
+pub fn run(
+    bundle: bool,
+    output: Option<PathBuf>,
+    restore: Option<PathBuf>,
+    merge: bool,
+) -> Result<()> {
+    if let Some(file) = restore {
+        return restore_bundle(&file, merge);
+    }
+
     // Load config
     let config = load_config()?;
 
@@ -63,6 +103,20 @@ pub fn run() -> Result<()> {
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)?;
 
+    if bundle {
+        return write_bundle(&conn, &config, output);
+    }
+
+    let snapshot = collect(&conn, &config)?;
+
+    // Output as JSON
+    println!("{}", serde_json::to_string_pretty(&snapshot)?);
+
+    Ok(())
+}
+
+/// Gather the current context: session, open work, and recent decisions
+fn collect(conn: &Connection, config: &ProjectConfig) -> Result<ContextSnapshot> {
     // Get active session
     let active_session: Option<SessionSnapshot> = conn
         .query_row(
@@ -141,29 +195,437 @@ pub fn run() -> Result<()> {
         .collect();
 
     // Build snapshot
-    let snapshot = ContextSnapshot {
-        project: config.name,
-        project_type: config.project_type,
+    Ok(ContextSnapshot {
+        project: config.name.clone(),
+        project_type: config.project_type.clone(),
         generated_at: Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
         active_session,
         active_tasks,
         active_blockers,
         open_questions,
         recent_decisions,
+    })
+}
+
+/// Write the project's state to a .tar.zst bundle
+fn write_bundle(conn: &Connection, config: &ProjectConfig, output: Option<PathBuf>) -> Result<()> {
+    let project_root = get_project_root()?;
+    let output =
+        output.unwrap_or_else(|| PathBuf::from(bundle_file_name(&config.name, "snapshot")));
+    let files = bundle(&project_root, conn, config, &output)?;
+
+    let size = std::fs::metadata(&output).map(|m| m.len()).unwrap_or(0);
+    println!(
+        "{} Wrote {} ({})",
+        "✓".green(),
+        output.display(),
+        format_size(size)
+    );
+    for name in &files {
+        println!("  {}", name);
+    }
+    println!("\nRestore it elsewhere with 'proj snapshot --restore <file>'.");
+    Ok(())
+}
+
+/// `<project>-<kind>-<timestamp>.tar.zst`, with the name made safe for a file name
+pub fn bundle_file_name(project: &str, kind: &str) -> String {
+    let name: String = project
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    format!(
+        "{}-{}-{}.tar.zst",
+        name,
+        kind,
+        Utc::now().format("%Y%m%d-%H%M%S")
+    )
+}
+
+/// Bundle the project at `project_root` (not necessarily the current one) into
+/// `output`, returning the size written
+pub fn bundle_project(project_root: &Path, output: &Path) -> Result<u64> {
+    let tracking = project_root.join(".tracking");
+    let content = std::fs::read_to_string(tracking.join("config.json"))
+        .with_context(|| format!("No project config in {}", tracking.display()))?;
+    let config =
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
+    let conn = open_database(&tracking.join("tracking.db"))?;
+    bundle(project_root, &conn, &config, output)?;
+    Ok(std::fs::metadata(output).map(|m| m.len()).unwrap_or(0))
+}
+
+/// Write a bundle of the project at `project_root` and return the files in it
+fn bundle(
+    project_root: &Path,
+    conn: &Connection,
+    config: &ProjectConfig,
+    output: &Path,
+) -> Result<Vec<String>> {
+    let mut files: Vec<(String, Vec<u8>)> = Vec::new();
+
+    // Webhook secrets stay with this copy of the project
+    let mut shared_config = config.clone();
+    for webhook in &mut shared_config.webhooks {
+        webhook.secret = None;
+    }
+    files.push((
+        "config.json".to_string(),
+        serde_json::to_string_pretty(&shared_config)?.into_bytes(),
+    ));
+    files.push((
+        "tracking.json".to_string(),
+        export::full_json(conn, config)?.into_bytes(),
+    ));
+
+    let mut docs_outline = None;
+    let docs_path = docs_db::find_docs_db(project_root);
+    if let Some(path) = &docs_path {
+        let docs_conn = docs_db::open_docs_db(path)?;
+        let sections = docs_db::get_all_sections(&docs_conn)?;
+        let terms = docs_db::get_all_terms(&docs_conn)?;
+        let mut dangling = 0;
+        files.push((
+            "docs.md".to_string(),
+            docs::export_markdown(&sections, &terms, &HashMap::new(), &mut dangling).into_bytes(),
+        ));
+        let file_name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        files.push((format!("docs/{}", file_name), copy_database(&docs_conn)?));
+        docs_outline = Some((
+            file_name,
+            sections
+                .iter()
+                .filter(|s| s.level == 1)
+                .map(|s| format!("{} {}", s.section_id, s.title))
+                .collect::<Vec<_>>(),
+            sections.len(),
+        ));
+    }
+
+    let manifest = Manifest {
+        format: BUNDLE_FORMAT,
+        project: config.name.clone(),
+        created_at: Utc::now().format("%Y-%m-%dT%H:%M:%S").to_string(),
+        schema_version: crate::SCHEMA_VERSION.to_string(),
+        proj_version: env!("CARGO_PKG_VERSION").to_string(),
+        docs_db: docs_outline.as_ref().map(|(name, _, _)| name.clone()),
     };
+    let summary = render_summary(conn, config, &collect(conn, config)?, docs_outline.as_ref())?;
+    files.insert(0, ("SUMMARY.md".to_string(), summary.into_bytes()));
+    files.insert(
+        0,
+        (
+            "manifest.json".to_string(),
+            serde_json::to_string_pretty(&manifest)?.into_bytes(),
+        ),
+    );
 
-    // Output as JSON
-    println!("{}", serde_json::to_string_pretty(&snapshot)?);
+    let file = std::fs::File::create(output)
+        .with_context(|| format!("Failed to create {}", output.display()))?;
+    let mut builder = tar::Builder::new(zstd::Encoder::new(file, 0)?);
+    let mtime = Utc::now().timestamp() as u64;
+    for (name, data) in &files {
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder.append_data(&mut header, name, data.as_slice())?;
+    }
+    builder.into_inner()?.finish()?;
+    Ok(files.into_iter().map(|(name, _)| name).collect())
+}
+
+/// A consistent copy of an open database, as file bytes
+fn copy_database(conn: &Connection) -> Result<Vec<u8>> {
+    let temp = std::env::temp_dir().join(format!("proj-snapshot-{}.db", std::process::id()));
+    let _ = std::fs::remove_file(&temp);
+    conn.execute("VACUUM INTO ?1", [temp.to_string_lossy()])?;
+    let data = std::fs::read(&temp);
+    let _ = std::fs::remove_file(&temp);
+    Ok(data?)
+}
+
+/// SUMMARY.md: what a person opening the bundle reads first
+fn render_summary(
+    conn: &Connection,
+    config: &ProjectConfig,
+    snapshot: &ContextSnapshot,
+    docs_outline: Option<&(String, Vec<String>, usize)>,
+) -> Result<String> {
+    let mut md = format!("# {} - project snapshot\n\n", config.name);
+    if let Some(description) = &config.description {
+        md.push_str(&format!("{}\n\n", description));
+    }
+    md.push_str(&format!(
+        "- Type: {}\n- Taken: {} UTC\n- Schema: v{} (proj {})\n\n",
+        config.project_type,
+        snapshot.generated_at.replace('T', " "),
+        crate::SCHEMA_VERSION,
+        env!("CARGO_PKG_VERSION")
+    ));
+
+    md.push_str("## Current session\n\n");
+    match &snapshot.active_session {
+        Some(s) => md.push_str(&format!(
+            "Session #{} started {}{}\n\n",
+            s.session_id,
+            s.started_at,
+            s.agent
+                .as_ref()
+                .map(|a| format!(" by {}", a))
+                .unwrap_or_default()
+        )),
+        None => md.push_str("No active session.\n\n"),
+    }
+
+    md.push_str(&format!(
+        "## Open tasks ({})\n\n",
+        snapshot.active_tasks.len()
+    ));
+    for t in &snapshot.active_tasks {
+        md.push_str(&format!(
+            "- #{} {} ({}{})\n",
+            t.task_id,
+            t.description,
+            t.status,
+            t.priority
+                .as_ref()
+                .map(|p| format!(", {}", p))
+                .unwrap_or_default()
+        ));
+    }
+    if snapshot.active_tasks.is_empty() {
+        md.push_str("None.\n");
+    }
+
+    if !snapshot.active_blockers.is_empty() {
+        md.push_str(&format!(
+            "\n## Blockers ({})\n\n",
+            snapshot.active_blockers.len()
+        ));
+        for b in &snapshot.active_blockers {
+            md.push_str(&format!("- #{} {}\n", b.blocker_id, b.description));
+        }
+    }
+
+    if !snapshot.open_questions.is_empty() {
+        md.push_str(&format!(
+            "\n## Open questions ({})\n\n",
+            snapshot.open_questions.len()
+        ));
+        for q in &snapshot.open_questions {
+            md.push_str(&format!("- #{} {}\n", q.question_id, q.question));
+        }
+    }
+
+    if !snapshot.recent_decisions.is_empty() {
+        md.push_str("\n## Recent decisions\n\n");
+        for d in &snapshot.recent_decisions {
+            md.push_str(&format!("- **{}**: {}", d.topic, d.decision));
+            if let Some(rationale) = &d.rationale {
+                md.push_str(&format!(" ({})", rationale));
+            }
+            md.push('\n');
+        }
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT session_id, ended_at, summary FROM sessions
+         WHERE status = 'completed' AND summary IS NOT NULL AND summary != ''
+         ORDER BY ended_at DESC LIMIT 5",
+    )?;
+    let sessions = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    if !sessions.is_empty() {
+        md.push_str("\n## Recent sessions\n\n");
+        for (id, ended_at, summary) in sessions {
+            let ended = ended_at.unwrap_or_default();
+            md.push_str(&format!(
+                "- #{} {}: {}\n",
+                id,
+                ended.get(..10).unwrap_or(&ended),
+                summary
+            ));
+        }
+    }
+
+    if let Some((file_name, top_level, count)) = docs_outline {
+        md.push_str(&format!(
+            "\n## Documentation\n\n{} sections from {} (full text in docs.md):\n\n",
+            count, file_name
+        ));
+        for title in top_level {
+            md.push_str(&format!("- {}\n", title));
+        }
+    }
 
+    md.push_str("\n## Totals\n\n| Item | Count |\n|------|-------|\n");
+    for (label, table) in [
+        ("Tasks", "tasks"),
+        ("Decisions", "decisions"),
+        ("Notes", "context_notes"),
+        ("Sessions", "sessions"),
+        ("Commits", "git_commits"),
+    ] {
+        let count: i64 = conn.query_row(&format!("SELECT COUNT(*) FROM {}", table), [], |row| {
+            row.get(0)
+        })?;
+        md.push_str(&format!("| {} | {} |\n", label, count));
+    }
+
+    md.push_str(
+        "\n## Restoring\n\nRun `proj snapshot --restore <file>` in the project directory. \
+         Without a project there, it creates one from config.json; otherwise add `--merge` \
+         to combine the tracking data with what's already there.\n",
+    );
+    Ok(md)
+}
+
+/// Unpack a bundle into the current directory's project, creating it if needed
+fn restore_bundle(file: &Path, merge: bool) -> Result<()> {
+    let files = read_bundle(file)?;
+    let not_a_bundle = || {
+        ProjError::InvalidInput(format!(
+            "{} is not a proj snapshot bundle. Create one with 'proj snapshot --bundle'.",
+            file.display()
+        ))
+    };
+    let manifest: Manifest = files
+        .get("manifest.json")
+        .and_then(|data| serde_json::from_slice(data).ok())
+        .ok_or_else(not_a_bundle)?;
+    if manifest.format > BUNDLE_FORMAT {
+        return Err(ProjError::InvalidInput(format!(
+            "{} was made by a newer proj ({}). Update proj to restore it.",
+            file.display(),
+            manifest.proj_version
+        ))
+        .into());
+    }
+    let tracking = files.get("tracking.json").ok_or_else(not_a_bundle)?;
+    let tracking = std::str::from_utf8(tracking).map_err(|_| not_a_bundle())?;
+
+    println!(
+        "Restoring snapshot of {} (taken {})",
+        manifest.project.bold(),
+        manifest.created_at.replace('T', " ")
+    );
+
+    // No project here yet: create one from the bundle's config
+    let created = get_project_root().is_err();
+    if created {
+        let config: ProjectConfig = files
+            .get("config.json")
+            .and_then(|data| serde_json::from_slice(data).ok())
+            .ok_or_else(not_a_bundle)?;
+        let tracking_path = crate::paths::get_start_dir()?.join(".tracking");
+        std::fs::create_dir_all(&tracking_path)?;
+        config.save()?;
+        let conn = open_database(&tracking_path.join("tracking.db"))?;
+        init_tracking_schema(&conn)
+            .with_context(|| "Failed to initialize tracking database schema")?;
+        println!(
+            "  {} Created .tracking/ from the bundle's config.json",
+            "✓".green()
+        );
+    } else {
+        println!(
+            "  {}",
+            "Keeping this project's config.json; the bundle's copy was not applied.".dimmed()
+        );
+    }
+    println!();
+
+    import::import_json(&file.display().to_string(), tracking, merge, false)?;
+
+    let project_root = get_project_root()?;
+    if let Some(name) = &manifest.docs_db {
+        // Only the file name is trusted, so a bundle can't write outside the project
+        let file_name = Path::new(name).file_name().ok_or_else(not_a_bundle)?;
+        let data = files
+            .get(&format!("docs/{}", name))
+            .ok_or_else(not_a_bundle)?;
+        if docs_db::find_docs_db(&project_root).is_some() {
+            println!(
+                "{}",
+                "Kept this project's docs database; the bundle's docs were not restored.".dimmed()
+            );
+        } else {
+            std::fs::write(project_root.join(file_name), data)?;
+            println!(
+                "{} Restored docs database {}",
+                "✓".green(),
+                file_name.to_string_lossy()
+            );
+        }
+    }
+
+    if created {
+        if let Err(e) = register::run(None) {
+            println!("{} Could not register project: {}", "⚠".yellow(), e);
+        }
+    }
     Ok(())
 }
 
+/// Every file in a bundle, by path
+fn read_bundle(file: &Path) -> Result<HashMap<String, Vec<u8>>> {
+    let input =
+        std::fs::File::open(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let not_a_bundle = || {
+        ProjError::InvalidInput(format!(
+            "{} is not a proj snapshot bundle (.tar.zst).",
+            file.display()
+        ))
+    };
+    let decoder = zstd::Decoder::new(input)?;
+    let mut archive = tar::Archive::new(decoder);
+    let mut files = HashMap::new();
+    for entry in archive.entries().map_err(|_| not_a_bundle())? {
+        let mut entry = entry.map_err(|_| not_a_bundle())?;
+        let path = entry
+            .path()
+            .map_err(|_| not_a_bundle())?
+            .to_string_lossy()
+            .to_string();
+        let mut data = Vec::new();
+        entry.read_to_end(&mut data).map_err(|_| not_a_bundle())?;
+        files.insert(path, data);
+    }
+    Ok(files)
+}
+
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    } else {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    }
+}
+
 /// Load project configuration
 fn load_config() -> Result<ProjectConfig> {
     let config_path = get_config_path()?;
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
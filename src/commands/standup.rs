@@ -0,0 +1,333 @@
+// Standup command - summarize recent activity for pasting into chat
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use chrono::{Duration, NaiveDateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::config::Registry;
+use crate::database::open_database;
+use crate::git;
+use crate::paths::{get_project_root, get_registry_path, get_tracking_db_path};
+
+/// Default look-back window when no previous standup is recorded
+const DEFAULT_DAYS: u32 = 1;
+
+#[derive(Serialize)]
+struct StandupReport {
+    project: String,
+    since: String,
+    sessions: Vec<SessionItem>,
+    completed_tasks: Vec<TaskItem>,
+    decisions: Vec<DecisionItem>,
+    commits: Vec<CommitItem>,
+    in_progress: Vec<TaskItem>,
+    blockers: Vec<BlockerItem>,
+}
+
+#[derive(Serialize)]
+struct SessionItem {
+    id: i64,
+    ended_at: String,
+    summary: String,
+}
+
+#[derive(Serialize)]
+struct TaskItem {
+    id: i64,
+    description: String,
+}
+
+#[derive(Serialize)]
+struct DecisionItem {
+    id: i64,
+    topic: String,
+    decision: String,
+}
+
+#[derive(Serialize)]
+struct CommitItem {
+    hash: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct BlockerItem {
+    id: i64,
+    description: String,
+}
+
+impl StandupReport {
+    fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+            && self.completed_tasks.is_empty()
+            && self.decisions.is_empty()
+            && self.commits.is_empty()
+            && self.in_progress.is_empty()
+            && self.blockers.is_empty()
+    }
+}
+
+pub fn run(days: Option<u32>, all: bool, json: bool) -> Result<()> {
+    let mut reports = Vec::new();
+
+    if all {
+        let registry = load_registry()?;
+        for p in &registry.registered_projects {
+            let root = Path::new(&p.path);
+            let db_path = root.join(".tracking").join("tracking.db");
+            if !db_path.exists() {
+                continue;
+            }
+            let Ok(conn) = open_database(&db_path) else {
+                continue;
+            };
+            let _ = git::sync_recent_commits(&conn, root, 50);
+            if let Ok(report) = build_report(&conn, &p.name, days) {
+                let _ = record_standup(&conn);
+                reports.push(report);
+            }
+        }
+    } else {
+        let db_path = get_tracking_db_path()?;
+        let conn = open_database(&db_path)
+            .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+        let root = get_project_root()?;
+        let _ = git::sync_recent_commits(&conn, &root, 50);
+
+        let name = crate::config::ProjectConfig::load()
+            .map(|c| c.name)
+            .unwrap_or_else(|_| "project".to_string());
+        reports.push(build_report(&conn, &name, days)?);
+        record_standup(&conn)?;
+    }
+
+    if json {
+        if all {
+            println!("{}", serde_json::to_string_pretty(&reports)?);
+        } else if let Some(report) = reports.first() {
+            println!("{}", serde_json::to_string_pretty(report)?);
+        }
+        return Ok(());
+    }
+
+    let active: Vec<&StandupReport> = reports.iter().filter(|r| !r.is_empty()).collect();
+    if active.is_empty() {
+        println!("No activity to report.");
+        return Ok(());
+    }
+
+    for (i, report) in active.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        print_markdown(report);
+    }
+
+    Ok(())
+}
+
+/// Collect activity since the last standup (or the last `days` days)
+fn build_report(conn: &Connection, project: &str, days: Option<u32>) -> Result<StandupReport> {
+    let since = match days {
+        Some(d) => days_ago(d),
+        None => last_standup(conn).unwrap_or_else(|| days_ago(DEFAULT_DAYS)),
+    };
+
+    let sessions = {
+        let mut stmt = conn.prepare(
+            "SELECT session_id, ended_at, summary FROM sessions
+             WHERE status = 'completed' AND ended_at >= ?1 AND summary IS NOT NULL
+             ORDER BY ended_at",
+        )?;
+        let rows = stmt.query_map([&since], |row| {
+            Ok(SessionItem {
+                id: row.get(0)?,
+                ended_at: row.get(1)?,
+                summary: row.get(2)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let completed_tasks = {
+        let mut stmt = conn.prepare(
+            "SELECT task_id, description FROM tasks
+             WHERE status = 'completed' AND completed_at >= ?1
+             ORDER BY completed_at",
+        )?;
+        let rows = stmt.query_map([&since], |row| {
+            Ok(TaskItem {
+                id: row.get(0)?,
+                description: row.get(1)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let decisions = {
+        let mut stmt = conn.prepare(
+            "SELECT decision_id, topic, decision FROM decisions
+             WHERE status = 'active' AND created_at >= ?1
+             ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([&since], |row| {
+            Ok(DecisionItem {
+                id: row.get(0)?,
+                topic: row.get(1)?,
+                decision: row.get(2)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    // Commit times are stored in the author's local time, not UTC
+    let commits = git::get_commits_since(conn, &utc_to_local(&since))
+        .unwrap_or_default()
+        .into_iter()
+        .map(|c| CommitItem {
+            hash: c.short_hash,
+            message: c.message,
+        })
+        .collect();
+
+    let in_progress = {
+        let mut stmt = conn.prepare(
+            "SELECT task_id, description FROM tasks WHERE status = 'in_progress' ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(TaskItem {
+                id: row.get(0)?,
+                description: row.get(1)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    let blockers = {
+        let mut stmt = conn.prepare(
+            "SELECT blocker_id, description FROM blockers WHERE status = 'active' ORDER BY created_at",
+        )?;
+        let rows = stmt.query_map([], |row| {
+            Ok(BlockerItem {
+                id: row.get(0)?,
+                description: row.get(1)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>()?
+    };
+
+    Ok(StandupReport {
+        project: project.to_string(),
+        since,
+        sessions,
+        completed_tasks,
+        decisions,
+        commits,
+        in_progress,
+        blockers,
+    })
+}
+
+/// Print a report as Slack-friendly markdown
+fn print_markdown(report: &StandupReport) {
+    println!(
+        "*{}* standup (since {})",
+        report.project,
+        utc_to_local(&report.since)
+            .get(..16)
+            .unwrap_or(&report.since)
+    );
+
+    if !report.completed_tasks.is_empty() || !report.sessions.is_empty() {
+        println!("*Done*");
+        for t in &report.completed_tasks {
+            println!("- #{} {}", t.id, t.description);
+        }
+        for s in &report.sessions {
+            println!("- {}", s.summary);
+        }
+    }
+
+    if !report.decisions.is_empty() {
+        println!("*Decisions*");
+        for d in &report.decisions {
+            println!("- {}: {}", d.topic, d.decision);
+        }
+    }
+
+    if !report.commits.is_empty() {
+        println!("*Commits*");
+        for c in &report.commits {
+            println!("- `{}` {}", c.hash, c.message);
+        }
+    }
+
+    if !report.in_progress.is_empty() {
+        println!("*In progress*");
+        for t in &report.in_progress {
+            println!("- #{} {}", t.id, t.description);
+        }
+    }
+
+    if !report.blockers.is_empty() {
+        println!("*Blockers*");
+        for b in &report.blockers {
+            println!("- #{} {}", b.id, b.description);
+        }
+    }
+}
+
+/// When the last standup was generated (UTC), if ever
+fn last_standup(conn: &Connection) -> Option<String> {
+    conn.query_row(
+        "SELECT value FROM project_meta WHERE key = 'last_standup'",
+        [],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+/// Remember this standup so the next one starts where it left off
+fn record_standup(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO project_meta (key, value, updated_at) VALUES ('last_standup', datetime('now'), datetime('now'))",
+        [],
+    )?;
+    Ok(())
+}
+
+fn days_ago(days: u32) -> String {
+    (Utc::now() - Duration::days(days as i64))
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+/// Convert a SQLite UTC timestamp to local time in the same format
+fn utc_to_local(s: &str) -> String {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S")
+        .map(|dt| {
+            dt.and_utc()
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M:%S")
+                .to_string()
+        })
+        .unwrap_or_else(|_| s.to_string())
+}
+
+/// Load the global registry
+fn load_registry() -> Result<Registry> {
+    let registry_path = get_registry_path()?;
+
+    if !registry_path.exists() {
+        return Ok(Registry::default());
+    }
+
+    let content =
+        std::fs::read_to_string(&registry_path).with_context(|| "Failed to read registry.json")?;
+    let registry: Registry =
+        serde_json::from_str(&content).with_context(|| "Failed to parse registry.json")?;
+    Ok(registry)
+}
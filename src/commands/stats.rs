@@ -0,0 +1,716 @@
+// Stats command - velocity and activity metrics over the last few weeks, or with
+// --usage, how often each command ran and what it cost (recorded with usage_metrics)
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use chrono::{Datelike, Duration, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use colored::Colorize;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::database::{get_schema_version, open_database};
+use crate::error::ProjError;
+use crate::git;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::text::truncate;
+
+/// Topics listed under "Decisions by topic"
+const TOP_TOPICS: usize = 10;
+
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+#[derive(Serialize)]
+struct StatsReport {
+    project: String,
+    /// Monday of the first week counted
+    since: String,
+    weeks: Vec<WeekStats>,
+    sessions: SessionStats,
+    tasks: TaskStats,
+    blockers: BlockerStats,
+    decisions_by_topic: Vec<TopicCount>,
+    commits: CommitStats,
+}
+
+#[derive(Serialize, Default, Clone)]
+struct WeekStats {
+    week_start: String,
+    sessions: usize,
+    session_hours: f64,
+    tasks_completed: usize,
+    decisions: usize,
+    blockers_resolved: usize,
+    commits: usize,
+}
+
+#[derive(Serialize)]
+struct SessionStats {
+    total: usize,
+    total_hours: f64,
+    avg_minutes: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct TaskStats {
+    completed: usize,
+    per_week: f64,
+    open: i64,
+    /// From creation to completion
+    avg_days_to_complete: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct BlockerStats {
+    resolved: usize,
+    active: i64,
+    avg_hours_to_resolve: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct TopicCount {
+    topic: String,
+    count: usize,
+}
+
+#[derive(Serialize)]
+struct CommitStats {
+    total: usize,
+    /// Commits made while a session was open
+    during_sessions: usize,
+    per_session: Option<f64>,
+    /// Pearson correlation of weekly session hours and weekly commits
+    hours_correlation: Option<f64>,
+}
+
+#[derive(Serialize)]
+struct UsageReport {
+    project: String,
+    since: String,
+    calls: usize,
+    commands: Vec<CommandUsage>,
+}
+
+/// One recorded run of a command
+struct Call {
+    duration_ms: i64,
+    output_tokens: Option<i64>,
+    terminal: bool,
+    success: bool,
+}
+
+/// Calls of one command with one set of options
+#[derive(Serialize)]
+struct CommandUsage {
+    command: String,
+    flags: Option<String>,
+    calls: usize,
+    /// Calls whose output went to a pipe or file rather than a terminal
+    agent_calls: usize,
+    failures: usize,
+    avg_ms: f64,
+    p95_ms: i64,
+    /// Estimated tokens per call, over the calls whose output was counted
+    avg_tokens: Option<f64>,
+    total_tokens: i64,
+}
+
+pub fn run(weeks: u32, usage: bool, json: bool) -> Result<()> {
+    if weeks == 0 {
+        bail!("--weeks must be at least 1");
+    }
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    if usage {
+        return run_usage(&conn, weeks, json);
+    }
+    if let Ok(root) = get_project_root() {
+        let _ = git::sync_recent_commits(&conn, &root, 200);
+    }
+
+    let project = crate::config::ProjectConfig::load()
+        .map(|c| c.name)
+        .unwrap_or_else(|_| "project".to_string());
+    let report = build_report(&conn, &project, weeks)?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report);
+    }
+    Ok(())
+}
+
+/// Monday of the first of the last `weeks` weeks, this one included
+fn window_start(weeks: u32) -> NaiveDate {
+    let today = Utc::now().date_naive();
+    let this_monday = today - Duration::days(today.weekday().num_days_from_monday() as i64);
+    this_monday - Duration::weeks(weeks as i64 - 1)
+}
+
+fn build_report(conn: &Connection, project: &str, weeks: u32) -> Result<StatsReport> {
+    let start = window_start(weeks);
+    let since = format!("{} 00:00:00", start);
+
+    let mut week_stats: Vec<WeekStats> = (0..weeks)
+        .map(|i| WeekStats {
+            week_start: (start + Duration::weeks(i as i64)).to_string(),
+            ..Default::default()
+        })
+        .collect();
+    let week_of = |t: &NaiveDateTime| -> Option<usize> {
+        let days = (t.date() - start).num_days();
+        (days >= 0).then_some((days / 7) as usize)
+    };
+
+    // Finished sessions: (start, end)
+    let sessions: Vec<(NaiveDateTime, NaiveDateTime)> = query_strings(
+        conn,
+        "SELECT started_at, ended_at FROM sessions
+         WHERE status = 'completed' AND ended_at IS NOT NULL AND started_at >= ?1
+         ORDER BY started_at",
+        &since,
+        2,
+    )?
+    .into_iter()
+    .filter_map(|row| Some((parse_utc(&row[0])?, parse_utc(&row[1])?)))
+    .filter(|(s, e)| e >= s)
+    .collect();
+
+    let mut total_minutes = 0.0;
+    for (s, e) in &sessions {
+        let minutes = (*e - *s).num_seconds() as f64 / 60.0;
+        total_minutes += minutes;
+        if let Some(w) = week_of(s).and_then(|w| week_stats.get_mut(w)) {
+            w.sessions += 1;
+            w.session_hours += minutes / 60.0;
+        }
+    }
+
+    // Completed tasks: (created, completed)
+    let completed: Vec<(Option<NaiveDateTime>, NaiveDateTime)> = query_strings(
+        conn,
+        "SELECT created_at, completed_at FROM tasks
+         WHERE status = 'completed' AND completed_at >= ?1",
+        &since,
+        2,
+    )?
+    .into_iter()
+    .filter_map(|row| Some((parse_utc(&row[0]), parse_utc(&row[1])?)))
+    .collect();
+    for (_, done) in &completed {
+        if let Some(w) = week_of(done).and_then(|w| week_stats.get_mut(w)) {
+            w.tasks_completed += 1;
+        }
+    }
+    let completion_days: Vec<f64> = completed
+        .iter()
+        .filter_map(|(created, done)| {
+            created.map(|c| (*done - c).num_seconds().max(0) as f64 / 86400.0)
+        })
+        .collect();
+    let open_tasks: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM tasks WHERE status NOT IN ('completed', 'cancelled')",
+        [],
+        |row| row.get(0),
+    )?;
+
+    // Blockers resolved in the window: (created, resolved)
+    let resolved: Vec<(NaiveDateTime, NaiveDateTime)> = query_strings(
+        conn,
+        "SELECT created_at, resolved_at FROM blockers
+         WHERE status = 'resolved' AND resolved_at >= ?1",
+        &since,
+        2,
+    )?
+    .into_iter()
+    .filter_map(|row| Some((parse_utc(&row[0])?, parse_utc(&row[1])?)))
+    .collect();
+    for (_, r) in &resolved {
+        if let Some(w) = week_of(r).and_then(|w| week_stats.get_mut(w)) {
+            w.blockers_resolved += 1;
+        }
+    }
+    let resolve_hours: Vec<f64> = resolved
+        .iter()
+        .map(|(c, r)| (*r - *c).num_seconds().max(0) as f64 / 3600.0)
+        .collect();
+    let active_blockers: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM blockers WHERE status = 'active'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    // Decisions by topic
+    let decisions = query_strings(
+        conn,
+        "SELECT topic, created_at FROM decisions WHERE created_at >= ?1",
+        &since,
+        2,
+    )?;
+    let mut topics: BTreeMap<String, usize> = BTreeMap::new();
+    for row in &decisions {
+        *topics.entry(row[0].clone()).or_default() += 1;
+        if let Some(w) = parse_utc(&row[1])
+            .as_ref()
+            .and_then(week_of)
+            .and_then(|w| week_stats.get_mut(w))
+        {
+            w.decisions += 1;
+        }
+    }
+    let mut decisions_by_topic: Vec<TopicCount> = topics
+        .into_iter()
+        .map(|(topic, count)| TopicCount { topic, count })
+        .collect();
+    decisions_by_topic.sort_by(|a, b| b.count.cmp(&a.count).then(a.topic.cmp(&b.topic)));
+    decisions_by_topic.truncate(TOP_TOPICS);
+
+    // Commit times are stored in the author's local time, not UTC
+    let commits: Vec<NaiveDateTime> = query_strings(
+        conn,
+        "SELECT committed_at FROM git_commits WHERE committed_at >= ?1",
+        &local_date(&since),
+        1,
+    )?
+    .into_iter()
+    .filter_map(|row| parse_local(&row[0]))
+    .filter(|t| t.date() >= start)
+    .collect();
+    let mut during_sessions = 0;
+    for c in &commits {
+        if let Some(w) = week_of(c).and_then(|w| week_stats.get_mut(w)) {
+            w.commits += 1;
+        }
+        if sessions.iter().any(|(s, e)| s <= c && c <= e) {
+            during_sessions += 1;
+        }
+    }
+    let hours: Vec<f64> = week_stats.iter().map(|w| w.session_hours).collect();
+    let commit_counts: Vec<f64> = week_stats.iter().map(|w| w.commits as f64).collect();
+
+    for w in &mut week_stats {
+        w.session_hours = round1(w.session_hours);
+    }
+
+    Ok(StatsReport {
+        project: project.to_string(),
+        since: start.to_string(),
+        sessions: SessionStats {
+            total: sessions.len(),
+            total_hours: round1(total_minutes / 60.0),
+            avg_minutes: (!sessions.is_empty())
+                .then(|| round1(total_minutes / sessions.len() as f64)),
+        },
+        tasks: TaskStats {
+            completed: completed.len(),
+            per_week: round1(completed.len() as f64 / weeks as f64),
+            open: open_tasks,
+            avg_days_to_complete: mean(&completion_days).map(round1),
+        },
+        blockers: BlockerStats {
+            resolved: resolved.len(),
+            active: active_blockers,
+            avg_hours_to_resolve: mean(&resolve_hours).map(round1),
+        },
+        decisions_by_topic,
+        commits: CommitStats {
+            total: commits.len(),
+            during_sessions,
+            per_session: (!sessions.is_empty())
+                .then(|| round1(during_sessions as f64 / sessions.len() as f64)),
+            hours_correlation: pearson(&hours, &commit_counts).map(|r| (r * 100.0).round() / 100.0),
+        },
+        weeks: week_stats,
+    })
+}
+
+fn print_report(report: &StatsReport) {
+    println!(
+        "{}",
+        format!(
+            "Project Stats: {} ({} weeks since {})",
+            report.project,
+            report.weeks.len(),
+            report.since
+        )
+        .bold()
+    );
+    println!();
+
+    // Weekly trends
+    println!("{}", "Weekly".bold());
+    let rows: [(&str, Vec<f64>); 5] = [
+        (
+            "Sessions",
+            report.weeks.iter().map(|w| w.sessions as f64).collect(),
+        ),
+        (
+            "Hours",
+            report.weeks.iter().map(|w| w.session_hours).collect(),
+        ),
+        (
+            "Tasks done",
+            report
+                .weeks
+                .iter()
+                .map(|w| w.tasks_completed as f64)
+                .collect(),
+        ),
+        (
+            "Decisions",
+            report.weeks.iter().map(|w| w.decisions as f64).collect(),
+        ),
+        (
+            "Commits",
+            report.weeks.iter().map(|w| w.commits as f64).collect(),
+        ),
+    ];
+    for (label, values) in &rows {
+        let total: f64 = values.iter().sum();
+        let last = values.last().copied().unwrap_or(0.0);
+        println!(
+            "  {:<12} {}  total {:<6} this week {}",
+            label,
+            sparkline(values).cyan(),
+            format_number(total),
+            format_number(last)
+        );
+    }
+    println!();
+
+    println!("{}", "Sessions".bold());
+    println!(
+        "  {} finished, {}h total, avg length {}",
+        report.sessions.total,
+        format_number(report.sessions.total_hours),
+        report
+            .sessions
+            .avg_minutes
+            .map(format_duration)
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!();
+
+    println!("{}", "Tasks".bold());
+    println!(
+        "  {} completed ({}/week), {} open, avg {} to complete",
+        report.tasks.completed,
+        format_number(report.tasks.per_week),
+        report.tasks.open,
+        report
+            .tasks
+            .avg_days_to_complete
+            .map(|d| format_duration(d * 24.0 * 60.0))
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!();
+
+    println!("{}", "Blockers".bold());
+    println!(
+        "  {} resolved, {} active, avg {} to resolve",
+        report.blockers.resolved,
+        report.blockers.active,
+        report
+            .blockers
+            .avg_hours_to_resolve
+            .map(|h| format_duration(h * 60.0))
+            .unwrap_or_else(|| "-".to_string())
+    );
+    println!();
+
+    println!("{}", "Decisions by topic".bold());
+    if report.decisions_by_topic.is_empty() {
+        println!("  {}", "(none)".dimmed());
+    }
+    let max = report
+        .decisions_by_topic
+        .first()
+        .map(|t| t.count)
+        .unwrap_or(1);
+    for t in &report.decisions_by_topic {
+        let width = (t.count * 20 / max).max(1);
+        println!(
+            "  {:<20} {} {}",
+            truncate(&t.topic, 20),
+            "█".repeat(width).cyan(),
+            t.count
+        );
+    }
+    println!();
+
+    println!("{}", "Commits".bold());
+    let c = &report.commits;
+    let share = (c.during_sessions * 100)
+        .checked_div(c.total)
+        .map(|pct| format!(" ({}%)", pct))
+        .unwrap_or_default();
+    println!(
+        "  {} total, {} during sessions{}, {} per session",
+        c.total,
+        c.during_sessions,
+        share,
+        c.per_session
+            .map(format_number)
+            .unwrap_or_else(|| "-".to_string())
+    );
+    if let Some(r) = c.hours_correlation {
+        let strength = match r.abs() {
+            x if x >= 0.7 => "strong",
+            x if x >= 0.4 => "moderate",
+            _ => "weak",
+        };
+        println!(
+            "  Weekly session hours vs commits: r = {:.2} ({})",
+            r, strength
+        );
+    }
+}
+
+fn run_usage(conn: &Connection, weeks: u32, json: bool) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='command_metrics')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.19".to_string(),
+        }
+        .into());
+    }
+
+    let config = crate::config::ProjectConfig::load().ok();
+    let start = window_start(weeks);
+    let report = build_usage(
+        conn,
+        config.as_ref().map_or("project", |c| c.name.as_str()),
+        start,
+    )?;
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+        return Ok(());
+    }
+    if report.commands.is_empty() {
+        if config.is_some_and(|c| c.usage_metrics) {
+            println!("No commands recorded since {}.", report.since);
+        } else {
+            println!(
+                "No usage recorded. Turn it on with '{}'.",
+                "proj config set usage_metrics true".cyan()
+            );
+        }
+        return Ok(());
+    }
+    print_usage(&report, weeks);
+    Ok(())
+}
+
+fn build_usage(conn: &Connection, project: &str, start: NaiveDate) -> Result<UsageReport> {
+    let mut stmt = conn.prepare(
+        "SELECT command, flags, duration_ms, output_tokens, terminal, success
+         FROM command_metrics WHERE timestamp >= ?1",
+    )?;
+    let rows = stmt
+        .query_map([format!("{} 00:00:00", start)], |row| {
+            Ok((
+                (row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?),
+                Call {
+                    duration_ms: row.get(2)?,
+                    output_tokens: row.get(3)?,
+                    terminal: row.get(4)?,
+                    success: row.get(5)?,
+                },
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    let total = rows.len();
+
+    // Calls grouped by command and options
+    let mut groups: BTreeMap<(String, Option<String>), Vec<Call>> = BTreeMap::new();
+    for (key, call) in rows {
+        groups.entry(key).or_default().push(call);
+    }
+
+    let mut commands: Vec<CommandUsage> = groups
+        .into_iter()
+        .map(|((command, flags), calls)| {
+            let mut durations: Vec<i64> = calls.iter().map(|c| c.duration_ms).collect();
+            durations.sort_unstable();
+            let p95 = durations[(durations.len() * 95).saturating_sub(1) / 100];
+            let tokens: Vec<f64> = calls
+                .iter()
+                .filter_map(|c| c.output_tokens)
+                .map(|t| t as f64)
+                .collect();
+            CommandUsage {
+                command,
+                flags,
+                calls: calls.len(),
+                agent_calls: calls.iter().filter(|c| !c.terminal).count(),
+                failures: calls.iter().filter(|c| !c.success).count(),
+                avg_ms: round1(durations.iter().sum::<i64>() as f64 / durations.len() as f64),
+                p95_ms: p95,
+                avg_tokens: mean(&tokens).map(round1),
+                total_tokens: tokens.iter().sum::<f64>() as i64,
+            }
+        })
+        .collect();
+    commands.sort_by(|a, b| {
+        b.calls
+            .cmp(&a.calls)
+            .then(b.total_tokens.cmp(&a.total_tokens))
+    });
+
+    Ok(UsageReport {
+        project: project.to_string(),
+        since: start.to_string(),
+        calls: total,
+        commands,
+    })
+}
+
+fn print_usage(report: &UsageReport, weeks: u32) {
+    println!(
+        "{}",
+        format!(
+            "Command Usage: {} ({} weeks since {}, {} calls)",
+            report.project, weeks, report.since, report.calls
+        )
+        .bold()
+    );
+    println!();
+    println!(
+        "  {:<32} {:>6} {:>6} {:>8} {:>8} {:>10} {:>12}",
+        "Command", "Calls", "Agent", "Avg ms", "p95 ms", "Avg tokens", "Total tokens"
+    );
+    for c in &report.commands {
+        let name = match &c.flags {
+            Some(flags) => format!("{} {}", c.command, flags),
+            None => c.command.clone(),
+        };
+        let mut line = format!(
+            "  {:<32} {:>6} {:>6} {:>8} {:>8} {:>10} {:>12}",
+            truncate(&name, 32),
+            c.calls,
+            c.agent_calls,
+            format_number(c.avg_ms),
+            c.p95_ms,
+            c.avg_tokens
+                .map(format_number)
+                .unwrap_or_else(|| "-".to_string()),
+            c.total_tokens
+        );
+        if c.failures > 0 {
+            line.push_str(&format!(" {}", format!("({} failed)", c.failures).red()));
+        }
+        println!("{}", line);
+    }
+    println!();
+    println!(
+        "{}",
+        "Agent: output went to a pipe or file. Tokens are estimated (4 characters each) for those calls, on macOS and Linux."
+            .dimmed()
+    );
+}
+
+/// Rows of `columns` text values for a query taking one date parameter (NULLs become "")
+fn query_strings(
+    conn: &Connection,
+    sql: &str,
+    since: &str,
+    columns: usize,
+) -> Result<Vec<Vec<String>>> {
+    let mut stmt = conn.prepare(sql)?;
+    let rows = stmt
+        .query_map([since], |row| {
+            (0..columns)
+                .map(|i| Ok(row.get::<_, Option<String>>(i)?.unwrap_or_default()))
+                .collect::<Result<Vec<_>, rusqlite::Error>>()
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+/// SQLite UTC timestamp
+fn parse_utc(s: &str) -> Option<NaiveDateTime> {
+    NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok()
+}
+
+/// Local-time commit timestamp, converted to UTC
+fn parse_local(s: &str) -> Option<NaiveDateTime> {
+    let local = NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").ok()?;
+    chrono::Local
+        .from_local_datetime(&local)
+        .earliest()
+        .map(|dt| dt.naive_utc())
+}
+
+/// A UTC timestamp as a local date, for comparing against commit times (a day early, to be safe)
+fn local_date(utc: &str) -> String {
+    parse_utc(utc)
+        .map(|dt| (dt - Duration::days(1)).format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| utc.to_string())
+}
+
+fn mean(values: &[f64]) -> Option<f64> {
+    (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+}
+
+/// Pearson correlation; None when either series is constant or too short
+fn pearson(x: &[f64], y: &[f64]) -> Option<f64> {
+    if x.len() != y.len() || x.len() < 3 {
+        return None;
+    }
+    let (mx, my) = (mean(x)?, mean(y)?);
+    let cov: f64 = x.iter().zip(y).map(|(a, b)| (a - mx) * (b - my)).sum();
+    let vx: f64 = x.iter().map(|a| (a - mx).powi(2)).sum();
+    let vy: f64 = y.iter().map(|b| (b - my).powi(2)).sum();
+    if vx == 0.0 || vy == 0.0 {
+        return None;
+    }
+    Some(cov / (vx.sqrt() * vy.sqrt()))
+}
+
+fn sparkline(values: &[f64]) -> String {
+    let max = values.iter().cloned().fold(0.0, f64::max);
+    values
+        .iter()
+        .map(|v| {
+            if max <= 0.0 {
+                SPARK_CHARS[0]
+            } else {
+                let level = (v / max * (SPARK_CHARS.len() - 1) as f64).round() as usize;
+                SPARK_CHARS[level.min(SPARK_CHARS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+fn round1(x: f64) -> f64 {
+    (x * 10.0).round() / 10.0
+}
+
+/// Whole numbers without decimals, everything else with one
+fn format_number(x: f64) -> String {
+    if x.fract() == 0.0 {
+        format!("{}", x as i64)
+    } else {
+        format!("{:.1}", x)
+    }
+}
+
+/// Minutes as "45m", "3h 20m", or "2.5d"
+fn format_duration(minutes: f64) -> String {
+    let minutes = minutes.round() as i64;
+    if minutes < 60 {
+        format!("{}m", minutes)
+    } else if minutes < 48 * 60 {
+        format!("{}h {}m", minutes / 60, minutes % 60)
+    } else {
+        format!("{:.1}d", minutes as f64 / (24.0 * 60.0))
+    }
+}
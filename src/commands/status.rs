@@ -1,18 +1,29 @@
 // Status command - tiered context output with first-run enforcement
 
+use std::fmt::Write;
+use std::path::Path;
+
 use anyhow::{Context, Result};
 use colored::Colorize;
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
 
-use crate::commands::update_check;
+use crate::commands::task::format_due;
+use crate::commands::{approve, scan_todos, task_import, update_check};
 use crate::config::ProjectConfig;
 use crate::database::open_database;
+use crate::error::ProjError;
 use crate::git;
-use crate::models::{Blocker, Decision, Question, Task};
+use crate::nudges;
+use crate::output_cache;
 use crate::paths::{get_config_path, get_project_root, get_tracking_db_path};
+use crate::porcelain;
+use crate::repository::Repository;
 use crate::session::{
-    get_last_completed_session, get_or_create_session_with_info, mark_full_context_shown,
+    get_last_completed_session, get_or_create_session_with_info, get_paused_sessions,
+    mark_full_context_shown, track_branch,
 };
+use crate::text::truncate;
+use crate::workspace;
 
 /// Status tier levels
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -27,7 +38,11 @@ pub enum StatusTier {
     Full,
 }
 
-pub fn run(quiet: bool, verbose: bool, full: bool) -> Result<()> {
+pub fn run(quiet: bool, verbose: bool, full: bool, workspace: bool) -> Result<()> {
+    if workspace {
+        return workspace_status();
+    }
+
     // Determine requested tier from flags
     let requested_tier = if quiet {
         StatusTier::Micro
@@ -56,6 +71,13 @@ pub fn run(quiet: bool, verbose: bool, full: bool) -> Result<()> {
                 // Silent fail - don't block status for this
                 eprintln!("{} Could not create AGENTS.md: {}", "⚠".yellow(), e);
             }
+        } else if cfg!(windows) {
+            // Without symlinks, CLAUDE.md/GEMINI.md are hard links or copies that can drift
+            match crate::commands::init::sync_agent_files(&root) {
+                Ok(Some(name)) => eprintln!("{} Synced {} edits into AGENTS.md", "✓".green(), name),
+                Ok(None) => {}
+                Err(e) => eprintln!("{} Could not sync AGENTS.md copies: {}", "⚠".yellow(), e),
+            }
         }
     }
 
@@ -64,12 +86,11 @@ pub fn run(quiet: bool, verbose: bool, full: bool) -> Result<()> {
     let session = session_result.session;
 
     // If a stale session was auto-closed, notify the user
-    if let Some(closed) = session_result.auto_closed_session {
-        println!(
-            "{} Previous session #{} was stale (8+ hours). Auto-closed.",
-            "⚠".yellow(),
-            closed.session_id
-        );
+    if let Some(closed) = session_result
+        .auto_closed_session
+        .filter(|_| !porcelain::enabled())
+    {
+        println!("{} {}", "⚠".yellow(), crate::session::stale_notice(&closed));
         println!(
             "{} Started new session #{}",
             "✓".green(),
@@ -78,6 +99,16 @@ pub fn run(quiet: bool, verbose: bool, full: bool) -> Result<()> {
         println!();
     }
 
+    // Note a branch switch since the last command (also tags what gets logged next)
+    let branch = track_branch(&conn, session.session_id).unwrap_or(None);
+
+    // Scripts get one line and none of the notices (or the first-run full context)
+    if porcelain::enabled() {
+        let repo = Repository::begin(&conn)?;
+        output_porcelain(&repo, &session, branch.as_deref())?;
+        return repo.finish();
+    }
+
     // First-run enforcement: if full_context_shown is false, force Full tier
     let effective_tier = if !session.full_context_shown {
         StatusTier::Full
@@ -91,21 +122,88 @@ pub fn run(quiet: bool, verbose: bool, full: bool) -> Result<()> {
     // Check for schema upgrade (only shows on first context of session)
     update_check::check_schema_upgrade(&config.schema_version, session.full_context_shown);
 
-    // Output based on tier
-    match effective_tier {
-        StatusTier::Micro => output_tier0(&conn, &config, &session)?,
-        StatusTier::Minimal => output_tier1(&conn, &config, &session)?,
-        StatusTier::Working => output_tier2(&conn, &config, &session)?,
-        StatusTier::Full => {
-            output_tier3(&conn, &config, &session)?;
-            // Mark that full context has been shown this session
-            mark_full_context_shown(&conn, session.session_id)?;
+    // Keep source-todo tasks in sync with the code (opt-in)
+    if config.scan_todos_on_status {
+        if let Ok(root) = get_project_root() {
+            match scan_todos::scan(&conn, &root, session.session_id, branch.as_deref(), false) {
+                Ok(result) if result.has_changes() && effective_tier != StatusTier::Micro => {
+                    println!(
+                        "{} TODO scan: {} new, {} moved, {} closed",
+                        "✓".green(),
+                        result.created.len(),
+                        result.moved.len(),
+                        result.closed.len()
+                    );
+                }
+                Ok(_) => {}
+                Err(e) => eprintln!("{} TODO scan failed: {}", "⚠".yellow(), e),
+            }
+        }
+    }
+
+    // Refresh tasks imported from Jira/Linear once they're older than the refresh interval
+    if task_import::refresh_due(&conn, &config) {
+        match task_import::refresh_all(&conn, session.session_id, &config, false) {
+            Ok(result) if result.has_changes() && effective_tier != StatusTier::Micro => {
+                println!(
+                    "{} Imported tasks refreshed: {} new, {} updated",
+                    "✓".green(),
+                    result.created,
+                    result.updated
+                );
+            }
+            Ok(_) => {}
+            Err(e) => eprintln!("{} Task import refresh failed: {}", "⚠".yellow(), e),
         }
     }
 
-    // Show review nudge if commits > decisions (only for non-quiet modes)
+    // Output based on tier, reused from the last identical call if nothing has changed since
+    let cache = output_cache::Entry::new(
+        &config,
+        &format!(
+            "status {:?} #{} {}",
+            effective_tier,
+            session.session_id,
+            branch.as_deref().unwrap_or_default()
+        ),
+    );
+    match cache.as_ref().and_then(|c| c.hit()) {
+        Some(output) => print!("{}", output),
+        None => {
+            let mut out = String::new();
+            let b = branch.as_deref();
+            let repo = Repository::begin(&conn)?;
+            match effective_tier {
+                StatusTier::Micro => output_tier0(&mut out, &repo, &config, &session, b)?,
+                StatusTier::Minimal => output_tier1(&mut out, &repo, &config, &session, b)?,
+                StatusTier::Working => output_tier2(&mut out, &repo, &config, &session, b)?,
+                StatusTier::Full => output_tier3(&mut out, &repo, &config, &session, b)?,
+            }
+            repo.finish()?;
+            print!("{}", out);
+            if let Some(cache) = &cache {
+                cache.store(&out);
+            }
+        }
+    }
+    if !session.full_context_shown {
+        // Mark that full context has been shown this session
+        mark_full_context_shown(&conn, session.session_id)?;
+    }
+
+    // Reminders to log what this session is doing (only for non-quiet modes)
     if effective_tier != StatusTier::Micro {
-        show_review_nudge(&conn, session.session_id, &session.started_at)?;
+        show_nudges(&conn, &session, &config)?;
+        let pending = approve::pending_count(&conn);
+        if pending > 0 {
+            println!(
+                "{} {} agent write{} awaiting approval. Run '{}'.\n",
+                "⏸".yellow(),
+                pending,
+                if pending == 1 { "" } else { "s" },
+                "proj approve".cyan()
+            );
+        }
     }
 
     // Check for updates (cached, runs at most once per day)
@@ -114,45 +212,24 @@ pub fn run(quiet: bool, verbose: bool, full: bool) -> Result<()> {
     Ok(())
 }
 
-/// Show a nudge to review session if no decisions are logged
-fn show_review_nudge(
+/// Show the nudges whose rules fire for this session
+fn show_nudges(
     conn: &Connection,
-    session_id: i64,
-    started_at: &chrono::DateTime<chrono::Utc>,
+    session: &crate::models::Session,
+    config: &ProjectConfig,
 ) -> Result<()> {
-    // Count decisions logged this session
-    let decision_count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM decisions WHERE session_id = ?",
-            [session_id],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
-
-    // Count tasks added this session (for potential future use)
-    let _task_count: i64 = conn
-        .query_row(
-            "SELECT COUNT(*) FROM tasks WHERE session_id = ?",
-            [session_id],
-            |row| row.get(0),
-        )
-        .unwrap_or(0);
-
-    // Check session age - only nudge after some work has likely been done (30+ minutes)
-    let session_age = chrono::Utc::now() - *started_at;
-    let session_minutes = session_age.num_minutes();
-
-    // Show nudge if session is 30+ minutes old and no decisions logged
-    if session_minutes >= 30 && decision_count == 0 {
+    let nudges = nudges::evaluate(conn, session, config)?;
+    for nudge in &nudges {
         println!(
-            "{} Session active {}+ min, 0 decisions logged. Run '{}' to review.",
+            "{} {}. Run '{}'.",
             "💡".yellow(),
-            session_minutes,
-            "proj review".cyan()
+            nudge.message,
+            nudge.action.cyan()
         );
+    }
+    if !nudges.is_empty() {
         println!();
     }
-
     Ok(())
 }
 
@@ -162,72 +239,280 @@ fn load_config() -> Result<ProjectConfig> {
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| format!("Failed to read config at {:?}", config_path))?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
 
+/// One project's state in `proj status --workspace`
+struct MemberStatus {
+    path: String,
+    name: String,
+    /// Start of the active session, if one is open
+    active_since: Option<String>,
+    open_tasks: i64,
+    blockers: i64,
+    last_activity: Option<String>,
+}
+
+/// Aggregate view of the workspace containing this project and all of its members
+fn workspace_status() -> Result<()> {
+    let root = get_project_root()?;
+    let ws_root = workspace::find_workspace(&root).ok_or_else(|| {
+        ProjError::InvalidInput(
+            "Not inside a workspace. Run 'proj init --workspace' at the repository root."
+                .to_string(),
+        )
+    })?;
+    let config = workspace::read_config(&ws_root)?;
+    let ws_name = config
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    // Projects created without 'proj init' (cloned, copied in) count too
+    let mut members = workspace::members_of(&config);
+    members.extend(workspace::discover_members(&ws_root));
+    members.sort();
+    members.dedup();
+
+    let mut rows = vec![Ok(member_status(&ws_root, "."))];
+    for member in &members {
+        let path = ws_root.join(member);
+        rows.push(if path.join(".tracking").is_dir() {
+            Ok(member_status(&path, member))
+        } else {
+            Err(member.clone())
+        });
+    }
+
+    if porcelain::enabled() {
+        for row in &rows {
+            match row {
+                Ok(m) => porcelain::print(&[
+                    &m.path,
+                    &m.name,
+                    if m.active_since.is_some() {
+                        "active"
+                    } else {
+                        ""
+                    },
+                    &m.open_tasks.to_string(),
+                    &m.blockers.to_string(),
+                    m.last_activity.as_deref().unwrap_or_default(),
+                ]),
+                Err(path) => porcelain::print(&[path, "", "missing", "", "", ""]),
+            }
+        }
+        return Ok(());
+    }
+
+    println!(
+        "\nWorkspace: {} ({} member project{})\n",
+        ws_name.bold(),
+        members.len(),
+        if members.len() == 1 { "" } else { "s" }
+    );
+    let (mut tasks, mut blockers, mut active) = (0, 0, 0);
+    for row in &rows {
+        let m = match row {
+            Ok(m) => m,
+            Err(path) => {
+                println!(
+                    "  {} {:<24} {}",
+                    "✗".red(),
+                    path,
+                    "no .tracking/ here".red()
+                );
+                continue;
+            }
+        };
+        tasks += m.open_tasks;
+        blockers += m.blockers;
+        let marker = if m.active_since.is_some() {
+            active += 1;
+            "●".green()
+        } else {
+            "○".dimmed()
+        };
+        let blocker_text = format!("{} blocker(s)", m.blockers);
+        println!(
+            "  {} {:<24} {:<20} {:>4} open task(s)  {}  {}",
+            marker,
+            m.path,
+            m.name,
+            m.open_tasks,
+            if m.blockers > 0 {
+                blocker_text.red()
+            } else {
+                blocker_text.normal()
+            },
+            match (&m.active_since, &m.last_activity) {
+                (Some(since), _) => format!("session since {}", since),
+                (None, Some(last)) => format!("last activity {}", last),
+                (None, None) => "no activity".to_string(),
+            }
+            .dimmed()
+        );
+    }
+    println!(
+        "\n  Total: {} open task(s), {} active blocker(s), {} active session(s)",
+        tasks, blockers, active
+    );
+    Ok(())
+}
+
+/// Read a project's counts without touching its database (read-only, no session start)
+fn member_status(project_root: &Path, rel: &str) -> MemberStatus {
+    let name = workspace::read_config(project_root)
+        .ok()
+        .and_then(|c| c.get("name").and_then(|v| v.as_str()).map(str::to_string))
+        .unwrap_or_default();
+    let conn = Connection::open_with_flags(
+        project_root.join(".tracking").join("tracking.db"),
+        OpenFlags::SQLITE_OPEN_READ_ONLY,
+    )
+    .ok();
+    let query = |sql: &str| -> Option<String> {
+        conn.as_ref()?
+            .query_row(sql, [], |row| row.get::<_, Option<String>>(0))
+            .ok()
+            .flatten()
+    };
+    let count = |sql: &str| -> i64 {
+        conn.as_ref()
+            .and_then(|c| c.query_row(sql, [], |row| row.get(0)).ok())
+            .unwrap_or(0)
+    };
+
+    MemberStatus {
+        path: rel.to_string(),
+        name,
+        active_since: query("SELECT MAX(started_at) FROM sessions WHERE status = 'active'"),
+        open_tasks: count(
+            "SELECT COUNT(*) FROM tasks WHERE status NOT IN ('completed', 'cancelled')",
+        ),
+        blockers: count("SELECT COUNT(*) FROM blockers WHERE status = 'active'"),
+        last_activity: query(
+            "SELECT MAX(t) FROM (
+                SELECT MAX(COALESCE(ended_at, started_at)) AS t FROM sessions
+                UNION ALL
+                SELECT MAX(timestamp) FROM activity_log
+            )",
+        ),
+    }
+}
+
+/// Porcelain: session ID, session name, open tasks, overdue tasks, active blockers, branch
+fn output_porcelain(
+    repo: &Repository,
+    session: &crate::models::Session,
+    branch: Option<&str>,
+) -> Result<()> {
+    let (open, overdue) = repo.open_task_counts()?;
+    porcelain::print(&[
+        &session.session_id.to_string(),
+        session.name.as_deref().unwrap_or_default(),
+        &open.to_string(),
+        &overdue.to_string(),
+        &repo.active_blocker_count()?.to_string(),
+        branch.unwrap_or_default(),
+    ]);
+    Ok(())
+}
+
 /// Tier 0: Micro context (~10 tokens)
 fn output_tier0(
-    conn: &Connection,
+    out: &mut String,
+    repo: &Repository,
     config: &ProjectConfig,
     session: &crate::models::Session,
+    branch: Option<&str>,
 ) -> Result<()> {
     let mut parts = vec![format!("{} [#{}]", config.name, session.session_id)];
+    if let Some(b) = branch {
+        parts.push(format!("on {}", b));
+    }
 
     // Add current task if any
-    if let Some(task) = get_priority_task(conn)? {
+    if let Some(task) = repo.next_task()? {
         parts.push(format!("Task: {}", truncate(&task.description, 30)));
     }
 
     // Add blocker count if any
-    let blocker_count = get_active_blocker_count(conn)?;
+    let blocker_count = repo.active_blocker_count()?;
     if blocker_count > 0 {
         parts.push(format!("{} blocker(s)", blocker_count));
     }
 
-    println!("{}", parts.join(" | "));
+    writeln!(out, "{}", parts.join(" | "))?;
     Ok(())
 }
 
 /// Tier 1: Minimal context (~50 tokens)
 fn output_tier1(
-    conn: &Connection,
+    out: &mut String,
+    repo: &Repository,
     config: &ProjectConfig,
     session: &crate::models::Session,
+    branch: Option<&str>,
 ) -> Result<()> {
     // Header
-    println!("{}", "=".repeat(60));
-    println!("PROJECT: {}", config.name.bold());
-    println!("{}", "=".repeat(60));
-    println!();
-    println!("[Session #{} active]", session.session_id);
-    println!();
+    writeln!(out, "{}", "=".repeat(60))?;
+    writeln!(out, "PROJECT: {}", config.name.bold())?;
+    writeln!(out, "{}", "=".repeat(60))?;
+    writeln!(out)?;
+    let on_branch = branch.map(|b| format!(" on {}", b)).unwrap_or_default();
+    match &session.name {
+        Some(name) => writeln!(
+            out,
+            "[Session #{} ({}) active{}]",
+            session.session_id, name, on_branch
+        )?,
+        None => writeln!(out, "[Session #{} active{}]", session.session_id, on_branch)?,
+    }
+    let paused = get_paused_sessions(repo.conn())?;
+    if !paused.is_empty() {
+        let ids: Vec<String> = paused
+            .iter()
+            .map(|p| match &p.name {
+                Some(name) => format!("#{} ({})", p.session_id, name),
+                None => format!("#{}", p.session_id),
+            })
+            .collect();
+        writeln!(
+            out,
+            "[Paused: {} - 'proj session resume <id>']",
+            ids.join(", ")
+        )?;
+    }
+    writeln!(out)?;
 
     // Last session summary
-    if let Some(last) = get_last_completed_session(conn)? {
+    if let Some(last) = get_last_completed_session(repo.conn())? {
         if let Some(ended) = &last.ended_at {
-            println!("Last session ({}):", ended.format("%Y-%m-%d %H:%M"));
+            writeln!(out, "Last session ({}):", ended.format("%Y-%m-%d %H:%M"))?;
             if let Some(summary) = &last.summary {
-                println!("  {}", summary);
+                writeln!(out, "  {}", summary)?;
             }
-            println!();
+            writeln!(out)?;
         }
     }
 
     // Active blockers
-    let blockers = get_active_blockers(conn)?;
+    let blockers = repo.active_blockers()?;
     if !blockers.is_empty() {
-        println!("Blockers ({}):", blockers.len());
-        for b in &blockers {
-            println!("  {} {}", "✗".red(), b.description);
+        writeln!(out, "Blockers ({}):", blockers.len())?;
+        for b in blockers {
+            writeln!(out, "  {} {}", "✗".red(), b.description)?;
         }
-        println!();
+        writeln!(out)?;
     }
 
     // Priority tasks
-    let tasks = get_priority_tasks(conn, 3)?;
+    let tasks = repo.priority_tasks(3)?;
     if !tasks.is_empty() {
-        println!("Priority Tasks:");
+        writeln!(out, "Priority Tasks:")?;
         for t in &tasks {
             let status_icon = match t.status.as_str() {
                 "in_progress" => "◐",
@@ -239,10 +524,14 @@ fn output_tier1(
             } else {
                 ""
             };
-            println!(
-                "  {} [{}] {}{}",
-                status_icon, t.task_id, t.description, priority_marker
-            );
+            let due_display = format_due(t)
+                .map(|d| format!(" ({})", d))
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "  {} [{}] {}{}{}",
+                status_icon, t.task_id, t.description, priority_marker, due_display
+            )?;
         }
     }
 
@@ -251,69 +540,92 @@ fn output_tier1(
 
 /// Tier 2: Working context (~200 tokens)
 fn output_tier2(
-    conn: &Connection,
+    out: &mut String,
+    repo: &Repository,
     config: &ProjectConfig,
     session: &crate::models::Session,
+    branch: Option<&str>,
 ) -> Result<()> {
-    // Start with Tier 1 content
-    output_tier1(conn, config, session)?;
+    // Start with Tier 1 content; its priority tasks come from the full list shown below
+    repo.active_tasks()?;
+    output_tier1(out, repo, config, session, branch)?;
 
-    println!();
-    println!("{}", "-".repeat(40));
+    writeln!(out)?;
+    writeln!(out, "{}", "-".repeat(40))?;
 
     // Type and description
     if let Some(desc) = &config.description {
-        println!("Type: {} | {}", config.project_type, desc);
+        writeln!(out, "Type: {} | {}", config.project_type, desc)?;
     } else {
-        println!("Type: {}", config.project_type);
+        writeln!(out, "Type: {}", config.project_type)?;
     }
-    println!();
+    writeln!(out)?;
 
     // All active tasks (not just priority)
-    let tasks = get_active_tasks(conn)?;
+    let tasks = repo.active_tasks()?;
     if !tasks.is_empty() {
-        println!("All Active Tasks ({}):", tasks.len());
-        for t in &tasks {
+        writeln!(out, "All Active Tasks ({}):", tasks.len())?;
+        for t in tasks {
             let status_icon = match t.status.as_str() {
                 "in_progress" => "◐".yellow(),
                 "blocked" => "✗".red(),
                 "pending" => "○".white(),
                 _ => "○".white(),
             };
-            println!(
-                "  {} [{}] {} ({})",
-                status_icon, t.task_id, t.description, t.priority
-            );
+            let due_display = format_due(t)
+                .map(|d| format!(", {}", d))
+                .unwrap_or_default();
+            writeln!(
+                out,
+                "  {} [{}] {} ({}{})",
+                status_icon, t.task_id, t.description, t.priority, due_display
+            )?;
         }
-        println!();
+        writeln!(out)?;
     }
 
     // Recent decisions
-    let decisions = get_recent_decisions(conn, 5)?;
+    let decisions = repo.recent_decisions(5)?;
     if !decisions.is_empty() {
-        println!("Recent Decisions:");
+        writeln!(out, "Recent Decisions:")?;
         for d in &decisions {
-            println!("  • {}: {}", d.topic.bold(), truncate(&d.decision, 50));
+            writeln!(out, "  • {}: {}", d.topic.bold(), truncate(&d.decision, 50))?;
         }
-        println!();
+        writeln!(out)?;
+    }
+
+    // Context notes, counted by category (tier 3 lists them)
+    let note_counts = repo.note_category_counts()?;
+    if !note_counts.is_empty() {
+        let counts: Vec<String> = note_counts
+            .iter()
+            .map(|(category, count)| format!("{} {}", count, category))
+            .collect();
+        writeln!(out, "Context Notes: {}", counts.join(", "))?;
+        writeln!(out)?;
     }
 
     // Recent commits
-    let commits = git::get_recent_commits(conn, 3)?;
+    let commits = git::get_recent_commits(repo.conn(), 3)?;
     if !commits.is_empty() {
-        println!("Recent Commits ({}):", commits.len());
+        writeln!(out, "Recent Commits ({}):", commits.len())?;
         for c in &commits {
-            println!("  {} {}", c.short_hash.dimmed(), truncate(&c.message, 50));
+            writeln!(
+                out,
+                "  {} {}",
+                c.short_hash.dimmed(),
+                truncate(&c.message, 50)
+            )?;
         }
-        println!();
+        writeln!(out)?;
     }
 
     // Open questions
-    let questions = get_open_questions(conn)?;
+    let questions = repo.open_questions()?;
     if !questions.is_empty() {
-        println!("Open Questions ({}):", questions.len());
+        writeln!(out, "Open Questions ({}):", questions.len())?;
         for q in &questions {
-            println!("  ? {}", truncate(&q.question, 60));
+            writeln!(out, "  ? {}", truncate(&q.question, 60))?;
         }
     }
 
@@ -322,42 +634,48 @@ fn output_tier2(
 
 /// Tier 3: Full context (~500+ tokens)
 fn output_tier3(
-    conn: &Connection,
+    out: &mut String,
+    repo: &Repository,
     config: &ProjectConfig,
     session: &crate::models::Session,
+    branch: Option<&str>,
 ) -> Result<()> {
-    println!("{}", "=".repeat(60));
-    println!("{}", "FULL PROJECT CONTEXT".bold());
-    println!("{}", "=".repeat(60));
-    println!();
+    writeln!(out, "{}", "=".repeat(60))?;
+    writeln!(out, "{}", "FULL PROJECT CONTEXT".bold())?;
+    writeln!(out, "{}", "=".repeat(60))?;
+    writeln!(out)?;
 
     // Project info
-    println!("Project: {}", config.name.bold());
-    println!("Type: {}", config.project_type);
+    writeln!(out, "Project: {}", config.name.bold())?;
+    writeln!(out, "Type: {}", config.project_type)?;
     if let Some(desc) = &config.description {
-        println!("Description: {}", desc);
+        writeln!(out, "Description: {}", desc)?;
     }
-    println!("Schema Version: {}", config.schema_version);
-    println!();
+    writeln!(out, "Schema Version: {}", config.schema_version)?;
+    writeln!(out)?;
 
     // Current session
-    println!("{}", "-".repeat(40));
-    println!("CURRENT SESSION #{}", session.session_id);
-    println!(
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "CURRENT SESSION #{}", session.session_id)?;
+    writeln!(
+        out,
         "Started: {}",
         session.started_at.format("%Y-%m-%d %H:%M:%S")
-    );
-    println!();
+    )?;
+    if let Some(b) = branch {
+        writeln!(out, "Branch: {}", b)?;
+    }
+    writeln!(out)?;
 
     // Last session summary
-    if let Some(last) = get_last_completed_session(conn)? {
-        println!("{}", "-".repeat(40));
-        println!("LAST SESSION (#{}):", last.session_id);
+    if let Some(last) = get_last_completed_session(repo.conn())? {
+        writeln!(out, "{}", "-".repeat(40))?;
+        writeln!(out, "LAST SESSION (#{}):", last.session_id)?;
         if let Some(ended) = &last.ended_at {
-            println!("Ended: {}", ended.format("%Y-%m-%d %H:%M:%S"));
+            writeln!(out, "Ended: {}", ended.format("%Y-%m-%d %H:%M:%S"))?;
         }
         if let Some(summary) = &last.summary {
-            println!("Summary: {}", summary);
+            writeln!(out, "Summary: {}", summary)?;
         }
         // Show structured summary highlights if available
         if let Some(ref structured) = last.structured_summary {
@@ -379,47 +697,49 @@ fn output_tier3(
                     }
                 }
                 if !highlights.is_empty() {
-                    println!("Activity: {}", highlights.join(", "));
+                    writeln!(out, "Activity: {}", highlights.join(", "))?;
                 }
             }
         }
-        println!();
+        writeln!(out)?;
     }
 
     // Active blockers
-    println!("{}", "-".repeat(40));
-    println!("BLOCKERS:");
-    let blockers = get_active_blockers(conn)?;
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "BLOCKERS:")?;
+    let blockers = repo.active_blockers()?;
     if blockers.is_empty() {
-        println!("  (none)");
+        writeln!(out, "  (none)")?;
     } else {
-        for b in &blockers {
-            println!(
+        for b in blockers {
+            writeln!(
+                out,
                 "  {} {} (created {})",
                 "✗".red(),
                 b.description,
                 b.created_at.format("%Y-%m-%d")
-            );
+            )?;
         }
     }
-    println!();
+    writeln!(out)?;
 
     // All active tasks
-    println!("{}", "-".repeat(40));
-    println!("TASKS:");
-    let tasks = get_active_tasks(conn)?;
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "TASKS:")?;
+    let tasks = repo.active_tasks()?;
     if tasks.is_empty() {
-        println!("  (none)");
+        writeln!(out, "  (none)")?;
     } else {
-        for t in &tasks {
+        for t in tasks {
             let status_icon = match t.status.as_str() {
                 "in_progress" => "◐".yellow(),
                 "blocked" => "✗".red(),
                 "pending" => "○".white(),
                 _ => "○".white(),
             };
-            println!(
-                "  {} [{}] {} [{}] {}",
+            writeln!(
+                out,
+                "  {} [{}] {} [{}] {}{}",
                 status_icon,
                 t.task_id,
                 t.description,
@@ -428,98 +748,116 @@ fn output_tier3(
                     "(blocked)"
                 } else {
                     ""
-                }
-            );
+                },
+                format_due(t)
+                    .map(|d| format!(" ({})", d))
+                    .unwrap_or_default()
+            )?;
             if let Some(notes) = &t.notes {
-                println!("       Notes: {}", notes);
+                writeln!(out, "       Notes: {}", notes)?;
             }
         }
     }
-    println!();
+    writeln!(out)?;
 
     // Recent decisions
-    println!("{}", "-".repeat(40));
-    println!("RECENT DECISIONS:");
-    let decisions = get_recent_decisions(conn, 10)?;
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "RECENT DECISIONS:")?;
+    let decisions = repo.recent_decisions(10)?;
     if decisions.is_empty() {
-        println!("  (none)");
+        writeln!(out, "  (none)")?;
     } else {
         for d in &decisions {
-            println!(
+            writeln!(
+                out,
                 "  • {} ({})",
                 d.topic.bold(),
                 d.created_at.format("%Y-%m-%d")
-            );
-            println!("    Decision: {}", d.decision);
+            )?;
+            writeln!(out, "    Decision: {}", d.decision)?;
             if let Some(rationale) = &d.rationale {
-                println!("    Rationale: {}", rationale);
+                writeln!(out, "    Rationale: {}", rationale)?;
             }
         }
     }
-    println!();
+    writeln!(out)?;
 
     // Open questions
-    println!("{}", "-".repeat(40));
-    println!("OPEN QUESTIONS:");
-    let questions = get_open_questions(conn)?;
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "OPEN QUESTIONS:")?;
+    let questions = repo.open_questions()?;
     if questions.is_empty() {
-        println!("  (none)");
+        writeln!(out, "  (none)")?;
     } else {
         for q in &questions {
-            println!("  ? {} ({})", q.question, q.created_at.format("%Y-%m-%d"));
+            writeln!(
+                out,
+                "  ? {} ({})",
+                q.question,
+                q.created_at.format("%Y-%m-%d")
+            )?;
             if let Some(ctx) = &q.context {
-                println!("    Context: {}", ctx);
+                writeln!(out, "    Context: {}", ctx)?;
             }
         }
     }
-    println!();
+    writeln!(out)?;
 
     // Context notes by category
-    println!("{}", "-".repeat(40));
-    println!("CONTEXT NOTES:");
-    let notes = get_active_context_notes(conn)?;
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "CONTEXT NOTES:")?;
+    let notes = repo.active_context_notes()?;
     if notes.is_empty() {
-        println!("  (none)");
+        writeln!(out, "  (none)")?;
     } else {
         let mut current_category = String::new();
         for n in &notes {
             if n.category != current_category {
                 current_category = n.category.clone();
-                println!();
-                println!("  [{}]", current_category.to_uppercase());
+                writeln!(out)?;
+                writeln!(out, "  [{}]", current_category.to_uppercase())?;
             }
-            println!("    • {}: {}", n.title, truncate(&n.content, 60));
+            // Markdown content: the first line stands in for the rest
+            let first_line = n.content.lines().find(|l| !l.trim().is_empty());
+            writeln!(
+                out,
+                "    • {}: {}",
+                n.title,
+                truncate(first_line.unwrap_or_default().trim(), 60)
+            )?;
         }
     }
-    println!();
+    writeln!(out)?;
 
     // Git history
-    println!("{}", "-".repeat(40));
-    println!("GIT HISTORY:");
-    let commits = git::get_recent_commits(conn, 10)?;
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "GIT HISTORY:")?;
+    let commits = git::get_recent_commits(repo.conn(), 10)?;
     if commits.is_empty() {
-        println!("  (none)");
+        writeln!(out, "  (none)")?;
     } else {
         for c in &commits {
-            println!(
+            writeln!(
+                out,
                 "  {} {} ({} files, +{}/-{})",
                 c.short_hash.dimmed(),
                 c.message,
                 c.files_changed,
                 c.insertions,
                 c.deletions,
-            );
+            )?;
         }
     }
-    println!();
+    writeln!(out)?;
 
     // Recent sessions list
-    println!("{}", "-".repeat(40));
-    println!("RECENT SESSIONS:");
-    let sessions = crate::session::get_recent_sessions(conn, 5)?;
+    writeln!(out, "{}", "-".repeat(40))?;
+    writeln!(out, "RECENT SESSIONS:")?;
+    let sessions = crate::session::get_recent_sessions(repo.conn(), 5)?;
     for s in &sessions {
         let status_indicator = if s.status == "active" { "(active)" } else { "" };
-        println!(
+        writeln!(
+            out,
             "  #{} {} - {} {}",
             s.session_id,
             s.started_at.format("%Y-%m-%d %H:%M"),
@@ -528,229 +866,8 @@ fn output_tier3(
                 .map(|s| truncate(s, 40))
                 .unwrap_or_else(|| "(no summary)".to_string()),
             status_indicator
-        );
+        )?;
     }
 
     Ok(())
 }
-
-// Helper functions for database queries
-
-fn get_priority_task(conn: &Connection) -> Result<Option<Task>> {
-    let mut stmt = conn.prepare(
-        "SELECT task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes
-         FROM tasks
-         WHERE status IN ('pending', 'in_progress')
-         ORDER BY
-           CASE priority WHEN 'urgent' THEN 1 WHEN 'high' THEN 2 WHEN 'normal' THEN 3 ELSE 4 END,
-           created_at
-         LIMIT 1"
-    )?;
-
-    let task = stmt.query_row([], |row| {
-        Ok(Task {
-            task_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            description: row.get(4)?,
-            status: row.get(5)?,
-            priority: row.get(6)?,
-            blocked_by: row.get(7)?,
-            parent_task_id: row.get(8)?,
-            notes: row.get(9)?,
-        })
-    });
-
-    match task {
-        Ok(t) => Ok(Some(t)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e.into()),
-    }
-}
-
-fn get_priority_tasks(conn: &Connection, limit: usize) -> Result<Vec<Task>> {
-    let mut stmt = conn.prepare(
-        "SELECT task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes
-         FROM tasks
-         WHERE status IN ('pending', 'in_progress', 'blocked')
-         ORDER BY
-           CASE priority WHEN 'urgent' THEN 1 WHEN 'high' THEN 2 WHEN 'normal' THEN 3 ELSE 4 END,
-           created_at
-         LIMIT ?1"
-    )?;
-
-    let tasks = stmt.query_map([limit as i64], |row| {
-        Ok(Task {
-            task_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            description: row.get(4)?,
-            status: row.get(5)?,
-            priority: row.get(6)?,
-            blocked_by: row.get(7)?,
-            parent_task_id: row.get(8)?,
-            notes: row.get(9)?,
-        })
-    })?;
-
-    tasks.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
-}
-
-fn get_active_tasks(conn: &Connection) -> Result<Vec<Task>> {
-    let mut stmt = conn.prepare(
-        "SELECT task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes
-         FROM tasks
-         WHERE status NOT IN ('completed', 'cancelled')
-         ORDER BY
-           CASE priority WHEN 'urgent' THEN 1 WHEN 'high' THEN 2 WHEN 'normal' THEN 3 ELSE 4 END,
-           created_at"
-    )?;
-
-    let tasks = stmt.query_map([], |row| {
-        Ok(Task {
-            task_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            description: row.get(4)?,
-            status: row.get(5)?,
-            priority: row.get(6)?,
-            blocked_by: row.get(7)?,
-            parent_task_id: row.get(8)?,
-            notes: row.get(9)?,
-        })
-    })?;
-
-    tasks.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
-}
-
-fn get_active_blocker_count(conn: &Connection) -> Result<i64> {
-    let count: i64 = conn.query_row(
-        "SELECT COUNT(*) FROM blockers WHERE status = 'active'",
-        [],
-        |row| row.get(0),
-    )?;
-    Ok(count)
-}
-
-fn get_active_blockers(conn: &Connection) -> Result<Vec<Blocker>> {
-    let mut stmt = conn.prepare(
-        "SELECT blocker_id, session_id, created_at, resolved_at, description, status, resolution, related_task_id
-         FROM blockers
-         WHERE status = 'active'
-         ORDER BY created_at DESC"
-    )?;
-
-    let blockers = stmt.query_map([], |row| {
-        Ok(Blocker {
-            blocker_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            resolved_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            description: row.get(4)?,
-            status: row.get(5)?,
-            resolution: row.get(6)?,
-            related_task_id: row.get(7)?,
-        })
-    })?;
-
-    blockers
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.into())
-}
-
-fn get_recent_decisions(conn: &Connection, limit: usize) -> Result<Vec<Decision>> {
-    let mut stmt = conn.prepare(
-        "SELECT decision_id, session_id, created_at, topic, decision, rationale, alternatives, status, superseded_by
-         FROM decisions
-         WHERE status = 'active'
-         ORDER BY created_at DESC
-         LIMIT ?1"
-    )?;
-
-    let decisions = stmt.query_map([limit as i64], |row| {
-        Ok(Decision {
-            decision_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            topic: row.get(3)?,
-            decision: row.get(4)?,
-            rationale: row.get(5)?,
-            alternatives: row.get(6)?,
-            status: row.get(7)?,
-            superseded_by: row.get(8)?,
-        })
-    })?;
-
-    decisions
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.into())
-}
-
-fn get_open_questions(conn: &Connection) -> Result<Vec<Question>> {
-    let mut stmt = conn.prepare(
-        "SELECT question_id, session_id, created_at, answered_at, question, context, answer, status
-         FROM questions
-         WHERE status = 'open'
-         ORDER BY created_at DESC",
-    )?;
-
-    let questions = stmt.query_map([], |row| {
-        Ok(Question {
-            question_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            answered_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            question: row.get(4)?,
-            context: row.get(5)?,
-            answer: row.get(6)?,
-            status: row.get(7)?,
-        })
-    })?;
-
-    questions
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.into())
-}
-
-fn get_active_context_notes(conn: &Connection) -> Result<Vec<crate::models::ContextNote>> {
-    let mut stmt = conn.prepare(
-        "SELECT note_id, session_id, created_at, updated_at, category, title, content, status
-         FROM context_notes
-         WHERE status = 'active'
-         ORDER BY category, created_at",
-    )?;
-
-    let notes = stmt.query_map([], |row| {
-        Ok(crate::models::ContextNote {
-            note_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            updated_at: parse_datetime(row.get::<_, String>(3)?),
-            category: row.get(4)?,
-            title: row.get(5)?,
-            content: row.get(6)?,
-            status: row.get(7)?,
-        })
-    })?;
-
-    notes.collect::<Result<Vec<_>, _>>().map_err(|e| e.into())
-}
-
-/// Parse datetime string from SQLite
-fn parse_datetime(s: String) -> chrono::DateTime<chrono::Utc> {
-    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
-        .map(|dt| dt.and_utc())
-        .unwrap_or_else(|_| chrono::Utc::now())
-}
-
-/// Truncate a string to a maximum length
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
-    }
-}
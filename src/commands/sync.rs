@@ -0,0 +1,678 @@
+// Sync command - share tracking data between machines through git-friendly snapshots
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use dialoguer::Select;
+use rusqlite::types::Value;
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+
+use crate::database::{get_schema_version, open_database, write_transaction};
+use crate::diagnostics;
+use crate::encoding::hex;
+use crate::error::ProjError;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::records::{
+    self, fill_self_refs, last_touched, read_records, write_record, DeferredRef, Record, TableSpec,
+};
+use crate::text::truncate;
+use crate::SCHEMA_VERSION;
+
+/// Snapshot directory, relative to the project root
+const SYNC_DIR: &str = ".tracking/sync";
+
+/// project_meta key holding record hashes as of the last sync
+const SYNC_BASE_KEY: &str = "sync_base";
+
+/// Let git keep both sides of concurrent edits; sync picks a winner afterwards
+const GITATTRIBUTES: &str = "*.jsonl merge=union\n";
+
+/// How one table is written to and read back from its snapshot file
+struct SyncSpec {
+    records: &'static TableSpec,
+    /// Columns that pair up one record holding a different UUID on each machine
+    identity: &'static [&'static str],
+    /// Line order within the file; chronological keeps new records at the end
+    order_by: &'static str,
+    /// Whether deleting a record on one machine deletes it everywhere
+    deletable: bool,
+}
+
+/// Sync order matters: referenced tables come first
+const SYNC_SPECS: &[SyncSpec] = &[
+    SyncSpec {
+        records: &records::SESSIONS,
+        identity: &["started_at"],
+        order_by: "started_at",
+        deletable: false,
+    },
+    SyncSpec {
+        records: &records::TASKS,
+        identity: &["description", "created_at"],
+        order_by: "created_at",
+        deletable: true,
+    },
+    SyncSpec {
+        records: &records::DECISIONS,
+        identity: &["topic", "decision", "created_at"],
+        order_by: "created_at",
+        deletable: true,
+    },
+    SyncSpec {
+        records: &records::NOTES,
+        identity: &["category", "title", "created_at"],
+        order_by: "created_at",
+        deletable: true,
+    },
+    SyncSpec {
+        records: &records::BLOCKERS,
+        identity: &["description", "created_at"],
+        order_by: "created_at",
+        deletable: true,
+    },
+    SyncSpec {
+        records: &records::QUESTIONS,
+        identity: &["question", "created_at"],
+        order_by: "created_at",
+        deletable: true,
+    },
+    SyncSpec {
+        records: &records::COMMITS,
+        identity: &["hash"],
+        order_by: "committed_at",
+        deletable: false,
+    },
+];
+
+/// How to settle a record that changed on both machines
+#[derive(Clone, Copy, PartialEq)]
+enum Strategy {
+    Newest,
+    Local,
+    Remote,
+    Ask,
+}
+
+/// Per-table sync outcome
+#[derive(Default)]
+struct TableResult {
+    added: usize,
+    updated: usize,
+    removed: usize,
+    conflicts: Vec<String>,
+}
+
+pub fn run(strategy: &str, dry_run: bool) -> Result<()> {
+    let strategy = match strategy {
+        "newest" => Strategy::Newest,
+        "local" => Strategy::Local,
+        "remote" => Strategy::Remote,
+        "ask" if dry_run => Strategy::Newest,
+        "ask" => {
+            if !atty::is(atty::Stream::Stdin) {
+                bail!("--strategy ask needs an interactive terminal");
+            }
+            Strategy::Ask
+        }
+        other => bail!(
+            "Unknown strategy '{}'. Use newest, local, remote, or ask.",
+            other
+        ),
+    };
+
+    let root = get_project_root()?;
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let version = get_schema_version(&conn)?.unwrap_or_else(|| "1.0".to_string());
+    if version != SCHEMA_VERSION {
+        return Err(ProjError::SchemaMismatch {
+            found: version,
+            required: SCHEMA_VERSION.to_string(),
+        }
+        .into());
+    }
+
+    let sync_dir = root.join(SYNC_DIR);
+    let mut remote = read_snapshot(&sync_dir)?;
+    let base = load_base(&conn);
+
+    let tx = write_transaction(&conn)?;
+
+    let mut renamed = HashMap::new();
+    let mut results = Vec::new();
+    let mut missing = Vec::new();
+    for spec in SYNC_SPECS {
+        let name = spec.records.name;
+        // A missing file says nothing about deletions (git clean, another branch), so
+        // without it the table is only written out, never pruned
+        let (snapshot, table_base) = match remote.remove(name) {
+            Some(snapshot) => (snapshot, base.get(name)),
+            None => {
+                if base.get(name).is_some_and(|b| !b.is_empty()) {
+                    missing.push(name);
+                }
+                (HashMap::new(), None)
+            }
+        };
+        let result = merge_table(&tx, spec, snapshot, table_base, strategy, &mut renamed)
+            .with_context(|| format!("Failed to sync {}", spec.records.name))?;
+        results.push((spec.records.name, result));
+    }
+
+    let added: usize = results.iter().map(|(_, r)| r.added).sum();
+    let updated: usize = results.iter().map(|(_, r)| r.updated).sum();
+    let removed: usize = results.iter().map(|(_, r)| r.removed).sum();
+
+    // Print summary
+    println!(
+        "{}",
+        if dry_run {
+            format!("Sync preview: {}", SYNC_DIR)
+        } else {
+            format!("Sync: {}", SYNC_DIR)
+        }
+        .bold()
+    );
+    println!("{}", "-".repeat(60));
+    if added + updated + removed == 0 {
+        println!("  Already up to date.");
+    }
+    for (name, r) in &results {
+        if r.added + r.updated + r.removed == 0 {
+            continue;
+        }
+        println!(
+            "  {:<10} {} new, {} updated, {} removed",
+            name, r.added, r.updated, r.removed
+        );
+    }
+
+    if !missing.is_empty() {
+        println!();
+        println!(
+            "{} No snapshot file for {} (synced before); nothing was removed from them. Restore {}/ from git if it was deleted by mistake.",
+            "⚠".yellow(),
+            missing.join(", "),
+            SYNC_DIR
+        );
+    }
+
+    let conflicts: Vec<&String> = results.iter().flat_map(|(_, r)| &r.conflicts).collect();
+    if !conflicts.is_empty() {
+        println!();
+        println!("{} Changed on both machines:", "⚠".yellow());
+        for c in conflicts.iter().take(10) {
+            println!("  {}", c);
+        }
+        if conflicts.len() > 10 {
+            println!("  ... and {} more", conflicts.len() - 10);
+        }
+    }
+
+    if dry_run {
+        tx.rollback()?;
+        println!();
+        println!("Dry run - nothing was written.");
+        return Ok(());
+    }
+
+    if added + updated + removed > 0 {
+        let summary = format!(
+            "Synced: {} added, {} updated, {} removed",
+            added, updated, removed
+        );
+        tx.execute(
+            "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (NULL, 'sync', NULL, ?1)",
+            [&summary],
+        )?;
+    }
+
+    // Written before committing so a failed write leaves the database untouched
+    let written = write_snapshot(&tx, &sync_dir)?;
+    tx.commit()?;
+
+    println!();
+    println!(
+        "{} Wrote {} records to {}/. Commit it to share with your other machines.",
+        "✓".green(),
+        written,
+        SYNC_DIR
+    );
+
+    if database_is_committed(&root) {
+        println!();
+        println!(
+            "{} .tracking/tracking.db is committed to git and will keep conflicting. Share only the snapshot:",
+            "⚠".yellow()
+        );
+        println!("    git rm --cached .tracking/tracking.db");
+        println!("    echo '.tracking/tracking.db*' >> .gitignore");
+    }
+
+    Ok(())
+}
+
+/// Bring one table in line with its snapshot file, using the last sync as the common ancestor.
+/// `renamed` collects the UUIDs given up when settling, so references follow them.
+fn merge_table(
+    conn: &Connection,
+    spec: &SyncSpec,
+    mut remote: HashMap<String, Vec<Record>>,
+    base: Option<&HashMap<String, String>>,
+    strategy: Strategy,
+    renamed: &mut HashMap<String, String>,
+) -> Result<TableResult> {
+    let table = spec.records;
+    let mut local = read_local(conn, table)?;
+    let mut result = TableResult::default();
+    let mut deferred: Vec<DeferredRef> = Vec::new();
+
+    if table.key == "uuid" {
+        result.updated += settle_uuids(conn, spec, &mut local, &mut remote, renamed)?;
+    }
+    let references: Vec<&str> = table
+        .refs
+        .iter()
+        .map(|(col, _)| *col)
+        .chain(table.self_refs.iter().copied())
+        .collect();
+    for record in remote
+        .values_mut()
+        .flatten()
+        .chain(local.values_mut().map(|(_, record)| record))
+    {
+        for col in &references {
+            if let Some(Value::Text(key)) = record.get_mut(*col) {
+                if let Some(settled) = renamed.get(key.as_str()) {
+                    *key = settled.clone();
+                }
+            }
+        }
+    }
+
+    let mut keys: BTreeSet<&String> = local.keys().collect();
+    keys.extend(remote.keys());
+
+    for key in keys {
+        let l = local.get(key);
+        let versions = remote.get(key).map_or(&[][..], |v| v.as_slice());
+        let r = pick_remote(spec, versions, l.map(|(_, rec)| rec));
+        let b = base.and_then(|m| m.get(key));
+        // Several versions of one line means git kept both sides of concurrent edits
+        let concurrent = versions.iter().any(|v| *v != versions[0]);
+
+        match (l, r) {
+            (Some((_, l)), Some(r)) if l == r => {}
+            (Some((pk, l)), Some(r)) => {
+                let take_remote = if !concurrent && b == Some(&record_hash(l)) {
+                    true
+                } else if !concurrent && b == Some(&record_hash(r)) {
+                    false
+                } else {
+                    let take = resolve_conflict(spec, key, l, r, strategy)?;
+                    let label = match l.get(spec.identity[0]) {
+                        Some(Value::Text(text)) => text.as_str(),
+                        _ => key.as_str(),
+                    };
+                    result.conflicts.push(format!(
+                        "{} \"{}\": {}",
+                        table.name,
+                        truncate(label, 40),
+                        if take { "took remote" } else { "kept local" }
+                    ));
+                    take
+                };
+                if take_remote {
+                    write_record(conn, table, Some(*pk), r, &mut deferred)?;
+                    result.updated += 1;
+                }
+            }
+            (Some((pk, l)), None) => {
+                // Unchanged here since the last sync, so it was deleted on the other machine
+                if spec.deletable && b == Some(&record_hash(l)) {
+                    delete_record(conn, table, *pk)?;
+                    result.removed += 1;
+                }
+            }
+            (None, Some(r)) => {
+                // Already synced before and now missing here means it was deleted here
+                if b != Some(&record_hash(r)) {
+                    write_record(conn, table, None, r, &mut deferred)?;
+                    result.added += 1;
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    fill_self_refs(conn, table, deferred)?;
+
+    Ok(result)
+}
+
+/// Choose the remote version of a record. A union merge can leave both sides of a
+/// concurrent edit in the file; prefer whichever isn't ours, newest first.
+fn pick_remote<'a>(
+    spec: &SyncSpec,
+    versions: &'a [Record],
+    local: Option<&Record>,
+) -> Option<&'a Record> {
+    versions
+        .iter()
+        .filter(|r| Some(*r) != local)
+        .max_by_key(|r| last_touched(spec.records, r))
+        .or_else(|| versions.first())
+}
+
+/// Decide a record changed on both machines; true means take the remote version
+fn resolve_conflict(
+    spec: &SyncSpec,
+    key: &str,
+    local: &Record,
+    remote: &Record,
+    strategy: Strategy,
+) -> Result<bool> {
+    match strategy {
+        Strategy::Newest => {
+            Ok(last_touched(spec.records, remote) > last_touched(spec.records, local))
+        }
+        Strategy::Local => Ok(false),
+        Strategy::Remote => Ok(true),
+        Strategy::Ask => {
+            println!();
+            println!(
+                "{} {} {} changed on both machines:",
+                "⚠".yellow(),
+                spec.records.name,
+                &key[..key.len().min(8)]
+            );
+            let fields: BTreeSet<&String> = local.keys().chain(remote.keys()).collect();
+            for field in fields {
+                let l = local
+                    .get(field)
+                    .cloned()
+                    .map_or(serde_json::Value::Null, value_to_json);
+                let r = remote
+                    .get(field)
+                    .cloned()
+                    .map_or(serde_json::Value::Null, value_to_json);
+                if l != r {
+                    println!("  {}:", field.bold());
+                    println!("    local:  {}", l);
+                    println!("    remote: {}", r);
+                }
+            }
+            let choice = Select::new()
+                .with_prompt("Keep which version?")
+                .items(&["Local", "Remote"])
+                .default(0)
+                .interact()?;
+            Ok(choice == 1)
+        }
+    }
+}
+
+/// A database copied to another machine and upgraded there to UUIDs (schema v1.15)
+/// gave the records both copies share a different UUID on each machine. Pair those
+/// up by their identity columns and settle each on the smaller UUID, the choice the
+/// other machine makes too. Returns how many records here took the remote UUID.
+fn settle_uuids(
+    conn: &Connection,
+    spec: &SyncSpec,
+    local: &mut BTreeMap<String, (i64, Record)>,
+    remote: &mut HashMap<String, Vec<Record>>,
+    renamed: &mut HashMap<String, String>,
+) -> Result<usize> {
+    let table = spec.records;
+    let identity = |record: &Record| -> Vec<serde_json::Value> {
+        spec.identity
+            .iter()
+            .map(|col| {
+                record
+                    .get(*col)
+                    .cloned()
+                    .map_or(serde_json::Value::Null, value_to_json)
+            })
+            .collect()
+    };
+    let mut unpaired: HashMap<Vec<serde_json::Value>, String> = local
+        .iter()
+        .filter(|(key, _)| !remote.contains_key(*key))
+        .map(|(key, (_, record))| (identity(record), key.clone()))
+        .collect();
+    let remote_only: Vec<String> = remote
+        .keys()
+        .filter(|key| !local.contains_key(*key))
+        .cloned()
+        .collect();
+
+    let mut adopted = 0;
+    for remote_key in remote_only {
+        let Some(local_key) = remote
+            .get(&remote_key)
+            .and_then(|versions| unpaired.remove(&identity(&versions[0])))
+        else {
+            continue;
+        };
+        if remote_key < local_key {
+            let Some((pk, mut record)) = local.remove(&local_key) else {
+                continue;
+            };
+            conn.execute(
+                &format!(
+                    "UPDATE {} SET uuid = ?1 WHERE {} = ?2",
+                    table.table, table.pk
+                ),
+                rusqlite::params![remote_key, pk],
+            )?;
+            record.insert("uuid".to_string(), Value::Text(remote_key.clone()));
+            local.insert(remote_key.clone(), (pk, record));
+            renamed.insert(local_key, remote_key);
+            adopted += 1;
+        } else if let Some(mut versions) = remote.remove(&remote_key) {
+            for version in &mut versions {
+                version.insert("uuid".to_string(), Value::Text(local_key.clone()));
+            }
+            remote.insert(local_key.clone(), versions);
+            renamed.insert(remote_key, local_key);
+        }
+    }
+    Ok(adopted)
+}
+
+/// Delete a record, detaching anything that points at it first
+fn delete_record(conn: &Connection, spec: &TableSpec, pk: i64) -> Result<()> {
+    for other in records::TABLES {
+        for (col, target) in other.refs {
+            if target.name == spec.name {
+                conn.execute(
+                    &format!(
+                        "UPDATE {} SET {} = NULL WHERE {} = ?1",
+                        other.table, col, col
+                    ),
+                    [pk],
+                )?;
+            }
+        }
+    }
+    for col in spec.self_refs {
+        conn.execute(
+            &format!(
+                "UPDATE {} SET {} = NULL WHERE {} = ?1",
+                spec.table, col, col
+            ),
+            [pk],
+        )?;
+    }
+    conn.execute(
+        &format!("DELETE FROM {} WHERE {} = ?1", spec.table, spec.pk),
+        [pk],
+    )?;
+    Ok(())
+}
+
+/// Local records of one table that belong in the snapshot, by key
+fn read_local(conn: &Connection, spec: &TableSpec) -> Result<BTreeMap<String, (i64, Record)>> {
+    Ok(read_records(conn, spec, spec.filter)?
+        .into_iter()
+        .map(|(pk, key, record)| (key, (pk, record)))
+        .collect())
+}
+
+/// Read every snapshot file; a key may appear more than once after a union merge
+fn read_snapshot(dir: &Path) -> Result<HashMap<&'static str, HashMap<String, Vec<Record>>>> {
+    let mut snapshot = HashMap::new();
+
+    for spec in SYNC_SPECS {
+        let path = dir.join(format!("{}.jsonl", spec.records.name));
+        if !path.exists() {
+            continue;
+        }
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+
+        let mut records: HashMap<String, Vec<Record>> = HashMap::new();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            if line.starts_with("<<<<<<<")
+                || line.starts_with("=======")
+                || line.starts_with(">>>>>>>")
+            {
+                bail!(
+                    "{} has unresolved merge conflicts. Keep both versions of each line (drop the markers) and run 'proj sync' again.",
+                    path.display()
+                );
+            }
+            let json: serde_json::Map<String, serde_json::Value> = serde_json::from_str(line)
+                .with_context(|| {
+                    format!("Invalid record on line {} of {}", i + 1, path.display())
+                })?;
+            let record: Record = json
+                .iter()
+                .map(|(col, value)| (col.clone(), json_to_value(value)))
+                .collect();
+            if let Some(Value::Text(key)) = record.get(spec.records.key) {
+                records.entry(key.clone()).or_default().push(record);
+            }
+        }
+        snapshot.insert(spec.records.name, records);
+    }
+
+    Ok(snapshot)
+}
+
+/// Write the snapshot files and remember what they contained; returns the record count
+fn write_snapshot(conn: &Connection, dir: &Path) -> Result<usize> {
+    std::fs::create_dir_all(dir).with_context(|| format!("Failed to create {:?}", dir))?;
+
+    let attributes = dir.join(".gitattributes");
+    if !attributes.exists() {
+        std::fs::write(&attributes, GITATTRIBUTES)
+            .with_context(|| format!("Failed to write {:?}", attributes))?;
+    }
+
+    let mut base: BTreeMap<&str, BTreeMap<String, String>> = BTreeMap::new();
+    let mut total = 0;
+
+    for spec in SYNC_SPECS {
+        let local = read_local(conn, spec.records)?;
+        let mut records: Vec<(String, &String, &Record)> = local
+            .iter()
+            .map(|(key, (_, record))| {
+                let order = match record.get(spec.order_by) {
+                    Some(Value::Text(ts)) => ts.clone(),
+                    _ => String::new(),
+                };
+                (order, key, record)
+            })
+            .collect();
+        records.sort_by(|a, b| (&a.0, a.1).cmp(&(&b.0, b.1)));
+
+        let mut content = String::new();
+        let hashes = base.entry(spec.records.name).or_default();
+        for (_, key, record) in &records {
+            content.push_str(&serde_json::to_string(&to_json(record))?);
+            content.push('\n');
+            hashes.insert(key.to_string(), record_hash(record));
+        }
+
+        let path = dir.join(format!("{}.jsonl", spec.records.name));
+        std::fs::write(&path, content).with_context(|| format!("Failed to write {:?}", path))?;
+        total += records.len();
+    }
+
+    conn.execute(
+        "INSERT OR REPLACE INTO project_meta (key, value, updated_at) VALUES (?1, ?2, datetime('now'))",
+        rusqlite::params![SYNC_BASE_KEY, serde_json::to_string(&base)?],
+    )?;
+
+    Ok(total)
+}
+
+/// Record hashes as of the last sync, by snapshot name and key
+fn load_base(conn: &Connection) -> HashMap<String, HashMap<String, String>> {
+    conn.query_row(
+        "SELECT value FROM project_meta WHERE key = ?1",
+        [SYNC_BASE_KEY],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+    .and_then(|value| serde_json::from_str(&value).ok())
+    .unwrap_or_default()
+}
+
+/// Whether the binary database is tracked by git alongside the snapshot
+fn database_is_committed(root: &Path) -> bool {
+    diagnostics::output(
+        Command::new("git")
+            .args(["ls-files", "--error-unmatch", ".tracking/tracking.db"])
+            .current_dir(root),
+    )
+    .map(|o| o.status.success())
+    .unwrap_or(false)
+}
+
+fn record_hash(record: &Record) -> String {
+    let json = serde_json::to_string(&to_json(record)).unwrap_or_default();
+    hex(&Sha256::digest(json.as_bytes()))[..16].to_string()
+}
+
+/// A record as one snapshot line
+fn to_json(record: &Record) -> serde_json::Map<String, serde_json::Value> {
+    record
+        .iter()
+        .map(|(col, value)| (col.clone(), value_to_json(value.clone())))
+        .collect()
+}
+
+fn value_to_json(value: Value) -> serde_json::Value {
+    match value {
+        Value::Null => serde_json::Value::Null,
+        Value::Integer(i) => serde_json::Value::from(i),
+        Value::Real(f) => serde_json::Value::from(f),
+        Value::Text(s) => serde_json::Value::from(s),
+        Value::Blob(b) => serde_json::Value::from(hex(&b)),
+    }
+}
+
+fn json_to_value(json: &serde_json::Value) -> Value {
+    match json {
+        serde_json::Value::Null => Value::Null,
+        serde_json::Value::Bool(b) => Value::Integer(*b as i64),
+        serde_json::Value::Number(n) => match n.as_i64() {
+            Some(i) => Value::Integer(i),
+            None => Value::Real(n.as_f64().unwrap_or(0.0)),
+        },
+        serde_json::Value::String(s) => Value::Text(s.clone()),
+        other => Value::Text(other.to_string()),
+    }
+}
@@ -1,14 +1,48 @@
-// Task commands - add, update, list
+// Task commands - add, update, show, list
 
-use anyhow::{bail, Context, Result};
+use std::fmt::Write;
+
+use anyhow::{Context, Result};
 use colored::Colorize;
-use rusqlite::Connection;
+use dialoguer::{Confirm, FuzzySelect, Select};
+use rusqlite::{Connection, OptionalExtension};
+use serde::Deserialize;
 
-use crate::cli::{TaskCommands, TaskSubcommand};
-use crate::database::open_database;
-use crate::models::Task;
+use crate::cli::{TaskCommands, TaskListArgs, TaskSubcommand};
+use crate::commands::log::Logged;
+use crate::commands::task_import;
+use crate::database::{open_database, record_uuid, write_transaction};
+use crate::error::ProjError;
+use crate::models::{DueStatus, Task};
+use crate::pager;
 use crate::paths::get_tracking_db_path;
-use crate::session::get_or_create_session_with_info;
+use crate::porcelain;
+use crate::repository::Repository;
+use crate::session::{get_or_create_session_with_info, resolve_author, track_branch};
+use crate::tags::{self, Tagged};
+use crate::text::truncate;
+use crate::webhooks;
+
+const VALID_STATUSES: [&str; 5] = [
+    "pending",
+    "in_progress",
+    "completed",
+    "blocked",
+    "cancelled",
+];
+
+const VALID_PRIORITIES: [&str; 4] = ["low", "normal", "high", "urgent"];
+
+/// Matches shown at once in the task picker
+const PICK_ROWS: usize = 15;
+
+/// What `proj task pick` can do with the picked task
+const PICK_ACTIONS: [(&str, &str); 4] = [
+    ("show", "Show"),
+    ("start", "Start (in progress)"),
+    ("done", "Complete"),
+    ("print", "Print ID"),
+];
 
 pub fn run(cmd: TaskCommands) -> Result<()> {
     let db_path = get_tracking_db_path()?;
@@ -19,6 +53,9 @@ pub fn run(cmd: TaskCommands) -> Result<()> {
         TaskSubcommand::Add {
             description,
             priority,
+            due,
+            tags,
+            author,
         } => {
             let session_result = get_or_create_session_with_info(&conn)?;
             let session = session_result.session;
@@ -33,7 +70,21 @@ pub fn run(cmd: TaskCommands) -> Result<()> {
                 );
             }
 
-            cmd_task_add(&conn, session.session_id, &description, &priority)
+            let branch = track_branch(&conn, session.session_id)?;
+            let author = resolve_author(author.as_deref());
+
+            cmd_task_add(
+                &conn,
+                session.session_id,
+                branch.as_deref(),
+                author.as_deref(),
+                None,
+                &description,
+                &priority,
+                due.as_deref(),
+                &tags,
+            )
+            .map(Logged::report)
         }
         TaskSubcommand::Update {
             id,
@@ -41,56 +92,112 @@ pub fn run(cmd: TaskCommands) -> Result<()> {
             notes,
             priority,
             blocked_by,
-        } => cmd_task_update(&conn, id, status, notes, priority, blocked_by),
-        TaskSubcommand::List => list(),
+            verify_commits,
+        } => {
+            if verify_commits && status.as_deref() != Some("completed") {
+                return Err(ProjError::InvalidInput(
+                    "--verify-commits only applies with --status completed".to_string(),
+                )
+                .into());
+            }
+            cmd_task_update(
+                &conn,
+                id,
+                status,
+                notes,
+                priority,
+                blocked_by,
+                verify_commits,
+            )
+        }
+        TaskSubcommand::Show { id } => cmd_task_show(&conn, id),
+        TaskSubcommand::Pick { print, start, done } => cmd_task_pick(&conn, print, start, done),
+        TaskSubcommand::Edit {
+            id,
+            description,
+            priority,
+            notes,
+            due,
+        } => {
+            let session = get_or_create_session_with_info(&conn)?.session;
+            cmd_task_edit(
+                &conn,
+                session.session_id,
+                id,
+                description,
+                priority,
+                notes,
+                due,
+            )
+        }
+        TaskSubcommand::Delete { id, force } => {
+            let session = get_or_create_session_with_info(&conn)?.session;
+            cmd_task_delete(&conn, session.session_id, id, force)
+        }
+        TaskSubcommand::List(args) => list(&args),
+        TaskSubcommand::Sync {
+            provider: _,
+            repo,
+            dry_run,
+        } => crate::commands::task_sync::github(repo.as_deref(), dry_run),
+        TaskSubcommand::Import {
+            from,
+            jql,
+            team,
+            refresh,
+        } => task_import::run(from.as_deref(), jql.as_deref(), team.as_deref(), refresh),
     }
 }
 
 /// Shortcut for 'task list'
-pub fn list() -> Result<()> {
+pub fn list(args: &TaskListArgs) -> Result<()> {
     let db_path = get_tracking_db_path()?;
     let conn = open_database(&db_path)
         .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
 
-    cmd_task_list(&conn)
+    cmd_task_list(&conn, args)
 }
 
 /// Add a new task
-fn cmd_task_add(
+#[allow(clippy::too_many_arguments)]
+fn cmd_task_add<'a>(
     conn: &Connection,
     session_id: i64,
-    description: &str,
+    branch: Option<&str>,
+    author: Option<&str>,
+    at: Option<&str>,
+    description: &'a str,
     priority: &str,
-) -> Result<()> {
+    due: Option<&str>,
+    tag_names: &[String],
+) -> Result<Logged<'a>> {
     // Validate priority
-    let valid_priorities = ["low", "normal", "high", "urgent"];
+    let valid_priorities = VALID_PRIORITIES;
     if !valid_priorities.contains(&priority) {
-        bail!(
+        return Err(ProjError::InvalidInput(format!(
             "Invalid priority '{}'. Valid priorities: {}",
             priority,
             valid_priorities.join(", ")
-        );
+        ))
+        .into());
     }
 
+    let due = due.map(validate_due_date).transpose()?;
+
     // Insert task
     conn.execute(
-        "INSERT INTO tasks (session_id, description, status, priority) VALUES (?1, ?2, 'pending', ?3)",
-        rusqlite::params![session_id, description, priority],
+        "INSERT INTO tasks (session_id, description, status, priority, due, branch, author, created_at) VALUES (?1, ?2, 'pending', ?3, ?4, ?5, ?6, COALESCE(?7, datetime('now')))",
+        rusqlite::params![session_id, description, priority, due, branch, author, at],
     )?;
 
     let task_id = conn.last_insert_rowid();
+    let task_tags = tags::add_tags(conn, Tagged::Task(task_id), tag_names)?;
 
     // Insert into activity_log
     let summary = format!("Task added: {}", truncate(description, 50));
     conn.execute(
-        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
-        rusqlite::params![session_id, task_id, summary],
-    )?;
-
-    // Update FTS index
-    conn.execute(
-        "INSERT INTO tracking_fts (content, table_name, record_id) VALUES (?1, 'tasks', ?2)",
-        rusqlite::params![description, task_id],
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary, timestamp) VALUES (?1, 'task_update', ?2, ?3, COALESCE(?4, datetime('now')))",
+        rusqlite::params![session_id, task_id, summary, at],
     )?;
 
     let priority_display = match priority {
@@ -99,14 +206,99 @@ fn cmd_task_add(
         _ => format!("[{}]", priority),
     };
 
-    println!(
-        "{} Added task #{} {}: {}",
-        "✓".green(),
-        task_id,
-        priority_display,
-        description
-    );
-    Ok(())
+    Ok(Logged::new(move || {
+        if porcelain::enabled() {
+            porcelain::print(&[&task_id.to_string()]);
+            return;
+        }
+        println!(
+            "{} Added task #{} {}: {}{}{}",
+            "✓".green(),
+            task_id,
+            priority_display,
+            description,
+            due.map(|d| format!(" (due {})", d)).unwrap_or_default(),
+            if task_tags.is_empty() {
+                String::new()
+            } else {
+                format!(" {}", tags::format_tags(&task_tags).cyan())
+            }
+        );
+    }))
+}
+
+/// A task added by `proj log batch`
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TaskEntry {
+    pub description: String,
+    #[serde(default = "default_priority")]
+    pub priority: String,
+    pub due: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+fn default_priority() -> String {
+    "normal".to_string()
+}
+
+impl TaskEntry {
+    /// Check the priority and due date before anything is written
+    pub fn validate(&self) -> Result<()> {
+        if !VALID_PRIORITIES.contains(&self.priority.as_str()) {
+            return Err(ProjError::InvalidInput(format!(
+                "Invalid priority '{}'. Valid priorities: {}",
+                self.priority,
+                VALID_PRIORITIES.join(", ")
+            ))
+            .into());
+        }
+        self.due.as_deref().map(validate_due_date).transpose()?;
+        Ok(())
+    }
+
+    /// Add the task to a session the caller has already started, created `at` that
+    /// time ("YYYY-MM-DD HH:MM:SS", UTC) or now
+    pub fn add(
+        &self,
+        conn: &Connection,
+        session_id: i64,
+        branch: Option<&str>,
+        author: Option<&str>,
+        at: Option<&str>,
+    ) -> Result<Logged<'_>> {
+        cmd_task_add(
+            conn,
+            session_id,
+            branch,
+            author,
+            at,
+            &self.description,
+            &self.priority,
+            self.due.as_deref(),
+            &self.tags,
+        )
+    }
+}
+
+/// Validate a due date argument, returning it normalized as YYYY-MM-DD
+fn validate_due_date(due: &str) -> Result<String> {
+    chrono::NaiveDate::parse_from_str(due.trim(), "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| {
+            ProjError::InvalidInput(format!("Invalid due date '{}'. Use YYYY-MM-DD", due)).into()
+        })
+}
+
+/// Colored due-date label for task listings, e.g. "due 2024-07-01" or "OVERDUE 2024-06-28"
+pub fn format_due(task: &Task) -> Option<String> {
+    let due = task.due.as_deref()?;
+    Some(match task.due_status() {
+        DueStatus::Overdue => format!("OVERDUE {}", due).red().bold().to_string(),
+        DueStatus::DueSoon => format!("due {}", due).yellow().to_string(),
+        _ => format!("due {}", due).dimmed().to_string(),
+    })
 }
 
 /// Update an existing task
@@ -117,6 +309,7 @@ fn cmd_task_update(
     notes: Option<String>,
     priority: Option<String>,
     blocked_by: Option<String>,
+    verify_commits: bool,
 ) -> Result<()> {
     // Check task exists
     let exists: bool = conn
@@ -126,27 +319,23 @@ fn cmd_task_update(
         .unwrap_or(false);
 
     if !exists {
-        bail!("Task #{} not found", task_id);
+        return Err(ProjError::InvalidInput(format!("Task #{} not found", task_id)).into());
     }
+    task_import::ensure_local(conn, task_id)?;
 
     let mut updates = Vec::new();
     let mut params: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
 
     // Handle status update
     if let Some(ref s) = status {
-        let valid_statuses = [
-            "pending",
-            "in_progress",
-            "completed",
-            "blocked",
-            "cancelled",
-        ];
+        let valid_statuses = VALID_STATUSES;
         if !valid_statuses.contains(&s.as_str()) {
-            bail!(
+            return Err(ProjError::InvalidInput(format!(
                 "Invalid status '{}'. Valid statuses: {}",
                 s,
                 valid_statuses.join(", ")
-            );
+            ))
+            .into());
         }
         updates.push("status = ?");
         params.push(Box::new(s.clone()));
@@ -165,13 +354,14 @@ fn cmd_task_update(
 
     // Handle priority update
     if let Some(ref p) = priority {
-        let valid_priorities = ["low", "normal", "high", "urgent"];
+        let valid_priorities = VALID_PRIORITIES;
         if !valid_priorities.contains(&p.as_str()) {
-            bail!(
+            return Err(ProjError::InvalidInput(format!(
                 "Invalid priority '{}'. Valid priorities: {}",
                 p,
                 valid_priorities.join(", ")
-            );
+            ))
+            .into());
         }
         updates.push("priority = ?");
         params.push(Box::new(p.clone()));
@@ -188,12 +378,13 @@ fn cmd_task_update(
     }
 
     if updates.is_empty() {
-        println!(
-            "{} No updates specified for task #{}",
-            "!".yellow(),
-            task_id
+        return Err(
+            ProjError::NothingToDo(format!("No updates specified for task #{}", task_id)).into(),
         );
-        return Ok(());
+    }
+
+    if verify_commits {
+        verify_linked_commits(conn, task_id)?;
     }
 
     // Build and execute update query
@@ -229,12 +420,35 @@ fn cmd_task_update(
         changes.push(format!("blocked by: {}", b));
     }
 
-    println!(
-        "{} Updated task #{}: {}",
-        "✓".green(),
-        task_id,
-        changes.join(", ")
-    );
+    if porcelain::enabled() {
+        porcelain::print(&[&task_id.to_string()]);
+    } else {
+        println!(
+            "{} Updated task #{}: {}",
+            "✓".green(),
+            task_id,
+            changes.join(", ")
+        );
+    }
+
+    if is_completed {
+        let description: String = conn
+            .query_row(
+                "SELECT description FROM tasks WHERE task_id = ?1",
+                [task_id],
+                |row| row.get(0),
+            )
+            .unwrap_or_default();
+        webhooks::notify(
+            webhooks::TASK_COMPLETED,
+            &format!("Task #{} completed: {}", task_id, description),
+            serde_json::json!({
+                "task_id": task_id,
+                "uuid": record_uuid(conn, "tasks", "task_id", task_id),
+                "description": description,
+            }),
+        );
+    }
 
     // Auto-commit on task completion if enabled
     if is_completed {
@@ -249,9 +463,37 @@ fn cmd_task_update(
                     )
                     .unwrap_or_else(|_| format!("task #{}", task_id));
 
-                let commit_message = format!("[proj] Completed task #{}: {}", task_id, task_desc);
-                if let Err(e) = crate::commit::auto_commit(&commit_message, &config) {
-                    println!("  {} Auto-commit skipped: {}", "⚠".yellow(), e);
+                let branch = crate::paths::get_project_root()
+                    .ok()
+                    .and_then(|root| crate::git::current_branch(&root))
+                    .unwrap_or_default();
+                let session_id = crate::session::get_active_session(conn)
+                    .ok()
+                    .flatten()
+                    .map(|s| s.session_id.to_string())
+                    .unwrap_or_default();
+                let mut commit_message = crate::commit::render_template(
+                    &config.task_commit_template,
+                    &[
+                        ("task_id", task_id.to_string()),
+                        ("task", task_desc),
+                        ("session_id", session_id),
+                        ("branch", branch),
+                        ("date", chrono::Local::now().format("%Y-%m-%d").to_string()),
+                    ],
+                );
+                // The trailer links the commit to the task
+                commit_message.push_str(&format!("\n\nProj-Task: {}", task_id));
+                match crate::commit::auto_commit(&commit_message, &config) {
+                    Ok(true) => {
+                        if let Ok(root) = crate::paths::get_project_root() {
+                            let _ = crate::git::record_commit(conn, &root, "HEAD");
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => {
+                        porcelain::note(format!("  {} Auto-commit skipped: {}", "⚠".yellow(), e))
+                    }
                 }
             }
         }
@@ -260,46 +502,507 @@ fn cmd_task_update(
     Ok(())
 }
 
-/// List active tasks
-fn cmd_task_list(conn: &Connection) -> Result<()> {
+/// A commit linked to a task by its message
+struct LinkedCommit {
+    hash: String,
+    short_hash: String,
+    message: String,
+    committed_at: String,
+    author: Option<String>,
+    files_changed: i64,
+    insertions: i64,
+    deletions: i64,
+}
+
+/// Commits whose messages name a task, oldest first (none before schema v1.16)
+fn linked_commits(conn: &Connection, task_id: i64) -> Result<Vec<LinkedCommit>> {
+    let has_links: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='git_commit_tasks')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_links {
+        return Ok(Vec::new());
+    }
+    let mut stmt = conn.prepare(
+        "SELECT c.hash, c.short_hash, c.message, c.committed_at, c.author,
+                c.files_changed, c.insertions, c.deletions
+         FROM git_commit_tasks t JOIN git_commits c ON c.commit_id = t.commit_id
+         WHERE t.task_id = ?1
+         ORDER BY c.committed_at, c.commit_id",
+    )?;
+    let commits = stmt
+        .query_map([task_id], |row| {
+            Ok(LinkedCommit {
+                hash: row.get(0)?,
+                short_hash: row.get(1)?,
+                message: row.get(2)?,
+                committed_at: row.get(3)?,
+                author: row.get(4)?,
+                files_changed: row.get(5)?,
+                insertions: row.get(6)?,
+                deletions: row.get(7)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(commits)
+}
+
+/// Before completing a task: it must have linked commits, and every one must still be in git
+fn verify_linked_commits(conn: &Connection, task_id: i64) -> Result<()> {
+    let root = crate::paths::get_project_root()?;
+    if !crate::git::is_repo(&root) {
+        return Err(ProjError::GitUnavailable(
+            "--verify-commits needs a git repository".to_string(),
+        )
+        .into());
+    }
+    // Pick up commits made since the last sync
+    crate::git::sync_recent_commits(conn, &root, 50)?;
+
+    let commits = linked_commits(conn, task_id)?;
+    if commits.is_empty() {
+        return Err(ProjError::InvalidInput(format!(
+            "Task #{} has no linked commits. Name it in a commit message (Proj-Task: {} or #t{}), \
+             or complete it without --verify-commits.",
+            task_id, task_id, task_id
+        ))
+        .into());
+    }
+    let missing: Vec<&str> = commits
+        .iter()
+        .filter(|c| !crate::git::commit_exists(&root, &c.hash))
+        .map(|c| c.short_hash.as_str())
+        .collect();
+    if !missing.is_empty() {
+        return Err(ProjError::InvalidInput(format!(
+            "Task #{}: linked commit{} {} no longer in git (rebased or on a deleted branch?)",
+            task_id,
+            if missing.len() == 1 { "" } else { "s" },
+            missing.join(", ")
+        ))
+        .into());
+    }
+    porcelain::note(format!(
+        "{} {} linked commit{} verified",
+        "✓".green(),
+        commits.len(),
+        if commits.len() == 1 { "" } else { "s" }
+    ));
+    Ok(())
+}
+
+/// Show one task in full, with what's linked to it
+fn cmd_task_show(conn: &Connection, task_id: i64) -> Result<()> {
+    let task = conn
+        .query_row(
+            "SELECT task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes, due
+             FROM tasks WHERE task_id = ?1",
+            [task_id],
+            |row| {
+                Ok(Task {
+                    task_id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    created_at: parse_datetime(row.get::<_, String>(2)?),
+                    completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
+                    description: row.get(4)?,
+                    status: row.get(5)?,
+                    priority: row.get(6)?,
+                    blocked_by: row.get(7)?,
+                    parent_task_id: row.get(8)?,
+                    notes: row.get(9)?,
+                    due: row.get(10)?,
+                })
+            },
+        )
+        .optional()?
+        .ok_or_else(|| ProjError::InvalidInput(format!("Task #{} not found", task_id)))?;
+
+    if let Ok(root) = crate::paths::get_project_root() {
+        let _ = crate::git::sync_recent_commits(conn, &root, 50);
+    }
+    let commits = linked_commits(conn, task_id)?;
+
+    println!(
+        "\n{} {}\n",
+        format!("Task #{}:", task.task_id).bold(),
+        task.description
+    );
+    println!("  Status:    {}", task.status);
+    println!("  Priority:  {}", task.priority);
+    if let Some(due) = format_due(&task) {
+        println!("  Due:       {}", due);
+    }
+    println!(
+        "  Created:   {}{}",
+        task.created_at.format("%Y-%m-%d %H:%M"),
+        task.session_id
+            .map(|id| format!(" (session #{})", id))
+            .unwrap_or_default()
+    );
+    if let Some(completed) = task.completed_at {
+        println!("  Completed: {}", completed.format("%Y-%m-%d %H:%M"));
+    }
+    if let Some(blocked_by) = &task.blocked_by {
+        println!("  Blocked by: {}", blocked_by);
+    }
+    let task_tags = tags::tags_for(conn, Tagged::Task(task.task_id))?;
+    if !task_tags.is_empty() {
+        println!("  Tags:      {}", tags::format_tags(&task_tags).cyan());
+    }
+    if let Some((label, key)) = task_import::origin(conn, task_id)? {
+        println!("  Origin:    {} {}", label, key);
+    }
+    if let Some(notes) = &task.notes {
+        println!("  Notes:     {}", notes);
+    }
+
     let mut stmt = conn.prepare(
-        "SELECT task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes
+        "SELECT blocker_id, description, status FROM blockers WHERE related_task_id = ?1 ORDER BY created_at",
+    )?;
+    let blockers = stmt
+        .query_map([task_id], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    if !blockers.is_empty() {
+        println!("\n  {}", "Blockers:".bold());
+        for (id, description, status) in blockers {
+            let icon = if status == "active" {
+                "✗".red()
+            } else {
+                "✓".green()
+            };
+            println!("    {} #{} {} ({})", icon, id, description, status);
+        }
+    }
+
+    println!("\n  {}", format!("Commits ({}):", commits.len()).bold());
+    if commits.is_empty() {
+        println!(
+            "    {}",
+            format!(
+                "None yet. Name the task in a commit message: Proj-Task: {} or #t{}",
+                task_id, task_id
+            )
+            .dimmed()
+        );
+    }
+    for c in &commits {
+        println!(
+            "    {}  {}  {}",
+            c.short_hash.yellow(),
+            c.committed_at.get(..16).unwrap_or(&c.committed_at),
+            c.message
+        );
+        println!(
+            "             {}",
+            format!(
+                "{} · {} file{}, +{} -{}",
+                c.author.as_deref().unwrap_or_default(),
+                c.files_changed,
+                if c.files_changed == 1 { "" } else { "s" },
+                c.insertions,
+                c.deletions
+            )
+            .dimmed()
+        );
+    }
+    println!();
+    Ok(())
+}
+
+/// Edit a task's description, priority, or notes
+fn cmd_task_edit(
+    conn: &Connection,
+    session_id: i64,
+    task_id: i64,
+    description: Option<String>,
+    priority: Option<String>,
+    notes: Option<String>,
+    due: Option<String>,
+) -> Result<()> {
+    let current: Option<String> = conn
+        .query_row(
+            "SELECT description FROM tasks WHERE task_id = ?1",
+            [task_id],
+            |row| row.get(0),
+        )
+        .ok();
+
+    let Some(current_description) = current else {
+        return Err(ProjError::InvalidInput(format!("Task #{} not found", task_id)).into());
+    };
+    task_import::ensure_local(conn, task_id)?;
+
+    if let Some(ref p) = priority {
+        let valid_priorities = VALID_PRIORITIES;
+        if !valid_priorities.contains(&p.as_str()) {
+            return Err(ProjError::InvalidInput(format!(
+                "Invalid priority '{}'. Valid priorities: {}",
+                p,
+                valid_priorities.join(", ")
+            ))
+            .into());
+        }
+    }
+
+    if let Some(ref d) = description {
+        if d.trim().is_empty() {
+            return Err(
+                ProjError::InvalidInput("Task description cannot be empty".to_string()).into(),
+            );
+        }
+    }
+
+    // "none" clears the due date; anything else must be a valid date
+    let due = match due.as_deref() {
+        Some("none") => Some(None),
+        Some(d) => Some(Some(validate_due_date(d)?)),
+        None => None,
+    };
+
+    if description.is_none() && priority.is_none() && notes.is_none() && due.is_none() {
+        return Err(
+            ProjError::NothingToDo(format!("No edits specified for task #{}", task_id)).into(),
+        );
+    }
+
+    conn.execute(
+        "UPDATE tasks SET
+           description = COALESCE(?1, description),
+           priority = COALESCE(?2, priority),
+           notes = COALESCE(?3, notes)
+         WHERE task_id = ?4",
+        rusqlite::params![description, priority, notes, task_id],
+    )?;
+
+    if let Some(ref d) = due {
+        conn.execute(
+            "UPDATE tasks SET due = ?1 WHERE task_id = ?2",
+            rusqlite::params![d, task_id],
+        )?;
+    }
+
+    let mut changes = Vec::new();
+    if let Some(ref d) = description {
+        changes.push(format!(
+            "description: \"{}\" → \"{}\"",
+            truncate(&current_description, 30),
+            truncate(d, 30)
+        ));
+    }
+    if let Some(ref p) = priority {
+        changes.push(format!("priority → {}", p));
+    }
+    if notes.is_some() {
+        changes.push("notes updated".to_string());
+    }
+    match due {
+        Some(Some(ref d)) => changes.push(format!("due → {}", d)),
+        Some(None) => changes.push("due date cleared".to_string()),
+        None => {}
+    }
+
+    let summary = format!("Task #{} edited: {}", task_id, changes.join(", "));
+    conn.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
+        rusqlite::params![session_id, task_id, summary],
+    )?;
+
+    if porcelain::enabled() {
+        porcelain::print(&[&task_id.to_string()]);
+    } else {
+        println!(
+            "{} Edited task #{}: {}",
+            "✓".green(),
+            task_id,
+            changes.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Delete a task, recording the deletion in the activity log
+fn cmd_task_delete(conn: &Connection, session_id: i64, task_id: i64, force: bool) -> Result<()> {
+    let task: Option<(String, String)> = conn
+        .query_row(
+            "SELECT description, status FROM tasks WHERE task_id = ?1",
+            [task_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .ok();
+
+    let Some((description, status)) = task else {
+        return Err(ProjError::InvalidInput(format!("Task #{} not found", task_id)).into());
+    };
+
+    if !force {
+        if !atty::is(atty::Stream::Stdin) {
+            return Err(ProjError::InvalidInput(format!(
+                "Refusing to delete task #{} without confirmation. Use --force in non-interactive mode.",
+                task_id
+            )).into());
+        }
+
+        println!("Task #{} [{}]: {}", task_id, status, description);
+        if !Confirm::new()
+            .with_prompt("Delete this task?")
+            .default(false)
+            .interact()?
+        {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let tx = write_transaction(conn)?;
+
+    // Detach references so foreign keys don't block the delete
+    tx.execute(
+        "UPDATE tasks SET parent_task_id = NULL WHERE parent_task_id = ?1",
+        [task_id],
+    )?;
+    tx.execute(
+        "UPDATE blockers SET related_task_id = NULL WHERE related_task_id = ?1",
+        [task_id],
+    )?;
+    tx.execute("DELETE FROM item_tags WHERE task_id = ?1", [task_id])?;
+    tx.execute("DELETE FROM tasks WHERE task_id = ?1", [task_id])?;
+
+    let summary = format!(
+        "Task deleted: #{} [{}] {}",
+        task_id,
+        status,
+        truncate(&description, 50)
+    );
+    tx.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_delete', ?2, ?3)",
+        rusqlite::params![session_id, task_id, summary],
+    )?;
+
+    tx.commit()?;
+
+    if porcelain::enabled() {
+        porcelain::print(&[&task_id.to_string()]);
+    } else {
+        println!("{} Deleted task #{}: {}", "✓".green(), task_id, description);
+    }
+    Ok(())
+}
+
+/// List active tasks
+fn cmd_task_list(conn: &Connection, args: &TaskListArgs) -> Result<()> {
+    let tag = args.tag.as_deref().and_then(tags::normalize);
+    let statuses = parse_choices("status", args.status.as_deref(), &VALID_STATUSES)?;
+    let priorities = parse_choices("priority", args.priority.as_deref(), &VALID_PRIORITIES)?;
+    let since = args.since.as_deref().map(validate_since).transpose()?;
+
+    let mut conditions = vec![match &statuses {
+        Some(list) => format!("status IN ({})", quoted(list)),
+        None => "status NOT IN ('completed', 'cancelled')".to_string(),
+    }];
+    if let Some(list) = &priorities {
+        conditions.push(format!("priority IN ({})", quoted(list)));
+    }
+    conditions.push(
+        "(?1 IS NULL OR task_id IN (SELECT task_id FROM item_tags WHERE tag = ?1))".to_string(),
+    );
+    conditions.push("(?2 IS NULL OR date(created_at) >= ?2)".to_string());
+    let filter = conditions.join(" AND ");
+
+    let total: i64 = conn.query_row(
+        &format!("SELECT COUNT(*) FROM tasks WHERE {}", filter),
+        rusqlite::params![tag, since],
+        |row| row.get(0),
+    )?;
+    let mut stmt = conn.prepare(&format!(
+        "SELECT task_id, session_id, created_at, completed_at, description, status, priority, blocked_by, parent_task_id, notes, due, source_ref, source
          FROM tasks
-         WHERE status NOT IN ('completed', 'cancelled')
+         WHERE {}
          ORDER BY
+           CASE WHEN due < date('now') THEN 0 WHEN due <= date('now', '+3 days') THEN 1 ELSE 2 END,
            CASE priority WHEN 'urgent' THEN 1 WHEN 'high' THEN 2 WHEN 'normal' THEN 3 ELSE 4 END,
-           created_at"
+           created_at
+         LIMIT ?3 OFFSET ?4",
+        filter
+    ))?;
+
+    let limit = args.page.limit.map_or(-1, |l| l as i64);
+    let tasks = stmt.query_map(
+        rusqlite::params![tag, since, limit, args.page.offset as i64],
+        |row| {
+            Ok((
+                Task {
+                    task_id: row.get(0)?,
+                    session_id: row.get(1)?,
+                    created_at: parse_datetime(row.get::<_, String>(2)?),
+                    completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
+                    description: row.get(4)?,
+                    status: row.get(5)?,
+                    priority: row.get(6)?,
+                    blocked_by: row.get(7)?,
+                    parent_task_id: row.get(8)?,
+                    notes: row.get(9)?,
+                    due: row.get(10)?,
+                },
+                row.get::<_, Option<String>>(11)?,
+                row.get::<_, Option<String>>(12)?,
+            ))
+        },
     )?;
 
-    let tasks = stmt.query_map([], |row| {
-        Ok(Task {
-            task_id: row.get(0)?,
-            session_id: row.get(1)?,
-            created_at: parse_datetime(row.get::<_, String>(2)?),
-            completed_at: row.get::<_, Option<String>>(3)?.map(parse_datetime),
-            description: row.get(4)?,
-            status: row.get(5)?,
-            priority: row.get(6)?,
-            blocked_by: row.get(7)?,
-            parent_task_id: row.get(8)?,
-            notes: row.get(9)?,
-        })
-    })?;
+    let tasks: Vec<(Task, Option<String>, Option<String>)> =
+        tasks.collect::<Result<Vec<_>, _>>()?;
 
-    let tasks: Vec<Task> = tasks.collect::<Result<Vec<_>, _>>()?;
+    // Porcelain: ID, status, priority, due date, description
+    if porcelain::enabled() {
+        for (task, _, _) in &tasks {
+            porcelain::print(&[
+                &task.task_id.to_string(),
+                &task.status,
+                &task.priority,
+                task.due.as_deref().unwrap_or_default(),
+                &task.description,
+            ]);
+        }
+        return Ok(());
+    }
 
+    let filtered = statuses.is_some() || priorities.is_some() || since.is_some();
     if tasks.is_empty() {
-        println!("No active tasks.");
+        if total > 0 {
+            println!("No tasks past the first {} (--offset).", total);
+        } else if filtered {
+            println!("No tasks match these filters.");
+        } else {
+            match &tag {
+                Some(t) => println!("No active tasks tagged #{}.", t),
+                None => println!("No active tasks."),
+            }
+        }
         return Ok(());
     }
 
-    println!("{}", "Active Tasks:".bold());
-    println!("{}", "-".repeat(60));
+    let mut out = String::new();
+    let title = if filtered { "Tasks" } else { "Active Tasks" };
+    match &tag {
+        Some(t) => writeln!(out, "{}", format!("{} (#{}):", title, t).bold())?,
+        None => writeln!(out, "{}", format!("{}:", title).bold())?,
+    }
+    writeln!(out, "{}", "-".repeat(60))?;
 
-    for task in tasks {
+    let shown = tasks.len();
+    for (task, source_ref, source) in tasks {
         let status_icon = match task.status.as_str() {
             "in_progress" => "◐".yellow(),
             "blocked" => "✗".red(),
+            "completed" => "✓".green(),
+            "cancelled" => "-".dimmed(),
             "pending" => "○".white(),
             _ => "○".white(),
         };
@@ -312,35 +1015,193 @@ fn cmd_task_list(conn: &Connection) -> Result<()> {
             _ => format!("[{}]", task.priority),
         };
 
-        println!(
-            "{} #{:<4} {} {}",
-            status_icon, task.task_id, priority_display, task.description
-        );
+        let due_display = format_due(&task)
+            .map(|d| format!(" ({})", d))
+            .unwrap_or_default();
+
+        let task_tags = tags::tags_for(conn, Tagged::Task(task.task_id))?;
+        let tags_display = if task_tags.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", tags::format_tags(&task_tags).cyan())
+        };
+
+        writeln!(
+            out,
+            "{} #{:<4} {} {}{}{}",
+            status_icon,
+            task.task_id,
+            priority_display,
+            task.description,
+            due_display,
+            tags_display
+        )?;
 
         if let Some(blocked_by) = &task.blocked_by {
-            println!("         {} Blocked by: {}", "→".red(), blocked_by);
+            writeln!(out, "         {} Blocked by: {}", "→".red(), blocked_by)?;
         }
 
         if let Some(notes) = &task.notes {
-            println!("         Notes: {}", notes.dimmed());
+            writeln!(out, "         Notes: {}", notes.dimmed())?;
+        }
+
+        if let Some(source_ref) = &source_ref {
+            let origin = match source.as_deref() {
+                Some(provider @ ("jira" | "linear")) => {
+                    format!("from {} {}", crate::trackers::label(provider), source_ref)
+                }
+                _ => format!("at {}", source_ref),
+            };
+            writeln!(out, "         {}", origin.dimmed())?;
         }
     }
 
+    if (shown as i64) < total {
+        let first = args.page.offset + 1;
+        writeln!(
+            out,
+            "{}",
+            format!(
+                "\nShowing {}-{} of {}. Use --limit and --offset for more.",
+                first,
+                args.page.offset + shown,
+                total
+            )
+            .dimmed()
+        )?;
+    }
+    pager::page(&out);
     Ok(())
 }
 
+/// Choose an active task with a fuzzy search, then act on it
+fn cmd_task_pick(conn: &Connection, print: bool, start: bool, done: bool) -> Result<()> {
+    if !atty::is(atty::Stream::Stdin) || !atty::is(atty::Stream::Stderr) {
+        return Err(ProjError::InvalidInput(
+            "proj task pick needs a terminal. Use 'proj tasks' to list tasks.".to_string(),
+        )
+        .into());
+    }
+
+    let repo = Repository::begin(conn)?;
+    let tasks = repo.active_tasks()?.to_vec();
+    repo.finish()?;
+    if tasks.is_empty() {
+        return Err(ProjError::NothingToDo("No active tasks.".to_string()).into());
+    }
+
+    // Tags and the due date are part of each line, so typing them finds the task too
+    let mut items = Vec::with_capacity(tasks.len());
+    for task in &tasks {
+        let task_tags = tags::tags_for(conn, Tagged::Task(task.task_id))?;
+        let mut item = format!(
+            "#{:<4} [{}] {}",
+            task.task_id, task.priority, task.description
+        );
+        if task.status != "pending" {
+            item.push_str(&format!(" ({})", task.status.replace('_', " ")));
+        }
+        if let Some(due) = &task.due {
+            item.push_str(&format!(" (due {})", due));
+        }
+        if !task_tags.is_empty() {
+            item.push_str(&format!(" {}", tags::format_tags(&task_tags)));
+        }
+        items.push(item);
+    }
+    let Some(index) = FuzzySelect::new()
+        .with_prompt("Task (type to filter)")
+        .items(&items)
+        .default(0)
+        .max_length(PICK_ROWS)
+        .interact_opt()?
+    else {
+        return Ok(());
+    };
+    let task_id = tasks[index].task_id;
+
+    let action = if print {
+        "print"
+    } else if start {
+        "start"
+    } else if done {
+        "done"
+    } else {
+        let labels: Vec<&str> = PICK_ACTIONS.iter().map(|(_, label)| *label).collect();
+        match Select::new()
+            .with_prompt(format!("#{}", task_id))
+            .items(&labels)
+            .default(0)
+            .interact_opt()?
+        {
+            Some(i) => PICK_ACTIONS[i].0,
+            None => return Ok(()),
+        }
+    };
+    let status = match action {
+        "show" => return cmd_task_show(conn, task_id),
+        "print" => {
+            println!("{}", task_id);
+            return Ok(());
+        }
+        "start" => "in_progress",
+        _ => "completed",
+    };
+    cmd_task_update(
+        conn,
+        task_id,
+        Some(status.to_string()),
+        None,
+        None,
+        None,
+        false,
+    )
+}
+
+/// Comma-separated values of a filter option, checked against the valid ones.
+/// "all" (or no value) means no filter.
+fn parse_choices(flag: &str, value: Option<&str>, valid: &[&str]) -> Result<Option<Vec<String>>> {
+    let Some(value) = value.filter(|v| v.trim() != "all") else {
+        return Ok(None);
+    };
+    let choices: Vec<String> = value
+        .split(',')
+        .map(|v| v.trim().to_lowercase())
+        .filter(|v| !v.is_empty())
+        .collect();
+    if let Some(bad) = choices.iter().find(|c| !valid.contains(&c.as_str())) {
+        return Err(ProjError::InvalidInput(format!(
+            "Invalid --{} '{}'. Use {}, or all.",
+            flag,
+            bad,
+            valid.join(", ")
+        ))
+        .into());
+    }
+    Ok(Some(choices))
+}
+
+/// SQL list of already-validated values
+fn quoted(values: &[String]) -> String {
+    values
+        .iter()
+        .map(|v| format!("'{}'", v))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Validate a --since argument, returning it normalized as YYYY-MM-DD
+fn validate_since(since: &str) -> Result<String> {
+    chrono::NaiveDate::parse_from_str(since.trim(), "%Y-%m-%d")
+        .map(|d| d.format("%Y-%m-%d").to_string())
+        .map_err(|_| {
+            ProjError::InvalidInput(format!("Invalid --since '{}'. Use YYYY-MM-DD.", since)).into()
+        })
+}
+
 /// Parse datetime string from SQLite
 fn parse_datetime(s: String) -> chrono::DateTime<chrono::Utc> {
     chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
         .map(|dt| dt.and_utc())
         .unwrap_or_else(|_| chrono::Utc::now())
 }
-
-/// Truncate a string to a maximum length
-fn truncate(s: &str, max_len: usize) -> String {
-    if s.len() <= max_len {
-        s.to_string()
-    } else {
-        format!("{}...", &s[..max_len - 3])
-    }
-}
@@ -0,0 +1,503 @@
+// Task import - read-only tasks mirrored from Jira or Linear (task_links table)
+
+use std::collections::{HashMap, HashSet};
+
+use anyhow::{bail, Context, Result};
+use colored::Colorize;
+use rusqlite::{Connection, OptionalExtension};
+
+use crate::config::{ProjectConfig, TaskImportConfig};
+use crate::database::{get_schema_version, open_database, record_uuid};
+use crate::error::ProjError;
+use crate::paths::get_tracking_db_path;
+use crate::session::get_or_create_session_with_info;
+use crate::text::truncate;
+use crate::trackers::{self, RemoteIssue, Tracker};
+use crate::webhooks;
+
+/// project_meta key holding when imported tasks were last refreshed
+const REFRESHED_AT_KEY: &str = "task_import_refreshed_at";
+
+/// Counts from one import or refresh
+#[derive(Default)]
+pub struct ImportResult {
+    pub created: usize,
+    pub updated: usize,
+    pub unchanged: usize,
+    /// Closed tickets that were never imported
+    pub skipped: usize,
+}
+
+impl ImportResult {
+    pub fn has_changes(&self) -> bool {
+        self.created + self.updated > 0
+    }
+
+    fn add(&mut self, other: ImportResult) {
+        self.created += other.created;
+        self.updated += other.updated;
+        self.unchanged += other.unchanged;
+        self.skipped += other.skipped;
+    }
+}
+
+/// `proj task import --from <tracker>`, or `--refresh` to re-run every saved import
+pub fn run(from: Option<&str>, jql: Option<&str>, team: Option<&str>, refresh: bool) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    require_table(&conn)?;
+    let mut config = ProjectConfig::load()?;
+    let session_id = get_or_create_session_with_info(&conn)?.session.session_id;
+
+    let result = if refresh {
+        if config.task_imports.is_empty() {
+            println!("No saved imports. Run 'proj task import --from jira --jql \"...\"' or '--from linear --team <key>' first.");
+            return Ok(());
+        }
+        refresh_all(&conn, session_id, &config, true)?
+    } else {
+        let from = from.unwrap_or_default();
+        let query = match (from, jql, team) {
+            ("jira", Some(jql), _) => jql,
+            ("linear", _, Some(team)) => team,
+            ("jira", _, _) => bail!("Jira imports need --jql \"<query>\""),
+            _ => bail!("Linear imports need --team <key>"),
+        };
+        let tracker = Tracker::connect(from)?;
+        println!("Importing from {}: {}", trackers::label(from), query.cyan());
+        let result = import(
+            &conn,
+            session_id,
+            &tracker,
+            query,
+            &mut HashSet::new(),
+            true,
+        )?;
+
+        let saved = TaskImportConfig {
+            from: from.to_string(),
+            query: query.to_string(),
+        };
+        if !config.task_imports.contains(&saved) {
+            config.task_imports.push(saved);
+            config.save()?;
+        }
+        mark_refreshed(&conn)?;
+        result
+    };
+
+    println!();
+    println!(
+        "{} {} task(s) imported, {} updated, {} unchanged",
+        "✓".green(),
+        result.created,
+        result.updated,
+        result.unchanged
+    );
+    if result.skipped > 0 {
+        println!(
+            "  {} closed ticket(s) skipped (only open tickets become tasks)",
+            result.skipped
+        );
+    }
+    if !refresh {
+        println!("Refresh with: proj task import --refresh");
+    }
+    Ok(())
+}
+
+/// Whether saved imports are older than `task_import_refresh_hours` (never offline)
+pub fn refresh_due(conn: &Connection, config: &ProjectConfig) -> bool {
+    if config.task_imports.is_empty()
+        || config.task_import_refresh_hours <= 0
+        || crate::config::offline()
+    {
+        return false;
+    }
+    if require_table(conn).is_err() {
+        return false;
+    }
+    let last: Option<String> = conn
+        .query_row(
+            "SELECT value FROM project_meta WHERE key = ?1",
+            [REFRESHED_AT_KEY],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()
+        .flatten();
+    let Some(last) = last
+        .as_deref()
+        .and_then(|l| chrono::NaiveDateTime::parse_from_str(l, "%Y-%m-%d %H:%M:%S").ok())
+    else {
+        return true;
+    };
+    chrono::Utc::now().naive_utc() - last
+        >= chrono::Duration::hours(config.task_import_refresh_hours)
+}
+
+/// Re-run every saved import, then refresh imported tasks no query returned
+/// (e.g. tickets that moved out of a JQL filter when they were closed)
+pub fn refresh_all(
+    conn: &Connection,
+    session_id: i64,
+    config: &ProjectConfig,
+    verbose: bool,
+) -> Result<ImportResult> {
+    let mut result = ImportResult::default();
+
+    for from in ["jira", "linear"] {
+        let queries: Vec<&TaskImportConfig> = config
+            .task_imports
+            .iter()
+            .filter(|i| i.from == from)
+            .collect();
+        if queries.is_empty() {
+            continue;
+        }
+        let tracker = Tracker::connect(from)?;
+
+        let mut seen = HashSet::new();
+        for saved in queries {
+            if verbose {
+                println!(
+                    "Refreshing from {}: {}",
+                    trackers::label(from),
+                    saved.query.cyan()
+                );
+            }
+            result.add(import(
+                conn,
+                session_id,
+                &tracker,
+                &saved.query,
+                &mut seen,
+                verbose,
+            )?);
+        }
+
+        let mut stmt = conn.prepare(
+            "SELECT l.remote_id FROM task_links l JOIN tasks t ON t.task_id = l.task_id
+             WHERE l.provider = ?1 AND t.status NOT IN ('completed', 'cancelled')",
+        )?;
+        let stale: Vec<String> = stmt
+            .query_map([from], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        for key in stale.iter().filter(|k| !seen.contains(*k)) {
+            match tracker.fetch(key)? {
+                Some(issue) => {
+                    result.add(upsert(conn, session_id, from, &issue, verbose)?);
+                }
+                None => {
+                    cancel_deleted(conn, session_id, from, key, verbose)?;
+                    result.updated += 1;
+                }
+            }
+        }
+    }
+
+    mark_refreshed(conn)?;
+    Ok(result)
+}
+
+/// Import every ticket a query returns; keys are added to `seen`
+fn import(
+    conn: &Connection,
+    session_id: i64,
+    tracker: &Tracker,
+    query: &str,
+    seen: &mut HashSet<String>,
+    verbose: bool,
+) -> Result<ImportResult> {
+    let mut result = ImportResult::default();
+    for issue in tracker.search(query)? {
+        if seen.insert(issue.key.clone()) {
+            result.add(upsert(
+                conn,
+                session_id,
+                tracker.provider(),
+                &issue,
+                verbose,
+            )?);
+        }
+    }
+    Ok(result)
+}
+
+/// The local task behind a link (description is None if the task was deleted)
+struct Mirror {
+    task_id: i64,
+    description: Option<String>,
+    status: Option<String>,
+    priority: Option<String>,
+    due: Option<String>,
+}
+
+/// Create or update the task mirroring one ticket. Tasks the user deleted stay deleted.
+fn upsert(
+    conn: &Connection,
+    session_id: i64,
+    provider: &str,
+    issue: &RemoteIssue,
+    verbose: bool,
+) -> Result<ImportResult> {
+    let mut result = ImportResult::default();
+    let label = trackers::label(provider);
+
+    let linked = conn
+        .query_row(
+            "SELECT l.task_id, t.description, t.status, t.priority, t.due
+             FROM task_links l LEFT JOIN tasks t ON t.task_id = l.task_id
+             WHERE l.provider = ?1 AND l.remote_id = ?2",
+            rusqlite::params![provider, issue.key],
+            |row| {
+                Ok(Mirror {
+                    task_id: row.get(0)?,
+                    description: row.get(1)?,
+                    status: row.get(2)?,
+                    priority: row.get(3)?,
+                    due: row.get(4)?,
+                })
+            },
+        )
+        .optional()?;
+
+    match linked {
+        // Deleted locally: don't bring it back
+        Some(Mirror {
+            description: None, ..
+        }) => {
+            result.unchanged += 1;
+        }
+        Some(Mirror {
+            task_id,
+            description: Some(description),
+            status,
+            priority,
+            due,
+        }) => {
+            let title = &issue.title;
+            if description == *title
+                && status.as_deref() == Some(issue.status.as_str())
+                && priority.as_deref() == Some(issue.priority.as_str())
+                && due == issue.due
+            {
+                result.unchanged += 1;
+            } else {
+                let completed = issue.status == "completed";
+                conn.execute(
+                    "UPDATE tasks SET description = ?1, status = ?2, priority = ?3, due = ?4,
+                       completed_at = CASE WHEN ?2 = 'completed' THEN COALESCE(completed_at, datetime('now')) ELSE NULL END
+                     WHERE task_id = ?5",
+                    rusqlite::params![title, issue.status, issue.priority, issue.due, task_id],
+                )?;
+                let summary = format!(
+                    "Task refreshed from {} {}: {}",
+                    label,
+                    issue.key,
+                    truncate(title, 40)
+                );
+                conn.execute(
+                    "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
+                    rusqlite::params![session_id, task_id, summary],
+                )?;
+                if verbose {
+                    println!(
+                        "  {} #{:<4} {} {} ({})",
+                        "~".yellow(),
+                        task_id,
+                        issue.key.dimmed(),
+                        truncate(title, 50),
+                        issue.status
+                    );
+                }
+                if completed && status.as_deref() != Some("completed") {
+                    webhooks::notify(
+                        webhooks::TASK_COMPLETED,
+                        &format!(
+                            "Task #{} completed: {} ({} {})",
+                            task_id, title, label, issue.key
+                        ),
+                        serde_json::json!({
+                            "task_id": task_id,
+                            "uuid": record_uuid(conn, "tasks", "task_id", task_id),
+                            "description": title,
+                        }),
+                    );
+                }
+                result.updated += 1;
+            }
+            conn.execute(
+                "UPDATE task_links SET url = ?1, remote_state = ?2, local_status = ?3, synced_at = datetime('now')
+                 WHERE provider = ?4 AND remote_id = ?5",
+                rusqlite::params![issue.url, issue.state, issue.status, provider, issue.key],
+            )?;
+        }
+        None if matches!(issue.status.as_str(), "completed" | "cancelled") => {
+            result.skipped += 1;
+        }
+        None => {
+            conn.execute(
+                "INSERT INTO tasks (session_id, description, status, priority, due, source, source_ref)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                rusqlite::params![
+                    session_id,
+                    issue.title,
+                    issue.status,
+                    issue.priority,
+                    issue.due,
+                    provider,
+                    issue.key
+                ],
+            )?;
+            let task_id = conn.last_insert_rowid();
+            conn.execute(
+                "INSERT INTO task_links (task_id, provider, remote_id, url, remote_state, local_status)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                rusqlite::params![
+                    task_id,
+                    provider,
+                    issue.key,
+                    issue.url,
+                    issue.state,
+                    issue.status
+                ],
+            )?;
+            let summary = format!(
+                "Task imported from {} {}: {}",
+                label,
+                issue.key,
+                truncate(&issue.title, 40)
+            );
+            conn.execute(
+                "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
+                rusqlite::params![session_id, task_id, summary],
+            )?;
+            if verbose {
+                println!(
+                    "  {} #{:<4} {} {}",
+                    "+".green(),
+                    task_id,
+                    issue.key.dimmed(),
+                    truncate(&issue.title, 50)
+                );
+            }
+            result.created += 1;
+        }
+    }
+    Ok(result)
+}
+
+/// A ticket deleted in the tracker: cancel its task
+fn cancel_deleted(
+    conn: &Connection,
+    session_id: i64,
+    provider: &str,
+    key: &str,
+    verbose: bool,
+) -> Result<()> {
+    let label = trackers::label(provider);
+    let task_id: i64 = conn.query_row(
+        "SELECT task_id FROM task_links WHERE provider = ?1 AND remote_id = ?2",
+        [provider, key],
+        |row| row.get(0),
+    )?;
+    conn.execute(
+        "UPDATE tasks SET status = 'cancelled', completed_at = NULL WHERE task_id = ?1",
+        [task_id],
+    )?;
+    conn.execute(
+        "UPDATE task_links SET remote_state = 'deleted', local_status = 'cancelled', synced_at = datetime('now')
+         WHERE provider = ?1 AND remote_id = ?2",
+        [provider, key],
+    )?;
+    let summary = format!("Task cancelled: {} {} was deleted", label, key);
+    conn.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
+        rusqlite::params![session_id, task_id, summary],
+    )?;
+    if verbose {
+        println!(
+            "  {} #{:<4} {} deleted in {}",
+            "-".dimmed(),
+            task_id,
+            key.dimmed(),
+            label
+        );
+    }
+    Ok(())
+}
+
+/// Where an imported task came from: (tracker label, key). None for local tasks.
+pub fn origin(conn: &Connection, task_id: i64) -> Result<Option<(String, String)>> {
+    let origin = conn
+        .query_row(
+            "SELECT source, source_ref FROM tasks WHERE task_id = ?1 AND source IN ('jira', 'linear')",
+            [task_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?)),
+        )
+        .optional()?;
+    Ok(origin.map(|(source, key)| {
+        (
+            trackers::label(&source).to_string(),
+            key.unwrap_or_default(),
+        )
+    }))
+}
+
+/// Origins of every imported task, by task ID, as "<tracker label> <key>"
+pub fn origins(conn: &Connection) -> Result<HashMap<i64, String>> {
+    let mut stmt = conn.prepare_cached(
+        "SELECT task_id, source, source_ref FROM tasks WHERE source IN ('jira', 'linear')",
+    )?;
+    let origins = stmt
+        .query_map([], |row| {
+            let source: String = row.get(1)?;
+            let key: Option<String> = row.get(2)?;
+            Ok((
+                row.get(0)?,
+                format!("{} {}", trackers::label(&source), key.unwrap_or_default()),
+            ))
+        })?
+        .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+    Ok(origins)
+}
+
+/// Imported tasks are read-only; fail with a pointer to the tracker
+pub fn ensure_local(conn: &Connection, task_id: i64) -> Result<()> {
+    if let Some((label, key)) = origin(conn, task_id)? {
+        bail!(
+            "Task #{} is imported from {} ({}). Change it there, then run 'proj task import --refresh'.",
+            task_id,
+            label,
+            key
+        );
+    }
+    Ok(())
+}
+
+fn mark_refreshed(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO project_meta (key, value, updated_at) VALUES (?1, datetime('now'), datetime('now'))",
+        [REFRESHED_AT_KEY],
+    )?;
+    Ok(())
+}
+
+/// Fail with an upgrade hint if the tracking database predates task links
+fn require_table(conn: &Connection) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='task_links')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.13".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
@@ -0,0 +1,323 @@
+// Task sync - link tasks to issues in external trackers (task_links table)
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use rusqlite::Connection;
+
+use crate::database::{get_schema_version, open_database, record_uuid};
+use crate::error::ProjError;
+use crate::github::GitHub;
+use crate::paths::{get_project_root, get_tracking_db_path};
+use crate::session::get_or_create_session_with_info;
+use crate::text::truncate;
+use crate::webhooks;
+
+/// Tasks with this tag get an issue created for them
+pub const PUBLISH_TAG: &str = "publish";
+
+/// A task_links row joined with its task (status is None if the task was deleted)
+struct Link {
+    link_id: i64,
+    task_id: i64,
+    remote_id: String,
+    remote_state: Option<String>,
+    local_status: Option<String>,
+    status: Option<String>,
+    description: String,
+}
+
+/// A task waiting for its first issue
+struct Unpublished {
+    task_id: i64,
+    description: String,
+    notes: Option<String>,
+    status: String,
+}
+
+/// Two-way sync with GitHub Issues: publish tasks tagged `publish`, then reconcile
+/// linked issues. When both sides changed since the last sync, the issue wins.
+pub fn github(repo: Option<&str>, dry_run: bool) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+    require_table(&conn)?;
+    let root = get_project_root()?;
+    let gh = GitHub::connect(&root, repo)?;
+
+    let session_id = if dry_run {
+        None
+    } else {
+        Some(get_or_create_session_with_info(&conn)?.session.session_id)
+    };
+
+    println!("Syncing tasks with {}...", gh.repo.cyan());
+
+    let mut tasks_updated = 0;
+    let mut issues_updated = 0;
+
+    for link in linked_tasks(&conn, &gh.repo)? {
+        let number: i64 = link
+            .remote_id
+            .rsplit('#')
+            .next()
+            .and_then(|n| n.parse().ok())
+            .unwrap_or_default();
+
+        let Some(status) = link.status.as_deref() else {
+            println!(
+                "  {} Issue #{} unlinked (task #{} was deleted)",
+                "-".dimmed(),
+                number,
+                link.task_id
+            );
+            if !dry_run {
+                conn.execute("DELETE FROM task_links WHERE link_id = ?1", [link.link_id])?;
+            }
+            continue;
+        };
+
+        let issue = gh.get_issue(number)?;
+        let task_done = matches!(status, "completed" | "cancelled");
+        let mut new_status = status.to_string();
+        let mut issue_state = issue.state.clone();
+
+        if link.remote_state.as_deref() != Some(issue.state.as_str()) {
+            // Issue closed or reopened on GitHub
+            if issue.state == "closed" {
+                new_status = if issue.state_reason.as_deref() == Some("not_planned") {
+                    "cancelled".to_string()
+                } else {
+                    "completed".to_string()
+                };
+            } else if task_done {
+                new_status = "pending".to_string();
+            }
+            if new_status != status {
+                println!(
+                    "  {} Task #{} {} (issue #{} {})",
+                    "✓".green(),
+                    link.task_id,
+                    new_status,
+                    number,
+                    if issue.state == "closed" {
+                        "closed"
+                    } else {
+                        "reopened"
+                    }
+                );
+                if let Some(session_id) = session_id {
+                    set_task_status(
+                        &conn,
+                        session_id,
+                        link.task_id,
+                        &new_status,
+                        &link.description,
+                        number,
+                    )?;
+                }
+                tasks_updated += 1;
+            }
+        } else if link.local_status.as_deref() != Some(status) {
+            // Task finished or reopened locally
+            let want = if task_done { "closed" } else { "open" };
+            if want != issue.state {
+                let reason = match status {
+                    "completed" => Some("completed"),
+                    "cancelled" => Some("not_planned"),
+                    _ => None,
+                };
+                println!(
+                    "  {} Issue #{} {} (task #{} {})",
+                    "→".cyan(),
+                    number,
+                    if want == "closed" {
+                        "closed"
+                    } else {
+                        "reopened"
+                    },
+                    link.task_id,
+                    status
+                );
+                if !dry_run {
+                    issue_state = gh.set_state(number, want, reason)?.state;
+                }
+                issues_updated += 1;
+            }
+        }
+
+        if !dry_run {
+            conn.execute(
+                "UPDATE task_links SET remote_state = ?1, local_status = ?2, url = ?3, synced_at = datetime('now')
+                 WHERE link_id = ?4",
+                rusqlite::params![issue_state, new_status, issue.url, link.link_id],
+            )?;
+        }
+    }
+
+    let mut created = 0;
+    for Unpublished {
+        task_id,
+        description,
+        notes,
+        status,
+    } in unpublished_tasks(&conn)?
+    {
+        if dry_run {
+            println!(
+                "  {} Task #{} would get a new issue: {}",
+                "+".green(),
+                task_id,
+                truncate(&description, 60)
+            );
+        } else {
+            let mut body = notes.unwrap_or_default();
+            if !body.is_empty() {
+                body.push_str("\n\n");
+            }
+            body.push_str(&format!("_Created by proj from task #{}_", task_id));
+
+            let issue = gh.create_issue(&description, &body)?;
+            conn.execute(
+                "INSERT INTO task_links (task_id, provider, remote_id, url, remote_state, local_status)
+                 VALUES (?1, 'github', ?2, ?3, ?4, ?5)",
+                rusqlite::params![
+                    task_id,
+                    format!("{}#{}", gh.repo, issue.number),
+                    issue.url,
+                    issue.state,
+                    status
+                ],
+            )?;
+            println!(
+                "  {} Task #{} → issue #{}: {}",
+                "+".green(),
+                task_id,
+                issue.number,
+                truncate(&description, 60)
+            );
+        }
+        created += 1;
+    }
+
+    println!();
+    println!(
+        "{} {} issue(s) created, {} task(s) updated, {} issue(s) updated{}",
+        "✓".green(),
+        created,
+        tasks_updated,
+        issues_updated,
+        if dry_run { " (dry run)" } else { "" }
+    );
+    Ok(())
+}
+
+/// Fail with an upgrade hint if the tracking database predates task links
+fn require_table(conn: &Connection) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='task_links')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.13".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Links to issues in `repo`, with the current state of each task
+fn linked_tasks(conn: &Connection, repo: &str) -> Result<Vec<Link>> {
+    let mut stmt = conn.prepare(
+        "SELECT l.link_id, l.task_id, l.remote_id, l.remote_state, l.local_status, t.status, t.description
+         FROM task_links l LEFT JOIN tasks t ON t.task_id = l.task_id
+         WHERE l.provider = 'github' AND l.remote_id LIKE ?1
+         ORDER BY l.link_id",
+    )?;
+    let links = stmt
+        .query_map([format!("{}#%", repo)], |row| {
+            Ok(Link {
+                link_id: row.get(0)?,
+                task_id: row.get(1)?,
+                remote_id: row.get(2)?,
+                remote_state: row.get(3)?,
+                local_status: row.get(4)?,
+                status: row.get(5)?,
+                description: row.get::<_, Option<String>>(6)?.unwrap_or_default(),
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(links)
+}
+
+/// Open tasks tagged `publish` that have no GitHub issue yet
+fn unpublished_tasks(conn: &Connection) -> Result<Vec<Unpublished>> {
+    let mut stmt = conn.prepare(
+        "SELECT t.task_id, t.description, t.notes, t.status FROM tasks t
+         WHERE t.status NOT IN ('completed', 'cancelled')
+           AND EXISTS (SELECT 1 FROM item_tags g WHERE g.task_id = t.task_id AND g.tag = ?1)
+           AND NOT EXISTS (SELECT 1 FROM task_links l WHERE l.task_id = t.task_id AND l.provider = 'github')
+         ORDER BY t.task_id",
+    )?;
+    let tasks = stmt
+        .query_map([PUBLISH_TAG], |row| {
+            Ok(Unpublished {
+                task_id: row.get(0)?,
+                description: row.get(1)?,
+                notes: row.get(2)?,
+                status: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tasks)
+}
+
+fn set_task_status(
+    conn: &Connection,
+    session_id: i64,
+    task_id: i64,
+    status: &str,
+    description: &str,
+    issue: i64,
+) -> Result<()> {
+    if status == "completed" {
+        conn.execute(
+            "UPDATE tasks SET status = ?1, completed_at = datetime('now') WHERE task_id = ?2",
+            rusqlite::params![status, task_id],
+        )?;
+    } else {
+        conn.execute(
+            "UPDATE tasks SET status = ?1, completed_at = NULL WHERE task_id = ?2",
+            rusqlite::params![status, task_id],
+        )?;
+    }
+
+    let summary = format!(
+        "Task {}: {} (GitHub issue #{})",
+        status,
+        truncate(description, 40),
+        issue
+    );
+    conn.execute(
+        "INSERT INTO activity_log (session_id, action_type, action_id, summary) VALUES (?1, 'task_update', ?2, ?3)",
+        rusqlite::params![session_id, task_id, summary],
+    )?;
+
+    if status == "completed" {
+        webhooks::notify(
+            webhooks::TASK_COMPLETED,
+            &format!(
+                "Task #{} completed: {} (GitHub issue #{} closed)",
+                task_id, description, issue
+            ),
+            serde_json::json!({
+                "task_id": task_id,
+                "uuid": record_uuid(conn, "tasks", "task_id", task_id),
+                "description": description,
+            }),
+        );
+    }
+    Ok(())
+}
@@ -0,0 +1,328 @@
+// Trash command - .tracking/ directories set aside instead of deleted
+//
+// Uninstall and `proj rollback --schema --backup` move a project's .tracking/
+// into ~/.proj/trash/<entry>/tracking/ with a trash.json saying where it came
+// from. Entries older than trash_retention_days are purged whenever something
+// new is trashed, or with `proj trash purge`.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, Utc};
+use colored::Colorize;
+use dialoguer::Confirm;
+
+use crate::cli::{TrashCommands, TrashSubcommand};
+use crate::commands::{register, rollback, snapshot};
+use crate::config::trash_retention_days;
+use crate::error::ProjError;
+use crate::paths::{ensure_dir, get_trash_dir};
+
+const METADATA_FILE: &str = "trash.json";
+
+pub fn run(cmd: TrashCommands) -> Result<()> {
+    match cmd.command {
+        TrashSubcommand::List => list(),
+        TrashSubcommand::Restore { id, to } => restore(&id, to.map(PathBuf::from)),
+        TrashSubcommand::Purge { id, all, force } => purge(id.as_deref(), all, force),
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize)]
+struct TrashMetadata {
+    project_name: String,
+    project_path: String,
+    /// What set it aside: "uninstall", "rollback", or "restore"
+    reason: String,
+    trashed_at: DateTime<Utc>,
+}
+
+struct Entry {
+    id: String,
+    path: PathBuf,
+    metadata: TrashMetadata,
+}
+
+/// Move the .tracking/ of the project at `project_root` to the trash, returning the
+/// entry's id
+pub fn move_to_trash(project_root: &Path, project_name: &str, reason: &str) -> Result<String> {
+    let tracking_path = project_root.join(".tracking");
+    let trash_dir = get_trash_dir()?;
+    ensure_dir(&trash_dir)?;
+
+    let name: String = project_name
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let stem = format!("{}-{}", name, Utc::now().format("%Y%m%d-%H%M%S"));
+    let mut id = stem.clone();
+    let mut n = 2;
+    while trash_dir.join(&id).exists() {
+        id = format!("{}-{}", stem, n);
+        n += 1;
+    }
+    let entry_dir = trash_dir.join(&id);
+    std::fs::create_dir(&entry_dir)?;
+
+    let metadata = TrashMetadata {
+        project_name: project_name.to_string(),
+        project_path: project_root.to_string_lossy().to_string(),
+        reason: reason.to_string(),
+        trashed_at: Utc::now(),
+    };
+    std::fs::write(
+        entry_dir.join(METADATA_FILE),
+        serde_json::to_string_pretty(&metadata)?,
+    )?;
+
+    if let Err(e) = move_dir(&tracking_path, &entry_dir.join("tracking")) {
+        let _ = std::fs::remove_dir_all(&entry_dir);
+        return Err(e.context(format!("Failed to move {:?} to the trash", tracking_path)));
+    }
+
+    // Expired entries go whenever something new comes in
+    let _ = purge_expired();
+    Ok(id)
+}
+
+/// Rename, or copy and delete when `dst` is on another filesystem
+fn move_dir(src: &Path, dst: &Path) -> Result<()> {
+    if std::fs::rename(src, dst).is_ok() {
+        return Ok(());
+    }
+    if let Err(e) = rollback::copy_dir_recursive(src, dst) {
+        let _ = std::fs::remove_dir_all(dst);
+        return Err(e);
+    }
+    std::fs::remove_dir_all(src)?;
+    Ok(())
+}
+
+/// Every entry in the trash, newest first
+fn load_entries() -> Result<Vec<Entry>> {
+    let trash_dir = get_trash_dir()?;
+    if !trash_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut entries = Vec::new();
+    for entry in std::fs::read_dir(&trash_dir)? {
+        let path = entry?.path();
+        let Ok(content) = std::fs::read_to_string(path.join(METADATA_FILE)) else {
+            continue;
+        };
+        if let Ok(metadata) = serde_json::from_str::<TrashMetadata>(&content) {
+            entries.push(Entry {
+                id: path.file_name().unwrap().to_string_lossy().to_string(),
+                path,
+                metadata,
+            });
+        }
+    }
+    entries.sort_by_key(|e| std::cmp::Reverse(e.metadata.trashed_at));
+    Ok(entries)
+}
+
+fn find_entry(id: &str) -> Result<Entry> {
+    load_entries()?
+        .into_iter()
+        .find(|e| e.id == id)
+        .ok_or_else(|| {
+            ProjError::InvalidInput(format!("No trash entry '{}'. See 'proj trash list'.", id))
+                .into()
+        })
+}
+
+/// When an entry stops being kept, or None if entries are kept until purged
+fn expires_at(entry: &Entry, retention_days: u32) -> Option<DateTime<Utc>> {
+    (retention_days > 0).then(|| entry.metadata.trashed_at + Duration::days(retention_days as i64))
+}
+
+/// Delete the entries past trash_retention_days, returning how many went
+pub fn purge_expired() -> Result<usize> {
+    let retention_days = trash_retention_days();
+    let now = Utc::now();
+    let mut purged = 0;
+    for entry in load_entries()? {
+        if expires_at(&entry, retention_days).is_some_and(|at| at <= now) {
+            std::fs::remove_dir_all(&entry.path)
+                .with_context(|| format!("Failed to delete {:?}", entry.path))?;
+            purged += 1;
+        }
+    }
+    Ok(purged)
+}
+
+fn list() -> Result<()> {
+    let entries = load_entries()?;
+    if entries.is_empty() {
+        println!("The trash is empty.");
+        return Ok(());
+    }
+
+    let retention_days = trash_retention_days();
+    println!("{}", "Trash".bold());
+    println!(
+        "{}",
+        if retention_days > 0 {
+            format!(
+                "Entries are kept {} days ({})",
+                retention_days,
+                get_trash_dir()?.display()
+            )
+        } else {
+            format!(
+                "Entries are kept until purged ({})",
+                get_trash_dir()?.display()
+            )
+        }
+        .dimmed()
+    );
+    println!();
+
+    let now = Utc::now();
+    for entry in &entries {
+        let expiry = match expires_at(entry, retention_days) {
+            Some(at) if at <= now => "expired".to_string(),
+            Some(at) => format!("expires in {}d", (at - now).num_days().max(1)),
+            None => String::new(),
+        };
+        println!(
+            "  {} {} ({}, {})  {}",
+            "•".cyan(),
+            entry.id,
+            entry.metadata.reason,
+            entry.metadata.trashed_at.format("%Y-%m-%d %H:%M"),
+            expiry.dimmed()
+        );
+        println!(
+            "    {} from {} ({})",
+            entry.metadata.project_name,
+            entry.metadata.project_path,
+            snapshot::format_size(dir_size(&entry.path))
+        );
+    }
+
+    println!();
+    println!("To restore: {}", "proj trash restore <id>".cyan());
+    Ok(())
+}
+
+fn restore(id: &str, to: Option<PathBuf>) -> Result<()> {
+    let entry = find_entry(id)?;
+    let project_root = to.unwrap_or_else(|| PathBuf::from(&entry.metadata.project_path));
+    if !project_root.is_dir() {
+        return Err(ProjError::InvalidInput(format!(
+            "{} doesn't exist. Pass --to <path> to restore somewhere else.",
+            project_root.display()
+        ))
+        .into());
+    }
+
+    // Bring it into the project first: trashing the current .tracking purges expired
+    // entries, which may include this one
+    let tracking_path = project_root.join(".tracking");
+    let incoming = project_root.join(".tracking.restoring");
+    move_dir(&entry.path.join("tracking"), &incoming)
+        .with_context(|| format!("Failed to restore {} to {:?}", id, project_root))?;
+    let _ = std::fs::remove_dir_all(&entry.path);
+
+    if tracking_path.exists() {
+        let current = move_to_trash(&project_root, &entry.metadata.project_name, "restore")?;
+        println!(
+            "{} Moved the current .tracking/ to the trash as {}",
+            "✓".green(),
+            current
+        );
+    }
+    std::fs::rename(&incoming, &tracking_path)
+        .with_context(|| format!("Failed to move {:?} to {:?}", incoming, tracking_path))?;
+    println!(
+        "{} Restored .tracking/ to {}",
+        "✓".green(),
+        project_root.display()
+    );
+
+    if entry.metadata.reason == "uninstall" {
+        if let Err(e) = register::register_at(&project_root) {
+            println!("{} Could not register project: {}", "⚠".yellow(), e);
+        }
+        println!(
+            "{}",
+            "Run 'proj githook install' there to put its git hooks back.".dimmed()
+        );
+    }
+    Ok(())
+}
+
+fn purge(id: Option<&str>, all: bool, force: bool) -> Result<()> {
+    let entries = match id {
+        Some(id) => vec![find_entry(id)?],
+        None if all => load_entries()?,
+        None => {
+            let purged = purge_expired()?;
+            if purged == 0 {
+                println!("Nothing in the trash has expired.");
+            } else {
+                println!("{} Purged {} expired entries", "✓".green(), purged);
+            }
+            return Ok(());
+        }
+    };
+    if entries.is_empty() {
+        println!("The trash is empty.");
+        return Ok(());
+    }
+
+    if !force {
+        for entry in &entries {
+            println!(
+                "  {} {} ({})",
+                "•".cyan(),
+                entry.id,
+                entry.metadata.project_path
+            );
+        }
+        println!();
+        if !Confirm::new()
+            .with_prompt(format!(
+                "Permanently delete {} trash entr{}?",
+                entries.len(),
+                if entries.len() == 1 { "y" } else { "ies" }
+            ))
+            .default(false)
+            .interact()?
+        {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    for entry in &entries {
+        std::fs::remove_dir_all(&entry.path)
+            .with_context(|| format!("Failed to delete {:?}", entry.path))?;
+    }
+    println!("{} Purged {} entries", "✓".green(), entries.len());
+    Ok(())
+}
+
+fn dir_size(path: &Path) -> u64 {
+    std::fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| match e.metadata() {
+                    Ok(meta) if meta.is_dir() => dir_size(&e.path()),
+                    Ok(meta) => meta.len(),
+                    Err(_) => 0,
+                })
+                .sum()
+        })
+        .unwrap_or(0)
+}
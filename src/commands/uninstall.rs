@@ -1,26 +1,38 @@
 // Uninstall command - remove proj from projects and system
+//
+// Before a project's .tracking/ is removed, its data is saved as a snapshot bundle
+// in ~/.proj/exports/ (which uninstall never touches), and the command to restore it
+// is printed. A project whose bundle can't be written is left alone; --no-export
+// skips the bundle. The .tracking/ itself goes to the trash (`proj trash`), not
+// straight to deletion.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
 use colored::Colorize;
 use dialoguer::Confirm;
 
-use crate::commands::shell;
-use crate::config::Registry;
-use crate::paths::get_registry_path;
-
-pub fn run(shell_only: bool, project_only: bool, all: bool, force: bool) -> Result<()> {
+use crate::commands::{shell, snapshot, trash};
+use crate::config::{trash_retention_days, Registry};
+use crate::paths::{ensure_dir, get_exports_dir, get_registry_path, same_path};
+
+pub fn run(
+    shell_only: bool,
+    project_only: bool,
+    all: bool,
+    force: bool,
+    no_export: bool,
+) -> Result<()> {
     if shell_only {
         return uninstall_shell();
     }
 
     if project_only {
-        return uninstall_current_project(force);
+        return uninstall_current_project(force, no_export);
     }
 
     if all {
-        return uninstall_all(force);
+        return uninstall_all(force, no_export);
     }
 
     // No flags - show help
@@ -41,6 +53,10 @@ pub fn run(shell_only: bool, project_only: bool, all: bool, force: bool) -> Resu
         "  {} Remove shell hook + .tracking/ from ALL registered projects",
         "--all".cyan()
     );
+    println!(
+        "  {} Don't save an export bundle of each project first",
+        "--no-export".cyan()
+    );
     println!();
     println!("Examples:");
     println!("  proj uninstall --shell    # Remove shell integration");
@@ -56,8 +72,8 @@ fn uninstall_shell() -> Result<()> {
 }
 
 /// Remove .tracking from current project
-fn uninstall_current_project(force: bool) -> Result<()> {
-    let project_root = std::env::current_dir()?;
+fn uninstall_current_project(force: bool, no_export: bool) -> Result<()> {
+    let project_root = crate::paths::get_start_dir()?;
     let tracking_path = project_root.join(".tracking");
 
     if !tracking_path.exists() {
@@ -74,15 +90,23 @@ fn uninstall_current_project(force: bool) -> Result<()> {
         println!("{}", "Uninstall proj from current project".bold());
         println!();
         println!(
-            "This will {} .tracking/ directory from {}",
-            "permanently delete".red(),
-            project_name
+            "This will {} the .tracking/ directory of {} to the trash ({})",
+            "move".red(),
+            project_name,
+            trash_kept()
         );
         println!();
-        println!("Data that will be lost:");
-        println!("  • All session history");
-        println!("  • All logged decisions, notes, tasks");
-        println!("  • Project configuration");
+        if no_export {
+            println!("After that, these are gone:");
+            println!("  • All session history");
+            println!("  • All logged decisions, notes, tasks");
+            println!("  • Project configuration");
+        } else {
+            println!(
+                "Its sessions, decisions, notes, tasks, docs, and configuration are first saved to {}.",
+                exports_dir_display()
+            );
+        }
         println!();
 
         if !Confirm::new()
@@ -95,20 +119,48 @@ fn uninstall_current_project(force: bool) -> Result<()> {
         }
     }
 
-    // Remove the tracking directory
-    std::fs::remove_dir_all(&tracking_path)
-        .with_context(|| format!("Failed to remove {:?}", tracking_path))?;
+    let export = if no_export {
+        None
+    } else {
+        Some(export_before_delete(&project_root, project_name).with_context(|| {
+            "Nothing was removed. Fix the problem, or pass --no-export to remove without an export."
+        })?)
+    };
+
+    // Remove git hooks (if any) while the repo is still known to be a proj project
+    if let Ok(hooks) = crate::commands::githook::remove_hooks(&project_root) {
+        if !hooks.is_empty() {
+            println!("{} Removed git hooks ({})", "✓".green(), hooks.join(", "));
+        }
+    }
 
-    println!("{} Removed .tracking/ from {}", "✓".green(), project_name);
+    let trashed = trash::move_to_trash(&project_root, project_name, "uninstall")?;
+    println!(
+        "{} Moved .tracking/ from {} to the trash as {}",
+        "✓".green(),
+        project_name,
+        trashed
+    );
 
     // Also remove from registry
     remove_from_registry(&project_root)?;
 
+    println!();
+    println!("To bring the data back:");
+    println!(
+        "  {}  ({})",
+        format!("proj trash restore {}", trashed).cyan(),
+        trash_kept()
+    );
+    if let Some(export) = export {
+        println!("  {}", restore_command(&project_root, &export).cyan());
+    }
+
     Ok(())
 }
 
 /// Remove everything - shell hook + all registered projects
-fn uninstall_all(force: bool) -> Result<()> {
+fn uninstall_all(force: bool, no_export: bool) -> Result<()> {
     // Load registry to see what will be removed
     let registry = load_registry()?;
     let project_count = registry.registered_projects.len();
@@ -128,18 +180,25 @@ fn uninstall_all(force: bool) -> Result<()> {
         }
         if project_count > 0 {
             println!(
-                "  • {} .tracking/ from {} registered project(s):",
-                "Permanently delete".red(),
-                project_count
+                "  • {} .tracking/ from {} registered project(s) to the trash ({}):",
+                "Move".red(),
+                project_count,
+                trash_kept()
             );
             for proj in &registry.registered_projects {
                 println!("    - {} ({})", proj.name, proj.path);
             }
+            if !no_export {
+                println!(
+                    "    Each project's data is first saved to {}",
+                    exports_dir_display()
+                );
+            }
         }
         println!();
 
         if !Confirm::new()
-            .with_prompt("Are you absolutely sure? This cannot be undone.")
+            .with_prompt("Are you absolutely sure?")
             .default(false)
             .interact()?
         {
@@ -173,27 +232,48 @@ fn uninstall_all(force: bool) -> Result<()> {
     // Remove tracking from all projects
     let mut removed_count = 0;
     let mut failed_count = 0;
+    let mut exports = Vec::new();
+    let mut trashed = Vec::new();
+    // Projects that failed keep their registration so a later run can retry them
+    let mut unregister = Vec::new();
 
     for proj in &registry.registered_projects {
         let tracking_path = Path::new(&proj.path).join(".tracking");
         if tracking_path.exists() {
-            match std::fs::remove_dir_all(&tracking_path) {
-                Ok(_) => {
-                    println!("{} Removed tracking from: {}", "✓".green(), proj.name);
+            if !no_export {
+                match export_before_delete(Path::new(&proj.path), &proj.name) {
+                    Ok(export) => exports.push((PathBuf::from(&proj.path), export)),
+                    Err(e) => {
+                        println!(
+                            "{} Kept {}: couldn't export it first: {:#}",
+                            "✗".red(),
+                            proj.name,
+                            e
+                        );
+                        failed_count += 1;
+                        continue;
+                    }
+                }
+            }
+            match trash::move_to_trash(Path::new(&proj.path), &proj.name, "uninstall") {
+                Ok(id) => {
+                    println!("{} Moved tracking to the trash: {}", "✓".green(), proj.name);
+                    trashed.push(id);
+                    unregister.push(PathBuf::from(&proj.path));
                     removed_count += 1;
                 }
                 Err(e) => {
-                    println!("{} Failed to remove {}: {}", "✗".red(), proj.name, e);
+                    println!("{} Failed to remove {}: {:#}", "✗".red(), proj.name, e);
                     failed_count += 1;
                 }
             }
         } else {
             println!("{} Already removed: {}", "○".dimmed(), proj.name);
+            unregister.push(PathBuf::from(&proj.path));
         }
     }
 
-    // Clear the registry
-    clear_registry()?;
+    remove_all_from_registry(&unregister)?;
 
     println!();
     println!(
@@ -203,14 +283,80 @@ fn uninstall_all(force: bool) -> Result<()> {
         failed_count
     );
 
+    if !trashed.is_empty() {
+        println!();
+        println!("To bring a project's data back ({}):", trash_kept());
+        for id in &trashed {
+            println!("  {}", format!("proj trash restore {}", id).cyan());
+        }
+    }
+    if !exports.is_empty() {
+        println!();
+        println!("Or import its export:");
+        for (root, export) in &exports {
+            println!("  {}", restore_command(root, export).cyan());
+        }
+    }
+
     if failed_count > 0 {
         println!();
-        println!("Some projects could not be removed. You may need to delete their .tracking/ directories manually.");
+        println!("Some projects could not be removed and are still registered. You may need to delete their .tracking/ directories manually, or run again with --no-export.");
     }
 
     Ok(())
 }
 
+/// Save a snapshot bundle of a project to ~/.proj/exports/, returning its path
+fn export_before_delete(project_root: &Path, name: &str) -> Result<PathBuf> {
+    let dir = get_exports_dir()?;
+    ensure_dir(&dir)?;
+    // Projects with the same name can be exported in the same second
+    let file_name = snapshot::bundle_file_name(name, "uninstall");
+    let mut path = dir.join(&file_name);
+    let mut n = 2;
+    while path.exists() {
+        let stem = file_name.trim_end_matches(".tar.zst");
+        path = dir.join(format!("{}-{}.tar.zst", stem, n));
+        n += 1;
+    }
+
+    let size = snapshot::bundle_project(project_root, &path).map_err(|e| {
+        let _ = std::fs::remove_file(&path);
+        e.context(format!("Couldn't export {}", name))
+    })?;
+    println!(
+        "{} Exported {} to {} ({})",
+        "✓".green(),
+        name,
+        path.display(),
+        snapshot::format_size(size)
+    );
+    Ok(path)
+}
+
+/// How to restore an exported bundle into the project it came from
+fn restore_command(project_root: &Path, export: &Path) -> String {
+    format!(
+        "proj --project '{}' snapshot --restore '{}'",
+        project_root.display(),
+        export.display()
+    )
+}
+
+/// How long trashed data stays, for messages
+fn trash_kept() -> String {
+    match trash_retention_days() {
+        0 => "kept until 'proj trash purge'".to_string(),
+        days => format!("kept {} days", days),
+    }
+}
+
+fn exports_dir_display() -> String {
+    get_exports_dir()
+        .map(|dir| dir.display().to_string())
+        .unwrap_or_else(|_| "~/.proj/exports".to_string())
+}
+
 /// Load the global registry
 fn load_registry() -> Result<Registry> {
     let registry_path = get_registry_path()?;
@@ -228,6 +374,11 @@ fn load_registry() -> Result<Registry> {
 
 /// Remove a project from the registry
 fn remove_from_registry(project_path: &Path) -> Result<()> {
+    remove_all_from_registry(&[project_path.to_path_buf()])
+}
+
+/// Remove several projects from the registry, leaving the rest registered
+fn remove_all_from_registry(project_paths: &[PathBuf]) -> Result<()> {
     let registry_path = get_registry_path()?;
 
     if !registry_path.exists() {
@@ -237,24 +388,14 @@ fn remove_from_registry(project_path: &Path) -> Result<()> {
     let content = std::fs::read_to_string(&registry_path)?;
     let mut registry: Registry = serde_json::from_str(&content)?;
 
-    let path_str = project_path.to_string_lossy().to_string();
-    registry.registered_projects.retain(|p| p.path != path_str);
+    registry.registered_projects.retain(|p| {
+        !project_paths
+            .iter()
+            .any(|q| same_path(Path::new(&p.path), q))
+    });
 
     let content = serde_json::to_string_pretty(&registry)?;
     std::fs::write(&registry_path, content)?;
 
     Ok(())
 }
-
-/// Clear the registry completely
-fn clear_registry() -> Result<()> {
-    let registry_path = get_registry_path()?;
-
-    if registry_path.exists() {
-        let empty_registry = Registry::default();
-        let content = serde_json::to_string_pretty(&empty_registry)?;
-        std::fs::write(&registry_path, content)?;
-    }
-
-    Ok(())
-}
@@ -1,4 +1,9 @@
 // Update check - check for new versions and notify users
+//
+// Which releases count comes from the channel in ~/.proj/update.json: "stable"
+// follows GitHub's latest release, "beta" takes pre-releases too, and "none" turns
+// the automatic check off (`proj update` still checks stable releases on request).
+// Downloads are only staged once their minisign signature checks out.
 
 use std::fs;
 use std::io::Read;
@@ -9,10 +14,13 @@ use anyhow::{anyhow, Result};
 use colored::Colorize;
 use serde::{Deserialize, Serialize};
 
+use crate::auto_update::{self, STAGED_ARCHIVE, STAGED_PATCH};
+use crate::config::UpdateConfig;
+use crate::error::ProjError;
+use crate::http;
 use crate::paths::{ensure_dir, get_global_dir, get_pending_update_dir};
 
-const GITHUB_API_URL: &str =
-    "https://api.github.com/repos/victorysightsound/aiproject/releases/latest";
+const GITHUB_API_URL: &str = "https://api.github.com/repos/victorysightsound/aiproject/releases";
 const GITHUB_REPO: &str = "victorysightsound/aiproject";
 const CHECK_INTERVAL_HOURS: u64 = 24;
 
@@ -21,12 +29,17 @@ struct VersionCache {
     latest_version: String,
     checked_at: u64, // Unix timestamp
     download_url: Option<String>,
+    /// Channel the check was made on; caches from before channels were stable ones
+    #[serde(default)]
+    channel: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct GitHubRelease {
     tag_name: String,
     html_url: String,
+    #[serde(default)]
+    draft: bool,
 }
 
 /// Get the path to the version cache file
@@ -51,12 +64,20 @@ fn should_check(cache_path: &PathBuf) -> bool {
     true
 }
 
-/// Read cached version info
-fn read_cache(cache_path: &PathBuf) -> Option<VersionCache> {
-    if let Ok(content) = fs::read_to_string(cache_path) {
-        serde_json::from_str(&content).ok()
-    } else {
-        None
+/// Read cached version info, if it was checked on `channel`
+fn read_cache(cache_path: &PathBuf, channel: &str) -> Option<VersionCache> {
+    let content = fs::read_to_string(cache_path).ok()?;
+    let cache: VersionCache = serde_json::from_str(&content).ok()?;
+    (cache.channel.as_deref().unwrap_or("stable") == channel).then_some(cache)
+}
+
+/// Version info to cache after a check on `channel`
+fn new_cache(channel: &str, version: &str, url: &str) -> VersionCache {
+    VersionCache {
+        latest_version: version.to_string(),
+        checked_at: auto_update::now(),
+        download_url: Some(url.to_string()),
+        channel: Some(channel.to_string()),
     }
 }
 
@@ -67,31 +88,49 @@ fn write_cache(cache_path: &PathBuf, cache: &VersionCache) -> Result<()> {
     Ok(())
 }
 
-/// Fetch latest version from GitHub API
-fn fetch_latest_version() -> Option<(String, String)> {
+/// Fetch the newest version on a channel from the GitHub API
+fn fetch_latest_version(channel: &str) -> Option<(String, String)> {
     // Use a short timeout to avoid slowing down CLI
-    let client = match ureq::AgentBuilder::new()
-        .timeout(Duration::from_secs(3))
-        .build()
-        .get(GITHUB_API_URL)
-        .set("User-Agent", "proj-cli")
-        .call()
-    {
-        Ok(response) => response,
-        Err(_) => return None,
+    let agent = http::agent(GITHUB_API_URL, Duration::from_secs(3));
+
+    // "latest" never points at a pre-release, so beta looks through the recent ones
+    let release: GitHubRelease = if channel == "beta" {
+        let releases: Vec<GitHubRelease> = agent
+            .get(&format!("{}?per_page=20", GITHUB_API_URL))
+            .set("User-Agent", "proj-cli")
+            .call()
+            .ok()?
+            .into_json()
+            .ok()?;
+        releases
+            .into_iter()
+            .filter(|r| !r.draft)
+            .reduce(|newest, r| {
+                if is_newer(release_version(&newest), release_version(&r)) {
+                    r
+                } else {
+                    newest
+                }
+            })?
+    } else {
+        agent
+            .get(&format!("{}/latest", GITHUB_API_URL))
+            .set("User-Agent", "proj-cli")
+            .call()
+            .ok()?
+            .into_json()
+            .ok()?
     };
 
-    let release: GitHubRelease = match client.into_json() {
-        Ok(r) => r,
-        Err(_) => return None,
-    };
+    Some((release_version(&release).to_string(), release.html_url))
+}
 
-    // Remove 'v' prefix if present
-    let version = release.tag_name.trim_start_matches('v').to_string();
-    Some((version, release.html_url))
+/// Version of a release, without the tag's 'v' prefix
+fn release_version(release: &GitHubRelease) -> &str {
+    release.tag_name.trim_start_matches('v')
 }
 
-/// Parse version string into comparable parts
+/// Parse version string into comparable parts (without any pre-release suffix)
 fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
     let parts: Vec<&str> = version.split('.').collect();
     if parts.len() >= 3 {
@@ -108,16 +147,50 @@ fn parse_version(version: &str) -> Option<(u32, u32, u32)> {
     }
 }
 
-/// Compare versions, returns true if latest > current
+/// Compare versions, returns true if latest > current. A pre-release (1.9.0-beta.2)
+/// comes after the one before it and before the release itself.
 fn is_newer(current: &str, latest: &str) -> bool {
+    let (current, current_pre) = split_prerelease(current);
+    let (latest, latest_pre) = split_prerelease(latest);
     match (parse_version(current), parse_version(latest)) {
-        (Some((c_maj, c_min, c_pat)), Some((l_maj, l_min, l_pat))) => {
-            (l_maj, l_min, l_pat) > (c_maj, c_min, c_pat)
-        }
+        (Some(c), Some(l)) if l != c => l > c,
+        (Some(_), Some(_)) => match (current_pre, latest_pre) {
+            (Some(_), None) => true,
+            (Some(c), Some(l)) => compare_prerelease(l, c) == std::cmp::Ordering::Greater,
+            _ => false,
+        },
         _ => false,
     }
 }
 
+/// "1.9.0-beta.2" -> ("1.9.0", Some("beta.2"))
+fn split_prerelease(version: &str) -> (&str, Option<&str>) {
+    match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    }
+}
+
+/// Order pre-release suffixes field by field, numbers as numbers (beta.10 > beta.9)
+fn compare_prerelease(a: &str, b: &str) -> std::cmp::Ordering {
+    let mut a_parts = a.split('.');
+    let mut b_parts = b.split('.');
+    loop {
+        let ordering = match (a_parts.next(), b_parts.next()) {
+            (None, None) => return std::cmp::Ordering::Equal,
+            (None, Some(_)) => return std::cmp::Ordering::Less,
+            (Some(_), None) => return std::cmp::Ordering::Greater,
+            (Some(x), Some(y)) => match (x.parse::<u64>(), y.parse::<u64>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                _ => x.cmp(y),
+            },
+        };
+        if ordering != std::cmp::Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
 /// Get the platform target string for binary downloads
 fn get_platform_target() -> Option<&'static str> {
     #[cfg(all(target_os = "macos", target_arch = "aarch64"))]
@@ -140,36 +213,48 @@ fn get_platform_target() -> Option<&'static str> {
     return None;
 }
 
-/// Download update binary to staging directory
-fn download_update(version: &str) -> Result<()> {
-    let target = get_platform_target().ok_or_else(|| anyhow!("Unsupported platform"))?;
+/// How an update was downloaded, and how many bytes that took
+enum Downloaded {
+    Patch(usize),
+    Full(usize),
+}
 
-    #[cfg(target_os = "windows")]
-    let ext = "zip";
-    #[cfg(not(target_os = "windows"))]
-    let ext = "tar.gz";
+/// Download an update and its signature, and stage them for the next start once
+/// the signature checks out. A patch from the running version is tried first;
+/// without one (or if it doesn't apply), the full release archive is downloaded.
+fn download_update(version: &str, public_key: Option<&str>) -> Result<Downloaded> {
+    let target = get_platform_target().ok_or_else(|| anyhow!("Unsupported platform"))?;
+    if cfg!(target_os = "windows") {
+        return Err(anyhow!(
+            "Auto-update on Windows not yet supported, please update manually"
+        ));
+    }
 
-    let url = format!(
-        "https://github.com/{}/releases/download/v{}/proj-{}.{}",
-        GITHUB_REPO, version, target, ext
+    let base_url = format!(
+        "https://github.com/{}/releases/download/v{}",
+        GITHUB_REPO, version
     );
+    let patch_url = format!(
+        "{}/proj-{}-{}.patch.zst",
+        base_url,
+        target,
+        env!("CARGO_PKG_VERSION")
+    );
+    let (name, data, signature, downloaded) = match download_patch(&patch_url, public_key) {
+        Ok((patch, signature)) => {
+            let size = patch.len();
+            (STAGED_PATCH, patch, signature, Downloaded::Patch(size))
+        }
+        Err(e) => {
+            tracing::debug!("no usable patch, downloading the full release: {:#}", e);
+            let url = format!("{}/proj-{}.tar.gz", base_url, target);
+            let (archive, signature) = download_signed(&url, public_key)?;
+            let size = archive.len();
+            (STAGED_ARCHIVE, archive, signature, Downloaded::Full(size))
+        }
+    };
 
-    // Download with short timeout
-    let response = ureq::AgentBuilder::new()
-        .timeout(Duration::from_secs(60))
-        .build()
-        .get(&url)
-        .set("User-Agent", "proj-cli")
-        .call()
-        .map_err(|e| anyhow!("Download failed: {}", e))?;
-
-    let mut bytes = Vec::new();
-    response
-        .into_reader()
-        .read_to_end(&mut bytes)
-        .map_err(|e| anyhow!("Failed to read response: {}", e))?;
-
-    // Extract to staging
+    // Stage them
     let staging = get_pending_update_dir()?;
 
     // Clean up any existing staging directory
@@ -177,38 +262,53 @@ fn download_update(version: &str) -> Result<()> {
         let _ = fs::remove_dir_all(&staging);
     }
     ensure_dir(&staging)?;
+    fs::write(staging.join(name), &data)?;
+    fs::write(staging.join(format!("{}.minisig", name)), &signature)?;
 
-    #[cfg(not(target_os = "windows"))]
-    {
-        use flate2::read::GzDecoder;
-        use tar::Archive;
-
-        let decoder = GzDecoder::new(&bytes[..]);
-        let mut archive = Archive::new(decoder);
-        archive
-            .unpack(&staging)
-            .map_err(|e| anyhow!("Failed to extract archive: {}", e))?;
-    }
+    // Write version file last: it marks the staged update complete
+    fs::write(staging.join("version"), version)?;
 
-    #[cfg(target_os = "windows")]
-    {
-        // Windows zip handling would go here
-        // For now, return an error indicating manual update needed
-        return Err(anyhow!(
-            "Auto-update on Windows not yet supported, please update manually"
-        ));
-    }
+    Ok(downloaded)
+}
 
-    // Write version file
-    fs::write(staging.join("version"), version)?;
+/// Download a patch for the running binary, making sure it applies
+fn download_patch(url: &str, public_key: Option<&str>) -> Result<(Vec<u8>, String)> {
+    let (patch, signature) = download_signed(url, public_key)?;
+    let current = fs::read(std::env::current_exe()?)?;
+    auto_update::apply_patch(&current, &patch)?;
+    Ok((patch, signature))
+}
 
-    Ok(())
+/// Download a release asset and its signature (<url>.minisig), and check them
+fn download_signed(url: &str, public_key: Option<&str>) -> Result<(Vec<u8>, String)> {
+    let data = download(url)?;
+    let signature = download(&format!("{}.minisig", url))
+        .map_err(|e| anyhow!("No signature for this release ({})", e))?;
+    let signature = String::from_utf8(signature).map_err(|_| anyhow!("Invalid signature file"))?;
+    auto_update::verify_signature(&data, &signature, public_key)?;
+    Ok((data, signature))
+}
+
+/// Download a release asset
+fn download(url: &str) -> Result<Vec<u8>> {
+    let response = http::agent(url, Duration::from_secs(60))
+        .get(url)
+        .set("User-Agent", "proj-cli")
+        .call()
+        .map_err(|e| anyhow!("Download failed: {}", e))?;
+
+    let mut bytes = Vec::new();
+    response
+        .into_reader()
+        .read_to_end(&mut bytes)
+        .map_err(|e| anyhow!("Failed to read response: {}", e))?;
+    Ok(bytes)
 }
 
 /// Spawn background thread to download update
-pub fn download_update_background(version: String) {
+pub fn download_update_background(version: String, public_key: Option<String>) {
     std::thread::spawn(move || {
-        if let Err(_e) = download_update(&version) {
+        if let Err(_e) = download_update(&version, public_key.as_deref()) {
             // Silent fail - will retry on next check
             // Could log to file for debugging if needed
         }
@@ -225,58 +325,49 @@ fn is_update_staged(version: &str) -> bool {
     false
 }
 
-/// Check for updates and print notification if available
+/// Check for updates and print notification if available (not on the "none"
+/// channel, while updates are deferred, or offline)
 /// Returns true if an update is available
 pub fn check_and_notify() -> bool {
     let cache_path = match get_cache_path() {
         Ok(p) => p,
         Err(_) => return false,
     };
+    let config = UpdateConfig::load().unwrap_or_default();
+    if config.channel == "none" || config.deferred(auto_update::now()) || crate::config::offline() {
+        return false;
+    }
 
     let current_version = env!("CARGO_PKG_VERSION");
-    let latest_version: String;
-    let download_url: Option<String>;
-
-    if should_check(&cache_path) {
+    let cached = if should_check(&cache_path) {
+        None
+    } else {
+        read_cache(&cache_path, &config.channel)
+    };
+    let (latest_version, download_url) = match cached {
+        Some(cache) => (cache.latest_version, cache.download_url),
         // Fetch from GitHub
-        if let Some((version, url)) = fetch_latest_version() {
-            latest_version = version.clone();
-            download_url = Some(url);
-
-            // Cache the result
-            let cache = VersionCache {
-                latest_version: version,
-                checked_at: SystemTime::now()
-                    .duration_since(SystemTime::UNIX_EPOCH)
-                    .map(|d| d.as_secs())
-                    .unwrap_or(0),
-                download_url: download_url.clone(),
-            };
-            let _ = write_cache(&cache_path, &cache);
-        } else {
-            // Network error, use cache if available
-            if let Some(cache) = read_cache(&cache_path) {
-                latest_version = cache.latest_version;
-                download_url = cache.download_url;
-            } else {
-                return false;
+        None => match fetch_latest_version(&config.channel) {
+            Some((version, url)) => {
+                let _ = write_cache(&cache_path, &new_cache(&config.channel, &version, &url));
+                (version, Some(url))
             }
-        }
-    } else {
-        // Use cached version
-        if let Some(cache) = read_cache(&cache_path) {
-            latest_version = cache.latest_version;
-            download_url = cache.download_url;
-        } else {
-            return false;
-        }
-    }
+            // Network error, use cache if available
+            None => match read_cache(&cache_path, &config.channel) {
+                Some(cache) => (cache.latest_version, cache.download_url),
+                None => return false,
+            },
+        },
+    };
 
     // Check if update is available
     if is_newer(current_version, &latest_version) {
         // Start background download if not already staged and platform supported
-        if !is_update_staged(&latest_version) && get_platform_target().is_some() {
-            download_update_background(latest_version.clone());
+        if auto_update::automatic_updates(&config)
+            && !is_update_staged(&latest_version)
+            && get_platform_target().is_some()
+        {
+            download_update_background(latest_version.clone(), config.public_key.clone());
         }
 
         println!();
@@ -326,13 +417,15 @@ pub fn check_schema_upgrade(project_schema: &str, session_full_context_shown: bo
 }
 
 /// Apply a downloaded update, with proper permission error handling
-fn apply_update(version: &str) -> Result<()> {
+fn apply_update(version: &str, public_key: Option<&str>) -> Result<()> {
     let staging = get_pending_update_dir()?;
-    let pending_binary = staging.join("proj");
-
-    if !pending_binary.exists() {
-        return Err(anyhow!("No staged update found"));
-    }
+    let pending_binary = match auto_update::staged_binary(&staging, public_key) {
+        Ok(binary) => binary,
+        Err(e) => {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+    };
 
     let current_exe = std::env::current_exe()?;
     let current_version = env!("CARGO_PKG_VERSION");
@@ -346,7 +439,7 @@ fn apply_update(version: &str) -> Result<()> {
     // Try to replace the binary
     let apply_result = (|| -> Result<()> {
         // First try atomic rename
-        if let Err(_) = fs::rename(&pending_binary, &current_exe) {
+        if fs::rename(&pending_binary, &current_exe).is_err() {
             // Rename failed, try copy
             fs::copy(&pending_binary, &current_exe)
                 .map_err(|e| anyhow!("Failed to copy binary: {}", e))?;
@@ -440,13 +533,40 @@ fn apply_update(version: &str) -> Result<()> {
     }
 }
 
-/// Force a version check (for manual `proj update` command)
-pub fn run(apply: bool, check_only: bool) -> Result<()> {
+/// Force a version check (for manual `proj update` command), or change the channel
+/// or deferral with --channel and --defer
+pub fn run(
+    apply: bool,
+    check_only: bool,
+    channel: Option<String>,
+    defer: Option<String>,
+) -> Result<()> {
+    let mut config = UpdateConfig::load()?;
+    if channel.is_some() || defer.is_some() {
+        return change_settings(&mut config, channel, defer.as_deref());
+    }
+
     let current_version = env!("CARGO_PKG_VERSION");
     println!("Current version: {}", current_version.cyan());
+    if crate::config::offline() {
+        println!(
+            "{} Offline mode is on, so proj doesn't check for updates.",
+            "ℹ".dimmed()
+        );
+        return Ok(());
+    }
+    // "none" only stops the automatic checks; asking still looks at stable releases
+    let channel = if config.channel == "none" {
+        "stable"
+    } else {
+        config.channel.as_str()
+    };
+    if channel != "stable" {
+        println!("Channel: {}", channel.cyan());
+    }
     println!("Checking for updates...");
 
-    match fetch_latest_version() {
+    match fetch_latest_version(channel) {
         Some((latest, url)) => {
             if is_newer(current_version, &latest) {
                 println!();
@@ -456,17 +576,31 @@ pub fn run(apply: bool, check_only: bool) -> Result<()> {
                 if apply {
                     // Download and apply immediately
                     println!("Downloading update...");
-                    download_update(&latest)?;
-                    println!("{} Download complete.", "✓".green());
+                    match download_update(&latest, config.public_key.as_deref())? {
+                        Downloaded::Patch(size) => println!(
+                            "{} Downloaded a {} patch from {}, signature verified.",
+                            "✓".green(),
+                            format_size(size),
+                            current_version
+                        ),
+                        Downloaded::Full(size) => println!(
+                            "{} Downloaded {}, signature verified.",
+                            "✓".green(),
+                            format_size(size)
+                        ),
+                    }
                     println!();
-                    apply_update(&latest)?;
+                    apply_update(&latest, config.public_key.as_deref())?;
                 } else {
                     // Show install options
-                    if !check_only && get_platform_target().is_some() {
+                    if !check_only
+                        && auto_update::automatic_updates(&config)
+                        && get_platform_target().is_some()
+                    {
                         // Start background download
                         if !is_update_staged(&latest) {
                             println!("  {} Downloading in background...", "↓".dimmed());
-                            download_update_background(latest.clone());
+                            download_update_background(latest.clone(), config.public_key.clone());
                         }
                         println!();
                     }
@@ -488,15 +622,7 @@ pub fn run(apply: bool, check_only: bool) -> Result<()> {
 
             // Update cache
             if let Ok(cache_path) = get_cache_path() {
-                let cache = VersionCache {
-                    latest_version: latest,
-                    checked_at: SystemTime::now()
-                        .duration_since(SystemTime::UNIX_EPOCH)
-                        .map(|d| d.as_secs())
-                        .unwrap_or(0),
-                    download_url: Some(url),
-                };
-                let _ = write_cache(&cache_path, &cache);
+                let _ = write_cache(&cache_path, &new_cache(channel, &latest, &url));
             }
         }
         None => {
@@ -507,5 +633,89 @@ pub fn run(apply: bool, check_only: bool) -> Result<()> {
         }
     }
 
+    if config.channel == "none" {
+        println!(
+            "\n{} Automatic updates are off (channel none). '{}' turns them back on.",
+            "ℹ".dimmed(),
+            "proj update --channel stable".cyan()
+        );
+    } else if let Some(until) = config
+        .deferred_until
+        .filter(|_| config.deferred(auto_update::now()))
+    {
+        println!(
+            "\n{} Updates are deferred until {}. '{}' resumes them.",
+            "ℹ".dimmed(),
+            format_timestamp(until),
+            "proj update --defer off".cyan()
+        );
+    }
+
     Ok(())
 }
+
+/// Save a new channel and/or deferral to ~/.proj/update.json
+fn change_settings(
+    config: &mut UpdateConfig,
+    channel: Option<String>,
+    defer: Option<&str>,
+) -> Result<()> {
+    if let Some(channel) = channel {
+        config.channel = channel;
+    }
+    if let Some(defer) = defer {
+        config.deferred_until = parse_defer(defer)?.map(|secs| auto_update::now() + secs);
+    }
+    config.save()?;
+
+    println!("{} Update channel: {}", "✓".green(), config.channel.cyan());
+    if let Some(until) = config.deferred_until {
+        println!("  Deferred until {}", format_timestamp(until));
+    } else if defer.is_some() {
+        println!("  No longer deferred");
+    }
+    Ok(())
+}
+
+/// --defer value in seconds: "7d", "12h", "2w", or "off" (None)
+fn parse_defer(value: &str) -> Result<Option<u64>> {
+    let value = value.trim();
+    if value == "off" {
+        return Ok(None);
+    }
+    let unit_start = value.len() - value.chars().last().map_or(0, char::len_utf8);
+    let (count, unit) = value.split_at(unit_start);
+    let seconds = match unit {
+        "h" => 3600,
+        "d" => 24 * 3600,
+        "w" => 7 * 24 * 3600,
+        _ => 0,
+    };
+    match count.parse::<u64>() {
+        Ok(count) if count > 0 && seconds > 0 => Ok(Some(count * seconds)),
+        _ => Err(ProjError::InvalidInput(format!(
+            "Invalid --defer '{}'. Use a number of hours, days, or weeks (12h, 7d, 2w), or 'off'.",
+            value
+        ))
+        .into()),
+    }
+}
+
+/// "1.4 MB"
+fn format_size(bytes: usize) -> String {
+    if bytes < 1024 * 1024 {
+        format!("{:.1} KB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// "2026-10-23 14:05" in local time
+fn format_timestamp(timestamp: u64) -> String {
+    use chrono::TimeZone;
+    chrono::Local
+        .timestamp_opt(timestamp as i64, 0)
+        .single()
+        .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+        .unwrap_or_default()
+}
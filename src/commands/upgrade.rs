@@ -1,40 +1,57 @@
-// Upgrade command - database schema migration system
+// Upgrade command - database schema migration system (rollback.rs runs migrations down)
 
 use std::path::Path;
+use std::sync::Mutex;
 
-use crate::commands::init::update_agents_rules_if_outdated;
+use crate::commands::agents;
+use crate::commands::rollback::create_backup_at;
 use crate::config::{ProjectConfig, Registry};
-use crate::database::{get_schema_version, open_database, set_schema_version};
-use crate::paths::{get_config_path, get_registry_path, get_tracking_db_path};
+use crate::database::{
+    get_schema_version, lock_for_migration, open_database, set_schema_version, write_transaction,
+};
+use crate::error::ProjError;
+use crate::paths::{
+    ensure_dir, get_config_path, get_global_dir, get_registry_path, get_tracking_db_path,
+};
+use crate::schema::{
+    ATTACHMENTS_AUDIT_TRIGGERS, AUDIT_TRIGGERS, DECISION_SECTIONS_AUDIT_TRIGGERS,
+    DECISION_TOPICS_AUDIT_TRIGGERS, DROP_ATTACHMENTS_AUDIT_TRIGGERS, DROP_AUDIT_TRIGGERS,
+    DROP_DECISION_SECTIONS_AUDIT_TRIGGERS, DROP_DECISION_TOPICS_AUDIT_TRIGGERS, DROP_FTS_TRIGGERS,
+    DROP_UUID_TRIGGERS, FTS_CONTENTLESS, FTS_REBUILD, FTS_TRIGGERS, MIGRATIONS_SCHEMA,
+    UUID_BACKFILL, UUID_TRIGGERS,
+};
 use crate::SCHEMA_VERSION;
 use anyhow::{Context, Result};
 use colored::Colorize;
+use rusqlite::{params, Connection};
 
-/// Schema change definition
-struct SchemaChange {
-    risk: &'static str,
-    description: &'static str,
-    sql: &'static str,
-    verify: &'static str,
+/// One step of a migration. `up` applies it, `down` undoes it (empty when there is
+/// nothing to undo), and `verify` returns a row once the step is in place.
+pub struct MigrationStep {
+    pub risk: &'static str,
+    pub description: &'static str,
+    pub up: &'static str,
+    pub down: &'static str,
+    pub verify: &'static str,
 }
 
-/// Schema upgrade definition
-struct SchemaUpgrade {
-    from_version: &'static str,
-    to_version: &'static str,
-    changes: &'static [SchemaChange],
+/// Migration between two adjacent schema versions
+pub struct Migration {
+    pub from_version: &'static str,
+    pub to_version: &'static str,
+    pub steps: &'static [MigrationStep],
 }
 
-/// Upgrade registry - all schema changes between versions
-const UPGRADE_REGISTRY: &[SchemaUpgrade] = &[
-    SchemaUpgrade {
+/// Migration registry - every schema change between versions, oldest first
+const MIGRATIONS: &[Migration] = &[
+    Migration {
         from_version: "1.0",
         to_version: "1.1",
-        changes: &[
-            SchemaChange {
+        steps: &[
+            MigrationStep {
                 risk: "safe",
                 description: "Track context snapshots for delta updates",
-                sql: "CREATE TABLE IF NOT EXISTS context_snapshots (
+                up: "CREATE TABLE IF NOT EXISTS context_snapshots (
                     snapshot_id INTEGER PRIMARY KEY AUTOINCREMENT,
                     session_id INTEGER,
                     created_at TEXT DEFAULT (datetime('now')),
@@ -43,12 +60,13 @@ const UPGRADE_REGISTRY: &[SchemaUpgrade] = &[
                     item_counts TEXT,
                     FOREIGN KEY (session_id) REFERENCES sessions(session_id)
                 )",
+                down: "DROP TABLE IF EXISTS context_snapshots",
                 verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='context_snapshots'",
             },
-            SchemaChange {
+            MigrationStep {
                 risk: "safe",
                 description: "Store compressed session summaries",
-                sql: "CREATE TABLE IF NOT EXISTS compressed_sessions (
+                up: "CREATE TABLE IF NOT EXISTS compressed_sessions (
                     compression_id INTEGER PRIMARY KEY AUTOINCREMENT,
                     created_at TEXT DEFAULT (datetime('now')),
                     session_ids TEXT NOT NULL,
@@ -58,48 +76,52 @@ const UPGRADE_REGISTRY: &[SchemaUpgrade] = &[
                     original_token_estimate INTEGER,
                     compressed_token_estimate INTEGER
                 )",
+                down: "DROP TABLE IF EXISTS compressed_sessions",
                 verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='compressed_sessions'",
             },
-            SchemaChange {
+            MigrationStep {
                 risk: "safe",
                 description: "Index for context snapshot lookups",
-                sql: "CREATE INDEX IF NOT EXISTS idx_context_snapshots_session ON context_snapshots(session_id)",
+                up: "CREATE INDEX IF NOT EXISTS idx_context_snapshots_session ON context_snapshots(session_id)",
+                down: "DROP INDEX IF EXISTS idx_context_snapshots_session",
                 verify: "SELECT 1 FROM sqlite_master WHERE type='index' AND name='idx_context_snapshots_session'",
             },
         ],
     },
-    SchemaUpgrade {
+    Migration {
         from_version: "1.1",
         to_version: "1.2",
-        changes: &[
-            SchemaChange {
+        steps: &[
+            MigrationStep {
                 risk: "safe",
                 description: "Track whether full context was shown this session",
-                sql: "ALTER TABLE sessions ADD COLUMN full_context_shown INTEGER DEFAULT 0",
-                verify: "SELECT full_context_shown FROM sessions LIMIT 0",
+                up: "ALTER TABLE sessions ADD COLUMN full_context_shown INTEGER DEFAULT 0",
+                down: "ALTER TABLE sessions DROP COLUMN full_context_shown",
+                verify: "SELECT 1 FROM pragma_table_info('sessions') WHERE name='full_context_shown'",
             },
         ],
     },
-    SchemaUpgrade {
+    Migration {
         from_version: "1.2",
         to_version: "1.3",
-        changes: &[
-            SchemaChange {
+        steps: &[
+            MigrationStep {
                 risk: "safe",
                 description: "FTS5 virtual table for full-text search across decisions, notes, tasks",
-                sql: "CREATE VIRTUAL TABLE IF NOT EXISTS tracking_fts USING fts5(content, table_name, record_id, content='', tokenize='porter')",
+                up: "CREATE VIRTUAL TABLE IF NOT EXISTS tracking_fts USING fts5(content, table_name, record_id, content='', tokenize='porter')",
+                down: "DROP TABLE IF EXISTS tracking_fts",
                 verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='tracking_fts'",
             },
         ],
     },
-    SchemaUpgrade {
+    Migration {
         from_version: "1.3",
         to_version: "1.4",
-        changes: &[
-            SchemaChange {
+        steps: &[
+            MigrationStep {
                 risk: "safe",
                 description: "Store git commit history for context",
-                sql: "CREATE TABLE IF NOT EXISTS git_commits (
+                up: "CREATE TABLE IF NOT EXISTS git_commits (
                     commit_id INTEGER PRIMARY KEY AUTOINCREMENT,
                     hash TEXT NOT NULL UNIQUE,
                     short_hash TEXT NOT NULL,
@@ -111,25 +133,555 @@ const UPGRADE_REGISTRY: &[SchemaUpgrade] = &[
                     deletions INTEGER DEFAULT 0,
                     synced_at TEXT DEFAULT (datetime('now'))
                 )",
+                down: "DROP TABLE IF EXISTS git_commits",
                 verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='git_commits'",
             },
-            SchemaChange {
+            MigrationStep {
                 risk: "safe",
                 description: "Index git commits by hash",
-                sql: "CREATE INDEX IF NOT EXISTS idx_git_commits_hash ON git_commits(hash)",
+                up: "CREATE INDEX IF NOT EXISTS idx_git_commits_hash ON git_commits(hash)",
+                down: "DROP INDEX IF EXISTS idx_git_commits_hash",
                 verify: "SELECT 1 FROM sqlite_master WHERE type='index' AND name='idx_git_commits_hash'",
             },
-            SchemaChange {
+            MigrationStep {
                 risk: "safe",
                 description: "Index git commits by date",
-                sql: "CREATE INDEX IF NOT EXISTS idx_git_commits_date ON git_commits(committed_at)",
+                up: "CREATE INDEX IF NOT EXISTS idx_git_commits_date ON git_commits(committed_at)",
+                down: "DROP INDEX IF EXISTS idx_git_commits_date",
                 verify: "SELECT 1 FROM sqlite_master WHERE type='index' AND name='idx_git_commits_date'",
             },
-            SchemaChange {
+            MigrationStep {
                 risk: "safe",
                 description: "Structured session summaries",
-                sql: "ALTER TABLE sessions ADD COLUMN structured_summary TEXT",
-                verify: "SELECT structured_summary FROM sessions LIMIT 0",
+                up: "ALTER TABLE sessions ADD COLUMN structured_summary TEXT",
+                down: "ALTER TABLE sessions DROP COLUMN structured_summary",
+                verify: "SELECT 1 FROM pragma_table_info('sessions') WHERE name='structured_summary'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.4",
+        to_version: "1.5",
+        steps: &[MigrationStep {
+            risk: "safe",
+            description: "Task due dates",
+            up: "ALTER TABLE tasks ADD COLUMN due TEXT",
+            down: "ALTER TABLE tasks DROP COLUMN due",
+            verify: "SELECT 1 FROM pragma_table_info('tasks') WHERE name='due'",
+        }],
+    },
+    Migration {
+        from_version: "1.5",
+        to_version: "1.6",
+        steps: &[MigrationStep {
+            risk: "safe",
+            description: "Keep searchable text in tracking_fts_docs so search can show snippets (rebuilds index)",
+            up: FTS_REBUILD,
+            down: FTS_CONTENTLESS,
+            verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='tracking_fts_docs'",
+        }],
+    },
+    Migration {
+        from_version: "1.6",
+        to_version: "1.7",
+        steps: &[MigrationStep {
+            risk: "safe",
+            description: "Triggers that keep the search index in sync on insert/update/delete",
+            up: FTS_TRIGGERS,
+            down: DROP_FTS_TRIGGERS,
+            verify: "SELECT 1 FROM sqlite_master WHERE type='trigger' AND name='git_commits_fts_delete'",
+        }],
+    },
+    Migration {
+        from_version: "1.7",
+        to_version: "1.8",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Session names for parallel sessions",
+                up: "ALTER TABLE sessions ADD COLUMN name TEXT",
+                down: "ALTER TABLE sessions DROP COLUMN name",
+                verify: "SELECT 1 FROM pragma_table_info('sessions') WHERE name='name'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Resume time for paused sessions",
+                up: "ALTER TABLE sessions ADD COLUMN resumed_at TEXT",
+                down: "ALTER TABLE sessions DROP COLUMN resumed_at",
+                verify: "SELECT 1 FROM pragma_table_info('sessions') WHERE name='resumed_at'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.8",
+        to_version: "1.9",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Git branch for sessions",
+                up: "ALTER TABLE sessions ADD COLUMN branch TEXT",
+                down: "ALTER TABLE sessions DROP COLUMN branch",
+                verify: "SELECT 1 FROM pragma_table_info('sessions') WHERE name='branch'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Git branch for tasks",
+                up: "ALTER TABLE tasks ADD COLUMN branch TEXT",
+                down: "ALTER TABLE tasks DROP COLUMN branch",
+                verify: "SELECT 1 FROM pragma_table_info('tasks') WHERE name='branch'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Git branch for decisions",
+                up: "ALTER TABLE decisions ADD COLUMN branch TEXT",
+                down: "ALTER TABLE decisions DROP COLUMN branch",
+                verify: "SELECT 1 FROM pragma_table_info('decisions') WHERE name='branch'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Git branch for notes",
+                up: "ALTER TABLE context_notes ADD COLUMN branch TEXT",
+                down: "ALTER TABLE context_notes DROP COLUMN branch",
+                verify: "SELECT 1 FROM pragma_table_info('context_notes') WHERE name='branch'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Git branch for blockers",
+                up: "ALTER TABLE blockers ADD COLUMN branch TEXT",
+                down: "ALTER TABLE blockers DROP COLUMN branch",
+                verify: "SELECT 1 FROM pragma_table_info('blockers') WHERE name='branch'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Git branch for questions",
+                up: "ALTER TABLE questions ADD COLUMN branch TEXT",
+                down: "ALTER TABLE questions DROP COLUMN branch",
+                verify: "SELECT 1 FROM pragma_table_info('questions') WHERE name='branch'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.9",
+        to_version: "1.10",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Origin of tasks created from source comments",
+                up: "ALTER TABLE tasks ADD COLUMN source TEXT",
+                down: "ALTER TABLE tasks DROP COLUMN source",
+                verify: "SELECT 1 FROM pragma_table_info('tasks') WHERE name='source'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "File and line of a task's source comment",
+                up: "ALTER TABLE tasks ADD COLUMN source_ref TEXT",
+                down: "ALTER TABLE tasks DROP COLUMN source_ref",
+                verify: "SELECT 1 FROM pragma_table_info('tasks') WHERE name='source_ref'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.10",
+        to_version: "1.11",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Tags for tasks, decisions, and notes",
+                up: "CREATE TABLE IF NOT EXISTS item_tags (
+                    item_tag_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    tag TEXT NOT NULL,
+                    task_id INTEGER,
+                    decision_id INTEGER,
+                    note_id INTEGER,
+                    created_at TEXT DEFAULT (datetime('now')),
+                    FOREIGN KEY (task_id) REFERENCES tasks(task_id),
+                    FOREIGN KEY (decision_id) REFERENCES decisions(decision_id),
+                    FOREIGN KEY (note_id) REFERENCES context_notes(note_id)
+                )",
+                down: "DROP TABLE IF EXISTS item_tags",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='item_tags'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Index tags by name",
+                up: "CREATE INDEX IF NOT EXISTS idx_item_tags_tag ON item_tags(tag)",
+                down: "DROP INDEX IF EXISTS idx_item_tags_tag",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='index' AND name='idx_item_tags_tag'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.11",
+        to_version: "1.12",
+        steps: &[MigrationStep {
+            risk: "safe",
+            description: "Embedding vectors for semantic search",
+            up: "CREATE TABLE IF NOT EXISTS embeddings (
+                    embedding_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    table_name TEXT NOT NULL,
+                    record_id INTEGER NOT NULL,
+                    model TEXT NOT NULL,
+                    content_hash TEXT NOT NULL,
+                    dimensions INTEGER NOT NULL,
+                    vector BLOB NOT NULL,
+                    created_at TEXT DEFAULT (datetime('now')),
+                    UNIQUE(table_name, record_id)
+                )",
+            down: "DROP TABLE IF EXISTS embeddings",
+            verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='embeddings'",
+        }],
+    },
+    Migration {
+        from_version: "1.12",
+        to_version: "1.13",
+        steps: &[MigrationStep {
+            risk: "safe",
+            description: "Task links to external issue trackers",
+            up: "CREATE TABLE IF NOT EXISTS task_links (
+                    link_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    task_id INTEGER NOT NULL,
+                    provider TEXT NOT NULL,
+                    remote_id TEXT NOT NULL,
+                    url TEXT,
+                    remote_state TEXT,
+                    local_status TEXT,
+                    synced_at TEXT DEFAULT (datetime('now')),
+                    UNIQUE(provider, remote_id)
+                )",
+            down: "DROP TABLE IF EXISTS task_links",
+            verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='task_links'",
+        }],
+    },
+    Migration {
+        from_version: "1.13",
+        to_version: "1.14",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Author of sessions",
+                up: "ALTER TABLE sessions ADD COLUMN author TEXT",
+                down: "ALTER TABLE sessions DROP COLUMN author",
+                verify: "SELECT 1 FROM pragma_table_info('sessions') WHERE name='author'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Author of tasks",
+                up: "ALTER TABLE tasks ADD COLUMN author TEXT",
+                down: "ALTER TABLE tasks DROP COLUMN author",
+                verify: "SELECT 1 FROM pragma_table_info('tasks') WHERE name='author'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Author of decisions",
+                up: "ALTER TABLE decisions ADD COLUMN author TEXT",
+                down: "ALTER TABLE decisions DROP COLUMN author",
+                verify: "SELECT 1 FROM pragma_table_info('decisions') WHERE name='author'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Author of notes",
+                up: "ALTER TABLE context_notes ADD COLUMN author TEXT",
+                down: "ALTER TABLE context_notes DROP COLUMN author",
+                verify: "SELECT 1 FROM pragma_table_info('context_notes') WHERE name='author'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Author of blockers",
+                up: "ALTER TABLE blockers ADD COLUMN author TEXT",
+                down: "ALTER TABLE blockers DROP COLUMN author",
+                verify: "SELECT 1 FROM pragma_table_info('blockers') WHERE name='author'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.14",
+        to_version: "1.15",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "UUIDs for sessions",
+                up: "ALTER TABLE sessions ADD COLUMN uuid TEXT;
+                      CREATE UNIQUE INDEX IF NOT EXISTS idx_sessions_uuid ON sessions(uuid);",
+                down: "DROP INDEX IF EXISTS idx_sessions_uuid;
+                       ALTER TABLE sessions DROP COLUMN uuid;",
+                verify: "SELECT 1 FROM pragma_table_info('sessions') WHERE name='uuid'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "UUIDs for tasks",
+                up: "ALTER TABLE tasks ADD COLUMN uuid TEXT;
+                      CREATE UNIQUE INDEX IF NOT EXISTS idx_tasks_uuid ON tasks(uuid);",
+                down: "DROP INDEX IF EXISTS idx_tasks_uuid;
+                       ALTER TABLE tasks DROP COLUMN uuid;",
+                verify: "SELECT 1 FROM pragma_table_info('tasks') WHERE name='uuid'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "UUIDs for decisions",
+                up: "ALTER TABLE decisions ADD COLUMN uuid TEXT;
+                      CREATE UNIQUE INDEX IF NOT EXISTS idx_decisions_uuid ON decisions(uuid);",
+                down: "DROP INDEX IF EXISTS idx_decisions_uuid;
+                       ALTER TABLE decisions DROP COLUMN uuid;",
+                verify: "SELECT 1 FROM pragma_table_info('decisions') WHERE name='uuid'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "UUIDs for notes",
+                up: "ALTER TABLE context_notes ADD COLUMN uuid TEXT;
+                      CREATE UNIQUE INDEX IF NOT EXISTS idx_context_notes_uuid ON context_notes(uuid);",
+                down: "DROP INDEX IF EXISTS idx_context_notes_uuid;
+                       ALTER TABLE context_notes DROP COLUMN uuid;",
+                verify: "SELECT 1 FROM pragma_table_info('context_notes') WHERE name='uuid'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "UUIDs for blockers",
+                up: "ALTER TABLE blockers ADD COLUMN uuid TEXT;
+                      CREATE UNIQUE INDEX IF NOT EXISTS idx_blockers_uuid ON blockers(uuid);",
+                down: "DROP INDEX IF EXISTS idx_blockers_uuid;
+                       ALTER TABLE blockers DROP COLUMN uuid;",
+                verify: "SELECT 1 FROM pragma_table_info('blockers') WHERE name='uuid'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "UUIDs for questions",
+                up: "ALTER TABLE questions ADD COLUMN uuid TEXT;
+                      CREATE UNIQUE INDEX IF NOT EXISTS idx_questions_uuid ON questions(uuid);",
+                down: "DROP INDEX IF EXISTS idx_questions_uuid;
+                       ALTER TABLE questions DROP COLUMN uuid;",
+                verify: "SELECT 1 FROM pragma_table_info('questions') WHERE name='uuid'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Assign UUIDs to existing records",
+                up: UUID_BACKFILL,
+                down: "",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='trigger' AND name='questions_uuid_insert'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Triggers that give new records a UUID",
+                up: UUID_TRIGGERS,
+                down: DROP_UUID_TRIGGERS,
+                verify: "SELECT 1 FROM sqlite_master WHERE type='trigger' AND name='questions_uuid_insert'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.15",
+        to_version: "1.16",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Branch each commit was recorded on",
+                up: "ALTER TABLE git_commits ADD COLUMN branch TEXT",
+                down: "ALTER TABLE git_commits DROP COLUMN branch",
+                verify: "SELECT 1 FROM pragma_table_info('git_commits') WHERE name='branch'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Files changed per commit, with insertions and deletions",
+                up: "CREATE TABLE IF NOT EXISTS git_commit_files (
+                    commit_file_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    commit_id INTEGER NOT NULL REFERENCES git_commits(commit_id) ON DELETE CASCADE,
+                    path TEXT NOT NULL,
+                    insertions INTEGER DEFAULT 0,
+                    deletions INTEGER DEFAULT 0,
+                    UNIQUE(commit_id, path)
+                );
+                CREATE INDEX IF NOT EXISTS idx_git_commit_files_path ON git_commit_files(path);",
+                down: "DROP INDEX IF EXISTS idx_git_commit_files_path;
+                       DROP TABLE IF EXISTS git_commit_files;",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='git_commit_files'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Links from commits to the tasks their messages name (#task-N)",
+                up: "CREATE TABLE IF NOT EXISTS git_commit_tasks (
+                    commit_id INTEGER NOT NULL REFERENCES git_commits(commit_id) ON DELETE CASCADE,
+                    task_id INTEGER NOT NULL REFERENCES tasks(task_id) ON DELETE CASCADE,
+                    PRIMARY KEY(commit_id, task_id)
+                );
+                CREATE INDEX IF NOT EXISTS idx_git_commit_tasks_task ON git_commit_tasks(task_id);",
+                down: "DROP INDEX IF EXISTS idx_git_commit_tasks_task;
+                       DROP TABLE IF EXISTS git_commit_tasks;",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='git_commit_tasks'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.16",
+        to_version: "1.17",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Audit log of inserts, updates, and deletes",
+                up: "CREATE TABLE IF NOT EXISTS audit_log (
+                    audit_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    timestamp TEXT DEFAULT (datetime('now')),
+                    table_name TEXT NOT NULL,
+                    record_id INTEGER NOT NULL,
+                    action TEXT NOT NULL,
+                    changes TEXT,
+                    session_id INTEGER,
+                    author TEXT
+                );
+                CREATE INDEX IF NOT EXISTS idx_audit_log_timestamp ON audit_log(timestamp);
+                CREATE INDEX IF NOT EXISTS idx_audit_log_record ON audit_log(table_name, record_id);",
+                down: "DROP INDEX IF EXISTS idx_audit_log_record;
+                       DROP INDEX IF EXISTS idx_audit_log_timestamp;
+                       DROP TABLE IF EXISTS audit_log;",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='audit_log'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Triggers that record changes in the audit log",
+                up: AUDIT_TRIGGERS,
+                down: DROP_AUDIT_TRIGGERS,
+                verify: "SELECT 1 FROM sqlite_master WHERE type='trigger' AND name='task_links_audit_delete'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.17",
+        to_version: "1.18",
+        steps: &[MigrationStep {
+            risk: "safe",
+            description: "Queue of agent writes awaiting approval",
+            up: "CREATE TABLE IF NOT EXISTS pending_writes (
+                pending_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                created_at TEXT DEFAULT (datetime('now')),
+                args TEXT NOT NULL,
+                session_id INTEGER,
+                author TEXT,
+                status TEXT DEFAULT 'pending',
+                resolved_at TEXT,
+                error TEXT
+            );
+            CREATE INDEX IF NOT EXISTS idx_pending_writes_status ON pending_writes(status);",
+            down: "DROP INDEX IF EXISTS idx_pending_writes_status;
+                   DROP TABLE IF EXISTS pending_writes;",
+            verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='pending_writes'",
+        }],
+    },
+    Migration {
+        from_version: "1.18",
+        to_version: "1.19",
+        steps: &[MigrationStep {
+            risk: "safe",
+            description: "Command timings for usage_metrics",
+            up: "CREATE TABLE IF NOT EXISTS command_metrics (
+                metric_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                timestamp TEXT DEFAULT (datetime('now')),
+                command TEXT NOT NULL,
+                flags TEXT,
+                duration_ms INTEGER NOT NULL,
+                output_tokens INTEGER,
+                terminal INTEGER DEFAULT 0,
+                success INTEGER DEFAULT 1
+            );
+            CREATE INDEX IF NOT EXISTS idx_command_metrics_timestamp ON command_metrics(timestamp);",
+            down: "DROP INDEX IF EXISTS idx_command_metrics_timestamp;
+                   DROP TABLE IF EXISTS command_metrics;",
+            verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='command_metrics'",
+        }],
+    },
+    Migration {
+        from_version: "1.19",
+        to_version: "1.20",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Decision topics with aliases, seeded from existing decisions",
+                up: "CREATE TABLE IF NOT EXISTS decision_topics (
+                    topic TEXT PRIMARY KEY COLLATE NOCASE,
+                    alias_of TEXT COLLATE NOCASE,
+                    created_at TEXT DEFAULT (datetime('now'))
+                );
+                CREATE INDEX IF NOT EXISTS idx_decision_topics_alias ON decision_topics(alias_of);
+                INSERT OR IGNORE INTO decision_topics (topic)
+                    SELECT trim(topic) FROM decisions WHERE trim(topic) != '' ORDER BY created_at;",
+                down: "DROP INDEX IF EXISTS idx_decision_topics_alias;
+                       DROP TABLE IF EXISTS decision_topics;",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='decision_topics'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Record decision topic changes in the audit log",
+                up: DECISION_TOPICS_AUDIT_TRIGGERS,
+                down: DROP_DECISION_TOPICS_AUDIT_TRIGGERS,
+                verify: "SELECT 1 FROM sqlite_master
+                         WHERE type='trigger' AND name='decision_topics_audit_delete'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.20",
+        to_version: "1.21",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "Links from decisions to docs sections",
+                up: "CREATE TABLE IF NOT EXISTS decision_sections (
+                    decision_id INTEGER NOT NULL,
+                    section_id TEXT NOT NULL,
+                    title TEXT,
+                    PRIMARY KEY (decision_id, section_id),
+                    FOREIGN KEY (decision_id) REFERENCES decisions(decision_id) ON DELETE CASCADE
+                );
+                CREATE INDEX IF NOT EXISTS idx_decision_sections_section ON decision_sections(section_id);",
+                down: "DROP INDEX IF EXISTS idx_decision_sections_section;
+                       DROP TABLE IF EXISTS decision_sections;",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='decision_sections'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Record decision section link changes in the audit log",
+                up: DECISION_SECTIONS_AUDIT_TRIGGERS,
+                down: DROP_DECISION_SECTIONS_AUDIT_TRIGGERS,
+                verify: "SELECT 1 FROM sqlite_master
+                         WHERE type='trigger' AND name='decision_sections_audit_delete'",
+            },
+        ],
+    },
+    Migration {
+        from_version: "1.21",
+        to_version: "1.22",
+        steps: &[
+            MigrationStep {
+                risk: "safe",
+                description: "File attachments on tracked items",
+                up: "CREATE TABLE IF NOT EXISTS attachments (
+                    attachment_id INTEGER PRIMARY KEY AUTOINCREMENT,
+                    task_id INTEGER,
+                    decision_id INTEGER,
+                    note_id INTEGER,
+                    blocker_id INTEGER,
+                    question_id INTEGER,
+                    file_name TEXT NOT NULL,
+                    size INTEGER NOT NULL,
+                    sha256 TEXT NOT NULL,
+                    content BLOB NOT NULL,
+                    author TEXT,
+                    created_at TEXT DEFAULT (datetime('now')),
+                    FOREIGN KEY (task_id) REFERENCES tasks(task_id) ON DELETE CASCADE,
+                    FOREIGN KEY (decision_id) REFERENCES decisions(decision_id) ON DELETE CASCADE,
+                    FOREIGN KEY (note_id) REFERENCES context_notes(note_id) ON DELETE CASCADE,
+                    FOREIGN KEY (blocker_id) REFERENCES blockers(blocker_id) ON DELETE CASCADE,
+                    FOREIGN KEY (question_id) REFERENCES questions(question_id) ON DELETE CASCADE
+                );
+                CREATE INDEX IF NOT EXISTS idx_attachments_decision ON attachments(decision_id);",
+                down: "DROP INDEX IF EXISTS idx_attachments_decision;
+                       DROP TABLE IF EXISTS attachments;",
+                verify: "SELECT 1 FROM sqlite_master WHERE type='table' AND name='attachments'",
+            },
+            MigrationStep {
+                risk: "safe",
+                description: "Record attachment changes in the audit log",
+                up: ATTACHMENTS_AUDIT_TRIGGERS,
+                down: DROP_ATTACHMENTS_AUDIT_TRIGGERS,
+                verify: "SELECT 1 FROM sqlite_master
+                         WHERE type='trigger' AND name='attachments_audit_delete'",
             },
         ],
     },
@@ -140,7 +692,7 @@ struct UpgradeCompatibility {
     can_upgrade: bool,
     current_version: String,
     target_version: String,
-    pending_upgrades: Vec<&'static SchemaUpgrade>,
+    pending_upgrades: Vec<&'static Migration>,
     safe_changes: Vec<ChangeInfo>,
     warnings: Vec<String>,
     errors: Vec<String>,
@@ -148,22 +700,46 @@ struct UpgradeCompatibility {
 
 /// Change info for display
 struct ChangeInfo {
+    migration: String,
     description: String,
     risk: String,
     status: String,
+    sql: &'static str,
+}
+
+/// One project's line in the `proj upgrade --all` report
+#[derive(serde::Serialize)]
+struct ProjectReport {
+    name: String,
+    path: String,
+    old_version: String,
+    new_version: String,
+    /// upgraded, failed, up_to_date, or skipped
+    status: &'static str,
+    backup: Option<String>,
+    error: Option<String>,
+    hint: Option<String>,
 }
 
-pub fn run(info: bool, all: bool, auto: bool) -> Result<()> {
+/// Report written to ~/.proj/reports/ after `proj upgrade --all`
+#[derive(serde::Serialize)]
+struct UpgradeReport {
+    created_at: String,
+    target_version: String,
+    projects: Vec<ProjectReport>,
+}
+
+pub fn run(info: bool, all: bool, auto: bool, jobs: Option<usize>) -> Result<()> {
     if all {
-        upgrade_all_projects(info, auto)
+        upgrade_all_projects(info, auto, jobs)
     } else {
         upgrade_current_project(info)
     }
 }
 
-/// Upgrade all registered projects
-fn upgrade_all_projects(info_mode: bool, auto_mode: bool) -> Result<()> {
-    let registry = load_registry()?;
+/// Upgrade all registered projects, `jobs` at a time, backing each one up first
+fn upgrade_all_projects(info_mode: bool, auto_mode: bool, jobs: Option<usize>) -> Result<()> {
+    let mut registry = load_registry()?;
 
     if registry.registered_projects.is_empty() {
         println!("No projects registered.");
@@ -178,17 +754,38 @@ fn upgrade_all_projects(info_mode: bool, auto_mode: bool) -> Result<()> {
     let mut upgradeable = Vec::new();
     let mut up_to_date = Vec::new();
     let mut errors = Vec::new();
+    let mut reports = Vec::new();
 
     for proj in &registry.registered_projects {
         let proj_path = Path::new(&proj.path);
+        let mut skipped = |error: String, hint: Option<&str>| {
+            reports.push(ProjectReport {
+                name: proj.name.clone(),
+                path: proj.path.clone(),
+                old_version: proj.schema_version.clone(),
+                new_version: proj.schema_version.clone(),
+                status: "skipped",
+                backup: None,
+                error: Some(error.clone()),
+                hint: hint.map(String::from),
+            });
+            errors.push((proj.name.clone(), error));
+        };
+
         if !proj_path.exists() {
-            errors.push((proj.name.clone(), "Path not found".to_string()));
+            skipped(
+                "Path not found".to_string(),
+                Some("Register the project again from its new location ('proj register'), or remove it from ~/.proj/registry.json"),
+            );
             continue;
         }
 
         let db_path = proj_path.join(".tracking").join("tracking.db");
         if !db_path.exists() {
-            errors.push((proj.name.clone(), "No tracking.db".to_string()));
+            skipped(
+                "No tracking.db".to_string(),
+                Some("Run 'proj init' in the project"),
+            );
             continue;
         }
 
@@ -196,15 +793,25 @@ fn upgrade_all_projects(info_mode: bool, auto_mode: bool) -> Result<()> {
             Ok(compat) => {
                 if compat.current_version == compat.target_version {
                     up_to_date.push((proj.name.clone(), compat.current_version.clone()));
+                    reports.push(ProjectReport {
+                        name: proj.name.clone(),
+                        path: proj.path.clone(),
+                        old_version: compat.current_version.clone(),
+                        new_version: compat.current_version.clone(),
+                        status: "up_to_date",
+                        backup: None,
+                        error: None,
+                        hint: None,
+                    });
                 } else if compat.can_upgrade {
                     upgradeable.push((proj.name.clone(), proj.path.clone(), compat));
                 } else {
                     let err_msg = compat.errors.join("; ");
-                    errors.push((proj.name.clone(), err_msg));
+                    skipped(err_msg, None);
                 }
             }
             Err(e) => {
-                errors.push((proj.name.clone(), e.to_string()));
+                skipped(e.to_string(), Some("Run 'proj doctor' in the project"));
             }
         }
     }
@@ -269,49 +876,74 @@ fn upgrade_all_projects(info_mode: bool, auto_mode: bool) -> Result<()> {
         }
     }
 
-    // Perform upgrades
-    println!("\nUpgrading {} project(s)...\n", upgradeable.len());
-    let mut success_count = 0;
-    let mut fail_count = 0;
+    // Perform upgrades, each worker taking the next project off the queue
+    let jobs = jobs
+        .unwrap_or_else(default_jobs)
+        .clamp(1, upgradeable.len());
+    println!(
+        "\nUpgrading {} project(s), {} at a time...\n",
+        upgradeable.len(),
+        jobs
+    );
+    let queue = Mutex::new(upgradeable.into_iter());
+    let upgraded = Mutex::new(Vec::new());
 
-    for (name, path, _) in upgradeable {
-        let db_path = Path::new(&path).join(".tracking").join("tracking.db");
-        let config_path = Path::new(&path).join(".tracking").join("config.json");
+    std::thread::scope(|scope| {
+        for _ in 0..jobs {
+            scope.spawn(|| loop {
+                let next = queue.lock().unwrap().next();
+                let Some((name, path, compat)) = next else {
+                    break;
+                };
+                let report = upgrade_registered_project(name, path, compat.current_version);
+                match report.status {
+                    "upgraded" => println!(
+                        "  {} {}: v{} → v{}",
+                        "✓".green(),
+                        report.name,
+                        report.old_version,
+                        report.new_version
+                    ),
+                    _ => println!(
+                        "  {} {}: {}",
+                        "✗".red(),
+                        report.name,
+                        report.error.as_deref().unwrap_or("failed")
+                    ),
+                }
+                upgraded.lock().unwrap().push(report);
+            });
+        }
+    });
 
-        match apply_upgrades(&db_path, &config_path) {
-            Ok(_) => {
-                println!(
-                    "  {} {}: Upgraded to v{}",
-                    "✓".green(),
-                    name,
-                    SCHEMA_VERSION
-                );
-                success_count += 1;
-            }
-            Err(e) => {
-                println!("  {} {}: {}", "✗".red(), name, e);
-                fail_count += 1;
-            }
+    let upgraded = upgraded.into_inner().unwrap();
+    let success_count = upgraded.iter().filter(|r| r.status == "upgraded").count();
+    let fail_count = upgraded.len() - success_count;
+
+    // Keep the registry's recorded versions in step
+    for report in &upgraded {
+        if let Some(entry) = registry
+            .registered_projects
+            .iter_mut()
+            .find(|e| e.path == report.path)
+        {
+            entry.schema_version = report.new_version.clone();
         }
     }
+    save_registry(&registry)?;
 
-    // Update AGENTS.md rules after all project upgrades
+    reports.extend(upgraded);
+    reports.sort_by(|a, b| a.name.cmp(&b.name));
+    print_report_table(&reports);
+    match write_report(reports) {
+        Ok(path) => println!("\nReport: {}", path.display()),
+        Err(e) => println!("\n  {} Could not write report: {}", "⚠".yellow(), e),
+    }
+
+    // AGENTS.md is only ever updated by `proj agents update`
     if success_count > 0 {
-        match update_agents_rules_if_outdated() {
-            Ok(updated_files) if !updated_files.is_empty() => {
-                println!("\nUpdated AI logging rules in:");
-                for file in updated_files {
-                    println!("  {} {}", "✓".green(), file);
-                }
-            }
-            Ok(_) => {} // No files needed updating
-            Err(e) => {
-                println!(
-                    "\n  {} Could not update AGENTS.md rules: {}",
-                    "⚠".yellow(),
-                    e
-                );
-            }
+        if let Some(hint) = agents::upgrade_hint() {
+            println!("\n{} {}", "⚠".yellow(), hint);
         }
     }
 
@@ -322,6 +954,125 @@ fn upgrade_all_projects(info_mode: bool, auto_mode: bool) -> Result<()> {
     Ok(())
 }
 
+/// Back up and upgrade one registered project for `proj upgrade --all`
+fn upgrade_registered_project(name: String, path: String, old_version: String) -> ProjectReport {
+    let project_root = Path::new(&path);
+    let db_path = project_root.join(".tracking").join("tracking.db");
+    let config_path = project_root.join(".tracking").join("config.json");
+    let mut report = ProjectReport {
+        name,
+        path: path.clone(),
+        old_version: old_version.clone(),
+        new_version: old_version,
+        status: "failed",
+        backup: None,
+        error: None,
+        hint: None,
+    };
+
+    match create_backup_at(project_root, &report.name) {
+        Ok(backup_path) => report.backup = Some(backup_path.to_string_lossy().to_string()),
+        Err(e) => {
+            report.error = Some(format!("Backup failed: {}", e));
+            report.hint = Some(
+                "Nothing was changed. Check free space and permissions in ~/.proj/backups, then rerun"
+                    .to_string(),
+            );
+            return report;
+        }
+    }
+
+    let result = apply_upgrades(&db_path, &config_path);
+    if let Some(version) = open_database(&db_path)
+        .ok()
+        .and_then(|conn| get_schema_version(&conn).ok().flatten())
+    {
+        report.new_version = version;
+    }
+    match result {
+        Ok(()) => report.status = "upgraded",
+        Err(e) => {
+            let locked = e.chain().any(|cause| {
+                let message = cause.to_string();
+                message.contains("database is locked") || message.contains("database is busy")
+            });
+            report.hint = Some(if locked {
+                "Another process has the database open; close it (editor extension, proj watch) and run 'proj upgrade' in the project".to_string()
+            } else {
+                format!(
+                    "Database left at v{}. Fix the cause and run 'proj upgrade' in the project, or restore the backup with 'proj rollback --schema --backup'",
+                    report.new_version
+                )
+            });
+            report.error = Some(format!("{:#}", e));
+        }
+    }
+    report
+}
+
+/// Default number of projects to upgrade at once
+fn default_jobs() -> usize {
+    std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1)
+        .min(4)
+}
+
+/// Print the `proj upgrade --all` report as a table, with hints under failures
+fn print_report_table(reports: &[ProjectReport]) {
+    let width = reports
+        .iter()
+        .map(|r| r.name.len())
+        .max()
+        .unwrap_or(0)
+        .max("Project".len());
+
+    println!();
+    println!(
+        "{}",
+        format!(
+            "{:<width$}  {:<8} {:<8} {}",
+            "Project", "Old", "New", "Status"
+        )
+        .bold()
+    );
+    for report in reports {
+        let status = match report.status {
+            "upgraded" => report.status.green(),
+            "up_to_date" => report.status.normal(),
+            _ => report.status.red(),
+        };
+        println!(
+            "{:<width$}  {:<8} {:<8} {}",
+            report.name,
+            format!("v{}", report.old_version),
+            format!("v{}", report.new_version),
+            status
+        );
+        if let Some(ref error) = report.error {
+            println!("{:<width$}  {}", "", error.red());
+        }
+        if let Some(ref hint) = report.hint {
+            println!("{:<width$}  {}", "", hint.dimmed());
+        }
+    }
+}
+
+/// Write the `proj upgrade --all` report as JSON under ~/.proj/reports/
+fn write_report(projects: Vec<ProjectReport>) -> Result<std::path::PathBuf> {
+    let dir = get_global_dir()?.join("reports");
+    ensure_dir(&dir)?;
+    let now = chrono::Utc::now();
+    let path = dir.join(format!("upgrade-{}.json", now.format("%Y%m%d_%H%M%S")));
+    let report = UpgradeReport {
+        created_at: now.to_rfc3339(),
+        target_version: SCHEMA_VERSION.to_string(),
+        projects,
+    };
+    std::fs::write(&path, serde_json::to_string_pretty(&report)?)?;
+    Ok(path)
+}
+
 /// Upgrade the current project
 fn upgrade_current_project(info_mode: bool) -> Result<()> {
     let config = load_config()?;
@@ -334,19 +1085,9 @@ fn upgrade_current_project(info_mode: bool) -> Result<()> {
         if compat.current_version == compat.target_version {
             println!("Database is up to date (v{}).", compat.current_version);
 
-            // Even if database is current, check if AGENTS.md rules need updating
-            match update_agents_rules_if_outdated() {
-                Ok(updated_files) if !updated_files.is_empty() => {
-                    println!();
-                    println!("Updated AI logging rules in:");
-                    for file in updated_files {
-                        println!("  {} {}", "✓".green(), file);
-                    }
-                }
-                Ok(_) => {} // No files needed updating
-                Err(e) => {
-                    println!("  {} Could not update AGENTS.md rules: {}", "⚠".yellow(), e);
-                }
+            // Even if database is current, AGENTS.md may be behind
+            if let Some(hint) = agents::upgrade_hint() {
+                println!("\n{} {}", "⚠".yellow(), hint);
             }
         } else {
             println!("Cannot upgrade: {}", compat.errors.join("; "));
@@ -376,6 +1117,21 @@ fn upgrade_current_project(info_mode: bool) -> Result<()> {
     }
 
     if info_mode {
+        println!();
+        println!("SQL plan:");
+        let mut heading = "";
+        for change in &compat.safe_changes {
+            if change.status != "pending" {
+                continue;
+            }
+            if change.migration != heading {
+                heading = &change.migration;
+                println!();
+                println!("{}", format!("-- {}", heading).bold());
+            }
+            println!("{}", format!("-- {}", change.description).dimmed());
+            print_sql(change.sql);
+        }
         println!("\n[DRY-RUN] No changes made.");
         return Ok(());
     }
@@ -402,25 +1158,14 @@ fn upgrade_current_project(info_mode: bool) -> Result<()> {
     apply_upgrades(&db_path, &config_path)?;
     println!("{} Upgraded to v{}", "✓".green(), SCHEMA_VERSION);
 
-    // Update AGENTS.md rules if outdated
-    match update_agents_rules_if_outdated() {
-        Ok(updated_files) if !updated_files.is_empty() => {
-            println!();
-            println!("Updated AI logging rules in:");
-            for file in updated_files {
-                println!("  {} {}", "✓".green(), file);
-            }
-        }
-        Ok(_) => {} // No files needed updating
-        Err(e) => {
-            println!("  {} Could not update AGENTS.md rules: {}", "⚠".yellow(), e);
-        }
+    if let Some(hint) = agents::upgrade_hint() {
+        println!("\n{} {}", "⚠".yellow(), hint);
     }
 
     println!();
     println!(
         "{}",
-        "Tip: Run 'proj rollback --list' to see available backups".dimmed()
+        "Tip: Run 'proj rollback --schema' to undo this upgrade".dimmed()
     );
 
     Ok(())
@@ -461,78 +1206,219 @@ fn check_upgrade_compatibility(db_path: &Path) -> Result<UpgradeCompatibility> {
     }
 
     // Validate each change
-    for upgrade in &result.pending_upgrades {
-        for change in upgrade.changes {
-            let mut change_info = ChangeInfo {
-                description: change.description.to_string(),
-                risk: change.risk.to_string(),
-                status: "pending".to_string(),
+    for migration in &result.pending_upgrades {
+        for step in migration.steps {
+            let status = if step_applied(&conn, step) {
+                "already_applied"
+            } else {
+                "pending"
             };
-
-            // Check if already applied
-            if let Ok(_) = conn.execute(change.verify, []) {
-                // Verify query succeeded, change is already applied
-                change_info.status = "already_applied".to_string();
-            } else if conn.query_row(change.verify, [], |_| Ok(())).is_ok() {
-                change_info.status = "already_applied".to_string();
-            }
-
-            result.safe_changes.push(change_info);
+            result.safe_changes.push(ChangeInfo {
+                migration: format!("v{} → v{}", migration.from_version, migration.to_version),
+                description: step.description.to_string(),
+                risk: step.risk.to_string(),
+                status: status.to_string(),
+                sql: step.up,
+            });
         }
     }
 
     Ok(result)
 }
 
-/// Apply pending upgrades to the database
+/// Apply pending upgrades to the database, one transaction per migration, so a failure
+/// leaves it at the last version that applied cleanly
 fn apply_upgrades(db_path: &Path, config_path: &Path) -> Result<()> {
+    // Another process may be upgrading too; the version is read again once we hold the lock
+    let _lock = lock_for_migration(db_path)?;
     let conn = open_database(db_path)?;
+    conn.execute_batch(MIGRATIONS_SCHEMA)?;
     let current_version = get_schema_version(&conn)?.unwrap_or_else(|| "1.0".to_string());
+    let batch: i64 = conn.query_row(
+        "SELECT COALESCE(MAX(batch), 0) + 1 FROM schema_migrations",
+        [],
+        |row| row.get(0),
+    )?;
 
-    let pending = get_pending_upgrades(&current_version, SCHEMA_VERSION);
+    let result = get_pending_upgrades(&current_version, SCHEMA_VERSION)
+        .into_iter()
+        .try_for_each(|migration| {
+            let tx = write_transaction(&conn)?;
+            for step in migration.steps {
+                if !step_applied(&tx, step) {
+                    tx.execute_batch(step.up)
+                        .with_context(|| format!("Failed to apply: {}", step.description))?;
+                }
+            }
+            tx.execute(
+                "INSERT INTO schema_migrations (from_version, to_version, batch) VALUES (?1, ?2, ?3)",
+                params![migration.from_version, migration.to_version, batch],
+            )?;
+            set_schema_version(&tx, migration.to_version)?;
+            tx.commit()?;
+            Ok::<_, anyhow::Error>(())
+        })
+        .and_then(|_| set_schema_version(&conn, SCHEMA_VERSION));
 
-    for upgrade in pending {
-        for change in upgrade.changes {
-            // Check if already applied
-            let already_applied = conn.query_row(change.verify, [], |_| Ok(())).is_ok();
+    let reached = get_schema_version(&conn)?.unwrap_or(current_version);
+    write_config_version(config_path, &reached)?;
+    result
+}
+
+/// Undo migrations, newest first, until the database is at `target`. Each migration is
+/// undone in its own transaction and its schema_migrations row removed.
+pub fn apply_rollback(db_path: &Path, config_path: &Path, target: &str) -> Result<()> {
+    let _lock = lock_for_migration(db_path)?;
+    let conn = open_database(db_path)?;
+    conn.execute_batch(MIGRATIONS_SCHEMA)?;
+    let current_version = get_schema_version(&conn)?.unwrap_or_else(|| "1.0".to_string());
 
-            if !already_applied {
-                conn.execute_batch(change.sql)
-                    .with_context(|| format!("Failed to apply: {}", change.description))?;
+    let result = rollback_plan(&current_version, target)?
+        .into_iter()
+        .try_for_each(|migration| {
+            let tx = write_transaction(&conn)?;
+            for step in migration.steps.iter().rev() {
+                if !step.down.is_empty() && step_applied(&tx, step) {
+                    tx.execute_batch(step.down)
+                        .with_context(|| format!("Failed to undo: {}", step.description))?;
+                }
             }
-        }
+            tx.execute(
+                "DELETE FROM schema_migrations WHERE migration_id =
+                    (SELECT MAX(migration_id) FROM schema_migrations WHERE to_version = ?1)",
+                [migration.to_version],
+            )?;
+            set_schema_version(&tx, migration.from_version)?;
+            tx.commit()?;
+            Ok::<_, anyhow::Error>(())
+        });
+
+    let reached = get_schema_version(&conn)?.unwrap_or(current_version);
+    write_config_version(config_path, &reached)?;
+    result
+}
+
+/// Migrations to undo, newest first, to take a database from `current` back to `target`
+pub fn rollback_plan(current: &str, target: &str) -> Result<Vec<&'static Migration>> {
+    if !MIGRATIONS.iter().any(|m| m.from_version == target) {
+        return Err(ProjError::InvalidInput(format!("Unknown schema version: v{}", target)).into());
+    }
+    let current_parts: Vec<u32> = current.split('.').filter_map(|s| s.parse().ok()).collect();
+    let target_parts: Vec<u32> = target.split('.').filter_map(|s| s.parse().ok()).collect();
+    if compare_versions(&target_parts, &current_parts) != std::cmp::Ordering::Less {
+        return Err(ProjError::InvalidInput(format!(
+            "v{} isn't older than the current schema (v{})",
+            target, current
+        ))
+        .into());
     }
 
-    // Update schema version in database
-    set_schema_version(&conn, SCHEMA_VERSION)?;
+    let mut plan = get_pending_upgrades(target, current);
+    plan.reverse();
+    Ok(plan)
+}
+
+/// A migration recorded in schema_migrations
+pub struct AppliedMigration {
+    pub from_version: String,
+    pub to_version: String,
+    pub batch: i64,
+    pub applied_at: String,
+}
+
+/// Migrations applied to a database, oldest first. Empty until the first upgrade that
+/// records them; databases created by `proj init` start with no history.
+pub fn migration_history(conn: &Connection) -> Result<Vec<AppliedMigration>> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='schema_migrations')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Ok(Vec::new());
+    }
+
+    let mut stmt = conn.prepare(
+        "SELECT from_version, to_version, batch, COALESCE(applied_at, '')
+         FROM schema_migrations ORDER BY migration_id",
+    )?;
+    let rows = stmt.query_map([], |row| {
+        Ok(AppliedMigration {
+            from_version: row.get(0)?,
+            to_version: row.get(1)?,
+            batch: row.get(2)?,
+            applied_at: row.get(3)?,
+        })
+    })?;
+    Ok(rows.collect::<std::result::Result<Vec<_>, _>>()?)
+}
+
+/// Whether a migration step is already in place
+fn step_applied(conn: &Connection, step: &MigrationStep) -> bool {
+    conn.query_row(step.verify, [], |_| Ok(())).is_ok()
+}
 
-    // Update config file
+/// Print migration SQL with its source indentation removed
+pub fn print_sql(sql: &str) {
+    let sql = sql.trim();
+    if sql.is_empty() {
+        return;
+    }
+    let lines: Vec<&str> = sql.lines().collect();
+    let indent = lines
+        .iter()
+        .skip(1)
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| line.len() - line.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let mut text: Vec<&str> = lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| {
+            if i == 0 {
+                line
+            } else {
+                line.get(indent..).unwrap_or("")
+            }
+        })
+        .map(|line| line.trim_end())
+        .collect();
+    let last = text.len() - 1;
+    let terminated = format!("{};", text[last]);
+    if !sql.ends_with(';') {
+        text[last] = &terminated;
+    }
+    println!("{}", text.join("\n"));
+}
+
+/// Record the schema version in config.json
+fn write_config_version(config_path: &Path, version: &str) -> Result<()> {
     if config_path.exists() {
         let content = std::fs::read_to_string(config_path)?;
         if let Ok(mut config) = serde_json::from_str::<serde_json::Value>(&content) {
-            config["schema_version"] = serde_json::Value::String(SCHEMA_VERSION.to_string());
+            config["schema_version"] = serde_json::Value::String(version.to_string());
             let updated = serde_json::to_string_pretty(&config)?;
             std::fs::write(config_path, updated)?;
         }
     }
-
     Ok(())
 }
 
 /// Get upgrades needed between two versions
-fn get_pending_upgrades(current: &str, target: &str) -> Vec<&'static SchemaUpgrade> {
+fn get_pending_upgrades(current: &str, target: &str) -> Vec<&'static Migration> {
     let current_parts: Vec<u32> = current.split('.').filter_map(|s| s.parse().ok()).collect();
     let target_parts: Vec<u32> = target.split('.').filter_map(|s| s.parse().ok()).collect();
 
-    UPGRADE_REGISTRY
+    MIGRATIONS
         .iter()
-        .filter(|upgrade| {
-            let from_parts: Vec<u32> = upgrade
+        .filter(|migration| {
+            let from_parts: Vec<u32> = migration
                 .from_version
                 .split('.')
                 .filter_map(|s| s.parse().ok())
                 .collect();
-            let to_parts: Vec<u32> = upgrade
+            let to_parts: Vec<u32> = migration
                 .to_version
                 .split('.')
                 .filter_map(|s| s.parse().ok())
@@ -562,7 +1448,7 @@ fn load_config() -> Result<ProjectConfig> {
     let content = std::fs::read_to_string(&config_path)
         .with_context(|| "No project found. Run 'proj init' to initialize.")?;
     let config: ProjectConfig =
-        serde_json::from_str(&content).with_context(|| "Failed to parse config.json")?;
+        ProjectConfig::from_json(&content).with_context(|| "Failed to parse config.json")?;
     Ok(config)
 }
 
@@ -580,3 +1466,125 @@ fn load_registry() -> Result<Registry> {
         serde_json::from_str(&content).with_context(|| "Failed to parse registry.json")?;
     Ok(registry)
 }
+
+/// Save the global registry
+fn save_registry(registry: &Registry) -> Result<()> {
+    let registry_path = get_registry_path()?;
+    let content = serde_json::to_string_pretty(registry)?;
+    std::fs::write(&registry_path, content).with_context(|| "Failed to write registry.json")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::init_tracking_schema;
+
+    /// Every table with its columns (in name order, since migrations append them), and
+    /// every index and trigger with its SQL, whitespace collapsed
+    fn schema_of(conn: &Connection) -> Vec<String> {
+        let mut stmt = conn
+            .prepare(
+                "SELECT type, name, COALESCE(sql, '') FROM sqlite_master
+                 WHERE name NOT LIKE 'sqlite_%' ORDER BY type, name",
+            )
+            .unwrap();
+        let objects: Vec<(String, String, String)> = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap()
+            .collect::<rusqlite::Result<_>>()
+            .unwrap();
+
+        objects
+            .into_iter()
+            .map(|(kind, name, sql)| {
+                if kind == "table" && !sql.starts_with("CREATE VIRTUAL TABLE") {
+                    let mut columns: Vec<String> = conn
+                        .prepare(&format!("SELECT name, type, \"notnull\", COALESCE(dflt_value, ''), pk FROM pragma_table_info('{}')", name))
+                        .unwrap()
+                        .query_map([], |row| {
+                            Ok(format!(
+                                "{} {} notnull={} default={} pk={}",
+                                row.get::<_, String>(0)?,
+                                row.get::<_, String>(1)?,
+                                row.get::<_, i64>(2)?,
+                                row.get::<_, String>(3)?,
+                                row.get::<_, i64>(4)?
+                            ))
+                        })
+                        .unwrap()
+                        .collect::<rusqlite::Result<_>>()
+                        .unwrap();
+                    columns.sort();
+                    format!("table {} ({})", name, columns.join(", "))
+                } else {
+                    let sql = sql.split_whitespace().collect::<Vec<_>>().join(" ");
+                    format!("{} {}: {}", kind, name, sql)
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rollback_to_1_0_and_upgrade_matches_a_new_database() {
+        let dir = tempfile::tempdir().unwrap();
+        let fresh_path = dir.path().join("fresh.db");
+        let db_path = dir.path().join("tracking.db");
+        let config_path = dir.path().join("config.json");
+
+        let fresh = open_database(&fresh_path).unwrap();
+        init_tracking_schema(&fresh).unwrap();
+        let expected = schema_of(&fresh);
+
+        let conn = open_database(&db_path).unwrap();
+        init_tracking_schema(&conn).unwrap();
+        conn.execute_batch(
+            "INSERT INTO sessions (status) VALUES ('active');
+             INSERT INTO tasks (session_id, description, notes) VALUES (1, 'Parser handles quotes', 'escaped too');
+             INSERT INTO decisions (session_id, topic, decision, rationale) VALUES (1, 'api', 'Use REST', 'simple');",
+        )
+        .unwrap();
+        drop(conn);
+
+        apply_rollback(&db_path, &config_path, "1.0").unwrap();
+        let conn = open_database(&db_path).unwrap();
+        assert_eq!(get_schema_version(&conn).unwrap().as_deref(), Some("1.0"));
+        let later_objects: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM sqlite_master
+                 WHERE name IN ('tracking_fts', 'tracking_fts_docs', 'audit_log', 'attachments')
+                    OR name LIKE '%_audit_%' OR name LIKE '%_fts_%'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(later_objects, 0);
+        drop(conn);
+
+        apply_upgrades(&db_path, &config_path).unwrap();
+        let conn = open_database(&db_path).unwrap();
+        assert_eq!(
+            get_schema_version(&conn).unwrap().as_deref(),
+            Some(SCHEMA_VERSION)
+        );
+        let actual = schema_of(&conn);
+        let missing: Vec<_> = expected.iter().filter(|o| !actual.contains(o)).collect();
+        let extra: Vec<_> = actual.iter().filter(|o| !expected.contains(o)).collect();
+        assert!(
+            missing.is_empty() && extra.is_empty(),
+            "upgraded schema differs from a new one\nmissing: {:#?}\nextra: {:#?}",
+            missing,
+            extra
+        );
+
+        // The records made it through, and the search index was rebuilt with them
+        let hits: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM tracking_fts WHERE tracking_fts MATCH 'parser OR rest'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(hits, 2);
+    }
+}
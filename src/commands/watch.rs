@@ -0,0 +1,258 @@
+// Watch command - stream tracking changes as they happen (for editor integrations)
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use colored::Colorize;
+use rusqlite::types::ValueRef;
+use rusqlite::Connection;
+use serde_json::{json, Map, Value};
+
+use crate::database::open_database;
+use crate::paths::get_tracking_db_path;
+use crate::text::truncate;
+
+/// A watched table: event prefix, table, primary key, and the column used as a label
+struct Watched {
+    kind: &'static str,
+    table: &'static str,
+    key: &'static str,
+    label: &'static str,
+}
+
+const WATCHED: [Watched; 4] = [
+    Watched {
+        kind: "session",
+        table: "sessions",
+        key: "session_id",
+        label: "summary",
+    },
+    Watched {
+        kind: "task",
+        table: "tasks",
+        key: "task_id",
+        label: "description",
+    },
+    Watched {
+        kind: "decision",
+        table: "decisions",
+        key: "decision_id",
+        label: "decision",
+    },
+    Watched {
+        kind: "blocker",
+        table: "blockers",
+        key: "blocker_id",
+        label: "description",
+    },
+];
+
+/// Rows of one table keyed by primary key
+type Rows = BTreeMap<i64, Map<String, Value>>;
+
+/// A single change, emitted as one line of output
+struct Change {
+    event: String,
+    id: i64,
+    changes: Vec<String>,
+    data: Value,
+}
+
+pub fn run(json: bool, interval_ms: u64) -> Result<()> {
+    let db_path = get_tracking_db_path()?;
+    let conn = open_database(&db_path)
+        .with_context(|| format!("Failed to open tracking database at {:?}", db_path))?;
+
+    let mut snapshot = WATCHED
+        .iter()
+        .map(|w| load_rows(&conn, w))
+        .collect::<Result<Vec<_>>>()?;
+    let mut version = data_version(&conn)?;
+
+    let ready = json!({
+        "event": "ready",
+        "at": Utc::now().to_rfc3339(),
+        "database": db_path.to_string_lossy(),
+    });
+    if json {
+        if !emit(&ready.to_string()) {
+            return Ok(());
+        }
+    } else {
+        eprintln!(
+            "Watching {} for changes (Ctrl-C to stop)",
+            db_path.display().to_string().cyan()
+        );
+    }
+
+    let interval = Duration::from_millis(interval_ms.max(50));
+    loop {
+        thread::sleep(interval);
+
+        // data_version only moves when another connection commits, so idle polls are cheap
+        let current = data_version(&conn)?;
+        if current == version {
+            continue;
+        }
+        version = current;
+
+        for (watched, rows) in WATCHED.iter().zip(snapshot.iter_mut()) {
+            let fresh = load_rows(&conn, watched)?;
+            for change in diff(watched, rows, &fresh) {
+                let line = if json {
+                    json!({
+                        "event": change.event,
+                        "id": change.id,
+                        "uuid": change.data.get("uuid").cloned().unwrap_or(Value::Null),
+                        "changes": change.changes,
+                        "data": change.data,
+                        "at": Utc::now().to_rfc3339(),
+                    })
+                    .to_string()
+                } else {
+                    format_change(watched, &change)
+                };
+                // Stop quietly once the reader goes away
+                if !emit(&line) {
+                    return Ok(());
+                }
+            }
+            *rows = fresh;
+        }
+    }
+}
+
+/// Write one line and flush, returning false if stdout is closed
+fn emit(line: &str) -> bool {
+    let mut stdout = io::stdout().lock();
+    writeln!(stdout, "{}", line).is_ok() && stdout.flush().is_ok()
+}
+
+fn data_version(conn: &Connection) -> Result<i64> {
+    Ok(conn.query_row("PRAGMA data_version", [], |row| row.get(0))?)
+}
+
+fn load_rows(conn: &Connection, watched: &Watched) -> Result<Rows> {
+    let mut stmt = conn.prepare(&format!("SELECT * FROM {}", watched.table))?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+    let mut rows = Rows::new();
+    let mut query = stmt.query([])?;
+    while let Some(row) = query.next()? {
+        let mut object = Map::new();
+        for (i, column) in columns.iter().enumerate() {
+            let value = match row.get_ref(i)? {
+                ValueRef::Null => Value::Null,
+                ValueRef::Integer(n) => json!(n),
+                ValueRef::Real(f) => json!(f),
+                ValueRef::Text(t) => json!(String::from_utf8_lossy(t)),
+                ValueRef::Blob(_) => Value::Null,
+            };
+            object.insert(column.clone(), value);
+        }
+        let Some(id) = object.get(watched.key).and_then(Value::as_i64) else {
+            continue;
+        };
+        rows.insert(id, object);
+    }
+    Ok(rows)
+}
+
+/// Compare two snapshots of a table, in id order
+fn diff(watched: &Watched, old: &Rows, new: &Rows) -> Vec<Change> {
+    let mut changes = Vec::new();
+
+    for (id, row) in new {
+        let (event, changed) = match old.get(id) {
+            None if watched.kind == "session" => ("started", Vec::new()),
+            None => ("created", Vec::new()),
+            Some(before) => {
+                let changed: Vec<String> = row
+                    .iter()
+                    .filter(|(column, value)| before.get(*column) != Some(*value))
+                    .map(|(column, _)| column.clone())
+                    .collect();
+                if changed.is_empty() {
+                    continue;
+                }
+                (status_event(watched, before, row), changed)
+            }
+        };
+        changes.push(Change {
+            event: format!("{}.{}", watched.kind, event),
+            id: *id,
+            changes: changed,
+            data: Value::Object(row.clone()),
+        });
+    }
+
+    for (id, row) in old {
+        if !new.contains_key(id) {
+            changes.push(Change {
+                event: format!("{}.deleted", watched.kind),
+                id: *id,
+                changes: Vec::new(),
+                data: Value::Object(row.clone()),
+            });
+        }
+    }
+
+    changes
+}
+
+/// Name status transitions that editors care about; everything else is "updated"
+fn status_event(
+    watched: &Watched,
+    before: &Map<String, Value>,
+    after: &Map<String, Value>,
+) -> &'static str {
+    let status = |row: &Map<String, Value>| {
+        row.get("status")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string()
+    };
+    let (was, now) = (status(before), status(after));
+    if was == now {
+        return "updated";
+    }
+
+    match (watched.kind, now.as_str()) {
+        ("session", "completed") | ("session", "abandoned") => "ended",
+        ("session", "paused") => "paused",
+        ("session", "active") => "resumed",
+        ("task", "completed") => "completed",
+        ("blocker", "resolved") => "resolved",
+        _ => "updated",
+    }
+}
+
+fn format_change(watched: &Watched, change: &Change) -> String {
+    let label = change
+        .data
+        .get(watched.label)
+        .and_then(Value::as_str)
+        .or_else(|| change.data.get("name").and_then(Value::as_str))
+        .unwrap_or("");
+    let mut line = format!(
+        "{} {} #{}",
+        Utc::now().format("%H:%M:%S").to_string().dimmed(),
+        change.event.bold(),
+        change.id
+    );
+    if !change.changes.is_empty() {
+        line.push_str(
+            &format!(" ({})", change.changes.join(", "))
+                .dimmed()
+                .to_string(),
+        );
+    }
+    if !label.is_empty() {
+        line.push_str(&format!(": {}", truncate(label, 60)));
+    }
+    line
+}
@@ -7,35 +7,68 @@ use colored::Colorize;
 use dialoguer::Confirm;
 
 use crate::config::ProjectConfig;
+use crate::git;
 use crate::paths::get_project_root;
+use crate::porcelain;
+
+/// Fill `{placeholder}`s in a commit message template.
+/// Lines whose placeholders all came out empty (e.g., "Tasks: {tasks}" with no tasks) are dropped.
+pub fn render_template(template: &str, values: &[(&str, String)]) -> String {
+    let mut lines = Vec::new();
+    for line in template.lines() {
+        let mut rendered = line.to_string();
+        let mut had_placeholder = false;
+        let mut all_empty = true;
+        for (key, value) in values {
+            let placeholder = format!("{{{}}}", key);
+            if rendered.contains(&placeholder) {
+                had_placeholder = true;
+                all_empty &= value.is_empty();
+                rendered = rendered.replace(&placeholder, value);
+            }
+        }
+        if had_placeholder && all_empty {
+            continue;
+        }
+        lines.push(rendered.trim_end().to_string());
+    }
+
+    // Keep a blank line between subject and body, but no trailing blank lines
+    while lines.last().is_some_and(|l| l.is_empty()) {
+        lines.pop();
+    }
+    lines.join("\n")
+}
 
 /// Perform an auto-commit with the given message.
 /// Returns Ok(true) if a commit was made, Ok(false) if skipped.
 pub fn auto_commit(message: &str, config: &ProjectConfig) -> Result<bool> {
     // Check if we're in a git repo
     let project_root = get_project_root()?;
-    if !project_root.join(".git").exists() {
+    if !git::is_repo(&project_root) {
         return Ok(false);
     }
 
     // Check if there are any changes to commit
-    let status_output = Command::new("git")
-        .args(["status", "--porcelain"])
-        .current_dir(&project_root)
-        .output()
-        .with_context(|| "Failed to run git status")?;
+    let status_output = git::run(
+        Command::new("git")
+            .args(["status", "--porcelain"])
+            .args(git::path_scope(&project_root))
+            .current_dir(&project_root),
+    )
+    .with_context(|| "Failed to run git status")?;
 
     let has_changes = !status_output.stdout.is_empty();
 
     if !has_changes {
-        println!("  {} No changes to commit", "ℹ".blue());
+        porcelain::note(format!("  {} No changes to commit", "ℹ".blue()));
         return Ok(false);
     }
 
     // Determine if we should commit
     let should_commit = match config.auto_commit_mode.as_str() {
         "auto" => true,
-        "prompt" | _ => {
+        _ => {
             if atty::is(atty::Stream::Stdin) {
                 Confirm::new()
                     .with_prompt("Commit changes?")
@@ -43,7 +76,10 @@ pub fn auto_commit(message: &str, config: &ProjectConfig) -> Result<bool> {
                     .interact()
                     .unwrap_or(false)
             } else {
-                println!("  {} Skipping commit (non-interactive)", "ℹ".blue());
+                porcelain::note(format!(
+                    "  {} Skipping commit (non-interactive)",
+                    "ℹ".blue()
+                ));
                 false
             }
         }
@@ -54,11 +90,13 @@ pub fn auto_commit(message: &str, config: &ProjectConfig) -> Result<bool> {
     }
 
     // Stage all changes
-    let add_result = Command::new("git")
-        .args(["add", "-A"])
-        .current_dir(&project_root)
-        .output()
-        .with_context(|| "Failed to run git add")?;
+    let add_result = git::run(
+        Command::new("git")
+            .args(["add", "-A"])
+            .args(git::path_scope(&project_root))
+            .current_dir(&project_root),
+    )
+    .with_context(|| "Failed to run git add")?;
 
     if !add_result.status.success() {
         bail!(
@@ -68,21 +106,27 @@ pub fn auto_commit(message: &str, config: &ProjectConfig) -> Result<bool> {
     }
 
     // Create commit
-    let commit_result = Command::new("git")
-        .args(["commit", "-m", message])
-        .current_dir(&project_root)
-        .output()
-        .with_context(|| "Failed to run git commit")?;
+    let commit_result = git::run(
+        Command::new("git")
+            .args(["commit", "-m", message])
+            .args(git::path_scope(&project_root))
+            .current_dir(&project_root),
+    )
+    .with_context(|| "Failed to run git commit")?;
 
     if !commit_result.status.success() {
         let stderr = String::from_utf8_lossy(&commit_result.stderr);
         if stderr.contains("nothing to commit") {
-            println!("  {} No changes to commit", "ℹ".blue());
+            porcelain::note(format!("  {} No changes to commit", "ℹ".blue()));
             return Ok(false);
         }
         bail!("git commit failed: {}", stderr);
     }
 
-    println!("  {} Committed: {}", "✓".green(), message);
+    porcelain::note(format!(
+        "  {} Committed: {}",
+        "✓".green(),
+        message.lines().next().unwrap_or_default()
+    ));
     Ok(true)
 }
@@ -0,0 +1,126 @@
+// Backup encryption - XChaCha20-Poly1305 with the key kept in the OS keychain
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use chacha20poly1305::aead::{Aead, AeadCore, KeyInit, OsRng};
+use chacha20poly1305::{Key, XChaCha20Poly1305, XNonce};
+use colored::Colorize;
+
+use crate::encoding::{base64_decode, base64_encode};
+
+/// File header identifying an encrypted proj backup (format version 1)
+const MAGIC: &[u8; 8] = b"PROJENC1";
+const NONCE_LEN: usize = 24;
+
+const KEYCHAIN_SERVICE: &str = "proj";
+const KEYCHAIN_USER: &str = "backup-key";
+
+/// Key override for machines without a usable keychain (CI, headless servers)
+const KEY_ENV: &str = "PROJ_BACKUP_KEY";
+
+/// Suffix added to encrypted backup files
+pub const ENCRYPTED_SUFFIX: &str = ".enc";
+
+/// Make sure a backup key is available (creating one if needed) before any plaintext is written
+pub fn ensure_key() -> Result<()> {
+    backup_key(true).map(|_| ())
+}
+
+/// Encrypt a file to `<path>.enc` and remove the plaintext; returns the new path
+pub fn encrypt_file(path: &Path) -> Result<PathBuf> {
+    let key = backup_key(true)?;
+    let plaintext =
+        std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+    let ciphertext = cipher
+        .encrypt(&nonce, plaintext.as_slice())
+        .map_err(|_| anyhow!("Failed to encrypt {}", path.display()))?;
+
+    let mut out = Vec::with_capacity(MAGIC.len() + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+
+    let encrypted = PathBuf::from(format!("{}{}", path.display(), ENCRYPTED_SUFFIX));
+    std::fs::write(&encrypted, out)
+        .with_context(|| format!("Failed to write {}", encrypted.display()))?;
+    std::fs::remove_file(path)
+        .with_context(|| format!("Failed to remove plaintext {}", path.display()))?;
+
+    Ok(encrypted)
+}
+
+/// Decrypt an encrypted backup into `output`
+pub fn decrypt_file(path: &Path, output: &Path) -> Result<()> {
+    let data = std::fs::read(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    if data.len() < MAGIC.len() + NONCE_LEN || &data[..MAGIC.len()] != MAGIC {
+        bail!("{} is not an encrypted proj backup", path.display());
+    }
+
+    let key = backup_key(false)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key));
+    let nonce = XNonce::from_slice(&data[MAGIC.len()..MAGIC.len() + NONCE_LEN]);
+    let plaintext = cipher
+        .decrypt(nonce, &data[MAGIC.len() + NONCE_LEN..])
+        .map_err(|_| {
+            anyhow!(
+                "Could not decrypt {}: wrong key or corrupted file",
+                path.display()
+            )
+        })?;
+
+    std::fs::write(output, plaintext)
+        .with_context(|| format!("Failed to write {}", output.display()))?;
+    Ok(())
+}
+
+/// The backup key, base64-encoded, for safekeeping outside the keychain
+pub fn export_key() -> Result<String> {
+    Ok(base64_encode(&backup_key(false)?))
+}
+
+/// Load the backup key from $PROJ_BACKUP_KEY or the keychain, creating one if asked
+fn backup_key(create: bool) -> Result<[u8; 32]> {
+    if let Ok(encoded) = std::env::var(KEY_ENV) {
+        return decode_key(&encoded).with_context(|| format!("Invalid {}", KEY_ENV));
+    }
+
+    let entry = keyring::Entry::new(KEYCHAIN_SERVICE, KEYCHAIN_USER)
+        .with_context(|| format!("OS keychain unavailable. Set {} instead.", KEY_ENV))?;
+
+    match entry.get_password() {
+        Ok(encoded) => decode_key(&encoded).with_context(|| "Invalid backup key in keychain"),
+        Err(keyring::Error::NoEntry) if create => {
+            let key = XChaCha20Poly1305::generate_key(&mut OsRng);
+            entry.set_password(&base64_encode(&key)).with_context(|| {
+                format!(
+                    "Failed to store backup key in the OS keychain. Set {} instead.",
+                    KEY_ENV
+                )
+            })?;
+            eprintln!(
+                "{} Created a backup encryption key in the OS keychain. Save a copy with 'proj backup --show-key'; encrypted backups can't be restored without it.",
+                "ℹ".blue()
+            );
+            Ok(key.into())
+        }
+        Err(keyring::Error::NoEntry) => {
+            bail!("No backup encryption key found. Encrypted backups need the key they were made with (keychain or {}).", KEY_ENV)
+        }
+        Err(e) => Err(anyhow!(
+            "Failed to read backup key from the OS keychain ({}). Set {} instead.",
+            e,
+            KEY_ENV
+        )),
+    }
+}
+
+fn decode_key(encoded: &str) -> Result<[u8; 32]> {
+    let bytes = base64_decode(encoded.trim())?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("Backup key must be 32 bytes"))
+}
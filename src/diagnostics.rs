@@ -0,0 +1,169 @@
+// Diagnostics - opt-in logging of database, git, and network activity (--debug / --trace)
+
+use std::fs::{self, File, OpenOptions};
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use atty::Stream;
+use chrono::{Local, NaiveDate};
+use tracing::debug;
+use tracing::level_filters::LevelFilter;
+
+use crate::paths::{ensure_dir, get_logs_dir};
+
+/// Daily log files older than this are removed when a new one is opened
+const LOG_RETENTION_DAYS: i64 = 7;
+
+/// Install the logger. Nothing is installed (and nothing logged) unless
+/// --debug, --trace, --log-file, or PROJ_LOG asks for it.
+pub fn init(debug: bool, trace: bool, log_file: bool, color: bool) -> Result<()> {
+    let level = if trace {
+        LevelFilter::TRACE
+    } else if debug {
+        LevelFilter::DEBUG
+    } else {
+        match std::env::var("PROJ_LOG")
+            .ok()
+            .and_then(|v| v.parse::<LevelFilter>().ok())
+        {
+            Some(level) => level,
+            None if log_file => LevelFilter::DEBUG,
+            None => return Ok(()),
+        }
+    };
+
+    let builder = tracing_subscriber::fmt().with_max_level(level);
+    if log_file {
+        let file = open_log_file()?;
+        builder
+            .with_ansi(false)
+            .with_writer(Mutex::new(file))
+            .init();
+    } else {
+        // Time since start makes it obvious where a slow command spends its time
+        builder
+            .with_timer(tracing_subscriber::fmt::time::uptime())
+            .with_ansi(color && atty::is(Stream::Stderr))
+            .with_writer(io::stderr)
+            .init();
+    }
+    Ok(())
+}
+
+/// Open today's log file, pruning old ones
+fn open_log_file() -> Result<File> {
+    let dir = get_logs_dir()?;
+    ensure_dir(&dir)?;
+
+    // Keep logs out of auto-commits
+    let ignore = dir.join(".gitignore");
+    if !ignore.exists() {
+        fs::write(&ignore, "*\n")?;
+    }
+
+    let today = Local::now().date_naive();
+    prune_logs(&dir, today);
+
+    let path = dir.join(format!("proj-{}.log", today.format("%Y-%m-%d")));
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .with_context(|| format!("Failed to open log file {}", path.display()))
+}
+
+fn prune_logs(dir: &Path, today: NaiveDate) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let name = entry.file_name().to_string_lossy().to_string();
+        let date = name
+            .strip_prefix("proj-")
+            .and_then(|n| n.strip_suffix(".log"))
+            .and_then(|d| NaiveDate::parse_from_str(d, "%Y-%m-%d").ok());
+        if let Some(date) = date {
+            if (today - date).num_days() >= LOG_RETENTION_DAYS {
+                let _ = fs::remove_file(entry.path());
+            }
+        }
+    }
+}
+
+/// Command logging lives with the database layer in proj-core
+pub use proj_core::diagnostics::output;
+
+/// ureq middleware logging each request's method, URL, status, and timing.
+/// Query strings are dropped since some APIs put credentials there.
+///
+/// In offline mode it also refuses every request to another host before connecting,
+/// so nothing waits on a timeout; loopback stays allowed for local services (Ollama).
+pub struct HttpLog {
+    pub offline: bool,
+}
+
+impl ureq::Middleware for HttpLog {
+    fn handle(
+        &self,
+        request: ureq::Request,
+        next: ureq::MiddlewareNext,
+    ) -> std::result::Result<ureq::Response, ureq::Error> {
+        let method = request.method().to_string();
+        let url = request
+            .url()
+            .split('?')
+            .next()
+            .unwrap_or_default()
+            .to_string();
+        if self.offline && !is_loopback(&request) {
+            debug!(target: "proj::http", "{} {} refused: offline mode", method, url);
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "offline mode is on (offline in config.json, or PROJ_OFFLINE)",
+            )
+            .into());
+        }
+        let start = Instant::now();
+
+        let result = next.handle(request);
+
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        match &result {
+            Ok(response) => debug!(
+                target: "proj::http",
+                "{} {} -> {} in {:.1}ms",
+                method,
+                url,
+                response.status(),
+                ms
+            ),
+            Err(ureq::Error::Status(code, _)) => debug!(
+                target: "proj::http",
+                "{} {} -> {} in {:.1}ms",
+                method,
+                url,
+                code,
+                ms
+            ),
+            Err(e) => debug!(
+                target: "proj::http",
+                "{} {} failed after {:.1}ms: {}",
+                method,
+                url,
+                ms,
+                e
+            ),
+        }
+        result
+    }
+}
+
+/// Whether a request goes to this machine
+fn is_loopback(request: &ureq::Request) -> bool {
+    request
+        .request_url()
+        .is_ok_and(|url| crate::http::is_loopback_host(url.host()))
+}
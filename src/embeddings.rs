@@ -0,0 +1,311 @@
+// Embeddings - vectors for semantic search from a local model (Ollama) or an OpenAI-compatible API
+
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use rusqlite::Connection;
+use sha2::{Digest, Sha256};
+
+use crate::config::EmbeddingConfig;
+use crate::database::get_schema_version;
+use crate::docs_db;
+use crate::encoding::hex;
+use crate::error::ProjError;
+use crate::http;
+
+/// Texts sent per request
+const BATCH_SIZE: usize = 32;
+
+/// Where embeddings are computed
+pub enum Provider {
+    /// Local model served by Ollama
+    Ollama { base_url: String, model: String },
+    /// Any endpoint implementing OpenAI's /embeddings API
+    OpenAi {
+        base_url: String,
+        model: String,
+        api_key: Option<String>,
+    },
+}
+
+impl Provider {
+    pub fn from_config(config: &EmbeddingConfig) -> Result<Self> {
+        let base_url = |default: &str| {
+            config
+                .base_url
+                .as_deref()
+                .unwrap_or(default)
+                .trim_end_matches('/')
+                .to_string()
+        };
+        match config.provider.as_str() {
+            "ollama" => Ok(Provider::Ollama {
+                base_url: base_url("http://localhost:11434"),
+                model: config.model.clone(),
+            }),
+            "openai" => Ok(Provider::OpenAi {
+                base_url: base_url("https://api.openai.com/v1"),
+                model: config.model.clone(),
+                api_key: config
+                    .api_key
+                    .clone()
+                    .or_else(|| std::env::var("OPENAI_API_KEY").ok()),
+            }),
+            other => bail!(
+                "Unknown embedding provider '{}'. Use 'ollama' or 'openai'.",
+                other
+            ),
+        }
+    }
+
+    /// Load the provider from ~/.proj/embeddings.json
+    pub fn load() -> Result<Self> {
+        let config = EmbeddingConfig::load()?.ok_or_else(|| {
+            anyhow!(
+                "No embedding provider configured. Create ~/.proj/embeddings.json, e.g. {{\"provider\": \"ollama\", \"model\": \"nomic-embed-text\"}}"
+            )
+        })?;
+        Self::from_config(&config)
+    }
+
+    /// Identifier stored with each vector; vectors from different models aren't comparable
+    pub fn model_id(&self) -> String {
+        match self {
+            Provider::Ollama { model, .. } => format!("ollama:{}", model),
+            Provider::OpenAi { model, .. } => format!("openai:{}", model),
+        }
+    }
+
+    /// Embed texts, one vector per text, in order
+    pub fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>> {
+        let mut vectors = Vec::with_capacity(texts.len());
+        for batch in texts.chunks(BATCH_SIZE) {
+            let batch_vectors = match self {
+                Provider::Ollama { base_url, model } => {
+                    let body = serde_json::json!({ "model": model, "input": batch });
+                    let url = format!("{}/api/embed", base_url);
+                    let response = post(agent(&url).post(&url), body)?;
+                    parse_vectors(response.get("embeddings"))?
+                }
+                Provider::OpenAi {
+                    base_url,
+                    model,
+                    api_key,
+                } => {
+                    let url = format!("{}/embeddings", base_url);
+                    let mut request = agent(&url).post(&url);
+                    if let Some(key) = api_key {
+                        request = request.set("Authorization", &format!("Bearer {}", key));
+                    }
+                    let body = serde_json::json!({ "model": model, "input": batch });
+                    let response = post(request, body)?;
+                    let data = response
+                        .get("data")
+                        .and_then(|d| d.as_array())
+                        .ok_or_else(|| anyhow!("Embedding response has no 'data'"))?;
+                    let embeddings: Vec<serde_json::Value> = data
+                        .iter()
+                        .filter_map(|d| d.get("embedding").cloned())
+                        .collect();
+                    parse_vectors(Some(&serde_json::Value::Array(embeddings)))?
+                }
+            };
+            if batch_vectors.len() != batch.len() {
+                bail!(
+                    "Embedding provider returned {} vectors for {} texts",
+                    batch_vectors.len(),
+                    batch.len()
+                );
+            }
+            vectors.extend(batch_vectors);
+        }
+        Ok(vectors)
+    }
+}
+
+fn agent(url: &str) -> ureq::Agent {
+    http::agent(url, Duration::from_secs(120))
+}
+
+fn post(request: ureq::Request, body: serde_json::Value) -> Result<serde_json::Value> {
+    match request.send_json(body) {
+        Ok(response) => response
+            .into_json()
+            .with_context(|| "Invalid JSON from embedding provider"),
+        Err(ureq::Error::Status(code, response)) => {
+            let detail = response.into_string().unwrap_or_default();
+            bail!("Embedding request failed: HTTP {} {}", code, detail.trim())
+        }
+        Err(e) => Err(anyhow!("Embedding request failed: {}", e)),
+    }
+}
+
+fn parse_vectors(value: Option<&serde_json::Value>) -> Result<Vec<Vec<f32>>> {
+    let rows = value
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| anyhow!("Embedding response has no vectors"))?;
+    rows.iter()
+        .map(|row| {
+            row.as_array()
+                .ok_or_else(|| anyhow!("Embedding vector is not an array"))?
+                .iter()
+                .map(|x| {
+                    x.as_f64()
+                        .map(|f| f as f32)
+                        .ok_or_else(|| anyhow!("Embedding vector has a non-number"))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// An item to embed; record_id points into table_name
+pub struct Embeddable {
+    pub table_name: &'static str,
+    pub record_id: i64,
+    pub text: String,
+}
+
+/// Active decisions and notes, plus the docs database's sections when there is one
+pub fn embeddable_items(conn: &Connection, root: &Path) -> Result<Vec<Embeddable>> {
+    let mut items = Vec::new();
+
+    let mut stmt = conn.prepare(
+        "SELECT decision_id, topic, decision, rationale FROM decisions WHERE status = 'active' ORDER BY decision_id",
+    )?;
+    let decisions = stmt.query_map([], |row| {
+        let topic: String = row.get(1)?;
+        let decision: String = row.get(2)?;
+        let rationale: Option<String> = row.get(3)?;
+        let mut text = format!("{}: {}", topic, decision);
+        if let Some(r) = rationale {
+            text.push_str(&format!("\n{}", r));
+        }
+        Ok(Embeddable {
+            table_name: "decisions",
+            record_id: row.get(0)?,
+            text,
+        })
+    })?;
+    items.extend(decisions.collect::<Result<Vec<_>, _>>()?);
+
+    let mut stmt = conn.prepare(
+        "SELECT note_id, title, content FROM context_notes WHERE status = 'active' ORDER BY note_id",
+    )?;
+    let notes = stmt.query_map([], |row| {
+        Ok(Embeddable {
+            table_name: "context_notes",
+            record_id: row.get(0)?,
+            text: format!("{}: {}", row.get::<_, String>(1)?, row.get::<_, String>(2)?),
+        })
+    })?;
+    items.extend(notes.collect::<Result<Vec<_>, _>>()?);
+
+    if let Some(docs_path) = docs_db::find_docs_db(root) {
+        let docs = docs_db::open_docs_db(&docs_path)?;
+        let mut stmt = docs.prepare("SELECT id, title, content FROM sections ORDER BY id")?;
+        let sections = stmt.query_map([], |row| {
+            Ok(Embeddable {
+                table_name: "sections",
+                record_id: row.get(0)?,
+                text: format!(
+                    "{}\n{}",
+                    row.get::<_, String>(1)?,
+                    row.get::<_, Option<String>>(2)?.unwrap_or_default()
+                ),
+            })
+        })?;
+        items.extend(sections.collect::<Result<Vec<_>, _>>()?);
+    }
+
+    Ok(items)
+}
+
+/// Fail with an upgrade hint if the tracking database predates embeddings
+pub fn require_table(conn: &Connection) -> Result<()> {
+    let exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='embeddings')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !exists {
+        return Err(ProjError::SchemaMismatch {
+            found: get_schema_version(conn)?.unwrap_or_else(|| "1.0".to_string()),
+            required: "1.12".to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// What each stored vector was computed from: (table_name, record_id) -> (model, content_hash)
+pub fn stored(conn: &Connection) -> Result<HashMap<(String, i64), (String, String)>> {
+    let mut stmt =
+        conn.prepare("SELECT table_name, record_id, model, content_hash FROM embeddings")?;
+    let stored = stmt
+        .query_map([], |row| {
+            Ok((
+                (row.get::<_, String>(0)?, row.get::<_, i64>(1)?),
+                (row.get::<_, String>(2)?, row.get::<_, String>(3)?),
+            ))
+        })?
+        .collect::<Result<_, _>>()?;
+    Ok(stored)
+}
+
+/// Stored vectors for a model most similar to `query`: (table_name, record_id, similarity)
+pub fn nearest(
+    conn: &Connection,
+    model_id: &str,
+    query: &[f32],
+) -> Result<Vec<(String, i64, f32)>> {
+    let mut stmt =
+        conn.prepare("SELECT table_name, record_id, vector FROM embeddings WHERE model = ?1")?;
+    let rows = stmt.query_map([model_id], |row| {
+        Ok((
+            row.get::<_, String>(0)?,
+            row.get::<_, i64>(1)?,
+            row.get::<_, Vec<u8>>(2)?,
+        ))
+    })?;
+
+    let mut scored = Vec::new();
+    for row in rows {
+        let (table_name, record_id, blob) = row?;
+        scored.push((table_name, record_id, cosine(query, &from_blob(&blob))));
+    }
+    scored.sort_by(|a, b| b.2.partial_cmp(&a.2).unwrap_or(std::cmp::Ordering::Equal));
+    Ok(scored)
+}
+
+/// Hash of the embedded text, to tell when an item needs re-embedding
+pub fn content_hash(text: &str) -> String {
+    hex(&Sha256::digest(text.as_bytes()))
+}
+
+/// Vectors are stored as little-endian f32s
+pub fn to_blob(vector: &[f32]) -> Vec<u8> {
+    vector.iter().flat_map(|x| x.to_le_bytes()).collect()
+}
+
+fn from_blob(blob: &[u8]) -> Vec<f32> {
+    blob.chunks_exact(4)
+        .map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .collect()
+}
+
+fn cosine(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        0.0
+    } else {
+        dot / (norm_a * norm_b)
+    }
+}
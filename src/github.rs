@@ -0,0 +1,206 @@
+// GitHub - issues API client for `proj task sync github` and `proj ci report --post`
+//
+// Token comes from ~/.proj/integrations.json, then `gh auth token`, then $GITHUB_TOKEN.
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::config::IntegrationsConfig;
+use crate::http;
+
+/// The parts of an issue that sync cares about
+pub struct Issue {
+    pub number: i64,
+    /// "open" or "closed"
+    pub state: String,
+    /// "completed", "not_planned", or "reopened" (None for issues never closed)
+    pub state_reason: Option<String>,
+    pub url: String,
+}
+
+pub struct GitHub {
+    api_url: String,
+    token: String,
+    /// owner/name
+    pub repo: String,
+}
+
+impl GitHub {
+    /// Client for `repo` (owner/name), or the repo behind the project's origin remote
+    pub fn connect(project_root: &Path, repo: Option<&str>) -> Result<Self> {
+        let config = IntegrationsConfig::load()?.github.unwrap_or_default();
+
+        let repo = match repo {
+            Some(r) => r.trim_matches('/').to_string(),
+            None => crate::git::remote_url(project_root, "origin")
+                .and_then(|url| repo_from_url(&url))
+                .ok_or_else(|| {
+                    anyhow!("Couldn't find a GitHub repo from the origin remote. Pass --repo owner/name.")
+                })?,
+        };
+        if repo.split('/').filter(|p| !p.is_empty()).count() != 2 {
+            bail!("Invalid repo '{}'. Use owner/name.", repo);
+        }
+
+        let token = config
+            .token
+            .or_else(gh_cli_token)
+            .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+            .filter(|t| !t.trim().is_empty())
+            .ok_or_else(|| {
+                anyhow!("No GitHub token. Run 'gh auth login', set $GITHUB_TOKEN, or add a token to ~/.proj/integrations.json.")
+            })?;
+
+        Ok(Self {
+            api_url: config
+                .api_url
+                .as_deref()
+                .unwrap_or("https://api.github.com")
+                .trim_end_matches('/')
+                .to_string(),
+            token,
+            repo,
+        })
+    }
+
+    pub fn create_issue(&self, title: &str, body: &str) -> Result<Issue> {
+        let url = format!("{}/repos/{}/issues", self.api_url, self.repo);
+        let response = self.send(
+            self.request("POST", &url),
+            Some(serde_json::json!({ "title": title, "body": body })),
+        )?;
+        parse_issue(&response)
+    }
+
+    pub fn get_issue(&self, number: i64) -> Result<Issue> {
+        let url = format!("{}/repos/{}/issues/{}", self.api_url, self.repo, number);
+        let response = self.send(self.request("GET", &url), None)?;
+        parse_issue(&response)
+    }
+
+    /// Open or close an issue; `reason` is "completed" or "not_planned" when closing
+    pub fn set_state(&self, number: i64, state: &str, reason: Option<&str>) -> Result<Issue> {
+        let url = format!("{}/repos/{}/issues/{}", self.api_url, self.repo, number);
+        let mut body = serde_json::json!({ "state": state });
+        if let Some(reason) = reason {
+            body["state_reason"] = serde_json::json!(reason);
+        }
+        let response = self.send(self.request("PATCH", &url), Some(body))?;
+        parse_issue(&response)
+    }
+
+    /// Comment on an issue or pull request, editing the earlier comment that contains
+    /// `marker` instead if there is one. Returns the comment's URL.
+    pub fn upsert_comment(&self, number: i64, marker: &str, body: &str) -> Result<String> {
+        let mut existing = None;
+        for page in 1.. {
+            let url = format!(
+                "{}/repos/{}/issues/{}/comments?per_page=100&page={}",
+                self.api_url, self.repo, number, page
+            );
+            let response = self.send(self.request("GET", &url), None)?;
+            let comments = response.as_array().cloned().unwrap_or_default();
+            existing = comments
+                .iter()
+                .filter(|c| {
+                    c.get("body")
+                        .and_then(|b| b.as_str())
+                        .is_some_and(|b| b.contains(marker))
+                })
+                .find_map(|c| c.get("id").and_then(|id| id.as_i64()));
+            if existing.is_some() || comments.len() < 100 {
+                break;
+            }
+        }
+
+        let body = serde_json::json!({ "body": body });
+        let response = match existing {
+            Some(id) => {
+                let url = format!(
+                    "{}/repos/{}/issues/comments/{}",
+                    self.api_url, self.repo, id
+                );
+                self.send(self.request("PATCH", &url), Some(body))?
+            }
+            None => {
+                let url = format!(
+                    "{}/repos/{}/issues/{}/comments",
+                    self.api_url, self.repo, number
+                );
+                self.send(self.request("POST", &url), Some(body))?
+            }
+        };
+        Ok(response
+            .get("html_url")
+            .and_then(|u| u.as_str())
+            .unwrap_or_default()
+            .to_string())
+    }
+
+    fn request(&self, method: &str, url: &str) -> ureq::Request {
+        http::agent(url, Duration::from_secs(30))
+            .request(method, url)
+            .set("Accept", "application/vnd.github+json")
+            .set("Authorization", &format!("Bearer {}", self.token))
+            .set("User-Agent", &format!("proj/{}", crate::VERSION))
+            .set("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    fn send(
+        &self,
+        request: ureq::Request,
+        body: Option<serde_json::Value>,
+    ) -> Result<serde_json::Value> {
+        let result = match body {
+            Some(body) => request.send_json(body),
+            None => request.call(),
+        };
+        match result {
+            Ok(response) => response
+                .into_json()
+                .with_context(|| "Invalid JSON from GitHub"),
+            Err(ureq::Error::Status(code, response)) => {
+                let detail: serde_json::Value = response.into_json().unwrap_or_default();
+                let message = detail.get("message").and_then(|m| m.as_str()).unwrap_or("");
+                bail!("GitHub request failed: HTTP {} {}", code, message)
+            }
+            Err(e) => Err(anyhow!("GitHub request failed: {}", e)),
+        }
+    }
+}
+
+fn parse_issue(value: &serde_json::Value) -> Result<Issue> {
+    let number = value
+        .get("number")
+        .and_then(|n| n.as_i64())
+        .ok_or_else(|| anyhow!("GitHub response has no issue number"))?;
+    let text = |key: &str| value.get(key).and_then(|v| v.as_str()).map(String::from);
+    Ok(Issue {
+        number,
+        state: text("state").unwrap_or_else(|| "open".to_string()),
+        state_reason: text("state_reason"),
+        url: text("html_url").unwrap_or_default(),
+    })
+}
+
+/// Token from the GitHub CLI, if it's installed and logged in
+fn gh_cli_token() -> Option<String> {
+    let output = Command::new("gh").args(["auth", "token"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+/// owner/name from an https or ssh remote URL
+fn repo_from_url(url: &str) -> Option<String> {
+    let path = url.trim().trim_end_matches('/').trim_end_matches(".git");
+    let mut parts = path.rsplit(['/', ':']).filter(|p| !p.is_empty());
+    let name = parts.next()?;
+    let owner = parts.next()?;
+    Some(format!("{}/{}", owner, name))
+}
@@ -0,0 +1,211 @@
+// HTTP - the agents every network request goes through
+//
+// Each request proj makes (update checks and downloads, release asset hashes,
+// webhooks, GitHub and tracker APIs, LLM and embedding providers, remote backups,
+// `note add --url`) gets its agent from `agent()`, so they all behave the same
+// behind a company proxy:
+//
+// - HTTPS_PROXY (for https URLs), HTTP_PROXY (for http URLs), or ALL_PROXY,
+//   upper or lower case, unless the host matches NO_PROXY. Loopback is never proxied.
+// - The built-in web roots plus the certificates in `ca_bundle` (config.json) or
+//   $PROJ_CA_BUNDLE, for proxies that re-sign TLS with their own CA.
+// - The HttpLog middleware (debug logging and offline mode).
+//
+// git, gh, and ssh (sftp backups) are separate programs with their own settings.
+
+use std::io;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use rustls::pki_types::pem::PemObject;
+use rustls::pki_types::CertificateDer;
+
+use crate::diagnostics;
+
+/// An agent for requests to `url` (its proxy depends on the host), with a timeout
+pub fn agent(url: &str, timeout: Duration) -> ureq::Agent {
+    agent_with(timeout, crate::config::offline(), proxy_for(url))
+}
+
+/// `agent` with offline mode and the proxy given rather than read from the environment
+fn agent_with(timeout: Duration, offline: bool, proxy: Option<String>) -> ureq::Agent {
+    let mut builder = ureq::AgentBuilder::new()
+        .timeout(timeout)
+        .middleware(diagnostics::HttpLog { offline });
+
+    // A setup problem fails each request with the reason rather than a vague TLS
+    // or connection error
+    match tls_config() {
+        Ok(Some(config)) => builder = builder.tls_config(config.clone()),
+        Ok(None) => {}
+        Err(message) => builder = builder.middleware(Refuse(message.clone())),
+    }
+    if let Some(proxy) = proxy {
+        match ureq::Proxy::new(&proxy) {
+            Ok(proxy) => builder = builder.proxy(proxy),
+            Err(e) => {
+                builder = builder.middleware(Refuse(format!("Invalid proxy '{}': {}", proxy, e)))
+            }
+        }
+    }
+    builder.build()
+}
+
+/// Whether a host is this machine
+pub fn is_loopback_host(host: &str) -> bool {
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    host.eq_ignore_ascii_case("localhost")
+        || host
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+/// The proxy the environment sets for `url`, if any
+fn proxy_for(url: &str) -> Option<String> {
+    let (scheme, rest) = url.split_once("://")?;
+    let host = host_of(rest);
+    if is_loopback_host(host) || no_proxy(host) {
+        return None;
+    }
+    let names: &[&str] = if scheme.eq_ignore_ascii_case("https") {
+        &["HTTPS_PROXY", "https_proxy", "ALL_PROXY", "all_proxy"]
+    } else {
+        &["http_proxy", "HTTP_PROXY", "ALL_PROXY", "all_proxy"]
+    };
+    names
+        .iter()
+        .find_map(|name| std::env::var(name).ok().filter(|v| !v.trim().is_empty()))
+        .map(|v| v.trim().to_string())
+}
+
+/// The host part of a URL after its scheme: no credentials, port, or path
+fn host_of(rest: &str) -> &str {
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let host = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    if host.starts_with('[') {
+        // [::1]:8080
+        return host.split_once(']').map_or(host, |(h, _)| &h[1..]);
+    }
+    host.split(':').next().unwrap_or(host)
+}
+
+/// Whether NO_PROXY (or no_proxy) covers the host: `*`, the host itself, or a
+/// domain it's under (`example.com` and `.example.com` both match `api.example.com`)
+fn no_proxy(host: &str) -> bool {
+    let Some(list) = std::env::var("NO_PROXY")
+        .ok()
+        .or_else(|| std::env::var("no_proxy").ok())
+    else {
+        return false;
+    };
+    let host = host.to_ascii_lowercase();
+    list.split(',')
+        .map(|entry| entry.trim().to_ascii_lowercase())
+        .filter(|entry| !entry.is_empty())
+        .any(|entry| {
+            if entry == "*" {
+                return true;
+            }
+            // Ports in entries are ignored
+            let entry = match entry.rsplit_once(':') {
+                Some((name, port)) if !name.contains(':') && port.parse::<u16>().is_ok() => {
+                    name.to_string()
+                }
+                _ => entry,
+            };
+            let domain = entry.trim_start_matches("*.").trim_start_matches('.');
+            host == domain || host.ends_with(&format!(".{}", domain))
+        })
+}
+
+/// TLS settings with the CA bundle's certificates added, loaded once; None
+/// without a bundle, so ureq's own defaults apply
+fn tls_config() -> &'static Result<Option<Arc<rustls::ClientConfig>>, String> {
+    static CONFIG: OnceLock<Result<Option<Arc<rustls::ClientConfig>>, String>> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let Some(path) = crate::config::ca_bundle() else {
+            return Ok(None);
+        };
+        let pem = std::fs::read(&path)
+            .map_err(|e| format!("Can't read the CA bundle {}: {}", path.display(), e))?;
+
+        let mut roots = rustls::RootCertStore {
+            roots: webpki_roots::TLS_SERVER_ROOTS.to_vec(),
+        };
+        let mut added = 0;
+        for cert in CertificateDer::pem_slice_iter(&pem) {
+            let cert = cert.map_err(|e| format!("Invalid CA bundle {}: {}", path.display(), e))?;
+            roots
+                .add(cert)
+                .map_err(|e| format!("Invalid certificate in {}: {}", path.display(), e))?;
+            added += 1;
+        }
+        if added == 0 {
+            return Err(format!(
+                "No certificates in the CA bundle {}",
+                path.display()
+            ));
+        }
+
+        let provider = Arc::new(rustls::crypto::ring::default_provider());
+        let config = rustls::ClientConfig::builder_with_provider(provider)
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("TLS setup failed: {}", e))?
+            .with_root_certificates(roots)
+            .with_no_client_auth();
+        Ok(Some(Arc::new(config)))
+    })
+}
+
+/// Middleware failing every request with a setup error
+struct Refuse(String);
+
+impl ureq::Middleware for Refuse {
+    fn handle(
+        &self,
+        _request: ureq::Request,
+        _next: ureq::MiddlewareNext,
+    ) -> Result<ureq::Response, ureq::Error> {
+        Err(io::Error::new(io::ErrorKind::Other, self.0.clone()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    #[test]
+    fn offline_mode_refuses_remote_requests_before_connecting() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let local = format!("http://{}", listener.local_addr().unwrap());
+        // Remote requests go through this proxy, so a connection to it would be one made
+        let err = agent_with(Duration::from_secs(5), true, Some(local.clone()))
+            .get("http://example.com/")
+            .call()
+            .unwrap_err();
+        assert!(err.to_string().contains("offline mode"), "{}", err);
+        listener.set_nonblocking(true).unwrap();
+        assert!(listener.accept().is_err(), "a connection was made");
+
+        // Loopback (a local Ollama, say) is still allowed
+        listener.set_nonblocking(false).unwrap();
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 1024];
+            let _ = stream.read(&mut request).unwrap();
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 2\r\nConnection: close\r\n\r\nok")
+                .unwrap();
+        });
+        let body = agent_with(Duration::from_secs(5), true, None)
+            .get(&local)
+            .call()
+            .unwrap()
+            .into_string()
+            .unwrap();
+        server.join().unwrap();
+        assert_eq!(body, "ok");
+    }
+}
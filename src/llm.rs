@@ -0,0 +1,251 @@
+// LLM - text generation through an OpenAI-compatible endpoint or a local Ollama model
+//
+// Configured globally in ~/.proj/llm.json. Callers pass a fallback so commands keep
+// working offline or without a provider configured.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::http;
+
+/// Provider settings stored in ~/.proj/llm.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LlmConfig {
+    /// "ollama" (local model) or "openai" (any OpenAI-compatible endpoint)
+    pub provider: String,
+    /// Chat model, e.g. llama3.1 or gpt-4o-mini
+    pub model: String,
+    /// API base URL (default: http://localhost:11434 for ollama, https://api.openai.com/v1 for openai)
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// API key for OpenAI-compatible endpoints (default: $OPENAI_API_KEY)
+    #[serde(default)]
+    pub api_key: Option<String>,
+    /// Extra attempts after a network error, rate limit, or server error
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Seconds to wait for a response
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+fn default_max_retries() -> u32 {
+    2
+}
+
+fn default_timeout_secs() -> u64 {
+    120
+}
+
+impl LlmConfig {
+    /// Load ~/.proj/llm.json, or None if no provider is configured
+    pub fn load() -> Result<Option<Self>> {
+        let path = crate::paths::get_llm_config_path()?;
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(&path)?;
+        let config: LlmConfig = serde_json::from_str(&content)
+            .map_err(|e| anyhow!("Failed to parse {}: {}", path.display(), e))?;
+        Ok(Some(config))
+    }
+}
+
+/// Where completions come from
+enum Provider {
+    Ollama {
+        base_url: String,
+    },
+    OpenAi {
+        base_url: String,
+        api_key: Option<String>,
+    },
+}
+
+pub struct Llm {
+    provider: Provider,
+    model: String,
+    max_retries: u32,
+    timeout: Duration,
+}
+
+/// Text from the LLM, or from the caller's fallback
+pub struct Completion {
+    pub text: String,
+    /// Model that wrote the text; None when the fallback was used
+    pub model: Option<String>,
+    /// Why the fallback was used (no provider, or the provider's error)
+    pub fallback_reason: Option<String>,
+}
+
+impl Llm {
+    pub fn from_config(config: &LlmConfig) -> Result<Self> {
+        let base_url = |default: &str| {
+            config
+                .base_url
+                .as_deref()
+                .unwrap_or(default)
+                .trim_end_matches('/')
+                .to_string()
+        };
+        let provider = match config.provider.as_str() {
+            "ollama" => Provider::Ollama {
+                base_url: base_url("http://localhost:11434"),
+            },
+            "openai" => Provider::OpenAi {
+                base_url: base_url("https://api.openai.com/v1"),
+                api_key: config
+                    .api_key
+                    .clone()
+                    .or_else(|| std::env::var("OPENAI_API_KEY").ok()),
+            },
+            other => bail!(
+                "Unknown LLM provider '{}'. Use 'ollama' or 'openai'.",
+                other
+            ),
+        };
+        Ok(Self {
+            provider,
+            model: config.model.clone(),
+            max_retries: config.max_retries,
+            timeout: Duration::from_secs(config.timeout_secs),
+        })
+    }
+
+    /// The configured LLM, or None if ~/.proj/llm.json doesn't exist
+    pub fn load() -> Result<Option<Self>> {
+        LlmConfig::load()?
+            .map(|config| Self::from_config(&config))
+            .transpose()
+    }
+
+    /// e.g. "ollama:llama3.1"
+    pub fn model_id(&self) -> String {
+        match self.provider {
+            Provider::Ollama { .. } => format!("ollama:{}", self.model),
+            Provider::OpenAi { .. } => format!("openai:{}", self.model),
+        }
+    }
+
+    /// One chat completion, retrying transient failures with backoff
+    pub fn complete(&self, system: &str, prompt: &str) -> Result<String> {
+        let mut attempt = 0;
+        loop {
+            match self.request(system, prompt) {
+                Ok(text) => return Ok(text),
+                Err(e) if e.retryable && attempt < self.max_retries => {
+                    attempt += 1;
+                    std::thread::sleep(Duration::from_secs(1 << (attempt - 1)));
+                }
+                Err(e) => return Err(e.error),
+            }
+        }
+    }
+
+    fn request(&self, system: &str, prompt: &str) -> Result<String, RequestError> {
+        let messages = serde_json::json!([
+            { "role": "system", "content": system },
+            { "role": "user", "content": prompt },
+        ]);
+        let (request, body) = match &self.provider {
+            Provider::Ollama { base_url } => {
+                let url = format!("{}/api/chat", base_url);
+                (
+                    http::agent(&url, self.timeout).post(&url),
+                    serde_json::json!({ "model": self.model, "messages": messages, "stream": false }),
+                )
+            }
+            Provider::OpenAi { base_url, api_key } => {
+                let url = format!("{}/chat/completions", base_url);
+                let mut request = http::agent(&url, self.timeout).post(&url);
+                if let Some(key) = api_key {
+                    request = request.set("Authorization", &format!("Bearer {}", key));
+                }
+                (
+                    request,
+                    serde_json::json!({ "model": self.model, "messages": messages }),
+                )
+            }
+        };
+
+        let response: serde_json::Value = match request.send_json(body) {
+            Ok(response) => response
+                .into_json()
+                .with_context(|| "Invalid JSON from LLM provider")
+                .map_err(RequestError::fatal)?,
+            Err(ureq::Error::Status(code, response)) => {
+                let detail = response.into_string().unwrap_or_default();
+                return Err(RequestError {
+                    retryable: code == 429 || code >= 500,
+                    error: anyhow!("LLM request failed: HTTP {} {}", code, detail.trim()),
+                });
+            }
+            Err(e) => {
+                return Err(RequestError {
+                    retryable: true,
+                    error: anyhow!("LLM request failed: {}", e),
+                })
+            }
+        };
+
+        let content = match self.provider {
+            Provider::Ollama { .. } => response.pointer("/message/content"),
+            Provider::OpenAi { .. } => response.pointer("/choices/0/message/content"),
+        };
+        content
+            .and_then(|c| c.as_str())
+            .map(|c| c.trim().to_string())
+            .filter(|c| !c.is_empty())
+            .ok_or_else(|| RequestError::fatal(anyhow!("LLM response has no content")))
+    }
+}
+
+struct RequestError {
+    retryable: bool,
+    error: anyhow::Error,
+}
+
+impl RequestError {
+    fn fatal(error: anyhow::Error) -> Self {
+        Self {
+            retryable: false,
+            error,
+        }
+    }
+}
+
+/// Ask the configured LLM; use `fallback` when none is configured or it can't be reached
+pub fn complete_or(system: &str, prompt: &str, fallback: impl FnOnce() -> String) -> Completion {
+    let llm = match Llm::load() {
+        Ok(Some(llm)) => llm,
+        Ok(None) => {
+            return Completion {
+                text: fallback(),
+                model: None,
+                fallback_reason: Some("no LLM configured in ~/.proj/llm.json".to_string()),
+            }
+        }
+        Err(e) => {
+            return Completion {
+                text: fallback(),
+                model: None,
+                fallback_reason: Some(e.to_string()),
+            }
+        }
+    };
+
+    match llm.complete(system, prompt) {
+        Ok(text) => Completion {
+            text,
+            model: Some(llm.model_id()),
+            fallback_reason: None,
+        },
+        Err(e) => Completion {
+            text: fallback(),
+            model: None,
+            fallback_reason: Some(e.to_string()),
+        },
+    }
+}
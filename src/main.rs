@@ -1,30 +1,48 @@
 // proj - Project tracking and context management for AI-assisted development
 
 mod auto_update;
+mod budget;
 mod cli;
 mod commands;
 mod commit;
-mod config;
-mod database;
-mod docs_db;
-mod git;
-mod models;
-mod paths;
-mod schema;
-mod schema_docs;
+mod crypto;
+mod diagnostics;
+mod embeddings;
+mod encoding;
+mod github;
+mod http;
+mod llm;
+mod metrics;
+mod nudges;
+mod output_cache;
+mod pager;
+mod porcelain;
+mod records;
+mod remote;
 mod session;
-mod source_analyzer;
+mod stdin_input;
+mod tags;
+mod templates;
+mod text;
+mod trackers;
+mod webhooks;
+mod workspace;
+
+use proj_core::{
+    config, database, docs_db, error, git, models, paths, projignore, repository, schema,
+    schema_docs, source_analyzer,
+};
+pub use proj_core::{MIN_SCHEMA_VERSION, SCHEMA_VERSION};
 
 use anyhow::Result;
 use atty::Stream;
 use clap::Parser;
 use cli::{Cli, Commands};
 use colored::control;
+use error::ProjError;
 
 /// Version constants
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
-pub const SCHEMA_VERSION: &str = "1.4";
-pub const MIN_SCHEMA_VERSION: &str = "1.0";
 
 fn main() -> Result<()> {
     // Check for pending updates FIRST (before parsing args)
@@ -34,17 +52,88 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
-    let cli = Cli::parse();
+    let mut cli = Cli::parse();
+    apply_global_options(&mut cli);
+
+    if let Err(e) = diagnostics::init(cli.debug, cli.trace, cli.log_file, !cli.no_color) {
+        eprintln!("Warning: could not start logging: {:#}", e);
+    }
+    let started = std::time::Instant::now();
+    tracing::debug!(
+        "proj {} {}",
+        VERSION,
+        std::env::args().skip(1).collect::<Vec<_>>().join(" ")
+    );
+
+    let recorder = metrics::start();
+    let result = if commands::approve::should_queue(&cli.command) {
+        commands::approve::queue(&cli.command)
+    } else {
+        dispatch(cli.command)
+    };
+    if let Some(recorder) = recorder {
+        recorder.finish(result.is_ok());
+    }
+
+    match &result {
+        Ok(()) => tracing::debug!("finished in {:.1?}", started.elapsed()),
+        Err(e) => tracing::debug!("failed after {:.1?}: {:#}", started.elapsed(), e),
+    }
+    if let Err(e) = result {
+        if porcelain::enabled() {
+            // One line, whatever the error
+            let message = format!("{:#}", e);
+            eprintln!("{}", message.lines().next().unwrap_or_default());
+        } else if let Some(ProjError::NothingToDo(message)) = ProjError::find(&e) {
+            // Not a failure, so no "Error:" prefix
+            eprintln!("{}", message);
+        } else {
+            eprintln!("Error: {:?}", e);
+        }
+        std::process::exit(exit_code(&e));
+    }
+    Ok(())
+}
+
+/// Put the global options into effect for the rest of the run (`proj approve` also
+/// applies a queued command's own before running it). --debug, --trace, and
+/// --log-file are set up once, by `diagnostics::init`.
+fn apply_global_options(cli: &mut Cli) {
+    // --project wins over an inherited PROJ_PROJECT_ROOT; made absolute so hooks and
+    // child processes that start elsewhere find the same project
+    if let Some(ref project) = cli.project {
+        let dir = std::env::current_dir()
+            .map(|cwd| cwd.join(project))
+            .unwrap_or_else(|_| project.into());
+        std::env::set_var(paths::PROJECT_ROOT_ENV, dir);
+    }
+
+    // `proj uninstall --project` predates the global option and still means "this project only"
+    let project_given = cli.project.is_some();
+    if let Commands::Uninstall { current, .. } = &mut cli.command {
+        *current = project_given;
+    }
 
     // Configure color output:
     // 1. Disable if --no-color flag is set
     // 2. Disable if not a TTY (piped/redirected output)
     // 3. Respect NO_COLOR environment variable (handled by colored crate)
-    if cli.no_color || !atty::is(Stream::Stdout) {
+    if cli.no_color || cli.porcelain || !atty::is(Stream::Stdout) {
         control::set_override(false);
     }
+    porcelain::set(cli.porcelain);
+    if cli.no_pager {
+        pager::disable();
+    }
+    // Also for the proj processes hooks start from this one
+    if cli.no_self_update {
+        std::env::set_var(auto_update::NO_SELF_UPDATE_ENV, "1");
+    }
+}
 
-    match cli.command {
+/// Run a parsed command (also how `proj approve` applies queued ones)
+fn dispatch(command: Commands) -> Result<()> {
+    match command {
         Commands::Init {
             path,
             name,
@@ -59,6 +148,8 @@ fn main() -> Result<()> {
             commit_mode,
             no_agents,
             shell_hook,
+            template,
+            workspace,
         } => commands::init::run(
             path,
             name,
@@ -73,45 +164,150 @@ fn main() -> Result<()> {
             commit_mode,
             no_agents,
             shell_hook,
+            template,
+            workspace,
         ),
         Commands::Migrate => commands::migrate::run(),
         Commands::Status {
             quiet,
             verbose,
             full,
-        } => commands::status::run(quiet, verbose, full),
+            workspace,
+        } => commands::status::run(quiet, verbose, full, workspace),
         Commands::Enter => commands::enter::run(),
-        Commands::Resume { for_ai } => commands::resume::run(for_ai),
+        Commands::Prompt { format } => commands::prompt::run(format),
+        Commands::Resume { for_ai, max_tokens } => commands::resume::run(for_ai, max_tokens),
         Commands::Session(cmd) => commands::session::run(cmd),
         Commands::Log(cmd) => commands::log::run(cmd),
+        Commands::Blocker(cmd) => commands::blocker::run(cmd),
+        Commands::Question(cmd) => commands::question::run(cmd),
+        Commands::Note(cmd) => commands::note::run(cmd),
+        Commands::Decision(cmd) => commands::decision::run(cmd),
+        Commands::Attachment(cmd) => commands::attachment::run(cmd),
         Commands::Task(cmd) => commands::task::run(cmd),
-        Commands::Tasks => commands::task::list(),
+        Commands::Tasks(args) => commands::task::list(&args),
         Commands::Context {
             topic,
             ranked,
             recent,
-        } => commands::context::run(&topic, ranked, recent),
-        Commands::Review => commands::review::run(),
-        Commands::Delta => commands::delta::run(),
-        Commands::Compress { auto } => commands::compress::run(auto),
-        Commands::Cleanup { auto, days } => commands::cleanup::run(auto, days),
-        Commands::Upgrade { info, all, auto } => commands::upgrade::run(info, all, auto),
-        Commands::Register => commands::register::run(),
-        Commands::Registered => commands::registered::run(),
-        Commands::Dashboard => commands::dashboard::run(),
-        Commands::Snapshot => commands::snapshot::run(),
-        Commands::Export { format } => commands::export::run(format),
-        Commands::Backup => commands::backup::run(),
-        Commands::Check => commands::check::run(),
+            branch,
+            since,
+            author,
+            limit,
+            verbose,
+            max_tokens,
+            semantic,
+            profile,
+        } => commands::context::run(
+            &topic,
+            &commands::context::ContextOptions {
+                ranked,
+                recent,
+                branch: branch.as_deref(),
+                since: since.as_deref(),
+                author: author.as_deref(),
+                limit,
+                verbose,
+                max_tokens,
+                semantic,
+                profile: profile.as_deref(),
+            },
+        ),
+        Commands::Search {
+            query,
+            all,
+            json,
+            limit,
+        } => commands::search::run(&query, all, json, limit),
+        Commands::Reindex => commands::reindex::run(),
+        Commands::ScanTodos { dry_run } => commands::scan_todos::run(dry_run),
+        Commands::Review { json, accept } => commands::review::run(json, &accept),
+        Commands::Delta { json } => commands::delta::run(json),
+        Commands::Compress { auto, llm } => commands::compress::run(auto, llm),
+        Commands::Cleanup {
+            auto,
+            days,
+            optimize,
+            dry_run,
+        } => commands::cleanup::run(auto, days, optimize, dry_run),
+        Commands::Upgrade {
+            info,
+            all,
+            auto,
+            jobs,
+        } => commands::upgrade::run(info, all, auto, jobs),
+        Commands::Register { move_paths } => commands::register::run(move_paths),
+        Commands::Registered { prune } => commands::registered::run(prune),
+        Commands::Dashboard { all, tui } => {
+            if tui {
+                commands::dashboard_tui::run()
+            } else {
+                commands::dashboard::run(all)
+            }
+        }
+        Commands::Prime { format, max_tokens } => commands::prime::run(&format, max_tokens),
+        Commands::Snapshot {
+            bundle,
+            output,
+            restore,
+            merge,
+        } => commands::snapshot::run(bundle, output, restore, merge),
+        Commands::Standup { days, all, json } => commands::standup::run(days, all, json),
+        Commands::Approve { ids, all, reject } => commands::approve::run(ids, all, reject),
+        Commands::Audit {
+            since,
+            table,
+            id,
+            session,
+            limit,
+            json,
+        } => commands::audit::run(since, table, id, session, limit, json),
+        Commands::Stats { weeks, usage, json } => commands::stats::run(weeks, usage, json),
+        Commands::Watch { json, interval } => commands::watch::run(json, interval),
+        Commands::Export {
+            format,
+            tables,
+            since,
+            output,
+        } => commands::export::run(format, tables, since, output),
+        Commands::Import {
+            file,
+            merge,
+            dry_run,
+        } => commands::import::run(&file, merge, dry_run),
+        Commands::Merge { file, dry_run } => commands::merge::run(&file, dry_run),
+        Commands::Sync { strategy, dry_run } => commands::sync::run(&strategy, dry_run),
+        Commands::Backup {
+            command: Some(cli::BackupSubcommand::Verify { file }),
+            ..
+        } => commands::backup_verify::run(file),
+        Commands::Backup {
+            command: None,
+            remote,
+            encrypt,
+            decrypt,
+            show_key,
+        } => commands::backup::run(remote, encrypt, decrypt, show_key),
+        Commands::Check { json, strict } => commands::check::run(json, strict),
+        Commands::Ci(cmd) => commands::ci::run(cmd),
+        Commands::Doctor { fix } => commands::doctor::run(fix),
         Commands::Extend { extension_type } => commands::extend::run(extension_type),
-        Commands::Archive => commands::archive::run(),
-        Commands::Update { apply, check } => commands::update_check::run(apply, check),
+        Commands::Archive(cmd) => commands::archive::run(cmd),
+        Commands::Update {
+            apply,
+            check,
+            channel,
+            defer,
+        } => commands::update_check::run(apply, check, channel, defer),
         Commands::Release { version, check } => commands::release::run(version, check),
         Commands::Rollback {
             version,
             schema,
             list,
-        } => commands::rollback::run(version, schema, list),
+            backup,
+            info,
+            dry_run,
+        } => commands::rollback::run(version, schema, list, backup, info, dry_run),
         Commands::Shell(cmd) => {
             use cli::ShellSubcommand;
             match cmd.command {
@@ -121,12 +317,48 @@ fn main() -> Result<()> {
                 ShellSubcommand::Check => commands::shell::check(),
             }
         }
+        Commands::Embed(cmd) => {
+            use cli::EmbedSubcommand;
+            match cmd.command {
+                EmbedSubcommand::Build { rebuild } => commands::embed::build(rebuild),
+                EmbedSubcommand::Status => commands::embed::status(),
+            }
+        }
+        Commands::Githook(cmd) => {
+            use cli::GithookSubcommand;
+            match cmd.command {
+                GithookSubcommand::Install => commands::githook::install(),
+                GithookSubcommand::Uninstall => commands::githook::uninstall(),
+                GithookSubcommand::Status => commands::githook::status(),
+                GithookSubcommand::Run { hook, args } => commands::githook::run(&hook, &args),
+            }
+        }
+        Commands::Agents(cmd) => commands::agents::run(cmd),
         Commands::Uninstall {
             shell,
-            project,
+            current,
             all,
             force,
-        } => commands::uninstall::run(shell, project, all, force),
+            no_export,
+        } => commands::uninstall::run(shell, current, all, force, no_export),
         Commands::Docs(cmd) => commands::docs::run(cmd),
+        Commands::Db(cmd) => commands::db::run(cmd),
+        Commands::Config(cmd) => commands::config::run(cmd),
+        Commands::Trash(cmd) => commands::trash::run(cmd),
+    }
+}
+
+/// Exit status for a failed command, so scripts can tell "no project here" or
+/// "nothing to do" from real failures. 2 is left to clap for usage errors.
+fn exit_code(err: &anyhow::Error) -> i32 {
+    match ProjError::find(err) {
+        Some(ProjError::NotAProject) => 3,
+        Some(ProjError::SchemaMismatch { .. }) => 4,
+        Some(ProjError::DbLocked) => 5,
+        Some(ProjError::DocsDbMissing) => 6,
+        Some(ProjError::GitUnavailable(_)) => 7,
+        Some(ProjError::InvalidInput(_)) => 8,
+        Some(ProjError::NothingToDo(_)) => 9,
+        None => 1,
     }
 }
@@ -0,0 +1,197 @@
+// Usage metrics - opt-in local timing of each command, for `proj stats --usage`
+//
+// With `usage_metrics` on in config.json, every command records how long it took and
+// about how many tokens it printed, in the tracking database's command_metrics table.
+// Nothing is sent anywhere. Output is counted by passing stdout through a pipe on its
+// way out, which is only done on Unix and when stdout isn't a terminal (agents,
+// scripts); other runs record the duration alone.
+
+use std::time::{Duration, Instant};
+
+use clap::parser::ValueSource;
+use clap::{ArgMatches, CommandFactory};
+
+use crate::cli::Cli;
+use crate::config::ProjectConfig;
+use crate::database::open_database;
+use crate::paths::get_tracking_db_path;
+
+/// Longest wait for the last of the output to pass through the counting pipe
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// A command being timed
+pub struct Recorder {
+    started: Instant,
+    /// Subcommand path, e.g. "log decision"
+    command: String,
+    /// Options given on the command line, e.g. "--full --porcelain" (no values)
+    flags: String,
+    terminal: bool,
+    counter: Option<OutputCounter>,
+}
+
+/// Start timing the command being run, if this project has usage_metrics on
+pub fn start() -> Option<Recorder> {
+    if !ProjectConfig::load().is_ok_and(|c| c.usage_metrics) {
+        return None;
+    }
+    let (command, flags) = describe_command()?;
+    let terminal = atty::is(atty::Stream::Stdout);
+    Some(Recorder {
+        started: Instant::now(),
+        command,
+        flags,
+        terminal,
+        counter: if terminal {
+            None
+        } else {
+            OutputCounter::start()
+        },
+    })
+}
+
+impl Recorder {
+    /// Stop counting output and save the measurement. Never fails the command: a
+    /// database without the table (before v1.19) or that can't be opened is skipped.
+    pub fn finish(self, success: bool) {
+        let duration_ms = self.started.elapsed().as_millis() as i64;
+        let output_tokens = self.counter.and_then(OutputCounter::finish);
+        let Ok(conn) = get_tracking_db_path().and_then(|path| open_database(&path)) else {
+            return;
+        };
+        let result = conn.execute(
+            "INSERT INTO command_metrics (command, flags, duration_ms, output_tokens, terminal, success)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            rusqlite::params![
+                self.command,
+                Some(self.flags).filter(|f| !f.is_empty()),
+                duration_ms,
+                output_tokens.map(|t| t as i64),
+                self.terminal,
+                success
+            ],
+        );
+        if let Err(e) = result {
+            tracing::debug!("usage metrics not recorded: {}", e);
+        }
+    }
+}
+
+/// The subcommand path and the options given, from this process's arguments
+fn describe_command() -> Option<(String, String)> {
+    let mut cmd = Cli::command();
+    let matches = cmd.try_get_matches_from_mut(std::env::args_os()).ok()?;
+    let mut names = Vec::new();
+    let mut matches: &ArgMatches = &matches;
+    while let Some((name, sub)) = matches.subcommand() {
+        names.push(name.to_string());
+        cmd = cmd.find_subcommand(name)?.clone();
+        matches = sub;
+    }
+    let mut flags: Vec<String> = cmd
+        .get_arguments()
+        .filter(|arg| !arg.is_positional())
+        .filter(|arg| matches.value_source(arg.get_id().as_str()) == Some(ValueSource::CommandLine))
+        .map(|arg| match arg.get_long() {
+            Some(long) => format!("--{}", long),
+            None => format!("-{}", arg.get_short().unwrap_or('?')),
+        })
+        .collect();
+    flags.sort();
+    Some((names.join(" "), flags.join(" ")))
+}
+
+/// Counts the characters written to stdout by sending them through a pipe to a
+/// thread that passes them on to the real stdout
+#[cfg(unix)]
+struct OutputCounter {
+    /// The real stdout, restored when counting stops
+    stdout_fd: i32,
+    /// Characters counted, sent once the pipe closes
+    done: std::sync::mpsc::Receiver<usize>,
+}
+
+#[cfg(unix)]
+impl OutputCounter {
+    fn start() -> Option<Self> {
+        use std::io::{Read, Write};
+        use std::os::unix::io::FromRawFd;
+
+        let mut fds = [0; 2];
+        // SAFETY: plain descriptor calls; every descriptor opened here is either
+        // handed to exactly one owner or closed on failure
+        let stdout_fd = unsafe {
+            if libc::pipe(fds.as_mut_ptr()) != 0 {
+                return None;
+            }
+            let stdout_fd = libc::dup(1);
+            if stdout_fd < 0 || libc::dup2(fds[1], 1) < 0 {
+                libc::close(fds[0]);
+                libc::close(fds[1]);
+                if stdout_fd >= 0 {
+                    libc::close(stdout_fd);
+                }
+                return None;
+            }
+            libc::close(fds[1]);
+            stdout_fd
+        };
+
+        let (tx, done) = std::sync::mpsc::channel();
+        let read_fd = fds[0];
+        std::thread::spawn(move || {
+            // SAFETY: read_fd is ours alone; stdout_fd stays open until counting stops
+            // and is closed by finish, so it's borrowed here without closing it
+            let mut input = unsafe { std::fs::File::from_raw_fd(read_fd) };
+            let mut output =
+                std::mem::ManuallyDrop::new(unsafe { std::fs::File::from_raw_fd(stdout_fd) });
+            let mut buf = [0u8; 8192];
+            let mut chars = 0;
+            let mut open = true;
+            while let Ok(n) = input.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                // UTF-8 continuation bytes don't start a character
+                chars += buf[..n].iter().filter(|b| (**b & 0xC0) != 0x80).count();
+                // A closed reader (`| head`) stops the copying but not the counting
+                open = open && output.write_all(&buf[..n]).is_ok();
+            }
+            let _ = tx.send(chars);
+        });
+        Some(Self { stdout_fd, done })
+    }
+
+    /// Put the real stdout back and return the output's size in tokens, estimated
+    /// like budget::estimate_tokens
+    fn finish(self) -> Option<usize> {
+        use std::io::Write;
+
+        let _ = std::io::stdout().flush();
+        // SAFETY: restoring fd 1 closes the pipe's write end, which ends the thread
+        unsafe {
+            libc::dup2(self.stdout_fd, 1);
+        }
+        let chars = self.done.recv_timeout(DRAIN_TIMEOUT).ok()?;
+        // SAFETY: the thread has finished with it
+        unsafe {
+            libc::close(self.stdout_fd);
+        }
+        Some((chars + 3) / 4)
+    }
+}
+
+/// Output isn't counted on Windows
+#[cfg(not(unix))]
+struct OutputCounter;
+
+#[cfg(not(unix))]
+impl OutputCounter {
+    fn start() -> Option<Self> {
+        None
+    }
+
+    fn finish(self) -> Option<usize> {
+        None
+    }
+}
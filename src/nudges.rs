@@ -0,0 +1,106 @@
+// Nudges - reminders to log what a session is doing, shown by `proj status` and
+// included in `proj resume --for-ai`
+//
+// Each rule checks one sign that logging has fallen behind and suggests the command
+// that catches it up. Thresholds and per-rule switches come from `nudges` in config.json.
+
+use anyhow::Result;
+use rusqlite::Connection;
+use serde::Serialize;
+
+use crate::config::ProjectConfig;
+use crate::git;
+use crate::models::Session;
+
+/// One reminder, with the command that acts on it
+#[derive(Debug, Serialize)]
+pub struct Nudge {
+    /// Rule name, as used in `nudges.disabled`
+    pub rule: &'static str,
+    pub message: String,
+    /// Command that addresses it
+    pub action: String,
+}
+
+/// Run every enabled rule against the current session
+pub fn evaluate(
+    conn: &Connection,
+    session: &Session,
+    config: &ProjectConfig,
+) -> Result<Vec<Nudge>> {
+    let rules = &config.nudges;
+    let mut nudges = Vec::new();
+    if !rules.enabled {
+        return Ok(nudges);
+    }
+
+    let count = |sql: &str| -> Result<i64> {
+        Ok(conn.query_row(sql, [session.session_id], |row| row.get(0))?)
+    };
+    let decisions = count("SELECT COUNT(*) FROM decisions WHERE session_id = ?")?;
+
+    let minutes = (chrono::Utc::now() - session.started_at).num_minutes();
+    if rules.rule_enabled("no_decisions")
+        && rules.no_decisions_minutes > 0
+        && minutes >= rules.no_decisions_minutes
+        && decisions == 0
+    {
+        nudges.push(Nudge {
+            rule: "no_decisions",
+            message: format!("Session active {}+ min, 0 decisions logged", minutes),
+            action: "proj review".to_string(),
+        });
+    }
+
+    if rules.rule_enabled("unlogged_commits") && rules.commits_per_log > 0 {
+        // git_commits stores local time
+        let since = session
+            .started_at
+            .with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M:%S")
+            .to_string();
+        let commits = git::get_commit_count_since(conn, &since).unwrap_or(0);
+        let logged = decisions + count("SELECT COUNT(*) FROM context_notes WHERE session_id = ?")?;
+        if commits >= rules.commits_per_log && commits > logged * rules.commits_per_log {
+            nudges.push(Nudge {
+                rule: "unlogged_commits",
+                message: format!(
+                    "{} commit{} this session, {} decision{} or note{} logged",
+                    commits,
+                    if commits == 1 { "" } else { "s" },
+                    logged,
+                    if logged == 1 { "" } else { "s" },
+                    if logged == 1 { "" } else { "s" }
+                ),
+                action: "proj review".to_string(),
+            });
+        }
+    }
+
+    if rules.rule_enabled("stale_blocker") && rules.blocker_age_days > 0 {
+        let mut stmt = conn.prepare(
+            "SELECT blocker_id, description, CAST(julianday('now') - julianday(created_at) AS INTEGER)
+             FROM blockers
+             WHERE status = 'active' AND created_at <= datetime('now', ?1)
+             ORDER BY created_at",
+        )?;
+        let stale = stmt
+            .query_map([format!("-{} days", rules.blocker_age_days)], |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        for (id, description, days) in stale {
+            nudges.push(Nudge {
+                rule: "stale_blocker",
+                message: format!("Blocker #{} open {} days: {}", id, days, description),
+                action: format!("proj blocker resolve {} \"<resolution>\"", id),
+            });
+        }
+    }
+
+    Ok(nudges)
+}
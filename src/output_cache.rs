@@ -0,0 +1,172 @@
+// Output cache - reuse what `proj status` and `proj context` printed last time while
+// nothing they read has changed
+//
+// Several agents polling one project run the same commands many times a minute. Each
+// rendered output is saved in .tracking/cache/ with a fingerprint of the data it was
+// built from: size and modification time of tracking.db, its WAL (with the WAL header,
+// which changes whenever the log restarts), config.json and the global defaults, the
+// embedding provider settings, and the docs database. Any
+// write changes the fingerprint, so saved output is only reused when running the
+// command again would print the same thing. `output_cache: false` turns it off.
+
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use sha2::{Digest, Sha256};
+
+use crate::config::ProjectConfig;
+use crate::encoding::hex;
+use crate::paths::{
+    get_cache_dir, get_embeddings_config_path, get_global_config_path, get_project_root,
+};
+
+/// Outputs kept; the least recently written ones beyond this are removed
+const MAX_ENTRIES: usize = 64;
+
+/// Bytes of the WAL header compared: its checkpoint sequence number and salts
+const WAL_HEADER_LEN: usize = 32;
+
+/// Where one command's output is cached, and the fingerprint of the data right now
+pub struct Entry {
+    path: PathBuf,
+    fingerprint: String,
+}
+
+impl Entry {
+    /// The cache slot for `key` (the command and everything that shapes its output),
+    /// or None when caching is off or there's no project
+    pub fn new(config: &ProjectConfig, key: &str) -> Option<Self> {
+        if !config.output_cache {
+            return None;
+        }
+        let root = get_project_root().ok()?;
+        // Colors and relative dates change the output without changing the data
+        let key = format!(
+            "{} {} {} {}",
+            crate::VERSION,
+            colored::control::SHOULD_COLORIZE.should_colorize(),
+            chrono::Local::now().date_naive(),
+            key
+        );
+        let hash = format!("{:x}", Sha256::digest(key.as_bytes()));
+        Some(Self {
+            path: get_cache_dir().ok()?.join(&hash[..16]),
+            fingerprint: fingerprint(&root),
+        })
+    }
+
+    /// The output saved under this key, if it was built from the same data
+    pub fn hit(&self) -> Option<String> {
+        let content = std::fs::read_to_string(&self.path).ok()?;
+        let (saved, output) = content.split_once('\n')?;
+        if saved != self.fingerprint {
+            return None;
+        }
+        tracing::debug!("output cache hit: {}", self.path.display());
+        Some(output.to_string())
+    }
+
+    /// Save output for the next call. Failures only cost that call a cache miss.
+    pub fn store(&self, output: &str) {
+        let Some(dir) = self.path.parent() else {
+            return;
+        };
+        // Written aside and renamed, so a concurrent reader never sees half an entry
+        let temp = self
+            .path
+            .with_extension(format!("tmp{}", std::process::id()));
+        let written = std::fs::create_dir_all(dir)
+            .and_then(|_| std::fs::write(&temp, format!("{}\n{}", self.fingerprint, output)))
+            .and_then(|_| std::fs::rename(&temp, &self.path));
+        if let Err(e) = written {
+            tracing::debug!("output cache not written: {}", e);
+            let _ = std::fs::remove_file(&temp);
+            return;
+        }
+        prune(dir);
+    }
+}
+
+/// Size and modification time of everything the cached commands read
+fn fingerprint(root: &Path) -> String {
+    let tracking = root.join(".tracking");
+    let mut parts = vec![
+        file_stamp(&tracking.join("tracking.db")),
+        file_stamp(&tracking.join("config.json")),
+    ];
+    for global in [get_global_config_path(), get_embeddings_config_path()] {
+        parts.push(global.map_or_else(|_| "-".to_string(), |path| file_stamp(&path)));
+    }
+
+    // An empty WAL is recreated by every connection, so only one with frames counts
+    let wal = tracking.join("tracking.db-wal");
+    if std::fs::metadata(&wal).is_ok_and(|m| m.len() > 0) {
+        parts.push(file_stamp(&wal));
+        if let Some(header) = wal_header(&wal) {
+            parts.push(hex(&header));
+        }
+    }
+
+    // Docs databases (<name>_<kind>.db) sit in the project root
+    let mut docs: Vec<PathBuf> = std::fs::read_dir(root)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.ends_with(".db") || n.ends_with(".db-wal"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    docs.sort();
+    parts.extend(docs.iter().map(|p| file_stamp(p)));
+
+    parts.join(" ")
+}
+
+/// The WAL's header, read without loading the rest of the log
+fn wal_header(wal: &Path) -> Option<Vec<u8>> {
+    let mut header = Vec::with_capacity(WAL_HEADER_LEN);
+    std::fs::File::open(wal)
+        .ok()?
+        .take(WAL_HEADER_LEN as u64)
+        .read_to_end(&mut header)
+        .ok()?;
+    Some(header)
+}
+
+/// "size:mtime" in nanoseconds, or "-" for a missing file
+fn file_stamp(path: &Path) -> String {
+    let Ok(meta) = std::fs::metadata(path) else {
+        return "-".to_string();
+    };
+    let modified = meta
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or_default();
+    format!("{}:{}", meta.len(), modified)
+}
+
+/// Remove the oldest entries beyond MAX_ENTRIES
+fn prune(dir: &Path) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let mut files: Vec<(std::time::SystemTime, PathBuf)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| Some((e.metadata().ok()?.modified().ok()?, e.path())))
+        .collect();
+    if files.len() <= MAX_ENTRIES {
+        return;
+    }
+    files.sort();
+    for (_, path) in &files[..files.len() - MAX_ENTRIES] {
+        let _ = std::fs::remove_file(path);
+    }
+}
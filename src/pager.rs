@@ -0,0 +1,86 @@
+// Pager - long listings go through $PAGER when they're printed to a terminal
+//
+// `proj tasks`, `proj session list`, and `proj docs show` build their output first and
+// hand it to `page`. Output that fits on the screen, that isn't going to a terminal, or
+// that's porcelain is printed as usual. Like git, the pager is $PAGER (default `less`)
+// with LESS=FRX unless LESS is already set, so colors survive and short pages don't
+// wait for a keypress. `--no-pager`, an empty $PAGER, or PAGER=cat turn it off.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use crate::porcelain;
+
+static DISABLED: AtomicBool = AtomicBool::new(false);
+
+/// Print everything directly for this run (--no-pager)
+pub fn disable() {
+    DISABLED.store(true, Ordering::Relaxed);
+}
+
+/// Show output through the pager if it's longer than the terminal, else print it
+pub fn page(output: &str) {
+    let Some(pager) = pager_for(output) else {
+        print_all(output);
+        return;
+    };
+    let mut command = shell_command(&pager);
+    if std::env::var_os("LESS").is_none() {
+        command.env("LESS", "FRX");
+    }
+    let mut child = match command.stdin(Stdio::piped()).spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            tracing::debug!("pager '{}' not started: {}", pager, e);
+            print_all(output);
+            return;
+        }
+    };
+    // Quitting the pager early closes its input; the rest of the output isn't wanted
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(output.as_bytes());
+    }
+    // 127: the shell couldn't find the pager
+    if child.wait().is_ok_and(|status| status.code() == Some(127)) {
+        print_all(output);
+    }
+}
+
+/// Print output, stopping quietly if the reader goes away (`| head`)
+fn print_all(output: &str) {
+    let _ = std::io::stdout().write_all(output.as_bytes());
+}
+
+/// The pager command to use for this output, if any
+fn pager_for(output: &str) -> Option<String> {
+    if DISABLED.load(Ordering::Relaxed) || porcelain::enabled() || !atty::is(atty::Stream::Stdout) {
+        return None;
+    }
+    let rows = crossterm::terminal::size().ok()?.1 as usize;
+    if rows == 0 || output.lines().count() < rows {
+        return None;
+    }
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less".to_string());
+    let pager = pager.trim();
+    if pager.is_empty() || pager == "cat" {
+        return None;
+    }
+    Some(pager.to_string())
+}
+
+/// $PAGER may carry options ("less -S"), so it's run through the shell
+#[cfg(unix)]
+fn shell_command(pager: &str) -> Command {
+    let mut command = Command::new("sh");
+    command.arg("-c").arg(pager);
+    command
+}
+
+#[cfg(not(unix))]
+fn shell_command(pager: &str) -> Command {
+    let mut parts = pager.split_whitespace();
+    let mut command = Command::new(parts.next().unwrap_or("more"));
+    command.args(parts);
+    command
+}
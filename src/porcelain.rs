@@ -0,0 +1,39 @@
+// Porcelain output - stable, tab-separated lines for scripts (--porcelain)
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Switch status, tasks, session, and check to porcelain output (or back) for this run
+pub fn set(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Print one record. Fields are tab-separated; empty fields print as "-" and
+/// tabs or newlines inside a field become spaces, so every record is one line.
+pub fn print(fields: &[&str]) {
+    let fields: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.is_empty() {
+                "-".to_string()
+            } else {
+                field.replace(['\t', '\n', '\r'], " ")
+            }
+        })
+        .collect();
+    println!("{}", fields.join("\t"));
+}
+
+/// Print a progress line. Goes to stderr in porcelain mode so stdout only has records.
+pub fn note(line: impl std::fmt::Display) {
+    if enabled() {
+        eprintln!("{}", line);
+    } else {
+        println!("{}", line);
+    }
+}
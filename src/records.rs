@@ -0,0 +1,346 @@
+// Records - tracking rows keyed by UUID, for copying them between databases
+//
+// A record is a row with its local primary key left out and every reference replaced
+// by the key of the record it points at, so it means the same thing in any copy of
+// the database. `proj sync` and `proj merge` read and write records through here.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::Result;
+use rusqlite::types::Value;
+use rusqlite::Connection;
+
+use crate::database::table_columns;
+use crate::encoding::hex;
+
+/// Column → value, with references replaced by the key of the record they point at
+pub type Record = BTreeMap<String, Value>;
+
+/// How one table's rows become records
+pub struct TableSpec {
+    /// Table name as users see it (and sync's snapshot file name)
+    pub name: &'static str,
+    pub table: &'static str,
+    pub pk: &'static str,
+    /// Column holding the machine-independent record key
+    pub key: &'static str,
+    /// Rows that are shared; the rest belong to the database that has them
+    pub filter: &'static str,
+    /// Foreign keys into tables listed earlier in `TABLES`
+    pub refs: &'static [(&'static str, &'static TableSpec)],
+    /// Foreign keys into this same table
+    pub self_refs: &'static [&'static str],
+    /// Columns that only mean something in the database that wrote them
+    pub local_only: &'static [&'static str],
+    /// Timestamps that move forward when a record changes; the newer version wins
+    pub touched: &'static [&'static str],
+}
+
+pub const SESSIONS: TableSpec = TableSpec {
+    name: "sessions",
+    table: "sessions",
+    pk: "session_id",
+    key: "uuid",
+    // An open session belongs to whoever is running it
+    filter: "status NOT IN ('active', 'paused')",
+    refs: &[],
+    self_refs: &[],
+    local_only: &["full_context_shown"],
+    touched: &["started_at", "ended_at"],
+};
+
+pub const TASKS: TableSpec = TableSpec {
+    name: "tasks",
+    table: "tasks",
+    pk: "task_id",
+    key: "uuid",
+    filter: "1 = 1",
+    refs: &[("session_id", &SESSIONS)],
+    self_refs: &["parent_task_id"],
+    local_only: &[],
+    touched: &["created_at", "completed_at"],
+};
+
+pub const DECISIONS: TableSpec = TableSpec {
+    name: "decisions",
+    table: "decisions",
+    pk: "decision_id",
+    key: "uuid",
+    filter: "1 = 1",
+    refs: &[("session_id", &SESSIONS)],
+    self_refs: &["superseded_by"],
+    local_only: &[],
+    touched: &["created_at"],
+};
+
+pub const NOTES: TableSpec = TableSpec {
+    name: "notes",
+    table: "context_notes",
+    pk: "note_id",
+    key: "uuid",
+    filter: "1 = 1",
+    refs: &[("session_id", &SESSIONS)],
+    self_refs: &[],
+    local_only: &[],
+    touched: &["created_at", "updated_at"],
+};
+
+pub const BLOCKERS: TableSpec = TableSpec {
+    name: "blockers",
+    table: "blockers",
+    pk: "blocker_id",
+    key: "uuid",
+    filter: "1 = 1",
+    refs: &[("session_id", &SESSIONS), ("related_task_id", &TASKS)],
+    self_refs: &[],
+    local_only: &[],
+    touched: &["created_at", "resolved_at"],
+};
+
+pub const QUESTIONS: TableSpec = TableSpec {
+    name: "questions",
+    table: "questions",
+    pk: "question_id",
+    key: "uuid",
+    filter: "1 = 1",
+    refs: &[("session_id", &SESSIONS)],
+    self_refs: &[],
+    local_only: &[],
+    touched: &["created_at", "answered_at"],
+};
+
+pub const COMMITS: TableSpec = TableSpec {
+    name: "commits",
+    table: "git_commits",
+    pk: "commit_id",
+    key: "hash",
+    filter: "1 = 1",
+    refs: &[],
+    self_refs: &[],
+    local_only: &["synced_at"],
+    touched: &["committed_at"],
+};
+
+/// Every shared table; referenced tables come first
+pub const TABLES: &[&TableSpec] = &[
+    &SESSIONS, &TASKS, &DECISIONS, &NOTES, &BLOCKERS, &QUESTIONS, &COMMITS,
+];
+
+/// A self-reference written once every record of its table is in: (local ID, column, key)
+pub type DeferredRef = (i64, &'static str, String);
+
+/// Records of one table matching `filter` as (local ID, key, record), in ID order
+pub fn read_records(
+    conn: &Connection,
+    spec: &TableSpec,
+    filter: &str,
+) -> Result<Vec<(i64, String, Record)>> {
+    let mut ref_keys: HashMap<&str, HashMap<i64, String>> = HashMap::new();
+    for (col, target) in spec.refs {
+        ref_keys.insert(col, key_map(conn, target)?);
+    }
+    for col in spec.self_refs {
+        ref_keys.insert(col, key_map(conn, spec)?);
+    }
+
+    let sql = format!(
+        "SELECT * FROM {} WHERE {} AND {} IS NOT NULL ORDER BY {}",
+        spec.table, filter, spec.key, spec.pk
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let columns: Vec<String> = stmt.column_names().iter().map(|c| c.to_string()).collect();
+
+    let rows = stmt
+        .query_map([], |row| {
+            (0..columns.len())
+                .map(|i| row.get::<_, Value>(i))
+                .collect::<Result<Vec<_>, _>>()
+        })?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut records = Vec::new();
+    for row in rows {
+        let mut pk = None;
+        let mut record = Record::new();
+        for (col, value) in columns.iter().zip(row) {
+            if col == spec.pk {
+                if let Value::Integer(id) = value {
+                    pk = Some(id);
+                }
+                continue;
+            }
+            if spec.local_only.contains(&col.as_str()) {
+                continue;
+            }
+            let value = match (ref_keys.get(col.as_str()), value) {
+                // References point at keys, never at local IDs
+                (Some(keys), Value::Integer(id)) => keys
+                    .get(&id)
+                    .map_or(Value::Null, |k| Value::Text(k.clone())),
+                (Some(_), _) => Value::Null,
+                (None, value) => value,
+            };
+            record.insert(col.clone(), value);
+        }
+
+        let key = match record.get(spec.key) {
+            Some(Value::Text(key)) => key.clone(),
+            _ => continue,
+        };
+        if let Some(pk) = pk {
+            records.push((pk, key, record));
+        }
+    }
+    Ok(records)
+}
+
+/// Insert a record, or overwrite an existing one, resolving references to local IDs.
+/// Self-references are left empty and added to `deferred` for `fill_self_refs`.
+pub fn write_record(
+    conn: &Connection,
+    spec: &TableSpec,
+    existing: Option<i64>,
+    record: &Record,
+    deferred: &mut Vec<DeferredRef>,
+) -> Result<i64> {
+    let columns = table_columns(conn, "main", spec.table)?;
+    let mut cols = Vec::new();
+    let mut values: Vec<Value> = Vec::new();
+    let mut self_refs: Vec<(&'static str, String)> = Vec::new();
+
+    for col in &columns {
+        if col == spec.pk || spec.local_only.contains(&col.as_str()) {
+            continue;
+        }
+        let Some(value) = record.get(col.as_str()) else {
+            continue;
+        };
+
+        let value = if let Some((_, target)) = spec.refs.iter().find(|(c, _)| c == col) {
+            match value {
+                Value::Text(key) => {
+                    lookup_pk(conn, target, key).map_or(Value::Null, Value::Integer)
+                }
+                _ => Value::Null,
+            }
+        } else if let Some(self_col) = spec.self_refs.iter().find(|c| *c == col) {
+            // Filled in once every record of this table is in
+            if let Value::Text(key) = value {
+                self_refs.push((self_col, key.clone()));
+            }
+            Value::Null
+        } else {
+            value.clone()
+        };
+
+        cols.push(col.as_str());
+        values.push(value);
+    }
+
+    let pk = match existing {
+        Some(pk) => {
+            let assignments: Vec<String> = cols
+                .iter()
+                .enumerate()
+                .map(|(i, c)| format!("{} = ?{}", c, i + 1))
+                .collect();
+            values.push(Value::Integer(pk));
+            conn.execute(
+                &format!(
+                    "UPDATE {} SET {} WHERE {} = ?{}",
+                    spec.table,
+                    assignments.join(", "),
+                    spec.pk,
+                    values.len()
+                ),
+                rusqlite::params_from_iter(values.iter()),
+            )?;
+            pk
+        }
+        None => {
+            let placeholders: Vec<String> = (1..=values.len()).map(|i| format!("?{}", i)).collect();
+            conn.execute(
+                &format!(
+                    "INSERT INTO {} ({}) VALUES ({})",
+                    spec.table,
+                    cols.join(", "),
+                    placeholders.join(", ")
+                ),
+                rusqlite::params_from_iter(values.iter()),
+            )?;
+            conn.last_insert_rowid()
+        }
+    };
+
+    deferred.extend(self_refs.into_iter().map(|(col, key)| (pk, col, key)));
+    Ok(pk)
+}
+
+/// Point the self-references left by `write_record` at their records
+pub fn fill_self_refs(
+    conn: &Connection,
+    spec: &TableSpec,
+    deferred: Vec<DeferredRef>,
+) -> Result<()> {
+    for (pk, col, target) in deferred {
+        let target_pk = lookup_pk(conn, spec, &target);
+        conn.execute(
+            &format!(
+                "UPDATE {} SET {} = ?1 WHERE {} = ?2",
+                spec.table, col, spec.pk
+            ),
+            rusqlite::params![target_pk, pk],
+        )?;
+    }
+    Ok(())
+}
+
+/// Local ID → key for the shared records of a table
+fn key_map(conn: &Connection, spec: &TableSpec) -> Result<HashMap<i64, String>> {
+    let sql = format!(
+        "SELECT {}, {} FROM {} WHERE {} AND {} IS NOT NULL",
+        spec.pk, spec.key, spec.table, spec.filter, spec.key
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let map = stmt
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<HashMap<_, _>, _>>()?;
+    Ok(map)
+}
+
+/// Local ID of the record with `key`
+pub fn lookup_pk(conn: &Connection, spec: &TableSpec, key: &str) -> Option<i64> {
+    conn.query_row(
+        &format!(
+            "SELECT {} FROM {} WHERE {} = ?1",
+            spec.pk, spec.table, spec.key
+        ),
+        [key],
+        |row| row.get(0),
+    )
+    .ok()
+}
+
+/// Latest lifecycle timestamp of a record
+pub fn last_touched(spec: &TableSpec, record: &Record) -> String {
+    spec.touched
+        .iter()
+        .filter_map(|col| match record.get(*col) {
+            Some(Value::Text(ts)) => Some(ts.as_str()),
+            _ => None,
+        })
+        .max()
+        .unwrap_or("")
+        .to_string()
+}
+
+/// A value as plain text (empty for NULL, hex for blobs)
+pub fn value_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::Integer(i) => i.to_string(),
+        Value::Real(f) => f.to_string(),
+        Value::Text(s) => s.clone(),
+        Value::Blob(b) => hex(b),
+    }
+}
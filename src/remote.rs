@@ -0,0 +1,598 @@
+// Remote backup targets - S3, WebDAV, and SFTP uploads with retention
+
+use std::path::Path;
+use std::process::Command;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use chrono::{NaiveDateTime, Utc};
+use sha2::{Digest, Sha256};
+
+use crate::config::RemoteConfig;
+use crate::encoding::{base64_encode, hex};
+use crate::http;
+
+/// A parsed remote backup location. Files for a project live under `<location>/<project>/`.
+pub enum Remote {
+    S3 {
+        bucket: String,
+        prefix: String,
+        region: String,
+        endpoint: Option<String>,
+        access_key_id: String,
+        secret_access_key: String,
+    },
+    WebDav {
+        /// Base URL with a trailing slash
+        base_url: String,
+        username: Option<String>,
+        password: Option<String>,
+    },
+    Sftp {
+        /// user@host
+        target: String,
+        port: Option<u16>,
+        path: String,
+    },
+}
+
+impl Remote {
+    /// Build a remote from its configuration, filling in credentials from the environment
+    pub fn from_config(config: &RemoteConfig) -> Result<Self> {
+        let url = config.url.trim();
+        let Some((scheme, rest)) = url.split_once("://") else {
+            bail!(
+                "Invalid remote '{}'. Use s3://, webdav://, or sftp:// URLs.",
+                url
+            );
+        };
+
+        match scheme {
+            "s3" => {
+                let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+                if bucket.is_empty() {
+                    bail!("Remote '{}' has no bucket", url);
+                }
+                let access_key_id = config
+                    .access_key_id
+                    .clone()
+                    .or_else(|| std::env::var("AWS_ACCESS_KEY_ID").ok())
+                    .with_context(|| {
+                        format!(
+                            "No access key for {}. Set access_key_id in ~/.proj/backup.json or AWS_ACCESS_KEY_ID.",
+                            url
+                        )
+                    })?;
+                let secret_access_key = config
+                    .secret_access_key
+                    .clone()
+                    .or_else(|| std::env::var("AWS_SECRET_ACCESS_KEY").ok())
+                    .with_context(|| {
+                        format!(
+                            "No secret key for {}. Set secret_access_key in ~/.proj/backup.json or AWS_SECRET_ACCESS_KEY.",
+                            url
+                        )
+                    })?;
+                let region = config
+                    .region
+                    .clone()
+                    .or_else(|| std::env::var("AWS_REGION").ok())
+                    .or_else(|| std::env::var("AWS_DEFAULT_REGION").ok())
+                    .unwrap_or_else(|| "us-east-1".to_string());
+
+                Ok(Remote::S3 {
+                    bucket: bucket.to_string(),
+                    prefix: with_trailing_slash(prefix.trim_start_matches('/')),
+                    region,
+                    endpoint: config
+                        .endpoint
+                        .as_ref()
+                        .map(|e| e.trim_end_matches('/').to_string()),
+                    access_key_id,
+                    secret_access_key,
+                })
+            }
+            "webdav" | "webdav+http" | "http" | "https" => {
+                let http_scheme = match scheme {
+                    "webdav" => "https",
+                    "webdav+http" => "http",
+                    other => other,
+                };
+                Ok(Remote::WebDav {
+                    base_url: with_trailing_slash(&format!("{}://{}", http_scheme, rest)),
+                    username: config
+                        .username
+                        .clone()
+                        .or_else(|| std::env::var("PROJ_WEBDAV_USER").ok()),
+                    password: config
+                        .password
+                        .clone()
+                        .or_else(|| std::env::var("PROJ_WEBDAV_PASSWORD").ok()),
+                })
+            }
+            "sftp" => {
+                let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+                let (target, port) = match authority.rsplit_once(':') {
+                    Some((host, port)) => (
+                        host.to_string(),
+                        Some(
+                            port.parse()
+                                .with_context(|| format!("Invalid port in {}", url))?,
+                        ),
+                    ),
+                    None => (authority.to_string(), None),
+                };
+                // sftp://host/backups is relative to the login directory; sftp://host//srv is absolute
+                let path = if path.is_empty() { "." } else { path };
+                Ok(Remote::Sftp {
+                    target,
+                    port,
+                    path: path.trim_end_matches('/').to_string(),
+                })
+            }
+            other => bail!(
+                "Unsupported remote scheme '{}'. Use s3://, webdav://, or sftp://.",
+                other
+            ),
+        }
+    }
+
+    /// Upload a local file as `<project>/<name>`
+    pub fn upload(&self, project: &str, local: &Path) -> Result<()> {
+        let name = local
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        match self {
+            Remote::S3 { prefix, .. } => {
+                let body = std::fs::read(local)
+                    .with_context(|| format!("Failed to read {}", local.display()))?;
+                let key = format!("{}{}/{}", prefix, project, name);
+                self.s3_request("PUT", &key, &[], &body)?;
+            }
+            Remote::WebDav { base_url, .. } => {
+                let body = std::fs::read(local)
+                    .with_context(|| format!("Failed to read {}", local.display()))?;
+                // MKCOL fails harmlessly when the collection already exists
+                let _ = self.webdav_request("MKCOL", base_url, &[], None);
+                let dir = format!("{}{}/", base_url, uri_encode(project, false));
+                let _ = self.webdav_request("MKCOL", &dir, &[], None);
+                let url = format!("{}{}", dir, uri_encode(&name, false));
+                self.webdav_request("PUT", &url, &body, None)?;
+            }
+            Remote::Sftp { target, port, path } => {
+                let dir = format!("{}/{}", path, project);
+                ssh(target, *port, &format!("mkdir -p {}", shell_quote(&dir)))?;
+
+                let mut cmd = Command::new("scp");
+                cmd.args(["-q", "-o", "BatchMode=yes"]);
+                if let Some(port) = port {
+                    cmd.args(["-P", &port.to_string()]);
+                }
+                let output = cmd
+                    .arg(local)
+                    .arg(format!(
+                        "{}:{}",
+                        target,
+                        shell_quote(&format!("{}/{}", dir, name))
+                    ))
+                    .output()
+                    .with_context(|| "Failed to run scp")?;
+                if !output.status.success() {
+                    bail!(
+                        "scp failed: {}",
+                        String::from_utf8_lossy(&output.stderr).trim()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// File names stored for a project
+    pub fn list(&self, project: &str) -> Result<Vec<String>> {
+        match self {
+            Remote::S3 { prefix, .. } => {
+                let list_prefix = format!("{}{}/", prefix, project);
+                let mut names = Vec::new();
+                let mut token: Option<String> = None;
+                loop {
+                    let mut query = vec![
+                        ("list-type", "2".to_string()),
+                        ("prefix", list_prefix.clone()),
+                    ];
+                    if let Some(t) = &token {
+                        query.push(("continuation-token", t.clone()));
+                    }
+                    let body = self.s3_request("GET", "", &query, &[])?.into_string()?;
+                    for key in xml_values(&body, "Key") {
+                        if let Some(name) = key.strip_prefix(&list_prefix) {
+                            names.push(name.to_string());
+                        }
+                    }
+                    token = xml_values(&body, "NextContinuationToken")
+                        .into_iter()
+                        .next();
+                    if token.is_none() {
+                        break;
+                    }
+                }
+                Ok(names)
+            }
+            Remote::WebDav { base_url, .. } => {
+                let dir = format!("{}{}/", base_url, uri_encode(project, false));
+                let body = match self.webdav_request("PROPFIND", &dir, &[], Some("1")) {
+                    Ok(response) => response.into_string()?,
+                    Err(_) => return Ok(Vec::new()),
+                };
+                Ok(xml_values(&body, "href")
+                    .iter()
+                    .filter(|href| !href.ends_with('/'))
+                    .filter_map(|href| href.rsplit('/').next())
+                    .map(percent_decode)
+                    .collect())
+            }
+            Remote::Sftp { target, port, path } => {
+                let dir = format!("{}/{}", path, project);
+                let output = ssh(
+                    target,
+                    *port,
+                    &format!("ls -1 {} 2>/dev/null || true", shell_quote(&dir)),
+                )?;
+                Ok(output
+                    .lines()
+                    .map(|l| l.trim().to_string())
+                    .filter(|l| !l.is_empty())
+                    .collect())
+            }
+        }
+    }
+
+    /// Delete `<project>/<name>`
+    pub fn delete(&self, project: &str, name: &str) -> Result<()> {
+        match self {
+            Remote::S3 { prefix, .. } => {
+                let key = format!("{}{}/{}", prefix, project, name);
+                self.s3_request("DELETE", &key, &[], &[])?;
+            }
+            Remote::WebDav { base_url, .. } => {
+                let url = format!(
+                    "{}{}/{}",
+                    base_url,
+                    uri_encode(project, false),
+                    uri_encode(name, false)
+                );
+                self.webdav_request("DELETE", &url, &[], None)?;
+            }
+            Remote::Sftp { target, port, path } => {
+                let file = format!("{}/{}/{}", path, project, name);
+                ssh(target, *port, &format!("rm -f {}", shell_quote(&file)))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Signed (AWS Signature Version 4) S3 request
+    fn s3_request(
+        &self,
+        method: &str,
+        key: &str,
+        query: &[(&str, String)],
+        body: &[u8],
+    ) -> Result<ureq::Response> {
+        let Remote::S3 {
+            bucket,
+            region,
+            endpoint,
+            access_key_id,
+            secret_access_key,
+            ..
+        } = self
+        else {
+            bail!("Not an S3 remote");
+        };
+
+        // Custom endpoints (MinIO, etc.) use path-style addressing
+        let (base, host, canonical_uri) = match endpoint {
+            Some(endpoint) => {
+                let host = endpoint
+                    .split_once("://")
+                    .map_or(endpoint.as_str(), |(_, h)| h)
+                    .to_string();
+                (
+                    endpoint.clone(),
+                    host,
+                    format!("/{}/{}", bucket, uri_encode(key, true)),
+                )
+            }
+            None => {
+                let host = format!("{}.s3.{}.amazonaws.com", bucket, region);
+                (
+                    format!("https://{}", host),
+                    host,
+                    format!("/{}", uri_encode(key, true)),
+                )
+            }
+        };
+
+        let mut params: Vec<(String, String)> = query
+            .iter()
+            .map(|(k, v)| (uri_encode(k, false), uri_encode(v, false)))
+            .collect();
+        params.sort();
+        let canonical_query = params
+            .iter()
+            .map(|(k, v)| format!("{}={}", k, v))
+            .collect::<Vec<_>>()
+            .join("&");
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex(&Sha256::digest(body));
+
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method, canonical_uri, canonical_query, host, payload_hash, amz_date, payload_hash
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+            amz_date,
+            scope,
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let k_date = hmac_sha256(
+            format!("AWS4{}", secret_access_key).as_bytes(),
+            date.as_bytes(),
+        );
+        let k_region = hmac_sha256(&k_date, region.as_bytes());
+        let k_service = hmac_sha256(&k_region, b"s3");
+        let k_signing = hmac_sha256(&k_service, b"aws4_request");
+        let signature = hex(&hmac_sha256(&k_signing, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            access_key_id, scope, signature
+        );
+
+        let mut url = format!("{}{}", base, canonical_uri);
+        if !canonical_query.is_empty() {
+            url = format!("{}?{}", url, canonical_query);
+        }
+
+        let request = agent(&url)
+            .request(method, &url)
+            .set("x-amz-date", &amz_date)
+            .set("x-amz-content-sha256", &payload_hash)
+            .set("Authorization", &authorization);
+
+        send(request, body).with_context(|| format!("S3 {} {} failed", method, url))
+    }
+
+    fn webdav_request(
+        &self,
+        method: &str,
+        url: &str,
+        body: &[u8],
+        depth: Option<&str>,
+    ) -> Result<ureq::Response> {
+        let Remote::WebDav {
+            username, password, ..
+        } = self
+        else {
+            bail!("Not a WebDAV remote");
+        };
+
+        let mut request = agent(url).request(method, url);
+        if let Some(user) = username {
+            let credentials = format!("{}:{}", user, password.as_deref().unwrap_or(""));
+            request = request.set(
+                "Authorization",
+                &format!("Basic {}", base64_encode(credentials.as_bytes())),
+            );
+        }
+        if let Some(depth) = depth {
+            request = request.set("Depth", depth);
+        }
+
+        send(request, body).with_context(|| format!("WebDAV {} {} failed", method, url))
+    }
+}
+
+/// Remove the backups that fall outside the retention policy; returns the deleted names.
+/// The newest `keep_last` per kind (tracking, docs) are always kept.
+pub fn prune(
+    remote: &Remote,
+    project: &str,
+    keep_last: Option<usize>,
+    keep_days: Option<u32>,
+) -> Result<Vec<String>> {
+    if keep_last.is_none() && keep_days.is_none() {
+        return Ok(Vec::new());
+    }
+
+    let cutoff = keep_days.map(|d| (Utc::now() - chrono::Duration::days(d as i64)).naive_utc());
+    let mut deleted = Vec::new();
+
+    for kind in ["tracking", "docs"] {
+        let marker = format!("{}_{}_", project, kind);
+        let mut backups: Vec<(NaiveDateTime, String)> = remote
+            .list(project)?
+            .into_iter()
+            .filter_map(|name| {
+                let stamp = name.strip_prefix(&marker)?.get(..15)?;
+                let time = NaiveDateTime::parse_from_str(stamp, "%Y%m%d_%H%M%S").ok()?;
+                Some((time, name))
+            })
+            .collect();
+        backups.sort_by_key(|b| std::cmp::Reverse(b.0));
+
+        for (i, (time, name)) in backups.iter().enumerate() {
+            if i < keep_last.unwrap_or(0) {
+                continue;
+            }
+            let expired = match cutoff {
+                Some(cutoff) => *time < cutoff,
+                None => true,
+            };
+            if expired {
+                remote.delete(project, name)?;
+                deleted.push(name.clone());
+            }
+        }
+    }
+
+    Ok(deleted)
+}
+
+/// Run a command on the SFTP host over ssh, returning stdout
+fn ssh(target: &str, port: Option<u16>, remote_command: &str) -> Result<String> {
+    let mut cmd = Command::new("ssh");
+    cmd.args(["-o", "BatchMode=yes"]);
+    if let Some(port) = port {
+        cmd.args(["-p", &port.to_string()]);
+    }
+    let output = cmd
+        .arg(target)
+        .arg(remote_command)
+        .output()
+        .with_context(|| "Failed to run ssh")?;
+    if !output.status.success() {
+        bail!(
+            "ssh failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+fn agent(url: &str) -> ureq::Agent {
+    http::agent(url, Duration::from_secs(120))
+}
+
+fn send(request: ureq::Request, body: &[u8]) -> Result<ureq::Response> {
+    let result = if body.is_empty() {
+        request.call()
+    } else {
+        request.send_bytes(body)
+    };
+    match result {
+        Ok(response) => Ok(response),
+        Err(ureq::Error::Status(code, response)) => {
+            let detail = response.into_string().unwrap_or_default();
+            let message = xml_values(&detail, "Message").into_iter().next();
+            bail!(
+                "HTTP {}{}",
+                code,
+                message.map(|m| format!(": {}", m)).unwrap_or_default()
+            )
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Text content of every `<tag>` element, ignoring namespace prefixes and case
+fn xml_values(xml: &str, tag: &str) -> Vec<String> {
+    // ASCII-only lowercasing keeps byte offsets valid for slicing `xml`
+    let lower = xml.to_ascii_lowercase();
+    let tag = tag.to_ascii_lowercase();
+    let mut values = Vec::new();
+    let mut pos = 0;
+
+    while let Some(found) = lower[pos..].find('<') {
+        let start = pos + found + 1;
+        let Some(end) = lower[start..].find('>').map(|e| start + e) else {
+            break;
+        };
+        let name = &lower[start..end];
+        let local = name.rsplit(':').next().unwrap_or(name);
+        pos = end + 1;
+        if local != tag {
+            continue;
+        }
+        if let Some(close) = lower[pos..].find("</") {
+            values.push(
+                xml[pos..pos + close]
+                    .replace("&amp;", "&")
+                    .replace("&lt;", "<")
+                    .replace("&gt;", ">")
+                    .replace("&quot;", "\"")
+                    .replace("&apos;", "'"),
+            );
+        }
+    }
+    values
+}
+
+/// HMAC-SHA256 (also used to sign webhook payloads)
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = if key.len() > BLOCK_SIZE {
+        Sha256::digest(key).to_vec()
+    } else {
+        key.to_vec()
+    };
+    block.resize(BLOCK_SIZE, 0);
+
+    let inner_pad: Vec<u8> = block.iter().map(|b| b ^ 0x36).collect();
+    let outer_pad: Vec<u8> = block.iter().map(|b| b ^ 0x5c).collect();
+
+    let mut inner = Sha256::new();
+    inner.update(&inner_pad);
+    inner.update(data);
+
+    let mut outer = Sha256::new();
+    outer.update(&outer_pad);
+    outer.update(inner.finalize());
+    outer.finalize().to_vec()
+}
+
+/// Percent-encode everything but unreserved characters (and `/` when `keep_slash`)
+fn uri_encode(s: &str, keep_slash: bool) -> String {
+    let mut out = String::new();
+    for b in s.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                out.push(b as char)
+            }
+            b'/' if keep_slash => out.push('/'),
+            _ => out.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    out
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = |b: u8| (b as char).to_digit(16);
+            if let (Some(hi), Some(lo)) = (hex(bytes[i + 1]), hex(bytes[i + 2])) {
+                out.push((hi * 16 + lo) as u8);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).to_string()
+}
+
+/// Quote for a POSIX shell on the remote host
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "'\\''"))
+}
+
+fn with_trailing_slash(s: &str) -> String {
+    if s.is_empty() || s.ends_with('/') {
+        s.to_string()
+    } else {
+        format!("{}/", s)
+    }
+}
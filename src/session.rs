@@ -1,22 +1,20 @@
-// Session management - get_or_create_session and related functions
+// Session management - proj_core's session functions, plus the terminal prompt for stale sessions
+//
+// proj_core never prompts; it reports a stale session and applies whatever choice it's given.
+// The wrappers here ask the user when auto_close_policy is "prompt" and there's a terminal.
 
 use anyhow::Result;
-use chrono::{DateTime, Duration, Utc};
+use chrono::Utc;
+use colored::Colorize;
+use dialoguer::{Input, Select};
 use rusqlite::Connection;
 
-use crate::models::Session;
-
-/// Stale session threshold in hours
-const STALE_SESSION_HOURS: i64 = 8;
+pub use proj_core::session::*;
 
-/// Result of get_or_create_session that indicates if a stale session was closed
-pub struct SessionResult {
-    pub session: Session,
-    pub auto_closed_session: Option<Session>,
-}
+use crate::models::Session;
 
 /// Gets the currently active session, or creates a new one if none exists.
-/// If an active session is stale (8+ hours old), it will be auto-closed.
+/// If an active session is stale, it is handled according to the project's auto_close_policy.
 pub fn get_or_create_session(conn: &Connection) -> Result<Session> {
     let result = get_or_create_session_with_info(conn)?;
     Ok(result.session)
@@ -24,231 +22,73 @@ pub fn get_or_create_session(conn: &Connection) -> Result<Session> {
 
 /// Gets the currently active session with info about whether a stale session was closed
 pub fn get_or_create_session_with_info(conn: &Connection) -> Result<SessionResult> {
-    // Try to get active session first
-    if let Some(session) = get_active_session(conn)? {
-        // Check if session is stale (8+ hours since started)
-        let now = Utc::now();
-        let session_age = now - session.started_at;
-
-        if session_age > Duration::hours(STALE_SESSION_HOURS) {
-            // Auto-close the stale session
-            auto_close_session(conn, session.session_id)?;
-            let closed_session = session;
-
-            // Create a new session
-            let new_session = create_session(conn)?;
-
-            return Ok(SessionResult {
-                session: new_session,
-                auto_closed_session: Some(closed_session),
-            });
-        }
-
-        return Ok(SessionResult {
+    match session_state(conn)? {
+        SessionState::Missing => Ok(SessionResult {
+            session: create_session(conn)?,
+            auto_closed_session: None,
+        }),
+        SessionState::Active(session) => Ok(SessionResult {
             session,
             auto_closed_session: None,
-        });
-    }
-
-    // No active session, create a new one
-    let session = create_session(conn)?;
-    Ok(SessionResult {
-        session,
-        auto_closed_session: None,
-    })
-}
-
-/// Auto-closes a stale session with a marker summary
-fn auto_close_session(conn: &Connection, session_id: i64) -> Result<()> {
-    conn.execute(
-        "UPDATE sessions SET ended_at = datetime('now'), status = 'completed', summary = '(auto-closed)' WHERE session_id = ?1",
-        [session_id],
-    )?;
-    Ok(())
-}
-
-/// Gets the currently active session if one exists
-pub fn get_active_session(conn: &Connection) -> Result<Option<Session>> {
-    let mut stmt = conn.prepare(
-        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary
-         FROM sessions
-         WHERE status = 'active'
-         ORDER BY started_at DESC
-         LIMIT 1"
-    )?;
-
-    let session = stmt.query_row([], |row| {
-        Ok(Session {
-            session_id: row.get(0)?,
-            started_at: parse_datetime(row.get::<_, String>(1)?),
-            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
-            agent: row.get(3)?,
-            summary: row.get(4)?,
-            files_touched: row.get(5)?,
-            status: row.get(6)?,
-            full_context_shown: row.get::<_, i32>(7)? != 0,
-            structured_summary: row.get(8)?,
-        })
-    });
-
-    match session {
-        Ok(s) => Ok(Some(s)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e.into()),
+        }),
+        SessionState::Stale(session, policy) => {
+            let choice = choose_stale_action(&session, &policy);
+            settle_stale_session(conn, session, choice)
+        }
     }
 }
 
-/// Creates a new session
-pub fn create_session(conn: &Connection) -> Result<Session> {
-    conn.execute(
-        "INSERT INTO sessions (status, full_context_shown) VALUES ('active', 0)",
-        [],
-    )?;
-
-    let session_id = conn.last_insert_rowid();
-
-    // Clean up any stale session warning markers
-    cleanup_warned_markers();
-
-    // Fetch the created session
-    let mut stmt = conn.prepare(
-        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary
-         FROM sessions
-         WHERE session_id = ?1"
-    )?;
-
-    let session = stmt.query_row([session_id], |row| {
-        Ok(Session {
-            session_id: row.get(0)?,
-            started_at: parse_datetime(row.get::<_, String>(1)?),
-            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
-            agent: row.get(3)?,
-            summary: row.get(4)?,
-            files_touched: row.get(5)?,
-            status: row.get(6)?,
-            full_context_shown: row.get::<_, i32>(7)? != 0,
-            structured_summary: row.get(8)?,
-        })
-    })?;
-
-    Ok(session)
-}
-
-/// Ends a session with a summary
-#[allow(dead_code)]
-pub fn end_session(conn: &Connection, session_id: i64, summary: &str) -> Result<()> {
-    conn.execute(
-        "UPDATE sessions SET ended_at = datetime('now'), status = 'completed', summary = ?1 WHERE session_id = ?2",
-        rusqlite::params![summary, session_id],
-    )?;
-    Ok(())
-}
-
-/// Ends a session with both plain and structured summaries
-pub fn end_session_with_structured(
+/// Applies the stale-session policy to a session.
+/// "prompt" falls back to "close" when there's no terminal to ask on.
+pub fn handle_stale_session(
     conn: &Connection,
-    session_id: i64,
-    summary: &str,
-    structured_summary: &str,
-) -> Result<()> {
-    conn.execute(
-        "UPDATE sessions SET ended_at = datetime('now'), status = 'completed', summary = ?1, structured_summary = ?2 WHERE session_id = ?3",
-        rusqlite::params![summary, structured_summary, session_id],
-    )?;
-    Ok(())
+    session: &Session,
+    policy: &StalePolicy,
+) -> Result<StaleOutcome> {
+    apply_stale_choice(conn, session, choose_stale_action(session, policy))
 }
 
-/// Marks a session as having shown full context
-pub fn mark_full_context_shown(conn: &Connection, session_id: i64) -> Result<()> {
-    conn.execute(
-        "UPDATE sessions SET full_context_shown = 1 WHERE session_id = ?1",
-        [session_id],
-    )?;
-    Ok(())
-}
-
-/// Clean up stale session warning markers from .tracking directory
-fn cleanup_warned_markers() {
-    let tracking_dir = std::path::Path::new(".tracking");
-    if !tracking_dir.exists() {
-        return;
-    }
-
-    // Remove any .warned_stale_* files
-    if let Ok(entries) = std::fs::read_dir(tracking_dir) {
-        for entry in entries.flatten() {
-            if let Some(name) = entry.file_name().to_str() {
-                if name.starts_with(".warned_stale_") {
-                    let _ = std::fs::remove_file(entry.path());
-                }
-            }
-        }
+fn choose_stale_action(session: &Session, policy: &StalePolicy) -> StaleChoice {
+    if policy.action == "prompt" && atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stderr)
+    {
+        prompt_stale_session(session)
+    } else {
+        policy.default_choice()
     }
 }
 
-/// Gets the last N completed sessions
-pub fn get_recent_sessions(conn: &Connection, limit: usize) -> Result<Vec<Session>> {
-    let mut stmt = conn.prepare(
-        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary
-         FROM sessions
-         ORDER BY started_at DESC
-         LIMIT ?1"
-    )?;
-
-    let sessions = stmt.query_map([limit as i64], |row| {
-        Ok(Session {
-            session_id: row.get(0)?,
-            started_at: parse_datetime(row.get::<_, String>(1)?),
-            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
-            agent: row.get(3)?,
-            summary: row.get(4)?,
-            files_touched: row.get(5)?,
-            status: row.get(6)?,
-            full_context_shown: row.get::<_, i32>(7)? != 0,
-            structured_summary: row.get(8)?,
-        })
-    })?;
-
-    sessions
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| e.into())
-}
-
-/// Gets the last completed session
-pub fn get_last_completed_session(conn: &Connection) -> Result<Option<Session>> {
-    let mut stmt = conn.prepare(
-        "SELECT session_id, started_at, ended_at, agent, summary, files_touched, status, full_context_shown, structured_summary
-         FROM sessions
-         WHERE status = 'completed'
-         ORDER BY ended_at DESC
-         LIMIT 1"
-    )?;
-
-    let session = stmt.query_row([], |row| {
-        Ok(Session {
-            session_id: row.get(0)?,
-            started_at: parse_datetime(row.get::<_, String>(1)?),
-            ended_at: row.get::<_, Option<String>>(2)?.map(parse_datetime),
-            agent: row.get(3)?,
-            summary: row.get(4)?,
-            files_touched: row.get(5)?,
-            status: row.get(6)?,
-            full_context_shown: row.get::<_, i32>(7)? != 0,
-            structured_summary: row.get(8)?,
-        })
-    });
-
-    match session {
-        Ok(s) => Ok(Some(s)),
-        Err(rusqlite::Error::QueryReturnedNoRows) => Ok(None),
-        Err(e) => Err(e.into()),
+/// Ask what to do with a stale session
+fn prompt_stale_session(session: &Session) -> StaleChoice {
+    let hours = (Utc::now() - session.resumed_at.unwrap_or(session.started_at)).num_hours();
+    eprintln!(
+        "{} Session #{} has been open for {} hours.",
+        "⚠".yellow(),
+        session.session_id,
+        hours
+    );
+
+    let options = [
+        "Close it with a placeholder summary",
+        "Write a summary and close it",
+        "Abandon it",
+        "Keep it open",
+    ];
+    let choice = Select::new()
+        .with_prompt("What should happen to it?")
+        .items(&options)
+        .default(0)
+        .interact()
+        .unwrap_or(0);
+
+    match choice {
+        1 => StaleChoice::Summarize(
+            Input::new()
+                .with_prompt("Summary")
+                .interact_text()
+                .unwrap_or_default(),
+        ),
+        2 => StaleChoice::Abandon,
+        3 => StaleChoice::KeepOpen,
+        _ => StaleChoice::Close,
     }
 }
-
-/// Parse datetime string from SQLite
-fn parse_datetime(s: String) -> DateTime<Utc> {
-    // SQLite stores as "YYYY-MM-DD HH:MM:SS"
-    chrono::NaiveDateTime::parse_from_str(&s, "%Y-%m-%d %H:%M:%S")
-        .map(|dt| dt.and_utc())
-        .unwrap_or_else(|_| Utc::now())
-}
@@ -0,0 +1,141 @@
+// Stdin input - fields and whole log entries read from stdin
+//
+// Any text field of `proj log ...` and `proj note add` can be '-' to read it from
+// stdin, so multi-line rationales can come from a here-doc instead of shell quoting.
+// '-' alone in place of the first field reads entries instead, any number of them:
+// JSON (an object, an array of objects, or one object per line), or one entry per
+// line with tab-separated fields in argument order, where \n, \t, and \\ stand for a
+// newline, a tab, and a backslash.
+
+use std::sync::Mutex;
+
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+use crate::error::ProjError;
+
+/// Input saved when the command was queued for approval, read in place of stdin
+static SAVED: Mutex<Option<String>> = Mutex::new(None);
+
+/// The input saved with the command being run, if any. `proj approve` applies
+/// several commands in one process, so each one sets (or clears) its own.
+pub fn set_saved(text: Option<String>) {
+    *SAVED.lock().unwrap_or_else(|e| e.into_inner()) = text;
+}
+
+/// The input saved with the command being run
+pub fn saved() -> Option<String> {
+    SAVED.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Whether there's input waiting: stdin is piped, or input was saved with the command
+pub fn is_piped() -> bool {
+    saved().is_some() || !atty::is(atty::Stream::Stdin)
+}
+
+/// Everything on stdin
+pub fn read() -> Result<String> {
+    if let Some(text) = saved() {
+        return Ok(text);
+    }
+    std::io::read_to_string(std::io::stdin()).context("Failed to read stdin")
+}
+
+/// Replace the field given as '-' with what's on stdin. Only one field can be.
+pub fn resolve_fields(fields: &mut [&mut String]) -> Result<()> {
+    let mut dashes = fields.iter_mut().filter(|f| f.as_str() == "-");
+    let Some(field) = dashes.next() else {
+        return Ok(());
+    };
+    if dashes.next().is_some() {
+        return Err(ProjError::InvalidInput(
+            "Only one field can be read from stdin ('-')".to_string(),
+        )
+        .into());
+    }
+    let text = read()?.trim().to_string();
+    if text.is_empty() {
+        return Err(ProjError::InvalidInput("Nothing on stdin for '-'".to_string()).into());
+    }
+    **field = text;
+    Ok(())
+}
+
+/// The entries on stdin. JSON objects are used as they are; a delimited line's
+/// fields are named by `fields`, in order, and blank ones left out.
+pub fn entries<T: DeserializeOwned>(fields: &[&str]) -> Result<Vec<T>> {
+    let text = read()?;
+    let trimmed = text.trim_start();
+    let objects: Vec<Value> = if trimmed.starts_with('[') {
+        serde_json::from_str(trimmed).context("Invalid JSON array on stdin")?
+    } else if trimmed.starts_with('{') {
+        serde_json::Deserializer::from_str(trimmed)
+            .into_iter::<Value>()
+            .collect::<Result<_, _>>()
+            .context("Invalid JSON on stdin")?
+    } else {
+        text.lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| delimited(i + 1, line, fields))
+            .collect::<Result<_>>()?
+    };
+
+    if objects.is_empty() {
+        return Err(ProjError::NothingToDo("No entries on stdin.".to_string()).into());
+    }
+    objects
+        .into_iter()
+        .enumerate()
+        .map(|(i, object)| {
+            serde_json::from_value(object)
+                .map_err(|e| ProjError::InvalidInput(format!("Entry {}: {}", i + 1, e)).into())
+        })
+        .collect()
+}
+
+/// One tab-separated line as a JSON object
+fn delimited(line_number: usize, line: &str, fields: &[&str]) -> Result<Value> {
+    let parts: Vec<&str> = line.split('\t').collect();
+    if parts.len() > fields.len() {
+        return Err(ProjError::InvalidInput(format!(
+            "Line {}: {} tab-separated fields, expected at most {} ({})",
+            line_number,
+            parts.len(),
+            fields.len(),
+            fields.join(", ")
+        ))
+        .into());
+    }
+    let object: Map<String, Value> = fields
+        .iter()
+        .zip(parts)
+        .filter(|(_, part)| !part.trim().is_empty())
+        .map(|(field, part)| (field.to_string(), Value::String(unescape(part.trim()))))
+        .collect();
+    Ok(Value::Object(object))
+}
+
+/// Turn \n, \t, and \\ back into a newline, a tab, and a backslash
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('\\') => out.push('\\'),
+            Some(other) => {
+                out.push('\\');
+                out.push(other);
+            }
+            None => out.push('\\'),
+        }
+    }
+    out
+}
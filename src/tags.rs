@@ -0,0 +1,78 @@
+// Tags - free-form labels on tasks, decisions, and notes (item_tags table)
+
+use anyhow::Result;
+use rusqlite::Connection;
+
+/// Something that can carry tags
+#[derive(Debug, Clone, Copy)]
+pub enum Tagged {
+    Task(i64),
+    Decision(i64),
+    Note(i64),
+}
+
+impl Tagged {
+    /// item_tags column referencing this kind of item
+    fn column(&self) -> &'static str {
+        match self {
+            Tagged::Task(_) => "task_id",
+            Tagged::Decision(_) => "decision_id",
+            Tagged::Note(_) => "note_id",
+        }
+    }
+
+    fn id(&self) -> i64 {
+        match self {
+            Tagged::Task(id) | Tagged::Decision(id) | Tagged::Note(id) => *id,
+        }
+    }
+}
+
+/// Canonical form of a tag: lowercase, no leading '#', spaces as dashes
+pub fn normalize(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+    if tag.is_empty() {
+        return None;
+    }
+    Some(tag.split_whitespace().collect::<Vec<_>>().join("-"))
+}
+
+/// Tag an item; returns the normalized tags (already-present tags are kept once)
+pub fn add_tags(conn: &Connection, item: Tagged, tags: &[String]) -> Result<Vec<String>> {
+    let mut added = Vec::new();
+    for tag in tags.iter().filter_map(|t| normalize(t)) {
+        if added.contains(&tag) {
+            continue;
+        }
+        conn.execute(
+            &format!(
+                "INSERT INTO item_tags (tag, {col})
+                 SELECT ?1, ?2 WHERE NOT EXISTS (SELECT 1 FROM item_tags WHERE tag = ?1 AND {col} = ?2)",
+                col = item.column()
+            ),
+            rusqlite::params![tag, item.id()],
+        )?;
+        added.push(tag);
+    }
+    Ok(added)
+}
+
+/// Tags on an item, alphabetically
+pub fn tags_for(conn: &Connection, item: Tagged) -> Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!(
+        "SELECT tag FROM item_tags WHERE {} = ?1 ORDER BY tag",
+        item.column()
+    ))?;
+    let tags = stmt
+        .query_map([item.id()], |row| row.get(0))?
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok(tags)
+}
+
+/// Display form: "#backend #auth"
+pub fn format_tags(tags: &[String]) -> String {
+    tags.iter()
+        .map(|t| format!("#{}", t))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
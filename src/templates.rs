@@ -0,0 +1,207 @@
+// Templates - starting tasks, notes, docs sections, and config for `proj init --template`
+//
+// User templates live in ~/.proj/templates/<name>.json and take precedence over the
+// built-in ones of the same name.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ProjectConfig;
+use crate::database::write_transaction;
+use crate::error::ProjError;
+use crate::paths::get_global_dir;
+
+/// Templates that ship with proj, in the same format as user templates
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[("rust-cli", RUST_CLI)];
+
+const RUST_CLI: &str = r#"{
+  "description": "Command-line tool written in Rust",
+  "project_type": "rust",
+  "tasks": [
+    { "description": "Set up CI (build, clippy, tests) on every push", "priority": "high" },
+    { "description": "Release pipeline: version bump, tag, and prebuilt binaries", "priority": "normal" },
+    { "description": "Write README usage docs and --help text for every command", "priority": "normal" },
+    { "description": "Decide on an error type and exit codes", "priority": "normal" },
+    { "description": "Shell completions and man page", "priority": "low" }
+  ],
+  "notes": [
+    { "category": "constraint", "title": "MSRV", "content": "Pick a minimum supported Rust version and test it in CI." },
+    { "category": "goal", "title": "Scriptable output", "content": "Commands that print data should offer --json so they can be scripted." }
+  ],
+  "doc_sections": [
+    { "title": "Overview", "content": "What the tool does and who it is for." },
+    { "title": "Commands", "content": "Each subcommand, its flags, and examples." },
+    { "title": "Architecture", "content": "Argument parsing, command modules, and shared state." },
+    { "title": "Configuration", "content": "Config files, environment variables, and precedence." },
+    { "title": "Errors and Exit Codes", "content": "Error types and the exit code each maps to." },
+    { "title": "Release Process", "content": "Versioning, changelog, and how binaries are built and published." }
+  ],
+  "config": {
+    "auto_commit_on_task": true
+  }
+}"#;
+
+/// A project template
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectTemplate {
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Used when `--type` isn't given
+    #[serde(default)]
+    pub project_type: Option<String>,
+    #[serde(default)]
+    pub tasks: Vec<TemplateTask>,
+    #[serde(default)]
+    pub notes: Vec<TemplateNote>,
+    /// Sections for a skeleton docs database, in order
+    #[serde(default)]
+    pub doc_sections: Vec<TemplateSection>,
+    /// config.json fields to override, e.g. {"session_timeout_hours": 4}
+    #[serde(default)]
+    pub config: serde_json::Map<String, serde_json::Value>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateTask {
+    pub description: String,
+    #[serde(default = "default_priority")]
+    pub priority: String,
+    #[serde(default)]
+    pub notes: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateNote {
+    #[serde(default = "default_category")]
+    pub category: String,
+    pub title: String,
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TemplateSection {
+    pub title: String,
+    #[serde(default)]
+    pub content: String,
+}
+
+fn default_priority() -> String {
+    "normal".to_string()
+}
+
+fn default_category() -> String {
+    "note".to_string()
+}
+
+/// Load a template by name, checking ~/.proj/templates/ before the built-ins
+pub fn load(name: &str) -> Result<ProjectTemplate> {
+    let path = get_global_dir()?
+        .join("templates")
+        .join(format!("{}.json", name));
+    let (content, source) = if path.exists() {
+        let content = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        (content, path.display().to_string())
+    } else if let Some((_, content)) = BUILTIN_TEMPLATES.iter().find(|(n, _)| *n == name) {
+        (content.to_string(), format!("built-in template '{}'", name))
+    } else {
+        return Err(ProjError::InvalidInput(format!(
+            "Unknown template '{}'. Available: {}",
+            name,
+            available()?.join(", ")
+        ))
+        .into());
+    };
+
+    let template: ProjectTemplate = serde_json::from_str(&content)
+        .map_err(|e| ProjError::InvalidInput(format!("Invalid {}: {}", source, e)))?;
+    validate(&template)
+        .map_err(|e| ProjError::InvalidInput(format!("Invalid {}: {}", source, e)))?;
+    Ok(template)
+}
+
+/// Names of all templates, built-in and user
+pub fn available() -> Result<Vec<String>> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(n, _)| n.to_string())
+        .collect();
+    let dir = get_global_dir()?.join("templates");
+    if let Ok(entries) = std::fs::read_dir(&dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                if let Some(stem) = path.file_stem().and_then(|s| s.to_str()) {
+                    names.push(stem.to_string());
+                }
+            }
+        }
+    }
+    names.sort();
+    names.dedup();
+    Ok(names)
+}
+
+/// Check priorities and note categories against the values proj accepts
+fn validate(template: &ProjectTemplate) -> std::result::Result<(), String> {
+    let valid_priorities = ["low", "normal", "high", "urgent"];
+    let valid_categories = ["goal", "constraint", "assumption", "requirement", "note"];
+
+    for task in &template.tasks {
+        if !valid_priorities.contains(&task.priority.as_str()) {
+            return Err(format!(
+                "task '{}' has priority '{}' (valid: {})",
+                task.description,
+                task.priority,
+                valid_priorities.join(", ")
+            ));
+        }
+    }
+    for note in &template.notes {
+        if !valid_categories.contains(&note.category.as_str()) {
+            return Err(format!(
+                "note '{}' has category '{}' (valid: {})",
+                note.title,
+                note.category,
+                valid_categories.join(", ")
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Apply a template's config overrides on top of the defaults `proj init` chose
+pub fn apply_config(config: ProjectConfig, template: &ProjectTemplate) -> Result<ProjectConfig> {
+    if template.config.is_empty() {
+        return Ok(config);
+    }
+    let mut value = serde_json::to_value(config)?;
+    if let Some(fields) = value.as_object_mut() {
+        for (key, v) in &template.config {
+            fields.insert(key.clone(), v.clone());
+        }
+    }
+    serde_json::from_value(value).map_err(|e| {
+        ProjError::InvalidInput(format!("Template config doesn't fit config.json: {}", e)).into()
+    })
+}
+
+/// Insert a template's tasks and notes, returning how many of each were added
+pub fn populate(conn: &Connection, template: &ProjectTemplate) -> Result<(usize, usize)> {
+    let tx = write_transaction(conn)?;
+    for task in &template.tasks {
+        tx.execute(
+            "INSERT INTO tasks (description, status, priority, notes) VALUES (?1, 'pending', ?2, ?3)",
+            params![task.description, task.priority, task.notes],
+        )?;
+    }
+    for note in &template.notes {
+        tx.execute(
+            "INSERT INTO context_notes (category, title, content, status) VALUES (?1, ?2, ?3, 'active')",
+            params![note.category, note.title, note.content],
+        )?;
+    }
+    tx.commit()?;
+    Ok((template.tasks.len(), template.notes.len()))
+}
@@ -0,0 +1,46 @@
+// Text helpers - shorten user text for one-line display
+
+/// Shorten `s` to at most `max_len` characters, ending in "..." when cut. Counts
+/// characters rather than bytes, so accented, CJK, or emoji text is never split
+/// inside a character.
+pub fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let kept: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", kept)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_text_is_unchanged() {
+        assert_eq!(truncate("fix parser", 10), "fix parser");
+        assert_eq!(truncate("", 5), "");
+    }
+
+    #[test]
+    fn long_text_ends_in_ellipsis_within_the_limit() {
+        assert_eq!(truncate("refactor the parser", 10), "refacto...");
+        assert_eq!(truncate("refactor the parser", 10).chars().count(), 10);
+    }
+
+    #[test]
+    fn multibyte_text_is_cut_between_characters() {
+        // Each of these would panic if cut at a byte offset
+        assert_eq!(truncate("café au lait crème", 8), "café ...");
+        assert_eq!(truncate("数据库迁移失败需要回滚", 6), "数据库...");
+        assert_eq!(truncate("deploy 🚀🚀🚀🚀 done", 10), "deploy ...");
+        assert_eq!(truncate("🚀🚀🚀🚀🚀", 4), "🚀...");
+        assert_eq!(truncate("naïve", 5), "naïve");
+    }
+
+    #[test]
+    fn tiny_limits_leave_only_the_ellipsis() {
+        assert_eq!(truncate("écrire", 3), "...");
+        assert_eq!(truncate("écrire", 0), "...");
+    }
+}
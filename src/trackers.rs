@@ -0,0 +1,412 @@
+// Trackers - read-only Jira and Linear clients for `proj task import`
+//
+// Credentials live in ~/.proj/integrations.json. Remote statuses and priorities are
+// mapped onto proj's own, so imported tickets look like any other task.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+
+use crate::config::IntegrationsConfig;
+use crate::encoding::base64_encode;
+use crate::http;
+
+/// Issues requested per page
+const PAGE_SIZE: usize = 100;
+
+/// A ticket, already mapped onto proj's task fields
+pub struct RemoteIssue {
+    /// Tracker's key, e.g. ENG-12
+    pub key: String,
+    pub title: String,
+    pub url: String,
+    /// Status name in the tracker, e.g. "In Review"
+    pub state: String,
+    /// pending, in_progress, completed, or cancelled
+    pub status: String,
+    /// low, normal, high, or urgent
+    pub priority: String,
+    /// YYYY-MM-DD
+    pub due: Option<String>,
+}
+
+pub enum Tracker {
+    Jira {
+        base_url: String,
+        /// Value of the Authorization header
+        auth: String,
+        /// Jira Cloud (v3 API, email + API token) rather than Server/Data Center
+        cloud: bool,
+    },
+    Linear {
+        api_url: String,
+        api_key: String,
+    },
+}
+
+impl Tracker {
+    /// Client for "jira" or "linear" from ~/.proj/integrations.json
+    pub fn connect(from: &str) -> Result<Self> {
+        let config = IntegrationsConfig::load()?;
+        match from {
+            "jira" => {
+                let jira = config.jira.ok_or_else(|| {
+                    anyhow!("Jira isn't configured. Add a \"jira\" entry with base_url, email, and api_token to ~/.proj/integrations.json.")
+                })?;
+                let token = jira
+                    .api_token
+                    .or_else(|| std::env::var("JIRA_API_TOKEN").ok())
+                    .ok_or_else(|| {
+                        anyhow!("No Jira API token. Set api_token in ~/.proj/integrations.json or $JIRA_API_TOKEN.")
+                    })?;
+                let (auth, cloud) = match &jira.email {
+                    Some(email) => (
+                        format!(
+                            "Basic {}",
+                            base64_encode(format!("{}:{}", email, token).as_bytes())
+                        ),
+                        true,
+                    ),
+                    None => (format!("Bearer {}", token), false),
+                };
+                Ok(Tracker::Jira {
+                    base_url: jira.base_url.trim_end_matches('/').to_string(),
+                    auth,
+                    cloud,
+                })
+            }
+            "linear" => {
+                let linear = config.linear.unwrap_or_default();
+                let api_key = linear
+                    .api_key
+                    .or_else(|| std::env::var("LINEAR_API_KEY").ok())
+                    .ok_or_else(|| {
+                        anyhow!("No Linear API key. Set linear.api_key in ~/.proj/integrations.json or $LINEAR_API_KEY.")
+                    })?;
+                Ok(Tracker::Linear {
+                    api_url: linear
+                        .api_url
+                        .unwrap_or_else(|| "https://api.linear.app/graphql".to_string()),
+                    api_key,
+                })
+            }
+            other => bail!("Unknown tracker '{}'. Use 'jira' or 'linear'.", other),
+        }
+    }
+
+    /// Stored as tasks.source and task_links.provider
+    pub fn provider(&self) -> &'static str {
+        match self {
+            Tracker::Jira { .. } => "jira",
+            Tracker::Linear { .. } => "linear",
+        }
+    }
+
+    /// Every issue matching a JQL query (Jira) or belonging to a team (Linear)
+    pub fn search(&self, query: &str) -> Result<Vec<RemoteIssue>> {
+        match self {
+            Tracker::Jira {
+                base_url,
+                auth,
+                cloud,
+            } => {
+                let mut issues = Vec::new();
+                let mut page_token: Option<String> = None;
+                loop {
+                    // Cloud pages with a token; Server/Data Center with an offset
+                    let mut request = if *cloud {
+                        get(&format!("{}/rest/api/3/search/jql", base_url), auth)
+                    } else {
+                        get(&format!("{}/rest/api/2/search", base_url), auth)
+                            .query("startAt", &issues.len().to_string())
+                    };
+                    request = request
+                        .query("jql", query)
+                        .query("fields", JIRA_FIELDS)
+                        .query("maxResults", &PAGE_SIZE.to_string());
+                    if let Some(token) = &page_token {
+                        request = request.query("nextPageToken", token);
+                    }
+                    let response = send(request, None, "Jira")?;
+
+                    let page = response
+                        .get("issues")
+                        .and_then(|i| i.as_array())
+                        .ok_or_else(|| anyhow!("Jira response has no 'issues'"))?;
+                    for issue in page {
+                        issues.push(jira_issue(base_url, issue)?);
+                    }
+
+                    page_token = response
+                        .get("nextPageToken")
+                        .and_then(|t| t.as_str())
+                        .map(String::from);
+                    let total = response.get("total").and_then(|t| t.as_u64());
+                    let more = if *cloud {
+                        page_token.is_some()
+                    } else {
+                        total.is_some_and(|t| (issues.len() as u64) < t)
+                    };
+                    if page.is_empty() || !more {
+                        break;
+                    }
+                }
+                Ok(issues)
+            }
+            Tracker::Linear { api_url, api_key } => {
+                let mut issues = Vec::new();
+                let mut after: Option<String> = None;
+                loop {
+                    let data = linear_query(
+                        api_url,
+                        api_key,
+                        LINEAR_TEAM_QUERY,
+                        serde_json::json!({ "team": query, "first": PAGE_SIZE, "after": after }),
+                    )?;
+                    let connection = data
+                        .get("issues")
+                        .ok_or_else(|| anyhow!("Linear response has no 'issues'"))?;
+                    for node in connection
+                        .get("nodes")
+                        .and_then(|n| n.as_array())
+                        .into_iter()
+                        .flatten()
+                    {
+                        issues.push(linear_issue(node)?);
+                    }
+                    after = connection
+                        .pointer("/pageInfo/endCursor")
+                        .and_then(|c| c.as_str())
+                        .map(String::from);
+                    let more = connection
+                        .pointer("/pageInfo/hasNextPage")
+                        .and_then(|m| m.as_bool())
+                        .unwrap_or(false);
+                    if !more || after.is_none() {
+                        break;
+                    }
+                }
+                Ok(issues)
+            }
+        }
+    }
+
+    /// One issue by key; None if it no longer exists
+    pub fn fetch(&self, key: &str) -> Result<Option<RemoteIssue>> {
+        match self {
+            Tracker::Jira {
+                base_url,
+                auth,
+                cloud,
+            } => {
+                let version = if *cloud { 3 } else { 2 };
+                let request = get(
+                    &format!("{}/rest/api/{}/issue/{}", base_url, version, key),
+                    auth,
+                )
+                .query("fields", JIRA_FIELDS);
+                match request.call() {
+                    Err(ureq::Error::Status(404, _)) => Ok(None),
+                    result => {
+                        let response = parse_response(result, "Jira")?;
+                        Ok(Some(jira_issue(base_url, &response)?))
+                    }
+                }
+            }
+            Tracker::Linear { api_url, api_key } => {
+                let data = match linear_query(
+                    api_url,
+                    api_key,
+                    LINEAR_ISSUE_QUERY,
+                    serde_json::json!({ "id": key }),
+                ) {
+                    Ok(data) => data,
+                    Err(e) if e.to_string().to_lowercase().contains("not found") => {
+                        return Ok(None)
+                    }
+                    Err(e) => return Err(e),
+                };
+                match data.get("issue") {
+                    Some(node) if !node.is_null() => Ok(Some(linear_issue(node)?)),
+                    _ => Ok(None),
+                }
+            }
+        }
+    }
+}
+
+/// Display name, e.g. for "imported from Jira"
+pub fn label(provider: &str) -> &str {
+    match provider {
+        "jira" => "Jira",
+        "linear" => "Linear",
+        other => other,
+    }
+}
+
+const JIRA_FIELDS: &str = "summary,status,priority,duedate";
+
+const LINEAR_TEAM_QUERY: &str = "query Issues($team: String!, $first: Int!, $after: String) {
+  issues(first: $first, after: $after, filter: { team: { key: { eq: $team } } }) {
+    nodes { identifier title url priority dueDate state { name type } }
+    pageInfo { hasNextPage endCursor }
+  }
+}";
+
+const LINEAR_ISSUE_QUERY: &str =
+    "query Issue($id: String!) { issue(id: $id) { identifier title url priority dueDate state { name type } } }";
+
+fn agent(url: &str) -> ureq::Agent {
+    http::agent(url, Duration::from_secs(30))
+}
+
+fn get(url: &str, auth: &str) -> ureq::Request {
+    agent(url)
+        .get(url)
+        .set("Accept", "application/json")
+        .set("Authorization", auth)
+        .set("User-Agent", &format!("proj/{}", crate::VERSION))
+}
+
+fn send(
+    request: ureq::Request,
+    body: Option<serde_json::Value>,
+    tracker: &str,
+) -> Result<serde_json::Value> {
+    let result = match body {
+        Some(body) => request.send_json(body),
+        None => request.call(),
+    };
+    parse_response(result, tracker)
+}
+
+fn parse_response(
+    result: Result<ureq::Response, ureq::Error>,
+    tracker: &str,
+) -> Result<serde_json::Value> {
+    match result {
+        Ok(response) => response
+            .into_json()
+            .with_context(|| format!("Invalid JSON from {}", tracker)),
+        Err(ureq::Error::Status(code, response)) => {
+            let detail = response.into_string().unwrap_or_default();
+            bail!(
+                "{} request failed: HTTP {} {}",
+                tracker,
+                code,
+                error_detail(&detail)
+            )
+        }
+        Err(e) => Err(anyhow!("{} request failed: {}", tracker, e)),
+    }
+}
+
+/// The useful part of an error body: Jira's errorMessages, Linear's errors, or the raw text
+fn error_detail(body: &str) -> String {
+    let parsed: serde_json::Value = serde_json::from_str(body).unwrap_or_default();
+    parsed
+        .pointer("/errorMessages/0")
+        .or_else(|| parsed.pointer("/errors/0/message"))
+        .and_then(|m| m.as_str())
+        .map(String::from)
+        .unwrap_or_else(|| body.lines().next().unwrap_or("").trim().to_string())
+}
+
+fn jira_issue(base_url: &str, issue: &serde_json::Value) -> Result<RemoteIssue> {
+    let key = issue
+        .get("key")
+        .and_then(|k| k.as_str())
+        .ok_or_else(|| anyhow!("Jira issue has no key"))?;
+    let text = |pointer: &str| {
+        issue
+            .pointer(pointer)
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+
+    let status = match text("/fields/status/statusCategory/key").as_deref() {
+        Some("done") => "completed",
+        Some("indeterminate") => "in_progress",
+        _ => "pending",
+    };
+    let priority = match text("/fields/priority/name")
+        .unwrap_or_default()
+        .to_lowercase()
+        .as_str()
+    {
+        "highest" | "blocker" | "critical" => "urgent",
+        "high" | "major" => "high",
+        "low" | "lowest" | "minor" | "trivial" => "low",
+        _ => "normal",
+    };
+
+    Ok(RemoteIssue {
+        key: key.to_string(),
+        title: text("/fields/summary").unwrap_or_else(|| key.to_string()),
+        url: format!("{}/browse/{}", base_url, key),
+        state: text("/fields/status/name").unwrap_or_default(),
+        status: status.to_string(),
+        priority: priority.to_string(),
+        due: text("/fields/duedate"),
+    })
+}
+
+fn linear_query(
+    api_url: &str,
+    api_key: &str,
+    query: &str,
+    variables: serde_json::Value,
+) -> Result<serde_json::Value> {
+    let request = agent(api_url)
+        .post(api_url)
+        .set("Authorization", api_key)
+        .set("User-Agent", &format!("proj/{}", crate::VERSION));
+    let response = send(
+        request,
+        Some(serde_json::json!({ "query": query, "variables": variables })),
+        "Linear",
+    )?;
+    if let Some(message) = response
+        .pointer("/errors/0/message")
+        .and_then(|m| m.as_str())
+    {
+        bail!("Linear request failed: {}", message);
+    }
+    response
+        .get("data")
+        .cloned()
+        .ok_or_else(|| anyhow!("Linear response has no 'data'"))
+}
+
+fn linear_issue(node: &serde_json::Value) -> Result<RemoteIssue> {
+    let text = |pointer: &str| {
+        node.pointer(pointer)
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    };
+    let key = text("/identifier").ok_or_else(|| anyhow!("Linear issue has no identifier"))?;
+
+    let status = match text("/state/type").as_deref() {
+        Some("started") => "in_progress",
+        Some("completed") => "completed",
+        Some("canceled") => "cancelled",
+        _ => "pending",
+    };
+    // Linear priorities: 0 none, 1 urgent, 2 high, 3 medium, 4 low
+    let priority = match node.get("priority").and_then(|p| p.as_i64()) {
+        Some(1) => "urgent",
+        Some(2) => "high",
+        Some(4) => "low",
+        _ => "normal",
+    };
+
+    Ok(RemoteIssue {
+        title: text("/title").unwrap_or_else(|| key.clone()),
+        url: text("/url").unwrap_or_default(),
+        state: text("/state/name").unwrap_or_default(),
+        status: status.to_string(),
+        priority: priority.to_string(),
+        due: text("/dueDate"),
+        key,
+    })
+}
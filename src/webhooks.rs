@@ -0,0 +1,71 @@
+// Webhooks - POST tracking events to the URLs in .tracking/config.json
+//
+// Payload: {"event", "project", "text", "timestamp", "data"}. `text` is a one-line
+// summary, so Slack incoming webhooks can take the payload as-is. With a secret,
+// X-Proj-Signature carries "sha256=" + the hex HMAC-SHA256 of the body.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use colored::Colorize;
+
+use crate::config::{ProjectConfig, WebhookConfig};
+use crate::encoding::hex;
+use crate::http;
+use crate::remote::hmac_sha256;
+
+pub const SESSION_END: &str = "session_end";
+pub const TASK_COMPLETED: &str = "task_completed";
+pub const BLOCKER_ADDED: &str = "blocker_added";
+
+/// Send an event to every webhook subscribed to it. Failures are reported but never
+/// fail the command that triggered the event. Nothing is sent in offline mode.
+pub fn notify(event: &str, text: &str, data: serde_json::Value) {
+    let Ok(config) = ProjectConfig::load() else {
+        return;
+    };
+    if crate::config::offline() {
+        return;
+    }
+    let targets: Vec<&WebhookConfig> = config
+        .webhooks
+        .iter()
+        .filter(|w| w.events.is_empty() || w.events.iter().any(|e| e == event))
+        .collect();
+    if targets.is_empty() {
+        return;
+    }
+
+    let payload = serde_json::json!({
+        "event": event,
+        "project": config.name,
+        "text": text,
+        "timestamp": chrono::Utc::now().to_rfc3339(),
+        "data": data,
+    });
+    let body = payload.to_string();
+
+    for webhook in targets {
+        if let Err(e) = send(webhook, event, &body) {
+            eprintln!("  {} Webhook failed: {}", "⚠".yellow(), e);
+        }
+    }
+}
+
+fn send(webhook: &WebhookConfig, event: &str, body: &str) -> Result<()> {
+    let mut request = http::agent(&webhook.url, Duration::from_secs(10))
+        .post(&webhook.url)
+        .set("Content-Type", "application/json")
+        .set("User-Agent", &format!("proj/{}", crate::VERSION))
+        .set("X-Proj-Event", event);
+    if let Some(secret) = &webhook.secret {
+        let signature = hex(&hmac_sha256(secret.as_bytes(), body.as_bytes()));
+        request = request.set("X-Proj-Signature", &format!("sha256={}", signature));
+    }
+
+    match request.send_string(body) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::Status(code, _)) => Err(anyhow!("{}: HTTP {}", webhook.url, code)),
+        Err(e) => Err(anyhow!("{}", e)),
+    }
+}
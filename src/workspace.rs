@@ -0,0 +1,118 @@
+// Workspaces - a project at a repository root that groups the projects below it
+//
+// Each member keeps its own .tracking/ and is found by the usual nearest-root lookup.
+// The workspace's config.json lists members by path relative to the workspace root.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde_json::Value;
+
+/// Directories never searched for member projects
+const SKIP_DIRS: &[&str] = &["target", "node_modules", "vendor", "dist", "build"];
+
+/// How deep below the workspace root to look for members
+const MAX_DEPTH: usize = 4;
+
+/// Whether a project's config.json marks it as a workspace
+pub fn is_workspace(project_root: &Path) -> bool {
+    read_config(project_root)
+        .ok()
+        .and_then(|c| c.get("workspace").and_then(|v| v.as_bool()))
+        .unwrap_or(false)
+}
+
+/// The nearest workspace containing a project: the project itself if it is one,
+/// otherwise the closest parent directory that is
+pub fn find_workspace(project_root: &Path) -> Option<PathBuf> {
+    project_root
+        .ancestors()
+        .find(|dir| dir.join(".tracking").is_dir() && is_workspace(dir))
+        .map(Path::to_path_buf)
+}
+
+/// Projects below a workspace root, as paths relative to it. Stops descending at each
+/// project found, so a nested workspace's members stay its own.
+pub fn discover_members(root: &Path) -> Vec<String> {
+    let mut members = Vec::new();
+    walk(root, root, 0, &mut members);
+    members.sort();
+    members
+}
+
+fn walk(root: &Path, dir: &Path, depth: usize, members: &mut Vec<String>) {
+    if depth >= MAX_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let name = entry.file_name().to_string_lossy().to_string();
+        if !path.is_dir() || name.starts_with('.') || SKIP_DIRS.contains(&name.as_str()) {
+            continue;
+        }
+        if path.join(".tracking").is_dir() {
+            if let Ok(rel) = path.strip_prefix(root) {
+                members.push(rel.to_string_lossy().replace('\\', "/"));
+            }
+        } else {
+            walk(root, &path, depth + 1, members);
+        }
+    }
+}
+
+/// Add a new project to the nearest workspace above it. Returns the workspace's
+/// name if the project was added.
+pub fn join_parent(project_root: &Path) -> Result<Option<String>> {
+    let Some(workspace) = project_root.parent().and_then(find_workspace) else {
+        return Ok(None);
+    };
+    let Ok(rel) = project_root.strip_prefix(&workspace) else {
+        return Ok(None);
+    };
+    let rel = rel.to_string_lossy().replace('\\', "/");
+
+    let mut config = read_config(&workspace)?;
+    let name = config
+        .get("name")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let mut members = members_of(&config);
+    if !members.contains(&rel) {
+        members.push(rel);
+        members.sort();
+        config["workspace_members"] = members.into();
+        write_config(&workspace, &config)?;
+    }
+    Ok(Some(name))
+}
+
+/// Member paths listed in a workspace's config.json
+pub fn members_of(config: &Value) -> Vec<String> {
+    config
+        .get("workspace_members")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|m| m.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A project's config.json as plain JSON, so keys it leaves to the global defaults stay unset
+pub fn read_config(project_root: &Path) -> Result<Value> {
+    let path = project_root.join(".tracking").join("config.json");
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+pub fn write_config(project_root: &Path, config: &Value) -> Result<()> {
+    let path = project_root.join(".tracking").join("config.json");
+    std::fs::write(&path, serde_json::to_string_pretty(config)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
@@ -2,7 +2,7 @@
  * CLI wrapper for executing proj commands
  */
 
-import { execFileSync, execFile } from 'child_process';
+import { execFileSync, execFile, spawn } from 'child_process';
 import { promisify } from 'util';
 import * as vscode from 'vscode';
 
@@ -190,6 +190,68 @@ export async function runProj(args: string[]): Promise<ProjResult> {
     }
 }
 
+/**
+ * Event emitted by `proj watch --json`
+ */
+export interface ProjEvent {
+    event: string;
+    id?: number;
+    changes?: string[];
+    data?: any;
+    at: string;
+}
+
+/**
+ * Start `proj watch --json` and call onEvent for each change.
+ * onExit fires when the watcher stops (e.g. an older CLI without `watch`).
+ * Returns a disposable that stops the watcher.
+ */
+export function watchProj(
+    onEvent: (event: ProjEvent) => void,
+    onExit: (code: number | null) => void
+): vscode.Disposable {
+    const workspacePath = getWorkspacePath();
+    if (!workspacePath) {
+        onExit(null);
+        return new vscode.Disposable(() => {});
+    }
+
+    const child = spawn(getProjPath(), ['watch', '--json'], {
+        cwd: workspacePath,
+        env: {
+            ...process.env,
+            NO_COLOR: '1',
+            PATH: getExtendedPath()
+        }
+    });
+
+    let buffer = '';
+    child.stdout.setEncoding('utf8');
+    child.stdout.on('data', (chunk: string) => {
+        buffer += chunk;
+        let newline: number;
+        while ((newline = buffer.indexOf('\n')) >= 0) {
+            const line = buffer.substring(0, newline).trim();
+            buffer = buffer.substring(newline + 1);
+            if (!line) {
+                continue;
+            }
+            try {
+                onEvent(JSON.parse(line));
+            } catch {
+                console.log(`[proj] Ignoring watch output: ${line}`);
+            }
+        }
+    });
+    child.on('error', () => onExit(null));
+    child.on('exit', code => onExit(code));
+
+    return new vscode.Disposable(() => {
+        child.removeAllListeners('exit');
+        child.kill();
+    });
+}
+
 /**
  * Check if proj is installed and accessible
  */
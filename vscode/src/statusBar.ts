@@ -7,6 +7,8 @@ import * as cli from './cli';
 
 let statusBarItem: vscode.StatusBarItem | undefined;
 let refreshInterval: NodeJS.Timeout | undefined;
+let watcher: vscode.Disposable | undefined;
+let pendingRefresh: NodeJS.Timeout | undefined;
 
 /**
  * Create and show the status bar item
@@ -30,11 +32,24 @@ export function createStatusBar(context: vscode.ExtensionContext): void {
     // Initial update
     updateStatusBar();
 
-    // Refresh every 60 seconds
-    refreshInterval = setInterval(updateStatusBar, 60000);
+    // Update live from `proj watch`, falling back to refreshing every 60 seconds
+    watcher = cli.watchProj(
+        event => {
+            if (event.event !== 'ready') {
+                scheduleRefresh();
+            }
+        },
+        () => {
+            watcher = undefined;
+            if (!refreshInterval) {
+                refreshInterval = setInterval(updateStatusBar, 60000);
+            }
+        }
+    );
 
     context.subscriptions.push({
         dispose: () => {
+            watcher?.dispose();
             if (refreshInterval) {
                 clearInterval(refreshInterval);
             }
@@ -42,6 +57,19 @@ export function createStatusBar(context: vscode.ExtensionContext): void {
     });
 }
 
+/**
+ * Coalesce bursts of watch events (one command often touches several rows)
+ */
+function scheduleRefresh(): void {
+    if (pendingRefresh) {
+        clearTimeout(pendingRefresh);
+    }
+    pendingRefresh = setTimeout(() => {
+        pendingRefresh = undefined;
+        updateStatusBar();
+    }, 250);
+}
+
 /**
  * Update the status bar with current project state
  */
@@ -139,6 +167,10 @@ export function refresh(): void {
  * Hide and dispose the status bar
  */
 export function dispose(): void {
+    watcher?.dispose();
+    if (pendingRefresh) {
+        clearTimeout(pendingRefresh);
+    }
     if (refreshInterval) {
         clearInterval(refreshInterval);
     }